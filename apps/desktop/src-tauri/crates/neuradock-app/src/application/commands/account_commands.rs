@@ -1,5 +1,6 @@
 use crate::application::commands::command_handler::Command;
-use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, HashMap};
 
 /// Create account command
 #[derive(Debug, Clone)]
@@ -8,6 +9,8 @@ pub struct CreateAccountCommand {
     pub provider_id: String,
     pub cookies: HashMap<String, String>,
     pub api_user: String,
+    /// Headers added to this account's requests, over the provider's own
+    pub extra_headers: Option<BTreeMap<String, String>>,
     pub auto_checkin_enabled: Option<bool>,
     pub auto_checkin_hour: Option<u8>,
     pub auto_checkin_minute: Option<u8>,
@@ -29,10 +32,14 @@ pub struct UpdateAccountCommand {
     pub provider_id: Option<String>,
     pub cookies: Option<HashMap<String, String>>,
     pub api_user: Option<String>,
+    /// Headers added to this account's requests, over the provider's own
+    pub extra_headers: Option<BTreeMap<String, String>>,
     pub auto_checkin_enabled: Option<bool>,
     pub auto_checkin_hour: Option<u8>,
     pub auto_checkin_minute: Option<u8>,
     pub check_in_interval_hours: Option<u8>,
+    /// Daily cap on check-in attempts, 0 removes the cap
+    pub max_attempts_per_day: Option<u8>,
 }
 
 impl Command for UpdateAccountCommand {}
@@ -57,6 +64,34 @@ pub struct DeleteAccountResult {
     pub success: bool,
 }
 
+/// Undo delete account command, restoring an account still in its grace period
+#[derive(Debug, Clone)]
+pub struct UndoDeleteAccountCommand {
+    pub account_id: String,
+}
+
+impl Command for UndoDeleteAccountCommand {}
+
+/// Undo delete account command result
+#[derive(Debug, Clone)]
+pub struct UndoDeleteAccountResult {
+    pub success: bool,
+}
+
+/// Purge deleted accounts command, removing accounts deleted before `deleted_before`
+#[derive(Debug, Clone)]
+pub struct PurgeDeletedAccountsCommand {
+    pub deleted_before: DateTime<Utc>,
+}
+
+impl Command for PurgeDeletedAccountsCommand {}
+
+/// Purge deleted accounts command result
+#[derive(Debug, Clone)]
+pub struct PurgeDeletedAccountsResult {
+    pub purged: usize,
+}
+
 /// Toggle account command
 #[derive(Debug, Clone)]
 pub struct ToggleAccountCommand {
@@ -71,3 +106,17 @@ impl Command for ToggleAccountCommand {}
 pub struct ToggleAccountResult {
     pub success: bool,
 }
+
+/// Toggle account pin command
+#[derive(Debug, Clone)]
+pub struct TogglePinCommand {
+    pub account_id: String,
+}
+
+impl Command for TogglePinCommand {}
+
+/// Toggle account pin command result
+#[derive(Debug, Clone)]
+pub struct TogglePinResult {
+    pub pinned: bool,
+}
@@ -17,6 +17,8 @@ pub struct CheckInCommandResult {
     pub provider_id: String,
     pub success: bool,
     pub message: String,
+    /// Reward reported by the provider, in balance units
+    pub reward: Option<f64>,
     pub balance: Option<BalanceDto>,
 }
 
@@ -28,6 +30,14 @@ pub struct BatchExecuteCheckInCommand {
 
 impl Command for BatchExecuteCheckInCommand {}
 
+/// Execute check-in for every enabled account of one provider
+#[derive(Debug, Clone)]
+pub struct ExecuteProviderCheckInCommand {
+    pub provider_id: String,
+}
+
+impl Command for ExecuteProviderCheckInCommand {}
+
 /// Batch execute check-in command result
 #[derive(Debug, Clone)]
 pub struct BatchCheckInCommandResult {
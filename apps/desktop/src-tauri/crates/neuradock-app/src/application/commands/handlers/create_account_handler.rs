@@ -9,7 +9,7 @@ use neuradock_domain::account::{Account, AccountRepository, Credentials};
 use neuradock_domain::events::account_events::AccountCreated;
 use neuradock_domain::events::EventBus;
 use neuradock_domain::session::SessionTokenExtractor;
-use neuradock_domain::shared::{DomainError, ProviderId};
+use neuradock_domain::shared::{DomainError, ExtraHeaders, ProviderId};
 
 /// Create account command handler
 pub struct CreateAccountCommandHandler {
@@ -43,6 +43,10 @@ impl CommandHandler<CreateAccountCommand> for CreateAccountCommandHandler {
             credentials,
         )?;
 
+        if let Some(extra_headers) = cmd.extra_headers {
+            account.update_extra_headers(ExtraHeaders::new(extra_headers)?);
+        }
+
         // 3. Set session expiration using token extractor
         let token = SessionTokenExtractor::extract(&cmd.cookies);
         let expires_at = Utc::now() + Duration::days(Account::DEFAULT_SESSION_EXPIRATION_DAYS);
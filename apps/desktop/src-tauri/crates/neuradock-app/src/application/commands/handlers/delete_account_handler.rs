@@ -10,7 +10,14 @@ use neuradock_domain::events::account_events::AccountDeleted;
 use neuradock_domain::events::EventBus;
 use neuradock_domain::shared::{AccountId, DomainError};
 
+/// Days a deleted account can be restored before it is purged
+pub const DELETE_GRACE_PERIOD_DAYS: i64 = 7;
+
 /// Delete account command handler
+///
+/// The account is only marked deleted; its history stays until
+/// [`PurgeDeletedAccountsCommandHandler`](super::PurgeDeletedAccountsCommandHandler)
+/// removes it after [`DELETE_GRACE_PERIOD_DAYS`].
 pub struct DeleteAccountCommandHandler {
     account_repo: Arc<dyn AccountRepository>,
     event_bus: Arc<dyn EventBus>,
@@ -47,16 +54,23 @@ impl CommandHandler<DeleteAccountCommand> for DeleteAccountCommandHandler {
         let name = account.name().to_string();
         info!("Deleting account: {}", name);
 
-        // 2. Delete account
-        self.account_repo.delete(&account_id).await?;
+        // 2. Mark the account deleted, it is purged after the grace period
+        let deleted_at = Utc::now();
+        self.account_repo
+            .soft_delete(&account_id, deleted_at)
+            .await?;
 
-        info!("Account deleted successfully: {}", name);
+        info!(
+            "Account deleted successfully: {} (can be undone for {} days)",
+            name, DELETE_GRACE_PERIOD_DAYS
+        );
 
         // 3. Publish domain event
         let event = AccountDeleted {
             account_id,
             name,
-            occurred_at: Utc::now(),
+            permanent: false,
+            occurred_at: deleted_at,
         };
 
         self.event_bus.publish(Box::new(event)).await?;
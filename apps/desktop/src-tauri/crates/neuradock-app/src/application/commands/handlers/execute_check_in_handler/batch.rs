@@ -6,13 +6,14 @@ use crate::application::commands::check_in_commands::*;
 use crate::application::commands::command_handler::CommandHandler;
 use crate::application::dtos::BalanceDto;
 use crate::application::services::{
-    BalanceHistoryService, CheckInExecutor, NotificationService, ProviderModelsService,
+    BalanceHistoryService, CheckInExecutor, NotificationService, ProviderModelsService, RunningJobs,
 };
 use crate::application::ResultExt;
-use neuradock_domain::account::AccountRepository;
+use neuradock_domain::account::{Account, AccountRepository};
 use neuradock_domain::check_in::ProviderRepository;
+use neuradock_domain::check_in_timing::CheckInTimingRepository;
 use neuradock_domain::proxy_config::ProxyConfigRepository;
-use neuradock_domain::shared::{AccountId, DomainError};
+use neuradock_domain::shared::{AccountId, DomainError, ProviderId};
 use neuradock_domain::waf_cookies::WafCookiesRepository;
 
 use super::shared;
@@ -26,6 +27,7 @@ pub struct BatchExecuteCheckInCommandHandler {
     provider_models_service: Arc<ProviderModelsService>,
     balance_history_service: Arc<BalanceHistoryService>,
     waf_cookies_repo: Arc<dyn WafCookiesRepository>,
+    timing_repo: Option<Arc<dyn CheckInTimingRepository>>,
     headless_browser: bool,
 }
 
@@ -47,6 +49,7 @@ impl BatchExecuteCheckInCommandHandler {
             provider_models_service,
             balance_history_service,
             waf_cookies_repo,
+            timing_repo: None,
             headless_browser,
         }
     }
@@ -55,6 +58,12 @@ impl BatchExecuteCheckInCommandHandler {
         self.notification_service = Some(service);
         self
     }
+
+    /// Record the phase timings of each check-in
+    pub fn with_timing_repo(mut self, repo: Arc<dyn CheckInTimingRepository>) -> Self {
+        self.timing_repo = Some(repo);
+        self
+    }
 }
 
 #[async_trait]
@@ -67,6 +76,9 @@ impl CommandHandler<BatchExecuteCheckInCommand> for BatchExecuteCheckInCommandHa
             cmd.account_ids.len()
         );
 
+        // Keeps maintenance out for the whole batch, including between check-ins
+        let _running_batch = RunningJobs::global().start_batch();
+
         let total = cmd.account_ids.len();
         let mut succeeded = 0;
         let mut failed = 0;
@@ -76,13 +88,35 @@ impl CommandHandler<BatchExecuteCheckInCommand> for BatchExecuteCheckInCommandHa
         let proxy_config = self.proxy_config_repo.get().await?;
         let proxy_url = proxy_config.proxy_url();
 
-        let executor = CheckInExecutor::with_proxy(
+        let mut executor = CheckInExecutor::with_proxy(
             self.account_repo.clone(),
             self.headless_browser,
             proxy_url,
         )
         .to_infra_err()?
         .with_waf_cookies_repo(self.waf_cookies_repo.clone());
+        if let Some(repo) = &self.timing_repo {
+            executor = executor.with_timing_repo(repo.clone());
+        }
+
+        // Solve per-account WAF challenges in one browser; load errors are reported below
+        let mut waf_targets = Vec::new();
+        for account_id in &cmd.account_ids {
+            let Ok(Some(account)) = self
+                .account_repo
+                .find_by_id(&AccountId::from_string(account_id))
+                .await
+            else {
+                continue;
+            };
+            if !account.is_enabled() {
+                continue;
+            }
+            if let Ok(Some(provider)) = self.provider_repo.find_by_id(account.provider_id()).await {
+                waf_targets.push((account, provider));
+            }
+        }
+        executor.prefetch_waf_cookies(&waf_targets).await;
 
         for account_id in cmd.account_ids {
             // Load account to get provider_id
@@ -101,6 +135,7 @@ impl CommandHandler<BatchExecuteCheckInCommand> for BatchExecuteCheckInCommandHa
                         provider_id: String::new(),
                         success: false,
                         message: format!("Account not found: {}", account_id),
+                        reward: None,
                         balance: None,
                     });
                     continue;
@@ -114,6 +149,7 @@ impl CommandHandler<BatchExecuteCheckInCommand> for BatchExecuteCheckInCommandHa
                         provider_id: String::new(),
                         success: false,
                         message: format!("Failed to load account: {}", e),
+                        reward: None,
                         balance: None,
                     });
                     continue;
@@ -134,6 +170,7 @@ impl CommandHandler<BatchExecuteCheckInCommand> for BatchExecuteCheckInCommandHa
                         provider_id: provider_id.clone(),
                         success: false,
                         message: format!("Provider not found: {}", provider_id),
+                        reward: None,
                         balance: None,
                     });
                     continue;
@@ -147,6 +184,7 @@ impl CommandHandler<BatchExecuteCheckInCommand> for BatchExecuteCheckInCommandHa
                         provider_id: provider_id.clone(),
                         success: false,
                         message: format!("Failed to load provider {}: {}", provider_id, e),
+                        reward: None,
                         balance: None,
                     });
                     continue;
@@ -208,6 +246,7 @@ impl CommandHandler<BatchExecuteCheckInCommand> for BatchExecuteCheckInCommandHa
                         provider.name(),
                         &result.message,
                         balance_tuple,
+                        result.reward,
                     )
                     .await;
 
@@ -222,6 +261,7 @@ impl CommandHandler<BatchExecuteCheckInCommand> for BatchExecuteCheckInCommandHa
                         provider_id: provider_id.clone(),
                         success: result.success,
                         message: result.message,
+                        reward: result.reward,
                         balance: balance_dto,
                     });
                 }
@@ -234,6 +274,7 @@ impl CommandHandler<BatchExecuteCheckInCommand> for BatchExecuteCheckInCommandHa
                         provider_id: provider_id.clone(),
                         success: false,
                         message: format!("Check-in failed: {}", e),
+                        reward: None,
                         balance: None,
                     });
                 }
@@ -253,3 +294,78 @@ impl CommandHandler<BatchExecuteCheckInCommand> for BatchExecuteCheckInCommandHa
         })
     }
 }
+
+#[async_trait]
+impl CommandHandler<ExecuteProviderCheckInCommand> for BatchExecuteCheckInCommandHandler {
+    type Result = BatchCheckInCommandResult;
+
+    /// Runs the batch flow over the provider's enabled accounts, sharing its
+    /// WAF browser and tab limit
+    async fn handle(
+        &self,
+        cmd: ExecuteProviderCheckInCommand,
+    ) -> Result<Self::Result, DomainError> {
+        let provider_id = ProviderId::from_string(&cmd.provider_id);
+        if self.provider_repo.find_by_id(&provider_id).await?.is_none() {
+            return Err(DomainError::ProviderNotFound(cmd.provider_id));
+        }
+
+        let accounts = self.account_repo.find_enabled().await?;
+        let account_ids = provider_account_ids(&accounts, &provider_id);
+        info!(
+            "Checking in {} enabled accounts of provider {}",
+            account_ids.len(),
+            provider_id
+        );
+
+        CommandHandler::<BatchExecuteCheckInCommand>::handle(
+            self,
+            BatchExecuteCheckInCommand { account_ids },
+        )
+        .await
+    }
+}
+
+/// Ids of the accounts that belong to `provider_id`
+fn provider_account_ids(accounts: &[Account], provider_id: &ProviderId) -> Vec<String> {
+    accounts
+        .iter()
+        .filter(|account| account.provider_id() == provider_id)
+        .map(|account| account.id().as_str().to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use neuradock_domain::account::Credentials;
+    use std::collections::HashMap;
+
+    fn account(name: &str, provider: &str) -> Account {
+        Account::new(
+            name.to_string(),
+            ProviderId::from_string(provider),
+            Credentials::new(HashMap::new(), format!("{}-user", name)),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_provider_account_ids_only_include_the_provider() {
+        let accounts = vec![
+            account("a", "anyrouter"),
+            account("b", "agentrouter"),
+            account("c", "anyrouter"),
+        ];
+
+        let ids = provider_account_ids(&accounts, &ProviderId::from_string("anyrouter"));
+
+        assert_eq!(
+            ids,
+            vec![
+                accounts[0].id().as_str().to_string(),
+                accounts[2].id().as_str().to_string()
+            ]
+        );
+    }
+}
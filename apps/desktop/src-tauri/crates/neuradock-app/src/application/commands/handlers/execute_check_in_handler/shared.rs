@@ -77,6 +77,7 @@ pub async fn auto_fetch_provider_models(
         {
             provider_models_service
                 .fetch_and_save_provider_models(
+                    account_id,
                     provider,
                     updated_acc.credentials().cookies(),
                     updated_acc.credentials().api_user(),
@@ -89,6 +90,7 @@ pub async fn auto_fetch_provider_models(
 }
 
 /// Send check-in notification (success or failure)
+#[allow(clippy::too_many_arguments)]
 pub async fn send_check_in_notification(
     notification_service: &Option<Arc<NotificationService>>,
     success: bool,
@@ -97,12 +99,13 @@ pub async fn send_check_in_notification(
     provider_name: &str,
     message: &str,
     balance: Option<(f64, f64, f64)>, // (current_balance, total_consumed, total_quota)
+    reward: Option<f64>,
 ) {
     if let Some(notification_service) = notification_service {
         if success {
             // Send success notification
             if let Err(e) = notification_service
-                .send_check_in_success(account_id, account_name, provider_name, balance)
+                .send_check_in_success(account_id, account_name, provider_name, balance, reward)
                 .await
             {
                 error!("Failed to send check-in success notification: {}", e);
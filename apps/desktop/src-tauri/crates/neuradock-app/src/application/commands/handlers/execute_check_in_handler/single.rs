@@ -10,6 +10,7 @@ use crate::application::services::{
 use crate::application::ResultExt;
 use neuradock_domain::account::AccountRepository;
 use neuradock_domain::check_in::ProviderRepository;
+use neuradock_domain::check_in_timing::CheckInTimingRepository;
 use neuradock_domain::proxy_config::ProxyConfigRepository;
 use neuradock_domain::shared::{AccountId, DomainError};
 use neuradock_domain::waf_cookies::WafCookiesRepository;
@@ -25,6 +26,7 @@ pub struct ExecuteCheckInCommandHandler {
     provider_models_service: Arc<ProviderModelsService>,
     balance_history_service: Arc<BalanceHistoryService>,
     waf_cookies_repo: Arc<dyn WafCookiesRepository>,
+    timing_repo: Option<Arc<dyn CheckInTimingRepository>>,
     headless_browser: bool,
 }
 
@@ -46,6 +48,7 @@ impl ExecuteCheckInCommandHandler {
             provider_models_service,
             balance_history_service,
             waf_cookies_repo,
+            timing_repo: None,
             headless_browser,
         }
     }
@@ -54,6 +57,12 @@ impl ExecuteCheckInCommandHandler {
         self.notification_service = Some(service);
         self
     }
+
+    /// Record the phase timings of each check-in
+    pub fn with_timing_repo(mut self, repo: Arc<dyn CheckInTimingRepository>) -> Self {
+        self.timing_repo = Some(repo);
+        self
+    }
 }
 
 #[async_trait]
@@ -92,13 +101,16 @@ impl CommandHandler<ExecuteCheckInCommand> for ExecuteCheckInCommandHandler {
         let proxy_url = proxy_config.proxy_url();
 
         // Create executor with proxy support
-        let executor = CheckInExecutor::with_proxy(
+        let mut executor = CheckInExecutor::with_proxy(
             self.account_repo.clone(),
             self.headless_browser,
             proxy_url,
         )
         .to_infra_err()?
         .with_waf_cookies_repo(self.waf_cookies_repo.clone());
+        if let Some(repo) = &self.timing_repo {
+            executor = executor.with_timing_repo(repo.clone());
+        }
 
         // Execute check-in
         let result = executor
@@ -160,6 +172,7 @@ impl CommandHandler<ExecuteCheckInCommand> for ExecuteCheckInCommandHandler {
             provider.name(),
             &result.message,
             balance_tuple,
+            result.reward,
         )
         .await;
 
@@ -169,6 +182,7 @@ impl CommandHandler<ExecuteCheckInCommand> for ExecuteCheckInCommandHandler {
             provider_id,
             success: result.success,
             message: result.message,
+            reward: result.reward,
             balance: balance_dto,
         })
     }
@@ -3,7 +3,10 @@ mod delete_account_handler;
 mod execute_check_in_handler;
 mod notification_handlers;
 mod provider_handlers;
+mod purge_deleted_accounts_handler;
 mod toggle_account_handler;
+mod toggle_pin_handler;
+mod undo_delete_account_handler;
 mod update_account_handler;
 
 #[cfg(test)]
@@ -21,5 +24,8 @@ pub use notification_handlers::{
 pub use provider_handlers::{
     CreateProviderCommandHandler, DeleteProviderCommandHandler, UpdateProviderCommandHandler,
 };
+pub use purge_deleted_accounts_handler::PurgeDeletedAccountsCommandHandler;
 pub use toggle_account_handler::ToggleAccountCommandHandler;
+pub use toggle_pin_handler::TogglePinCommandHandler;
+pub use undo_delete_account_handler::UndoDeleteAccountCommandHandler;
 pub use update_account_handler::UpdateAccountCommandHandler;
@@ -5,10 +5,11 @@ use std::sync::Arc;
 
 use crate::application::commands::command_handler::CommandHandler;
 use crate::application::commands::notification_commands::*;
-use crate::application::dtos::NotificationChannelDto;
+use crate::application::dtos::{parse_channel_locale, NotificationChannelDto};
+use crate::application::services::i18n::{default_locale, t_in};
 use neuradock_domain::notification::{
-    ChannelConfig, ChannelType, NotificationChannel, NotificationChannelId,
-    NotificationChannelRepository,
+    ChannelConfig, ChannelType, Locale, NotificationChannel, NotificationChannelId,
+    NotificationChannelRepository, NotificationMessage,
 };
 use neuradock_domain::shared::DomainError;
 use neuradock_infrastructure::notification::create_sender;
@@ -49,7 +50,16 @@ impl CommandHandler<CreateNotificationChannelCommand> for CreateNotificationChan
         }
 
         // Create domain aggregate
-        let channel = NotificationChannel::new(config)?;
+        let mut channel = NotificationChannel::new(config)?;
+        if let Some(min_severity) = cmd.input.min_severity {
+            channel.set_min_severity(min_severity);
+        }
+        if let Some(templates) = cmd.input.templates {
+            channel.set_templates(templates.parse()?);
+        }
+        if let Some(locale) = cmd.input.locale {
+            channel.set_locale(parse_channel_locale(&locale)?);
+        }
 
         // Persist
         self.channel_repo.save(&channel).await?;
@@ -64,6 +74,9 @@ impl CommandHandler<CreateNotificationChannelCommand> for CreateNotificationChan
                 DomainError::Serialization(format!("Failed to serialize config: {}", e))
             })?,
             enabled: channel.is_enabled(),
+            min_severity: channel.min_severity(),
+            templates: channel.templates().into(),
+            locale: channel.locale(),
             created_at: channel.created_at().to_rfc3339(),
         })
     }
@@ -117,6 +130,18 @@ impl CommandHandler<UpdateNotificationChannelCommand> for UpdateNotificationChan
             }
         }
 
+        if let Some(min_severity) = cmd.input.min_severity {
+            channel.set_min_severity(min_severity);
+        }
+
+        if let Some(templates) = cmd.input.templates {
+            channel.set_templates(templates.parse()?);
+        }
+
+        if let Some(locale) = cmd.input.locale {
+            channel.set_locale(parse_channel_locale(&locale)?);
+        }
+
         // Persist
         self.channel_repo.update(&channel).await?;
 
@@ -130,6 +155,9 @@ impl CommandHandler<UpdateNotificationChannelCommand> for UpdateNotificationChan
                 DomainError::Serialization(format!("Failed to serialize config: {}", e))
             })?,
             enabled: channel.is_enabled(),
+            min_severity: channel.min_severity(),
+            templates: channel.templates().into(),
+            locale: channel.locale(),
             created_at: channel.created_at().to_rfc3339(),
         })
     }
@@ -201,22 +229,31 @@ impl CommandHandler<TestNotificationChannelCommand> for TestNotificationChannelH
         // Create sender
         let sender = create_sender(channel.config())?;
 
-        // Test
-        match sender.test().await {
+        // Test in the locale the channel's notifications are sent in
+        let locale = channel.locale().unwrap_or_else(default_locale);
+        match sender.send(&test_message(locale)).await {
             Ok(_) => {
                 info!("Notification channel test successful: {}", cmd.channel_id);
                 Ok(TestNotificationChannelResult {
                     success: true,
-                    message: "测试通知发送成功".to_string(),
+                    message: t_in(locale, "notification.test.sent"),
                 })
             }
             Err(e) => {
                 info!("Notification channel test failed: {}", e);
                 Ok(TestNotificationChannelResult {
                     success: false,
-                    message: format!("测试失败: {}", e),
+                    message: format!("{}: {}", t_in(locale, "notification.test.failed"), e),
                 })
             }
         }
     }
 }
+
+/// Message sent when a channel is tested, clearly labeled as a test
+fn test_message(locale: Locale) -> NotificationMessage {
+    NotificationMessage::new(
+        t_in(locale, "notification.test.title"),
+        t_in(locale, "notification.test.body"),
+    )
+}
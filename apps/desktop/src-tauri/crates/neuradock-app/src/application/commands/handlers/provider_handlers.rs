@@ -1,20 +1,47 @@
 use async_trait::async_trait;
+use chrono::Utc;
 use log::info;
 use std::sync::Arc;
 
 use crate::application::commands::command_handler::CommandHandler;
 use crate::application::commands::provider_commands::*;
-use neuradock_domain::check_in::{Provider, ProviderConfig, ProviderRepository};
-use neuradock_domain::shared::DomainError;
+use neuradock_domain::check_in::{
+    Provider, ProviderConfig, ProviderDialect, ProviderRepository, WafChallengeKind,
+};
+use neuradock_domain::events::provider_events::{
+    ProviderCreated, ProviderDeleted, ProviderUpdated,
+};
+use neuradock_domain::events::EventBus;
+use neuradock_domain::shared::{DomainError, ExtraHeaders};
+
+/// Parse the sign-in body of a provider command, blank meaning no body
+fn parse_sign_in_body(body: Option<&str>) -> Result<Option<serde_json::Value>, DomainError> {
+    match body.map(str::trim) {
+        None | Some("") => Ok(None),
+        Some(body) => serde_json::from_str(body)
+            .map(Some)
+            .map_err(|e| DomainError::Validation(format!("Invalid sign-in body JSON: {}", e))),
+    }
+}
+
+/// Optional API path of a provider command, blank meaning the endpoint is not available
+fn optional_path(path: String) -> Option<String> {
+    let path = path.trim();
+    (!path.is_empty()).then(|| path.to_string())
+}
 
 /// Create provider command handler
 pub struct CreateProviderCommandHandler {
     provider_repo: Arc<dyn ProviderRepository>,
+    event_bus: Arc<dyn EventBus>,
 }
 
 impl CreateProviderCommandHandler {
-    pub fn new(provider_repo: Arc<dyn ProviderRepository>) -> Self {
-        Self { provider_repo }
+    pub fn new(provider_repo: Arc<dyn ProviderRepository>, event_bus: Arc<dyn EventBus>) -> Self {
+        Self {
+            provider_repo,
+            event_bus,
+        }
     }
 }
 
@@ -37,6 +64,33 @@ impl CommandHandler<CreateProviderCommand> for CreateProviderCommandHandler {
 
         let supports_check_in = cmd.supports_check_in.unwrap_or(true);
         let check_in_bugged = cmd.check_in_bugged.unwrap_or(false);
+        let quota_reset_schedule = cmd
+            .quota_reset_schedule
+            .as_ref()
+            .map(|schedule| schedule.to_domain())
+            .transpose()?;
+        let balance_display = cmd
+            .balance_display
+            .as_ref()
+            .map(|display| display.to_domain())
+            .transpose()?;
+        let reward_extraction = match cmd.reward_extraction.as_ref() {
+            Some(extraction) => extraction.to_domain()?,
+            None => None,
+        };
+        let extra_headers = ExtraHeaders::new(cmd.extra_headers.unwrap_or_default())?;
+        let dialect = cmd
+            .dialect
+            .as_deref()
+            .map(ProviderDialect::parse)
+            .transpose()?
+            .unwrap_or_default();
+        let waf_challenge_kind = cmd
+            .bypass_method
+            .as_deref()
+            .map(WafChallengeKind::parse)
+            .transpose()?
+            .unwrap_or_default();
 
         // Use provided values or new-api defaults
         let provider = Provider::new(ProviderConfig {
@@ -44,21 +98,29 @@ impl CommandHandler<CreateProviderCommand> for CreateProviderCommandHandler {
             domain: cmd.domain.clone(),
             login_path: cmd.login_path.unwrap_or_else(|| "/login".to_string()),
             sign_in_path: cmd.sign_in_path.or(Some("/api/user/sign_in".to_string())),
+            sign_in_body: parse_sign_in_body(cmd.sign_in_body.as_deref())?,
             user_info_path: cmd
                 .user_info_path
                 .unwrap_or_else(|| "/api/user/self".to_string()),
             token_api_path: cmd.token_api_path.or(Some("/api/token/".to_string())),
             models_path: cmd.models_path.or(Some("/api/user/models".to_string())),
+            transaction_log_path: cmd.transaction_log_path.and_then(optional_path),
             api_user_key: cmd
                 .api_user_key
                 .unwrap_or_else(|| "new-api-user".to_string()),
+            extra_headers,
+            dialect,
             bypass_method: if cmd.needs_waf_bypass {
-                Some("waf_cookies".to_string())
+                Some(waf_challenge_kind.as_bypass_method().to_string())
             } else {
                 None
             },
+            requires_turnstile: cmd.requires_turnstile.unwrap_or(false),
             supports_check_in,
             check_in_bugged,
+            quota_reset_schedule,
+            balance_display,
+            reward_extraction,
         });
 
         let provider_id = provider.id().as_str().to_string();
@@ -67,6 +129,14 @@ impl CommandHandler<CreateProviderCommand> for CreateProviderCommandHandler {
         // Save provider
         self.provider_repo.save(&provider).await?;
 
+        // Publish domain event
+        let event = ProviderCreated {
+            provider_id: provider.id().clone(),
+            name: provider.name().to_string(),
+            occurred_at: Utc::now(),
+        };
+        self.event_bus.publish(Box::new(event)).await?;
+
         info!(
             "✅ Provider created successfully: {} ({})",
             cmd.name, provider_id
@@ -79,11 +149,15 @@ impl CommandHandler<CreateProviderCommand> for CreateProviderCommandHandler {
 /// Update provider command handler
 pub struct UpdateProviderCommandHandler {
     provider_repo: Arc<dyn ProviderRepository>,
+    event_bus: Arc<dyn EventBus>,
 }
 
 impl UpdateProviderCommandHandler {
-    pub fn new(provider_repo: Arc<dyn ProviderRepository>) -> Self {
-        Self { provider_repo }
+    pub fn new(provider_repo: Arc<dyn ProviderRepository>, event_bus: Arc<dyn EventBus>) -> Self {
+        Self {
+            provider_repo,
+            event_bus,
+        }
     }
 }
 
@@ -135,12 +209,21 @@ impl CommandHandler<UpdateProviderCommand> for UpdateProviderCommandHandler {
             .models_url()
             .as_ref()
             .map(|url| url.trim_start_matches(existing.domain()).to_string());
+        let current_transaction_log_path = existing.transaction_log_path().map(str::to_string);
         let current_name = existing.name().to_string();
         let current_domain = existing.domain().to_string();
         let current_api_user_key = existing.api_user_key().to_string();
+        let current_extra_headers = existing.extra_headers().clone();
+        let current_dialect = existing.dialect();
+        let current_sign_in_body = existing.sign_in_body().cloned();
         let current_needs_waf = existing.needs_waf_bypass();
+        let current_waf_challenge_kind = existing.waf_challenge_kind();
+        let current_requires_turnstile = existing.requires_turnstile();
         let current_supports_check_in = existing.supports_check_in();
         let current_check_in_bugged = existing.check_in_bugged();
+        let current_quota_reset_schedule = existing.quota_reset_schedule().cloned();
+        let current_balance_display = existing.balance_display().clone();
+        let current_reward_extraction = existing.reward_extraction().cloned();
         let current_is_builtin = existing.is_builtin();
         let current_created_at = existing.created_at();
 
@@ -153,17 +236,50 @@ impl CommandHandler<UpdateProviderCommand> for UpdateProviderCommandHandler {
                 domain: cmd.domain.unwrap_or(current_domain),
                 login_path: cmd.login_path.unwrap_or(current_login_path),
                 sign_in_path: cmd.sign_in_path.or(current_sign_in_path),
+                sign_in_body: match cmd.sign_in_body.as_deref() {
+                    Some(body) => parse_sign_in_body(Some(body))?,
+                    None => current_sign_in_body,
+                },
                 user_info_path: cmd.user_info_path.unwrap_or(current_user_info_path),
                 token_api_path: cmd.token_api_path.or(current_token_api_path),
                 models_path: cmd.models_path.or(current_models_path),
+                transaction_log_path: match cmd.transaction_log_path {
+                    Some(path) => optional_path(path),
+                    None => current_transaction_log_path,
+                },
                 api_user_key: cmd.api_user_key.unwrap_or(current_api_user_key),
+                extra_headers: match cmd.extra_headers {
+                    Some(headers) => ExtraHeaders::new(headers)?,
+                    None => current_extra_headers,
+                },
+                dialect: match cmd.dialect.as_deref() {
+                    Some(dialect) => ProviderDialect::parse(dialect)?,
+                    None => current_dialect,
+                },
                 bypass_method: if cmd.needs_waf_bypass.unwrap_or(current_needs_waf) {
-                    Some("waf_cookies".to_string())
+                    let kind = match cmd.bypass_method.as_deref() {
+                        Some(method) => WafChallengeKind::parse(method)?,
+                        None => current_waf_challenge_kind.unwrap_or_default(),
+                    };
+                    Some(kind.as_bypass_method().to_string())
                 } else {
                     None
                 },
+                requires_turnstile: cmd.requires_turnstile.unwrap_or(current_requires_turnstile),
                 supports_check_in: cmd.supports_check_in.unwrap_or(current_supports_check_in),
                 check_in_bugged: cmd.check_in_bugged.unwrap_or(current_check_in_bugged),
+                quota_reset_schedule: match cmd.quota_reset_schedule.as_ref() {
+                    Some(schedule) => Some(schedule.to_domain()?),
+                    None => current_quota_reset_schedule,
+                },
+                balance_display: Some(match cmd.balance_display.as_ref() {
+                    Some(display) => display.to_domain()?,
+                    None => current_balance_display,
+                }),
+                reward_extraction: match cmd.reward_extraction.as_ref() {
+                    Some(extraction) => extraction.to_domain()?,
+                    None => current_reward_extraction,
+                },
             },
             current_is_builtin,
             current_created_at,
@@ -172,6 +288,14 @@ impl CommandHandler<UpdateProviderCommand> for UpdateProviderCommandHandler {
         // Save updated provider
         self.provider_repo.save(&updated_provider).await?;
 
+        // Publish domain event
+        let event = ProviderUpdated {
+            provider_id,
+            name: updated_provider.name().to_string(),
+            occurred_at: Utc::now(),
+        };
+        self.event_bus.publish(Box::new(event)).await?;
+
         info!("Provider updated successfully: {}", cmd.provider_id);
 
         Ok(UpdateProviderResult { success: true })
@@ -181,11 +305,15 @@ impl CommandHandler<UpdateProviderCommand> for UpdateProviderCommandHandler {
 /// Delete provider command handler
 pub struct DeleteProviderCommandHandler {
     provider_repo: Arc<dyn ProviderRepository>,
+    event_bus: Arc<dyn EventBus>,
 }
 
 impl DeleteProviderCommandHandler {
-    pub fn new(provider_repo: Arc<dyn ProviderRepository>) -> Self {
-        Self { provider_repo }
+    pub fn new(provider_repo: Arc<dyn ProviderRepository>, event_bus: Arc<dyn EventBus>) -> Self {
+        Self {
+            provider_repo,
+            event_bus,
+        }
     }
 }
 
@@ -204,6 +332,13 @@ impl CommandHandler<DeleteProviderCommand> for DeleteProviderCommandHandler {
         // Delete will fail if provider is builtin (checked in repository)
         self.provider_repo.delete(&provider_id).await?;
 
+        // Publish domain event
+        let event = ProviderDeleted {
+            provider_id,
+            occurred_at: Utc::now(),
+        };
+        self.event_bus.publish(Box::new(event)).await?;
+
         info!("Provider deleted successfully: {}", cmd.provider_id);
 
         Ok(DeleteProviderResult { success: true })
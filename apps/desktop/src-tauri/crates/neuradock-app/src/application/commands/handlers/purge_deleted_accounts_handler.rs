@@ -0,0 +1,79 @@
+use async_trait::async_trait;
+use chrono::{Duration as ChronoDuration, Utc};
+use log::{error, info};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+use super::delete_account_handler::DELETE_GRACE_PERIOD_DAYS;
+use crate::application::commands::account_commands::*;
+use crate::application::commands::command_handler::CommandHandler;
+use neuradock_domain::account::AccountRepository;
+use neuradock_domain::events::account_events::AccountDeleted;
+use neuradock_domain::events::EventBus;
+use neuradock_domain::shared::DomainError;
+
+/// How often the cleanup worker looks for accounts past their grace period
+const CLEANUP_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Purge deleted accounts command handler
+pub struct PurgeDeletedAccountsCommandHandler {
+    account_repo: Arc<dyn AccountRepository>,
+    event_bus: Arc<dyn EventBus>,
+}
+
+impl PurgeDeletedAccountsCommandHandler {
+    pub fn new(account_repo: Arc<dyn AccountRepository>, event_bus: Arc<dyn EventBus>) -> Self {
+        Self {
+            account_repo,
+            event_bus,
+        }
+    }
+
+    /// Spawn the worker that purges accounts deleted more than
+    /// [`DELETE_GRACE_PERIOD_DAYS`] ago
+    pub fn spawn_cleanup_worker(self: Arc<Self>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(CLEANUP_INTERVAL);
+            loop {
+                interval.tick().await;
+
+                let command = PurgeDeletedAccountsCommand {
+                    deleted_before: Utc::now() - ChronoDuration::days(DELETE_GRACE_PERIOD_DAYS),
+                };
+                if let Err(e) = self.handle(command).await {
+                    error!("Failed to purge deleted accounts: {}", e);
+                }
+            }
+        })
+    }
+}
+
+#[async_trait]
+impl CommandHandler<PurgeDeletedAccountsCommand> for PurgeDeletedAccountsCommandHandler {
+    type Result = PurgeDeletedAccountsResult;
+
+    async fn handle(&self, cmd: PurgeDeletedAccountsCommand) -> Result<Self::Result, DomainError> {
+        // 1. Remove the accounts together with their history
+        let purged = self.account_repo.purge_deleted(cmd.deleted_before).await?;
+        if purged.is_empty() {
+            return Ok(PurgeDeletedAccountsResult { purged: 0 });
+        }
+
+        info!("Purged {} deleted account(s)", purged.len());
+
+        // 2. Publish domain events
+        let count = purged.len();
+        for (account_id, name) in purged {
+            let event = AccountDeleted {
+                account_id,
+                name,
+                permanent: true,
+                occurred_at: Utc::now(),
+            };
+            self.event_bus.publish(Box::new(event)).await?;
+        }
+
+        Ok(PurgeDeletedAccountsResult { purged: count })
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 
 use crate::application::commands::account_commands::*;
@@ -12,12 +12,14 @@ use neuradock_domain::shared::{AccountId, DomainError, ProviderId};
 
 struct MockAccountRepository {
     accounts: tokio::sync::RwLock<HashMap<String, Account>>,
+    deleted: tokio::sync::RwLock<HashMap<String, (Account, chrono::DateTime<chrono::Utc>)>>,
 }
 
 impl MockAccountRepository {
     fn new() -> Self {
         Self {
             accounts: tokio::sync::RwLock::new(HashMap::new()),
+            deleted: tokio::sync::RwLock::new(HashMap::new()),
         }
     }
 }
@@ -30,6 +32,14 @@ impl AccountRepository for MockAccountRepository {
         Ok(())
     }
 
+    async fn save_in_transaction(
+        &self,
+        _tx: &mut neuradock_domain::shared::DynTransactionContext,
+        account: &Account,
+    ) -> Result<(), DomainError> {
+        self.save(account).await
+    }
+
     async fn find_by_id(&self, id: &AccountId) -> Result<Option<Account>, DomainError> {
         let accounts = self.accounts.read().await;
         Ok(accounts.get(id.as_str()).cloned())
@@ -62,6 +72,59 @@ impl AccountRepository for MockAccountRepository {
         accounts.remove(id.as_str());
         Ok(())
     }
+
+    async fn soft_delete(
+        &self,
+        id: &AccountId,
+        deleted_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<(), DomainError> {
+        let mut accounts = self.accounts.write().await;
+        if let Some(account) = accounts.remove(id.as_str()) {
+            let mut deleted = self.deleted.write().await;
+            deleted.insert(id.as_str().to_string(), (account, deleted_at));
+        }
+        Ok(())
+    }
+
+    async fn restore_deleted(&self, id: &AccountId) -> Result<bool, DomainError> {
+        let mut deleted = self.deleted.write().await;
+        let Some((account, _)) = deleted.remove(id.as_str()) else {
+            return Ok(false);
+        };
+        let mut accounts = self.accounts.write().await;
+        accounts.insert(id.as_str().to_string(), account);
+        Ok(true)
+    }
+
+    async fn purge_deleted(
+        &self,
+        deleted_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<(AccountId, String)>, DomainError> {
+        let mut deleted = self.deleted.write().await;
+        let expired: Vec<String> = deleted
+            .iter()
+            .filter(|(_, (_, deleted_at))| *deleted_at <= deleted_before)
+            .map(|(id, _)| id.clone())
+            .collect();
+        Ok(expired
+            .into_iter()
+            .filter_map(|id| deleted.remove(&id))
+            .map(|(account, _)| (account.id().clone(), account.name().to_string()))
+            .collect())
+    }
+
+    async fn record_check_in_attempt(
+        &self,
+        id: &AccountId,
+        day: chrono::NaiveDate,
+    ) -> Result<u32, DomainError> {
+        let mut accounts = self.accounts.write().await;
+        let account = accounts
+            .get_mut(id.as_str())
+            .ok_or_else(|| DomainError::AccountNotFound(id.to_string()))?;
+        account.record_check_in_attempt(day);
+        Ok(account.check_in_attempts_on(day))
+    }
 }
 
 struct MockEventBus {
@@ -105,6 +168,10 @@ async fn test_create_account_command_handler() {
         provider_id: ProviderId::new().as_str().to_string(),
         cookies,
         api_user: "test@user".to_string(),
+        extra_headers: Some(BTreeMap::from([(
+            "Authorization".to_string(),
+            "Bearer token".to_string(),
+        )])),
         auto_checkin_enabled: Some(true),
         auto_checkin_hour: Some(8),
         auto_checkin_minute: Some(30),
@@ -123,6 +190,10 @@ async fn test_create_account_command_handler() {
     let account = saved_account.unwrap();
     assert_eq!(account.name(), "Test Account");
     assert!(account.auto_checkin_enabled());
+    assert_eq!(
+        account.extra_headers().get("authorization"),
+        Some("Bearer token")
+    );
 
     // Verify event was published
     let event_count = event_bus.get_event_count().await;
@@ -140,6 +211,7 @@ async fn test_create_account_with_empty_name_fails() {
         provider_id: ProviderId::new().as_str().to_string(),
         cookies: HashMap::new(),
         api_user: "test@user".to_string(),
+        extra_headers: None,
         auto_checkin_enabled: Some(false),
         auto_checkin_hour: Some(0),
         auto_checkin_minute: Some(0),
@@ -175,10 +247,12 @@ async fn test_update_account_command_handler() {
         provider_id: None,
         cookies: None,
         api_user: None,
+        extra_headers: None,
         auto_checkin_enabled: Some(true),
         auto_checkin_hour: Some(10),
         auto_checkin_minute: Some(30),
         check_in_interval_hours: Some(24),
+        max_attempts_per_day: Some(3),
     };
 
     let result = handler.handle(command).await;
@@ -190,6 +264,7 @@ async fn test_update_account_command_handler() {
     assert!(updated.auto_checkin_enabled());
     assert_eq!(updated.auto_checkin_hour(), 10);
     assert_eq!(updated.auto_checkin_minute(), 30);
+    assert_eq!(updated.max_attempts_per_day(), 3);
 
     // Verify event
     let event_count = event_bus.get_event_count().await;
@@ -232,6 +307,62 @@ async fn test_delete_account_command_handler() {
     assert_eq!(event_count, 1);
 }
 
+#[tokio::test]
+async fn test_deleted_account_can_be_restored_until_purged() {
+    let repo = Arc::new(MockAccountRepository::new());
+    let event_bus = Arc::new(MockEventBus::new());
+
+    let mut cookies = HashMap::new();
+    cookies.insert("session".to_string(), "test_session".to_string());
+    let account = Account::new(
+        "Test Account".to_string(),
+        ProviderId::new(),
+        Credentials::new(cookies, "test@user".to_string()),
+    )
+    .unwrap();
+    let account_id = account.id().clone();
+    repo.save(&account).await.unwrap();
+
+    let delete = DeleteAccountCommandHandler::new(repo.clone(), event_bus.clone());
+    let undo = UndoDeleteAccountCommandHandler::new(repo.clone(), event_bus.clone());
+    let purge = PurgeDeletedAccountsCommandHandler::new(repo.clone(), event_bus.clone());
+    let delete_command = DeleteAccountCommand {
+        account_id: account_id.as_str().to_string(),
+    };
+    let undo_command = UndoDeleteAccountCommand {
+        account_id: account_id.as_str().to_string(),
+    };
+
+    // Undo within the grace period brings the account back
+    delete.handle(delete_command.clone()).await.unwrap();
+    let purged = purge
+        .handle(PurgeDeletedAccountsCommand {
+            deleted_before: chrono::Utc::now() - chrono::Duration::days(1),
+        })
+        .await
+        .unwrap();
+    assert_eq!(purged.purged, 0);
+    undo.handle(undo_command.clone()).await.unwrap();
+    assert!(repo.find_by_id(&account_id).await.unwrap().is_some());
+
+    // Once purged it is gone for good
+    delete.handle(delete_command).await.unwrap();
+    let purged = purge
+        .handle(PurgeDeletedAccountsCommand {
+            deleted_before: chrono::Utc::now(),
+        })
+        .await
+        .unwrap();
+    assert_eq!(purged.purged, 1);
+    assert!(matches!(
+        undo.handle(undo_command).await,
+        Err(DomainError::AccountNotFound(_))
+    ));
+
+    // Two deletes, one restore and one purge
+    assert_eq!(event_bus.get_event_count().await, 4);
+}
+
 #[tokio::test]
 async fn test_toggle_account_command_handler() {
     let repo = Arc::new(MockAccountRepository::new());
@@ -269,6 +400,60 @@ async fn test_toggle_account_command_handler() {
     assert_eq!(event_count, 1);
 }
 
+#[tokio::test]
+async fn test_toggle_pin_command_handler() {
+    let repo = Arc::new(MockAccountRepository::new());
+
+    let mut cookies = HashMap::new();
+    cookies.insert("session".to_string(), "test_session".to_string());
+
+    let account = Account::new(
+        "Test Account".to_string(),
+        ProviderId::new(),
+        Credentials::new(cookies, "test@user".to_string()),
+    )
+    .unwrap();
+    let account_id = account.id().clone();
+    repo.save(&account).await.unwrap();
+
+    let handler = TogglePinCommandHandler::new(repo.clone());
+    let command = TogglePinCommand {
+        account_id: account_id.as_str().to_string(),
+    };
+
+    // Pin, then unpin
+    let result = handler.handle(command.clone()).await.unwrap();
+    assert!(result.pinned);
+    assert!(repo
+        .find_by_id(&account_id)
+        .await
+        .unwrap()
+        .unwrap()
+        .is_pinned());
+
+    let result = handler.handle(command).await.unwrap();
+    assert!(!result.pinned);
+    assert!(!repo
+        .find_by_id(&account_id)
+        .await
+        .unwrap()
+        .unwrap()
+        .is_pinned());
+}
+
+#[tokio::test]
+async fn test_toggle_pin_missing_account() {
+    let repo = Arc::new(MockAccountRepository::new());
+    let handler = TogglePinCommandHandler::new(repo);
+
+    let result = handler
+        .handle(TogglePinCommand {
+            account_id: "missing".to_string(),
+        })
+        .await;
+    assert!(matches!(result, Err(DomainError::AccountNotFound(_))));
+}
+
 #[tokio::test]
 async fn test_update_nonexistent_account_fails() {
     let repo = Arc::new(MockAccountRepository::new());
@@ -281,10 +466,12 @@ async fn test_update_nonexistent_account_fails() {
         provider_id: None,
         cookies: None,
         api_user: None,
+        extra_headers: None,
         auto_checkin_enabled: None,
         auto_checkin_hour: None,
         auto_checkin_minute: None,
         check_in_interval_hours: None,
+        max_attempts_per_day: None,
     };
 
     let result = handler.handle(command).await;
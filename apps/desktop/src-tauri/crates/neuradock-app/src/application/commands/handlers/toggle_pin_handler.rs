@@ -0,0 +1,45 @@
+use async_trait::async_trait;
+use log::info;
+use std::sync::Arc;
+
+use crate::application::commands::account_commands::*;
+use crate::application::commands::command_handler::CommandHandler;
+use neuradock_domain::account::AccountRepository;
+use neuradock_domain::shared::{AccountId, DomainError};
+
+/// Toggle account pin command handler
+pub struct TogglePinCommandHandler {
+    account_repo: Arc<dyn AccountRepository>,
+}
+
+impl TogglePinCommandHandler {
+    pub fn new(account_repo: Arc<dyn AccountRepository>) -> Self {
+        Self { account_repo }
+    }
+}
+
+#[async_trait]
+impl CommandHandler<TogglePinCommand> for TogglePinCommandHandler {
+    type Result = TogglePinResult;
+
+    async fn handle(&self, cmd: TogglePinCommand) -> Result<Self::Result, DomainError> {
+        let account_id = AccountId::from_string(&cmd.account_id);
+
+        let mut account = self
+            .account_repo
+            .find_by_id(&account_id)
+            .await?
+            .ok_or_else(|| DomainError::AccountNotFound(cmd.account_id.clone()))?;
+
+        let pinned = account.toggle_pin();
+        self.account_repo.save(&account).await?;
+
+        info!(
+            "Account {} {}",
+            account.name(),
+            if pinned { "pinned" } else { "unpinned" }
+        );
+
+        Ok(TogglePinResult { pinned })
+    }
+}
@@ -0,0 +1,64 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use log::info;
+use std::sync::Arc;
+
+use crate::application::commands::account_commands::*;
+use crate::application::commands::command_handler::CommandHandler;
+use neuradock_domain::account::AccountRepository;
+use neuradock_domain::events::account_events::AccountRestored;
+use neuradock_domain::events::EventBus;
+use neuradock_domain::shared::{AccountId, DomainError};
+
+/// Undo delete account command handler
+pub struct UndoDeleteAccountCommandHandler {
+    account_repo: Arc<dyn AccountRepository>,
+    event_bus: Arc<dyn EventBus>,
+}
+
+impl UndoDeleteAccountCommandHandler {
+    pub fn new(account_repo: Arc<dyn AccountRepository>, event_bus: Arc<dyn EventBus>) -> Self {
+        Self {
+            account_repo,
+            event_bus,
+        }
+    }
+}
+
+#[async_trait]
+impl CommandHandler<UndoDeleteAccountCommand> for UndoDeleteAccountCommandHandler {
+    type Result = UndoDeleteAccountResult;
+
+    async fn handle(&self, cmd: UndoDeleteAccountCommand) -> Result<Self::Result, DomainError> {
+        info!(
+            "Handling UndoDeleteAccountCommand for account: {}",
+            cmd.account_id
+        );
+
+        let account_id = AccountId::from_string(&cmd.account_id);
+
+        // 1. Restore the account, gone for good once purged
+        if !self.account_repo.restore_deleted(&account_id).await? {
+            return Err(DomainError::AccountNotFound(cmd.account_id));
+        }
+
+        let account = self
+            .account_repo
+            .find_by_id(&account_id)
+            .await?
+            .ok_or_else(|| DomainError::AccountNotFound(cmd.account_id.clone()))?;
+
+        info!("Account restored: {}", account.name());
+
+        // 2. Publish domain event
+        let event = AccountRestored {
+            account_id,
+            name: account.name().to_string(),
+            occurred_at: Utc::now(),
+        };
+
+        self.event_bus.publish(Box::new(event)).await?;
+
+        Ok(UndoDeleteAccountResult { success: true })
+    }
+}
@@ -9,7 +9,7 @@ use neuradock_domain::account::{Account, AccountRepository, Credentials};
 use neuradock_domain::events::account_events::AccountUpdated;
 use neuradock_domain::events::EventBus;
 use neuradock_domain::session::SessionTokenExtractor;
-use neuradock_domain::shared::{AccountId, DomainError, ProviderId};
+use neuradock_domain::shared::{AccountId, DomainError, ExtraHeaders, ProviderId};
 
 /// Update account command handler
 pub struct UpdateAccountCommandHandler {
@@ -84,6 +84,11 @@ impl CommandHandler<UpdateAccountCommand> for UpdateAccountCommandHandler {
             );
         }
 
+        if let Some(extra_headers) = cmd.extra_headers {
+            account.update_extra_headers(ExtraHeaders::new(extra_headers)?);
+            credentials_updated = true;
+        }
+
         // 5. Update auto check-in configuration if provided
         if let Some(enabled) = cmd.auto_checkin_enabled {
             let hour = cmd.auto_checkin_hour.unwrap_or(account.auto_checkin_hour());
@@ -99,7 +104,12 @@ impl CommandHandler<UpdateAccountCommand> for UpdateAccountCommandHandler {
             account.set_check_in_interval_hours(interval_hours)?;
         }
 
-        // 7. Save updated account
+        // 7. Update daily attempt cap if provided
+        if let Some(max_attempts) = cmd.max_attempts_per_day {
+            account.set_max_attempts_per_day(max_attempts);
+        }
+
+        // 8. Save updated account
         self.account_repo.save(&account).await?;
 
         info!("Account updated successfully: {}", account.name());
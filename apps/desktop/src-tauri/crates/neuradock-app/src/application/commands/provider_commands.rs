@@ -1,6 +1,8 @@
 use crate::application::commands::command_handler::Command;
+use crate::application::dtos::{BalanceDisplayDto, QuotaResetScheduleDto, RewardExtractionDto};
 use serde::{Deserialize, Serialize};
 use specta::Type;
+use std::collections::BTreeMap;
 
 /// Create provider command
 #[derive(Debug, Clone, Deserialize, Type)]
@@ -8,15 +10,30 @@ pub struct CreateProviderCommand {
     pub name: String,
     pub domain: String,
     pub needs_waf_bypass: bool,
+    /// WAF protection to bypass: `waf_cookies`/`aliyun` (default) or `cloudflare`
+    pub bypass_method: Option<String>,
+    /// Check-in needs a Cloudflare Turnstile token solved in a browser
+    pub requires_turnstile: Option<bool>,
     pub supports_check_in: Option<bool>,
     pub check_in_bugged: Option<bool>,
     // Optional API paths (with defaults)
     pub login_path: Option<String>,
     pub sign_in_path: Option<String>,
+    /// JSON body to POST on check-in, e.g. `{}` (empty = no body)
+    pub sign_in_body: Option<String>,
     pub user_info_path: Option<String>,
     pub token_api_path: Option<String>,
     pub models_path: Option<String>,
+    /// Usage/transaction log endpoint, e.g. `/api/log/self` (empty = none)
+    pub transaction_log_path: Option<String>,
     pub api_user_key: Option<String>,
+    /// Static headers added to user info and check-in requests, e.g. `Authorization`
+    pub extra_headers: Option<BTreeMap<String, String>>,
+    /// API fork the provider runs, `new_api` (default) or `one_api`
+    pub dialect: Option<String>,
+    pub quota_reset_schedule: Option<QuotaResetScheduleDto>,
+    pub balance_display: Option<BalanceDisplayDto>,
+    pub reward_extraction: Option<RewardExtractionDto>,
 }
 
 impl Command for CreateProviderCommand {}
@@ -34,15 +51,30 @@ pub struct UpdateProviderCommand {
     pub name: Option<String>,
     pub domain: Option<String>,
     pub needs_waf_bypass: Option<bool>,
+    /// WAF protection to bypass: `waf_cookies`/`aliyun` or `cloudflare`
+    pub bypass_method: Option<String>,
+    pub requires_turnstile: Option<bool>,
     pub supports_check_in: Option<bool>,
     pub check_in_bugged: Option<bool>,
     // Optional API paths
     pub login_path: Option<String>,
     pub sign_in_path: Option<String>,
+    /// JSON body to POST on check-in, e.g. `{}` (empty = no body)
+    pub sign_in_body: Option<String>,
     pub user_info_path: Option<String>,
     pub token_api_path: Option<String>,
     pub models_path: Option<String>,
+    /// Usage/transaction log endpoint, e.g. `/api/log/self` (empty = none)
+    pub transaction_log_path: Option<String>,
     pub api_user_key: Option<String>,
+    /// Static headers added to user info and check-in requests, e.g. `Authorization`
+    pub extra_headers: Option<BTreeMap<String, String>>,
+    /// API fork the provider runs, `new_api` (default) or `one_api`
+    pub dialect: Option<String>,
+    pub quota_reset_schedule: Option<QuotaResetScheduleDto>,
+    pub balance_display: Option<BalanceDisplayDto>,
+    /// Reward parsing, a value without path and pattern turns it off
+    pub reward_extraction: Option<RewardExtractionDto>,
 }
 
 impl Command for UpdateProviderCommand {}
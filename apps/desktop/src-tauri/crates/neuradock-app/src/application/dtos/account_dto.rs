@@ -1,11 +1,11 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use specta::Type;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use neuradock_domain::account::Account;
 
-use super::BalanceDto;
+use super::{BalanceDisplayDto, BalanceDto};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct AccountDto {
@@ -20,6 +20,8 @@ pub struct AccountDto {
     pub auto_checkin_hour: u8,
     pub auto_checkin_minute: u8,
     pub check_in_interval_hours: u8,
+    /// Daily cap on check-in attempts, 0 means no limit
+    pub max_attempts_per_day: u8,
     pub last_balance_check_at: Option<String>,
     pub current_balance: Option<f64>,
     pub total_consumed: Option<f64>,
@@ -30,6 +32,9 @@ pub struct AccountDto {
     pub session_expires_at: Option<String>,
     pub session_expires_soon: bool, // true if session expires within 7 days
     pub session_days_remaining: Option<i64>, // days until session expires
+    pub pinned: bool,
+    /// How the provider's balances should be labelled
+    pub balance_display: BalanceDisplayDto,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -41,6 +46,8 @@ pub struct AccountDetailDto {
     pub api_user: String,
     pub cookies: HashMap<String, String>,
     pub cookies_count: i32,
+    /// Headers added to this account's requests, over the provider's own
+    pub extra_headers: BTreeMap<String, String>,
     pub enabled: bool,
     pub last_check_in: Option<String>,
     pub last_balance: Option<BalanceDto>,
@@ -49,6 +56,7 @@ pub struct AccountDetailDto {
     pub auto_checkin_hour: u8,
     pub auto_checkin_minute: u8,
     pub check_in_interval_hours: u8,
+    pub max_attempts_per_day: u8,
 }
 
 // ============================================================
@@ -60,6 +68,7 @@ pub struct AccountDetailDto {
 pub struct AccountDtoMapper<'a> {
     pub provider_name: String,
     pub now: DateTime<Utc>,
+    pub balance_display: BalanceDisplayDto,
     account: &'a Account,
 }
 
@@ -68,6 +77,7 @@ impl<'a> AccountDtoMapper<'a> {
         Self {
             provider_name,
             now: Utc::now(),
+            balance_display: BalanceDisplayDto::default(),
             account,
         }
     }
@@ -77,6 +87,11 @@ impl<'a> AccountDtoMapper<'a> {
         self
     }
 
+    pub fn with_balance_display(mut self, balance_display: BalanceDisplayDto) -> Self {
+        self.balance_display = balance_display;
+        self
+    }
+
     pub fn into_dto(self) -> AccountDto {
         let acc = self.account;
 
@@ -110,6 +125,7 @@ impl<'a> AccountDtoMapper<'a> {
             auto_checkin_hour: acc.auto_checkin_hour(),
             auto_checkin_minute: acc.auto_checkin_minute(),
             check_in_interval_hours: acc.check_in_interval_hours(),
+            max_attempts_per_day: acc.max_attempts_per_day(),
             last_balance_check_at: acc.last_balance_check_at().map(|dt| dt.to_rfc3339()),
             current_balance: acc.current_balance(),
             total_consumed: acc.total_consumed(),
@@ -119,6 +135,8 @@ impl<'a> AccountDtoMapper<'a> {
             session_expires_at: session_expires_at.map(|dt| dt.to_rfc3339()),
             session_expires_soon,
             session_days_remaining,
+            pinned: acc.is_pinned(),
+            balance_display: self.balance_display,
         }
     }
 }
@@ -155,6 +173,7 @@ impl<'a> AccountDetailDtoMapper<'a> {
             api_user: acc.credentials().api_user().to_string(),
             cookies: acc.credentials().cookies().clone(),
             cookies_count: acc.credentials().cookies().len() as i32,
+            extra_headers: acc.extra_headers().as_map().clone(),
             enabled: acc.is_enabled(),
             last_check_in: acc.last_check_in().map(|dt| dt.to_rfc3339()),
             last_balance: self.last_balance,
@@ -163,6 +182,7 @@ impl<'a> AccountDetailDtoMapper<'a> {
             auto_checkin_hour: acc.auto_checkin_hour(),
             auto_checkin_minute: acc.auto_checkin_minute(),
             check_in_interval_hours: acc.check_in_interval_hours(),
+            max_attempts_per_day: acc.max_attempts_per_day(),
         }
     }
 }
@@ -177,6 +197,7 @@ pub struct CreateAccountInput {
     pub provider_id: String,
     pub cookies: HashMap<String, String>,
     pub api_user: String,
+    pub extra_headers: Option<BTreeMap<String, String>>,
     pub auto_checkin_enabled: Option<bool>,
     pub auto_checkin_hour: Option<u8>,
     pub auto_checkin_minute: Option<u8>,
@@ -189,10 +210,13 @@ pub struct UpdateAccountInput {
     pub provider_id: Option<String>,
     pub cookies: Option<HashMap<String, String>>,
     pub api_user: Option<String>,
+    /// Replaces the account's extra headers, an empty map removes them
+    pub extra_headers: Option<BTreeMap<String, String>>,
     pub auto_checkin_enabled: Option<bool>,
     pub auto_checkin_hour: Option<u8>,
     pub auto_checkin_minute: Option<u8>,
     pub check_in_interval_hours: Option<u8>,
+    pub max_attempts_per_day: Option<u8>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
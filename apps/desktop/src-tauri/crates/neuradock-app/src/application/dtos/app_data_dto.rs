@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::BTreeMap;
+
+use neuradock_infrastructure::persistence::{AppDataImportReport, ImportMode};
+
+/// Result of exporting all application data to an archive
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct AppDataExportDto {
+    pub path: String,
+    /// Rows exported per table
+    pub row_counts: BTreeMap<String, u64>,
+}
+
+/// Result of importing an application data archive
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct AppDataImportDto {
+    /// `merge` or `replace`
+    pub mode: String,
+    /// Rows written per table
+    pub imported: BTreeMap<String, u64>,
+    /// Rows per table already present and kept (merge only)
+    pub skipped: BTreeMap<String, u64>,
+}
+
+impl AppDataImportDto {
+    pub fn new(mode: ImportMode, report: AppDataImportReport) -> Self {
+        Self {
+            mode: mode.as_str().to_string(),
+            imported: report.imported,
+            skipped: report.skipped,
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::application::services::AppLockStatus;
+
+/// App lock state for the settings page and the unlock prompt
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct AppLockStatusDto {
+    pub enabled: bool,
+    pub locked: bool,
+    /// Minutes without credential access before locking again (0 = only at launch)
+    pub idle_timeout_minutes: u32,
+    /// Seconds until another unlock attempt is accepted after too many wrong ones
+    pub retry_after_secs: Option<u32>,
+}
+
+impl From<AppLockStatus> for AppLockStatusDto {
+    fn from(status: AppLockStatus) -> Self {
+        Self {
+            enabled: status.enabled,
+            locked: status.locked,
+            idle_timeout_minutes: status.idle_timeout_minutes,
+            retry_after_secs: status
+                .retry_after_secs
+                .map(|secs| secs.min(u64::from(u32::MAX)) as u32),
+        }
+    }
+}
+
+/// Input for turning the app lock on or off or changing it
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SetAppLockInput {
+    pub enabled: bool,
+    /// New passphrase, keeps the current one when omitted
+    pub passphrase: Option<String>,
+    pub idle_timeout_minutes: u32,
+    /// Required while an app lock passphrase is set
+    pub current_passphrase: Option<String>,
+}
@@ -3,6 +3,8 @@ use specta::Type;
 
 use neuradock_domain::check_in::Balance;
 
+use super::BalanceDisplayDto;
+
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct BalanceDto {
     pub current_balance: f64,
@@ -28,6 +30,7 @@ pub struct ProviderBalanceDto {
     pub total_consumed: f64,
     pub total_quota: f64,
     pub account_count: i32,
+    pub balance_display: BalanceDisplayDto,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -37,3 +40,48 @@ pub struct BalanceStatisticsDto {
     pub total_consumed: f64,
     pub total_quota: f64,
 }
+
+/// Provider totals per day over a window of days
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct BalanceStatisticsOverTimeDto {
+    /// First day of the window (YYYY-MM-DD, UTC)
+    pub start_date: String,
+    /// Last day of the window (YYYY-MM-DD, UTC)
+    pub end_date: String,
+    pub providers: Vec<ProviderBalanceSeriesDto>,
+}
+
+/// Daily totals of one provider's enabled accounts
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ProviderBalanceSeriesDto {
+    pub provider_id: String,
+    pub provider_name: String,
+    pub balance_display: BalanceDisplayDto,
+    /// Days with history, oldest first
+    pub points: Vec<ProviderDailyBalanceDto>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ProviderDailyBalanceDto {
+    /// YYYY-MM-DD
+    pub date: String,
+    pub current_balance: f64,
+    pub total_consumed: f64,
+    pub total_quota: f64,
+    /// Accounts with history on the day, the others are not in the sums
+    pub account_count: u32,
+}
+
+/// Result of backfilling an account's balance history from a transaction log
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct BalanceBackfillDto {
+    pub account_id: String,
+    /// Balance changes read from the transaction log
+    pub transactions: u32,
+    /// Days added to the history, days that already had a snapshot are kept
+    pub days_added: u32,
+    /// Oldest added day (YYYY-MM-DD)
+    pub first_day: Option<String>,
+    /// Newest added day (YYYY-MM-DD)
+    pub last_day: Option<String>,
+}
@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 use specta::Type;
 
 use super::BalanceDto;
+use crate::application::services::RunningJob;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct CheckInHistoryDto {
@@ -35,12 +36,26 @@ pub struct RunningJobDto {
     pub started_at: String,
 }
 
+impl From<RunningJob> for RunningJobDto {
+    fn from(job: RunningJob) -> Self {
+        Self {
+            job_id: job.job_id,
+            account_id: job.account_id,
+            account_name: job.account_name,
+            status: "running".to_string(),
+            started_at: job.started_at.to_rfc3339(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct ExecuteCheckInResult {
     pub account_id: String,
     pub account_name: String,
     pub provider_id: String,
     pub success: bool,
+    /// Reward reported by the provider, in balance units
+    pub reward: Option<f64>,
     pub balance: Option<BalanceDto>,
     pub error: Option<String>,
 }
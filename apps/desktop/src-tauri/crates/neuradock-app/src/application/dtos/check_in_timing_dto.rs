@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use neuradock_domain::check_in_timing::{CheckInPhase, CheckInTiming};
+use neuradock_infrastructure::monitoring::{CheckInPhaseMetrics, CheckInTimingMetricsSnapshot};
+
+/// Time spent in each phase of a check-in run
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CheckInTimingDto {
+    pub account_id: String,
+    pub provider_id: String,
+    pub started_at: String,
+    pub success: bool,
+    pub cookie_preparation_ms: f64,
+    /// Zero unless the cookies were rejected and the WAF challenge solved again
+    pub waf_refresh_ms: f64,
+    pub user_info_ms: f64,
+    pub check_in_request_ms: f64,
+    pub balance_fetch_ms: f64,
+    /// Wall time of the run, including time outside the phases above
+    pub total_ms: f64,
+}
+
+/// Check-in phase timings since app start
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CheckInTimingMetricsDto {
+    pub runs: u32,
+    pub average_total_ms: Option<f64>,
+    pub max_total_ms: f64,
+    pub phases: Vec<CheckInPhaseMetricsDto>,
+}
+
+/// Timings of a single check-in phase since app start
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CheckInPhaseMetricsDto {
+    /// cookie_preparation, waf_refresh, user_info, check_in_request or balance_fetch
+    pub phase: String,
+    /// Runs in which the phase took place
+    pub runs: u32,
+    pub average_ms: Option<f64>,
+    pub max_ms: f64,
+}
+
+impl From<&CheckInTiming> for CheckInTimingDto {
+    fn from(timing: &CheckInTiming) -> Self {
+        let ms = |phase| timing.phase_ms(phase) as f64;
+        Self {
+            account_id: timing.account_id().as_str().to_string(),
+            provider_id: timing.provider_id().as_str().to_string(),
+            started_at: timing.started_at().to_rfc3339(),
+            success: timing.success(),
+            cookie_preparation_ms: ms(CheckInPhase::CookiePreparation),
+            waf_refresh_ms: ms(CheckInPhase::WafRefresh),
+            user_info_ms: ms(CheckInPhase::UserInfo),
+            check_in_request_ms: ms(CheckInPhase::CheckInRequest),
+            balance_fetch_ms: ms(CheckInPhase::BalanceFetch),
+            total_ms: timing.total_ms() as f64,
+        }
+    }
+}
+
+impl From<CheckInTimingMetricsSnapshot> for CheckInTimingMetricsDto {
+    fn from(snapshot: CheckInTimingMetricsSnapshot) -> Self {
+        Self {
+            runs: snapshot.runs.min(u32::MAX as u64) as u32,
+            average_total_ms: snapshot.average_total_ms.map(|ms| ms as f64),
+            max_total_ms: snapshot.max_total_ms as f64,
+            phases: snapshot.phases.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<CheckInPhaseMetrics> for CheckInPhaseMetricsDto {
+    fn from(metrics: CheckInPhaseMetrics) -> Self {
+        Self {
+            phase: metrics.phase.as_str().to_string(),
+            runs: metrics.runs.min(u32::MAX as u64) as u32,
+            average_ms: metrics.average_ms.map(|ms| ms as f64),
+            max_ms: metrics.max_ms as f64,
+        }
+    }
+}
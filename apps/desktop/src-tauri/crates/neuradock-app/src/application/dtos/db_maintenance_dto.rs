@@ -0,0 +1,167 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::BTreeMap;
+
+use neuradock_infrastructure::persistence::{
+    DatabaseMaintenanceReport, DatabaseMove, DatabaseRecovery, DatabaseStats, MigrationFailure,
+};
+
+/// Outcome of a database maintenance run
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct DbMaintenanceReportDto {
+    /// `incremental` or `full`
+    pub vacuum_mode: String,
+    /// Database file plus its WAL, before the run
+    pub size_before_bytes: u64,
+    /// Database file plus its WAL, after the run
+    pub size_after_bytes: u64,
+    pub reclaimed_bytes: u64,
+    pub duration_ms: u64,
+    /// When the run finished (RFC 3339)
+    pub completed_at: String,
+}
+
+impl From<DatabaseMaintenanceReport> for DbMaintenanceReportDto {
+    fn from(report: DatabaseMaintenanceReport) -> Self {
+        Self {
+            vacuum_mode: report.vacuum_mode.as_str().to_string(),
+            size_before_bytes: report.size_before_bytes,
+            size_after_bytes: report.size_after_bytes,
+            reclaimed_bytes: report.reclaimed_bytes(),
+            duration_ms: report.duration.as_millis() as u64,
+            completed_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+/// Weekly database maintenance setting and last run
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct DbMaintenanceSettingsDto {
+    pub weekly: bool,
+    /// Last completed run (RFC 3339)
+    pub last_run_at: Option<String>,
+}
+
+/// Recovery of a corrupt database performed at startup
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct DatabaseRecoveryDto {
+    /// `restored_from_backup` or `started_fresh`
+    pub outcome: String,
+    /// Where the corrupt database was moved to
+    pub corrupt_path: String,
+    pub backup_path: Option<String>,
+    /// Explanation to show to the user
+    pub message: String,
+}
+
+impl From<&DatabaseRecovery> for DatabaseRecoveryDto {
+    fn from(recovery: &DatabaseRecovery) -> Self {
+        let (outcome, backup_path) = match recovery {
+            DatabaseRecovery::RestoredFromBackup { backup_path, .. } => (
+                "restored_from_backup",
+                Some(backup_path.to_string_lossy().to_string()),
+            ),
+            DatabaseRecovery::StartedFresh { .. } => ("started_fresh", None),
+        };
+        Self {
+            outcome: outcome.to_string(),
+            corrupt_path: recovery.corrupt_path().to_string_lossy().to_string(),
+            backup_path,
+            message: recovery.message(),
+        }
+    }
+}
+
+/// Whether the schema migrations applied at startup, and what failed if they did not
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct MigrationStatusDto {
+    /// The database was restored after a failed migration and cannot be written
+    pub read_only: bool,
+    pub failure: Option<MigrationFailureDto>,
+}
+
+/// A migration that failed at startup and was rolled back
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct MigrationFailureDto {
+    pub version: i64,
+    pub description: String,
+    pub error: String,
+    /// Snapshot the database was restored from
+    pub snapshot_path: String,
+    /// RFC 3339
+    pub failed_at: String,
+    /// Explanation to show to the user
+    pub message: String,
+}
+
+impl From<Option<&MigrationFailure>> for MigrationStatusDto {
+    fn from(failure: Option<&MigrationFailure>) -> Self {
+        Self {
+            read_only: failure.is_some(),
+            failure: failure.map(|failure| MigrationFailureDto {
+                version: failure.version,
+                description: failure.description.clone(),
+                error: failure.error.clone(),
+                snapshot_path: failure.snapshot_path.to_string_lossy().to_string(),
+                failed_at: failure.failed_at.to_rfc3339(),
+                message: failure.message(),
+            }),
+        }
+    }
+}
+
+/// Where the database is stored
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct DatabaseLocationDto {
+    pub db_path: String,
+    /// Whether the database is in the default app data directory
+    pub is_default: bool,
+    /// Why a configured data directory was not used at startup
+    pub stale_error: Option<String>,
+}
+
+/// Database moved to a new data directory, applied after a restart
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct DatabaseMoveDto {
+    pub from_path: String,
+    pub to_path: String,
+    pub size_bytes: u64,
+}
+
+impl From<DatabaseMove> for DatabaseMoveDto {
+    fn from(moved: DatabaseMove) -> Self {
+        Self {
+            from_path: moved.from_path.to_string_lossy().to_string(),
+            to_path: moved.to_path.to_string_lossy().to_string(),
+            size_bytes: moved.size_bytes,
+        }
+    }
+}
+
+/// Storage used by the database, for the settings storage section
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct DatabaseStatsDto {
+    pub db_size_bytes: u64,
+    pub wal_size_bytes: u64,
+    /// Rows per table
+    pub row_counts: BTreeMap<String, u64>,
+    /// Latest applied migration
+    pub schema_version: i64,
+    /// Newest automatic backup (RFC 3339)
+    pub last_backup_at: Option<String>,
+    /// Last completed maintenance run (RFC 3339)
+    pub last_maintenance_at: Option<String>,
+}
+
+impl DatabaseStatsDto {
+    pub fn new(stats: DatabaseStats, last_maintenance_at: Option<String>) -> Self {
+        Self {
+            db_size_bytes: stats.db_size_bytes,
+            wal_size_bytes: stats.wal_size_bytes,
+            row_counts: stats.row_counts,
+            schema_version: stats.schema_version,
+            last_backup_at: stats.last_backup_at.map(|at| at.to_rfc3339()),
+            last_maintenance_at,
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::application::services::{ScheduledTaskHealth, SchedulerHealth};
+use neuradock_infrastructure::logging::DiagnosticBundle;
+
+/// State of the auto check-in scheduler
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SchedulerHealthDto {
+    /// The background task restarting stopped check-in tasks is alive
+    pub health_check_running: bool,
+    pub tasks: Vec<ScheduledTaskHealthDto>,
+    /// Accounts whose task stopped too often and is no longer restarted
+    pub given_up_account_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ScheduledTaskHealthDto {
+    pub account_id: String,
+    pub account_name: String,
+    /// Local time of the daily check-in, HH:MM
+    pub scheduled_time: String,
+    pub running: bool,
+    /// Last check-in run by the task (RFC 3339)
+    pub last_execution: Option<String>,
+    /// Restarts within the last 24 hours
+    pub recent_restarts: u32,
+}
+
+impl From<SchedulerHealth> for SchedulerHealthDto {
+    fn from(health: SchedulerHealth) -> Self {
+        Self {
+            health_check_running: health.health_check_running,
+            tasks: health
+                .tasks
+                .into_iter()
+                .map(ScheduledTaskHealthDto::from)
+                .collect(),
+            given_up_account_ids: health
+                .given_up
+                .into_iter()
+                .map(|id| id.as_str().to_string())
+                .collect(),
+        }
+    }
+}
+
+impl From<ScheduledTaskHealth> for ScheduledTaskHealthDto {
+    fn from(task: ScheduledTaskHealth) -> Self {
+        Self {
+            account_id: task.account_id.as_str().to_string(),
+            account_name: task.account_name,
+            scheduled_time: format!("{:02}:{:02}", task.hour, task.minute),
+            running: task.running,
+            last_execution: task.last_execution.map(|at| at.to_rfc3339()),
+            recent_restarts: task.recent_restarts as u32,
+        }
+    }
+}
+
+/// Diagnostic bundle written by `export_diagnostics`
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct DiagnosticsExportDto {
+    pub path: String,
+    pub size_bytes: u64,
+    /// Log files included, oldest first
+    pub log_files: Vec<String>,
+}
+
+impl From<DiagnosticBundle> for DiagnosticsExportDto {
+    fn from(bundle: DiagnosticBundle) -> Self {
+        Self {
+            path: bundle.path.to_string_lossy().to_string(),
+            size_bytes: bundle.size_bytes,
+            log_files: bundle.log_files,
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::BTreeMap;
+
+use neuradock_infrastructure::persistence::KeyRotationReport;
+
+/// Result of rotating (or, in a dry run, verifying) the encryption key
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct EncryptionKeyRotationDto {
+    pub dry_run: bool,
+    /// Encrypted values processed per `table.column`
+    pub rows: BTreeMap<String, u64>,
+}
+
+impl EncryptionKeyRotationDto {
+    pub fn new(dry_run: bool, report: KeyRotationReport) -> Self {
+        Self {
+            dry_run,
+            rows: report.rows,
+        }
+    }
+}
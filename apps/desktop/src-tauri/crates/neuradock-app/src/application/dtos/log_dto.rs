@@ -0,0 +1,153 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use neuradock_infrastructure::logging::{CrashReportFile, FrontendLog, LogEntry, LogPage};
+
+/// Level override of a module prefix
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ModuleLogLevelDto {
+    /// Module path prefix, e.g. `neuradock_infrastructure::http`
+    pub module: String,
+    /// `error`, `warn`, `info`, `debug` or `trace`
+    pub level: String,
+}
+
+/// Log line reported by the frontend
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct FrontendLogInput {
+    /// `error`, `warn`, `info`, `debug` or `trace`, anything else is logged as `info`
+    pub level: String,
+    pub target: String,
+    pub message: String,
+    /// Structured fields as a JSON object string
+    pub fields: Option<String>,
+}
+
+impl From<FrontendLogInput> for FrontendLog {
+    fn from(input: FrontendLogInput) -> Self {
+        Self {
+            level: input.level,
+            target: input.target,
+            message: input.message,
+            fields: input.fields.and_then(|f| serde_json::from_str(&f).ok()),
+        }
+    }
+}
+
+/// Filters for the in-app log viewer
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct LogQueryInput {
+    /// Minimum level (`trace`, `debug`, `info`, `warn`, `error`)
+    pub level: Option<String>,
+    pub target_prefix: Option<String>,
+    /// Earliest entry to include (RFC 3339)
+    pub since: Option<String>,
+    /// Latest entry to include (RFC 3339)
+    pub until: Option<String>,
+    /// Case-insensitive search in the message and fields
+    pub text: Option<String>,
+    pub correlation_id: Option<String>,
+    /// Also read the previous rotated log file
+    #[serde(default)]
+    pub include_previous: bool,
+    /// Defaults to 200, at most 1000
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct LogEntryDto {
+    /// RFC 3339 with the original offset
+    pub timestamp: String,
+    pub level: String,
+    pub target: String,
+    pub message: String,
+    /// `backend` or `frontend`
+    pub source: Option<String>,
+    /// Structured fields as a JSON object string
+    pub fields: Option<String>,
+    pub file: Option<String>,
+    pub line: Option<u32>,
+}
+
+impl From<LogEntry> for LogEntryDto {
+    fn from(entry: LogEntry) -> Self {
+        Self {
+            timestamp: entry.timestamp.to_rfc3339(),
+            level: entry.level.to_string(),
+            target: entry.target,
+            message: entry.message,
+            source: entry.source,
+            fields: entry.fields.map(|fields| fields.to_string()),
+            file: entry.file,
+            line: entry.line,
+        }
+    }
+}
+
+/// One page of log entries, newest first
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct LogPageDto {
+    pub entries: Vec<LogEntryDto>,
+    /// Lines that could not be parsed (truncated or corrupt)
+    pub skipped_lines: u32,
+    /// More matching entries exist beyond the limit
+    pub has_more: bool,
+}
+
+impl From<LogPage> for LogPageDto {
+    fn from(page: LogPage) -> Self {
+        Self {
+            entries: page.entries.into_iter().map(LogEntryDto::from).collect(),
+            skipped_lines: page.skipped_lines as u32,
+            has_more: page.has_more,
+        }
+    }
+}
+
+/// Crash report written by the panic hook
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CrashReportDto {
+    /// Pass to `open_crash_report`
+    pub file_name: String,
+    pub path: String,
+    /// RFC 3339
+    pub occurred_at: String,
+    pub message: String,
+    /// `file:line:column`
+    pub location: Option<String>,
+    pub thread: Option<String>,
+    pub backtrace: Option<String>,
+    pub version: String,
+    pub os: String,
+    pub arch: String,
+    /// Redacted log lines before the crash, oldest first
+    pub recent_logs: Vec<String>,
+}
+
+impl From<CrashReportFile> for CrashReportDto {
+    fn from(file: CrashReportFile) -> Self {
+        let report = file.report;
+        Self {
+            file_name: file.file_name,
+            path: file.path.display().to_string(),
+            occurred_at: report.occurred_at.to_rfc3339(),
+            message: report.message,
+            location: report.location,
+            thread: report.thread,
+            backtrace: report.backtrace,
+            version: report.version,
+            os: report.os,
+            arch: report.arch,
+            recent_logs: report.recent_logs,
+        }
+    }
+}
+
+/// State of the provider request capture
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct RequestCaptureDto {
+    /// Requests still to be captured, 0 when the capture is off
+    pub remaining: u32,
+    /// Debug file the captured requests are appended to
+    pub file_path: Option<String>,
+}
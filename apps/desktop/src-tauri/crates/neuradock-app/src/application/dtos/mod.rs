@@ -10,6 +10,10 @@ pub use balance_dto::*;
 mod check_in_dto;
 pub use check_in_dto::*;
 
+// Check-in timing DTOs
+mod check_in_timing_dto;
+pub use check_in_timing_dto::*;
+
 // Streak DTOs
 mod streak_dto;
 pub use streak_dto::*;
@@ -33,3 +37,35 @@ pub use independent_key_dto::*;
 // Proxy Config DTOs
 mod proxy_config_dto;
 pub use proxy_config_dto::*;
+
+// Database maintenance DTOs
+mod db_maintenance_dto;
+pub use db_maintenance_dto::*;
+
+// Application data archive DTOs
+mod app_data_dto;
+pub use app_data_dto::*;
+
+// Encryption key rotation DTOs
+mod encryption_key_dto;
+pub use encryption_key_dto::*;
+
+// App lock DTOs
+mod app_lock_dto;
+pub use app_lock_dto::*;
+
+// Diagnostics DTOs
+mod diagnostics_dto;
+pub use diagnostics_dto::*;
+
+// Log viewer DTOs
+mod log_dto;
+pub use log_dto::*;
+
+// WAF Metrics DTOs
+mod waf_metrics_dto;
+pub use waf_metrics_dto::*;
+
+// Runtime metrics DTOs
+mod runtime_metrics_dto;
+pub use runtime_metrics_dto::*;
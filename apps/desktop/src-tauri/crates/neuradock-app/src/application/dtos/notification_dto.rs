@@ -1,6 +1,11 @@
 use serde::{Deserialize, Serialize};
 use specta::Type;
 
+use std::str::FromStr;
+
+use neuradock_domain::notification::{Locale, NotificationTemplates};
+use neuradock_domain::shared::{DomainError, ErrorSeverity};
+
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct NotificationChannelDto {
     pub id: String,
@@ -8,6 +13,12 @@ pub struct NotificationChannelDto {
     #[specta(type = String)]
     pub config: serde_json::Value,
     pub enabled: bool,
+    /// Lowest severity sent through the channel
+    pub min_severity: ErrorSeverity,
+    /// Content templates of the channel, over the global ones
+    pub templates: NotificationTemplatesDto,
+    /// Locale of the channel's notifications, None uses the default
+    pub locale: Option<Locale>,
     pub created_at: String,
 }
 
@@ -16,6 +27,12 @@ pub struct CreateNotificationChannelInput {
     pub channel_type: String,
     #[specta(type = String)]
     pub config: serde_json::Value,
+    /// Defaults to `Info`, sending every notification
+    pub min_severity: Option<ErrorSeverity>,
+    /// Defaults to none, using the global or built-in content
+    pub templates: Option<NotificationTemplatesDto>,
+    /// "zh-CN" or "en-US", defaults to the default notification locale
+    pub locale: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -24,4 +41,55 @@ pub struct UpdateNotificationChannelInput {
     #[specta(type = String)]
     pub config: Option<serde_json::Value>,
     pub enabled: Option<bool>,
+    pub min_severity: Option<ErrorSeverity>,
+    pub templates: Option<NotificationTemplatesDto>,
+    /// Blank switches back to the default notification locale
+    pub locale: Option<String>,
+}
+
+/// Parse a channel locale, blank means the default notification locale
+pub fn parse_channel_locale(locale: &str) -> Result<Option<Locale>, DomainError> {
+    let locale = locale.trim();
+    if locale.is_empty() {
+        return Ok(None);
+    }
+    Locale::from_str(locale).map(Some)
+}
+
+/// Check-in notification templates with `{account}`, `{provider}`, `{balance}`,
+/// `{reward}` and `{error}` placeholders, None or blank for the built-in content
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct NotificationTemplatesDto {
+    pub check_in_success: Option<String>,
+    pub check_in_failure: Option<String>,
+}
+
+impl NotificationTemplatesDto {
+    /// Validate the templates, rejecting unknown placeholders
+    pub fn parse(self) -> Result<NotificationTemplates, DomainError> {
+        NotificationTemplates::parse(self.check_in_success, self.check_in_failure)
+    }
+}
+
+impl From<&NotificationTemplates> for NotificationTemplatesDto {
+    fn from(templates: &NotificationTemplates) -> Self {
+        Self {
+            check_in_success: templates
+                .check_in_success
+                .as_ref()
+                .map(|t| t.as_str().to_string()),
+            check_in_failure: templates
+                .check_in_failure
+                .as_ref()
+                .map(|t| t.as_str().to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct DailySummarySettingsDto {
+    /// Local time the summary is sent at (HH:MM), None when disabled
+    pub time: Option<String>,
+    /// Local date the last summary was sent for (YYYY-MM-DD)
+    pub last_sent_on: Option<String>,
 }
@@ -1,5 +1,9 @@
 use serde::{Deserialize, Serialize};
 use specta::Type;
+use std::collections::BTreeMap;
+
+use neuradock_domain::check_in::{BalanceDisplay, QuotaResetSchedule, RewardExtraction};
+use neuradock_domain::shared::DomainError;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct ProviderDto {
@@ -13,11 +17,130 @@ pub struct ProviderDto {
     // API configuration fields
     pub login_path: String,
     pub sign_in_path: Option<String>,
+    /// JSON body POSTed on check-in
+    pub sign_in_body: Option<String>,
     pub user_info_path: String,
     pub token_api_path: Option<String>,
     pub models_path: Option<String>,
+    /// Usage/transaction log endpoint used to backfill balance history
+    pub transaction_log_path: Option<String>,
     pub api_user_key: String,
+    /// Static headers added to user info and check-in requests
+    pub extra_headers: BTreeMap<String, String>,
+    /// API fork the provider runs (`new_api` or `one_api`)
+    pub dialect: String,
     pub needs_waf_bypass: bool,
+    /// WAF protection the provider sits behind (`waf_cookies` or `cloudflare`)
+    pub bypass_method: Option<String>,
+    pub requires_turnstile: bool,
+    pub quota_reset_schedule: Option<QuotaResetScheduleDto>,
+    pub balance_display: BalanceDisplayDto,
+    pub reward_extraction: Option<RewardExtractionDto>,
+}
+
+/// Daily quota reset schedule (time of day + UTC offset)
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct QuotaResetScheduleDto {
+    /// Reset time in HH:MM
+    pub reset_time: String,
+    /// UTC offset, e.g. "+08:00"
+    pub utc_offset: String,
+}
+
+impl From<&QuotaResetSchedule> for QuotaResetScheduleDto {
+    fn from(schedule: &QuotaResetSchedule) -> Self {
+        Self {
+            reset_time: schedule.reset_time().to_string(),
+            utc_offset: schedule.utc_offset().to_string(),
+        }
+    }
+}
+
+impl QuotaResetScheduleDto {
+    pub fn to_domain(&self) -> Result<QuotaResetSchedule, DomainError> {
+        QuotaResetSchedule::new(&self.reset_time, &self.utc_offset)
+    }
+}
+
+/// How balances of a provider are labelled (display only, amounts are not converted)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+pub struct BalanceDisplayDto {
+    pub currency_symbol: String,
+    pub unit_label: String,
+    pub decimal_places: u8,
+}
+
+impl From<&BalanceDisplay> for BalanceDisplayDto {
+    fn from(display: &BalanceDisplay) -> Self {
+        Self {
+            currency_symbol: display.currency_symbol().to_string(),
+            unit_label: display.unit_label().to_string(),
+            decimal_places: display.decimal_places(),
+        }
+    }
+}
+
+impl Default for BalanceDisplayDto {
+    fn default() -> Self {
+        Self::from(&BalanceDisplay::default())
+    }
+}
+
+impl BalanceDisplayDto {
+    pub fn to_domain(&self) -> Result<BalanceDisplay, DomainError> {
+        BalanceDisplay::new(&self.currency_symbol, &self.unit_label, self.decimal_places)
+    }
+}
+
+/// Where a provider reports the check-in reward
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+pub struct RewardExtractionDto {
+    /// Dot-separated path into the response JSON, e.g. `data.quota`
+    pub json_path: Option<String>,
+    /// Regex on the response message, the first capture group is the amount
+    pub message_pattern: Option<String>,
+    /// Raw reward units per balance unit (default 1)
+    pub quota_per_unit: Option<f64>,
+}
+
+impl From<&RewardExtraction> for RewardExtractionDto {
+    fn from(extraction: &RewardExtraction) -> Self {
+        Self {
+            json_path: extraction.json_path().map(str::to_string),
+            message_pattern: extraction.message_pattern().map(str::to_string),
+            quota_per_unit: Some(extraction.quota_per_unit()),
+        }
+    }
+}
+
+impl RewardExtractionDto {
+    /// Domain value, `None` when neither a path nor a pattern is set
+    pub fn to_domain(&self) -> Result<Option<RewardExtraction>, DomainError> {
+        let is_blank =
+            |value: &Option<String>| value.as_deref().is_none_or(|v| v.trim().is_empty());
+        if is_blank(&self.json_path) && is_blank(&self.message_pattern) {
+            return Ok(None);
+        }
+
+        RewardExtraction::new(
+            self.json_path.clone(),
+            self.message_pattern.clone(),
+            self.quota_per_unit.unwrap_or(1.0),
+        )
+        .map(Some)
+    }
+}
+
+/// Next quota reset of a provider
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct QuotaResetInfoDto {
+    pub provider_id: String,
+    pub provider_name: String,
+    pub reset_time: String,
+    pub utc_offset: String,
+    pub next_reset_at: String,
+    pub last_reset_at: String,
+    pub seconds_until_reset: i64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -34,6 +157,10 @@ pub struct AddProviderInput {
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct BrowserInfoDto {
     pub available: bool,
+    /// Browser that will be launched for WAF bypass (configured first, then detected)
     pub path: Option<String>,
     pub message: Option<String>,
+    pub detected_path: Option<String>,
+    pub configured_path: Option<String>,
+    pub version: Option<String>,
 }
@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use neuradock_infrastructure::monitoring::RuntimeSample;
+
+/// Runtime state at one point in time
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct RuntimeSampleDto {
+    pub sampled_at: String,
+    /// Per-account auto check-in loops
+    pub scheduler_tasks: u32,
+    pub running_check_ins: u32,
+    /// Resident memory of the app process, `None` where it cannot be read
+    pub memory_rss_bytes: Option<f64>,
+    /// Open SQLite connections, idle ones included
+    pub db_connections: u32,
+    pub db_idle_connections: u32,
+    /// Events published but not yet handled
+    pub event_bus_pending: u32,
+    pub uptime_secs: f64,
+}
+
+/// Current runtime state and the recent samples for charting
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct RuntimeMetricsDto {
+    pub current: RuntimeSampleDto,
+    /// Samples taken every 30 seconds over the last hour, oldest first
+    pub history: Vec<RuntimeSampleDto>,
+}
+
+impl From<RuntimeSample> for RuntimeSampleDto {
+    fn from(sample: RuntimeSample) -> Self {
+        let count = |value: u64| value.min(u32::MAX as u64) as u32;
+        Self {
+            sampled_at: sample.sampled_at.to_rfc3339(),
+            scheduler_tasks: count(sample.scheduler_tasks),
+            running_check_ins: count(sample.running_check_ins),
+            memory_rss_bytes: sample.memory_rss_bytes.map(|bytes| bytes as f64),
+            db_connections: sample.db_connections,
+            db_idle_connections: sample.db_idle_connections,
+            event_bus_pending: count(sample.event_bus_pending),
+            uptime_secs: sample.uptime_secs as f64,
+        }
+    }
+}
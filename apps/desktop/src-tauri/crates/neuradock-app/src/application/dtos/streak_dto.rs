@@ -11,6 +11,8 @@ pub struct CheckInStreakDto {
     pub longest_streak: u32,
     pub total_check_in_days: u32,
     pub last_check_in_date: Option<String>, // ISO 8601 date (YYYY-MM-DD)
+    /// Streak freezes left this month, each keeps the streak over one missed day
+    pub freezes_remaining: u32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -56,3 +58,67 @@ pub struct TrendDataPoint {
     pub current_balance: f64,
     pub is_checked_in: bool,
 }
+
+/// Period a consistency metric covers, `Custom` dates are YYYY-MM-DD and inclusive
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ConsistencyPeriod {
+    /// Monday to Sunday of the current week
+    Week,
+    /// The current calendar month
+    Month,
+    Custom {
+        start_date: String,
+        end_date: String,
+    },
+}
+
+/// Days checked in over a period, like "26/30 days this month"
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CheckInConsistencyDto {
+    pub account_id: String,
+    pub start_date: String,
+    pub end_date: String,
+    pub checked_days: u32,
+    /// Days of the period elapsed so far, today included
+    pub total_days: u32,
+    pub percentage: f64, // 0.0 - 100.0
+}
+
+/// A whole year of check-ins for a GitHub-style heatmap
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CheckInYearHeatmapDto {
+    pub account_id: String,
+    pub year: i32,
+    /// One entry per day from January 1st (365 or 366): 0 = not checked in,
+    /// 1-4 = checked in, scaled by the quota gained against the year's best day
+    pub intensities: Vec<u8>,
+    pub checked_days: u32,
+    /// Largest daily quota increment of the year
+    pub max_increment: f64,
+}
+
+/// Per-day series of several accounts aligned on the same dates, for a multi-line chart
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct AccountsComparisonDto {
+    pub start_date: String,
+    pub end_date: String,
+    /// Every day of the range (YYYY-MM-DD), the x axis of all series
+    pub dates: Vec<String>,
+    /// In the requested order
+    pub accounts: Vec<AccountComparisonSeriesDto>,
+}
+
+/// One account of a comparison, each series has one entry per date and
+/// `null` on days without history
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct AccountComparisonSeriesDto {
+    pub account_id: String,
+    pub account_name: String,
+    pub current_streak: u32,
+    pub longest_streak: u32,
+    pub total_quota: Vec<Option<f64>>,
+    pub current_balance: Vec<Option<f64>>,
+    /// Quota gained over the previous recorded day of the range
+    pub income_increment: Vec<Option<f64>>,
+}
@@ -0,0 +1,87 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use neuradock_infrastructure::http::waf_bypass::WafDiagnosticCapture;
+use neuradock_infrastructure::monitoring::{ProviderWafMetrics, WafMetricsSnapshot};
+
+/// WAF bypass telemetry since app start
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct WafMetricsDto {
+    pub validation_valid: u32,
+    pub validation_challenged: u32,
+    pub validation_failed: u32,
+    pub validation_skipped: u32,
+    pub providers: Vec<ProviderWafMetricsDto>,
+}
+
+/// WAF bypass telemetry of a single provider
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ProviderWafMetricsDto {
+    pub provider_id: String,
+    pub refresh_attempts: u32,
+    pub refresh_successes: u32,
+    pub refresh_failures: u32,
+    pub average_browser_time_ms: Option<f64>,
+    pub cache_hits: u32,
+    pub cache_misses: u32,
+    /// Share of cache lookups that were hits (0.0 - 1.0)
+    pub cache_hit_rate: Option<f64>,
+    pub last_failure_reason: Option<String>,
+    pub last_failure_at: Option<String>,
+}
+
+/// Screenshot and HTML saved after a failed WAF bypass
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct WafDiagnosticCaptureDto {
+    pub id: String,
+    pub captured_at: String,
+    pub screenshot_path: Option<String>,
+    pub html_path: Option<String>,
+    pub size_bytes: u32,
+}
+
+fn count(value: u64) -> u32 {
+    value.min(u32::MAX as u64) as u32
+}
+
+impl From<WafMetricsSnapshot> for WafMetricsDto {
+    fn from(snapshot: WafMetricsSnapshot) -> Self {
+        Self {
+            validation_valid: count(snapshot.validation_valid),
+            validation_challenged: count(snapshot.validation_challenged),
+            validation_failed: count(snapshot.validation_failed),
+            validation_skipped: count(snapshot.validation_skipped),
+            providers: snapshot.providers.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<ProviderWafMetrics> for ProviderWafMetricsDto {
+    fn from(metrics: ProviderWafMetrics) -> Self {
+        Self {
+            provider_id: metrics.provider_id,
+            refresh_attempts: count(metrics.refresh_attempts),
+            refresh_successes: count(metrics.refresh_successes),
+            refresh_failures: count(metrics.refresh_failures),
+            average_browser_time_ms: metrics.average_browser_time_ms.map(|ms| ms as f64),
+            cache_hits: count(metrics.cache_hits),
+            cache_misses: count(metrics.cache_misses),
+            cache_hit_rate: metrics.cache_hit_rate,
+            last_failure_reason: metrics.last_failure_reason,
+            last_failure_at: metrics.last_failure_at.map(|at| at.to_rfc3339()),
+        }
+    }
+}
+
+impl From<WafDiagnosticCapture> for WafDiagnosticCaptureDto {
+    fn from(capture: WafDiagnosticCapture) -> Self {
+        let path = |path: Option<std::path::PathBuf>| path.map(|p| p.display().to_string());
+        Self {
+            id: capture.id,
+            captured_at: capture.captured_at.to_rfc3339(),
+            screenshot_path: path(capture.screenshot_path),
+            html_path: path(capture.html_path),
+            size_bytes: count(capture.size_bytes),
+        }
+    }
+}
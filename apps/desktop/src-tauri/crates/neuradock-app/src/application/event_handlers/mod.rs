@@ -1,3 +1,5 @@
+pub mod provider_cache_handler;
 pub mod scheduler_reload_handler;
 
+pub use provider_cache_handler::ProviderCacheInvalidationHandler;
 pub use scheduler_reload_handler::SchedulerReloadEventHandler;
@@ -0,0 +1,54 @@
+use async_trait::async_trait;
+use log::info;
+
+use crate::application::services::CachedProviderRepository;
+use neuradock_domain::events::event_bus::EventHandler;
+use neuradock_domain::events::provider_events::*;
+use neuradock_domain::shared::DomainError;
+
+/// Handler for provider lifecycle events that invalidates the provider cache
+/// Writes through the cache already update it; this covers changes made
+/// through another repository instance
+#[derive(Clone)]
+pub struct ProviderCacheInvalidationHandler {
+    cache: CachedProviderRepository,
+}
+
+impl ProviderCacheInvalidationHandler {
+    pub fn new(cache: CachedProviderRepository) -> Self {
+        Self { cache }
+    }
+}
+
+#[async_trait]
+impl EventHandler<ProviderCreated> for ProviderCacheInvalidationHandler {
+    async fn handle(&self, event: &ProviderCreated) -> Result<(), DomainError> {
+        info!(
+            "🔔 [EVENT] ProviderCreated: {} ({})",
+            event.name, event.provider_id
+        );
+        self.cache.invalidate(&event.provider_id).await;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl EventHandler<ProviderUpdated> for ProviderCacheInvalidationHandler {
+    async fn handle(&self, event: &ProviderUpdated) -> Result<(), DomainError> {
+        info!(
+            "🔔 [EVENT] ProviderUpdated: {} ({})",
+            event.name, event.provider_id
+        );
+        self.cache.invalidate(&event.provider_id).await;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl EventHandler<ProviderDeleted> for ProviderCacheInvalidationHandler {
+    async fn handle(&self, event: &ProviderDeleted) -> Result<(), DomainError> {
+        info!("🔔 [EVENT] ProviderDeleted: {}", event.provider_id);
+        self.cache.invalidate(&event.provider_id).await;
+        Ok(())
+    }
+}
@@ -35,7 +35,8 @@ impl SchedulerReloadEventHandler {
         }
     }
 
-    async fn reload_schedules(&self) -> Result<(), DomainError> {
+    /// Reload all auto check-in schedules from the repositories
+    pub async fn reload_schedules(&self) -> Result<(), DomainError> {
         info!("🔄 [SCHEDULER] Reloading schedules due to account change");
 
         let provider_list = self.provider_repo.find_all().await?;
@@ -104,16 +105,36 @@ impl EventHandler<AccountUpdated> for SchedulerReloadEventHandler {
 impl EventHandler<AccountDeleted> for SchedulerReloadEventHandler {
     async fn handle(&self, event: &AccountDeleted) -> Result<(), DomainError> {
         info!(
-            "🔔 [EVENT] AccountDeleted: {} ({})",
-            event.name, event.account_id
+            "🔔 [EVENT] AccountDeleted: {} ({}) - permanent: {}",
+            event.name, event.account_id, event.permanent
         );
 
+        // The schedule went with the soft delete, purging only removes history
+        if event.permanent {
+            info!("⏭️  Account already unscheduled, skipping scheduler reload");
+            return Ok(());
+        }
+
         info!("🔄 Reloading scheduler to remove deleted account's schedule");
         self.reload_schedules().await?;
         Ok(())
     }
 }
 
+#[async_trait]
+impl EventHandler<AccountRestored> for SchedulerReloadEventHandler {
+    async fn handle(&self, event: &AccountRestored) -> Result<(), DomainError> {
+        info!(
+            "🔔 [EVENT] AccountRestored: {} ({})",
+            event.name, event.account_id
+        );
+
+        info!("🔄 Reloading scheduler to restore the account's schedule");
+        self.reload_schedules().await?;
+        Ok(())
+    }
+}
+
 #[async_trait]
 impl EventHandler<AccountToggled> for SchedulerReloadEventHandler {
     async fn handle(&self, event: &AccountToggled) -> Result<(), DomainError> {
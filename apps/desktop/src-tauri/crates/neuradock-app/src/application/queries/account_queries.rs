@@ -2,7 +2,7 @@ use chrono::Utc;
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use crate::application::dtos::AccountDto;
+use crate::application::dtos::{AccountDto, BalanceDisplayDto};
 use neuradock_domain::account::AccountRepository;
 use neuradock_domain::check_in::Provider;
 use neuradock_domain::shared::DomainError;
@@ -19,6 +19,8 @@ impl AccountQueryService {
     }
 
     /// Get all accounts with optional filtering
+    ///
+    /// Pinned accounts come first; the repository order is kept within each group.
     pub async fn get_all_accounts(
         &self,
         enabled_only: bool,
@@ -33,20 +35,27 @@ impl AccountQueryService {
         use crate::application::dtos::AccountDtoMapper;
 
         let now = Utc::now();
-        let dtos = accounts
+        let mut dtos: Vec<AccountDto> = accounts
             .iter()
             .map(|acc| {
-                let provider_name = providers
-                    .get(acc.provider_id().as_str())
+                let provider = providers.get(acc.provider_id().as_str());
+                let provider_name = provider
                     .map(|p| p.name().to_string())
                     .unwrap_or_else(|| "Unknown".to_string());
+                let balance_display = provider
+                    .map(|p| BalanceDisplayDto::from(p.balance_display()))
+                    .unwrap_or_default();
 
                 AccountDtoMapper::new(acc, provider_name)
                     .with_time(now)
+                    .with_balance_display(balance_display)
                     .into_dto()
             })
             .collect();
 
+        // Stable sort keeps the repository's ordering as the secondary sort
+        dtos.sort_by_key(|dto| !dto.pinned);
+
         Ok(dtos)
     }
 }
@@ -55,6 +64,7 @@ impl AccountQueryService {
 mod tests {
     use super::*;
     use neuradock_domain::account::{Account, Credentials};
+    use neuradock_domain::check_in::{BalanceDisplay, ProviderConfig};
     use neuradock_domain::shared::{AccountId, ProviderId};
     use std::collections::HashMap;
 
@@ -94,9 +104,44 @@ mod tests {
             Ok(())
         }
 
+        async fn save_in_transaction(
+            &self,
+            _tx: &mut neuradock_domain::shared::DynTransactionContext,
+            _account: &Account,
+        ) -> Result<(), DomainError> {
+            Ok(())
+        }
+
         async fn delete(&self, _id: &AccountId) -> Result<(), DomainError> {
             Ok(())
         }
+
+        async fn soft_delete(
+            &self,
+            _id: &AccountId,
+            _deleted_at: chrono::DateTime<chrono::Utc>,
+        ) -> Result<(), DomainError> {
+            Ok(())
+        }
+
+        async fn restore_deleted(&self, _id: &AccountId) -> Result<bool, DomainError> {
+            Ok(false)
+        }
+
+        async fn purge_deleted(
+            &self,
+            _deleted_before: chrono::DateTime<chrono::Utc>,
+        ) -> Result<Vec<(AccountId, String)>, DomainError> {
+            Ok(Vec::new())
+        }
+
+        async fn record_check_in_attempt(
+            &self,
+            _id: &AccountId,
+            _day: chrono::NaiveDate,
+        ) -> Result<u32, DomainError> {
+            Ok(1)
+        }
     }
 
     fn create_test_account(name: &str, enabled: bool) -> Account {
@@ -153,4 +198,117 @@ mod tests {
         assert_eq!(result.len(), 1);
         assert_eq!(result[0].name, "Account 1");
     }
+
+    #[tokio::test]
+    async fn test_pinned_accounts_first_keeping_secondary_order() {
+        let mut accounts = vec![
+            create_test_account("Newest", true),
+            create_test_account("Pinned Middle", true),
+            create_test_account("Middle", true),
+            create_test_account("Pinned Oldest", true),
+            create_test_account("Oldest", true),
+        ];
+        accounts[1].toggle_pin();
+        accounts[3].toggle_pin();
+
+        let repo = Arc::new(MockAccountRepository { accounts });
+        let service = AccountQueryService::new(repo);
+
+        let result = service
+            .get_all_accounts(false, &HashMap::new())
+            .await
+            .unwrap();
+        let names: Vec<&str> = result.iter().map(|dto| dto.name.as_str()).collect();
+
+        assert_eq!(
+            names,
+            vec![
+                "Pinned Middle",
+                "Pinned Oldest",
+                "Newest",
+                "Middle",
+                "Oldest"
+            ]
+        );
+        assert!(result[0].pinned && result[1].pinned);
+        assert!(!result[2].pinned);
+    }
+
+    #[tokio::test]
+    async fn test_pinned_ordering_with_enabled_filter() {
+        let mut accounts = vec![
+            create_test_account("Enabled", true),
+            create_test_account("Pinned Disabled", false),
+            create_test_account("Pinned Enabled", true),
+        ];
+        accounts[1].toggle_pin();
+        accounts[2].toggle_pin();
+
+        let repo = Arc::new(MockAccountRepository { accounts });
+        let service = AccountQueryService::new(repo);
+
+        let result = service
+            .get_all_accounts(true, &HashMap::new())
+            .await
+            .unwrap();
+        let names: Vec<&str> = result.iter().map(|dto| dto.name.as_str()).collect();
+
+        assert_eq!(names, vec!["Pinned Enabled", "Enabled"]);
+    }
+
+    #[tokio::test]
+    async fn test_provider_balance_display_flows_to_account_dto() {
+        let provider = Provider::new(ProviderConfig {
+            name: "Credits Provider".to_string(),
+            domain: "https://credits.example.com".to_string(),
+            login_path: "/login".to_string(),
+            sign_in_path: None,
+            sign_in_body: None,
+            user_info_path: "/api/user/self".to_string(),
+            token_api_path: None,
+            models_path: None,
+            transaction_log_path: None,
+            api_user_key: "new-api-user".to_string(),
+            extra_headers: Default::default(),
+            dialect: Default::default(),
+            bypass_method: None,
+            requires_turnstile: false,
+            supports_check_in: true,
+            check_in_bugged: false,
+            quota_reset_schedule: None,
+            balance_display: Some(BalanceDisplay::new("¥", "credits", 0).unwrap()),
+            reward_extraction: None,
+        });
+
+        let mut cookies = HashMap::new();
+        cookies.insert("session".to_string(), "test_session".to_string());
+        let custom = Account::new(
+            "Custom".to_string(),
+            provider.id().clone(),
+            Credentials::new(cookies, "test@user".to_string()),
+        )
+        .unwrap();
+        let unknown = create_test_account("Unknown Provider", true);
+
+        let mut providers = HashMap::new();
+        providers.insert(provider.id().as_str().to_string(), provider);
+
+        let repo = Arc::new(MockAccountRepository {
+            accounts: vec![custom, unknown],
+        });
+        let service = AccountQueryService::new(repo);
+
+        let result = service.get_all_accounts(false, &providers).await.unwrap();
+
+        assert_eq!(result[0].provider_name, "Credits Provider");
+        assert_eq!(
+            result[0].balance_display,
+            BalanceDisplayDto {
+                currency_symbol: "¥".to_string(),
+                unit_label: "credits".to_string(),
+                decimal_places: 0,
+            }
+        );
+        assert_eq!(result[1].balance_display, BalanceDisplayDto::default());
+    }
 }
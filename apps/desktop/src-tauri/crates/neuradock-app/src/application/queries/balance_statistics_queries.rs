@@ -1,16 +1,25 @@
+use chrono::{Duration, Utc};
 use std::collections::HashMap;
 use std::sync::Arc;
 
 use neuradock_domain::account::AccountRepository;
+use neuradock_domain::balance_history::BalanceHistoryRepository;
 use neuradock_domain::check_in::ProviderRepository;
 use neuradock_domain::shared::DomainError;
 
-use crate::application::dtos::{BalanceStatisticsDto, ProviderBalanceDto};
+use crate::application::dtos::{
+    BalanceDisplayDto, BalanceStatisticsDto, BalanceStatisticsOverTimeDto, ProviderBalanceDto,
+    ProviderBalanceSeriesDto, ProviderDailyBalanceDto,
+};
 use crate::application::services::BalanceHistoryService;
 
+/// Most days a time-windowed statistics query covers
+const MAX_STATISTICS_DAYS: u32 = 365;
+
 pub struct BalanceStatisticsQueryService {
     account_repo: Arc<dyn AccountRepository>,
     provider_repo: Arc<dyn ProviderRepository>,
+    balance_history_repo: Arc<dyn BalanceHistoryRepository>,
     balance_history_service: Arc<BalanceHistoryService>,
 }
 
@@ -18,24 +27,104 @@ impl BalanceStatisticsQueryService {
     pub fn new(
         account_repo: Arc<dyn AccountRepository>,
         provider_repo: Arc<dyn ProviderRepository>,
+        balance_history_repo: Arc<dyn BalanceHistoryRepository>,
         balance_history_service: Arc<BalanceHistoryService>,
     ) -> Self {
         Self {
             account_repo,
             provider_repo,
+            balance_history_repo,
             balance_history_service,
         }
     }
 
-    pub async fn get_balance_statistics(&self) -> Result<BalanceStatisticsDto, DomainError> {
-        let accounts = self.account_repo.find_enabled().await?;
+    /// Provider totals per day over the last `days` days (UTC), from balance history
+    ///
+    /// Each day sums the snapshots of a provider's enabled accounts that have
+    /// history on that day.
+    pub async fn get_balance_statistics_over_time(
+        &self,
+        days: u32,
+    ) -> Result<BalanceStatisticsOverTimeDto, DomainError> {
+        if days == 0 || days > MAX_STATISTICS_DAYS {
+            return Err(DomainError::Validation(format!(
+                "Days must be between 1 and {}",
+                MAX_STATISTICS_DAYS
+            )));
+        }
+
+        let end_date = Utc::now().date_naive();
+        let start_date = end_date - Duration::days(days as i64 - 1);
+
+        let totals = self
+            .balance_history_repo
+            .list_provider_daily_totals_in_range(start_date, end_date)
+            .await?;
+        let providers_by_id = self
+            .provider_repo
+            .find_all()
+            .await?
+            .into_iter()
+            .map(|provider| (provider.id().clone(), provider))
+            .collect::<HashMap<_, _>>();
+
+        // Rows come ordered by provider, then date
+        let mut series: Vec<ProviderBalanceSeriesDto> = Vec::new();
+        for total in totals {
+            let provider_id = total.provider_id().as_str();
+            if series.last().map(|s| s.provider_id.as_str()) != Some(provider_id) {
+                let provider = providers_by_id.get(total.provider_id());
+                series.push(ProviderBalanceSeriesDto {
+                    provider_id: provider_id.to_string(),
+                    provider_name: provider
+                        .map(|p| p.name().to_string())
+                        .unwrap_or_else(|| "Unknown".to_string()),
+                    balance_display: provider
+                        .map(|p| BalanceDisplayDto::from(p.balance_display()))
+                        .unwrap_or_default(),
+                    points: Vec::new(),
+                });
+            }
+            if let Some(current) = series.last_mut() {
+                current.points.push(ProviderDailyBalanceDto {
+                    date: total.date().to_string(),
+                    current_balance: total.current_balance(),
+                    total_consumed: total.total_consumed(),
+                    total_quota: total.total_quota(),
+                    account_count: total.account_count(),
+                });
+            }
+        }
+
+        Ok(BalanceStatisticsOverTimeDto {
+            start_date: start_date.to_string(),
+            end_date: end_date.to_string(),
+            providers: series,
+        })
+    }
+
+    /// Current balance totals by provider
+    ///
+    /// Only enabled accounts are counted unless `include_disabled` is set.
+    pub async fn get_balance_statistics(
+        &self,
+        include_disabled: bool,
+    ) -> Result<BalanceStatisticsDto, DomainError> {
+        let accounts = if include_disabled {
+            self.account_repo.find_all().await?
+        } else {
+            self.account_repo.find_enabled().await?
+        };
         let providers = self.provider_repo.find_all().await?;
         let providers_by_id = providers
             .iter()
             .map(|provider| {
                 (
                     provider.id().as_str().to_string(),
-                    provider.name().to_string(),
+                    (
+                        provider.name().to_string(),
+                        BalanceDisplayDto::from(provider.balance_display()),
+                    ),
                 )
             })
             .collect::<HashMap<_, _>>();
@@ -76,10 +165,10 @@ impl BalanceStatisticsQueryService {
             };
 
             let provider_id = account.provider_id().as_str();
-            let provider_name = providers_by_id
+            let (provider_name, balance_display) = providers_by_id
                 .get(provider_id)
                 .cloned()
-                .unwrap_or_else(|| "Unknown".to_string());
+                .unwrap_or_else(|| ("Unknown".to_string(), BalanceDisplayDto::default()));
 
             let stat =
                 provider_stats
@@ -91,6 +180,7 @@ impl BalanceStatisticsQueryService {
                         total_consumed: 0.0,
                         total_quota: 0.0,
                         account_count: 0,
+                        balance_display,
                     });
 
             stat.current_balance += current_balance;
@@ -111,3 +201,253 @@ impl BalanceStatisticsQueryService {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use chrono::{DateTime, NaiveDate};
+    use neuradock_domain::account::{Account, Credentials};
+    use neuradock_domain::balance_history::{
+        BalanceHistoryDailySummary, BalanceHistoryRecord, ProviderDailyBalanceTotal,
+    };
+    use neuradock_domain::check_in::Provider;
+    use neuradock_domain::shared::{AccountId, ProviderId};
+
+    struct MockAccountRepository {
+        accounts: Vec<Account>,
+    }
+
+    #[async_trait]
+    impl AccountRepository for MockAccountRepository {
+        async fn find_all(&self) -> Result<Vec<Account>, DomainError> {
+            Ok(self.accounts.clone())
+        }
+
+        async fn find_enabled(&self) -> Result<Vec<Account>, DomainError> {
+            Ok(self
+                .accounts
+                .iter()
+                .filter(|a| a.is_enabled())
+                .cloned()
+                .collect())
+        }
+
+        async fn find_by_id(&self, _id: &AccountId) -> Result<Option<Account>, DomainError> {
+            unimplemented!()
+        }
+
+        async fn find_by_ids(&self, _ids: &[AccountId]) -> Result<Vec<Account>, DomainError> {
+            unimplemented!()
+        }
+
+        async fn save(&self, _account: &Account) -> Result<(), DomainError> {
+            unimplemented!()
+        }
+
+        async fn save_in_transaction(
+            &self,
+            _tx: &mut neuradock_domain::shared::DynTransactionContext,
+            _account: &Account,
+        ) -> Result<(), DomainError> {
+            unimplemented!()
+        }
+
+        async fn delete(&self, _id: &AccountId) -> Result<(), DomainError> {
+            unimplemented!()
+        }
+
+        async fn soft_delete(
+            &self,
+            _id: &AccountId,
+            _deleted_at: DateTime<Utc>,
+        ) -> Result<(), DomainError> {
+            unimplemented!()
+        }
+
+        async fn restore_deleted(&self, _id: &AccountId) -> Result<bool, DomainError> {
+            unimplemented!()
+        }
+
+        async fn purge_deleted(
+            &self,
+            _deleted_before: DateTime<Utc>,
+        ) -> Result<Vec<(AccountId, String)>, DomainError> {
+            unimplemented!()
+        }
+
+        async fn record_check_in_attempt(
+            &self,
+            _id: &AccountId,
+            _day: NaiveDate,
+        ) -> Result<u32, DomainError> {
+            unimplemented!()
+        }
+    }
+
+    struct EmptyProviderRepository;
+
+    #[async_trait]
+    impl ProviderRepository for EmptyProviderRepository {
+        async fn save(&self, _provider: &Provider) -> Result<(), DomainError> {
+            unimplemented!()
+        }
+
+        async fn find_by_id(&self, _id: &ProviderId) -> Result<Option<Provider>, DomainError> {
+            Ok(None)
+        }
+
+        async fn find_all(&self) -> Result<Vec<Provider>, DomainError> {
+            Ok(Vec::new())
+        }
+
+        async fn delete(&self, _id: &ProviderId) -> Result<(), DomainError> {
+            unimplemented!()
+        }
+    }
+
+    /// No history, the accounts under test carry cached balances
+    struct EmptyBalanceHistoryRepository;
+
+    #[async_trait]
+    impl BalanceHistoryRepository for EmptyBalanceHistoryRepository {
+        async fn save(&self, _record: &BalanceHistoryRecord) -> Result<(), DomainError> {
+            unimplemented!()
+        }
+
+        async fn upsert_daily_snapshot(
+            &self,
+            _account_id: &AccountId,
+            _current_balance: f64,
+            _total_consumed: f64,
+            _total_quota: f64,
+            _recorded_at: DateTime<Utc>,
+        ) -> Result<BalanceHistoryRecord, DomainError> {
+            unimplemented!()
+        }
+
+        async fn find_latest_by_account_id(
+            &self,
+            _account_id: &AccountId,
+        ) -> Result<Option<BalanceHistoryRecord>, DomainError> {
+            Ok(None)
+        }
+
+        async fn find_latest_by_account_id_on_date(
+            &self,
+            _account_id: &AccountId,
+            _date: NaiveDate,
+        ) -> Result<Option<BalanceHistoryRecord>, DomainError> {
+            unimplemented!()
+        }
+
+        async fn list_all_daily_summaries(
+            &self,
+            _account_id: &AccountId,
+        ) -> Result<Vec<BalanceHistoryDailySummary>, DomainError> {
+            unimplemented!()
+        }
+
+        async fn list_daily_summaries_in_range(
+            &self,
+            _account_id: &AccountId,
+            _start_date: NaiveDate,
+            _end_date: NaiveDate,
+        ) -> Result<Vec<BalanceHistoryDailySummary>, DomainError> {
+            unimplemented!()
+        }
+
+        async fn list_daily_summaries_for_accounts_in_range(
+            &self,
+            _account_ids: &[AccountId],
+            _start_date: NaiveDate,
+            _end_date: NaiveDate,
+        ) -> Result<HashMap<AccountId, Vec<BalanceHistoryDailySummary>>, DomainError> {
+            unimplemented!()
+        }
+
+        async fn list_provider_daily_totals_in_range(
+            &self,
+            _start_date: NaiveDate,
+            _end_date: NaiveDate,
+        ) -> Result<Vec<ProviderDailyBalanceTotal>, DomainError> {
+            unimplemented!()
+        }
+
+        async fn find_daily_summary(
+            &self,
+            _account_id: &AccountId,
+            _date: NaiveDate,
+        ) -> Result<Option<BalanceHistoryDailySummary>, DomainError> {
+            unimplemented!()
+        }
+
+        async fn list_distinct_account_ids(&self) -> Result<Vec<AccountId>, DomainError> {
+            unimplemented!()
+        }
+
+        async fn find_streak_reset(
+            &self,
+            _account_id: &AccountId,
+        ) -> Result<Option<NaiveDate>, DomainError> {
+            unimplemented!()
+        }
+
+        async fn set_streak_reset(
+            &self,
+            _account_id: &AccountId,
+            _reset_on: Option<NaiveDate>,
+        ) -> Result<(), DomainError> {
+            unimplemented!()
+        }
+
+        async fn clear_streak_resets(&self) -> Result<(), DomainError> {
+            unimplemented!()
+        }
+    }
+
+    fn account_with_balance(name: &str, enabled: bool, balance: f64) -> Account {
+        let mut account = Account::new(
+            name.to_string(),
+            ProviderId::from_string("provider"),
+            Credentials::new(
+                HashMap::from([("session".to_string(), "test_session".to_string())]),
+                "api_user".to_string(),
+            ),
+        )
+        .unwrap();
+        account.update_balance(balance, 1.0, balance + 1.0);
+        if !enabled {
+            account.toggle(false);
+        }
+        account
+    }
+
+    fn service(accounts: Vec<Account>) -> BalanceStatisticsQueryService {
+        let balance_history_repo: Arc<dyn BalanceHistoryRepository> =
+            Arc::new(EmptyBalanceHistoryRepository);
+        BalanceStatisticsQueryService::new(
+            Arc::new(MockAccountRepository { accounts }),
+            Arc::new(EmptyProviderRepository),
+            balance_history_repo.clone(),
+            Arc::new(BalanceHistoryService::new(balance_history_repo)),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_disabled_account_counted_only_when_included() {
+        let service = service(vec![
+            account_with_balance("Enabled", true, 10.0),
+            account_with_balance("Disabled", false, 5.0),
+        ]);
+
+        let enabled_only = service.get_balance_statistics(false).await.unwrap();
+        assert_eq!(enabled_only.total_current_balance, 10.0);
+        assert_eq!(enabled_only.providers[0].account_count, 1);
+
+        let all = service.get_balance_statistics(true).await.unwrap();
+        assert_eq!(all.total_current_balance, 15.0);
+        assert_eq!(all.total_quota, 17.0);
+        assert_eq!(all.providers[0].account_count, 2);
+    }
+}
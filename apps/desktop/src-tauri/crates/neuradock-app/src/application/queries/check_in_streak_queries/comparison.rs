@@ -0,0 +1,202 @@
+use chrono::{Duration, NaiveDate};
+use log::info;
+use std::collections::HashMap;
+
+use crate::application::dtos::{AccountComparisonSeriesDto, AccountsComparisonDto};
+use neuradock_domain::account::AccountRepository;
+use neuradock_domain::balance_history::{BalanceHistoryDailySummary, BalanceHistoryRepository};
+use neuradock_domain::shared::{AccountId, DomainError};
+
+use super::streak::compute_streak;
+use super::types::StreakFreezes;
+
+/// Most accounts compared at once
+const MAX_COMPARED_ACCOUNTS: usize = 10;
+
+/// Series of one account aligned on the comparison dates
+#[derive(Debug, Default, PartialEq)]
+struct AlignedSeries {
+    total_quota: Vec<Option<f64>>,
+    current_balance: Vec<Option<f64>>,
+    income_increment: Vec<Option<f64>>,
+}
+
+/// Compare the last `days` days of several accounts
+pub async fn get_accounts_comparison(
+    account_repo: &dyn AccountRepository,
+    balance_history_repo: &dyn BalanceHistoryRepository,
+    account_ids: &[String],
+    days: u32,
+    freezes: StreakFreezes,
+) -> Result<AccountsComparisonDto, DomainError> {
+    if days == 0 || days > 365 {
+        return Err(DomainError::Validation(
+            "Days must be between 1 and 365".to_string(),
+        ));
+    }
+
+    let mut ids: Vec<AccountId> = Vec::new();
+    for id in account_ids {
+        let id = AccountId::from_string(id);
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+    if ids.is_empty() || ids.len() > MAX_COMPARED_ACCOUNTS {
+        return Err(DomainError::Validation(format!(
+            "Select between 1 and {} accounts to compare",
+            MAX_COMPARED_ACCOUNTS
+        )));
+    }
+
+    let names: HashMap<AccountId, String> = account_repo
+        .find_by_ids(&ids)
+        .await?
+        .into_iter()
+        .map(|account| (account.id().clone(), account.name().to_string()))
+        .collect();
+    if let Some(missing) = ids.iter().find(|id| !names.contains_key(*id)) {
+        return Err(DomainError::AccountNotFound(missing.as_str().to_string()));
+    }
+
+    let end_date = freezes.today;
+    let start_date = end_date - Duration::days(days as i64 - 1);
+    let dates = date_range(start_date, end_date);
+
+    let mut summaries = balance_history_repo
+        .list_daily_summaries_for_accounts_in_range(&ids, start_date, end_date)
+        .await?;
+
+    let mut accounts = Vec::with_capacity(ids.len());
+    for id in &ids {
+        let rows = summaries.remove(id).unwrap_or_default();
+        let series = align_series(&dates, &rows);
+        let streak = compute_streak(balance_history_repo, id.as_str(), freezes).await?;
+
+        accounts.push(AccountComparisonSeriesDto {
+            account_id: id.as_str().to_string(),
+            account_name: names.get(id).cloned().unwrap_or_default(),
+            current_streak: streak.current_streak,
+            longest_streak: streak.longest_streak,
+            total_quota: series.total_quota,
+            current_balance: series.current_balance,
+            income_increment: series.income_increment,
+        });
+    }
+
+    info!(
+        "[streak] comparison accounts={} range={}~{}",
+        accounts.len(),
+        start_date,
+        end_date
+    );
+
+    Ok(AccountsComparisonDto {
+        start_date: start_date.format("%Y-%m-%d").to_string(),
+        end_date: end_date.format("%Y-%m-%d").to_string(),
+        dates: dates
+            .iter()
+            .map(|date| date.format("%Y-%m-%d").to_string())
+            .collect(),
+        accounts,
+    })
+}
+
+/// Every day from `start` to `end`, both inclusive
+fn date_range(start: NaiveDate, end: NaiveDate) -> Vec<NaiveDate> {
+    start.iter_days().take_while(|date| *date <= end).collect()
+}
+
+/// Place `rows` on `dates`, `None` where the account has no history
+///
+/// The increment compares against the previous recorded day of the range, so
+/// the first record of an account has none.
+fn align_series(dates: &[NaiveDate], rows: &[BalanceHistoryDailySummary]) -> AlignedSeries {
+    let by_date: HashMap<NaiveDate, &BalanceHistoryDailySummary> =
+        rows.iter().map(|row| (row.check_in_date(), row)).collect();
+
+    let mut series = AlignedSeries::default();
+    let mut prev_quota: Option<f64> = None;
+    for date in dates {
+        match by_date.get(date) {
+            Some(row) => {
+                let quota = row.daily_total_quota();
+                series.total_quota.push(Some(quota));
+                series.current_balance.push(Some(row.daily_balance()));
+                series
+                    .income_increment
+                    .push(prev_quota.map(|prev| (quota - prev).max(0.0)));
+                prev_quota = Some(quota);
+            }
+            None => {
+                series.total_quota.push(None);
+                series.current_balance.push(None);
+                series.income_increment.push(None);
+            }
+        }
+    }
+    series
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, day).unwrap()
+    }
+
+    fn summary(day: u32, total_quota: f64) -> BalanceHistoryDailySummary {
+        BalanceHistoryDailySummary::restore(date(day), total_quota, total_quota / 2.0, 0.0)
+    }
+
+    #[test]
+    fn test_accounts_with_different_start_dates_align() {
+        let dates = date_range(date(1), date(5));
+        // One account has history from the 1st, the other only from the 4th
+        let long = align_series(
+            &dates,
+            &[
+                summary(1, 10.0),
+                summary(2, 20.0),
+                summary(3, 20.0),
+                summary(4, 30.0),
+                summary(5, 40.0),
+            ],
+        );
+        let short = align_series(&dates, &[summary(4, 100.0), summary(5, 125.0)]);
+
+        assert_eq!(dates.len(), 5);
+        assert_eq!(long.total_quota.len(), dates.len());
+        assert_eq!(short.total_quota.len(), dates.len());
+        assert_eq!(
+            long.income_increment,
+            vec![None, Some(10.0), Some(0.0), Some(10.0), Some(10.0)]
+        );
+        assert_eq!(
+            short.total_quota,
+            vec![None, None, None, Some(100.0), Some(125.0)]
+        );
+        assert_eq!(
+            short.current_balance,
+            vec![None, None, None, Some(50.0), Some(62.5)]
+        );
+        assert_eq!(
+            short.income_increment,
+            vec![None, None, None, None, Some(25.0)]
+        );
+    }
+
+    #[test]
+    fn test_gaps_and_missing_history_are_null() {
+        let dates = date_range(date(1), date(4));
+
+        let gap = align_series(&dates, &[summary(1, 10.0), summary(4, 25.0)]);
+        assert_eq!(gap.total_quota, vec![Some(10.0), None, None, Some(25.0)]);
+        assert_eq!(gap.income_increment, vec![None, None, None, Some(15.0)]);
+
+        let empty = align_series(&dates, &[]);
+        assert_eq!(empty.total_quota, vec![None; 4]);
+        assert_eq!(empty.income_increment, vec![None; 4]);
+    }
+}
@@ -0,0 +1,194 @@
+use chrono::{Datelike, Duration, NaiveDate};
+use log::info;
+
+use crate::application::dtos::{CheckInConsistencyDto, ConsistencyPeriod};
+use neuradock_domain::balance_history::{BalanceHistoryDailySummary, BalanceHistoryRepository};
+use neuradock_domain::shared::DomainError;
+
+use super::helpers;
+
+/// Get how many days of `period` the account checked in on
+pub async fn get_consistency(
+    balance_history_repo: &dyn BalanceHistoryRepository,
+    account_id: &str,
+    period: &ConsistencyPeriod,
+    today: NaiveDate,
+) -> Result<CheckInConsistencyDto, DomainError> {
+    let (start_date, end_date) = period_range(period, today)?;
+
+    // The whole history, so the first day of the period compares against the day before it
+    let rows = helpers::fetch_all_daily_summaries(balance_history_repo, account_id).await?;
+    let (checked_days, total_days) = count_checked_days(&rows, start_date, end_date, today);
+
+    let percentage = if total_days > 0 {
+        (checked_days as f64 / total_days as f64) * 100.0
+    } else {
+        0.0
+    };
+
+    info!(
+        "[streak] consistency account_id={} range={}~{} checked={}/{}",
+        account_id, start_date, end_date, checked_days, total_days
+    );
+
+    Ok(CheckInConsistencyDto {
+        account_id: account_id.to_string(),
+        start_date: start_date.format("%Y-%m-%d").to_string(),
+        end_date: end_date.format("%Y-%m-%d").to_string(),
+        checked_days,
+        total_days,
+        percentage,
+    })
+}
+
+/// First and last day of `period`, both inclusive
+fn period_range(
+    period: &ConsistencyPeriod,
+    today: NaiveDate,
+) -> Result<(NaiveDate, NaiveDate), DomainError> {
+    match period {
+        ConsistencyPeriod::Week => {
+            let start = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+            Ok((start, start + Duration::days(6)))
+        }
+        ConsistencyPeriod::Month => {
+            let start = today.with_day(1).unwrap_or(today);
+            let next_month = if start.month() == 12 {
+                NaiveDate::from_ymd_opt(start.year() + 1, 1, 1)
+            } else {
+                NaiveDate::from_ymd_opt(start.year(), start.month() + 1, 1)
+            };
+            let end = next_month
+                .and_then(|d| d.pred_opt())
+                .ok_or_else(|| DomainError::Validation("Invalid date".to_string()))?;
+            Ok((start, end))
+        }
+        ConsistencyPeriod::Custom {
+            start_date,
+            end_date,
+        } => {
+            let parse = |date: &str| {
+                NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|_| {
+                    DomainError::Validation(format!("Invalid date: {} (expected YYYY-MM-DD)", date))
+                })
+            };
+            let (start, end) = (parse(start_date)?, parse(end_date)?);
+            if start > end {
+                return Err(DomainError::Validation(
+                    "Start date must not be after end date".to_string(),
+                ));
+            }
+            Ok((start, end))
+        }
+    }
+}
+
+/// Checked-in days and elapsed days of `start..=end`
+///
+/// Days after `today` have not happened yet, so a current period only counts
+/// the days elapsed so far. A day is checked in when its quota grew over the
+/// previous recorded day, or when it is the first recorded day.
+fn count_checked_days(
+    rows: &[BalanceHistoryDailySummary],
+    start: NaiveDate,
+    end: NaiveDate,
+    today: NaiveDate,
+) -> (u32, u32) {
+    let end = end.min(today);
+    if start > end {
+        return (0, 0);
+    }
+    let total_days = (end - start).num_days() as u32 + 1;
+
+    let mut prev_income: Option<f64> = None;
+    let mut checked_days = 0u32;
+    for row in rows {
+        let date = row.check_in_date();
+        if date > end {
+            break;
+        }
+
+        let is_checked_in = prev_income.is_none_or(|prev| row.daily_total_quota() > prev);
+        if is_checked_in && date >= start {
+            checked_days += 1;
+        }
+        prev_income = Some(row.daily_total_quota());
+    }
+
+    (checked_days, total_days)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, month, day).unwrap()
+    }
+
+    /// A check-in on each of `days` of `month`, the quota growing with every one
+    fn check_ins(month: u32, days: &[u32]) -> Vec<BalanceHistoryDailySummary> {
+        days.iter()
+            .enumerate()
+            .map(|(i, day)| {
+                BalanceHistoryDailySummary::restore(
+                    date(month, *day),
+                    10.0 * (i + 1) as f64,
+                    5.0,
+                    1.0,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_full_past_month() {
+        // Checked in every day of March but the 5th, 12th, 19th, 26th and 30th
+        let days: Vec<u32> = (1..=31)
+            .filter(|day| ![5, 12, 19, 26, 30].contains(day))
+            .collect();
+        let rows = check_ins(3, &days);
+        let (start, end) =
+            period_range(&ConsistencyPeriod::Month, date(3, 15)).expect("month range");
+
+        let counted = count_checked_days(&rows, start, end, date(4, 10));
+
+        assert_eq!((start, end), (date(3, 1), date(3, 31)));
+        assert_eq!(counted, (26, 31));
+    }
+
+    #[test]
+    fn test_partial_current_month_counts_elapsed_days() {
+        let mut rows = check_ins(2, &[27, 28]);
+        let mut march = check_ins(3, &[1, 2, 3, 5, 6, 7, 8, 10]);
+        // March quotas continue from February's
+        for row in &mut march {
+            *row = BalanceHistoryDailySummary::restore(
+                row.check_in_date(),
+                row.daily_total_quota() + 20.0,
+                5.0,
+                1.0,
+            );
+        }
+        rows.extend(march);
+        let today = date(3, 10);
+        let (start, end) = period_range(&ConsistencyPeriod::Month, today).expect("month range");
+
+        let counted = count_checked_days(&rows, start, end, today);
+
+        assert_eq!(counted, (8, 10));
+    }
+
+    #[test]
+    fn test_week_and_custom_ranges() {
+        // 2026-03-11 is a Wednesday
+        let (start, end) = period_range(&ConsistencyPeriod::Week, date(3, 11)).expect("week range");
+        assert_eq!((start, end), (date(3, 9), date(3, 15)));
+
+        let custom = ConsistencyPeriod::Custom {
+            start_date: "2026-03-10".to_string(),
+            end_date: "2026-03-01".to_string(),
+        };
+        assert!(period_range(&custom, date(3, 11)).is_err());
+    }
+}
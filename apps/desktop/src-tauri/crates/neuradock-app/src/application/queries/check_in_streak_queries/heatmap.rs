@@ -0,0 +1,134 @@
+use chrono::{Datelike, NaiveDate};
+use log::info;
+use std::collections::HashMap;
+
+use crate::application::dtos::CheckInYearHeatmapDto;
+use neuradock_domain::balance_history::{BalanceHistoryDailySummary, BalanceHistoryRepository};
+use neuradock_domain::shared::{AccountId, DomainError};
+
+/// Highest heatmap intensity
+const MAX_INTENSITY: u8 = 4;
+
+/// Get a year of check-ins as one intensity per day
+pub async fn get_year_heatmap(
+    balance_history_repo: &dyn BalanceHistoryRepository,
+    account_id: &str,
+    year: i32,
+) -> Result<CheckInYearHeatmapDto, DomainError> {
+    let first_day = NaiveDate::from_ymd_opt(year, 1, 1)
+        .ok_or_else(|| DomainError::Validation("Invalid year".to_string()))?;
+    let last_day = NaiveDate::from_ymd_opt(year, 12, 31)
+        .ok_or_else(|| DomainError::Validation("Invalid year".to_string()))?;
+
+    let rows = balance_history_repo
+        .list_daily_summaries_in_range(&AccountId::from_string(account_id), first_day, last_day)
+        .await?;
+
+    let (intensities, max_increment) = year_intensities(&rows, year);
+    let checked_days = intensities.iter().filter(|&&level| level > 0).count() as u32;
+
+    info!(
+        "[streak] heatmap account_id={} year={} rows={} checked_days={}",
+        account_id,
+        year,
+        rows.len(),
+        checked_days
+    );
+
+    Ok(CheckInYearHeatmapDto {
+        account_id: account_id.to_string(),
+        year,
+        intensities,
+        checked_days,
+        max_increment,
+    })
+}
+
+/// One intensity per day of `year` and the largest quota increment
+///
+/// Like the monthly calendar, a day is checked in when its quota grew over the
+/// previous recorded day of the range, or when it is the first recorded day.
+/// Checked days are scaled 1-4 by their increment against the best day.
+fn year_intensities(rows: &[BalanceHistoryDailySummary], year: i32) -> (Vec<u8>, f64) {
+    let days_in_year = if NaiveDate::from_ymd_opt(year, 2, 29).is_some() {
+        366
+    } else {
+        365
+    };
+
+    // Quota gained per checked-in day, keyed by day of year (0-based)
+    let mut increments: HashMap<usize, f64> = HashMap::new();
+    let mut prev_income: Option<f64> = None;
+    for row in rows {
+        let date = row.check_in_date();
+        if date.year() != year {
+            continue;
+        }
+
+        let increment = match prev_income {
+            None => Some(row.daily_total_quota().max(0.0)),
+            Some(prev) if row.daily_total_quota() > prev => Some(row.daily_total_quota() - prev),
+            Some(_) => None,
+        };
+        if let Some(increment) = increment {
+            increments.insert(date.ordinal0() as usize, increment);
+        }
+        prev_income = Some(row.daily_total_quota());
+    }
+
+    let max_increment = increments.values().copied().fold(0.0, f64::max);
+    let mut intensities = vec![0u8; days_in_year];
+    for (day, increment) in increments {
+        intensities[day] = if max_increment > 0.0 {
+            let scaled = (increment / max_increment * MAX_INTENSITY as f64).ceil() as u8;
+            scaled.clamp(1, MAX_INTENSITY)
+        } else {
+            1
+        };
+    }
+
+    (intensities, max_increment)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(year: i32, month: u32, day: u32, total_quota: f64) -> BalanceHistoryDailySummary {
+        BalanceHistoryDailySummary::restore(
+            NaiveDate::from_ymd_opt(year, month, day).unwrap(),
+            total_quota,
+            0.0,
+            0.0,
+        )
+    }
+
+    #[test]
+    fn test_day_count_handles_leap_years() {
+        assert_eq!(year_intensities(&[], 2024).0.len(), 366);
+        assert_eq!(year_intensities(&[], 2026).0.len(), 365);
+        assert_eq!(year_intensities(&[], 2000).0.len(), 366);
+        assert_eq!(year_intensities(&[], 1900).0.len(), 365);
+    }
+
+    #[test]
+    fn test_checked_days_map_to_intensities() {
+        let rows = vec![
+            summary(2024, 1, 1, 10.0),   // first record, +10
+            summary(2024, 1, 2, 50.0),   // +40, the best day
+            summary(2024, 1, 3, 50.0),   // no growth
+            summary(2024, 2, 29, 70.0),  // +20
+            summary(2024, 12, 31, 75.0), // +5
+        ];
+
+        let (intensities, max_increment) = year_intensities(&rows, 2024);
+
+        assert_eq!(max_increment, 40.0);
+        assert_eq!(intensities[0], 1);
+        assert_eq!(intensities[1], 4);
+        assert_eq!(intensities[2], 0);
+        assert_eq!(intensities[59], 2); // Feb 29th
+        assert_eq!(intensities[365], 1); // Dec 31st of a leap year
+        assert_eq!(intensities.iter().filter(|&&level| level > 0).count(), 4);
+    }
+}
@@ -1,23 +1,32 @@
+use chrono::Utc;
 use std::sync::Arc;
 
 use crate::application::dtos::{
-    CheckInCalendarDto, CheckInDayDto, CheckInStreakDto, CheckInTrendDto,
+    AccountsComparisonDto, CheckInCalendarDto, CheckInConsistencyDto, CheckInDayDto,
+    CheckInStreakDto, CheckInTrendDto, CheckInYearHeatmapDto, ConsistencyPeriod,
 };
+use crate::application::services::ConfigService;
 use neuradock_domain::account::AccountRepository;
 use neuradock_domain::balance_history::BalanceHistoryRepository;
 use neuradock_domain::check_in::ProviderRepository;
 use neuradock_domain::shared::DomainError;
 
 mod calendar;
+mod comparison;
+mod consistency;
+mod heatmap;
 mod helpers;
 mod streak;
 mod trend;
 mod types;
 
+use types::StreakFreezes;
+
 pub struct CheckInStreakQueries {
     account_repo: Arc<dyn AccountRepository>,
     provider_repo: Arc<dyn ProviderRepository>,
     balance_history_repo: Arc<dyn BalanceHistoryRepository>,
+    config: Arc<ConfigService>,
 }
 
 impl CheckInStreakQueries {
@@ -25,11 +34,21 @@ impl CheckInStreakQueries {
         account_repo: Arc<dyn AccountRepository>,
         provider_repo: Arc<dyn ProviderRepository>,
         balance_history_repo: Arc<dyn BalanceHistoryRepository>,
+        config: Arc<ConfigService>,
     ) -> Self {
         Self {
             account_repo,
             provider_repo,
             balance_history_repo,
+            config,
+        }
+    }
+
+    /// Freezes configured for streaks computed now
+    fn freezes(&self) -> StreakFreezes {
+        StreakFreezes {
+            per_month: self.config.get_streak_freezes_per_month(),
+            today: Utc::now().date_naive(),
         }
     }
 
@@ -43,6 +62,7 @@ impl CheckInStreakQueries {
             self.provider_repo.as_ref(),
             self.balance_history_repo.as_ref(),
             account_id,
+            self.freezes(),
         )
         .await
     }
@@ -53,6 +73,23 @@ impl CheckInStreakQueries {
             self.account_repo.as_ref(),
             self.provider_repo.as_ref(),
             self.balance_history_repo.as_ref(),
+            self.freezes(),
+        )
+        .await
+    }
+
+    /// Get aligned per-day series and streaks of several accounts over the last `days` days
+    pub async fn get_accounts_comparison(
+        &self,
+        account_ids: &[String],
+        days: u32,
+    ) -> Result<AccountsComparisonDto, DomainError> {
+        comparison::get_accounts_comparison(
+            self.account_repo.as_ref(),
+            self.balance_history_repo.as_ref(),
+            account_ids,
+            days,
+            self.freezes(),
         )
         .await
     }
@@ -67,6 +104,15 @@ impl CheckInStreakQueries {
         calendar::get_calendar(self.balance_history_repo.as_ref(), account_id, year, month).await
     }
 
+    /// Get a whole year of check-ins for a heatmap
+    pub async fn get_year_heatmap(
+        &self,
+        account_id: &str,
+        year: i32,
+    ) -> Result<CheckInYearHeatmapDto, DomainError> {
+        heatmap::get_year_heatmap(self.balance_history_repo.as_ref(), account_id, year).await
+    }
+
     /// Get check-in trend data (last N days)
     pub async fn get_trend(
         &self,
@@ -76,6 +122,21 @@ impl CheckInStreakQueries {
         trend::get_trend(self.balance_history_repo.as_ref(), account_id, days).await
     }
 
+    /// Get how many days of a week, month or custom range were checked in
+    pub async fn get_consistency(
+        &self,
+        account_id: &str,
+        period: &ConsistencyPeriod,
+    ) -> Result<CheckInConsistencyDto, DomainError> {
+        consistency::get_consistency(
+            self.balance_history_repo.as_ref(),
+            account_id,
+            period,
+            Utc::now().date_naive(),
+        )
+        .await
+    }
+
     /// Get details for a specific day
     pub async fn get_day_detail(
         &self,
@@ -85,8 +146,24 @@ impl CheckInStreakQueries {
         trend::get_day_detail(self.balance_history_repo.as_ref(), account_id, date).await
     }
 
-    /// Recalculate all streaks from balance_history
+    /// Recalculate all streaks from balance_history, dropping manual resets
     pub async fn recalculate_all_streaks(&self) -> Result<(), DomainError> {
         streak::recalculate_all_streaks(self.balance_history_repo.as_ref()).await
     }
+
+    /// Recalculate one account's streak from balance_history, dropping its manual reset
+    pub async fn recalculate_streak(
+        &self,
+        account_id: &str,
+    ) -> Result<CheckInStreakDto, DomainError> {
+        streak::recalculate_streak(self.balance_history_repo.as_ref(), account_id).await?;
+        self.get_streak_stats(account_id).await
+    }
+
+    /// Start one account's streak over from today
+    pub async fn reset_streak(&self, account_id: &str) -> Result<CheckInStreakDto, DomainError> {
+        let today = Utc::now().date_naive();
+        streak::reset_streak(self.balance_history_repo.as_ref(), account_id, today).await?;
+        self.get_streak_stats(account_id).await
+    }
 }
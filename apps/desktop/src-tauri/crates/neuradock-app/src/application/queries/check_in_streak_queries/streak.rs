@@ -1,14 +1,15 @@
-use chrono::NaiveDate;
+use chrono::{Datelike, NaiveDate};
 use log::{info, warn};
+use std::collections::HashMap;
 
 use crate::application::dtos::CheckInStreakDto;
 use neuradock_domain::account::AccountRepository;
 use neuradock_domain::balance_history::{BalanceHistoryDailySummary, BalanceHistoryRepository};
 use neuradock_domain::check_in::ProviderRepository;
-use neuradock_domain::shared::DomainError;
+use neuradock_domain::shared::{AccountId, DomainError};
 
 use super::helpers;
-use super::types::StreakComputation;
+use super::types::{StreakComputation, StreakFreezes};
 
 /// Get streak statistics for a single account
 pub async fn get_streak_stats(
@@ -16,11 +17,11 @@ pub async fn get_streak_stats(
     provider_repo: &dyn ProviderRepository,
     balance_history_repo: &dyn BalanceHistoryRepository,
     account_id: &str,
+    freezes: StreakFreezes,
 ) -> Result<CheckInStreakDto, DomainError> {
     // Get account metadata and calculate streak from raw data
     let account_info = helpers::get_account_info(account_repo, provider_repo, account_id).await?;
-    let daily_rows = helpers::fetch_all_daily_summaries(balance_history_repo, account_id).await?;
-    let streak = calculate_streak_stats(account_id, &daily_rows);
+    let streak = compute_streak(balance_history_repo, account_id, freezes).await?;
 
     let dto = CheckInStreakDto {
         account_id: account_id.to_string(),
@@ -33,6 +34,7 @@ pub async fn get_streak_stats(
         last_check_in_date: streak
             .last_check_in_date
             .map(|d| d.format("%Y-%m-%d").to_string()),
+        freezes_remaining: streak.freezes_remaining,
     };
 
     info!(
@@ -48,14 +50,13 @@ pub async fn get_all_streaks(
     account_repo: &dyn AccountRepository,
     provider_repo: &dyn ProviderRepository,
     balance_history_repo: &dyn BalanceHistoryRepository,
+    freezes: StreakFreezes,
 ) -> Result<Vec<CheckInStreakDto>, DomainError> {
     let accounts = helpers::get_all_account_infos(account_repo, provider_repo).await?;
     let mut results = Vec::new();
 
     for account in accounts {
-        let daily_rows =
-            helpers::fetch_all_daily_summaries(balance_history_repo, &account.account_id).await?;
-        let streak = calculate_streak_stats(&account.account_id, &daily_rows);
+        let streak = compute_streak(balance_history_repo, &account.account_id, freezes).await?;
 
         results.push(CheckInStreakDto {
             account_id: account.account_id,
@@ -68,6 +69,7 @@ pub async fn get_all_streaks(
             last_check_in_date: streak
                 .last_check_in_date
                 .map(|d| d.format("%Y-%m-%d").to_string()),
+            freezes_remaining: streak.freezes_remaining,
         });
     }
 
@@ -77,10 +79,11 @@ pub async fn get_all_streaks(
     Ok(results)
 }
 
-/// Recalculate all streaks from balance_history
+/// Recalculate all streaks from balance_history, dropping manual resets
 pub async fn recalculate_all_streaks(
     balance_history_repo: &dyn BalanceHistoryRepository,
 ) -> Result<(), DomainError> {
+    balance_history_repo.clear_streak_resets().await?;
     let account_ids = balance_history_repo.list_distinct_account_ids().await?;
 
     info!(
@@ -91,11 +94,63 @@ pub async fn recalculate_all_streaks(
     Ok(())
 }
 
+/// Recalculate one account's streak from balance_history, dropping its manual reset
+pub async fn recalculate_streak(
+    balance_history_repo: &dyn BalanceHistoryRepository,
+    account_id: &str,
+) -> Result<(), DomainError> {
+    balance_history_repo
+        .set_streak_reset(&AccountId::from_string(account_id), None)
+        .await?;
+
+    info!("[streak] recalc_streak account_id={}", account_id);
+    Ok(())
+}
+
+/// Start one account's streak over, check-ins up to `today` no longer count
+pub async fn reset_streak(
+    balance_history_repo: &dyn BalanceHistoryRepository,
+    account_id: &str,
+    today: NaiveDate,
+) -> Result<(), DomainError> {
+    balance_history_repo
+        .set_streak_reset(&AccountId::from_string(account_id), Some(today))
+        .await?;
+
+    info!(
+        "[streak] reset_streak account_id={} reset_on={}",
+        account_id, today
+    );
+    Ok(())
+}
+
+/// Streak of an account from its history, after its manual reset if any
+pub(super) async fn compute_streak(
+    balance_history_repo: &dyn BalanceHistoryRepository,
+    account_id: &str,
+    freezes: StreakFreezes,
+) -> Result<StreakComputation, DomainError> {
+    let daily_rows = helpers::fetch_all_daily_summaries(balance_history_repo, account_id).await?;
+    let reset_on = balance_history_repo
+        .find_streak_reset(&AccountId::from_string(account_id))
+        .await?;
+    Ok(calculate_streak_stats(
+        account_id,
+        &daily_rows,
+        reset_on,
+        freezes,
+    ))
+}
+
 fn calculate_streak_stats(
     account_id: &str,
     rows: &[BalanceHistoryDailySummary],
+    reset_on: Option<NaiveDate>,
+    freezes: StreakFreezes,
 ) -> StreakComputation {
     let mut prev_income: Option<f64> = None;
+    // Freezes used per (year, month) of the missed day
+    let mut freezes_used: HashMap<(i32, u32), u32> = HashMap::new();
     let mut current_streak = 0u32;
     let mut longest_streak = 0u32;
     let mut total_check_in_days = 0u32;
@@ -110,6 +165,12 @@ fn calculate_streak_stats(
 
     for row in rows {
         let date = row.check_in_date();
+        // Days up to a reset only serve as the quota baseline of the next day
+        if reset_on.is_some_and(|reset_on| date <= reset_on) {
+            prev_income = Some(row.daily_total_quota());
+            continue;
+        }
+
         let is_checked_in = prev_income.is_none_or(|prev| row.daily_total_quota() > prev);
 
         if is_checked_in {
@@ -121,6 +182,19 @@ fn calculate_streak_stats(
                         current_streak + 1
                     }
                 }
+                // A single missed day keeps the streak when its month has a freeze left
+                Some(prev_date) if (date - prev_date).num_days() == 2 && current_streak > 0 => {
+                    let missed = prev_date.succ_opt().unwrap_or(prev_date);
+                    let used = freezes_used
+                        .entry((missed.year(), missed.month()))
+                        .or_default();
+                    if *used < freezes.per_month {
+                        *used += 1;
+                        current_streak + 1
+                    } else {
+                        1
+                    }
+                }
                 _ => 1,
             };
 
@@ -136,10 +210,255 @@ fn calculate_streak_stats(
         prev_income = Some(row.daily_total_quota());
     }
 
+    let used_this_month = freezes_used
+        .get(&(freezes.today.year(), freezes.today.month()))
+        .copied()
+        .unwrap_or(0);
+
     StreakComputation {
         current_streak,
         longest_streak,
         total_check_in_days,
         last_check_in_date,
+        freezes_remaining: freezes.per_month.saturating_sub(used_this_month),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use chrono::{DateTime, Utc};
+    use neuradock_domain::balance_history::{BalanceHistoryRecord, ProviderDailyBalanceTotal};
+    use std::collections::HashMap;
+    use std::sync::RwLock;
+
+    /// History of a few accounts with in-memory streak resets
+    struct MockBalanceHistoryRepository {
+        summaries: HashMap<String, Vec<BalanceHistoryDailySummary>>,
+        resets: RwLock<HashMap<String, NaiveDate>>,
+    }
+
+    #[async_trait]
+    impl BalanceHistoryRepository for MockBalanceHistoryRepository {
+        async fn save(&self, _record: &BalanceHistoryRecord) -> Result<(), DomainError> {
+            unimplemented!()
+        }
+
+        async fn upsert_daily_snapshot(
+            &self,
+            _account_id: &AccountId,
+            _current_balance: f64,
+            _total_consumed: f64,
+            _total_quota: f64,
+            _recorded_at: DateTime<Utc>,
+        ) -> Result<BalanceHistoryRecord, DomainError> {
+            unimplemented!()
+        }
+
+        async fn find_latest_by_account_id(
+            &self,
+            _account_id: &AccountId,
+        ) -> Result<Option<BalanceHistoryRecord>, DomainError> {
+            unimplemented!()
+        }
+
+        async fn find_latest_by_account_id_on_date(
+            &self,
+            _account_id: &AccountId,
+            _date: NaiveDate,
+        ) -> Result<Option<BalanceHistoryRecord>, DomainError> {
+            unimplemented!()
+        }
+
+        async fn list_all_daily_summaries(
+            &self,
+            account_id: &AccountId,
+        ) -> Result<Vec<BalanceHistoryDailySummary>, DomainError> {
+            Ok(self
+                .summaries
+                .get(account_id.as_str())
+                .cloned()
+                .unwrap_or_default())
+        }
+
+        async fn list_daily_summaries_in_range(
+            &self,
+            _account_id: &AccountId,
+            _start_date: NaiveDate,
+            _end_date: NaiveDate,
+        ) -> Result<Vec<BalanceHistoryDailySummary>, DomainError> {
+            unimplemented!()
+        }
+
+        async fn list_daily_summaries_for_accounts_in_range(
+            &self,
+            _account_ids: &[AccountId],
+            _start_date: NaiveDate,
+            _end_date: NaiveDate,
+        ) -> Result<HashMap<AccountId, Vec<BalanceHistoryDailySummary>>, DomainError> {
+            unimplemented!()
+        }
+
+        async fn list_provider_daily_totals_in_range(
+            &self,
+            _start_date: NaiveDate,
+            _end_date: NaiveDate,
+        ) -> Result<Vec<ProviderDailyBalanceTotal>, DomainError> {
+            unimplemented!()
+        }
+
+        async fn find_daily_summary(
+            &self,
+            _account_id: &AccountId,
+            _date: NaiveDate,
+        ) -> Result<Option<BalanceHistoryDailySummary>, DomainError> {
+            unimplemented!()
+        }
+
+        async fn list_distinct_account_ids(&self) -> Result<Vec<AccountId>, DomainError> {
+            Ok(self
+                .summaries
+                .keys()
+                .map(|id| AccountId::from_string(id))
+                .collect())
+        }
+
+        async fn find_streak_reset(
+            &self,
+            account_id: &AccountId,
+        ) -> Result<Option<NaiveDate>, DomainError> {
+            Ok(self
+                .resets
+                .read()
+                .unwrap()
+                .get(account_id.as_str())
+                .copied())
+        }
+
+        async fn set_streak_reset(
+            &self,
+            account_id: &AccountId,
+            reset_on: Option<NaiveDate>,
+        ) -> Result<(), DomainError> {
+            let mut resets = self.resets.write().unwrap();
+            match reset_on {
+                Some(day) => resets.insert(account_id.as_str().to_string(), day),
+                None => resets.remove(account_id.as_str()),
+            };
+            Ok(())
+        }
+
+        async fn clear_streak_resets(&self) -> Result<(), DomainError> {
+            self.resets.write().unwrap().clear();
+            Ok(())
+        }
+    }
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, day).unwrap()
+    }
+
+    fn freezes(per_month: u32) -> StreakFreezes {
+        StreakFreezes {
+            per_month,
+            today: date(31),
+        }
+    }
+
+    /// A check-in each day in `days`, the quota growing with every one
+    fn check_ins(days: &[u32]) -> Vec<BalanceHistoryDailySummary> {
+        days.iter()
+            .enumerate()
+            .map(|(i, day)| {
+                BalanceHistoryDailySummary::restore(date(*day), 10.0 * (i + 1) as f64, 5.0, 1.0)
+            })
+            .collect()
+    }
+
+    /// Two accounts, both with their streak reset on the 3rd
+    fn repo() -> MockBalanceHistoryRepository {
+        let summaries = HashMap::from([
+            ("main".to_string(), check_ins(&[1, 2, 3, 4, 5])),
+            ("backup".to_string(), check_ins(&[2, 3, 5, 6])),
+        ]);
+        let resets = summaries.keys().map(|id| (id.clone(), date(3))).collect();
+        MockBalanceHistoryRepository {
+            summaries,
+            resets: RwLock::new(resets),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reset_counts_only_later_check_ins() {
+        let repo = repo();
+
+        let streak = compute_streak(&repo, "main", freezes(0)).await.unwrap();
+
+        assert_eq!(streak.current_streak, 2);
+        assert_eq!(streak.longest_streak, 2);
+        assert_eq!(streak.total_check_in_days, 2);
+        assert_eq!(streak.last_check_in_date, Some(date(5)));
+
+        reset_streak(&repo, "main", date(5)).await.unwrap();
+        let streak = compute_streak(&repo, "main", freezes(0)).await.unwrap();
+        assert_eq!(streak.total_check_in_days, 0);
+        assert_eq!(streak.last_check_in_date, None);
+    }
+
+    #[tokio::test]
+    async fn test_single_recalculation_matches_global() {
+        let global = repo();
+        recalculate_all_streaks(&global).await.unwrap();
+
+        let single = repo();
+        recalculate_streak(&single, "main").await.unwrap();
+
+        let recalculated = compute_streak(&single, "main", freezes(0)).await.unwrap();
+        assert_eq!(
+            recalculated,
+            compute_streak(&global, "main", freezes(0)).await.unwrap()
+        );
+        assert_eq!(recalculated.current_streak, 5);
+        assert_eq!(recalculated.total_check_in_days, 5);
+
+        // Only the requested account is recalculated
+        assert_ne!(
+            compute_streak(&single, "backup", freezes(0)).await.unwrap(),
+            compute_streak(&global, "backup", freezes(0)).await.unwrap()
+        );
+    }
+
+    #[test]
+    fn test_single_missed_day_consumes_a_freeze() {
+        let rows = check_ins(&[1, 2, 4, 5]);
+
+        let frozen = calculate_streak_stats("main", &rows, None, freezes(1));
+        assert_eq!(frozen.current_streak, 4);
+        assert_eq!(frozen.longest_streak, 4);
+        assert_eq!(frozen.freezes_remaining, 0);
+
+        let unfrozen = calculate_streak_stats("main", &rows, None, freezes(0));
+        assert_eq!(unfrozen.current_streak, 2);
+        assert_eq!(unfrozen.longest_streak, 2);
+    }
+
+    #[test]
+    fn test_streak_breaks_when_no_freeze_remains() {
+        // The second missed day finds the month's only freeze used
+        let rows = check_ins(&[1, 2, 4, 5, 7]);
+
+        let streak = calculate_streak_stats("main", &rows, None, freezes(1));
+
+        assert_eq!(streak.current_streak, 1);
+        assert_eq!(streak.longest_streak, 4);
+        assert_eq!(streak.total_check_in_days, 5);
+        assert_eq!(streak.freezes_remaining, 0);
+
+        // Two missed days in a row are never frozen
+        let rows = check_ins(&[1, 2, 5]);
+        let streak = calculate_streak_stats("main", &rows, None, freezes(2));
+        assert_eq!(streak.current_streak, 1);
+        assert_eq!(streak.freezes_remaining, 2);
     }
 }
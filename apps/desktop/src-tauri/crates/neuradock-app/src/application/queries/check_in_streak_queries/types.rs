@@ -1,8 +1,18 @@
 use chrono::NaiveDate;
 
+#[derive(Debug, PartialEq)]
 pub struct StreakComputation {
     pub current_streak: u32,
     pub longest_streak: u32,
     pub total_check_in_days: u32,
     pub last_check_in_date: Option<NaiveDate>,
+    /// Freezes left in the month of `StreakFreezes::today`
+    pub freezes_remaining: u32,
+}
+
+/// Missed days a streak may skip, each month of history gets `per_month`
+#[derive(Debug, Clone, Copy)]
+pub struct StreakFreezes {
+    pub per_month: u32,
+    pub today: NaiveDate,
 }
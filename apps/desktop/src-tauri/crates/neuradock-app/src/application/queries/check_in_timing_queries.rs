@@ -0,0 +1,41 @@
+use std::sync::Arc;
+
+use crate::application::dtos::CheckInTimingDto;
+use neuradock_domain::check_in_timing::CheckInTimingRepository;
+use neuradock_domain::shared::{AccountId, DomainError};
+
+/// Runs returned when no limit is given
+const DEFAULT_CHECK_IN_TIMINGS_LIMIT: u32 = 20;
+
+/// Check-in timing query service
+/// Serves the phase breakdowns of recent check-ins for the performance panel
+pub struct CheckInTimingQueries {
+    timing_repo: Arc<dyn CheckInTimingRepository>,
+}
+
+impl CheckInTimingQueries {
+    pub fn new(timing_repo: Arc<dyn CheckInTimingRepository>) -> Self {
+        Self { timing_repo }
+    }
+
+    /// Phase timings of the latest check-ins of an account, newest first
+    pub async fn get_check_in_timings(
+        &self,
+        account_id: &str,
+        limit: Option<u32>,
+    ) -> Result<Vec<CheckInTimingDto>, DomainError> {
+        let limit = limit.unwrap_or(DEFAULT_CHECK_IN_TIMINGS_LIMIT);
+        if limit == 0 {
+            return Err(DomainError::Validation(
+                "Limit must be at least 1".to_string(),
+            ));
+        }
+
+        let timings = self
+            .timing_repo
+            .find_recent_by_account(&AccountId::from_string(account_id), limit)
+            .await?;
+
+        Ok(timings.iter().map(Into::into).collect())
+    }
+}
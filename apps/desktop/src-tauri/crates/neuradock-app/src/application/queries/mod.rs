@@ -1,7 +1,11 @@
 mod account_queries;
 mod balance_statistics_queries;
 mod check_in_streak_queries;
+mod check_in_timing_queries;
+mod provider_queries;
 
 pub use account_queries::AccountQueryService;
 pub use balance_statistics_queries::BalanceStatisticsQueryService;
 pub use check_in_streak_queries::CheckInStreakQueries;
+pub use check_in_timing_queries::CheckInTimingQueries;
+pub use provider_queries::ProviderQueryService;
@@ -0,0 +1,54 @@
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+
+use crate::application::dtos::QuotaResetInfoDto;
+use neuradock_domain::check_in::{Provider, ProviderRepository};
+use neuradock_domain::shared::{DomainError, ProviderId};
+
+/// Provider query service
+/// Handles read-only provider projections such as quota reset countdowns
+pub struct ProviderQueryService {
+    provider_repo: Arc<dyn ProviderRepository>,
+}
+
+impl ProviderQueryService {
+    pub fn new(provider_repo: Arc<dyn ProviderRepository>) -> Self {
+        Self { provider_repo }
+    }
+
+    /// Get the next quota reset of a provider, or None if it has no reset schedule
+    pub async fn get_quota_reset_info(
+        &self,
+        provider_id: &str,
+    ) -> Result<Option<QuotaResetInfoDto>, DomainError> {
+        let provider = self
+            .provider_repo
+            .find_by_id(&ProviderId::from_string(provider_id))
+            .await?
+            .ok_or_else(|| DomainError::ProviderNotFound(provider_id.to_string()))?;
+
+        quota_reset_info(&provider, Utc::now())
+    }
+}
+
+fn quota_reset_info(
+    provider: &Provider,
+    now: DateTime<Utc>,
+) -> Result<Option<QuotaResetInfoDto>, DomainError> {
+    let Some(schedule) = provider.quota_reset_schedule() else {
+        return Ok(None);
+    };
+
+    let next_reset = schedule.next_reset_after(now)?;
+    let last_reset = schedule.previous_reset_before(now)?;
+
+    Ok(Some(QuotaResetInfoDto {
+        provider_id: provider.id().as_str().to_string(),
+        provider_name: provider.name().to_string(),
+        reset_time: schedule.reset_time().to_string(),
+        utc_offset: schedule.utc_offset().to_string(),
+        next_reset_at: next_reset.to_rfc3339(),
+        last_reset_at: last_reset.to_rfc3339(),
+        seconds_until_reset: (next_reset - now).num_seconds(),
+    }))
+}
@@ -0,0 +1,124 @@
+use anyhow::Result;
+use sqlx::SqlitePool;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use neuradock_infrastructure::persistence::{
+    export_app_data, import_app_data, open_archive, seal_archive, AppDataImportReport, ImportMode,
+};
+use neuradock_infrastructure::security::EncryptionService;
+
+use super::running_jobs::RunningJobs;
+use super::{CachedProviderRepository, ConfigService};
+use crate::application::event_handlers::SchedulerReloadEventHandler;
+
+/// Exports and imports all application data as one passphrase-encrypted archive
+///
+/// Credentials are encrypted with a per-installation key, so the archive
+/// carries them re-encrypted under the passphrase and the import encrypts them
+/// again with the key of the receiving installation.
+pub struct AppDataService {
+    pool: Arc<SqlitePool>,
+    encryption: Arc<EncryptionService>,
+    config: Arc<ConfigService>,
+    scheduler_reload: Option<SchedulerReloadEventHandler>,
+    provider_cache: Option<CachedProviderRepository>,
+    running_jobs: &'static RunningJobs,
+}
+
+impl AppDataService {
+    pub fn new(
+        pool: Arc<SqlitePool>,
+        encryption: Arc<EncryptionService>,
+        config: Arc<ConfigService>,
+    ) -> Self {
+        Self {
+            pool,
+            encryption,
+            config,
+            scheduler_reload: None,
+            provider_cache: None,
+            running_jobs: RunningJobs::global(),
+        }
+    }
+
+    /// Reload auto check-in schedules after an import
+    pub fn with_scheduler_reload(mut self, handler: SchedulerReloadEventHandler) -> Self {
+        self.scheduler_reload = Some(handler);
+        self
+    }
+
+    /// Clear the provider cache after an import, which writes providers directly
+    pub fn with_provider_cache(mut self, cache: CachedProviderRepository) -> Self {
+        self.provider_cache = Some(cache);
+        self
+    }
+
+    /// Write all data and settings to `path`, returning the rows exported per table
+    pub async fn export(&self, path: &Path, passphrase: &str) -> Result<BTreeMap<String, u64>> {
+        let mut archive = export_app_data(&self.pool, &self.encryption).await?;
+        archive.settings = Some(self.config.export_settings()?);
+        let sealed = seal_archive(&archive, passphrase)?;
+
+        tokio::fs::write(path, sealed)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to write archive {}: {}", path.display(), e))?;
+
+        let row_counts = archive.row_counts();
+        info!(path = %path.display(), ?row_counts, "Exported application data");
+        Ok(row_counts)
+    }
+
+    /// Restore the archive at `path`, refused while check-ins run
+    ///
+    /// Settings are only applied in [`ImportMode::Replace`]; a merge keeps the
+    /// settings of this installation.
+    pub async fn import(
+        &self,
+        path: &Path,
+        passphrase: &str,
+        mode: ImportMode,
+    ) -> Result<AppDataImportReport> {
+        let bytes = tokio::fs::read(path)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to read archive {}: {}", path.display(), e))?;
+        let archive = open_archive(&bytes, passphrase)?;
+
+        let Some(_import) = self.running_jobs.try_start_maintenance() else {
+            anyhow::bail!(
+                "Data cannot be imported while check-ins are in progress or database maintenance is running"
+            );
+        };
+
+        let report = import_app_data(&self.pool, &self.encryption, &archive, mode).await?;
+        info!(
+            path = %path.display(),
+            mode = mode.as_str(),
+            imported = ?report.imported,
+            skipped = ?report.skipped,
+            "Imported application data"
+        );
+
+        if mode == ImportMode::Replace {
+            if let Some(settings) = archive.settings {
+                if let Err(e) = self.config.import_settings(settings) {
+                    warn!("Failed to apply imported settings: {}", e);
+                }
+            }
+        }
+
+        if let Some(provider_cache) = &self.provider_cache {
+            provider_cache.invalidate_all().await;
+        }
+
+        if let Some(scheduler_reload) = &self.scheduler_reload {
+            if let Err(e) = scheduler_reload.reload_schedules().await {
+                warn!("Failed to reload schedules after import: {}", e);
+            }
+        }
+
+        Ok(report)
+    }
+}
@@ -0,0 +1,318 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+use neuradock_domain::shared::DomainError;
+use neuradock_infrastructure::security::{hash_passphrase, verify_passphrase};
+
+use super::{AppLockConfig, ConfigService, MAX_APP_LOCK_IDLE_MINUTES};
+
+/// Wrong passphrases in a row before unlocking is delayed
+const FREE_ATTEMPTS: u32 = 5;
+
+/// Delay after the first attempt past [`FREE_ATTEMPTS`], doubled for each further one
+const BASE_LOCKOUT: Duration = Duration::from_secs(30);
+
+/// Longest delay between unlock attempts
+const MAX_LOCKOUT: Duration = Duration::from_secs(15 * 60);
+
+/// Shortest passphrase accepted for the app lock, in characters
+const MIN_PASSPHRASE_CHARS: usize = 8;
+
+/// App lock state as shown in the settings and the unlock prompt
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppLockStatus {
+    pub enabled: bool,
+    pub locked: bool,
+    pub idle_timeout_minutes: u32,
+    /// Seconds until the next unlock attempt is accepted after too many wrong ones
+    pub retry_after_secs: Option<u64>,
+}
+
+/// Optional passphrase lock in front of credential-revealing commands
+///
+/// With a passphrase set the app starts locked. Guarded commands call
+/// [`AppLockService::ensure_unlocked`], which counts as activity and fails with
+/// `AppLocked` once the idle timeout has passed since the last guarded command.
+pub struct AppLockService {
+    config: Arc<ConfigService>,
+    state: Mutex<LockState>,
+    /// Serializes passphrase checks so parallel attempts cannot skip the lockout
+    attempt: tokio::sync::Mutex<()>,
+}
+
+impl AppLockService {
+    pub fn new(config: Arc<ConfigService>) -> Self {
+        Self {
+            config,
+            state: Mutex::new(LockState::default()),
+            attempt: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// Fail with `AppLocked` unless the lock is off or was unlocked recently
+    pub fn ensure_unlocked(&self) -> Result<(), DomainError> {
+        let config = self.config.get_app_lock();
+        if config.passphrase_hash.is_none() {
+            return Ok(());
+        }
+
+        if self.state().touch(Instant::now(), idle_timeout(&config)) {
+            Ok(())
+        } else {
+            Err(DomainError::AppLocked(
+                "Enter the app passphrase to reveal credentials".to_string(),
+            ))
+        }
+    }
+
+    /// Unlock with the passphrase
+    pub async fn unlock(&self, passphrase: String) -> Result<(), DomainError> {
+        let Some(hash) = self.config.get_app_lock().passphrase_hash else {
+            return Ok(());
+        };
+        self.check_passphrase(passphrase, hash).await?;
+        info!("🔓 App unlocked");
+        Ok(())
+    }
+
+    /// Lock right away, until the passphrase is entered again
+    pub fn lock(&self) {
+        self.state().last_activity = None;
+        info!("🔒 App locked");
+    }
+
+    /// Turn the app lock on or off, change its passphrase or its idle timeout
+    ///
+    /// While a passphrase is set, `current_passphrase` must match it. Enabling
+    /// without a new passphrase keeps the current one.
+    pub async fn set(
+        &self,
+        enabled: bool,
+        passphrase: Option<String>,
+        idle_timeout_minutes: u32,
+        current_passphrase: Option<String>,
+    ) -> Result<(), DomainError> {
+        if idle_timeout_minutes > MAX_APP_LOCK_IDLE_MINUTES {
+            return Err(DomainError::Validation(format!(
+                "Idle timeout must be at most {} minutes",
+                MAX_APP_LOCK_IDLE_MINUTES
+            )));
+        }
+
+        let current = self.config.get_app_lock();
+        if let Some(hash) = current.passphrase_hash.clone() {
+            let current_passphrase = current_passphrase.ok_or_else(|| {
+                DomainError::Validation(
+                    "Enter the current passphrase to change the app lock".to_string(),
+                )
+            })?;
+            self.check_passphrase(current_passphrase, hash).await?;
+        }
+
+        let passphrase_hash = match (enabled, passphrase) {
+            (false, _) => None,
+            (true, Some(passphrase)) => Some(hash_new_passphrase(passphrase).await?),
+            (true, None) => Some(current.passphrase_hash.ok_or_else(|| {
+                DomainError::Validation("Enter a passphrase to turn on the app lock".to_string())
+            })?),
+        };
+
+        self.config
+            .set_app_lock(AppLockConfig {
+                passphrase_hash,
+                idle_timeout_minutes,
+            })
+            .map_err(|e| DomainError::Infrastructure(e.to_string()))?;
+        // Whoever just set the passphrase knows it
+        self.state().record_success(Instant::now());
+
+        Ok(())
+    }
+
+    pub fn status(&self) -> AppLockStatus {
+        let config = self.config.get_app_lock();
+        let now = Instant::now();
+        let state = self.state();
+        AppLockStatus {
+            enabled: config.passphrase_hash.is_some(),
+            locked: config.passphrase_hash.is_some()
+                && !state.is_unlocked(now, idle_timeout(&config)),
+            idle_timeout_minutes: config.idle_timeout_minutes,
+            retry_after_secs: state.retry_after(now).map(|delay| delay.as_secs().max(1)),
+        }
+    }
+
+    /// Verify a passphrase against `hash`, counting wrong attempts towards the lockout
+    async fn check_passphrase(&self, passphrase: String, hash: String) -> Result<(), DomainError> {
+        let _attempt = self.attempt.lock().await;
+
+        if let Some(delay) = self.state().retry_after(Instant::now()) {
+            return Err(DomainError::AppLocked(format!(
+                "Too many wrong passphrases, try again in {} seconds",
+                delay.as_secs().max(1)
+            )));
+        }
+
+        let matches = tokio::task::spawn_blocking(move || verify_passphrase(&passphrase, &hash))
+            .await
+            .map_err(|e| DomainError::Infrastructure(e.to_string()))?
+            .map_err(|e| DomainError::Infrastructure(e.to_string()))?;
+
+        let mut state = self.state();
+        if matches {
+            state.record_success(Instant::now());
+            Ok(())
+        } else {
+            state.record_failure(Instant::now());
+            warn!(
+                "⚠️  Wrong app lock passphrase ({} in a row)",
+                state.failed_attempts
+            );
+            Err(DomainError::InvalidCredentials(
+                "Wrong passphrase".to_string(),
+            ))
+        }
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, LockState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Validate and hash a new passphrase off the async runtime
+async fn hash_new_passphrase(passphrase: String) -> Result<String, DomainError> {
+    if passphrase.chars().count() < MIN_PASSPHRASE_CHARS {
+        return Err(DomainError::Validation(format!(
+            "Passphrase must be at least {} characters",
+            MIN_PASSPHRASE_CHARS
+        )));
+    }
+    tokio::task::spawn_blocking(move || hash_passphrase(&passphrase))
+        .await
+        .map_err(|e| DomainError::Infrastructure(e.to_string()))?
+        .map_err(|e| DomainError::Infrastructure(e.to_string()))
+}
+
+/// Idle time before locking again, `None` when the lock only engages at launch
+fn idle_timeout(config: &AppLockConfig) -> Option<Duration> {
+    (config.idle_timeout_minutes > 0)
+        .then(|| Duration::from_secs(u64::from(config.idle_timeout_minutes) * 60))
+}
+
+/// Delay before the next attempt after `failed_attempts` wrong passphrases in a row
+fn lockout_for(failed_attempts: u32) -> Option<Duration> {
+    let over = failed_attempts.checked_sub(FREE_ATTEMPTS)?;
+    Some(
+        BASE_LOCKOUT
+            .saturating_mul(1 << over.min(16))
+            .min(MAX_LOCKOUT),
+    )
+}
+
+/// In-memory unlock state, reset to locked on every launch
+#[derive(Debug, Default)]
+struct LockState {
+    /// Unlock or last guarded command (None = locked)
+    last_activity: Option<Instant>,
+    failed_attempts: u32,
+    locked_out_until: Option<Instant>,
+}
+
+impl LockState {
+    fn is_unlocked(&self, now: Instant, idle: Option<Duration>) -> bool {
+        self.last_activity
+            .is_some_and(|last| idle.is_none_or(|idle| now.saturating_duration_since(last) < idle))
+    }
+
+    /// Record activity if still unlocked, otherwise lock
+    fn touch(&mut self, now: Instant, idle: Option<Duration>) -> bool {
+        if self.is_unlocked(now, idle) {
+            self.last_activity = Some(now);
+            true
+        } else {
+            self.last_activity = None;
+            false
+        }
+    }
+
+    fn retry_after(&self, now: Instant) -> Option<Duration> {
+        self.locked_out_until
+            .map(|until| until.saturating_duration_since(now))
+            .filter(|delay| !delay.is_zero())
+    }
+
+    fn record_success(&mut self, now: Instant) {
+        self.last_activity = Some(now);
+        self.failed_attempts = 0;
+        self.locked_out_until = None;
+    }
+
+    fn record_failure(&mut self, now: Instant) {
+        self.failed_attempts += 1;
+        self.locked_out_until = lockout_for(self.failed_attempts).map(|delay| now + delay);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IDLE: Option<Duration> = Some(Duration::from_secs(15 * 60));
+
+    #[test]
+    fn test_starts_locked() {
+        let mut state = LockState::default();
+
+        assert!(!state.touch(Instant::now(), None));
+    }
+
+    #[test]
+    fn test_locks_after_idle_timeout() {
+        let now = Instant::now();
+        let mut state = LockState::default();
+        state.record_success(now);
+
+        assert!(state.touch(now + Duration::from_secs(14 * 60), IDLE));
+        // Activity pushes the timeout back
+        assert!(state.touch(now + Duration::from_secs(28 * 60), IDLE));
+        assert!(!state.touch(now + Duration::from_secs(44 * 60), IDLE));
+        assert!(!state.is_unlocked(now + Duration::from_secs(44 * 60), None));
+    }
+
+    #[test]
+    fn test_without_idle_timeout_stays_unlocked() {
+        let now = Instant::now();
+        let mut state = LockState::default();
+        state.record_success(now);
+
+        assert!(state.touch(now + Duration::from_secs(7 * 24 * 60 * 60), None));
+    }
+
+    #[test]
+    fn test_lockout_grows_after_free_attempts() {
+        assert_eq!(lockout_for(FREE_ATTEMPTS - 1), None);
+        assert_eq!(lockout_for(FREE_ATTEMPTS), Some(BASE_LOCKOUT));
+        assert_eq!(lockout_for(FREE_ATTEMPTS + 1), Some(BASE_LOCKOUT * 2));
+        assert_eq!(lockout_for(FREE_ATTEMPTS + 40), Some(MAX_LOCKOUT));
+    }
+
+    #[test]
+    fn test_wrong_attempts_delay_and_success_resets() {
+        let now = Instant::now();
+        let mut state = LockState::default();
+        for _ in 0..FREE_ATTEMPTS {
+            state.record_failure(now);
+        }
+
+        assert_eq!(state.retry_after(now), Some(BASE_LOCKOUT));
+        assert_eq!(state.retry_after(now + BASE_LOCKOUT), None);
+
+        state.record_success(now + BASE_LOCKOUT);
+        assert_eq!(state.failed_attempts, 0);
+        state.record_failure(now + BASE_LOCKOUT);
+        assert_eq!(state.retry_after(now + BASE_LOCKOUT), None);
+    }
+}
@@ -1,10 +1,12 @@
 use crate::application::dtos::BalanceDto;
-use chrono::Utc;
-use sha2::{Digest, Sha256};
+use chrono::{NaiveDate, NaiveTime, Utc};
+use std::collections::HashSet;
 use std::sync::Arc;
-use tracing::{debug, warn};
+use tracing::{debug, info, warn};
 
-use neuradock_domain::balance_history::{BalanceHistoryRecord, BalanceHistoryRepository};
+use neuradock_domain::balance_history::{
+    backfill_daily_snapshots, BalanceChange, BalanceHistoryDailySummary, BalanceHistoryRepository,
+};
 use neuradock_domain::shared::{AccountId, DomainError};
 
 /// Service for managing balance history records
@@ -17,33 +19,21 @@ impl BalanceHistoryService {
         Self { repository }
     }
 
-    /// Save balance to balance_history table (one record per day, uses deterministic ID to prevent duplicates)
+    /// Save balance as today's snapshot, replacing an earlier one from the same day
     pub async fn save_balance_history(
         &self,
         account_id: &str,
         balance: &BalanceDto,
     ) -> Result<(), DomainError> {
-        let now = Utc::now();
-        let date_str = now.format("%Y-%m-%d").to_string();
-
-        // Generate deterministic ID based on account_id and date
-        // This ensures the same account on the same day always has the same ID
-        let mut hasher = Sha256::new();
-        hasher.update(account_id.as_bytes());
-        hasher.update(date_str.as_bytes());
-        let hash_result = hasher.finalize();
-        let id = format!("{:x}", hash_result);
-
-        let record = BalanceHistoryRecord::new(
-            id,
-            AccountId::from_string(account_id),
-            balance.current_balance,
-            balance.total_consumed,
-            balance.total_quota,
-            now,
-        )?;
-
-        self.repository.save(&record).await?;
+        self.repository
+            .upsert_daily_snapshot(
+                &AccountId::from_string(account_id),
+                balance.current_balance,
+                balance.total_consumed,
+                balance.total_quota,
+                Utc::now(),
+            )
+            .await?;
 
         debug!(
             account_id,
@@ -56,6 +46,58 @@ impl BalanceHistoryService {
         Ok(())
     }
 
+    /// Add snapshots rebuilt from a transaction log for the days without history
+    ///
+    /// `balance` is the current balance the log is walked back from. Days that
+    /// already have a snapshot keep it. Returns the added days, oldest first.
+    pub async fn backfill_from_transaction_log(
+        &self,
+        account_id: &str,
+        balance: &BalanceDto,
+        changes: &[BalanceChange],
+    ) -> Result<Vec<BalanceHistoryDailySummary>, DomainError> {
+        let account = AccountId::from_string(account_id);
+        let existing_days: HashSet<NaiveDate> = self
+            .repository
+            .list_all_daily_summaries(&account)
+            .await?
+            .iter()
+            .map(|summary| summary.check_in_date())
+            .collect();
+
+        let snapshots = backfill_daily_snapshots(
+            balance.current_balance,
+            balance.total_consumed,
+            changes,
+            Utc::now().date_naive(),
+            &existing_days,
+        );
+
+        // A backfilled day is recorded as of its last second, like a snapshot
+        // taken at the end of the day
+        let end_of_day = NaiveTime::from_hms_opt(23, 59, 59).unwrap_or_default();
+        for snapshot in &snapshots {
+            self.repository
+                .upsert_daily_snapshot(
+                    &account,
+                    snapshot.daily_balance(),
+                    snapshot.daily_consumed(),
+                    snapshot.daily_total_quota(),
+                    snapshot.check_in_date().and_time(end_of_day).and_utc(),
+                )
+                .await?;
+        }
+
+        info!(
+            account_id,
+            changes = changes.len(),
+            days_added = snapshots.len(),
+            "Balance history backfilled from transaction log"
+        );
+
+        Ok(snapshots)
+    }
+
     pub async fn get_latest_balance(
         &self,
         account_id: &str,
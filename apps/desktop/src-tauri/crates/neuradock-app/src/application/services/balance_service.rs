@@ -1,13 +1,31 @@
+use futures::stream::{self, StreamExt};
+use std::collections::HashMap;
+use std::future::Future;
 use std::sync::Arc;
+use tracing::warn;
 
 use neuradock_domain::account::AccountRepository;
 use neuradock_domain::check_in::ProviderRepository;
 use neuradock_domain::proxy_config::ProxyConfigRepository;
 use neuradock_domain::shared::{AccountId, DomainError};
 
-use crate::application::dtos::BalanceDto;
+use crate::application::dtos::{BalanceBackfillDto, BalanceDto};
 use crate::application::services::{BalanceHistoryService, CheckInExecutor};
 
+/// Maximum number of accounts refreshed at the same time by a batch refresh
+const BATCH_CONCURRENCY: usize = 4;
+
+/// Result of one account in a batch balance refresh, reported as soon as it completes
+#[derive(Debug, Clone)]
+pub struct BalanceRefreshProgress {
+    pub account_id: String,
+    /// Accounts finished so far, including this one
+    pub completed: usize,
+    pub total: usize,
+    pub balance: Option<BalanceDto>,
+    pub error: Option<String>,
+}
+
 pub struct BalanceService {
     account_repo: Arc<dyn AccountRepository>,
     provider_repo: Arc<dyn ProviderRepository>,
@@ -103,4 +121,200 @@ impl BalanceService {
 
         Ok(balance_dto)
     }
+
+    /// Backfill an account's balance history from its provider's transaction log
+    pub async fn backfill_balance_history(
+        &self,
+        account_id: &str,
+    ) -> Result<BalanceBackfillDto, DomainError> {
+        let account = self
+            .account_repo
+            .find_by_id(&AccountId::from_string(account_id))
+            .await?
+            .ok_or_else(|| DomainError::AccountNotFound(account_id.to_string()))?;
+        let provider = self
+            .provider_repo
+            .find_by_id(account.provider_id())
+            .await?
+            .ok_or_else(|| {
+                DomainError::ProviderNotFound(account.provider_id().as_str().to_string())
+            })?;
+        if provider.transaction_log_path().is_none() {
+            return Err(DomainError::Validation(format!(
+                "Provider {} has no transaction log to backfill from",
+                provider.name()
+            )));
+        }
+
+        let proxy_url = self.proxy_config_repo.get().await?.proxy_url();
+        let executor = CheckInExecutor::with_proxy(
+            self.account_repo.clone(),
+            self.headless_browser,
+            proxy_url,
+        )
+        .map_err(|e| DomainError::Infrastructure(e.to_string()))?;
+        let (user_info, changes) = executor
+            .fetch_transaction_log(account_id, &provider)
+            .await
+            .map_err(|e| DomainError::Infrastructure(e.to_string()))?;
+
+        let balance = BalanceDto {
+            current_balance: user_info.current_balance,
+            total_consumed: user_info.total_consumed,
+            total_quota: user_info.current_balance + user_info.total_consumed,
+        };
+        let added = self
+            .balance_history_service
+            .backfill_from_transaction_log(account_id, &balance, &changes)
+            .await?;
+
+        Ok(BalanceBackfillDto {
+            account_id: account_id.to_string(),
+            transactions: changes.len() as u32,
+            days_added: added.len() as u32,
+            first_day: added
+                .first()
+                .map(|summary| summary.check_in_date().to_string()),
+            last_day: added
+                .last()
+                .map(|summary| summary.check_in_date().to_string()),
+        })
+    }
+
+    /// Fetch balances of several accounts concurrently
+    ///
+    /// `on_progress` is called once per account as its refresh completes. Failed
+    /// refreshes map to `None` in the returned map.
+    pub async fn fetch_accounts_balances(
+        &self,
+        account_ids: Vec<String>,
+        force_refresh: bool,
+        on_progress: impl FnMut(BalanceRefreshProgress),
+    ) -> HashMap<String, Option<BalanceDto>> {
+        refresh_concurrently(
+            account_ids,
+            BATCH_CONCURRENCY,
+            move |account_id| async move {
+                self.fetch_account_balance(&account_id, force_refresh).await
+            },
+            on_progress,
+        )
+        .await
+    }
+}
+
+async fn refresh_concurrently<F, Fut>(
+    account_ids: Vec<String>,
+    concurrency: usize,
+    fetch: F,
+    mut on_progress: impl FnMut(BalanceRefreshProgress),
+) -> HashMap<String, Option<BalanceDto>>
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = Result<BalanceDto, DomainError>>,
+{
+    let total = account_ids.len();
+    let mut results = HashMap::with_capacity(total);
+    let mut completed = 0;
+
+    let mut refreshes = stream::iter(account_ids)
+        .map(|account_id| {
+            let refresh = fetch(account_id.clone());
+            async move { (account_id, refresh.await) }
+        })
+        .buffer_unordered(concurrency.max(1));
+
+    while let Some((account_id, result)) = refreshes.next().await {
+        completed += 1;
+        let (balance, error) = match result {
+            Ok(balance) => (Some(balance), None),
+            Err(e) => {
+                warn!("Failed to refresh balance of account {}: {}", account_id, e);
+                (None, Some(e.to_string()))
+            }
+        };
+
+        on_progress(BalanceRefreshProgress {
+            account_id: account_id.clone(),
+            completed,
+            total,
+            balance: balance.clone(),
+            error,
+        });
+        results.insert(account_id, balance);
+    }
+
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    fn balance(current_balance: f64) -> BalanceDto {
+        BalanceDto {
+            current_balance,
+            total_consumed: 0.0,
+            total_quota: current_balance,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_batch_refresh_reports_progress_for_every_account() {
+        let account_ids: Vec<String> = (0..10).map(|i| format!("account-{}", i)).collect();
+        let mut events = Vec::new();
+
+        let results = refresh_concurrently(
+            account_ids.clone(),
+            BATCH_CONCURRENCY,
+            |account_id| async move {
+                if account_id == "account-3" {
+                    Err(DomainError::AccountNotFound(account_id))
+                } else {
+                    Ok(balance(1.0))
+                }
+            },
+            |progress| events.push(progress),
+        )
+        .await;
+
+        assert_eq!(events.len(), account_ids.len());
+        assert_eq!(results.len(), account_ids.len());
+        assert!(results["account-3"].is_none());
+        assert!(results["account-0"].is_some());
+
+        let completed: Vec<usize> = events.iter().map(|e| e.completed).collect();
+        assert_eq!(completed, (1..=account_ids.len()).collect::<Vec<_>>());
+        assert!(events.iter().all(|e| e.total == account_ids.len()));
+
+        let failed = events.iter().find(|e| e.account_id == "account-3").unwrap();
+        assert!(failed.balance.is_none());
+        assert!(failed.error.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_batch_refresh_runs_concurrently_within_limit() {
+        let in_flight = AtomicUsize::new(0);
+        let max_in_flight = AtomicUsize::new(0);
+        let account_ids: Vec<String> = (0..8).map(|i| format!("account-{}", i)).collect();
+
+        let results = refresh_concurrently(
+            account_ids,
+            3,
+            |_| async {
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                max_in_flight.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                Ok(balance(2.0))
+            },
+            |_| {},
+        )
+        .await;
+
+        assert_eq!(results.len(), 8);
+        assert_eq!(max_in_flight.load(Ordering::SeqCst), 3);
+    }
 }
@@ -10,7 +10,6 @@ pub async fn fetch_updated_balance_after_check_in(
     user_info_service: &UserInfoService<'_>,
     account: &Account,
     provider: &Provider,
-    account_name: &str,
     cookies: &HashMap<String, String>,
     check_in_result: &CheckInResult,
     initial_user_info: Option<UserInfo>,
@@ -20,9 +19,7 @@ pub async fn fetch_updated_balance_after_check_in(
         return initial_user_info;
     }
 
-    let api_user = account.credentials().api_user();
-
     user_info_service
-        .fetch_updated_balance(account_name, provider, cookies, api_user, initial_user_info)
+        .fetch_updated_balance(account, provider, cookies, initial_user_info)
         .await
 }
@@ -1,6 +1,8 @@
 use log::{error, info};
 use std::collections::HashMap;
 
+use neuradock_domain::check_in::ProviderDialect;
+use neuradock_domain::shared::ExtraHeaders;
 use neuradock_infrastructure::http::{CheckInResult, HttpClient};
 
 /// Execute check-in via page visit
@@ -18,6 +20,7 @@ pub async fn execute_page_visit_check_in(
             CheckInResult {
                 success: true,
                 message: "Check-in page visited successfully".to_string(),
+                response: None,
             }
         }
         Err(e) => {
@@ -28,16 +31,28 @@ pub async fn execute_page_visit_check_in(
 }
 
 /// Execute check-in via API call with WAF retry logic
+#[allow(clippy::too_many_arguments)]
 pub async fn execute_api_check_in(
     http_client: &HttpClient,
     sign_in_url: &str,
     cookies: &HashMap<String, String>,
     api_user_key: &str,
     api_user: &str,
+    extra_headers: &ExtraHeaders,
+    dialect: ProviderDialect,
+    sign_in_body: Option<&serde_json::Value>,
     account_name: &str,
 ) -> anyhow::Result<CheckInResult> {
     let result = http_client
-        .execute_check_in(sign_in_url, cookies, api_user_key, api_user)
+        .execute_check_in(
+            sign_in_url,
+            cookies,
+            api_user_key,
+            api_user,
+            extra_headers,
+            dialect,
+            sign_in_body,
+        )
         .await?;
 
     if result.success {
@@ -54,6 +69,7 @@ pub fn create_error_result(message: &str) -> CheckInResult {
     CheckInResult {
         success: false,
         message: message.to_string(),
+        response: None,
     }
 }
 
@@ -1,21 +1,35 @@
 use anyhow::{Context, Result};
+use chrono::Local;
 use log::info;
 use std::sync::Arc;
 use tracing::instrument;
 
+use neuradock_domain::balance_history::BalanceChange;
+use neuradock_domain::check_in_timing::{CheckInPhase, CheckInTiming, CheckInTimingRepository};
 use neuradock_domain::waf_cookies::WafCookiesRepository;
-use neuradock_domain::{account::AccountRepository, check_in::Provider, shared::AccountId};
+use neuradock_domain::{
+    account::{Account, AccountRepository},
+    check_in::Provider,
+    shared::{AccountId, ErrorCode},
+};
+use neuradock_infrastructure::http::waf_bypass::{BrowserTurnstileSolver, TurnstileTokenSource};
 use neuradock_infrastructure::http::{CheckInResult, HttpClient, UserInfo};
+use neuradock_infrastructure::monitoring::{CheckInTimingMetrics, RuntimeMetrics, TrackedTask};
 
+use crate::application::services::running_jobs::RunningJobs;
 use crate::application::services::user_info_service::UserInfoService;
 use crate::application::services::waf_cookie_manager::WafCookieManager;
 
 mod balance;
 mod execution;
+mod reward;
+mod timing;
+mod turnstile;
 mod types;
 mod validation;
 mod waf_handler;
 
+pub(crate) use timing::PhaseTimer;
 pub use types::AccountCheckInResult;
 
 /// Check-in executor service
@@ -23,6 +37,8 @@ pub struct CheckInExecutor {
     http_client: HttpClient,
     waf_manager: WafCookieManager,
     account_repo: Arc<dyn AccountRepository>,
+    turnstile_source: Arc<dyn TurnstileTokenSource>,
+    timing_repo: Option<Arc<dyn CheckInTimingRepository>>,
 }
 
 impl CheckInExecutor {
@@ -36,12 +52,15 @@ impl CheckInExecutor {
         proxy_url: Option<String>,
     ) -> Result<Self> {
         let http_client = HttpClient::with_proxy(proxy_url.clone())?;
+        let turnstile_source = Arc::new(BrowserTurnstileSolver::with_proxy(proxy_url.clone()));
         let waf_manager = WafCookieManager::new(headless_browser, proxy_url);
 
         Ok(Self {
             http_client,
             waf_manager,
             account_repo,
+            turnstile_source,
+            timing_repo: None,
         })
     }
 
@@ -51,6 +70,17 @@ impl CheckInExecutor {
         self
     }
 
+    /// Set repository for the phase timings of each run
+    pub fn with_timing_repo(mut self, repo: Arc<dyn CheckInTimingRepository>) -> Self {
+        self.timing_repo = Some(repo);
+        self
+    }
+
+    /// Solve WAF challenges for the accounts of a batch before checking them in
+    pub async fn prefetch_waf_cookies(&self, targets: &[(Account, Provider)]) {
+        self.waf_manager.prefetch_waf_cookies(targets).await;
+    }
+
     /// Create UserInfoService from current executor state
     fn create_user_info_service(&self) -> UserInfoService<'_> {
         UserInfoService::new(&self.http_client, &self.waf_manager)
@@ -62,6 +92,27 @@ impl CheckInExecutor {
         &self,
         account_id: &str,
         provider: &Provider,
+    ) -> Result<AccountCheckInResult> {
+        let _task = RuntimeMetrics::global().track(TrackedTask::CheckIn);
+        let timer = PhaseTimer::start();
+        let result = self.run_check_in(account_id, provider, &timer).await;
+
+        let success = result.as_ref().is_ok_and(|result| result.success);
+        if let Some(timing) = timer.finish(
+            AccountId::from_string(account_id),
+            provider.id().clone(),
+            success,
+        ) {
+            self.record_timing(&timing).await;
+        }
+        result
+    }
+
+    async fn run_check_in(
+        &self,
+        account_id: &str,
+        provider: &Provider,
+        timer: &PhaseTimer,
     ) -> Result<AccountCheckInResult> {
         let account_id_obj = AccountId::from_string(account_id);
 
@@ -69,44 +120,73 @@ impl CheckInExecutor {
         let account =
             validation::load_and_validate_account(&*self.account_repo, &account_id_obj).await?;
         let account_name = account.name().to_string();
+        let _running_job = RunningJobs::global().start_check_in(account_id, &account_name);
 
         info!("[{}] Starting check-in process", account_name);
 
         // 2. Validate using domain service
+        let today = Local::now().date_naive();
         if let Some(error_result) =
-            validation::validate_check_in_eligibility(&account, provider, &account_name)
+            validation::validate_check_in_eligibility(&account, provider, &account_name, today)
         {
             return Ok(error_result);
         }
 
+        // Count the attempt up front so failed attempts are capped too
+        let attempts = self
+            .account_repo
+            .record_check_in_attempt(&account_id_obj, today)
+            .await
+            .context("Failed to record check-in attempt")?;
+        if account.max_attempts_per_day() > 0 {
+            info!(
+                "[{}] Check-in attempt {}/{} today",
+                account_name,
+                attempts,
+                account.max_attempts_per_day()
+            );
+        }
+
         // 3. Prepare cookies and fetch user info with WAF handling
         let (mut cookies, user_info) = self
-            .prepare_cookies_and_fetch_user_info(&account, provider, &account_name)
+            .prepare_cookies_and_fetch_user_info(&account, provider, timer)
             .await?;
 
         // 4. Execute check-in request
         let check_in_result = self
-            .perform_check_in_request(&account, provider, &account_name, &mut cookies)
+            .perform_check_in_request(&account, provider, &account_name, &mut cookies, timer)
             .await;
 
+        let reward = reward::extract_reward(provider, &check_in_result, &account_name);
+        let quota_before = user_info.as_ref().map(|info| info.total_quota);
+
         // 5. Fetch updated balance after successful check-in
         let user_info_service = self.create_user_info_service();
-        let final_user_info = balance::fetch_updated_balance_after_check_in(
-            &user_info_service,
-            &account,
-            provider,
-            &account_name,
-            &cookies,
-            &check_in_result,
-            user_info,
-        )
-        .await;
+        let mut final_user_info = timer
+            .time(
+                CheckInPhase::BalanceFetch,
+                balance::fetch_updated_balance_after_check_in(
+                    &user_info_service,
+                    &account,
+                    provider,
+                    &cookies,
+                    &check_in_result,
+                    user_info,
+                ),
+            )
+            .await;
+        if let Some(amount) = reward {
+            final_user_info = final_user_info
+                .map(|info| reward::credit_reward(&account_id_obj, info, quota_before, amount));
+        }
 
         Ok(AccountCheckInResult {
             account_name,
             success: check_in_result.success,
             message: check_in_result.message,
+            reward,
             user_info: final_user_info,
+            error_code: (!check_in_result.success).then_some(ErrorCode::CheckInFailed),
         })
     }
 
@@ -136,39 +216,92 @@ impl CheckInExecutor {
         );
 
         // Prepare cookies
-        let cookies = self
-            .waf_manager
-            .prepare_cookies(&account_name, provider, account.credentials().cookies())
-            .await?;
-
-        let user_info_service = self.create_user_info_service();
-        let api_user = account.credentials().api_user();
+        let cookies = self.waf_manager.prepare_cookies(&account, provider).await?;
 
         // Get user info (balance)
-        user_info_service
-            .fetch_user_info(&account_name, provider, &cookies, api_user)
+        self.create_user_info_service()
+            .fetch_user_info(&account, provider, &cookies)
             .await
     }
 
+    /// Fetch the current balance and the balance changes of the provider's transaction log
+    #[instrument(skip(self, provider), fields(account_id = %account_id, provider_id = %provider.id()))]
+    pub async fn fetch_transaction_log(
+        &self,
+        account_id: &str,
+        provider: &Provider,
+    ) -> Result<(UserInfo, Vec<BalanceChange>)> {
+        let url = provider.transaction_log_url().ok_or_else(|| {
+            anyhow::anyhow!("Provider {} has no transaction log", provider.name())
+        })?;
+
+        let account = self
+            .account_repo
+            .find_by_id(&AccountId::from_string(account_id))
+            .await
+            .context("Failed to load account")?
+            .ok_or_else(|| anyhow::anyhow!("Account not found"))?;
+        let account_name = account.name().to_string();
+
+        info!("[{}] Fetching transaction log", account_name);
+
+        let cookies = self.waf_manager.prepare_cookies(&account, provider).await?;
+
+        let user_info = self
+            .create_user_info_service()
+            .fetch_user_info(&account, provider, &cookies)
+            .await?;
+        let changes = self
+            .http_client
+            .get_transaction_log(
+                &url,
+                &cookies,
+                provider.api_user_key(),
+                account.credentials().api_user(),
+                &provider
+                    .extra_headers()
+                    .merged_with(account.extra_headers()),
+                provider.dialect(),
+            )
+            .await?;
+
+        Ok((user_info, changes))
+    }
+
     // ========== Private helper methods for execute_check_in ==========
 
+    /// Add the timings of a run to the monitoring metrics and the timing history
+    async fn record_timing(&self, timing: &CheckInTiming) {
+        CheckInTimingMetrics::global().record(timing);
+        info!(
+            "Check-in phases of account {} (ms): cookies={} waf_refresh={} user_info={} check_in={} balance={} total={}",
+            timing.account_id(),
+            timing.phase_ms(CheckInPhase::CookiePreparation),
+            timing.phase_ms(CheckInPhase::WafRefresh),
+            timing.phase_ms(CheckInPhase::UserInfo),
+            timing.phase_ms(CheckInPhase::CheckInRequest),
+            timing.phase_ms(CheckInPhase::BalanceFetch),
+            timing.total_ms()
+        );
+
+        if let Some(repo) = &self.timing_repo {
+            if let Err(e) = repo.save(timing).await {
+                log::warn!("Failed to save check-in timings: {}", e);
+            }
+        }
+    }
+
     /// Prepare cookies and fetch user info with WAF handling
     async fn prepare_cookies_and_fetch_user_info(
         &self,
         account: &neuradock_domain::account::Account,
         provider: &Provider,
-        account_name: &str,
+        timer: &PhaseTimer,
     ) -> Result<(std::collections::HashMap<String, String>, Option<UserInfo>)> {
         let user_info_service = self.create_user_info_service();
-        let api_user = account.credentials().api_user();
 
         user_info_service
-            .fetch_user_info_with_retry(
-                account_name,
-                provider,
-                account.credentials().cookies(),
-                api_user,
-            )
+            .fetch_user_info_with_retry(account, provider, timer)
             .await
     }
 
@@ -179,6 +312,7 @@ impl CheckInExecutor {
         provider: &Provider,
         account_name: &str,
         cookies: &mut std::collections::HashMap<String, String>,
+        timer: &PhaseTimer,
     ) -> CheckInResult {
         let api_user = account.credentials().api_user();
 
@@ -192,6 +326,7 @@ impl CheckInExecutor {
             return CheckInResult {
                 success: true,
                 message: "Provider does not require explicit check-in".to_string(),
+                response: None,
             };
         };
 
@@ -205,13 +340,17 @@ impl CheckInExecutor {
         let is_page_visit = !sign_in_url.contains("/api/");
 
         if is_page_visit {
-            execution::execute_page_visit_check_in(
-                &self.http_client,
-                account_name,
-                &sign_in_url,
-                cookies,
-            )
-            .await
+            timer
+                .time(
+                    CheckInPhase::CheckInRequest,
+                    execution::execute_page_visit_check_in(
+                        &self.http_client,
+                        account_name,
+                        &sign_in_url,
+                        cookies,
+                    ),
+                )
+                .await
         } else {
             self.execute_api_check_in_with_retry(
                 account,
@@ -220,12 +359,14 @@ impl CheckInExecutor {
                 &sign_in_url,
                 cookies,
                 api_user,
+                timer,
             )
             .await
         }
     }
 
     /// Execute API check-in with WAF retry logic
+    #[allow(clippy::too_many_arguments)]
     async fn execute_api_check_in_with_retry(
         &self,
         account: &neuradock_domain::account::Account,
@@ -234,16 +375,46 @@ impl CheckInExecutor {
         sign_in_url: &str,
         cookies: &mut std::collections::HashMap<String, String>,
         api_user: &str,
+        timer: &PhaseTimer,
     ) -> CheckInResult {
-        let check_in_call = execution::execute_api_check_in(
-            &self.http_client,
-            sign_in_url,
-            cookies,
-            provider.api_user_key(),
-            api_user,
-            account_name,
-        )
-        .await;
+        let (sign_in_url, sign_in_body) = match timer
+            .time(
+                CheckInPhase::CheckInRequest,
+                turnstile::prepare_check_in_request(
+                    &*self.turnstile_source,
+                    provider,
+                    sign_in_url,
+                    account_name,
+                ),
+            )
+            .await
+        {
+            Ok(request) => request,
+            Err(e) => {
+                log::error!("[{}] {:#}", account_name, e);
+                return execution::create_error_result(&format!("{:#}", e));
+            }
+        };
+
+        let extra_headers = provider
+            .extra_headers()
+            .merged_with(account.extra_headers());
+        let check_in_call = timer
+            .time(
+                CheckInPhase::CheckInRequest,
+                execution::execute_api_check_in(
+                    &self.http_client,
+                    &sign_in_url,
+                    cookies,
+                    provider.api_user_key(),
+                    api_user,
+                    &extra_headers,
+                    provider.dialect(),
+                    sign_in_body.as_ref(),
+                    account_name,
+                ),
+            )
+            .await;
 
         match check_in_call {
             Ok(result) => result,
@@ -254,9 +425,11 @@ impl CheckInExecutor {
                     account,
                     provider,
                     account_name,
-                    sign_in_url,
+                    &sign_in_url,
+                    sign_in_body.as_ref(),
                     cookies,
                     api_user,
+                    timer,
                 )
                 .await
             }
@@ -0,0 +1,154 @@
+use chrono::Utc;
+use log::{info, warn};
+
+use neuradock_domain::balance::Balance;
+use neuradock_domain::check_in::Provider;
+use neuradock_domain::shared::AccountId;
+use neuradock_infrastructure::http::{CheckInResult, UserInfo};
+
+/// Reward granted by a successful check-in, if the provider reports it
+pub fn extract_reward(
+    provider: &Provider,
+    check_in_result: &CheckInResult,
+    account_name: &str,
+) -> Option<f64> {
+    if !check_in_result.success {
+        return None;
+    }
+
+    let reward = provider
+        .reward_extraction()?
+        .extract(check_in_result.response.as_ref(), &check_in_result.message);
+    match reward {
+        Some(amount) => info!("[{}] Check-in reward: {:.2}", account_name, amount),
+        None => warn!(
+            "[{}] Check-in succeeded but no reward was found in the response",
+            account_name
+        ),
+    }
+    reward
+}
+
+/// Credit the reward as income when the balance fetched after check-in does not show it yet
+///
+/// `quota_before` is the total quota fetched before check-in. The provider may
+/// apply the reward late, or the post-check-in fetch may have failed.
+pub fn credit_reward(
+    account_id: &AccountId,
+    user_info: UserInfo,
+    quota_before: Option<f64>,
+    reward: f64,
+) -> UserInfo {
+    let Some(quota_before) = quota_before else {
+        return user_info;
+    };
+    if user_info.total_quota > quota_before {
+        return user_info;
+    }
+
+    let mut balance = Balance::restore(
+        account_id.clone(),
+        user_info.current_balance,
+        user_info.total_consumed,
+        user_info.total_quota,
+        Utc::now(),
+    );
+    if let Err(e) = balance.record_income(reward) {
+        warn!("Failed to record check-in reward: {}", e);
+        return user_info;
+    }
+
+    UserInfo {
+        current_balance: balance.current(),
+        total_consumed: balance.total_consumed(),
+        total_quota: balance.total_quota(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use neuradock_domain::check_in::{ProviderConfig, RewardExtraction};
+
+    fn provider() -> Provider {
+        Provider::new(ProviderConfig {
+            name: "Reward Provider".to_string(),
+            domain: "https://example.com".to_string(),
+            login_path: "/login".to_string(),
+            sign_in_path: Some("/api/user/sign_in".to_string()),
+            sign_in_body: None,
+            user_info_path: "/api/user/self".to_string(),
+            token_api_path: None,
+            models_path: None,
+            transaction_log_path: None,
+            api_user_key: "new-api-user".to_string(),
+            extra_headers: Default::default(),
+            dialect: Default::default(),
+            bypass_method: None,
+            requires_turnstile: false,
+            supports_check_in: true,
+            check_in_bugged: false,
+            quota_reset_schedule: None,
+            balance_display: None,
+            reward_extraction: Some(
+                RewardExtraction::new(Some("data.quota".to_string()), None, 500000.0).unwrap(),
+            ),
+        })
+    }
+
+    fn user_info(current_balance: f64, total_quota: f64) -> UserInfo {
+        UserInfo {
+            current_balance,
+            total_consumed: total_quota - current_balance,
+            total_quota,
+        }
+    }
+
+    #[test]
+    fn test_extract_reward_from_sample_response() {
+        let result = CheckInResult {
+            success: true,
+            message: "签到成功".to_string(),
+            response: Some(serde_json::json!({
+                "success": true,
+                "message": "签到成功",
+                "data": { "quota": 250000 }
+            })),
+        };
+
+        assert_eq!(extract_reward(&provider(), &result, "Test"), Some(0.5));
+    }
+
+    #[test]
+    fn test_failed_check_in_has_no_reward() {
+        let result = CheckInResult {
+            success: false,
+            message: "今日已签到".to_string(),
+            response: Some(serde_json::json!({ "data": { "quota": 250000 } })),
+        };
+
+        assert_eq!(extract_reward(&provider(), &result, "Test"), None);
+    }
+
+    #[test]
+    fn test_credit_reward_when_balance_is_unchanged() {
+        let account_id = AccountId::new();
+
+        let credited = credit_reward(&account_id, user_info(10.0, 15.0), Some(15.0), 1.0);
+
+        assert_eq!(credited.current_balance, 11.0);
+        assert_eq!(credited.total_quota, 16.0);
+        assert_eq!(credited.total_consumed, 5.0);
+    }
+
+    #[test]
+    fn test_reward_already_in_balance_is_not_credited_twice() {
+        let account_id = AccountId::new();
+
+        let credited = credit_reward(&account_id, user_info(11.0, 16.0), Some(15.0), 1.0);
+        assert_eq!(credited.total_quota, 16.0);
+
+        let unknown_before = credit_reward(&account_id, user_info(11.0, 16.0), None, 1.0);
+        assert_eq!(unknown_before.total_quota, 16.0);
+    }
+}
@@ -0,0 +1,116 @@
+use chrono::{DateTime, Utc};
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::Instant;
+use tracing::Instrument;
+
+use neuradock_domain::check_in_timing::{CheckInPhase, CheckInPhaseDurations, CheckInTiming};
+use neuradock_domain::shared::{AccountId, ProviderId};
+
+/// Collects the phase durations of one check-in run
+pub(crate) struct PhaseTimer {
+    started_at: DateTime<Utc>,
+    started: Instant,
+    /// `None` until the first phase ran
+    phases: Mutex<Option<CheckInPhaseDurations>>,
+}
+
+impl PhaseTimer {
+    pub(crate) fn start() -> Self {
+        Self {
+            started_at: Utc::now(),
+            started: Instant::now(),
+            phases: Mutex::new(None),
+        }
+    }
+
+    /// Run `future` as `phase` in a child span that records `elapsed_ms` on close
+    pub(crate) async fn time<F: Future>(&self, phase: CheckInPhase, future: F) -> F::Output {
+        let span = tracing::info_span!(
+            "check_in_phase",
+            phase = phase.as_str(),
+            elapsed_ms = tracing::field::Empty
+        );
+        let started = Instant::now();
+        let output = future.instrument(span.clone()).await;
+        let elapsed = started.elapsed();
+
+        let elapsed_ms = elapsed.as_millis() as u64;
+        span.record("elapsed_ms", elapsed_ms);
+        tracing::debug!(parent: &span, phase = phase.as_str(), elapsed_ms, "Check-in phase finished");
+
+        self.phases
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get_or_insert_with(CheckInPhaseDurations::default)
+            .add(phase, elapsed);
+        output
+    }
+
+    /// Timings of the run, `None` if it stopped before any phase ran
+    pub(crate) fn finish(
+        self,
+        account_id: AccountId,
+        provider_id: ProviderId,
+        success: bool,
+    ) -> Option<CheckInTiming> {
+        let phases = self
+            .phases
+            .into_inner()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())?;
+
+        Some(CheckInTiming::new(
+            account_id,
+            provider_id,
+            self.started_at,
+            success,
+            phases,
+            self.started.elapsed(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_timer_adds_up_phases() {
+        let timer = PhaseTimer::start();
+        let value = timer
+            .time(CheckInPhase::UserInfo, async {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                7
+            })
+            .await;
+        timer
+            .time(
+                CheckInPhase::UserInfo,
+                tokio::time::sleep(Duration::from_millis(20)),
+            )
+            .await;
+
+        let timing = timer
+            .finish(
+                AccountId::from_string("account"),
+                ProviderId::from_string("provider"),
+                true,
+            )
+            .expect("phases ran");
+        assert_eq!(value, 7);
+        assert!(timing.phase_ms(CheckInPhase::UserInfo) >= 40);
+        assert_eq!(timing.phase_ms(CheckInPhase::WafRefresh), 0);
+        assert!(timing.total_ms() >= timing.phase_ms(CheckInPhase::UserInfo));
+    }
+
+    #[test]
+    fn test_timer_without_phases_has_no_timing() {
+        let timing = PhaseTimer::start().finish(
+            AccountId::from_string("account"),
+            ProviderId::from_string("provider"),
+            false,
+        );
+        assert!(timing.is_none());
+    }
+}
@@ -0,0 +1,168 @@
+use anyhow::{Context, Result};
+use log::info;
+use serde_json::Value;
+
+use neuradock_domain::check_in::Provider;
+use neuradock_infrastructure::http::waf_bypass::TurnstileTokenSource;
+
+/// Name of the Turnstile token in the check-in query string and JSON body
+///
+/// new-api reads the token from the `turnstile` query parameter, forks that
+/// take a JSON body expect the same key there.
+const TURNSTILE_FIELD: &str = "turnstile";
+
+/// Check-in URL and body, with a Turnstile token when the provider requires one
+pub async fn prepare_check_in_request(
+    token_source: &dyn TurnstileTokenSource,
+    provider: &Provider,
+    sign_in_url: &str,
+    account_name: &str,
+) -> Result<(String, Option<Value>)> {
+    let body = provider.sign_in_body().cloned();
+    if !provider.requires_turnstile() {
+        return Ok((sign_in_url.to_string(), body));
+    }
+
+    info!(
+        "[{}] Provider requires Turnstile, solving it before check-in",
+        account_name
+    );
+    let token = token_source
+        .solve(&provider.login_url(), account_name)
+        .await
+        .context("Failed to solve Turnstile")?;
+
+    inject_turnstile_token(sign_in_url, body, &token)
+}
+
+/// Add a Turnstile token to the check-in query string and to a JSON object body
+fn inject_turnstile_token(
+    sign_in_url: &str,
+    body: Option<Value>,
+    token: &str,
+) -> Result<(String, Option<Value>)> {
+    let mut url = url::Url::parse(sign_in_url).context("Invalid sign-in URL")?;
+    url.query_pairs_mut().append_pair(TURNSTILE_FIELD, token);
+
+    let body = body.map(|mut body| {
+        if let Value::Object(fields) = &mut body {
+            fields.insert(
+                TURNSTILE_FIELD.to_string(),
+                Value::String(token.to_string()),
+            );
+        }
+        body
+    });
+
+    Ok((url.to_string(), body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use neuradock_domain::check_in::ProviderConfig;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MockTokenSource {
+        solved_pages: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl TurnstileTokenSource for MockTokenSource {
+        async fn solve(&self, page_url: &str, _account_name: &str) -> Result<String> {
+            self.solved_pages.lock().unwrap().push(page_url.to_string());
+            Ok("token-123".to_string())
+        }
+    }
+
+    fn provider(requires_turnstile: bool, sign_in_body: Option<Value>) -> Provider {
+        Provider::new(ProviderConfig {
+            name: "Turnstile Provider".to_string(),
+            domain: "https://example.com".to_string(),
+            login_path: "/login".to_string(),
+            sign_in_path: Some("/api/user/sign_in".to_string()),
+            sign_in_body,
+            user_info_path: "/api/user/self".to_string(),
+            token_api_path: None,
+            models_path: None,
+            transaction_log_path: None,
+            api_user_key: "new-api-user".to_string(),
+            extra_headers: Default::default(),
+            dialect: Default::default(),
+            bypass_method: None,
+            requires_turnstile,
+            supports_check_in: true,
+            check_in_bugged: false,
+            quota_reset_schedule: None,
+            balance_display: None,
+            reward_extraction: None,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_token_is_injected_into_url_and_body() {
+        let source = MockTokenSource::default();
+        let provider = provider(true, Some(serde_json::json!({ "source": "app" })));
+
+        let (url, body) = prepare_check_in_request(
+            &source,
+            &provider,
+            "https://example.com/api/user/sign_in",
+            "Test Account",
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            url,
+            "https://example.com/api/user/sign_in?turnstile=token-123"
+        );
+        assert_eq!(
+            body,
+            Some(serde_json::json!({ "source": "app", "turnstile": "token-123" }))
+        );
+        assert_eq!(
+            source.solved_pages.into_inner().unwrap(),
+            vec!["https://example.com/login"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_no_body_is_added_when_none_is_configured() {
+        let source = MockTokenSource::default();
+        let provider = provider(true, None);
+
+        let (url, body) = prepare_check_in_request(
+            &source,
+            &provider,
+            "https://example.com/api/user/sign_in",
+            "Test Account",
+        )
+        .await
+        .unwrap();
+
+        assert!(url.ends_with("?turnstile=token-123"));
+        assert!(body.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_token_source_is_not_used_without_turnstile() {
+        let source = MockTokenSource::default();
+        let provider = provider(false, Some(serde_json::json!({})));
+
+        let (url, body) = prepare_check_in_request(
+            &source,
+            &provider,
+            "https://example.com/api/user/sign_in",
+            "Test Account",
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(url, "https://example.com/api/user/sign_in");
+        assert_eq!(body, Some(serde_json::json!({})));
+        assert!(source.solved_pages.into_inner().unwrap().is_empty());
+    }
+}
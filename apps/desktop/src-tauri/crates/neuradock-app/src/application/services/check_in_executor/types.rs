@@ -1,3 +1,4 @@
+use neuradock_domain::shared::ErrorCode;
 use neuradock_infrastructure::http::UserInfo;
 
 /// Check-in result for a single account
@@ -6,5 +7,9 @@ pub struct AccountCheckInResult {
     pub account_name: String,
     pub success: bool,
     pub message: String,
+    /// Reward reported by the provider, in balance units
+    pub reward: Option<f64>,
     pub user_info: Option<UserInfo>,
+    /// Classification of the failure, `None` on success
+    pub error_code: Option<ErrorCode>,
 }
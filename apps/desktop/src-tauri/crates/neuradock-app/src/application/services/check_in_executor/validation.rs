@@ -1,4 +1,5 @@
 use anyhow::{Context, Result};
+use chrono::NaiveDate;
 use log::warn;
 
 use neuradock_domain::{
@@ -26,6 +27,7 @@ pub fn validate_check_in_eligibility(
     account: &Account,
     provider: &Provider,
     account_name: &str,
+    today: NaiveDate,
 ) -> Option<AccountCheckInResult> {
     // Check account eligibility
     if let Err(e) = CheckInDomainService::can_check_in(account) {
@@ -34,7 +36,22 @@ pub fn validate_check_in_eligibility(
             account_name: account_name.to_string(),
             success: false,
             message: e.to_string(),
+            reward: None,
             user_info: None,
+            error_code: Some(e.code()),
+        });
+    }
+
+    // Check the daily attempt cap (retries included)
+    if let Err(e) = CheckInDomainService::check_daily_attempt_limit(account, today) {
+        warn!("[{}] Check-in refused: {}", account_name, e);
+        return Some(AccountCheckInResult {
+            account_name: account_name.to_string(),
+            success: false,
+            message: e.to_string(),
+            reward: None,
+            user_info: None,
+            error_code: Some(e.code()),
         });
     }
 
@@ -45,7 +62,9 @@ pub fn validate_check_in_eligibility(
             account_name: account_name.to_string(),
             success: false,
             message: e.to_string(),
+            reward: None,
             user_info: None,
+            error_code: Some(e.code()),
         });
     }
 
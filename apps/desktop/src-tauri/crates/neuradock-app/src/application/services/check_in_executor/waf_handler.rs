@@ -1,10 +1,11 @@
 use log::{error, info, warn};
 use std::collections::HashMap;
 
-use neuradock_domain::{account::Account, check_in::Provider};
+use neuradock_domain::{account::Account, check_in::Provider, check_in_timing::CheckInPhase};
 use neuradock_infrastructure::http::{CheckInResult, HttpClient};
 
 use super::execution::create_error_result;
+use super::timing::PhaseTimer;
 use crate::application::services::waf_cookie_manager::WafCookieManager;
 
 /// Retry check-in after refreshing WAF cookies
@@ -16,8 +17,10 @@ pub async fn retry_check_in_after_waf_refresh(
     provider: &Provider,
     account_name: &str,
     sign_in_url: &str,
+    sign_in_body: Option<&serde_json::Value>,
     cookies: &mut HashMap<String, String>,
     api_user: &str,
+    timer: &PhaseTimer,
 ) -> CheckInResult {
     warn!(
         "[{}] WAF challenge detected during check-in, refreshing cookies and retrying...",
@@ -25,8 +28,11 @@ pub async fn retry_check_in_after_waf_refresh(
     );
 
     // Refresh WAF cookies
-    let fresh_cookies = match waf_manager
-        .refresh_waf_cookies(account_name, provider, account.credentials().cookies())
+    let fresh_cookies = match timer
+        .time(
+            CheckInPhase::WafRefresh,
+            waf_manager.refresh_waf_cookies(account, provider),
+        )
         .await
     {
         Ok(fresh) => fresh,
@@ -43,8 +49,21 @@ pub async fn retry_check_in_after_waf_refresh(
     *cookies = fresh_cookies;
 
     // Retry check-in with fresh cookies
-    match http_client
-        .execute_check_in(sign_in_url, cookies, provider.api_user_key(), api_user)
+    match timer
+        .time(
+            CheckInPhase::CheckInRequest,
+            http_client.execute_check_in(
+                sign_in_url,
+                cookies,
+                provider.api_user_key(),
+                api_user,
+                &provider
+                    .extra_headers()
+                    .merged_with(account.extra_headers()),
+                provider.dialect(),
+                sign_in_body,
+            ),
+        )
         .await
     {
         Ok(result) => {
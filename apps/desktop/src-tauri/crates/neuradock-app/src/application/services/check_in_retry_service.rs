@@ -0,0 +1,416 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use log::{error, info, warn};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+use neuradock_domain::account::AccountRepository;
+use neuradock_domain::check_in::ProviderRepository;
+use neuradock_domain::check_in_retry::{CheckInRetry, CheckInRetryRepository, CheckInRetryStatus};
+use neuradock_domain::check_in_timing::CheckInTimingRepository;
+use neuradock_domain::proxy_config::ProxyConfigRepository;
+use neuradock_domain::shared::{AccountId, DomainError, ErrorCode};
+use neuradock_domain::waf_cookies::WafCookiesRepository;
+
+use super::check_in_executor::AccountCheckInResult;
+use super::CheckInExecutor;
+
+/// How often the background worker looks for due retries
+const DRAIN_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Classify an error returned by the check-in executor
+///
+/// Domain errors keep their own code, HTTP transport errors are treated as
+/// transient and everything else is considered permanent.
+pub fn classify_check_in_error(error: &anyhow::Error) -> ErrorCode {
+    for cause in error.chain() {
+        if let Some(domain_error) = cause.downcast_ref::<DomainError>() {
+            return domain_error.code();
+        }
+        if let Some(http_error) = cause.downcast_ref::<reqwest::Error>() {
+            return if http_error.is_timeout() {
+                ErrorCode::TimeoutError
+            } else {
+                ErrorCode::NetworkError
+            };
+        }
+    }
+    ErrorCode::InfrastructureError
+}
+
+/// Persistent retry queue for check-ins that failed with a recoverable error
+pub struct CheckInRetryService {
+    retry_repo: Arc<dyn CheckInRetryRepository>,
+}
+
+impl CheckInRetryService {
+    pub fn new(retry_repo: Arc<dyn CheckInRetryRepository>) -> Self {
+        Self { retry_repo }
+    }
+
+    /// Record the outcome of a check-in attempt
+    ///
+    /// Successful check-ins clear the queued retry of the account, failed ones
+    /// are queued (or rescheduled) when the error is recoverable.
+    pub async fn record_outcome(
+        &self,
+        account_id: &AccountId,
+        outcome: &Result<AccountCheckInResult>,
+        now: DateTime<Utc>,
+    ) -> Result<(), DomainError> {
+        match outcome {
+            Ok(result) if result.success => self.retry_repo.delete(account_id).await,
+            Ok(result) => {
+                let code = result.error_code.unwrap_or(ErrorCode::CheckInFailed);
+                self.record_failure(account_id, code, &result.message, now)
+                    .await
+                    .map(|_| ())
+            }
+            Err(e) => self
+                .record_failure(account_id, classify_check_in_error(e), &e.to_string(), now)
+                .await
+                .map(|_| ()),
+        }
+    }
+
+    /// Record a failed check-in, returning whether a retry is pending afterwards
+    pub async fn record_failure(
+        &self,
+        account_id: &AccountId,
+        code: ErrorCode,
+        error: &str,
+        now: DateTime<Utc>,
+    ) -> Result<bool, DomainError> {
+        let existing = self
+            .retry_repo
+            .find_by_account_id(account_id)
+            .await?
+            .filter(|retry| retry.status() == CheckInRetryStatus::Pending);
+
+        let retry = match existing {
+            Some(mut retry) => {
+                retry.record_failure(code, error, now);
+                retry
+            }
+            None => match CheckInRetry::for_failure(account_id.clone(), code, error, now) {
+                Some(retry) => retry,
+                None => {
+                    info!(
+                        "Check-in failure for {} is not recoverable ({:?}), not queueing a retry",
+                        account_id, code
+                    );
+                    return Ok(false);
+                }
+            },
+        };
+
+        self.retry_repo.save(&retry).await?;
+
+        let pending = retry.status() == CheckInRetryStatus::Pending;
+        if pending {
+            info!(
+                "Queued check-in retry for {} (attempt {}), next attempt at {}",
+                account_id,
+                retry.attempts() + 1,
+                retry.next_attempt_at()
+            );
+        } else {
+            warn!(
+                "Giving up on check-in retry for {} after {} attempts: {}",
+                account_id,
+                retry.attempts(),
+                retry.last_error()
+            );
+        }
+
+        Ok(pending)
+    }
+
+    /// Run every retry due at `now`, in next-attempt order
+    ///
+    /// Returns the number of retries attempted.
+    pub async fn drain_due<F, Fut>(
+        &self,
+        now: DateTime<Utc>,
+        attempt: F,
+    ) -> Result<usize, DomainError>
+    where
+        F: Fn(AccountId) -> Fut,
+        Fut: Future<Output = Result<AccountCheckInResult>>,
+    {
+        let due = self.retry_repo.find_due(now).await?;
+
+        for retry in &due {
+            let account_id = retry.account_id().clone();
+            info!(
+                "Retrying check-in for {} (attempt {})",
+                account_id,
+                retry.attempts() + 1
+            );
+
+            let outcome = attempt(account_id.clone()).await;
+            self.record_outcome(&account_id, &outcome, Utc::now())
+                .await?;
+        }
+
+        Ok(due.len())
+    }
+
+    /// Spawn the background worker that drains due retries
+    pub fn spawn_worker(self: Arc<Self>, executor: RetryExecutor) -> JoinHandle<()> {
+        let executor = Arc::new(executor);
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(DRAIN_INTERVAL);
+            loop {
+                interval.tick().await;
+
+                let executor = executor.clone();
+                let drained = self
+                    .drain_due(Utc::now(), move |account_id| {
+                        let executor = executor.clone();
+                        async move { executor.execute(&account_id).await }
+                    })
+                    .await;
+
+                match drained {
+                    Ok(0) => {}
+                    Ok(count) => info!("Processed {} queued check-in retries", count),
+                    Err(e) => error!("Failed to drain check-in retry queue: {}", e),
+                }
+            }
+        })
+    }
+}
+
+/// Runs queued check-ins for the retry worker
+pub struct RetryExecutor {
+    account_repo: Arc<dyn AccountRepository>,
+    provider_repo: Arc<dyn ProviderRepository>,
+    proxy_config_repo: Arc<dyn ProxyConfigRepository>,
+    waf_cookies_repo: Arc<dyn WafCookiesRepository>,
+    timing_repo: Option<Arc<dyn CheckInTimingRepository>>,
+    headless_browser: bool,
+}
+
+impl RetryExecutor {
+    pub fn new(
+        account_repo: Arc<dyn AccountRepository>,
+        provider_repo: Arc<dyn ProviderRepository>,
+        proxy_config_repo: Arc<dyn ProxyConfigRepository>,
+        waf_cookies_repo: Arc<dyn WafCookiesRepository>,
+        headless_browser: bool,
+    ) -> Self {
+        Self {
+            account_repo,
+            provider_repo,
+            proxy_config_repo,
+            waf_cookies_repo,
+            timing_repo: None,
+            headless_browser,
+        }
+    }
+
+    /// Record the phase timings of each retried check-in
+    pub fn with_timing_repo(mut self, repo: Arc<dyn CheckInTimingRepository>) -> Self {
+        self.timing_repo = Some(repo);
+        self
+    }
+
+    async fn execute(&self, account_id: &AccountId) -> Result<AccountCheckInResult> {
+        let account = self
+            .account_repo
+            .find_by_id(account_id)
+            .await?
+            .ok_or_else(|| DomainError::AccountNotFound(account_id.to_string()))?;
+        let provider = self
+            .provider_repo
+            .find_by_id(account.provider_id())
+            .await?
+            .ok_or_else(|| DomainError::ProviderNotFound(account.provider_id().to_string()))?;
+        let proxy_url = self.proxy_config_repo.get().await?.proxy_url();
+
+        let mut executor = CheckInExecutor::with_proxy(
+            self.account_repo.clone(),
+            self.headless_browser,
+            proxy_url,
+        )?
+        .with_waf_cookies_repo(self.waf_cookies_repo.clone());
+        if let Some(repo) = &self.timing_repo {
+            executor = executor.with_timing_repo(repo.clone());
+        }
+
+        executor
+            .execute_check_in(account_id.as_str(), &provider)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct InMemoryRetryRepository {
+        retries: Mutex<HashMap<String, CheckInRetry>>,
+    }
+
+    #[async_trait]
+    impl CheckInRetryRepository for InMemoryRetryRepository {
+        async fn save(&self, retry: &CheckInRetry) -> Result<(), DomainError> {
+            self.retries
+                .lock()
+                .unwrap()
+                .insert(retry.account_id().to_string(), retry.clone());
+            Ok(())
+        }
+
+        async fn find_by_account_id(
+            &self,
+            account_id: &AccountId,
+        ) -> Result<Option<CheckInRetry>, DomainError> {
+            Ok(self
+                .retries
+                .lock()
+                .unwrap()
+                .get(account_id.as_str())
+                .cloned())
+        }
+
+        async fn find_due(&self, now: DateTime<Utc>) -> Result<Vec<CheckInRetry>, DomainError> {
+            let mut due: Vec<CheckInRetry> = self
+                .retries
+                .lock()
+                .unwrap()
+                .values()
+                .filter(|retry| retry.is_due(now))
+                .cloned()
+                .collect();
+            due.sort_by_key(|retry| retry.next_attempt_at());
+            Ok(due)
+        }
+
+        async fn delete(&self, account_id: &AccountId) -> Result<(), DomainError> {
+            self.retries.lock().unwrap().remove(account_id.as_str());
+            Ok(())
+        }
+    }
+
+    fn service() -> (CheckInRetryService, Arc<InMemoryRetryRepository>) {
+        let repo = Arc::new(InMemoryRetryRepository::default());
+        (CheckInRetryService::new(repo.clone()), repo)
+    }
+
+    fn failed(error_code: ErrorCode) -> Result<AccountCheckInResult> {
+        Ok(AccountCheckInResult {
+            account_name: "Test Account".to_string(),
+            success: false,
+            message: "failed".to_string(),
+            reward: None,
+            user_info: None,
+            error_code: Some(error_code),
+        })
+    }
+
+    fn succeeded() -> Result<AccountCheckInResult> {
+        Ok(AccountCheckInResult {
+            account_name: "Test Account".to_string(),
+            success: true,
+            message: "ok".to_string(),
+            reward: None,
+            user_info: None,
+            error_code: None,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_recoverable_failure_is_enqueued() {
+        let (service, repo) = service();
+        let account_id = AccountId::new();
+
+        service
+            .record_outcome(&account_id, &failed(ErrorCode::NetworkError), Utc::now())
+            .await
+            .unwrap();
+
+        let retry = repo.find_by_account_id(&account_id).await.unwrap().unwrap();
+        assert_eq!(retry.status(), CheckInRetryStatus::Pending);
+        assert_eq!(retry.attempts(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_permanent_failure_is_not_enqueued() {
+        let (service, repo) = service();
+        let account_id = AccountId::new();
+
+        service
+            .record_outcome(&account_id, &failed(ErrorCode::ValidationError), Utc::now())
+            .await
+            .unwrap();
+        let outcome = Err(anyhow::Error::new(DomainError::InvalidCredentials(
+            "session expired".to_string(),
+        )));
+        service
+            .record_outcome(&account_id, &outcome, Utc::now())
+            .await
+            .unwrap();
+
+        assert!(repo
+            .find_by_account_id(&account_id)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_drain_runs_due_retries_by_next_attempt_time() {
+        let (service, repo) = service();
+        let now = Utc::now();
+        let later = AccountId::new();
+        let earliest = AccountId::new();
+        let not_due = AccountId::new();
+
+        for (account_id, queued_at) in [
+            (&later, now - chrono::Duration::minutes(10)),
+            (&earliest, now - chrono::Duration::minutes(30)),
+            (&not_due, now),
+        ] {
+            service
+                .record_failure(account_id, ErrorCode::TimeoutError, "timeout", queued_at)
+                .await
+                .unwrap();
+        }
+
+        let attempted = Mutex::new(Vec::new());
+        let drained = service
+            .drain_due(now, |account_id| {
+                attempted.lock().unwrap().push(account_id.clone());
+                let outcome = if account_id == earliest {
+                    succeeded()
+                } else {
+                    failed(ErrorCode::InvalidCredentials)
+                };
+                async move { outcome }
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(drained, 2);
+        assert_eq!(
+            attempted.into_inner().unwrap(),
+            vec![earliest.clone(), later.clone()]
+        );
+
+        // success removes the entry, a permanent failure marks it failed
+        assert!(repo.find_by_account_id(&earliest).await.unwrap().is_none());
+        let failed_retry = repo.find_by_account_id(&later).await.unwrap().unwrap();
+        assert_eq!(failed_retry.status(), CheckInRetryStatus::Failed);
+        let pending = repo.find_by_account_id(&not_due).await.unwrap().unwrap();
+        assert_eq!(pending.status(), CheckInRetryStatus::Pending);
+    }
+}
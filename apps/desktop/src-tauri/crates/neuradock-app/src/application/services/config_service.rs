@@ -1,10 +1,34 @@
 use anyhow::Result;
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
-use std::sync::atomic::{AtomicU8, Ordering};
-use std::sync::Arc;
+use specta::Type;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU8, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
 use tauri::{AppHandle, Manager};
-use tracing::info;
+use tracing::{info, warn};
+
+use super::i18n;
+
+use neuradock_domain::notification::{Locale, NotificationTemplates};
+use neuradock_infrastructure::config::{
+    BrowserSettings, HttpSettings, WafBypassTuning, WafSettings,
+};
+use neuradock_infrastructure::http::capture::MAX_CAPTURE_REQUESTS;
+use neuradock_infrastructure::http::waf_bypass::{validate_browser_args, validate_browser_path};
+use neuradock_infrastructure::http::RequestCapture;
+use neuradock_infrastructure::logging;
+
+/// Most streak freezes a month can have, one for every day
+pub const MAX_STREAK_FREEZES_PER_MONTH: u32 = 31;
+
+/// Largest response body limit that can be configured, in MiB
+pub const MAX_RESPONSE_BODY_MB: u32 = 1024;
+
+/// Longest idle time before the app lock engages, one day
+pub const MAX_APP_LOCK_IDLE_MINUTES: u32 = 24 * 60;
 
 /// Log level configuration
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -40,18 +64,218 @@ impl LogLevel {
             LogLevel::Trace => "trace",
         }
     }
+
+    /// Parse a level name, case-insensitive
+    pub fn parse(level: &str) -> Option<Self> {
+        match level.trim().to_lowercase().as_str() {
+            "error" => Some(LogLevel::Error),
+            "warn" => Some(LogLevel::Warn),
+            "info" => Some(LogLevel::Info),
+            "debug" => Some(LogLevel::Debug),
+            "trace" => Some(LogLevel::Trace),
+            _ => None,
+        }
+    }
+
+    pub fn to_tracing_level(self) -> tracing::Level {
+        match self {
+            LogLevel::Error => tracing::Level::ERROR,
+            LogLevel::Warn => tracing::Level::WARN,
+            LogLevel::Info => tracing::Level::INFO,
+            LogLevel::Debug => tracing::Level::DEBUG,
+            LogLevel::Trace => tracing::Level::TRACE,
+        }
+    }
+}
+
+/// Browser timing used while waiting for WAF cookies, and tabs per batch browser
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(default)]
+pub struct WafBypassConfig {
+    /// Maximum time for the login page navigation
+    pub navigation_timeout_secs: u32,
+    /// Delay after the page loaded before cookies are first read
+    pub settle_delay_ms: u32,
+    /// Deadline for a whole bypass attempt; cookies are polled until then
+    pub max_total_secs: u32,
+    /// Cookie names to wait for (empty = built-in WAF cookie names)
+    pub wait_for_cookies: Vec<String>,
+    /// Browser tabs solving challenges in parallel during a batch check-in
+    pub tab_concurrency: u32,
+}
+
+impl Default for WafBypassConfig {
+    fn default() -> Self {
+        let tuning = WafBypassTuning::DEFAULT;
+        Self {
+            navigation_timeout_secs: tuning.navigation_timeout.as_secs() as u32,
+            settle_delay_ms: tuning.settle_delay.as_millis() as u32,
+            max_total_secs: tuning.max_total_time.as_secs() as u32,
+            wait_for_cookies: Vec::new(),
+            tab_concurrency: tuning.tab_concurrency as u32,
+        }
+    }
+}
+
+impl WafBypassConfig {
+    const MAX_NAVIGATION_TIMEOUT_SECS: u32 = 300;
+    const MAX_SETTLE_DELAY_MS: u32 = 60_000;
+    const MAX_TOTAL_SECS: u32 = 600;
+    const MAX_TAB_CONCURRENCY: u32 = 8;
+
+    /// Validate ranges and normalize the cookie names
+    fn normalized(mut self) -> Result<Self> {
+        if !(1..=Self::MAX_NAVIGATION_TIMEOUT_SECS).contains(&self.navigation_timeout_secs) {
+            anyhow::bail!(
+                "Navigation timeout must be between 1 and {} seconds",
+                Self::MAX_NAVIGATION_TIMEOUT_SECS
+            );
+        }
+        if self.settle_delay_ms > Self::MAX_SETTLE_DELAY_MS {
+            anyhow::bail!(
+                "Settle delay must be at most {} ms",
+                Self::MAX_SETTLE_DELAY_MS
+            );
+        }
+        if !(1..=Self::MAX_TOTAL_SECS).contains(&self.max_total_secs) {
+            anyhow::bail!(
+                "Maximum total time must be between 1 and {} seconds",
+                Self::MAX_TOTAL_SECS
+            );
+        }
+        if u64::from(self.settle_delay_ms) >= u64::from(self.max_total_secs) * 1000 {
+            anyhow::bail!("Settle delay must be shorter than the maximum total time");
+        }
+        if !(1..=Self::MAX_TAB_CONCURRENCY).contains(&self.tab_concurrency) {
+            anyhow::bail!(
+                "Tab concurrency must be between 1 and {}",
+                Self::MAX_TAB_CONCURRENCY
+            );
+        }
+
+        let mut cookies = Vec::new();
+        for name in self.wait_for_cookies.iter().map(|name| name.trim()) {
+            if name.is_empty() || cookies.iter().any(|c| c == name) {
+                continue;
+            }
+            if name.contains(['=', ';']) || name.chars().any(char::is_whitespace) {
+                anyhow::bail!("Invalid cookie name: {:?}", name);
+            }
+            cookies.push(name.to_string());
+        }
+        self.wait_for_cookies = cookies;
+
+        Ok(self)
+    }
+
+    fn to_tuning(&self) -> WafBypassTuning {
+        WafBypassTuning {
+            navigation_timeout: Duration::from_secs(self.navigation_timeout_secs.into()),
+            settle_delay: Duration::from_millis(self.settle_delay_ms.into()),
+            max_total_time: Duration::from_secs(self.max_total_secs.into()),
+            wait_for_cookies: self.wait_for_cookies.clone(),
+            tab_concurrency: self.tab_concurrency as usize,
+        }
+    }
+}
+
+/// Passphrase that guards credential-revealing commands
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppLockConfig {
+    /// Argon2 PHC hash of the passphrase (None = app lock off)
+    pub passphrase_hash: Option<String>,
+    /// Minutes without guarded commands before locking again (0 = only at launch)
+    pub idle_timeout_minutes: u32,
+}
+
+impl Default for AppLockConfig {
+    fn default() -> Self {
+        Self {
+            passphrase_hash: None,
+            idle_timeout_minutes: 15,
+        }
+    }
 }
 
 /// Persistent configuration structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct AppConfig {
     log_level: LogLevel,
+    /// Level overrides by module prefix, e.g. `neuradock_infrastructure::http` → `trace`
+    #[serde(default)]
+    module_log_levels: BTreeMap<String, LogLevel>,
+    /// Browser executable used for WAF bypass (None = auto-detect)
+    #[serde(default)]
+    browser_path: Option<String>,
+    /// Providers whose cached WAF cookies are used without a validation request
+    #[serde(default)]
+    waf_validation_skip_providers: Vec<String>,
+    /// Providers whose WAF cookies are cached per account instead of per domain
+    #[serde(default)]
+    waf_per_account_cookie_providers: Vec<String>,
+    /// Reuse a per-provider browser profile for WAF bypass instead of a temp profile
+    #[serde(default)]
+    persistent_browser_profile: bool,
+    /// Extra Chromium flags for WAF bypass, e.g. `--disable-gpu` on headless servers
+    #[serde(default)]
+    browser_extra_args: Vec<String>,
+    /// WAF bypass navigation timeout and cookie wait strategy
+    #[serde(default)]
+    waf_bypass: WafBypassConfig,
+    /// Run database maintenance in the background once a week
+    #[serde(default)]
+    weekly_db_maintenance: bool,
+    /// Last completed database maintenance run
+    #[serde(default)]
+    last_db_maintenance_at: Option<DateTime<Utc>>,
+    /// Local time the daily summary is sent at (None = disabled)
+    #[serde(default)]
+    daily_summary_time: Option<NaiveTime>,
+    /// Local date the last daily summary was sent for
+    #[serde(default)]
+    last_daily_summary_on: Option<NaiveDate>,
+    /// Check-in notification templates for channels without their own
+    #[serde(default)]
+    notification_templates: NotificationTemplates,
+    /// Locale of notifications for channels without their own (None = follow the system)
+    #[serde(default)]
+    notification_locale: Option<Locale>,
+    /// Missed days per month that do not break a check-in streak
+    #[serde(default)]
+    streak_freezes_per_month: u32,
+    /// Provider requests still to be captured to the debug file (0 = off)
+    #[serde(default)]
+    request_capture_remaining: u32,
+    /// Largest provider response body read, in MiB (None = built-in default)
+    #[serde(default)]
+    max_response_body_mb: Option<u32>,
+    /// Passphrase lock for credential-revealing commands
+    #[serde(default)]
+    app_lock: AppLockConfig,
 }
 
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
             log_level: LogLevel::Info,
+            module_log_levels: BTreeMap::new(),
+            browser_path: None,
+            waf_validation_skip_providers: Vec::new(),
+            waf_per_account_cookie_providers: Vec::new(),
+            persistent_browser_profile: false,
+            browser_extra_args: Vec::new(),
+            waf_bypass: WafBypassConfig::default(),
+            weekly_db_maintenance: false,
+            last_db_maintenance_at: None,
+            daily_summary_time: None,
+            last_daily_summary_on: None,
+            notification_templates: NotificationTemplates::default(),
+            notification_locale: None,
+            streak_freezes_per_month: 0,
+            request_capture_remaining: 0,
+            max_response_body_mb: None,
+            app_lock: AppLockConfig::default(),
         }
     }
 }
@@ -59,6 +283,22 @@ impl Default for AppConfig {
 /// Application configuration service
 pub struct ConfigService {
     log_level: Arc<AtomicU8>,
+    module_log_levels: RwLock<BTreeMap<String, LogLevel>>,
+    browser_path: RwLock<Option<String>>,
+    waf_validation_skip_providers: RwLock<Vec<String>>,
+    waf_per_account_cookie_providers: RwLock<Vec<String>>,
+    persistent_browser_profile: AtomicBool,
+    browser_extra_args: RwLock<Vec<String>>,
+    waf_bypass: RwLock<WafBypassConfig>,
+    weekly_db_maintenance: AtomicBool,
+    last_db_maintenance_at: RwLock<Option<DateTime<Utc>>>,
+    daily_summary_time: RwLock<Option<NaiveTime>>,
+    last_daily_summary_on: RwLock<Option<NaiveDate>>,
+    notification_templates: RwLock<NotificationTemplates>,
+    notification_locale: RwLock<Option<Locale>>,
+    streak_freezes_per_month: AtomicU32,
+    max_response_body_mb: RwLock<Option<u32>>,
+    app_lock: RwLock<AppLockConfig>,
     config_path: PathBuf,
 }
 
@@ -87,8 +327,123 @@ impl ConfigService {
         info!("📁 Config loaded from: {:?}", config_path);
         info!("🔧 Initial log level: {}", config.log_level.as_str());
 
+        // A configured browser that has since been removed falls back to auto-detection
+        if let Some(path) = config.browser_path.as_deref() {
+            match validate_browser_path(path) {
+                Ok(browser_path) => {
+                    info!("🌐 Configured browser path: {:?}", browser_path);
+                    BrowserSettings::update(|s| s.browser_path = Some(browser_path));
+                }
+                Err(e) => warn!("⚠️  Ignoring configured browser path: {}", e),
+            }
+        }
+
+        // Persistent WAF browser profiles live under the app data directory
+        let profiles_dir = app_handle
+            .path()
+            .app_data_dir()
+            .map_err(|e| anyhow::anyhow!("Failed to get app data dir: {}", e))?
+            .join("browser_profiles");
+        let persistent_profile = config.persistent_browser_profile;
+        BrowserSettings::update(|s| {
+            s.profiles_dir = Some(profiles_dir);
+            s.persistent_profile = persistent_profile;
+        });
+
+        // A hand-edited flag list that no longer validates is dropped as a whole
+        let browser_extra_args = validate_browser_args(config.browser_extra_args.clone())
+            .unwrap_or_else(|e| {
+                warn!("⚠️  Ignoring configured browser arguments: {}", e);
+                Vec::new()
+            });
+        let extra_args = browser_extra_args.clone();
+        BrowserSettings::update(|s| s.extra_args = extra_args);
+
+        let skip_providers = config.waf_validation_skip_providers.clone();
+        WafSettings::update(|s| s.skip_validation_providers = skip_providers.into_iter().collect());
+        let per_account_providers = config.waf_per_account_cookie_providers.clone();
+        WafSettings::update(|s| {
+            s.per_account_cookie_providers = per_account_providers.into_iter().collect()
+        });
+
+        // A hand-edited config with out-of-range values falls back to the defaults
+        let waf_bypass = config.waf_bypass.clone().normalized().unwrap_or_else(|e| {
+            warn!("⚠️  Ignoring WAF bypass settings: {}", e);
+            WafBypassConfig::default()
+        });
+        let tuning = waf_bypass.to_tuning();
+        WafSettings::update(|s| s.bypass = tuning);
+
+        i18n::set_default_locale(config.notification_locale);
+
+        // A hand-edited limit out of range falls back to the default
+        let max_response_body_mb = config.max_response_body_mb.filter(|mb| {
+            let valid = (1..=MAX_RESPONSE_BODY_MB).contains(mb);
+            if !valid {
+                warn!("⚠️  Ignoring response body limit of {} MiB", mb);
+            }
+            valid
+        });
+        HttpSettings::update(|s| {
+            s.max_response_body_bytes = response_body_bytes(max_response_body_mb)
+        });
+
+        // A capture left armed by the previous session keeps its remaining requests
+        if config.request_capture_remaining > 0 {
+            match logging::get_log_dir() {
+                Some(log_dir) => {
+                    RequestCapture::global().start(&log_dir, config.request_capture_remaining)
+                }
+                None => warn!("⚠️  Request capture needs file logging, not resuming it"),
+            }
+        }
+
+        // A hand-edited module that is not a module path is dropped on its own
+        let module_log_levels: BTreeMap<String, LogLevel> = config
+            .module_log_levels
+            .into_iter()
+            .filter_map(
+                |(module, level)| match logging::normalize_log_module(&module) {
+                    Ok(module) => Some((module, level)),
+                    Err(e) => {
+                        warn!("⚠️  Ignoring module log level: {}", e);
+                        None
+                    }
+                },
+            )
+            .collect();
+        if let Err(e) = apply_module_log_levels(&module_log_levels) {
+            warn!("⚠️  Failed to apply module log levels: {}", e);
+        }
+
         Ok(Self {
             log_level: Arc::new(AtomicU8::new(config.log_level as u8)),
+            module_log_levels: RwLock::new(module_log_levels),
+            browser_path: RwLock::new(config.browser_path),
+            waf_validation_skip_providers: RwLock::new(config.waf_validation_skip_providers),
+            waf_per_account_cookie_providers: RwLock::new(config.waf_per_account_cookie_providers),
+            persistent_browser_profile: AtomicBool::new(config.persistent_browser_profile),
+            browser_extra_args: RwLock::new(browser_extra_args),
+            waf_bypass: RwLock::new(waf_bypass),
+            weekly_db_maintenance: AtomicBool::new(config.weekly_db_maintenance),
+            last_db_maintenance_at: RwLock::new(config.last_db_maintenance_at),
+            daily_summary_time: RwLock::new(config.daily_summary_time),
+            last_daily_summary_on: RwLock::new(config.last_daily_summary_on),
+            notification_templates: RwLock::new(config.notification_templates),
+            notification_locale: RwLock::new(config.notification_locale),
+            streak_freezes_per_month: AtomicU32::new(
+                config
+                    .streak_freezes_per_month
+                    .min(MAX_STREAK_FREEZES_PER_MONTH),
+            ),
+            max_response_body_mb: RwLock::new(max_response_body_mb),
+            app_lock: RwLock::new(AppLockConfig {
+                idle_timeout_minutes: config
+                    .app_lock
+                    .idle_timeout_minutes
+                    .min(MAX_APP_LOCK_IDLE_MINUTES),
+                ..config.app_lock
+            }),
             config_path,
         })
     }
@@ -105,16 +460,575 @@ impl ConfigService {
         self.log_level.store(level as u8, Ordering::Relaxed);
 
         // Persist to disk
-        let config = AppConfig { log_level: level };
-
-        let content = serde_json::to_string_pretty(&config)?;
-        std::fs::write(&self.config_path, content)?;
+        self.persist()?;
 
         info!("💾 Log level saved to: {:?}", self.config_path);
         info!("⚠️  Log level will take effect on next app restart");
 
         Ok(())
     }
+
+    /// Level overrides by module prefix
+    pub fn get_module_log_levels(&self) -> BTreeMap<String, LogLevel> {
+        self.module_log_levels
+            .read()
+            .map(|levels| levels.clone())
+            .unwrap_or_default()
+    }
+
+    /// Override the level of a module prefix, applied immediately and persisted to disk
+    pub fn set_module_log_level(&self, module: &str, level: LogLevel) -> Result<()> {
+        let module = logging::normalize_log_module(module)?;
+        info!("🔧 Changing log level of {} to: {}", module, level.as_str());
+        self.update_module_log_levels(|levels| {
+            levels.insert(module, level);
+        })
+    }
+
+    /// Remove the level override of a module prefix, applied immediately and persisted to disk
+    pub fn clear_module_log_level(&self, module: &str) -> Result<()> {
+        let module = module.trim();
+        info!("🔧 Clearing log level of {}", module);
+        self.update_module_log_levels(|levels| {
+            levels.remove(module);
+        })
+    }
+
+    /// Replace all module level overrides, applied immediately and persisted to disk
+    pub fn set_module_log_levels(&self, levels: BTreeMap<String, LogLevel>) -> Result<()> {
+        let levels = levels
+            .into_iter()
+            .map(|(module, level)| Ok((logging::normalize_log_module(&module)?, level)))
+            .collect::<Result<BTreeMap<_, _>>>()?;
+        self.update_module_log_levels(|current| *current = levels)
+    }
+
+    fn update_module_log_levels(
+        &self,
+        update: impl FnOnce(&mut BTreeMap<String, LogLevel>),
+    ) -> Result<()> {
+        {
+            let mut levels = self
+                .module_log_levels
+                .write()
+                .map_err(|_| anyhow::anyhow!("Module log levels lock poisoned"))?;
+            update(&mut levels);
+            apply_module_log_levels(&levels)?;
+        }
+
+        self.persist()?;
+        info!("💾 Module log levels saved to: {:?}", self.config_path);
+
+        Ok(())
+    }
+
+    /// Get the configured browser path (None = auto-detect)
+    pub fn get_browser_path(&self) -> Option<String> {
+        self.browser_path
+            .read()
+            .map(|path| path.clone())
+            .unwrap_or_default()
+    }
+
+    /// Set or clear the browser path used for WAF bypass and persist to disk
+    pub fn set_browser_path(&self, path: Option<String>) -> Result<()> {
+        let browser_path = match path.as_deref().map(str::trim) {
+            Some(path) if !path.is_empty() => Some(validate_browser_path(path)?),
+            _ => None,
+        };
+
+        info!("🔧 Changing browser path to: {:?}", browser_path);
+        let stored = browser_path
+            .as_ref()
+            .map(|path| path.to_string_lossy().to_string());
+        *self
+            .browser_path
+            .write()
+            .map_err(|_| anyhow::anyhow!("Browser path lock poisoned"))? = stored;
+        BrowserSettings::update(|s| s.browser_path = browser_path);
+
+        self.persist()?;
+        info!("💾 Browser path saved to: {:?}", self.config_path);
+
+        Ok(())
+    }
+
+    /// Whether WAF bypass reuses a persistent per-provider browser profile
+    pub fn get_persistent_browser_profile(&self) -> bool {
+        self.persistent_browser_profile.load(Ordering::Relaxed)
+    }
+
+    /// Enable or disable persistent browser profiles and persist to disk
+    pub fn set_persistent_browser_profile(&self, enabled: bool) -> Result<()> {
+        info!("🔧 Changing persistent browser profile to: {}", enabled);
+        self.persistent_browser_profile
+            .store(enabled, Ordering::Relaxed);
+        BrowserSettings::update(|s| s.persistent_profile = enabled);
+
+        self.persist()?;
+        info!(
+            "💾 Browser profile setting saved to: {:?}",
+            self.config_path
+        );
+
+        Ok(())
+    }
+
+    /// Get the extra Chromium flags used for WAF bypass
+    pub fn get_browser_extra_args(&self) -> Vec<String> {
+        self.browser_extra_args
+            .read()
+            .map(|args| args.clone())
+            .unwrap_or_default()
+    }
+
+    /// Validate and set the extra Chromium flags used for WAF bypass and persist to disk
+    pub fn set_browser_extra_args(&self, args: Vec<String>) -> Result<()> {
+        let args = validate_browser_args(args)?;
+
+        info!("🔧 Changing browser arguments to: {:?}", args);
+        *self
+            .browser_extra_args
+            .write()
+            .map_err(|_| anyhow::anyhow!("Browser arguments lock poisoned"))? = args.clone();
+        BrowserSettings::update(|s| s.extra_args = args);
+
+        self.persist()?;
+        info!("💾 Browser arguments saved to: {:?}", self.config_path);
+
+        Ok(())
+    }
+
+    /// Get providers whose cached WAF cookies skip validation
+    pub fn get_waf_validation_skip_providers(&self) -> Vec<String> {
+        self.waf_validation_skip_providers
+            .read()
+            .map(|providers| providers.clone())
+            .unwrap_or_default()
+    }
+
+    /// Set providers whose cached WAF cookies skip validation and persist to disk
+    pub fn set_waf_validation_skip_providers(&self, provider_ids: Vec<String>) -> Result<()> {
+        let provider_ids = normalize_provider_ids(provider_ids);
+
+        info!(
+            "🔧 Changing WAF validation skip list to: {:?}",
+            provider_ids
+        );
+        *self
+            .waf_validation_skip_providers
+            .write()
+            .map_err(|_| anyhow::anyhow!("WAF settings lock poisoned"))? = provider_ids.clone();
+        WafSettings::update(|s| s.skip_validation_providers = provider_ids.into_iter().collect());
+
+        self.persist()?;
+        info!("💾 WAF settings saved to: {:?}", self.config_path);
+
+        Ok(())
+    }
+
+    /// Get providers whose WAF cookies are cached per account
+    pub fn get_waf_per_account_cookie_providers(&self) -> Vec<String> {
+        self.waf_per_account_cookie_providers
+            .read()
+            .map(|providers| providers.clone())
+            .unwrap_or_default()
+    }
+
+    /// Set providers whose WAF cookies are cached per account and persist to disk
+    pub fn set_waf_per_account_cookie_providers(&self, provider_ids: Vec<String>) -> Result<()> {
+        let provider_ids = normalize_provider_ids(provider_ids);
+
+        info!(
+            "🔧 Changing per-account WAF cookie providers to: {:?}",
+            provider_ids
+        );
+        *self
+            .waf_per_account_cookie_providers
+            .write()
+            .map_err(|_| anyhow::anyhow!("WAF settings lock poisoned"))? = provider_ids.clone();
+        WafSettings::update(|s| {
+            s.per_account_cookie_providers = provider_ids.into_iter().collect()
+        });
+
+        self.persist()?;
+        info!("💾 WAF settings saved to: {:?}", self.config_path);
+
+        Ok(())
+    }
+
+    /// Get the WAF bypass navigation timeout and cookie wait settings
+    pub fn get_waf_bypass_config(&self) -> WafBypassConfig {
+        self.waf_bypass
+            .read()
+            .map(|config| config.clone())
+            .unwrap_or_default()
+    }
+
+    /// Set the WAF bypass navigation timeout and cookie wait settings and persist to disk
+    pub fn set_waf_bypass_config(&self, config: WafBypassConfig) -> Result<()> {
+        let config = config.normalized()?;
+
+        info!("🔧 Changing WAF bypass settings to: {:?}", config);
+        let tuning = config.to_tuning();
+        *self
+            .waf_bypass
+            .write()
+            .map_err(|_| anyhow::anyhow!("WAF settings lock poisoned"))? = config;
+        WafSettings::update(|s| s.bypass = tuning);
+
+        self.persist()?;
+        info!("💾 WAF settings saved to: {:?}", self.config_path);
+
+        Ok(())
+    }
+
+    /// Whether database maintenance runs in the background once a week
+    pub fn get_weekly_db_maintenance(&self) -> bool {
+        self.weekly_db_maintenance.load(Ordering::Relaxed)
+    }
+
+    /// Enable or disable weekly database maintenance and persist to disk
+    pub fn set_weekly_db_maintenance(&self, enabled: bool) -> Result<()> {
+        info!("🔧 Changing weekly database maintenance to: {}", enabled);
+        self.weekly_db_maintenance.store(enabled, Ordering::Relaxed);
+
+        self.persist()?;
+        info!("💾 Maintenance setting saved to: {:?}", self.config_path);
+
+        Ok(())
+    }
+
+    /// When database maintenance last completed
+    pub fn get_last_db_maintenance_at(&self) -> Option<DateTime<Utc>> {
+        self.last_db_maintenance_at
+            .read()
+            .map(|at| *at)
+            .unwrap_or_default()
+    }
+
+    /// Remember a completed database maintenance run and persist to disk
+    pub fn record_db_maintenance(&self, at: DateTime<Utc>) -> Result<()> {
+        *self
+            .last_db_maintenance_at
+            .write()
+            .map_err(|_| anyhow::anyhow!("Maintenance settings lock poisoned"))? = Some(at);
+
+        self.persist()
+    }
+
+    /// Local time the daily summary is sent at, `None` when disabled
+    pub fn get_daily_summary_time(&self) -> Option<NaiveTime> {
+        self.daily_summary_time
+            .read()
+            .map(|time| *time)
+            .unwrap_or_default()
+    }
+
+    /// Set or disable the daily summary time and persist to disk
+    pub fn set_daily_summary_time(&self, time: Option<NaiveTime>) -> Result<()> {
+        info!("🔧 Changing daily summary time to: {:?}", time);
+        *self
+            .daily_summary_time
+            .write()
+            .map_err(|_| anyhow::anyhow!("Daily summary settings lock poisoned"))? = time;
+
+        self.persist()?;
+        info!("💾 Daily summary setting saved to: {:?}", self.config_path);
+
+        Ok(())
+    }
+
+    /// Local date the last daily summary was sent for
+    pub fn get_last_daily_summary_on(&self) -> Option<NaiveDate> {
+        self.last_daily_summary_on
+            .read()
+            .map(|day| *day)
+            .unwrap_or_default()
+    }
+
+    /// Remember the day a daily summary was sent for and persist to disk
+    pub fn record_daily_summary(&self, day: NaiveDate) -> Result<()> {
+        *self
+            .last_daily_summary_on
+            .write()
+            .map_err(|_| anyhow::anyhow!("Daily summary settings lock poisoned"))? = Some(day);
+
+        self.persist()
+    }
+
+    /// Check-in notification templates used by channels without their own
+    pub fn get_notification_templates(&self) -> NotificationTemplates {
+        self.notification_templates
+            .read()
+            .map(|templates| templates.clone())
+            .unwrap_or_default()
+    }
+
+    /// Set the global check-in notification templates and persist to disk
+    pub fn set_notification_templates(&self, templates: NotificationTemplates) -> Result<()> {
+        info!("🔧 Changing notification templates to: {:?}", templates);
+        *self
+            .notification_templates
+            .write()
+            .map_err(|_| anyhow::anyhow!("Notification templates lock poisoned"))? = templates;
+
+        self.persist()?;
+        info!("💾 Notification templates saved to: {:?}", self.config_path);
+
+        Ok(())
+    }
+
+    /// Locale of notifications for channels without their own, `None` follows the system
+    pub fn get_notification_locale(&self) -> Option<Locale> {
+        self.notification_locale
+            .read()
+            .map(|locale| *locale)
+            .unwrap_or_default()
+    }
+
+    /// Set the default notification locale and persist to disk
+    pub fn set_notification_locale(&self, locale: Option<Locale>) -> Result<()> {
+        info!("🔧 Changing notification locale to: {:?}", locale);
+        *self
+            .notification_locale
+            .write()
+            .map_err(|_| anyhow::anyhow!("Notification locale lock poisoned"))? = locale;
+        i18n::set_default_locale(locale);
+
+        self.persist()?;
+        info!("💾 Notification locale saved to: {:?}", self.config_path);
+
+        Ok(())
+    }
+
+    /// Missed days per month that do not break a check-in streak
+    pub fn get_streak_freezes_per_month(&self) -> u32 {
+        self.streak_freezes_per_month.load(Ordering::Relaxed)
+    }
+
+    /// Set the streak freezes per month and persist to disk
+    pub fn set_streak_freezes_per_month(&self, freezes: u32) -> Result<()> {
+        if freezes > MAX_STREAK_FREEZES_PER_MONTH {
+            anyhow::bail!(
+                "Streak freezes per month must be at most {}",
+                MAX_STREAK_FREEZES_PER_MONTH
+            );
+        }
+        info!("🔧 Changing streak freezes per month to: {}", freezes);
+        self.streak_freezes_per_month
+            .store(freezes, Ordering::Relaxed);
+
+        self.persist()?;
+        info!("💾 Streak setting saved to: {:?}", self.config_path);
+
+        Ok(())
+    }
+
+    /// Largest provider response body read in MiB, `None` uses the built-in default
+    pub fn get_max_response_body_mb(&self) -> Option<u32> {
+        self.max_response_body_mb
+            .read()
+            .map(|mb| *mb)
+            .unwrap_or_default()
+    }
+
+    /// Set or reset the response body limit and persist to disk
+    pub fn set_max_response_body_mb(&self, mb: Option<u32>) -> Result<()> {
+        if let Some(mb) = mb {
+            if !(1..=MAX_RESPONSE_BODY_MB).contains(&mb) {
+                anyhow::bail!(
+                    "Response body limit must be between 1 and {} MiB",
+                    MAX_RESPONSE_BODY_MB
+                );
+            }
+        }
+        info!("🔧 Changing response body limit to: {:?} MiB", mb);
+        *self
+            .max_response_body_mb
+            .write()
+            .map_err(|_| anyhow::anyhow!("HTTP settings lock poisoned"))? = mb;
+        HttpSettings::update(|s| s.max_response_body_bytes = response_body_bytes(mb));
+
+        self.persist()?;
+        info!("💾 HTTP settings saved to: {:?}", self.config_path);
+
+        Ok(())
+    }
+
+    /// Passphrase hash and idle timeout of the app lock
+    pub fn get_app_lock(&self) -> AppLockConfig {
+        self.app_lock
+            .read()
+            .map(|config| config.clone())
+            .unwrap_or_default()
+    }
+
+    /// Set the app lock settings and persist to disk
+    ///
+    /// Only the app lock service calls this, after checking the current passphrase.
+    pub fn set_app_lock(&self, config: AppLockConfig) -> Result<()> {
+        if config.idle_timeout_minutes > MAX_APP_LOCK_IDLE_MINUTES {
+            anyhow::bail!(
+                "App lock idle timeout must be at most {} minutes",
+                MAX_APP_LOCK_IDLE_MINUTES
+            );
+        }
+        info!(
+            "🔧 Changing app lock to: {} (idle timeout {} min)",
+            if config.passphrase_hash.is_some() {
+                "on"
+            } else {
+                "off"
+            },
+            config.idle_timeout_minutes
+        );
+        *self
+            .app_lock
+            .write()
+            .map_err(|_| anyhow::anyhow!("App lock settings lock poisoned"))? = config;
+
+        self.persist()?;
+        info!("💾 App lock settings saved to: {:?}", self.config_path);
+
+        Ok(())
+    }
+
+    /// Provider requests still to be captured, counting down as they are sent
+    pub fn get_request_capture_remaining(&self) -> u32 {
+        RequestCapture::global().remaining()
+    }
+
+    /// Capture the next `count` provider requests into `log_dir`, 0 turns it off
+    pub fn set_request_capture(&self, log_dir: &Path, count: u32) -> Result<()> {
+        if count > MAX_CAPTURE_REQUESTS {
+            anyhow::bail!(
+                "Request capture is limited to {} requests",
+                MAX_CAPTURE_REQUESTS
+            );
+        }
+        info!("🔧 Changing request capture to the next {} requests", count);
+        if count == 0 {
+            RequestCapture::global().stop();
+        } else {
+            RequestCapture::global().start(log_dir, count);
+        }
+
+        self.persist()?;
+        info!(
+            "💾 Request capture setting saved to: {:?}",
+            self.config_path
+        );
+
+        Ok(())
+    }
+
+    /// Settings carried by a data archive, without machine-specific state
+    pub fn export_settings(&self) -> Result<serde_json::Value> {
+        let mut config = self.snapshot();
+        config.last_db_maintenance_at = None;
+        config.last_daily_summary_on = None;
+        config.request_capture_remaining = 0;
+        config.app_lock = AppLockConfig::default();
+        Ok(serde_json::to_value(config)?)
+    }
+
+    /// Apply settings from a data archive and persist them
+    ///
+    /// A browser path, browser flags or WAF settings that do not validate on
+    /// this machine are skipped and keep their current value. The app lock is
+    /// never exported and stays as it is.
+    pub fn import_settings(&self, settings: serde_json::Value) -> Result<()> {
+        let config: AppConfig = serde_json::from_value(settings)?;
+
+        self.set_log_level(config.log_level)?;
+        if let Err(e) = self.set_module_log_levels(config.module_log_levels) {
+            warn!("⚠️  Skipping imported module log levels: {}", e);
+        }
+        if let Err(e) = self.set_browser_path(config.browser_path) {
+            warn!("⚠️  Skipping imported browser path: {}", e);
+        }
+        if let Err(e) = self.set_browser_extra_args(config.browser_extra_args) {
+            warn!("⚠️  Skipping imported browser arguments: {}", e);
+        }
+        self.set_persistent_browser_profile(config.persistent_browser_profile)?;
+        self.set_waf_validation_skip_providers(config.waf_validation_skip_providers)?;
+        self.set_waf_per_account_cookie_providers(config.waf_per_account_cookie_providers)?;
+        if let Err(e) = self.set_waf_bypass_config(config.waf_bypass) {
+            warn!("⚠️  Skipping imported WAF bypass settings: {}", e);
+        }
+        self.set_weekly_db_maintenance(config.weekly_db_maintenance)?;
+        self.set_daily_summary_time(config.daily_summary_time)?;
+        self.set_notification_templates(config.notification_templates)?;
+        self.set_notification_locale(config.notification_locale)?;
+        if let Err(e) = self.set_streak_freezes_per_month(config.streak_freezes_per_month) {
+            warn!("⚠️  Skipping imported streak freezes: {}", e);
+        }
+        if let Err(e) = self.set_max_response_body_mb(config.max_response_body_mb) {
+            warn!("⚠️  Skipping imported response body limit: {}", e);
+        }
+
+        Ok(())
+    }
+
+    /// Current configuration as stored on disk
+    fn snapshot(&self) -> AppConfig {
+        AppConfig {
+            log_level: self.get_log_level(),
+            module_log_levels: self.get_module_log_levels(),
+            browser_path: self.get_browser_path(),
+            waf_validation_skip_providers: self.get_waf_validation_skip_providers(),
+            waf_per_account_cookie_providers: self.get_waf_per_account_cookie_providers(),
+            persistent_browser_profile: self.get_persistent_browser_profile(),
+            browser_extra_args: self.get_browser_extra_args(),
+            waf_bypass: self.get_waf_bypass_config(),
+            weekly_db_maintenance: self.get_weekly_db_maintenance(),
+            last_db_maintenance_at: self.get_last_db_maintenance_at(),
+            daily_summary_time: self.get_daily_summary_time(),
+            last_daily_summary_on: self.get_last_daily_summary_on(),
+            notification_templates: self.get_notification_templates(),
+            notification_locale: self.get_notification_locale(),
+            streak_freezes_per_month: self.get_streak_freezes_per_month(),
+            request_capture_remaining: self.get_request_capture_remaining(),
+            max_response_body_mb: self.get_max_response_body_mb(),
+            app_lock: self.get_app_lock(),
+        }
+    }
+
+    /// Write the current configuration to disk
+    fn persist(&self) -> Result<()> {
+        let content = serde_json::to_string_pretty(&self.snapshot())?;
+        std::fs::write(&self.config_path, content)?;
+        Ok(())
+    }
+}
+
+/// Response body limit in bytes for a configured limit in MiB
+fn response_body_bytes(mb: Option<u32>) -> usize {
+    mb.map(|mb| mb as usize * 1024 * 1024)
+        .unwrap_or(HttpSettings::DEFAULT.max_response_body_bytes)
+}
+
+/// Compose module level overrides into the live log filters
+fn apply_module_log_levels(levels: &BTreeMap<String, LogLevel>) -> Result<()> {
+    logging::set_module_log_levels(
+        levels
+            .iter()
+            .map(|(module, level)| (module.clone(), level.to_tracing_level()))
+            .collect(),
+    )
+}
+
+/// Trim, drop empty entries, sort and dedup a provider id list
+fn normalize_provider_ids(provider_ids: Vec<String>) -> Vec<String> {
+    let mut provider_ids: Vec<String> = provider_ids
+        .into_iter()
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty())
+        .collect();
+    provider_ids.sort();
+    provider_ids.dedup();
+    provider_ids
 }
 
 #[cfg(test)]
@@ -129,6 +1043,100 @@ mod tests {
         assert_eq!(LogLevel::from_u8(99), LogLevel::Info); // Invalid -> Info
     }
 
+    #[test]
+    fn test_log_level_parse() {
+        assert_eq!(LogLevel::parse(" TRACE "), Some(LogLevel::Trace));
+        assert_eq!(LogLevel::parse("warn"), Some(LogLevel::Warn));
+        assert_eq!(LogLevel::parse("verbose"), None);
+    }
+
+    #[test]
+    fn test_app_config_loads_module_log_levels() {
+        let config: AppConfig = serde_json::from_str(
+            r#"{"log_level":"info","module_log_levels":{"neuradock_infrastructure::http":"trace"}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config
+                .module_log_levels
+                .get("neuradock_infrastructure::http"),
+            Some(&LogLevel::Trace)
+        );
+    }
+
+    #[test]
+    fn test_app_config_without_browser_path_still_loads() {
+        let config: AppConfig = serde_json::from_str(r#"{"log_level":"debug"}"#).unwrap();
+        assert_eq!(config.log_level, LogLevel::Debug);
+        assert!(config.module_log_levels.is_empty());
+        assert!(config.browser_path.is_none());
+        assert!(config.waf_validation_skip_providers.is_empty());
+        assert!(config.waf_per_account_cookie_providers.is_empty());
+        assert!(!config.persistent_browser_profile);
+        assert!(config.browser_extra_args.is_empty());
+        assert_eq!(config.waf_bypass, WafBypassConfig::default());
+        assert!(config.daily_summary_time.is_none());
+        assert!(config.notification_templates.is_empty());
+        assert!(config.notification_locale.is_none());
+        assert_eq!(config.streak_freezes_per_month, 0);
+    }
+
+    #[test]
+    fn test_waf_bypass_config_defaults_match_infrastructure() {
+        assert_eq!(
+            WafBypassConfig::default().to_tuning(),
+            WafBypassTuning::default()
+        );
+    }
+
+    #[test]
+    fn test_waf_bypass_config_normalizes_cookie_names() {
+        let config = WafBypassConfig {
+            wait_for_cookies: vec![
+                " acw_tc ".to_string(),
+                String::new(),
+                "acw_tc".to_string(),
+                "cf_clearance".to_string(),
+            ],
+            ..WafBypassConfig::default()
+        }
+        .normalized()
+        .unwrap();
+
+        assert_eq!(config.wait_for_cookies, vec!["acw_tc", "cf_clearance"]);
+    }
+
+    #[test]
+    fn test_waf_bypass_config_rejects_invalid_values() {
+        let invalid = [
+            WafBypassConfig {
+                navigation_timeout_secs: 0,
+                ..WafBypassConfig::default()
+            },
+            WafBypassConfig {
+                max_total_secs: 2,
+                settle_delay_ms: 2000,
+                ..WafBypassConfig::default()
+            },
+            WafBypassConfig {
+                wait_for_cookies: vec!["a=b".to_string()],
+                ..WafBypassConfig::default()
+            },
+            WafBypassConfig {
+                tab_concurrency: 0,
+                ..WafBypassConfig::default()
+            },
+            WafBypassConfig {
+                tab_concurrency: 9,
+                ..WafBypassConfig::default()
+            },
+        ];
+
+        for config in invalid {
+            assert!(config.normalized().is_err());
+        }
+    }
+
     #[test]
     fn test_log_level_string() {
         assert_eq!(LogLevel::Error.as_str(), "error");
@@ -0,0 +1,307 @@
+use anyhow::Result;
+use chrono::{DateTime, Duration as ChronoDuration, Local, NaiveDate, NaiveTime, TimeZone};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use super::{ConfigService, NotificationService};
+use crate::application::services::i18n::t_in;
+use neuradock_domain::account::AccountRepository;
+use neuradock_domain::balance_history::{BalanceHistoryDailySummary, BalanceHistoryRepository};
+use neuradock_domain::notification::{Locale, NotificationMessage};
+
+/// How often the worker checks whether the summary is due
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How far back the balance before the summarized day is looked for
+const LOOKBACK_DAYS: i64 = 30;
+
+/// One account's activity on the summarized day
+#[derive(Debug, Clone)]
+pub struct AccountDay {
+    pub name: String,
+    /// Check-in attempts made that day, retries included
+    pub attempts: u32,
+    /// Balance recorded that day, present when a check-in succeeded
+    pub today: Option<BalanceHistoryDailySummary>,
+    /// Latest balance recorded before that day
+    pub previous: Option<BalanceHistoryDailySummary>,
+}
+
+/// Totals over all accounts for one day
+#[derive(Debug, Clone, PartialEq)]
+pub struct DailySummary {
+    pub date: NaiveDate,
+    pub check_ins: usize,
+    pub successes: usize,
+    pub failures: usize,
+    pub total_balance: f64,
+    /// Account that consumed the most that day, with the amount
+    pub biggest_burn: Option<(String, f64)>,
+}
+
+impl DailySummary {
+    /// Summarize the accounts' day
+    ///
+    /// An account counts as checked in when a balance was recorded that day,
+    /// and as failed when it made attempts without one.
+    pub fn build(date: NaiveDate, accounts: &[AccountDay]) -> Self {
+        let successes = accounts.iter().filter(|day| day.today.is_some()).count();
+        let failures = accounts
+            .iter()
+            .filter(|day| day.today.is_none() && day.attempts > 0)
+            .count();
+
+        let total_balance = accounts
+            .iter()
+            .filter_map(|day| day.today.as_ref().or(day.previous.as_ref()))
+            .map(|summary| summary.daily_balance())
+            .sum();
+
+        let biggest_burn = accounts
+            .iter()
+            .filter_map(|day| {
+                let consumed =
+                    day.today.as_ref()?.daily_consumed() - day.previous.as_ref()?.daily_consumed();
+                (consumed > 0.0).then(|| (day.name.clone(), consumed))
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+
+        Self {
+            date,
+            check_ins: successes + failures,
+            successes,
+            failures,
+            total_balance,
+            biggest_burn,
+        }
+    }
+
+    /// Notification carrying the summary in `locale`
+    pub fn to_message(&self, locale: Locale) -> NotificationMessage {
+        let biggest_burn = match &self.biggest_burn {
+            Some((name, consumed)) => format!("{} (${:.2})", name, consumed),
+            None => "-".to_string(),
+        };
+
+        let content = format!(
+            "{}: {}\n\n{}: {}\n{}: {}\n{}: {}\n\n{}: ${:.2}\n{}: {}",
+            t_in(locale, "notification.label.date"),
+            self.date,
+            t_in(locale, "notification.label.checkIns"),
+            self.check_ins,
+            t_in(locale, "notification.label.successes"),
+            self.successes,
+            t_in(locale, "notification.label.failures"),
+            self.failures,
+            t_in(locale, "notification.label.totalBalance"),
+            self.total_balance,
+            t_in(locale, "notification.label.biggestBurn"),
+            biggest_burn
+        );
+
+        NotificationMessage::new(t_in(locale, "notification.dailySummary.title"), content)
+    }
+}
+
+/// Sends one summary of all check-ins at a configured time each day
+pub struct DailySummaryService {
+    account_repo: Arc<dyn AccountRepository>,
+    balance_history_repo: Arc<dyn BalanceHistoryRepository>,
+    notification_service: Arc<NotificationService>,
+}
+
+impl DailySummaryService {
+    pub fn new(
+        account_repo: Arc<dyn AccountRepository>,
+        balance_history_repo: Arc<dyn BalanceHistoryRepository>,
+        notification_service: Arc<NotificationService>,
+    ) -> Self {
+        Self {
+            account_repo,
+            balance_history_repo,
+            notification_service,
+        }
+    }
+
+    /// Summarize `date` from the accounts and their balance history
+    pub async fn summarize(&self, date: NaiveDate) -> Result<DailySummary> {
+        let accounts = self.account_repo.find_all().await?;
+        let start_date = date - ChronoDuration::days(LOOKBACK_DAYS);
+
+        let mut days = Vec::with_capacity(accounts.len());
+        for account in &accounts {
+            let mut summaries = self
+                .balance_history_repo
+                .list_daily_summaries_in_range(account.id(), start_date, date)
+                .await?;
+            let today = summaries
+                .last()
+                .filter(|summary| summary.check_in_date() == date)
+                .cloned();
+            if today.is_some() {
+                summaries.pop();
+            }
+
+            days.push(AccountDay {
+                name: account.name().to_string(),
+                attempts: account.check_in_attempts_on(date),
+                today,
+                previous: summaries.pop(),
+            });
+        }
+
+        Ok(DailySummary::build(date, &days))
+    }
+
+    /// Send the summary of `date` through the notification channels
+    pub async fn send(&self, date: NaiveDate) -> Result<DailySummary> {
+        let summary = self.summarize(date).await?;
+        self.notification_service
+            .send_localized(|locale| summary.to_message(locale))
+            .await?;
+        info!(
+            date = %date,
+            check_ins = summary.check_ins,
+            failures = summary.failures,
+            "Sent daily summary"
+        );
+        Ok(summary)
+    }
+
+    /// Spawn the worker that sends the summary once a day at the configured time
+    pub fn spawn_worker(self: Arc<Self>, config: Arc<ConfigService>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(CHECK_INTERVAL);
+            loop {
+                interval.tick().await;
+
+                let now = Local::now();
+                let Some(send_at) = config.get_daily_summary_time() else {
+                    continue;
+                };
+                if !is_summary_due(send_at, config.get_last_daily_summary_on(), now) {
+                    continue;
+                }
+
+                let today = now.date_naive();
+                match self.send(today).await {
+                    Ok(_) => {
+                        if let Err(e) = config.record_daily_summary(today) {
+                            warn!("Failed to save daily summary date: {}", e);
+                        }
+                    }
+                    Err(e) => warn!("Failed to send daily summary: {}", e),
+                }
+            }
+        })
+    }
+}
+
+/// Whether the summary time has passed today and today's summary was not sent yet
+fn is_summary_due<Tz: TimeZone>(
+    send_at: NaiveTime,
+    last_sent_on: Option<NaiveDate>,
+    now: DateTime<Tz>,
+) -> bool {
+    now.time() >= send_at && last_sent_on != Some(now.date_naive())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, day).unwrap()
+    }
+
+    fn history(day: u32, balance: f64, consumed: f64) -> Option<BalanceHistoryDailySummary> {
+        Some(BalanceHistoryDailySummary::restore(
+            date(day),
+            balance + consumed,
+            balance,
+            consumed,
+        ))
+    }
+
+    fn account_day(
+        name: &str,
+        attempts: u32,
+        today: Option<BalanceHistoryDailySummary>,
+        previous: Option<BalanceHistoryDailySummary>,
+    ) -> AccountDay {
+        AccountDay {
+            name: name.to_string(),
+            attempts,
+            today,
+            previous,
+        }
+    }
+
+    #[test]
+    fn test_summary_body_from_seeded_history() {
+        let accounts = vec![
+            // Checked in, consumed 3.50 since yesterday
+            account_day("Main", 1, history(10, 20.0, 13.5), history(9, 15.0, 10.0)),
+            // Checked in after a retry, consumed 8.00 since two days ago
+            account_day("Backup", 2, history(10, 42.0, 28.0), history(8, 40.0, 20.0)),
+            // Failed all attempts, the last known balance still counts
+            account_day("Broken", 3, None, history(7, 5.25, 1.0)),
+            // Not attempted and no history at all
+            account_day("Idle", 0, None, None),
+        ];
+
+        let summary = DailySummary::build(date(10), &accounts);
+
+        assert_eq!(summary.check_ins, 3);
+        assert_eq!(summary.successes, 2);
+        assert_eq!(summary.failures, 1);
+        assert_eq!(summary.total_balance, 67.25);
+        assert_eq!(summary.biggest_burn, Some(("Backup".to_string(), 8.0)));
+
+        let message = summary.to_message(Locale::ZhCn);
+        assert!(message.content.contains("2026-03-10"));
+        assert!(message.content.contains("$67.25"));
+        assert!(message.content.contains("Backup ($8.00)"));
+    }
+
+    #[test]
+    fn test_same_summary_in_two_locales() {
+        let accounts = vec![account_day("Main", 1, history(10, 20.0, 13.5), None)];
+        let summary = DailySummary::build(date(10), &accounts);
+
+        let zh = summary.to_message(Locale::ZhCn);
+        let en = summary.to_message(Locale::EnUs);
+
+        assert_eq!(zh.title, "📊 每日签到汇总");
+        assert!(zh.content.starts_with("日期: 2026-03-10"));
+        assert!(zh.content.contains("总余额: $20.00"));
+        assert_eq!(en.title, "📊 Daily Check-in Summary");
+        assert!(en.content.starts_with("Date: 2026-03-10"));
+        assert!(en.content.contains("Total Balance: $20.00"));
+    }
+
+    #[test]
+    fn test_summary_without_consumption_has_no_burn() {
+        let accounts = vec![account_day("First", 1, history(10, 10.0, 0.0), None)];
+
+        let summary = DailySummary::build(date(10), &accounts);
+
+        assert_eq!(summary.successes, 1);
+        assert_eq!(summary.biggest_burn, None);
+        assert!(summary.to_message(Locale::ZhCn).content.ends_with(": -"));
+    }
+
+    #[test]
+    fn test_summary_is_due_once_after_the_configured_time() {
+        let send_at = NaiveTime::from_hms_opt(21, 0, 0).unwrap();
+        let before = Utc.with_ymd_and_hms(2026, 3, 10, 20, 59, 0).unwrap();
+        let after = Utc.with_ymd_and_hms(2026, 3, 10, 21, 0, 0).unwrap();
+
+        assert!(!is_summary_due(send_at, None, before));
+        assert!(is_summary_due(send_at, Some(date(9)), after));
+        assert!(!is_summary_due(send_at, Some(date(10)), after));
+    }
+}
@@ -0,0 +1,273 @@
+use anyhow::Result;
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use sqlx::SqlitePool;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+use neuradock_infrastructure::persistence::{
+    database_stats, move_database, run_database_maintenance, DataLocation,
+    DatabaseMaintenanceReport, DatabaseMove, DatabaseRecovery, DatabaseStats, MigrationFailure,
+    StaleDataLocation, VacuumMode,
+};
+
+use super::running_jobs::RunningJobs;
+use super::ConfigService;
+
+/// How often the background worker checks whether weekly maintenance is due
+const CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Days between background maintenance runs
+const WEEKLY_INTERVAL_DAYS: i64 = 7;
+
+/// Runs SQLite maintenance (optimize, analyze, vacuum, WAL checkpoint)
+///
+/// Runs are refused while a check-in or batch is in flight.
+pub struct DbMaintenanceService {
+    pool: Arc<SqlitePool>,
+    db_path: PathBuf,
+    running_jobs: &'static RunningJobs,
+    startup_recovery: Option<DatabaseRecovery>,
+    migration_failure: Option<MigrationFailure>,
+    location: Option<DataLocation>,
+    stale_location: Option<StaleDataLocation>,
+}
+
+impl DbMaintenanceService {
+    pub fn new(pool: Arc<SqlitePool>, db_path: PathBuf) -> Self {
+        Self {
+            pool,
+            db_path,
+            running_jobs: RunningJobs::global(),
+            startup_recovery: None,
+            migration_failure: None,
+            location: None,
+            stale_location: None,
+        }
+    }
+
+    /// Allow moving the database, `stale` is a configured directory startup could not use
+    pub fn with_data_location(
+        mut self,
+        location: DataLocation,
+        stale: Option<StaleDataLocation>,
+    ) -> Self {
+        self.location = Some(location);
+        self.stale_location = stale;
+        self
+    }
+
+    /// Remember how a corrupt database was recovered at startup
+    pub fn with_startup_recovery(mut self, recovery: Option<DatabaseRecovery>) -> Self {
+        self.startup_recovery = recovery;
+        self
+    }
+
+    /// Recovery performed when the database was opened, if any
+    pub fn startup_recovery(&self) -> Option<&DatabaseRecovery> {
+        self.startup_recovery.as_ref()
+    }
+
+    /// Remember a migration that failed at startup and was rolled back
+    pub fn with_migration_failure(mut self, failure: Option<MigrationFailure>) -> Self {
+        self.migration_failure = failure;
+        self
+    }
+
+    /// Migration rolled back at startup, the database is read-only while set
+    pub fn migration_failure(&self) -> Option<&MigrationFailure> {
+        self.migration_failure.as_ref()
+    }
+
+    /// Path of the open database
+    pub fn db_path(&self) -> &Path {
+        &self.db_path
+    }
+
+    /// Whether the database is in the default app data directory
+    pub fn is_default_location(&self) -> bool {
+        self.location
+            .as_ref()
+            .is_none_or(|location| Some(location.default_dir()) == self.db_path.parent())
+    }
+
+    /// Configured data directory that was unavailable at startup, if any
+    pub fn stale_location(&self) -> Option<&StaleDataLocation> {
+        self.stale_location.as_ref()
+    }
+
+    /// File sizes, row counts and schema version, safe to read while check-ins run
+    pub async fn stats(&self) -> Result<DatabaseStats> {
+        database_stats(&self.pool, &self.db_path)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to read database statistics: {}", e))
+    }
+
+    /// Move the database to `new_dir`, refused while check-ins run
+    ///
+    /// The pool is closed before the original file is deleted, so the app
+    /// must restart to open the database at its new location.
+    pub async fn move_to(&self, new_dir: &Path) -> Result<DatabaseMove> {
+        let Some(location) = &self.location else {
+            anyhow::bail!("The database location cannot be changed");
+        };
+        let Some(_move) = self.running_jobs.try_start_maintenance() else {
+            anyhow::bail!(
+                "The database cannot be moved while check-ins are in progress or database maintenance is running"
+            );
+        };
+        let from_dir = self
+            .db_path
+            .parent()
+            .ok_or_else(|| anyhow::anyhow!("Database path has no parent directory"))?;
+
+        let moved = move_database(&self.pool, location, from_dir, new_dir)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to move the database: {}", e))?;
+        self.pool.close().await;
+        moved.remove_original();
+
+        info!(
+            from = %moved.from_path.display(),
+            to = %moved.to_path.display(),
+            size_bytes = moved.size_bytes,
+            "Moved database, restart required"
+        );
+        Ok(moved)
+    }
+
+    /// Run maintenance now, `trigger` is logged to tell manual and scheduled runs apart
+    pub async fn run(
+        &self,
+        vacuum_mode: VacuumMode,
+        trigger: &str,
+    ) -> Result<DatabaseMaintenanceReport> {
+        let Some(_maintenance) = self.running_jobs.try_start_maintenance() else {
+            anyhow::bail!(
+                "Database maintenance cannot run while check-ins are in progress or another run is active"
+            );
+        };
+
+        info!(
+            trigger,
+            vacuum_mode = vacuum_mode.as_str(),
+            "Starting database maintenance"
+        );
+        let report = run_database_maintenance(&self.pool, &self.db_path, vacuum_mode)
+            .await
+            .map_err(|e| anyhow::anyhow!("Database maintenance failed: {}", e))?;
+
+        info!(
+            trigger,
+            vacuum_mode = report.vacuum_mode.as_str(),
+            size_before_bytes = report.size_before_bytes,
+            size_after_bytes = report.size_after_bytes,
+            reclaimed_bytes = report.reclaimed_bytes(),
+            duration_ms = report.duration.as_millis() as u64,
+            "Database maintenance completed"
+        );
+
+        Ok(report)
+    }
+
+    /// Spawn the worker that runs a full maintenance once a week when enabled
+    pub fn spawn_weekly_worker(self: Arc<Self>, config: Arc<ConfigService>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(CHECK_INTERVAL);
+            loop {
+                interval.tick().await;
+
+                if !config.get_weekly_db_maintenance()
+                    || !is_weekly_run_due(config.get_last_db_maintenance_at(), Utc::now())
+                {
+                    continue;
+                }
+                if self.running_jobs.is_busy() {
+                    info!("Check-ins in progress, postponing weekly database maintenance");
+                    continue;
+                }
+
+                match self.run(VacuumMode::Full, "weekly").await {
+                    Ok(_) => {
+                        if let Err(e) = config.record_db_maintenance(Utc::now()) {
+                            warn!("Failed to save database maintenance time: {}", e);
+                        }
+                    }
+                    Err(e) => error!("Weekly database maintenance failed: {}", e),
+                }
+            }
+        })
+    }
+}
+
+/// Whether a week has passed since the last run, or there has been none
+fn is_weekly_run_due(last_run_at: Option<DateTime<Utc>>, now: DateTime<Utc>) -> bool {
+    last_run_at.is_none_or(|last| now - last >= ChronoDuration::days(WEEKLY_INTERVAL_DAYS))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use neuradock_infrastructure::persistence::Database;
+
+    async fn service(dir: &std::path::Path) -> DbMaintenanceService {
+        let db_path = dir.join("maintenance.db");
+        let database = Database::new(db_path.to_str().unwrap()).await.unwrap();
+        DbMaintenanceService {
+            pool: Arc::new(database.pool().clone()),
+            db_path,
+            running_jobs: Box::leak(Box::new(RunningJobs::new())),
+            startup_recovery: None,
+            migration_failure: None,
+            location: Some(DataLocation::new(dir.to_path_buf(), "maintenance.db")),
+            stale_location: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_reports_sizes() {
+        let dir = tempfile::tempdir().unwrap();
+        let service = service(dir.path()).await;
+
+        let report = service.run(VacuumMode::Full, "test").await.unwrap();
+
+        assert_eq!(report.vacuum_mode, VacuumMode::Full);
+        assert!(report.size_after_bytes > 0);
+    }
+
+    #[tokio::test]
+    async fn test_run_is_refused_during_check_in() {
+        let dir = tempfile::tempdir().unwrap();
+        let service = service(dir.path()).await;
+
+        let check_in = service.running_jobs.start_check_in("account-1", "Main");
+        assert!(service.run(VacuumMode::Full, "test").await.is_err());
+
+        drop(check_in);
+        assert!(service.run(VacuumMode::Full, "test").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_move_closes_pool_and_removes_original() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = tempfile::tempdir().unwrap();
+        let service = service(dir.path()).await;
+
+        let moved = service.move_to(target.path()).await.unwrap();
+
+        assert!(service.pool.is_closed());
+        assert!(!service.db_path.exists());
+        assert!(moved.to_path.exists());
+    }
+
+    #[test]
+    fn test_weekly_run_due() {
+        let now = Utc::now();
+
+        assert!(is_weekly_run_due(None, now));
+        assert!(is_weekly_run_due(Some(now - ChronoDuration::days(8)), now));
+        assert!(!is_weekly_run_due(Some(now - ChronoDuration::days(2)), now));
+    }
+}
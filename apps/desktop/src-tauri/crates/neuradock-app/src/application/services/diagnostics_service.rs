@@ -0,0 +1,104 @@
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::info;
+
+use neuradock_infrastructure::logging::log_utils::mask_sensitive;
+use neuradock_infrastructure::logging::{write_diagnostic_bundle, DiagnosticBundle};
+
+use super::{AutoCheckInScheduler, ConfigService, DbMaintenanceService, ProxyConfigService};
+use crate::application::dtos::{DatabaseStatsDto, SchedulerHealthDto};
+
+/// Days of logs included in a diagnostic bundle unless asked otherwise
+pub const DEFAULT_DIAGNOSTIC_LOG_DAYS: u32 = 3;
+
+/// Most days of logs a diagnostic bundle may include
+pub const MAX_DIAGNOSTIC_LOG_DAYS: u32 = 30;
+
+/// Collects what a bug report needs into one zip
+///
+/// Logs are redacted line by line and settings never carry cookies or keys,
+/// which live in the database and are not part of the bundle.
+pub struct DiagnosticsService {
+    config: Arc<ConfigService>,
+    db_maintenance: Arc<DbMaintenanceService>,
+    proxy_config: Arc<ProxyConfigService>,
+    scheduler: Arc<AutoCheckInScheduler>,
+}
+
+impl DiagnosticsService {
+    pub fn new(
+        config: Arc<ConfigService>,
+        db_maintenance: Arc<DbMaintenanceService>,
+        proxy_config: Arc<ProxyConfigService>,
+        scheduler: Arc<AutoCheckInScheduler>,
+    ) -> Self {
+        Self {
+            config,
+            db_maintenance,
+            proxy_config,
+            scheduler,
+        }
+    }
+
+    /// Scheduled check-in tasks, their last run and restarts
+    pub async fn scheduler_health(&self) -> SchedulerHealthDto {
+        self.scheduler.health().await.into()
+    }
+
+    /// Write the bundle to `path` with `log_days` days of logs from `log_dir`
+    pub async fn export(
+        &self,
+        path: &Path,
+        log_dir: PathBuf,
+        log_days: u32,
+    ) -> Result<DiagnosticBundle> {
+        let database_stats = DatabaseStatsDto::new(
+            self.db_maintenance.stats().await?,
+            self.config
+                .get_last_db_maintenance_at()
+                .map(|at| at.to_rfc3339()),
+        );
+        let mut proxy = self.proxy_config.get().await?;
+        proxy.host = mask_sensitive(&proxy.host);
+
+        let reports = vec![
+            ("system", system_info()),
+            ("database_stats", serde_json::to_value(database_stats)?),
+            (
+                "scheduler_health",
+                serde_json::to_value(self.scheduler_health().await)?,
+            ),
+            ("settings", self.config.export_settings()?),
+            ("proxy", serde_json::to_value(proxy)?),
+        ];
+
+        let path = path.to_path_buf();
+        // Log files roll over at UTC midnight
+        let today = chrono::Utc::now().date_naive();
+        let bundle = tokio::task::spawn_blocking(move || {
+            write_diagnostic_bundle(&path, &log_dir, log_days, today, &reports)
+        })
+        .await??;
+
+        info!(
+            "Diagnostic bundle written to {:?} ({} bytes, {} log files)",
+            bundle.path,
+            bundle.size_bytes,
+            bundle.log_files.len()
+        );
+        Ok(bundle)
+    }
+}
+
+/// App version and the platform it runs on
+fn system_info() -> serde_json::Value {
+    serde_json::json!({
+        "version": env!("CARGO_PKG_VERSION"),
+        "profile": if cfg!(debug_assertions) { "Debug" } else { "Release" },
+        "os": std::env::consts::OS,
+        "os_family": std::env::consts::FAMILY,
+        "arch": std::env::consts::ARCH,
+        "exported_at": chrono::Utc::now().to_rfc3339(),
+    })
+}
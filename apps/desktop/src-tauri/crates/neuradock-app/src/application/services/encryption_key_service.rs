@@ -0,0 +1,104 @@
+use anyhow::Result;
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use neuradock_infrastructure::persistence::{
+    finish_key_rotation, pending_key_rotation, rotate_encrypted_columns, verify_encrypted_columns,
+    KeyRotationProgress, KeyRotationReport,
+};
+use neuradock_infrastructure::security::{EncryptionService, KeyManager};
+
+use super::running_jobs::RunningJobs;
+
+/// Rotates the key that encrypts stored credentials
+///
+/// The key is derived from the installation salt, so rotating means a new
+/// salt: every encrypted column is re-encrypted in one transaction, the shared
+/// [`EncryptionService`] switches to the new key and the stored salt is replaced.
+pub struct EncryptionKeyService {
+    pool: Arc<SqlitePool>,
+    encryption: Arc<EncryptionService>,
+    key_manager: Arc<KeyManager>,
+    password: String,
+    running_jobs: &'static RunningJobs,
+}
+
+impl EncryptionKeyService {
+    pub fn new(
+        pool: Arc<SqlitePool>,
+        encryption: Arc<EncryptionService>,
+        key_manager: KeyManager,
+        password: impl Into<String>,
+    ) -> Self {
+        Self {
+            pool,
+            encryption,
+            key_manager: Arc::new(key_manager),
+            password: password.into(),
+            running_jobs: RunningJobs::global(),
+        }
+    }
+
+    /// Finish a rotation whose database changes committed but whose stored salt
+    /// was not replaced, returning whether there was one
+    pub async fn complete_pending_rotation(&self) -> Result<bool> {
+        let Some(salt) = pending_key_rotation(&self.pool).await? else {
+            return Ok(false);
+        };
+
+        warn!("Completing an interrupted encryption key rotation");
+        self.encryption
+            .replace_key(EncryptionService::from_password(&self.password, &salt)?);
+        self.store_salt(&salt).await?;
+        Ok(true)
+    }
+
+    /// Re-encrypt all credentials under a new key, refused while check-ins run
+    ///
+    /// Every value is first checked to decrypt with the current key; with
+    /// `dry_run` nothing else happens.
+    pub async fn rotate(
+        &self,
+        dry_run: bool,
+        mut on_progress: impl FnMut(KeyRotationProgress),
+    ) -> Result<KeyRotationReport> {
+        let Some(_rotation) = self.running_jobs.try_start_maintenance() else {
+            anyhow::bail!(
+                "The encryption key cannot be rotated while check-ins are in progress or database maintenance is running"
+            );
+        };
+
+        let verified =
+            verify_encrypted_columns(&self.pool, &self.encryption, &mut on_progress).await?;
+        if dry_run {
+            info!(rows = ?verified.rows, "Verified encrypted credentials");
+            return Ok(verified);
+        }
+
+        let salt = KeyManager::generate_salt();
+        let next = EncryptionService::from_password(&self.password, &salt)?;
+        let report =
+            rotate_encrypted_columns(&self.pool, &self.encryption, &next, &salt, on_progress)
+                .await?;
+        self.encryption.replace_key(next);
+        info!(rows = ?report.rows, "Rotated encryption key");
+
+        // The database is already under the new key; if this fails the salt is
+        // stored on the next start
+        if let Err(e) = self.store_salt(&salt).await {
+            warn!("Failed to store the rotated encryption salt: {}", e);
+        }
+
+        Ok(report)
+    }
+
+    async fn store_salt(&self, salt: &[u8; 32]) -> Result<()> {
+        // The salt may live in the OS keyring, whose calls block
+        let key_manager = self.key_manager.clone();
+        let salt = *salt;
+        tokio::task::spawn_blocking(move || key_manager.replace_salt(&salt)).await??;
+        finish_key_rotation(&self.pool).await?;
+        Ok(())
+    }
+}
@@ -1,7 +1,13 @@
 use once_cell::sync::Lazy;
 use serde_json::Value;
+use std::sync::RwLock;
 use tracing::error;
 
+use neuradock_domain::notification::Locale;
+
+/// Locale chosen in the settings, `None` follows `NEURADOCK_LOCALE`
+static CONFIGURED_LOCALE: RwLock<Option<Locale>> = RwLock::new(None);
+
 static TRANSLATIONS_ZH: Lazy<Value> = Lazy::new(|| {
     let json_str = include_str!("i18n/locales/zh-CN.json");
     serde_json::from_str(json_str).unwrap_or_else(|e| {
@@ -18,14 +24,32 @@ static TRANSLATIONS_EN: Lazy<Value> = Lazy::new(|| {
     })
 });
 
-/// Get translation by key path (e.g., "notification.checkIn.success.title")
-/// Selects language from `NEURADOCK_LOCALE` env var (prefix match: "zh" => Chinese, otherwise English).
-pub fn t(key: &str) -> String {
-    let locale = std::env::var("NEURADOCK_LOCALE").unwrap_or_else(|_| "zh_CN".to_string());
-    let translations = if locale.to_lowercase().starts_with("zh") {
-        &*TRANSLATIONS_ZH
-    } else {
-        &*TRANSLATIONS_EN
+/// Use `locale` for notifications of channels without their own, `None` follows the system
+pub fn set_default_locale(locale: Option<Locale>) {
+    if let Ok(mut configured) = CONFIGURED_LOCALE.write() {
+        *configured = locale;
+    }
+}
+
+/// Locale of notifications without a channel override
+///
+/// The configured locale, else `NEURADOCK_LOCALE` ("zh" prefix => Chinese,
+/// otherwise English), else Chinese.
+pub fn default_locale() -> Locale {
+    if let Some(locale) = CONFIGURED_LOCALE.read().ok().and_then(|locale| *locale) {
+        return locale;
+    }
+    std::env::var("NEURADOCK_LOCALE")
+        .map(|tag| Locale::from_language_tag(&tag))
+        .unwrap_or(Locale::ZhCn)
+}
+
+/// Get translation by key path (e.g., "notification.checkIn.success.title") in `locale`,
+/// the key itself when it is missing
+pub fn t_in(locale: Locale, key: &str) -> String {
+    let translations = match locale {
+        Locale::ZhCn => &*TRANSLATIONS_ZH,
+        Locale::EnUs => &*TRANSLATIONS_EN,
     };
 
     // Navigate the nested JSON structure using the key path
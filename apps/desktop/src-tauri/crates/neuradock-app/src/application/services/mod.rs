@@ -1,24 +1,47 @@
+mod app_data_service;
+mod app_lock_service;
 mod balance_history_service;
 mod balance_service;
 mod check_in_executor;
+mod check_in_retry_service;
 mod config_service;
-mod i18n;
+mod daily_summary_service;
+mod db_maintenance_service;
+mod diagnostics_service;
+mod encryption_key_service;
+pub(crate) mod i18n;
 mod notification_service;
+mod provider_cache;
 mod provider_models_query_service;
 mod provider_models_service;
 mod proxy_config_service;
+mod running_jobs;
 mod scheduler;
 pub mod token;
 mod user_info_service;
 mod waf_cookie_manager;
 
+pub use app_data_service::AppDataService;
+pub use app_lock_service::{AppLockService, AppLockStatus};
 pub use balance_history_service::BalanceHistoryService;
 pub use balance_service::BalanceService;
 pub use check_in_executor::CheckInExecutor;
-pub use config_service::{ConfigService, LogLevel};
+pub use check_in_retry_service::{CheckInRetryService, RetryExecutor};
+pub use config_service::{
+    AppLockConfig, ConfigService, LogLevel, WafBypassConfig, MAX_APP_LOCK_IDLE_MINUTES,
+    MAX_RESPONSE_BODY_MB, MAX_STREAK_FREEZES_PER_MONTH,
+};
+pub use daily_summary_service::DailySummaryService;
+pub use db_maintenance_service::DbMaintenanceService;
+pub use diagnostics_service::{
+    DiagnosticsService, DEFAULT_DIAGNOSTIC_LOG_DAYS, MAX_DIAGNOSTIC_LOG_DAYS,
+};
+pub use encryption_key_service::EncryptionKeyService;
 pub use notification_service::NotificationService;
+pub use provider_cache::CachedProviderRepository;
 pub use provider_models_query_service::ProviderModelsQueryService;
 pub use provider_models_service::ProviderModelsService;
 pub use proxy_config_service::ProxyConfigService;
-pub use scheduler::AutoCheckInScheduler;
+pub use running_jobs::{RunningJob, RunningJobs};
+pub use scheduler::{AutoCheckInScheduler, ScheduledTaskHealth, SchedulerHealth};
 pub use token::{ClaudeConfigService, CodexConfigService, TokenService};
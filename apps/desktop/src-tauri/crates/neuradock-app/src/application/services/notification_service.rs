@@ -1,12 +1,17 @@
 use anyhow::Result;
 use chrono::Utc;
 use log::{error, info};
+use std::collections::HashMap;
 use std::sync::Arc;
 
-use crate::application::services::i18n::t;
+use super::ConfigService;
+use crate::application::services::i18n::{default_locale, t_in};
 use neuradock_domain::balance_history::{BalanceHistoryRecord, BalanceHistoryRepository};
-use neuradock_domain::notification::{NotificationChannelRepository, NotificationMessage};
-use neuradock_domain::shared::AccountId;
+use neuradock_domain::notification::{
+    Locale, NotificationChannel, NotificationChannelRepository, NotificationMessage,
+    NotificationTemplates, TemplateKind, TemplateValues,
+};
+use neuradock_domain::shared::{AccountId, ErrorCode};
 use neuradock_infrastructure::notification::create_sender;
 
 /// Notification application service
@@ -14,21 +19,28 @@ use neuradock_infrastructure::notification::create_sender;
 pub struct NotificationService {
     channel_repo: Arc<dyn NotificationChannelRepository>,
     balance_history_repo: Arc<dyn BalanceHistoryRepository>,
+    config: Arc<ConfigService>,
 }
 
 impl NotificationService {
     pub fn new(
         channel_repo: Arc<dyn NotificationChannelRepository>,
         balance_history_repo: Arc<dyn BalanceHistoryRepository>,
+        config: Arc<ConfigService>,
     ) -> Self {
         Self {
             channel_repo,
             balance_history_repo,
+            config,
         }
     }
 
-    /// Send notification to all enabled channels
-    pub async fn send_to_all(&self, message: &NotificationMessage) -> Result<()> {
+    /// Send a notification to all enabled channels whose threshold it meets,
+    /// built by `build` in the locale of each channel
+    pub async fn send_localized(
+        &self,
+        build: impl Fn(Locale) -> NotificationMessage,
+    ) -> Result<()> {
         let channels = self.channel_repo.find_all_enabled().await?;
 
         if channels.is_empty() {
@@ -36,13 +48,35 @@ impl NotificationService {
             return Ok(());
         }
 
+        let default = default_locale();
+        let mut messages = HashMap::from([(default, build(default))]);
+        let message = &messages[&default];
+
+        let enabled = channels.len();
+        let channels = recipients(channels, message);
+        if channels.len() < enabled {
+            info!(
+                "{} channel(s) skipped, disabled or below their minimum severity for {:?}",
+                enabled - channels.len(),
+                message.severity
+            );
+        }
+        if channels.is_empty() {
+            return Ok(());
+        }
+
         info!(
             "Sending notification to {} enabled channel(s): {}",
             channels.len(),
             message.title
         );
 
+        let global_templates = self.config.get_notification_templates();
         for channel in channels {
+            let message = messages
+                .entry(channel_locale(&channel, default))
+                .or_insert_with_key(|locale| build(*locale));
+            let message = render_for_channel(message, &channel, &global_templates);
             let sender = match create_sender(channel.config()) {
                 Ok(s) => s,
                 Err(e) => {
@@ -55,7 +89,7 @@ impl NotificationService {
                 }
             };
 
-            if let Err(e) = sender.send(message).await {
+            if let Err(e) = sender.send(&message).await {
                 error!(
                     "Failed to send notification via channel {} ({}): {}",
                     channel.id(),
@@ -164,105 +198,21 @@ impl NotificationService {
         account_name: &str,
         provider_name: &str,
         balance: Option<(f64, f64, f64)>, // (current_balance, total_consumed, total_quota)
+        reward: Option<f64>,
     ) -> Result<()> {
         let yesterday_balance = self.get_yesterday_balance(account_id).await;
 
-        let content = if let Some((today_current, today_consumed, today_income)) = balance {
-            if let Some((yesterday_current, yesterday_consumed, yesterday_income)) =
-                yesterday_balance
-            {
-                // Calculate changes
-                let current_change = today_current - yesterday_current;
-                let consumed_change = today_consumed - yesterday_consumed;
-                let income_change = today_income - yesterday_income;
-
-                let current_emoji = if current_change > 0.0 {
-                    "📈"
-                } else if current_change < 0.0 {
-                    "📉"
-                } else {
-                    "➡️"
-                };
-                let consumed_emoji = if consumed_change > 0.0 {
-                    "📈"
-                } else if consumed_change < 0.0 {
-                    "📉"
-                } else {
-                    "➡️"
-                };
-                let income_emoji = if income_change > 0.0 {
-                    "📈"
-                } else if income_change < 0.0 {
-                    "📉"
-                } else {
-                    "➡️"
-                };
-
-                format!(
-                    "{}: {}\n{}: {}\n\n{}:\n   {}: ${:.2}\n   {}: ${:.2}\n   {}: ${:.2}\n\n{}:\n   {}: ${:.2} {}\n   {}: ${:.2} {}\n   {}: ${:.2} {}\n\n{}:\n   {}: {:+.2} {}\n   {}: {:+.2} {}\n   {}: {:+.2} {}",
-                    t("notification.label.account"),
-                    account_name,
-                    t("notification.label.provider"),
-                    provider_name,
-                    t("notification.label.yesterday"),
-                    t("notification.label.currentBalance"),
-                    yesterday_current,
-                    t("notification.label.totalConsumed"),
-                    yesterday_consumed,
-                    t("notification.label.totalQuota"),
-                    yesterday_income,
-                    t("notification.label.today"),
-                    t("notification.label.currentBalance"),
-                    today_current,
-                    current_emoji,
-                    t("notification.label.totalConsumed"),
-                    today_consumed,
-                    consumed_emoji,
-                    t("notification.label.totalQuota"),
-                    today_income,
-                    income_emoji,
-                    t("notification.label.changes"),
-                    t("notification.label.currentBalance"),
-                    current_change,
-                    "$",
-                    t("notification.label.totalConsumed"),
-                    consumed_change,
-                    "$",
-                    t("notification.label.totalQuota"),
-                    income_change,
-                    "$"
-                )
-            } else {
-                // No yesterday data, just show today
-                format!(
-                    "{}: {}\n{}: {}\n\n{}:\n   {}: ${:.2}\n   {}: ${:.2}\n   {}: ${:.2}",
-                    t("notification.label.account"),
-                    account_name,
-                    t("notification.label.provider"),
-                    provider_name,
-                    t("notification.label.today"),
-                    t("notification.label.currentBalance"),
-                    today_current,
-                    t("notification.label.totalConsumed"),
-                    today_consumed,
-                    t("notification.label.totalQuota"),
-                    today_income
-                )
-            }
-        } else {
-            format!(
-                "{}: {}\n{}: {}\n\n{}",
-                t("notification.label.account"),
+        self.send_localized(|locale| {
+            check_in_success_message(
+                locale,
                 account_name,
-                t("notification.label.provider"),
                 provider_name,
-                t("notification.checkIn.success.simple")
+                balance,
+                yesterday_balance,
+                reward,
             )
-        };
-
-        let message = NotificationMessage::new(t("notification.checkIn.success.title"), content);
-
-        self.send_to_all(&message).await
+        })
+        .await
     }
 
     /// Send check-in failure notification
@@ -272,18 +222,302 @@ impl NotificationService {
         provider_name: &str,
         error: &str,
     ) -> Result<()> {
-        let content = format!(
-            "{}: {}\n{}: {}\n\n❌ {}: {}",
-            t("notification.label.account"),
+        self.send_localized(|locale| {
+            check_in_failure_message(locale, account_name, provider_name, error)
+        })
+        .await
+    }
+}
+
+/// Check-in success content in `locale`, compared with yesterday when known
+fn check_in_success_message(
+    locale: Locale,
+    account_name: &str,
+    provider_name: &str,
+    balance: Option<(f64, f64, f64)>, // (current_balance, total_consumed, total_quota)
+    yesterday_balance: Option<(f64, f64, f64)>,
+    reward: Option<f64>,
+) -> NotificationMessage {
+    let content = if let Some((today_current, today_consumed, today_income)) = balance {
+        if let Some((yesterday_current, yesterday_consumed, yesterday_income)) = yesterday_balance {
+            // Calculate changes
+            let current_change = today_current - yesterday_current;
+            let consumed_change = today_consumed - yesterday_consumed;
+            let income_change = today_income - yesterday_income;
+
+            let current_emoji = if current_change > 0.0 {
+                "📈"
+            } else if current_change < 0.0 {
+                "📉"
+            } else {
+                "➡️"
+            };
+            let consumed_emoji = if consumed_change > 0.0 {
+                "📈"
+            } else if consumed_change < 0.0 {
+                "📉"
+            } else {
+                "➡️"
+            };
+            let income_emoji = if income_change > 0.0 {
+                "📈"
+            } else if income_change < 0.0 {
+                "📉"
+            } else {
+                "➡️"
+            };
+
+            format!(
+                "{}: {}\n{}: {}\n\n{}:\n   {}: ${:.2}\n   {}: ${:.2}\n   {}: ${:.2}\n\n{}:\n   {}: ${:.2} {}\n   {}: ${:.2} {}\n   {}: ${:.2} {}\n\n{}:\n   {}: {:+.2} {}\n   {}: {:+.2} {}\n   {}: {:+.2} {}",
+                t_in(locale, "notification.label.account"),
+                account_name,
+                t_in(locale, "notification.label.provider"),
+                provider_name,
+                t_in(locale, "notification.label.yesterday"),
+                t_in(locale, "notification.label.currentBalance"),
+                yesterday_current,
+                t_in(locale, "notification.label.totalConsumed"),
+                yesterday_consumed,
+                t_in(locale, "notification.label.totalQuota"),
+                yesterday_income,
+                t_in(locale, "notification.label.today"),
+                t_in(locale, "notification.label.currentBalance"),
+                today_current,
+                current_emoji,
+                t_in(locale, "notification.label.totalConsumed"),
+                today_consumed,
+                consumed_emoji,
+                t_in(locale, "notification.label.totalQuota"),
+                today_income,
+                income_emoji,
+                t_in(locale, "notification.label.changes"),
+                t_in(locale, "notification.label.currentBalance"),
+                current_change,
+                "$",
+                t_in(locale, "notification.label.totalConsumed"),
+                consumed_change,
+                "$",
+                t_in(locale, "notification.label.totalQuota"),
+                income_change,
+                "$"
+            )
+        } else {
+            // No yesterday data, just show today
+            format!(
+                "{}: {}\n{}: {}\n\n{}:\n   {}: ${:.2}\n   {}: ${:.2}\n   {}: ${:.2}",
+                t_in(locale, "notification.label.account"),
+                account_name,
+                t_in(locale, "notification.label.provider"),
+                provider_name,
+                t_in(locale, "notification.label.today"),
+                t_in(locale, "notification.label.currentBalance"),
+                today_current,
+                t_in(locale, "notification.label.totalConsumed"),
+                today_consumed,
+                t_in(locale, "notification.label.totalQuota"),
+                today_income
+            )
+        }
+    } else {
+        format!(
+            "{}: {}\n{}: {}\n\n{}",
+            t_in(locale, "notification.label.account"),
             account_name,
-            t("notification.label.provider"),
+            t_in(locale, "notification.label.provider"),
             provider_name,
-            t("notification.label.error"),
-            error
+            t_in(locale, "notification.checkIn.success.simple")
+        )
+    };
+
+    let values = TemplateValues {
+        balance: balance.map(|(current, _, _)| format!("${:.2}", current)),
+        reward: reward.map(|reward| format!("${:.2}", reward)),
+        ..TemplateValues::new(TemplateKind::CheckInSuccess, account_name, provider_name)
+    };
+    NotificationMessage::new(t_in(locale, "notification.checkIn.success.title"), content)
+        .with_template_values(values)
+}
+
+/// Check-in failure content in `locale`
+fn check_in_failure_message(
+    locale: Locale,
+    account_name: &str,
+    provider_name: &str,
+    error: &str,
+) -> NotificationMessage {
+    let content = format!(
+        "{}: {}\n{}: {}\n\n❌ {}: {}",
+        t_in(locale, "notification.label.account"),
+        account_name,
+        t_in(locale, "notification.label.provider"),
+        provider_name,
+        t_in(locale, "notification.label.error"),
+        error
+    );
+
+    let values = TemplateValues {
+        error: Some(error.to_string()),
+        ..TemplateValues::new(TemplateKind::CheckInFailure, account_name, provider_name)
+    };
+    NotificationMessage::new(t_in(locale, "notification.checkIn.failure.title"), content)
+        .with_severity(ErrorCode::CheckInFailed.severity())
+        .with_template_values(values)
+}
+
+/// Locale `channel` receives notifications in
+fn channel_locale(channel: &NotificationChannel, default: Locale) -> Locale {
+    channel.locale().unwrap_or(default)
+}
+
+/// Enabled channels that accept the severity of `message`
+fn recipients(
+    channels: Vec<NotificationChannel>,
+    message: &NotificationMessage,
+) -> Vec<NotificationChannel> {
+    channels
+        .into_iter()
+        .filter(|channel| channel.accepts(message.severity))
+        .collect()
+}
+
+/// `message` as sent through `channel`, rendered from the channel's template,
+/// else the global one, else left with its built-in content
+fn render_for_channel(
+    message: &NotificationMessage,
+    channel: &NotificationChannel,
+    global_templates: &NotificationTemplates,
+) -> NotificationMessage {
+    let template = message.template_values.as_ref().and_then(|values| {
+        channel
+            .templates()
+            .get(values.kind)
+            .or_else(|| global_templates.get(values.kind))
+    });
+    message.rendered(template)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use neuradock_domain::notification::ChannelConfig;
+    use neuradock_domain::shared::ErrorSeverity;
+
+    fn channel(min_severity: ErrorSeverity) -> NotificationChannel {
+        let mut channel = NotificationChannel::new(ChannelConfig::Feishu {
+            webhook_key: "test_key_123".to_string(),
+        })
+        .unwrap();
+        channel.set_min_severity(min_severity);
+        channel
+    }
+
+    #[test]
+    fn test_warning_is_suppressed_for_error_only_channel() {
+        let warning =
+            NotificationMessage::new("title", "content").with_severity(ErrorSeverity::Warning);
+
+        let error_only = recipients(vec![channel(ErrorSeverity::Error)], &warning);
+        assert!(error_only.is_empty());
+
+        let all = recipients(
+            vec![channel(ErrorSeverity::Info), channel(ErrorSeverity::Error)],
+            &warning,
         );
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].min_severity(), ErrorSeverity::Info);
+    }
+
+    #[test]
+    fn test_disabled_channel_is_skipped() {
+        let critical =
+            NotificationMessage::new("title", "content").with_severity(ErrorSeverity::Critical);
+        let mut muted = channel(ErrorSeverity::Info);
+        muted.disable();
 
-        let message = NotificationMessage::new(t("notification.checkIn.failure.title"), content);
+        let sent_to = recipients(vec![muted, channel(ErrorSeverity::Error)], &critical);
+
+        assert_eq!(sent_to.len(), 1);
+        assert!(sent_to[0].is_enabled());
+    }
 
-        self.send_to_all(&message).await
+    #[test]
+    fn test_channel_template_overrides_global_and_built_in() {
+        let failure =
+            NotificationMessage::new("title", "built-in").with_template_values(TemplateValues {
+                error: Some("timeout".to_string()),
+                ..TemplateValues::new(TemplateKind::CheckInFailure, "main", "AnyRouter")
+            });
+        let global =
+            NotificationTemplates::parse(None, Some("{account} failed".to_string())).unwrap();
+        let mut templated = channel(ErrorSeverity::Info);
+        templated.set_templates(
+            NotificationTemplates::parse(None, Some("{provider}: {error}".to_string())).unwrap(),
+        );
+
+        let plain = channel(ErrorSeverity::Info);
+        assert_eq!(
+            render_for_channel(&failure, &plain, &NotificationTemplates::default()).content,
+            "built-in"
+        );
+        assert_eq!(
+            render_for_channel(&failure, &plain, &global).content,
+            "main failed"
+        );
+        assert_eq!(
+            render_for_channel(&failure, &templated, &global).content,
+            "AnyRouter: timeout"
+        );
+
+        // Messages without values, like the daily summary, keep their content
+        let summary = NotificationMessage::new("title", "summary");
+        assert_eq!(
+            render_for_channel(&summary, &templated, &global).content,
+            "summary"
+        );
+    }
+
+    #[test]
+    fn test_same_check_in_failure_in_two_locales() {
+        let zh = check_in_failure_message(Locale::ZhCn, "main", "AnyRouter", "timeout");
+        let en = check_in_failure_message(Locale::EnUs, "main", "AnyRouter", "timeout");
+
+        assert_eq!(zh.title, "❌ 签到失败");
+        assert_eq!(
+            zh.content,
+            "账户: main\n服务商: AnyRouter\n\n❌ 错误信息: timeout"
+        );
+        assert_eq!(en.title, "❌ Check-in Failed");
+        assert_eq!(
+            en.content,
+            "Account: main\nProvider: AnyRouter\n\n❌ Error: timeout"
+        );
+        assert_eq!(zh.severity, en.severity);
+        assert_eq!(zh.template_values, en.template_values);
+    }
+
+    #[test]
+    fn test_same_check_in_success_in_two_locales() {
+        let balance = Some((12.5, 7.5, 20.0));
+        let zh = check_in_success_message(Locale::ZhCn, "main", "AnyRouter", balance, None, None);
+        let en = check_in_success_message(Locale::EnUs, "main", "AnyRouter", balance, None, None);
+
+        assert_eq!(zh.title, "✅ 签到成功");
+        assert!(zh.content.contains("📅 今天余额:\n   当前余额: $12.50"));
+        assert_eq!(en.title, "✅ Check-in Success");
+        assert!(en
+            .content
+            .contains("📅 Today's Balance:\n   Current Balance: $12.50"));
+    }
+
+    #[test]
+    fn test_channel_locale_overrides_default() {
+        let mut english = channel(ErrorSeverity::Info);
+        english.set_locale(Some(Locale::EnUs));
+
+        assert_eq!(channel_locale(&english, Locale::ZhCn), Locale::EnUs);
+        assert_eq!(
+            channel_locale(&channel(ErrorSeverity::Info), Locale::ZhCn),
+            Locale::ZhCn
+        );
     }
 }
@@ -0,0 +1,292 @@
+use async_trait::async_trait;
+use log::debug;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use neuradock_domain::check_in::{Provider, ProviderRepository};
+use neuradock_domain::shared::{DomainError, ProviderId};
+
+/// Read-through cache over a [`ProviderRepository`]
+///
+/// Providers are read on every check-in, token refresh and query but change
+/// only through the provider commands, so they are kept in memory after the
+/// first read. Saves and deletes through the cache update it directly, and
+/// the provider events invalidate it, so every holder of a clone sees changes
+/// immediately.
+#[derive(Clone)]
+pub struct CachedProviderRepository {
+    inner: Arc<dyn ProviderRepository>,
+    providers: Arc<RwLock<HashMap<ProviderId, Provider>>>,
+    /// Whether `providers` holds every provider, set by `find_all`
+    complete: Arc<AtomicBool>,
+    /// Bumped on every invalidation, a read that started before one does not
+    /// fill the cache with what it loaded
+    generation: Arc<AtomicU64>,
+}
+
+impl CachedProviderRepository {
+    pub fn new(inner: Arc<dyn ProviderRepository>) -> Self {
+        Self {
+            inner,
+            providers: Arc::new(RwLock::new(HashMap::new())),
+            complete: Arc::new(AtomicBool::new(false)),
+            generation: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Drop one provider, the next read loads it again
+    pub async fn invalidate(&self, id: &ProviderId) {
+        let mut providers = self.providers.write().await;
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        self.complete.store(false, Ordering::SeqCst);
+        providers.remove(id);
+        debug!("Provider cache invalidated for {}", id);
+    }
+
+    /// Drop every provider, for changes made outside the repository
+    pub async fn invalidate_all(&self) {
+        let mut providers = self.providers.write().await;
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        self.complete.store(false, Ordering::SeqCst);
+        providers.clear();
+        debug!("Provider cache cleared");
+    }
+}
+
+/// Same order as the repository lists providers in, built-in ones first
+fn sort_providers(providers: &mut [Provider]) {
+    providers.sort_by(|a, b| {
+        b.is_builtin()
+            .cmp(&a.is_builtin())
+            .then_with(|| a.created_at().cmp(&b.created_at()))
+    });
+}
+
+#[async_trait]
+impl ProviderRepository for CachedProviderRepository {
+    async fn save(&self, provider: &Provider) -> Result<(), DomainError> {
+        let mut providers = self.providers.write().await;
+        let result = self.inner.save(provider).await;
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        match result {
+            Ok(()) => {
+                providers.insert(provider.id().clone(), provider.clone());
+                Ok(())
+            }
+            Err(e) => {
+                providers.remove(provider.id());
+                self.complete.store(false, Ordering::SeqCst);
+                Err(e)
+            }
+        }
+    }
+
+    async fn find_by_id(&self, id: &ProviderId) -> Result<Option<Provider>, DomainError> {
+        let generation = {
+            let providers = self.providers.read().await;
+            if let Some(provider) = providers.get(id) {
+                return Ok(Some(provider.clone()));
+            }
+            if self.complete.load(Ordering::SeqCst) {
+                return Ok(None);
+            }
+            self.generation.load(Ordering::SeqCst)
+        };
+
+        let provider = self.inner.find_by_id(id).await?;
+        if let Some(provider) = &provider {
+            let mut providers = self.providers.write().await;
+            if self.generation.load(Ordering::SeqCst) == generation {
+                providers.insert(id.clone(), provider.clone());
+            }
+        }
+        Ok(provider)
+    }
+
+    async fn find_all(&self) -> Result<Vec<Provider>, DomainError> {
+        let generation = {
+            let providers = self.providers.read().await;
+            if self.complete.load(Ordering::SeqCst) {
+                let mut all: Vec<Provider> = providers.values().cloned().collect();
+                sort_providers(&mut all);
+                return Ok(all);
+            }
+            self.generation.load(Ordering::SeqCst)
+        };
+
+        let all = self.inner.find_all().await?;
+        let mut providers = self.providers.write().await;
+        if self.generation.load(Ordering::SeqCst) == generation {
+            *providers = all
+                .iter()
+                .map(|provider| (provider.id().clone(), provider.clone()))
+                .collect();
+            self.complete.store(true, Ordering::SeqCst);
+        }
+        Ok(all)
+    }
+
+    async fn delete(&self, id: &ProviderId) -> Result<(), DomainError> {
+        let mut providers = self.providers.write().await;
+        let result = self.inner.delete(id).await;
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        providers.remove(id);
+        if result.is_err() {
+            self.complete.store(false, Ordering::SeqCst);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use neuradock_domain::check_in::ProviderConfig;
+    use std::sync::atomic::AtomicUsize;
+
+    /// Counts the reads that reach the database
+    #[derive(Default)]
+    struct CountingRepository {
+        providers: RwLock<HashMap<ProviderId, Provider>>,
+        reads: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl ProviderRepository for CountingRepository {
+        async fn save(&self, provider: &Provider) -> Result<(), DomainError> {
+            let mut providers = self.providers.write().await;
+            providers.insert(provider.id().clone(), provider.clone());
+            Ok(())
+        }
+
+        async fn find_by_id(&self, id: &ProviderId) -> Result<Option<Provider>, DomainError> {
+            self.reads.fetch_add(1, Ordering::SeqCst);
+            Ok(self.providers.read().await.get(id).cloned())
+        }
+
+        async fn find_all(&self) -> Result<Vec<Provider>, DomainError> {
+            self.reads.fetch_add(1, Ordering::SeqCst);
+            let mut all: Vec<Provider> = self.providers.read().await.values().cloned().collect();
+            sort_providers(&mut all);
+            Ok(all)
+        }
+
+        async fn delete(&self, id: &ProviderId) -> Result<(), DomainError> {
+            self.providers.write().await.remove(id);
+            Ok(())
+        }
+    }
+
+    fn config(name: &str) -> ProviderConfig {
+        ProviderConfig {
+            name: name.to_string(),
+            domain: format!("https://{}.example.com", name),
+            login_path: "/login".to_string(),
+            sign_in_path: Some("/api/user/sign_in".to_string()),
+            sign_in_body: None,
+            user_info_path: "/api/user/self".to_string(),
+            token_api_path: None,
+            models_path: None,
+            transaction_log_path: None,
+            api_user_key: "new-api-user".to_string(),
+            extra_headers: Default::default(),
+            dialect: Default::default(),
+            bypass_method: None,
+            requires_turnstile: false,
+            supports_check_in: true,
+            check_in_bugged: false,
+            quota_reset_schedule: None,
+            balance_display: None,
+            reward_extraction: None,
+        }
+    }
+
+    fn provider(name: &str) -> Provider {
+        Provider::new(config(name))
+    }
+
+    #[tokio::test]
+    async fn test_reads_are_served_from_cache_after_first_load() {
+        let inner = Arc::new(CountingRepository::default());
+        let existing = provider("first");
+        inner.save(&existing).await.unwrap();
+        let cache = CachedProviderRepository::new(inner.clone());
+
+        assert_eq!(cache.find_all().await.unwrap().len(), 1);
+        assert!(cache.find_by_id(existing.id()).await.unwrap().is_some());
+        assert!(cache
+            .find_by_id(&ProviderId::from_string("missing"))
+            .await
+            .unwrap()
+            .is_none());
+        assert_eq!(cache.find_all().await.unwrap().len(), 1);
+
+        assert_eq!(inner.reads.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_writes_are_visible_to_every_clone() {
+        let inner = Arc::new(CountingRepository::default());
+        let cache = CachedProviderRepository::new(inner.clone());
+        let other = cache.clone();
+        assert!(other.find_all().await.unwrap().is_empty());
+
+        let created = provider("created");
+        cache.save(&created).await.unwrap();
+        let listed = other.find_all().await.unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].name(), "created");
+
+        cache.delete(created.id()).await.unwrap();
+        assert!(other.find_by_id(created.id()).await.unwrap().is_none());
+        assert!(other.find_all().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_invalidation_reloads_changes_made_elsewhere() {
+        let inner = Arc::new(CountingRepository::default());
+        let cache = CachedProviderRepository::new(inner.clone());
+        let changed = provider("before");
+        inner.save(&changed).await.unwrap();
+        assert_eq!(
+            cache
+                .find_by_id(changed.id())
+                .await
+                .unwrap()
+                .unwrap()
+                .name(),
+            "before"
+        );
+
+        // Written around the cache, as an import does
+        let renamed = Provider::restore(
+            changed.id().clone(),
+            config("after"),
+            false,
+            changed.created_at(),
+        );
+        inner.save(&renamed).await.unwrap();
+        assert_eq!(
+            cache
+                .find_by_id(changed.id())
+                .await
+                .unwrap()
+                .unwrap()
+                .name(),
+            "before"
+        );
+
+        cache.invalidate(changed.id()).await;
+        assert_eq!(
+            cache
+                .find_by_id(changed.id())
+                .await
+                .unwrap()
+                .unwrap()
+                .name(),
+            "after"
+        );
+    }
+}
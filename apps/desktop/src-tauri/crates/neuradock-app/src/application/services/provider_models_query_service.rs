@@ -9,6 +9,8 @@ use neuradock_domain::shared::{AccountId, DomainError, ProviderId};
 use neuradock_domain::waf_cookies::WafCookiesRepository;
 use neuradock_infrastructure::http::{token::TokenClient, WafBypassService};
 
+use super::waf_cookie_manager::waf_cookie_cache_key;
+
 pub struct ProviderModelsQueryService {
     account_repo: Arc<dyn AccountRepository>,
     provider_repo: Arc<dyn ProviderRepository>,
@@ -75,6 +77,7 @@ impl ProviderModelsQueryService {
             .find_by_id(&provider_id_obj)
             .await?
             .ok_or_else(|| DomainError::ProviderNotFound(provider_id.clone()))?;
+        let waf_cache_key = waf_cookie_cache_key(&provider, &account_id);
 
         let models_path = provider.models_path().ok_or_else(|| {
             DomainError::Validation("Provider does not support models API".to_string())
@@ -91,7 +94,7 @@ impl ProviderModelsQueryService {
 
         // Merge cached WAF cookies if provider requires it.
         if provider.needs_waf_bypass() {
-            if let Ok(Some(cached_waf)) = self.waf_cookies_repo.get_valid(&provider_id).await {
+            if let Ok(Some(cached_waf)) = self.waf_cookies_repo.get_valid(&waf_cache_key).await {
                 for (k, v) in cached_waf.cookies {
                     cookies.insert(k, v);
                 }
@@ -125,7 +128,7 @@ impl ProviderModelsQueryService {
             Err(e) => {
                 let msg = e.to_string();
                 if msg.contains("WAF_CHALLENGE:") {
-                    let _ = self.waf_cookies_repo.delete(&provider_id).await;
+                    let _ = self.waf_cookies_repo.delete(&waf_cache_key).await;
                     return Err(DomainError::Infrastructure(
                         "WAF challenge detected. Please use 'Refresh with WAF' to refresh cookies."
                             .to_string(),
@@ -159,6 +162,7 @@ impl ProviderModelsQueryService {
             .find_by_id(&provider_id_obj)
             .await?
             .ok_or_else(|| DomainError::ProviderNotFound(provider_id.clone()))?;
+        let waf_cache_key = waf_cookie_cache_key(&provider, &account_id);
 
         let models_path = provider.models_path().ok_or_else(|| {
             DomainError::Validation("Provider does not support models API".to_string())
@@ -184,7 +188,7 @@ impl ProviderModelsQueryService {
         let mut cookies: HashMap<String, String> = account.credentials().cookies().clone();
 
         if provider.needs_waf_bypass() {
-            match self.waf_cookies_repo.get_valid(&provider_id).await {
+            match self.waf_cookies_repo.get_valid(&waf_cache_key).await {
                 Ok(Some(cached_waf)) => {
                     for (k, v) in cached_waf.cookies {
                         cookies.insert(k, v);
@@ -192,13 +196,20 @@ impl ProviderModelsQueryService {
                 }
                 _ => {
                     let new_cookies = waf_service
-                        .get_waf_cookies(&provider.login_url(), &account_id)
+                        .get_waf_cookies(
+                            &provider.login_url(),
+                            &account_id,
+                            provider.waf_challenge_kind().unwrap_or_default(),
+                        )
                         .await
                         .map_err(|e| {
                             DomainError::Infrastructure(format!("WAF bypass failed: {e}"))
                         })?;
 
-                    let _ = self.waf_cookies_repo.save(&provider_id, &new_cookies).await;
+                    let _ = self
+                        .waf_cookies_repo
+                        .save(&waf_cache_key, &new_cookies)
+                        .await;
                     for (k, v) in new_cookies {
                         cookies.insert(k, v);
                     }
@@ -216,7 +227,7 @@ impl ProviderModelsQueryService {
             api_user_header_opt,
             api_user,
             &account_id,
-            &provider_id,
+            &waf_cache_key,
             self.waf_cookies_repo.as_ref(),
             cookies,
         )
@@ -247,7 +258,7 @@ async fn fetch_models_with_waf_retry(
     api_user_header_opt: Option<&str>,
     api_user: &str,
     account_id: &str,
-    provider_id: &str,
+    waf_cache_key: &str,
     waf_cookies_repo: &dyn WafCookiesRepository,
     mut cookies: HashMap<String, String>,
 ) -> Result<Vec<String>, DomainError> {
@@ -270,13 +281,17 @@ async fn fetch_models_with_waf_retry(
                 return Err(DomainError::Infrastructure(error_msg));
             }
 
-            let _ = waf_cookies_repo.delete(provider_id).await;
+            let _ = waf_cookies_repo.delete(waf_cache_key).await;
             let fresh_waf = waf_service
-                .get_waf_cookies(&provider.login_url(), account_id)
+                .get_waf_cookies(
+                    &provider.login_url(),
+                    account_id,
+                    provider.waf_challenge_kind().unwrap_or_default(),
+                )
                 .await
                 .map_err(|e| DomainError::Infrastructure(format!("WAF bypass failed: {e}")))?;
 
-            let _ = waf_cookies_repo.save(provider_id, &fresh_waf).await;
+            let _ = waf_cookies_repo.save(waf_cache_key, &fresh_waf).await;
             for (k, v) in fresh_waf {
                 cookies.insert(k, v);
             }
@@ -7,6 +7,8 @@ use neuradock_infrastructure::http::token::TokenClient;
 use std::collections::HashMap;
 use std::sync::Arc;
 
+use super::waf_cookie_manager::waf_cookie_cache_key;
+
 /// Service for fetching and saving provider models
 pub struct ProviderModelsService {
     provider_models_repo: Arc<dyn ProviderModelsRepository>,
@@ -30,6 +32,7 @@ impl ProviderModelsService {
     /// Fetch and save provider models after successful check-in
     pub async fn fetch_and_save_provider_models(
         &self,
+        account_id: &str,
         provider: &Provider,
         cookies: &HashMap<String, String>,
         api_user: &str,
@@ -59,7 +62,8 @@ impl ProviderModelsService {
         let mut all_cookies = cookies.clone();
 
         // Try to get cached WAF cookies
-        match self.waf_cookies_repo.get_valid(provider_id).await {
+        let waf_cache_key = waf_cookie_cache_key(provider, account_id);
+        match self.waf_cookies_repo.get_valid(&waf_cache_key).await {
             Ok(Some(cached_waf)) => {
                 info!("Using cached WAF cookies for provider models fetch");
                 all_cookies.extend(cached_waf.cookies);
@@ -0,0 +1,189 @@
+use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+use std::sync::{Mutex, MutexGuard};
+use uuid::Uuid;
+
+/// A check-in that is currently executing
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunningJob {
+    pub job_id: String,
+    pub account_id: String,
+    pub account_name: String,
+    pub started_at: DateTime<Utc>,
+}
+
+#[derive(Default)]
+struct Registry {
+    jobs: BTreeMap<String, RunningJob>,
+    /// Batches in flight, covering the gaps between their check-ins
+    batches: usize,
+    maintenance: bool,
+}
+
+/// Registry of in-flight check-ins and batches
+///
+/// Shared by manual, scheduled and retried check-ins so work that needs the
+/// database to itself, such as VACUUM, can wait until nothing is running.
+pub struct RunningJobs {
+    registry: Mutex<Registry>,
+}
+
+static GLOBAL_RUNNING_JOBS: RunningJobs = RunningJobs::new();
+
+impl RunningJobs {
+    pub const fn new() -> Self {
+        Self {
+            registry: Mutex::new(Registry {
+                jobs: BTreeMap::new(),
+                batches: 0,
+                maintenance: false,
+            }),
+        }
+    }
+
+    /// Shared registry instance
+    pub fn global() -> &'static RunningJobs {
+        &GLOBAL_RUNNING_JOBS
+    }
+
+    /// Register a check-in until the returned guard is dropped
+    pub fn start_check_in(&self, account_id: &str, account_name: &str) -> RunningJobGuard<'_> {
+        let job = RunningJob {
+            job_id: Uuid::new_v4().to_string(),
+            account_id: account_id.to_string(),
+            account_name: account_name.to_string(),
+            started_at: Utc::now(),
+        };
+        let job_id = job.job_id.clone();
+        self.lock().jobs.insert(job_id.clone(), job);
+        RunningJobGuard {
+            registry: self,
+            job_id,
+        }
+    }
+
+    /// Register a batch until the returned guard is dropped
+    pub fn start_batch(&self) -> RunningBatchGuard<'_> {
+        self.lock().batches += 1;
+        RunningBatchGuard { registry: self }
+    }
+
+    /// Running check-ins, oldest first
+    pub fn snapshot(&self) -> Vec<RunningJob> {
+        let mut jobs: Vec<RunningJob> = self.lock().jobs.values().cloned().collect();
+        jobs.sort_by_key(|job| job.started_at);
+        jobs
+    }
+
+    /// Whether any check-in or batch is in flight
+    pub fn is_busy(&self) -> bool {
+        let registry = self.lock();
+        !registry.jobs.is_empty() || registry.batches > 0
+    }
+
+    /// Claim the database for maintenance, `None` while check-ins run or
+    /// another maintenance run holds it
+    pub fn try_start_maintenance(&self) -> Option<MaintenanceGuard<'_>> {
+        let mut registry = self.lock();
+        if registry.maintenance || !registry.jobs.is_empty() || registry.batches > 0 {
+            return None;
+        }
+        registry.maintenance = true;
+        Some(MaintenanceGuard { registry: self })
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Registry> {
+        // The registry stays consistent even if a holder panicked
+        self.registry
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Default for RunningJobs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Removes its check-in from the registry when dropped
+pub struct RunningJobGuard<'a> {
+    registry: &'a RunningJobs,
+    job_id: String,
+}
+
+impl Drop for RunningJobGuard<'_> {
+    fn drop(&mut self) {
+        self.registry.lock().jobs.remove(&self.job_id);
+    }
+}
+
+/// Removes its batch from the registry when dropped
+pub struct RunningBatchGuard<'a> {
+    registry: &'a RunningJobs,
+}
+
+impl Drop for RunningBatchGuard<'_> {
+    fn drop(&mut self) {
+        let mut registry = self.registry.lock();
+        registry.batches = registry.batches.saturating_sub(1);
+    }
+}
+
+/// Releases the maintenance claim when dropped
+pub struct MaintenanceGuard<'a> {
+    registry: &'a RunningJobs,
+}
+
+impl Drop for MaintenanceGuard<'_> {
+    fn drop(&mut self) {
+        self.registry.lock().maintenance = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_in_is_listed_until_guard_drops() {
+        let jobs = RunningJobs::new();
+
+        let guard = jobs.start_check_in("account-1", "Main");
+        let running = jobs.snapshot();
+        assert_eq!(running.len(), 1);
+        assert_eq!(running[0].account_id, "account-1");
+        assert_eq!(running[0].account_name, "Main");
+        assert!(jobs.is_busy());
+
+        drop(guard);
+        assert!(jobs.snapshot().is_empty());
+        assert!(!jobs.is_busy());
+    }
+
+    #[test]
+    fn test_maintenance_waits_for_check_ins_and_batches() {
+        let jobs = RunningJobs::new();
+
+        let batch = jobs.start_batch();
+        assert!(jobs.try_start_maintenance().is_none());
+        drop(batch);
+
+        let check_in = jobs.start_check_in("account-1", "Main");
+        assert!(jobs.try_start_maintenance().is_none());
+        drop(check_in);
+
+        assert!(jobs.try_start_maintenance().is_some());
+    }
+
+    #[test]
+    fn test_only_one_maintenance_run_at_a_time() {
+        let jobs = RunningJobs::new();
+
+        let maintenance = jobs.try_start_maintenance().unwrap();
+        assert!(jobs.try_start_maintenance().is_none());
+
+        drop(maintenance);
+        assert!(jobs.try_start_maintenance().is_some());
+    }
+}
@@ -1,12 +1,24 @@
 use std::sync::Arc;
+use tokio::task::JoinHandle;
 use tokio::time::Duration;
 use tracing::{error, info, warn};
 
+use super::restart_policy::{restart_notification, RestartDecision, MAX_RESTARTS};
+use super::types::{ScheduledTaskHealth, SchedulerHealth};
+
 impl super::AutoCheckInScheduler {
     /// Start health check background task to monitor scheduled tasks
+    ///
+    /// A task that stopped is restarted, up to [`MAX_RESTARTS`] times a day;
+    /// repeated restarts and giving up are reported through the notification
+    /// channels.
     pub(super) async fn start_health_check_task(&self) {
         let tasks = Arc::clone(&self.tasks);
         let metadata = Arc::clone(&self.task_metadata);
+        let configs = Arc::clone(&self.task_configs);
+        let restarts = Arc::clone(&self.restarts);
+        let retry_service = self.retry_service.clone();
+        let notification_service = self.notification_service.clone();
 
         let handle = tokio::spawn(async move {
             let mut check_interval = tokio::time::interval(Duration::from_secs(300)); // Check every 5 minutes
@@ -14,8 +26,8 @@ impl super::AutoCheckInScheduler {
             loop {
                 check_interval.tick().await;
 
-                let tasks_lock = tasks.lock().await;
-                let mut metadata_lock = metadata.lock().await;
+                let mut tasks_lock = tasks.lock().await;
+                let metadata_lock = metadata.lock().await;
 
                 let mut dead_tasks = Vec::new();
 
@@ -41,17 +53,64 @@ impl super::AutoCheckInScheduler {
                     }
                 }
 
-                // Remove metadata for dead tasks
-                for account_id in dead_tasks {
-                    metadata_lock.remove(&account_id);
-                    error!(
-                        "🔴 Health Check: Removed dead task metadata for account {}",
-                        account_id.as_str()
-                    );
-                }
+                let dead_tasks: Vec<_> = dead_tasks
+                    .into_iter()
+                    .filter_map(|account_id| {
+                        let handle = tasks_lock.remove(&account_id)?;
+                        let account_name = metadata_lock
+                            .get(&account_id)
+                            .map(|meta| meta.account_name.clone())
+                            .unwrap_or_else(|| account_id.as_str().to_string());
+                        Some((account_id, account_name, handle))
+                    })
+                    .collect();
 
                 drop(tasks_lock);
                 drop(metadata_lock);
+
+                for (account_id, account_name, handle) in dead_tasks {
+                    let reason = stop_reason(handle).await;
+                    let decision = restarts
+                        .lock()
+                        .await
+                        .record_stop(&account_id, chrono::Utc::now());
+
+                    let config = match &decision {
+                        RestartDecision::Restart { .. } => {
+                            configs.lock().await.get(&account_id).cloned()
+                        }
+                        RestartDecision::GiveUp { .. } | RestartDecision::Stopped => None,
+                    };
+                    match config {
+                        Some(config) => {
+                            warn!(
+                                "🔁 Health Check: Restarting task for '{}' ({}): {:?}",
+                                account_name, reason, decision
+                            );
+                            let handle =
+                                Self::start_task(config, &metadata, retry_service.clone()).await;
+                            tasks.lock().await.insert(account_id.clone(), handle);
+                        }
+                        None => {
+                            metadata.lock().await.remove(&account_id);
+                            configs.lock().await.remove(&account_id);
+                            error!(
+                                "🔴 Health Check: Gave up on task for '{}' after {} restarts ({})",
+                                account_name, MAX_RESTARTS, reason
+                            );
+                        }
+                    }
+
+                    let Some(build) = restart_notification(&decision, &account_name, &reason)
+                    else {
+                        continue;
+                    };
+                    if let Some(service) = &notification_service {
+                        if let Err(e) = service.send_localized(build).await {
+                            warn!("Failed to send scheduler health notification: {}", e);
+                        }
+                    }
+                }
             }
         });
 
@@ -60,4 +119,67 @@ impl super::AutoCheckInScheduler {
 
         info!("✅ Health check task started (checking every 5 minutes)");
     }
+
+    /// Scheduled tasks, their last run and restarts, sorted by account name
+    pub async fn health(&self) -> SchedulerHealth {
+        let now = chrono::Utc::now();
+        let health_check_running = self
+            .health_check_handle
+            .lock()
+            .await
+            .as_ref()
+            .is_some_and(|handle| !handle.is_finished());
+
+        // Same order as stop_all_tasks
+        let tasks = self.tasks.lock().await;
+        let metadata = self.task_metadata.lock().await;
+        let configs = self.task_configs.lock().await;
+        let restarts = self.restarts.lock().await;
+
+        let mut task_health: Vec<ScheduledTaskHealth> = configs
+            .values()
+            .map(|config| ScheduledTaskHealth {
+                account_id: config.account_id.clone(),
+                account_name: config.account_name.clone(),
+                hour: config.hour,
+                minute: config.minute,
+                running: tasks
+                    .get(&config.account_id)
+                    .is_some_and(|handle| !handle.is_finished()),
+                last_execution: metadata
+                    .get(&config.account_id)
+                    .and_then(|meta| meta.last_execution),
+                recent_restarts: restarts.recent_restarts(&config.account_id, now),
+            })
+            .collect();
+        task_health.sort_by(|a, b| a.account_name.cmp(&b.account_name));
+
+        let mut given_up: Vec<_> = restarts.given_up().cloned().collect();
+        given_up.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+
+        SchedulerHealth {
+            health_check_running,
+            tasks: task_health,
+            given_up,
+        }
+    }
+}
+
+/// Why a finished task stopped, for logs and notifications
+async fn stop_reason(handle: JoinHandle<()>) -> String {
+    match handle.await {
+        Ok(()) => "the task exited".to_string(),
+        Err(e) if e.is_panic() => {
+            let panic = e.into_panic();
+            let message = panic
+                .downcast_ref::<&str>()
+                .map(|message| message.to_string())
+                .or_else(|| panic.downcast_ref::<String>().cloned());
+            match message {
+                Some(message) => format!("the task panicked: {}", message),
+                None => "the task panicked".to_string(),
+            }
+        }
+        Err(e) => format!("the task was cancelled: {}", e),
+    }
 }
@@ -1,4 +1,5 @@
 mod health_check;
+mod restart_policy;
 mod task_manager;
 mod task_spawner;
 mod types;
@@ -10,7 +11,11 @@ use tokio::sync::Mutex;
 use tokio::task::JoinHandle;
 use tracing::info;
 
-use types::TaskMetadata;
+use super::{CheckInRetryService, NotificationService};
+use restart_policy::RestartTracker;
+use types::{CheckInTaskConfig, TaskMetadata};
+
+pub use types::{ScheduledTaskHealth, SchedulerHealth};
 
 pub struct AutoCheckInScheduler {
     /// Active tasks mapped by account ID
@@ -18,8 +23,16 @@ pub struct AutoCheckInScheduler {
     tasks: Arc<Mutex<HashMap<AccountId, JoinHandle<()>>>>,
     /// Task metadata for health monitoring
     task_metadata: Arc<Mutex<HashMap<AccountId, TaskMetadata>>>,
+    /// How each task was spawned, so the health check can restart it
+    task_configs: Arc<Mutex<HashMap<AccountId, CheckInTaskConfig>>>,
+    /// Restarts of stopped tasks, reset when the schedules are reloaded
+    restarts: Arc<Mutex<RestartTracker>>,
     /// Health check task handle
     health_check_handle: Arc<Mutex<Option<JoinHandle<()>>>>,
+    /// Retry queue for auto check-ins that failed with a recoverable error
+    retry_service: Option<Arc<CheckInRetryService>>,
+    /// Told when tasks are restarted repeatedly or given up on
+    notification_service: Option<Arc<NotificationService>>,
 }
 
 impl AutoCheckInScheduler {
@@ -27,10 +40,24 @@ impl AutoCheckInScheduler {
         Ok(Self {
             tasks: Arc::new(Mutex::new(HashMap::new())),
             task_metadata: Arc::new(Mutex::new(HashMap::new())),
+            task_configs: Arc::new(Mutex::new(HashMap::new())),
+            restarts: Arc::new(Mutex::new(RestartTracker::default())),
             health_check_handle: Arc::new(Mutex::new(None)),
+            retry_service: None,
+            notification_service: None,
         })
     }
 
+    pub fn with_retry_service(mut self, service: Arc<CheckInRetryService>) -> Self {
+        self.retry_service = Some(service);
+        self
+    }
+
+    pub fn with_notification_service(mut self, service: Arc<NotificationService>) -> Self {
+        self.notification_service = Some(service);
+        self
+    }
+
     pub async fn start(&self) -> Result<(), Box<dyn std::error::Error>> {
         info!("✅ Auto check-in scheduler started (using tokio timer)");
 
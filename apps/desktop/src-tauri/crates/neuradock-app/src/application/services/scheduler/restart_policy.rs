@@ -0,0 +1,188 @@
+use chrono::{DateTime, Duration, Utc};
+use neuradock_domain::notification::{Locale, NotificationMessage};
+use neuradock_domain::shared::{AccountId, ErrorSeverity};
+use std::collections::{HashMap, HashSet};
+
+use crate::application::services::i18n::t_in;
+
+/// Restarts allowed within [`RESTART_WINDOW`] before a task is given up on
+pub(super) const MAX_RESTARTS: usize = 3;
+
+/// Only restarts this recent count toward [`MAX_RESTARTS`]
+const RESTART_WINDOW: Duration = Duration::hours(24);
+
+/// What the health check does with a task that stopped unexpectedly
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum RestartDecision {
+    /// Spawn the task again, `restarts` counts this one
+    Restart { restarts: usize },
+    /// The threshold was exceeded, the task stays stopped
+    GiveUp { restarts: usize },
+    /// Already given up on, nothing left to do or report
+    Stopped,
+}
+
+/// Restarts per account, kept by the health check across its runs
+#[derive(Debug, Default)]
+pub(super) struct RestartTracker {
+    restarts: HashMap<AccountId, Vec<DateTime<Utc>>>,
+    given_up: HashSet<AccountId>,
+}
+
+impl RestartTracker {
+    /// Decide what to do about the task of `account_id` stopping at `now`
+    pub fn record_stop(&mut self, account_id: &AccountId, now: DateTime<Utc>) -> RestartDecision {
+        if self.given_up.contains(account_id) {
+            return RestartDecision::Stopped;
+        }
+
+        let restarts = self.restarts.entry(account_id.clone()).or_default();
+        restarts.retain(|at| now - *at < RESTART_WINDOW);
+        if restarts.len() >= MAX_RESTARTS {
+            let restarts = restarts.len();
+            self.restarts.remove(account_id);
+            self.given_up.insert(account_id.clone());
+            return RestartDecision::GiveUp { restarts };
+        }
+
+        restarts.push(now);
+        RestartDecision::Restart {
+            restarts: restarts.len(),
+        }
+    }
+
+    /// Restarts of `account_id` still counting toward [`MAX_RESTARTS`] at `now`
+    pub fn recent_restarts(&self, account_id: &AccountId, now: DateTime<Utc>) -> usize {
+        self.restarts.get(account_id).map_or(0, |at| {
+            at.iter().filter(|at| now - **at < RESTART_WINDOW).count()
+        })
+    }
+
+    /// Accounts whose task was given up on
+    pub fn given_up(&self) -> impl Iterator<Item = &AccountId> {
+        self.given_up.iter()
+    }
+
+    /// Forget all restarts, the schedules were rebuilt from scratch
+    pub fn clear(&mut self) {
+        self.restarts.clear();
+        self.given_up.clear();
+    }
+}
+
+/// Notification for a decision, built per locale, `None` for a first restart
+/// or nothing to report
+///
+/// A single restart is routine; repeated restarts are a warning and giving up
+/// is an error, so severity filters of the channels apply.
+pub(super) fn restart_notification<'a>(
+    decision: &RestartDecision,
+    account_name: &'a str,
+    reason: &'a str,
+) -> Option<impl Fn(Locale) -> NotificationMessage + 'a> {
+    let (title, severity, restarts, hint) = match decision {
+        RestartDecision::Restart { restarts } if *restarts > 1 => (
+            "notification.scheduler.restarted.title",
+            ErrorSeverity::Warning,
+            *restarts,
+            None,
+        ),
+        RestartDecision::GiveUp { restarts } => (
+            "notification.scheduler.gaveUp.title",
+            ErrorSeverity::Error,
+            *restarts,
+            Some("notification.scheduler.gaveUp.hint"),
+        ),
+        _ => return None,
+    };
+
+    Some(move |locale| {
+        let mut content = format!(
+            "{}: {}\n{}: {}\n{}: {}",
+            t_in(locale, "notification.label.account"),
+            account_name,
+            t_in(locale, "notification.label.error"),
+            reason,
+            t_in(locale, "notification.label.restarts"),
+            restarts
+        );
+        if let Some(hint) = hint {
+            content.push_str("\n\n");
+            content.push_str(&t_in(locale, hint));
+        }
+
+        NotificationMessage::new(t_in(locale, title), content).with_severity(severity)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exceeding_threshold_gives_up_exactly_once() {
+        let mut tracker = RestartTracker::default();
+        let account_id = AccountId::from_string("account-1");
+        let now = Utc::now();
+
+        let gave_up: Vec<_> = (0..MAX_RESTARTS + 3)
+            .map(|stop| {
+                let at = now + Duration::minutes(5 * stop as i64);
+                tracker.record_stop(&account_id, at)
+            })
+            .filter_map(|decision| {
+                restart_notification(&decision, "Main", "task panicked")
+                    .map(|build| build(Locale::ZhCn))
+            })
+            .filter(|message| message.severity == ErrorSeverity::Error)
+            .collect();
+
+        assert_eq!(gave_up.len(), 1);
+        assert!(gave_up[0].content.contains("Main"));
+        assert!(gave_up[0].content.contains("task panicked"));
+        assert_eq!(
+            tracker.record_stop(&account_id, now + Duration::hours(1)),
+            RestartDecision::Stopped
+        );
+    }
+
+    #[test]
+    fn test_only_repeated_restarts_are_reported() {
+        let mut tracker = RestartTracker::default();
+        let account_id = AccountId::from_string("account-1");
+        let now = Utc::now();
+
+        let first = tracker.record_stop(&account_id, now);
+        assert_eq!(first, RestartDecision::Restart { restarts: 1 });
+        assert!(restart_notification(&first, "Main", "exited").is_none());
+
+        let second = tracker.record_stop(&account_id, now + Duration::minutes(5));
+        let build = restart_notification(&second, "Main", "exited").unwrap();
+        assert_eq!(build(Locale::ZhCn).severity, ErrorSeverity::Warning);
+        assert_eq!(build(Locale::EnUs).title, "🔁 Auto Check-in Task Restarted");
+    }
+
+    #[test]
+    fn test_old_restarts_expire() {
+        let mut tracker = RestartTracker::default();
+        let account_id = AccountId::from_string("account-1");
+        let now = Utc::now();
+
+        for stop in 0..MAX_RESTARTS {
+            tracker.record_stop(&account_id, now + Duration::minutes(stop as i64));
+        }
+        assert_eq!(
+            tracker.recent_restarts(&account_id, now + Duration::hours(1)),
+            MAX_RESTARTS
+        );
+        assert_eq!(
+            tracker.recent_restarts(&account_id, now + Duration::days(2)),
+            0
+        );
+
+        assert_eq!(
+            tracker.record_stop(&account_id, now + Duration::days(2)),
+            RestartDecision::Restart { restarts: 1 }
+        );
+    }
+}
@@ -28,6 +28,8 @@ impl super::AutoCheckInScheduler {
         }
 
         metadata.clear();
+        self.task_configs.lock().await.clear();
+        self.restarts.lock().await.clear();
 
         info!("✅ All scheduled tasks stopped");
     }
@@ -101,6 +103,9 @@ impl super::AutoCheckInScheduler {
 
         info!("✅ Scheduled {} auto check-in jobs", scheduled_count);
 
+        // Stopping the tasks also stopped the health check
+        self.start_health_check_task().await;
+
         Ok(())
     }
 }
@@ -1,11 +1,44 @@
 use super::types::{CheckInTaskConfig, TaskMetadata};
+use super::CheckInRetryService;
 use chrono::Local;
+use neuradock_domain::shared::AccountId;
+use neuradock_infrastructure::monitoring::{RuntimeMetrics, TrackedTask};
+use std::collections::HashMap;
 use std::sync::Arc;
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
 use tokio::time::Duration;
 use tracing::{error, info, warn};
 
 impl super::AutoCheckInScheduler {
     pub(super) async fn spawn_check_in_task(&self, config: CheckInTaskConfig) {
+        let account_id = config.account_id.clone();
+        self.task_configs
+            .lock()
+            .await
+            .insert(account_id.clone(), config.clone());
+
+        let handle =
+            Self::start_task(config, &self.task_metadata, self.retry_service.clone()).await;
+
+        // Store the task handle for later cleanup
+        let mut tasks = self.tasks.lock().await;
+
+        // If there was an old task for this account, abort it
+        if let Some(old_handle) = tasks.insert(account_id.clone(), handle) {
+            warn!("⚠️  Aborting old task for account: {}", account_id.as_str());
+            old_handle.abort();
+        }
+
+        info!("✅ Task registered for account: {}", account_id.as_str());
+    }
+
+    /// Reset the metadata of the account and spawn its check-in loop
+    pub(super) async fn start_task(
+        config: CheckInTaskConfig,
+        task_metadata: &Arc<Mutex<HashMap<AccountId, TaskMetadata>>>,
+        retry_service: Option<Arc<CheckInRetryService>>,
+    ) -> JoinHandle<()> {
         // Destructure config for easier use
         let CheckInTaskConfig {
             account_id,
@@ -22,26 +55,23 @@ impl super::AutoCheckInScheduler {
             account_name, hour, minute
         );
 
-        // Clone account_id before moving it into the async closure
-        let account_id_for_storage = account_id.clone();
-        let account_name_clone = account_name.clone();
-
         // Clone task metadata for updating within the task
-        let task_metadata = Arc::clone(&self.task_metadata);
+        let task_metadata = Arc::clone(task_metadata);
 
         // Initialize metadata
         {
             let mut metadata = task_metadata.lock().await;
             metadata.insert(
-                account_id_for_storage.clone(),
+                account_id.clone(),
                 TaskMetadata {
-                    account_name: account_name_clone.clone(),
+                    account_name: account_name.clone(),
                     last_execution: None,
                 },
             );
         }
 
-        let handle = tokio::spawn(async move {
+        tokio::spawn(async move {
+            let _task = RuntimeMetrics::global().track(TrackedTask::Scheduler);
             loop {
                 let now = Local::now();
                 // Validate and clamp hour/minute to valid ranges to prevent panics
@@ -109,10 +139,11 @@ impl super::AutoCheckInScheduler {
                 use crate::application::services::CheckInExecutor;
                 match CheckInExecutor::new(account_repo.clone(), true) {
                     Ok(executor) => {
-                        match executor
+                        let outcome = executor
                             .execute_check_in(account_id.as_str(), &provider)
-                            .await
-                        {
+                            .await;
+
+                        match &outcome {
                             Ok(result) => {
                                 if result.success {
                                     info!(
@@ -145,29 +176,24 @@ impl super::AutoCheckInScheduler {
                                 error!("❌ [AUTO CHECK-IN] Error for {}: {}", account_name, e);
                             }
                         }
+
+                        if let Some(retry_service) = &retry_service {
+                            if let Err(e) = retry_service
+                                .record_outcome(&account_id, &outcome, chrono::Utc::now())
+                                .await
+                            {
+                                error!(
+                                    "❌ [AUTO CHECK-IN] Failed to update retry queue for {}: {}",
+                                    account_name, e
+                                );
+                            }
+                        }
                     }
                     Err(e) => {
                         error!("❌ [AUTO CHECK-IN] Failed to create executor: {}", e);
                     }
                 }
             }
-        });
-
-        // Store the task handle for later cleanup
-        let mut tasks = self.tasks.lock().await;
-
-        // If there was an old task for this account, abort it
-        if let Some(old_handle) = tasks.insert(account_id_for_storage.clone(), handle) {
-            warn!(
-                "⚠️  Aborting old task for account: {}",
-                account_id_for_storage.as_str()
-            );
-            old_handle.abort();
-        }
-
-        info!(
-            "✅ Task registered for account: {}",
-            account_id_for_storage.as_str()
-        );
+        })
     }
 }
@@ -11,6 +11,7 @@ pub(super) struct TaskMetadata {
 }
 
 /// Configuration for spawning a check-in task
+#[derive(Clone)]
 pub(super) struct CheckInTaskConfig {
     pub account_id: AccountId,
     pub account_name: String,
@@ -20,3 +21,26 @@ pub(super) struct CheckInTaskConfig {
     pub account_repo: Arc<dyn AccountRepository>,
     pub app_handle: tauri::AppHandle,
 }
+
+/// Snapshot of the scheduler for diagnostics
+#[derive(Debug, Clone)]
+pub struct SchedulerHealth {
+    pub health_check_running: bool,
+    pub tasks: Vec<ScheduledTaskHealth>,
+    /// Accounts whose task stopped too often and was not restarted
+    pub given_up: Vec<AccountId>,
+}
+
+/// State of one account's scheduled check-in task
+#[derive(Debug, Clone)]
+pub struct ScheduledTaskHealth {
+    pub account_id: AccountId,
+    pub account_name: String,
+    pub hour: u8,
+    pub minute: u8,
+    /// The task is spawned and has not finished
+    pub running: bool,
+    pub last_execution: Option<chrono::DateTime<chrono::Utc>>,
+    /// Restarts within the last 24 hours
+    pub recent_restarts: usize,
+}
@@ -3,6 +3,8 @@ use neuradock_domain::shared::AccountId;
 use neuradock_domain::token::ApiToken;
 use neuradock_infrastructure::http::token::FetchTokensRequest;
 
+use crate::application::services::waf_cookie_manager::waf_cookie_cache_key;
+
 impl super::TokenService {
     /// Fetch and cache tokens from API
     pub async fn fetch_and_cache_tokens(
@@ -65,7 +67,7 @@ impl super::TokenService {
 
         // 4. Fetch from API
         let provider = self.load_provider(account.provider_id()).await?;
-        let provider_id_str = provider.id().as_str().to_string();
+        let waf_cache_key = waf_cookie_cache_key(&provider, account.id().as_str());
         let base_url = provider.domain().trim_end_matches('/').to_string();
         let token_api_path = provider
             .token_api_path()
@@ -88,7 +90,7 @@ impl super::TokenService {
 
         // Try to get cached WAF cookies first
         if let Some(ref waf_cookies_repo) = self.waf_cookies_repo {
-            match waf_cookies_repo.get_valid(&provider_id_str).await {
+            match waf_cookies_repo.get_valid(&waf_cache_key).await {
                 Ok(Some(cached_waf)) => {
                     log::info!(
                         "Using cached WAF cookies (expires at {})",
@@ -146,7 +148,7 @@ impl super::TokenService {
 
                 // Invalidate cached WAF cookies first (they are clearly invalid)
                 if let Some(ref waf_cookies_repo) = self.waf_cookies_repo {
-                    if let Err(e) = waf_cookies_repo.delete(&provider_id_str).await {
+                    if let Err(e) = waf_cookies_repo.delete(&waf_cache_key).await {
                         log::warn!("Failed to delete cached WAF cookies: {}", e);
                     } else {
                         log::info!("Invalidated cached WAF cookies");
@@ -4,6 +4,8 @@ use neuradock_domain::check_in::Provider;
 use neuradock_infrastructure::http::WafBypassService;
 use std::collections::HashMap;
 
+use crate::application::services::waf_cookie_manager::waf_cookie_cache_key;
+
 impl super::TokenService {
     /// Get fresh WAF cookies via browser bypass (skips cache)
     pub(super) async fn get_fresh_waf_cookies(
@@ -15,7 +17,11 @@ impl super::TokenService {
         let login_url = provider.login_url();
 
         let waf_cookies = waf_service
-            .get_waf_cookies(&login_url, account.name())
+            .get_waf_cookies(
+                &login_url,
+                account.name(),
+                provider.waf_challenge_kind().unwrap_or_default(),
+            )
             .await
             .context("Failed to get WAF cookies")?;
 
@@ -27,7 +33,10 @@ impl super::TokenService {
         // Cache the new WAF cookies
         if let Some(ref waf_cookies_repo) = self.waf_cookies_repo {
             if let Err(e) = waf_cookies_repo
-                .save(provider.id().as_str(), &waf_cookies)
+                .save(
+                    &waf_cookie_cache_key(provider, account.id().as_str()),
+                    &waf_cookies,
+                )
                 .await
             {
                 log::warn!("Failed to cache WAF cookies: {}", e);
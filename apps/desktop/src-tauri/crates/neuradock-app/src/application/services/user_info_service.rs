@@ -1,9 +1,12 @@
 use anyhow::Result;
 use log::{info, warn};
+use neuradock_domain::account::Account;
 use neuradock_domain::check_in::Provider;
+use neuradock_domain::check_in_timing::CheckInPhase;
 use neuradock_infrastructure::http::{HttpClient, UserInfo};
 use std::collections::HashMap;
 
+use super::check_in_executor::PhaseTimer;
 use super::waf_cookie_manager::WafCookieManager;
 use crate::application::config::TimeoutConfig;
 
@@ -25,27 +28,38 @@ impl<'a> UserInfoService<'a> {
 
     /// Fetch user info with automatic WAF retry handling
     /// Returns (cookies, user_info) where cookies may be updated after WAF refresh
-    pub async fn fetch_user_info_with_retry(
+    pub(crate) async fn fetch_user_info_with_retry(
         &self,
-        account_name: &str,
+        account: &Account,
         provider: &Provider,
-        account_cookies: &HashMap<String, String>,
-        api_user: &str,
+        timer: &PhaseTimer,
     ) -> Result<(HashMap<String, String>, Option<UserInfo>)> {
+        let account_name = account.name();
+        let api_user = account.credentials().api_user();
+        let extra_headers = provider
+            .extra_headers()
+            .merged_with(account.extra_headers());
+
         // Prepare cookies (with WAF cookies from cache or bypass)
-        let mut cookies = self
-            .waf_manager
-            .prepare_cookies(account_name, provider, account_cookies)
+        let mut cookies = timer
+            .time(
+                CheckInPhase::CookiePreparation,
+                self.waf_manager.prepare_cookies(account, provider),
+            )
             .await?;
 
         // Get user info first
-        let user_info_result = self
-            .http_client
-            .get_user_info(
-                &provider.user_info_url(),
-                &cookies,
-                provider.api_user_key(),
-                api_user,
+        let user_info_result = timer
+            .time(
+                CheckInPhase::UserInfo,
+                self.http_client.get_user_info(
+                    &provider.user_info_url(),
+                    &cookies,
+                    provider.api_user_key(),
+                    api_user,
+                    &extra_headers,
+                    provider.dialect(),
+                ),
             )
             .await;
 
@@ -65,19 +79,25 @@ impl<'a> UserInfoService<'a> {
                 );
 
                 // Invalidate WAF cache and get fresh cookies
-                cookies = self
-                    .waf_manager
-                    .refresh_waf_cookies(account_name, provider, account_cookies)
+                cookies = timer
+                    .time(
+                        CheckInPhase::WafRefresh,
+                        self.waf_manager.refresh_waf_cookies(account, provider),
+                    )
                     .await?;
 
                 // Retry get user info
-                match self
-                    .http_client
-                    .get_user_info(
-                        &provider.user_info_url(),
-                        &cookies,
-                        provider.api_user_key(),
-                        api_user,
+                match timer
+                    .time(
+                        CheckInPhase::UserInfo,
+                        self.http_client.get_user_info(
+                            &provider.user_info_url(),
+                            &cookies,
+                            provider.api_user_key(),
+                            api_user,
+                            &extra_headers,
+                            provider.dialect(),
+                        ),
                     )
                     .await
                 {
@@ -110,12 +130,12 @@ impl<'a> UserInfoService<'a> {
     /// Waits for server to process check-in before fetching
     pub async fn fetch_updated_balance(
         &self,
-        account_name: &str,
+        account: &Account,
         provider: &Provider,
         cookies: &HashMap<String, String>,
-        api_user: &str,
         initial_user_info: Option<UserInfo>,
     ) -> Option<UserInfo> {
+        let account_name = account.name();
         info!(
             "[{}] Fetching updated balance after check-in...",
             account_name
@@ -130,7 +150,11 @@ impl<'a> UserInfoService<'a> {
                 &provider.user_info_url(),
                 cookies,
                 provider.api_user_key(),
-                api_user,
+                account.credentials().api_user(),
+                &provider
+                    .extra_headers()
+                    .merged_with(account.extra_headers()),
+                provider.dialect(),
             )
             .await
         {
@@ -154,18 +178,22 @@ impl<'a> UserInfoService<'a> {
     /// Fetch user info without WAF retry (simpler version for query-only operations)
     pub async fn fetch_user_info(
         &self,
-        account_name: &str,
+        account: &Account,
         provider: &Provider,
         cookies: &HashMap<String, String>,
-        api_user: &str,
     ) -> Result<UserInfo> {
+        let account_name = account.name();
         let user_info = self
             .http_client
             .get_user_info(
                 &provider.user_info_url(),
                 cookies,
                 provider.api_user_key(),
-                api_user,
+                account.credentials().api_user(),
+                &provider
+                    .extra_headers()
+                    .merged_with(account.extra_headers()),
+                provider.dialect(),
             )
             .await?;
 
@@ -1,24 +1,87 @@
 use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
 use log::{info, warn};
-use std::collections::HashMap;
-use std::sync::Arc;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex as AsyncMutex, OwnedMutexGuard};
+use tracing::Instrument;
 
-use neuradock_domain::check_in::Provider;
-use neuradock_domain::waf_cookies::WafCookiesRepository;
-use neuradock_infrastructure::http::WafBypassService;
+use neuradock_domain::account::Account;
+use neuradock_domain::check_in::{Provider, WafChallengeKind};
+use neuradock_domain::waf_cookies::{WafCookies, WafCookiesRepository};
+use neuradock_infrastructure::config::WafSettings;
+use neuradock_infrastructure::http::{HttpClient, WafBatchRequest, WafBypassService};
+use neuradock_infrastructure::monitoring::{WafMetrics, WafValidationOutcome};
+
+/// Maximum time to wait for another account's WAF bypass on the same domain
+const DOMAIN_LOCK_TIMEOUT: Duration = Duration::from_secs(180);
+
+/// Cookies acquired this recently are trusted without a validation request
+///
+/// Lets the accounts after a refresh in a running batch pick the new cookies up directly.
+const FRESHLY_ACQUIRED_WINDOW: chrono::Duration = chrono::Duration::minutes(5);
+
+/// Key the WAF cookies of an account are cached under
+///
+/// Shared by all accounts of the provider's domain, unless the provider is configured
+/// for account-specific WAF cookies.
+pub fn waf_cookie_cache_key(provider: &Provider, account_id: &str) -> String {
+    if WafSettings::global().per_account_cookies(provider.id().as_str()) {
+        WafCookies::account_key(provider.domain(), account_id)
+    } else {
+        WafCookies::domain_key(provider.domain())
+    }
+}
+
+fn is_freshly_acquired(cached_waf: &WafCookies, now: DateTime<Utc>) -> bool {
+    now - cached_waf.fetched_at < FRESHLY_ACQUIRED_WINDOW
+}
+
+/// Process-wide locks serializing WAF cookie acquisition per provider domain
+static DOMAIN_LOCKS: OnceLock<Mutex<HashMap<String, Arc<AsyncMutex<()>>>>> = OnceLock::new();
+
+fn domain_lock(domain: &str) -> Arc<AsyncMutex<()>> {
+    let locks = DOMAIN_LOCKS.get_or_init(|| Mutex::new(HashMap::new()));
+    let mut locks = locks
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    locks
+        .entry(domain.to_string())
+        .or_insert_with(|| Arc::new(AsyncMutex::new(())))
+        .clone()
+}
+
+/// Wait for the domain lock; on timeout continue without it rather than stalling the batch
+async fn acquire_domain_lock(account_name: &str, domain: &str) -> Option<OwnedMutexGuard<()>> {
+    match tokio::time::timeout(DOMAIN_LOCK_TIMEOUT, domain_lock(domain).lock_owned()).await {
+        Ok(guard) => Some(guard),
+        Err(_) => {
+            warn!(
+                "[{}] Timed out after {}s waiting for WAF bypass lock on {}, continuing without it",
+                account_name,
+                DOMAIN_LOCK_TIMEOUT.as_secs(),
+                domain
+            );
+            None
+        }
+    }
+}
 
 /// Service for managing WAF cookies with caching support
 pub struct WafCookieManager {
     waf_service: WafBypassService,
     waf_cookies_repo: Option<Arc<dyn WafCookiesRepository>>,
+    proxy_url: Option<String>,
 }
 
 impl WafCookieManager {
     /// Create a new WAF cookie manager
     pub fn new(headless_browser: bool, proxy_url: Option<String>) -> Self {
         Self {
-            waf_service: WafBypassService::with_proxy(headless_browser, proxy_url),
+            waf_service: WafBypassService::with_proxy(headless_browser, proxy_url.clone()),
             waf_cookies_repo: None,
+            proxy_url,
         }
     }
 
@@ -31,19 +94,34 @@ impl WafCookieManager {
     /// Prepare cookies with WAF bypass if needed (with caching support)
     pub async fn prepare_cookies(
         &self,
-        account_name: &str,
+        account: &Account,
         provider: &Provider,
-        user_cookies: &HashMap<String, String>,
     ) -> Result<HashMap<String, String>> {
-        let mut cookies = user_cookies.clone();
+        let account_name = account.name();
+        let mut cookies = account.credentials().cookies().clone();
 
-        if provider.needs_waf_bypass() {
-            let provider_id = provider.id().as_str();
+        if let Some(declared_kind) = provider.waf_challenge_kind() {
+            let mut kind = declared_kind;
+            let cache_key = waf_cookie_cache_key(provider, account.id().as_str());
 
             // Try to use cached WAF cookies first
-            if let Some(ref waf_cookies_repo) = self.waf_cookies_repo {
-                match waf_cookies_repo.get_valid(provider_id).await {
-                    Ok(Some(cached_waf)) => {
+            let cached = self.cached_waf_cookies(account_name, &cache_key).await;
+            WafMetrics::global().record_cache_lookup(provider.id().as_str(), cached.is_some());
+            if let Some(cached_waf) = cached {
+                if is_freshly_acquired(&cached_waf, Utc::now()) {
+                    info!(
+                        "[{}] Using WAF cookies acquired at {}",
+                        account_name, cached_waf.fetched_at
+                    );
+                    cookies.extend(cached_waf.cookies);
+                    return Ok(cookies);
+                }
+
+                match self
+                    .validate_cached_cookies(account_name, provider, &cached_waf.cookies)
+                    .await
+                {
+                    None => {
                         info!(
                             "[{}] Using cached WAF cookies (expires at {})",
                             account_name, cached_waf.expires_at
@@ -51,16 +129,30 @@ impl WafCookieManager {
                         cookies.extend(cached_waf.cookies);
                         return Ok(cookies);
                     }
-                    Ok(None) => {
-                        info!("[{}] No valid cached WAF cookies found", account_name);
-                    }
-                    Err(e) => {
-                        warn!(
-                            "[{}] Failed to check cached WAF cookies: {}",
-                            account_name, e
-                        );
+                    Some(detected_kind) => {
+                        if detected_kind != declared_kind {
+                            warn!(
+                                "[{}] Provider declares a {:?} bypass but answered with a {:?} challenge, solving the latter",
+                                account_name, declared_kind, detected_kind
+                            );
+                        }
+                        kind = detected_kind;
                     }
                 }
+
+                // Challenged: drop the stale cookies so the bypass below refreshes them
+                self.invalidate_cache(account_name, &cache_key).await;
+            }
+
+            // Only one browser per domain: wait for any in-flight bypass, then re-check the cache
+            let _domain_guard = acquire_domain_lock(account_name, provider.domain()).await;
+            if let Some(cached_waf) = self.cached_waf_cookies(account_name, &cache_key).await {
+                info!(
+                    "[{}] Reusing WAF cookies acquired by another account",
+                    account_name
+                );
+                cookies.extend(cached_waf.cookies);
+                return Ok(cookies);
             }
 
             // No valid cache, run WAF bypass
@@ -70,13 +162,12 @@ impl WafCookieManager {
             );
 
             let waf_cookies = self
-                .waf_service
-                .get_waf_cookies(&provider.login_url(), account_name)
+                .run_waf_bypass(account_name, provider, kind)
                 .await
                 .context("Failed to get WAF cookies")?;
 
             // Cache the new WAF cookies
-            self.cache_waf_cookies(account_name, provider_id, &waf_cookies)
+            self.cache_waf_cookies(account_name, &cache_key, &waf_cookies)
                 .await;
 
             // Merge WAF cookies with user cookies
@@ -108,14 +199,31 @@ impl WafCookieManager {
     /// Invalidate WAF cache and get fresh cookies
     pub async fn refresh_waf_cookies(
         &self,
-        account_name: &str,
+        account: &Account,
         provider: &Provider,
-        user_cookies: &HashMap<String, String>,
     ) -> Result<HashMap<String, String>> {
-        let provider_id = provider.id().as_str();
+        let account_name = account.name();
+        let user_cookies = account.credentials().cookies();
+        let cache_key = waf_cookie_cache_key(provider, account.id().as_str());
+        let requested_at = Utc::now();
+
+        // Serialize refreshes per domain; if another account refreshed while we waited, reuse it
+        let _domain_guard = acquire_domain_lock(account_name, provider.domain()).await;
+        if let Some(fresh) = self
+            .cached_waf_cookies_since(account_name, &cache_key, requested_at)
+            .await
+        {
+            info!(
+                "[{}] Reusing WAF cookies refreshed by another account",
+                account_name
+            );
+            let mut cookies = user_cookies.clone();
+            cookies.extend(fresh.cookies);
+            return Ok(cookies);
+        }
 
         // Delete cached WAF cookies
-        self.invalidate_cache(account_name, provider_id).await;
+        self.invalidate_cache(account_name, &cache_key).await;
 
         // Run fresh WAF bypass
         info!(
@@ -123,9 +231,9 @@ impl WafCookieManager {
             account_name
         );
 
+        let kind = provider.waf_challenge_kind().unwrap_or_default();
         let waf_cookies = self
-            .waf_service
-            .get_waf_cookies(&provider.login_url(), account_name)
+            .run_waf_bypass(account_name, provider, kind)
             .await
             .context("Failed to get fresh WAF cookies after challenge")?;
 
@@ -136,7 +244,7 @@ impl WafCookieManager {
         );
 
         // Cache the new WAF cookies
-        self.cache_waf_cookies(account_name, provider_id, &waf_cookies)
+        self.cache_waf_cookies(account_name, &cache_key, &waf_cookies)
             .await;
 
         // Merge with user cookies
@@ -146,15 +254,219 @@ impl WafCookieManager {
         Ok(cookies)
     }
 
+    /// Solve the WAF challenges of a batch up front, one shared browser per provider
+    ///
+    /// Only providers with account-specific WAF cookies need a bypass per account,
+    /// shared cookies are solved once by the first check-in anyway. Accounts whose
+    /// tab fails stay uncached and go through the regular bypass during check-in.
+    pub async fn prefetch_waf_cookies(&self, targets: &[(Account, Provider)]) {
+        let settings = WafSettings::global();
+        let mut by_provider: BTreeMap<&str, (&Provider, Vec<&Account>)> = BTreeMap::new();
+        for (account, provider) in targets {
+            if provider.waf_challenge_kind().is_none()
+                || !settings.per_account_cookies(provider.id().as_str())
+            {
+                continue;
+            }
+            let cache_key = waf_cookie_cache_key(provider, account.id().as_str());
+            if self
+                .cached_waf_cookies(account.name(), &cache_key)
+                .await
+                .is_some()
+            {
+                continue;
+            }
+            by_provider
+                .entry(provider.id().as_str())
+                .or_insert_with(|| (provider, Vec::new()))
+                .1
+                .push(account);
+        }
+
+        for (provider, accounts) in by_provider.into_values() {
+            // A single account gains nothing from sharing a browser
+            if accounts.len() < 2 {
+                continue;
+            }
+
+            let provider_id = provider.id().as_str();
+            let kind = provider.waf_challenge_kind().unwrap_or_default();
+            let requests: Vec<WafBatchRequest> = accounts
+                .iter()
+                .map(|account| WafBatchRequest {
+                    login_url: provider.login_url(),
+                    account_name: account.name().to_string(),
+                })
+                .collect();
+
+            let _domain_guard = acquire_domain_lock(provider.name(), provider.domain()).await;
+            let started_at = Instant::now();
+            let results = self
+                .waf_service
+                .get_waf_cookies_batch(&requests, kind)
+                .await;
+            // The browser is shared, so each account is charged an even part of its time
+            let browser_time = started_at.elapsed() / requests.len() as u32;
+
+            let mut solved = 0;
+            for (account, result) in accounts.iter().zip(results) {
+                let error = result.as_ref().err().map(|e| e.to_string());
+                WafMetrics::global().record_refresh(provider_id, browser_time, error.as_deref());
+                match result {
+                    Ok(waf_cookies) => {
+                        solved += 1;
+                        let cache_key = waf_cookie_cache_key(provider, account.id().as_str());
+                        self.cache_waf_cookies(account.name(), &cache_key, &waf_cookies)
+                            .await;
+                    }
+                    Err(e) => warn!(
+                        "[{}] Batch WAF bypass failed, check-in will retry with its own browser: {}",
+                        account.name(),
+                        e
+                    ),
+                }
+            }
+
+            info!(
+                "[{}] Batch WAF bypass solved {}/{} accounts in {:?}",
+                provider.name(),
+                solved,
+                requests.len(),
+                started_at.elapsed()
+            );
+        }
+    }
+
+    /// Run the browser WAF bypass, recording its duration and outcome per provider
+    async fn run_waf_bypass(
+        &self,
+        account_name: &str,
+        provider: &Provider,
+        kind: WafChallengeKind,
+    ) -> Result<HashMap<String, String>> {
+        let provider_id = provider.id().as_str();
+        let span = tracing::info_span!(
+            "waf_refresh",
+            provider_id,
+            duration_ms = tracing::field::Empty
+        );
+
+        let started_at = Instant::now();
+        let result = self
+            .waf_service
+            .get_waf_cookies(&provider.login_url(), account_name, kind)
+            .instrument(span.clone())
+            .await;
+        let elapsed = started_at.elapsed();
+
+        let error = result.as_ref().err().map(|e| e.to_string());
+        WafMetrics::global().record_refresh(provider_id, elapsed, error.as_deref());
+
+        // The JSON log only carries event fields, so repeat the duration on the event
+        let duration_ms = elapsed.as_millis() as u64;
+        span.record("duration_ms", duration_ms);
+        span.in_scope(|| {
+            tracing::info!(
+                provider_id,
+                duration_ms,
+                success = error.is_none(),
+                "[{}] WAF refresh finished",
+                account_name
+            )
+        });
+
+        result
+    }
+
+    /// Load valid cached WAF cookies stored under a cache key
+    async fn cached_waf_cookies(&self, account_name: &str, cache_key: &str) -> Option<WafCookies> {
+        let waf_cookies_repo = self.waf_cookies_repo.as_ref()?;
+        match waf_cookies_repo.get_valid(cache_key).await {
+            Ok(Some(cached_waf)) => Some(cached_waf),
+            Ok(None) => {
+                info!("[{}] No valid cached WAF cookies found", account_name);
+                None
+            }
+            Err(e) => {
+                warn!(
+                    "[{}] Failed to check cached WAF cookies: {}",
+                    account_name, e
+                );
+                None
+            }
+        }
+    }
+
+    /// Check cached WAF cookies against the provider before trusting them
+    ///
+    /// Returns the challenge kind only when the provider answers with a challenge page;
+    /// a failed validation request keeps the cached cookies so the real request decides.
+    async fn validate_cached_cookies(
+        &self,
+        account_name: &str,
+        provider: &Provider,
+        waf_cookies: &HashMap<String, String>,
+    ) -> Option<WafChallengeKind> {
+        let metrics = WafMetrics::global();
+        if !WafSettings::global().should_validate(provider.id().as_str()) {
+            metrics.record_validation(WafValidationOutcome::Skipped);
+            return None;
+        }
+
+        let result = match HttpClient::with_proxy(self.proxy_url.clone()) {
+            Ok(client) => {
+                client
+                    .detect_waf_challenge(provider.domain(), waf_cookies)
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+
+        match result {
+            Ok(None) => {
+                metrics.record_validation(WafValidationOutcome::Valid);
+                None
+            }
+            Ok(Some(kind)) => {
+                warn!(
+                    "[{}] Cached WAF cookies were challenged ({:?}), refreshing before check-in",
+                    account_name, kind
+                );
+                metrics.record_validation(WafValidationOutcome::Challenged);
+                Some(kind)
+            }
+            Err(e) => {
+                warn!(
+                    "[{}] Failed to validate cached WAF cookies, using them as-is: {}",
+                    account_name, e
+                );
+                metrics.record_validation(WafValidationOutcome::Failed);
+                None
+            }
+        }
+    }
+
+    /// Load cached WAF cookies only if they were fetched after `since`
+    async fn cached_waf_cookies_since(
+        &self,
+        account_name: &str,
+        cache_key: &str,
+        since: DateTime<Utc>,
+    ) -> Option<WafCookies> {
+        self.cached_waf_cookies(account_name, cache_key)
+            .await
+            .filter(|cached_waf| cached_waf.fetched_at >= since)
+    }
+
     /// Cache WAF cookies for future use
     async fn cache_waf_cookies(
         &self,
         account_name: &str,
-        provider_id: &str,
+        cache_key: &str,
         waf_cookies: &HashMap<String, String>,
     ) {
         if let Some(ref waf_cookies_repo) = self.waf_cookies_repo {
-            if let Err(e) = waf_cookies_repo.save(provider_id, waf_cookies).await {
+            if let Err(e) = waf_cookies_repo.save(cache_key, waf_cookies).await {
                 warn!("[{}] Failed to cache WAF cookies: {}", account_name, e);
             } else {
                 info!("[{}] WAF cookies cached for 24 hours", account_name);
@@ -162,10 +474,10 @@ impl WafCookieManager {
         }
     }
 
-    /// Invalidate cached WAF cookies for a provider
-    async fn invalidate_cache(&self, account_name: &str, provider_id: &str) {
+    /// Invalidate cached WAF cookies stored under a cache key
+    async fn invalidate_cache(&self, account_name: &str, cache_key: &str) {
         if let Some(ref waf_cookies_repo) = self.waf_cookies_repo {
-            if let Err(e) = waf_cookies_repo.delete(provider_id).await {
+            if let Err(e) = waf_cookies_repo.delete(cache_key).await {
                 warn!(
                     "[{}] Failed to delete cached WAF cookies: {}",
                     account_name, e
@@ -201,4 +513,34 @@ mod tests {
         let error = anyhow::anyhow!("Network timeout");
         assert!(!manager.is_waf_challenge_error(&error));
     }
+
+    #[test]
+    fn test_domain_lock_is_shared_per_domain() {
+        let first = domain_lock("https://anyrouter.top");
+        let second = domain_lock("https://anyrouter.top");
+        let other = domain_lock("https://agentrouter.org");
+
+        assert!(Arc::ptr_eq(&first, &second));
+        assert!(!Arc::ptr_eq(&first, &other));
+    }
+
+    #[test]
+    fn test_freshly_acquired_cookies_skip_validation_window() {
+        let now = Utc::now();
+        let cached = |fetched_at| WafCookies {
+            cache_key: WafCookies::domain_key("https://anyrouter.top"),
+            cookies: HashMap::new(),
+            fetched_at,
+            expires_at: fetched_at + chrono::Duration::hours(24),
+        };
+
+        assert!(is_freshly_acquired(
+            &cached(now - chrono::Duration::minutes(1)),
+            now
+        ));
+        assert!(!is_freshly_acquired(
+            &cached(now - chrono::Duration::hours(1)),
+            now
+        ));
+    }
 }
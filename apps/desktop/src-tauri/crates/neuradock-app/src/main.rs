@@ -5,6 +5,8 @@ mod presentation;
 
 // Use external crates
 
+use neuradock_domain::shared::DomainError;
+use neuradock_infrastructure::logging::{self, CrashReport};
 use presentation::ipc;
 use presentation::state::{AppState, CommandHandlers, Queries, Repositories, Services};
 use std::time::Instant;
@@ -16,37 +18,45 @@ const DEFAULT_HEIGHT: f64 = 800.0;
 const MIN_WIDTH: f64 = 800.0;
 const MIN_HEIGHT: f64 = 600.0;
 
+/// Log panics and write a crash report to the log dir, so the next start can tell the user
 fn install_panic_hook() {
     std::panic::set_hook(Box::new(|info| {
-        let payload = info
-            .payload()
-            .downcast_ref::<&str>()
-            .copied()
-            .or_else(|| info.payload().downcast_ref::<String>().map(|s| s.as_str()))
-            .unwrap_or("<non-string panic payload>");
-
-        if let Some(location) = info.location() {
-            eprintln!(
-                "💥 panic at {}:{}:{}: {}",
-                location.file(),
-                location.line(),
-                location.column(),
-                payload
-            );
-            tracing::error!(
-                "💥 panic at {}:{}:{}: {}",
-                location.file(),
-                location.line(),
-                location.column(),
-                payload
-            );
+        let log_dir = logging::get_log_dir();
+        let report = CrashReport::from_panic(info, log_dir.as_deref());
+
+        if let Some(location) = &report.location {
+            eprintln!("💥 panic at {}: {}", location, report.message);
+            tracing::error!("💥 panic at {}: {}", location, report.message);
         } else {
-            eprintln!("💥 panic: {}", payload);
-            tracing::error!("💥 panic: {}", payload);
+            eprintln!("💥 panic: {}", report.message);
+            tracing::error!("💥 panic: {}", report.message);
+        }
+
+        if let Some(log_dir) = log_dir {
+            match logging::write_crash_report(&log_dir, &report) {
+                Ok(path) => eprintln!("💥 crash report written to {}", path.display()),
+                Err(e) => eprintln!("⚠️  Failed to write crash report: {}", e),
+            }
         }
     }));
 }
 
+/// User-readable reason the app state failed to initialize
+///
+/// A database that is corrupt beyond automatic recovery gets its own message
+/// with what to do, instead of the generic initialization error.
+fn startup_error_message(error: &(dyn std::error::Error + 'static)) -> String {
+    match error.downcast_ref::<DomainError>() {
+        Some(DomainError::DataIntegrity(details)) => format!(
+            "NeuraDock's database is corrupted and could not be recovered automatically ({}). \
+             Quit NeuraDock, move the database file out of the app data folder or replace it \
+             with a copy from its backups folder, then start NeuraDock again.",
+            details
+        ),
+        _ => error.to_string(),
+    }
+}
+
 #[tokio::main]
 async fn main() {
     let builder = ipc::builder();
@@ -127,7 +137,7 @@ async fn main() {
                     std::env::temp_dir().join("neuradock").join("logs")
                 });
 
-            match neuradock_infrastructure::logging::init_logger(log_dir.clone()) {
+            match logging::init_logger(log_dir.clone()) {
                 Ok(_) => {
                     tracing::info!("🚀 NeuraDock starting...");
                     tracing::info!("📝 File logging initialized at: {}", log_dir.display());
@@ -149,13 +159,16 @@ async fn main() {
             }
 
             install_panic_hook();
+            presentation::bootstrap::notify_previous_crash(&handle, &log_dir);
 
             // Initialize state and block startup until ready, so commands can't be invoked before
             // `AppState` is managed.
             let (tx, rx) = std::sync::mpsc::channel::<Result<AppState, String>>();
             let init_handle = handle.clone();
             tauri::async_runtime::spawn(async move {
-                let result = AppState::new(init_handle).await.map_err(|e| e.to_string());
+                let result = AppState::new(init_handle)
+                    .await
+                    .map_err(|e| startup_error_message(e.as_ref()));
                 let _ = tx.send(result);
             });
 
@@ -1,24 +1,34 @@
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::Arc;
 use std::time::Instant;
 use tauri::Manager;
-use tracing::{info, warn};
+use tracing::{error, info, warn};
 
 use crate::application::commands::handlers::*;
-use crate::application::event_handlers::SchedulerReloadEventHandler;
+use crate::application::event_handlers::{
+    ProviderCacheInvalidationHandler, SchedulerReloadEventHandler,
+};
 use crate::application::queries::BalanceStatisticsQueryService;
-use crate::application::queries::{AccountQueryService, CheckInStreakQueries};
+use crate::application::queries::{
+    AccountQueryService, CheckInStreakQueries, CheckInTimingQueries, ProviderQueryService,
+};
 use crate::application::services::{
-    AutoCheckInScheduler, BalanceHistoryService, BalanceService, ClaudeConfigService,
-    CodexConfigService, ConfigService, NotificationService, ProviderModelsQueryService,
-    ProviderModelsService, ProxyConfigService, TokenService,
+    AppDataService, AppLockService, AutoCheckInScheduler, BalanceHistoryService, BalanceService,
+    CachedProviderRepository, CheckInRetryService, ClaudeConfigService, CodexConfigService,
+    ConfigService, DailySummaryService, DbMaintenanceService, DiagnosticsService,
+    EncryptionKeyService, NotificationService, ProviderModelsQueryService, ProviderModelsService,
+    ProxyConfigService, RetryExecutor, TokenService,
 };
 use crate::presentation::state::{AppState, CommandHandlers, Queries, Repositories, Services};
 use neuradock_domain::account::AccountRepository;
 use neuradock_domain::balance_history::BalanceHistoryRepository;
 use neuradock_domain::check_in::{Provider, ProviderRepository};
+use neuradock_domain::check_in_retry::CheckInRetryRepository;
+use neuradock_domain::check_in_timing::CheckInTimingRepository;
 use neuradock_domain::custom_node::CustomProviderNodeRepository;
 use neuradock_domain::events::account_events::*;
+use neuradock_domain::events::provider_events::*;
 use neuradock_domain::independent_key::IndependentKeyRepository;
 use neuradock_domain::notification::NotificationChannelRepository;
 use neuradock_domain::provider_models::ProviderModelsRepository;
@@ -28,15 +38,19 @@ use neuradock_domain::token::TokenRepository;
 use neuradock_domain::waf_cookies::WafCookiesRepository;
 use neuradock_infrastructure::bootstrap::seed_builtin_providers;
 use neuradock_infrastructure::events::InMemoryEventBus;
+use neuradock_infrastructure::logging;
+use neuradock_infrastructure::monitoring::RuntimeMetricsCollector;
 use neuradock_infrastructure::notification::SqliteNotificationChannelRepository;
 use neuradock_infrastructure::persistence::{
     repositories::{
-        SqliteAccountRepository, SqliteBalanceHistoryRepository,
-        SqliteCustomProviderNodeRepository, SqliteIndependentKeyRepository,
-        SqliteProviderModelsRepository, SqliteProviderRepository, SqliteProxyConfigRepository,
-        SqliteSessionRepository, SqliteTokenRepository, SqliteWafCookiesRepository,
+        SqliteAccountRepository, SqliteBalanceHistoryRepository, SqliteCheckInRetryRepository,
+        SqliteCheckInTimingRepository, SqliteCustomProviderNodeRepository,
+        SqliteIndependentKeyRepository, SqliteProviderModelsRepository, SqliteProviderRepository,
+        SqliteProxyConfigRepository, SqliteSessionRepository, SqliteTokenRepository,
+        SqliteWafCookiesRepository,
     },
-    Database,
+    DataLocation, Database, DatabaseRecovery, MigrationFailure, ResolvedDataDir, SqliteUnitOfWork,
+    StaleDataLocation,
 };
 use neuradock_infrastructure::security::{EncryptionService, KeyManager};
 
@@ -71,7 +85,17 @@ pub async fn build_app_state(
         "neuradock.db"
     };
 
-    let db_path = app_data_dir.join(db_filename);
+    // The database may have been moved, a pointer in the app data dir names its directory
+    let data_location = DataLocation::new(app_data_dir.clone(), db_filename);
+    let ResolvedDataDir {
+        data_dir,
+        stale: stale_location,
+    } = data_location.resolve();
+    if let Some(stale) = &stale_location {
+        notify_stale_data_location(&app_handle, stale);
+    }
+
+    let db_path = data_location.db_path(&data_dir);
     let db_path_str = db_path.to_str().ok_or("Invalid database path")?;
 
     info!("Database path: {}", db_path_str);
@@ -79,9 +103,12 @@ pub async fn build_app_state(
     // Initialize encryption
     info!("🔐 Initializing encryption...");
     let started_at = Instant::now();
-    let key_manager = KeyManager::new(app_data_dir.clone());
-    let salt = key_manager
-        .initialize()
+    // The OS keyring blocks while it answers, and a salt kept there that
+    // cannot be read must stop startup instead of generating a new one
+    let key_manager = KeyManager::new(data_dir.clone());
+    let salt = tokio::task::spawn_blocking(move || key_manager.initialize())
+        .await
+        .map_err(|e| format!("Failed to initialize encryption salt: {}", e))?
         .map_err(|e| format!("Failed to initialize encryption salt: {}", e))?;
 
     // TODO: In production, get password from secure input
@@ -98,22 +125,44 @@ pub async fn build_app_state(
 
     info!("🔌 Connecting to database...");
     let started_at = Instant::now();
-    let database = Database::new(db_path_str).await?;
+    let mut database = Database::new(db_path_str).await?;
     info!(
         "✓ Database connection established ({}ms)",
         started_at.elapsed().as_millis()
     );
+    let database_recovery = database.recovery().cloned();
+    if let Some(recovery) = &database_recovery {
+        notify_database_recovery(&app_handle, recovery);
+    }
 
     info!("🔄 Running migrations...");
     let started_at = Instant::now();
-    database.run_migrations().await?;
+    database.run_migrations_with_rollback().await?;
     info!(
         "✓ Migrations completed ({}ms)",
         started_at.elapsed().as_millis()
     );
+    // After a rolled back migration the database is read-only: nothing is seeded,
+    // scheduled or run in the background, the data can only be viewed and exported
+    let migration_failure = database.migration_failure().cloned();
+    let read_only = migration_failure.is_some();
+    if let Some(failure) = &migration_failure {
+        notify_migration_failure(&app_handle, failure);
+    }
 
     let pool = Arc::new(database.pool().clone());
 
+    // Repositories must not decrypt anything before an interrupted key rotation is completed
+    let encryption_key = Arc::new(EncryptionKeyService::new(
+        pool.clone(),
+        encryption_service.clone(),
+        KeyManager::new(data_dir.clone()),
+        encryption_password,
+    ));
+    if !read_only {
+        encryption_key.complete_pending_rotation().await?;
+    }
+
     let account_repo = Arc::new(SqliteAccountRepository::new(
         pool.clone(),
         encryption_service.clone(),
@@ -129,8 +178,10 @@ pub async fn build_app_state(
         pool.clone(),
         encryption_service.clone(),
     )) as Arc<dyn IndependentKeyRepository>;
-    let provider_repo =
-        Arc::new(SqliteProviderRepository::new(pool.clone())) as Arc<dyn ProviderRepository>;
+    // Shared by everything that reads providers, so all of them see changes at once
+    let provider_cache =
+        CachedProviderRepository::new(Arc::new(SqliteProviderRepository::new(pool.clone())));
+    let provider_repo = Arc::new(provider_cache.clone()) as Arc<dyn ProviderRepository>;
     let provider_models_repo = Arc::new(SqliteProviderModelsRepository::new(pool.clone()))
         as Arc<dyn ProviderModelsRepository>;
     let waf_cookies_repo =
@@ -139,20 +190,28 @@ pub async fn build_app_state(
         Arc::new(SqliteProxyConfigRepository::new(pool.clone())) as Arc<dyn ProxyConfigRepository>;
     let balance_history_repo = Arc::new(SqliteBalanceHistoryRepository::new(pool.clone()))
         as Arc<dyn BalanceHistoryRepository>;
+    let check_in_retry_repo = Arc::new(SqliteCheckInRetryRepository::new(pool.clone()))
+        as Arc<dyn CheckInRetryRepository>;
+    let check_in_timing_repo = Arc::new(SqliteCheckInTimingRepository::new(pool.clone()))
+        as Arc<dyn CheckInTimingRepository>;
+
+    if !read_only {
+        info!("🌱 Seeding built-in providers...");
+        let started_at = Instant::now();
+        seed_builtin_providers(provider_repo.clone(), custom_node_repo.clone())
+            .await
+            .map_err(|e| format!("Failed to seed built-in providers: {}", e))?;
+        info!(
+            "✓ Built-in providers seeded ({}ms)",
+            started_at.elapsed().as_millis()
+        );
+    }
 
-    info!("🌱 Seeding built-in providers...");
-    let started_at = Instant::now();
-    seed_builtin_providers(provider_repo.clone(), custom_node_repo.clone())
-        .await
-        .map_err(|e| format!("Failed to seed built-in providers: {}", e))?;
-    info!(
-        "✓ Built-in providers seeded ({}ms)",
-        started_at.elapsed().as_millis()
-    );
-
+    let config_service = build_config_service(&app_handle)?;
     let notification_service = Arc::new(NotificationService::new(
         notification_channel_repo.clone(),
         balance_history_repo.clone(),
+        config_service.clone(),
     ));
     let token_service = build_token_service(
         token_repo.clone(),
@@ -163,14 +222,16 @@ pub async fn build_app_state(
     )?;
     let claude_config_service = Arc::new(ClaudeConfigService::new());
     let codex_config_service = Arc::new(CodexConfigService::new());
-    let config_service = build_config_service(&app_handle)?;
 
     let account_queries = Arc::new(AccountQueryService::new(account_repo.clone()));
     let streak_queries = Arc::new(CheckInStreakQueries::new(
         account_repo.clone(),
         provider_repo.clone(),
         balance_history_repo.clone(),
+        config_service.clone(),
     ));
+    let provider_queries = Arc::new(ProviderQueryService::new(provider_repo.clone()));
+    let check_in_timing_queries = Arc::new(CheckInTimingQueries::new(check_in_timing_repo.clone()));
 
     // Initialize check-in related services
     let provider_models_service = Arc::new(ProviderModelsService::new(
@@ -185,7 +246,8 @@ pub async fn build_app_state(
         waf_cookies_repo.clone(),
         proxy_config_repo.clone(),
     ));
-    let balance_history_service = Arc::new(BalanceHistoryService::new(balance_history_repo));
+    let balance_history_service =
+        Arc::new(BalanceHistoryService::new(balance_history_repo.clone()));
     let balance_service = Arc::new(BalanceService::new(
         account_repo.clone(),
         provider_repo.clone(),
@@ -196,29 +258,82 @@ pub async fn build_app_state(
     let balance_statistics_queries = Arc::new(BalanceStatisticsQueryService::new(
         account_repo.clone(),
         provider_repo.clone(),
+        balance_history_repo.clone(),
         balance_history_service.clone(),
     ));
 
+    // Retry queue for check-ins that failed with a recoverable error
+    let check_in_retry_service = Arc::new(CheckInRetryService::new(check_in_retry_repo));
+    if !read_only {
+        check_in_retry_service.clone().spawn_worker(
+            RetryExecutor::new(
+                account_repo.clone(),
+                provider_repo.clone(),
+                proxy_config_repo.clone(),
+                waf_cookies_repo.clone(),
+                true, // headless_browser
+            )
+            .with_timing_repo(check_in_timing_repo.clone()),
+        );
+    }
+
+    // Database maintenance, on demand and weekly when enabled
+    let db_maintenance = Arc::new(
+        DbMaintenanceService::new(pool.clone(), db_path.clone())
+            .with_startup_recovery(database_recovery)
+            .with_migration_failure(migration_failure)
+            .with_data_location(data_location, stale_location),
+    );
+    if !read_only {
+        db_maintenance
+            .clone()
+            .spawn_weekly_worker(config_service.clone());
+    }
+
+    // One summary of the day's check-ins, sent at the configured time
+    let daily_summary = Arc::new(DailySummaryService::new(
+        account_repo.clone(),
+        balance_history_repo,
+        notification_service.clone(),
+    ));
+    if !read_only {
+        daily_summary.clone().spawn_worker(config_service.clone());
+    }
+
     info!("📊 Initializing scheduler...");
     let started_at = Instant::now();
-    let scheduler = Arc::new(AutoCheckInScheduler::new().await?);
+    let scheduler = Arc::new(
+        AutoCheckInScheduler::new()
+            .await?
+            .with_retry_service(check_in_retry_service)
+            .with_notification_service(notification_service.clone()),
+    );
     info!(
         "✓ Scheduler initialized ({}ms)",
         started_at.elapsed().as_millis()
     );
 
-    info!("▶️  Starting scheduler...");
-    let started_at = Instant::now();
-    scheduler.start().await?;
-    info!(
-        "✓ Scheduler started ({}ms)",
-        started_at.elapsed().as_millis()
-    );
+    if !read_only {
+        info!("▶️  Starting scheduler...");
+        let started_at = Instant::now();
+        scheduler.start().await?;
+        info!(
+            "✓ Scheduler started ({}ms)",
+            started_at.elapsed().as_millis()
+        );
+    }
 
     // Initialize event bus and register event handlers
     info!("🔧 Initializing event bus...");
     let event_bus = Arc::new(InMemoryEventBus::new());
 
+    // Task, memory, pool and event bus samples for the debug page
+    let runtime_metrics = Arc::new(RuntimeMetricsCollector::new(
+        pool.as_ref().clone(),
+        event_bus.clone(),
+    ));
+    runtime_metrics.clone().spawn_worker();
+
     // Register SchedulerReloadEventHandler for account events
     let scheduler_reload_handler = SchedulerReloadEventHandler::new(
         scheduler.clone(),
@@ -244,38 +359,59 @@ pub async fn build_app_state(
             TypedEventHandlerWrapper::<AccountDeleted, _>::new(scheduler_reload_handler.clone()),
         ))
         .await;
+    let _ = event_bus
+        .subscribe::<AccountRestored>(Arc::new(
+            TypedEventHandlerWrapper::<AccountRestored, _>::new(scheduler_reload_handler.clone()),
+        ))
+        .await;
     let _ = event_bus
         .subscribe::<AccountToggled>(Arc::new(
-            TypedEventHandlerWrapper::<AccountToggled, _>::new(scheduler_reload_handler),
+            TypedEventHandlerWrapper::<AccountToggled, _>::new(scheduler_reload_handler.clone()),
+        ))
+        .await;
+
+    // Invalidate cached providers on provider events
+    let provider_cache_handler = ProviderCacheInvalidationHandler::new(provider_cache.clone());
+    let _ = event_bus
+        .subscribe::<ProviderCreated>(Arc::new(
+            TypedEventHandlerWrapper::<ProviderCreated, _>::new(provider_cache_handler.clone()),
+        ))
+        .await;
+    let _ = event_bus
+        .subscribe::<ProviderUpdated>(Arc::new(
+            TypedEventHandlerWrapper::<ProviderUpdated, _>::new(provider_cache_handler.clone()),
+        ))
+        .await;
+    let _ = event_bus
+        .subscribe::<ProviderDeleted>(Arc::new(
+            TypedEventHandlerWrapper::<ProviderDeleted, _>::new(provider_cache_handler),
         ))
         .await;
 
     info!("✓ Event bus initialized and handlers registered");
 
-    // Load existing schedules from database
-    info!("📋 Loading auto check-in schedules...");
-    let started_at = Instant::now();
-    let provider_list = provider_repo
-        .find_all()
-        .await
-        .map_err(|e| format!("Failed to load providers: {}", e))?;
-    info!("📦 Got {} providers", provider_list.len());
+    // Whole-installation export and import, reloading schedules after an import
+    let app_data = Arc::new(
+        AppDataService::new(
+            pool.clone(),
+            encryption_service.clone(),
+            config_service.clone(),
+        )
+        .with_scheduler_reload(scheduler_reload_handler)
+        .with_provider_cache(provider_cache),
+    );
 
-    let providers_map: HashMap<String, Provider> = provider_list
-        .into_iter()
-        .map(|provider| (provider.id().as_str().to_string(), provider))
-        .collect();
+    if !read_only {
+        load_schedules(&scheduler, &provider_repo, &account_repo, &app_handle).await?;
+    }
 
-    if let Err(e) = scheduler
-        .reload_schedules(providers_map, account_repo.clone(), app_handle.clone())
-        .await
-    {
-        warn!("⚠️  Failed to load schedules: {}", e);
-    } else {
-        info!(
-            "✓ Auto check-in schedules loaded ({}ms)",
-            started_at.elapsed().as_millis()
-        );
+    // Deleted accounts are purged once their undo window has passed
+    let purge_deleted_accounts = Arc::new(PurgeDeletedAccountsCommandHandler::new(
+        account_repo.clone(),
+        event_bus.clone(),
+    ));
+    if !read_only {
+        purge_deleted_accounts.clone().spawn_cleanup_worker();
     }
 
     info!("🔧 Initializing command handlers...");
@@ -292,10 +428,16 @@ pub async fn build_app_state(
             account_repo.clone(),
             event_bus.clone(),
         )),
+        undo_delete_account: Arc::new(UndoDeleteAccountCommandHandler::new(
+            account_repo.clone(),
+            event_bus.clone(),
+        )),
+        purge_deleted_accounts,
         toggle_account: Arc::new(ToggleAccountCommandHandler::new(
             account_repo.clone(),
             event_bus.clone(),
         )),
+        toggle_pin: Arc::new(TogglePinCommandHandler::new(account_repo.clone())),
         execute_check_in: Arc::new(
             ExecuteCheckInCommandHandler::new(
                 account_repo.clone(),
@@ -306,7 +448,8 @@ pub async fn build_app_state(
                 waf_cookies_repo.clone(),
                 true, // headless_browser
             )
-            .with_notification_service(notification_service.clone()),
+            .with_notification_service(notification_service.clone())
+            .with_timing_repo(check_in_timing_repo.clone()),
         ),
         batch_execute_check_in: Arc::new(
             BatchExecuteCheckInCommandHandler::new(
@@ -318,7 +461,8 @@ pub async fn build_app_state(
                 waf_cookies_repo.clone(),
                 true, // headless_browser
             )
-            .with_notification_service(notification_service.clone()),
+            .with_notification_service(notification_service.clone())
+            .with_timing_repo(check_in_timing_repo.clone()),
         ),
         create_notification_channel: Arc::new(CreateNotificationChannelHandler::new(
             notification_channel_repo.clone(),
@@ -332,12 +476,30 @@ pub async fn build_app_state(
         test_notification_channel: Arc::new(TestNotificationChannelHandler::new(
             notification_channel_repo.clone(),
         )),
-        create_provider: Arc::new(CreateProviderCommandHandler::new(provider_repo.clone())),
-        update_provider: Arc::new(UpdateProviderCommandHandler::new(provider_repo.clone())),
-        delete_provider: Arc::new(DeleteProviderCommandHandler::new(provider_repo.clone())),
+        create_provider: Arc::new(CreateProviderCommandHandler::new(
+            provider_repo.clone(),
+            event_bus.clone(),
+        )),
+        update_provider: Arc::new(UpdateProviderCommandHandler::new(
+            provider_repo.clone(),
+            event_bus.clone(),
+        )),
+        delete_provider: Arc::new(DeleteProviderCommandHandler::new(
+            provider_repo.clone(),
+            event_bus.clone(),
+        )),
     };
     info!("✓ Command handlers initialized");
 
+    let proxy_config = Arc::new(ProxyConfigService::new(proxy_config_repo.clone()));
+    let diagnostics = Arc::new(DiagnosticsService::new(
+        config_service.clone(),
+        db_maintenance.clone(),
+        proxy_config.clone(),
+        scheduler,
+    ));
+    let app_lock = Arc::new(AppLockService::new(config_service.clone()));
+
     info!(
         "✅ AppState ready ({}ms)",
         startup_started_at.elapsed().as_millis()
@@ -351,6 +513,7 @@ pub async fn build_app_state(
             custom_node: custom_node_repo,
             independent_key: independent_key_repo,
             provider: provider_repo,
+            unit_of_work: Arc::new(SqliteUnitOfWork::new(pool.clone())),
         },
         services: Services {
             token: token_service,
@@ -358,13 +521,21 @@ pub async fn build_app_state(
             codex_config: codex_config_service,
             config: config_service,
             balance: balance_service,
-            proxy_config: Arc::new(ProxyConfigService::new(proxy_config_repo.clone())),
+            proxy_config,
             provider_models_query,
+            db_maintenance,
+            app_data,
+            encryption_key,
+            diagnostics,
+            runtime_metrics,
+            app_lock,
         },
         queries: Queries {
             account: account_queries,
             streak: streak_queries,
+            check_in_timing: check_in_timing_queries,
             balance_statistics: balance_statistics_queries,
+            provider: provider_queries,
         },
         command_handlers,
     })
@@ -386,6 +557,40 @@ fn build_config_service(
     Ok(service)
 }
 
+/// Load existing auto check-in schedules from the database
+async fn load_schedules(
+    scheduler: &AutoCheckInScheduler,
+    provider_repo: &Arc<dyn ProviderRepository>,
+    account_repo: &Arc<dyn AccountRepository>,
+    app_handle: &tauri::AppHandle,
+) -> Result<(), Box<dyn std::error::Error>> {
+    info!("📋 Loading auto check-in schedules...");
+    let started_at = Instant::now();
+    let provider_list = provider_repo
+        .find_all()
+        .await
+        .map_err(|e| format!("Failed to load providers: {}", e))?;
+    info!("📦 Got {} providers", provider_list.len());
+
+    let providers_map: HashMap<String, Provider> = provider_list
+        .into_iter()
+        .map(|provider| (provider.id().as_str().to_string(), provider))
+        .collect();
+
+    if let Err(e) = scheduler
+        .reload_schedules(providers_map, account_repo.clone(), app_handle.clone())
+        .await
+    {
+        warn!("⚠️  Failed to load schedules: {}", e);
+    } else {
+        info!(
+            "✓ Auto check-in schedules loaded ({}ms)",
+            started_at.elapsed().as_millis()
+        );
+    }
+    Ok(())
+}
+
 fn build_token_service(
     token_repo: Arc<dyn TokenRepository>,
     account_repo: Arc<dyn AccountRepository>,
@@ -406,3 +611,92 @@ fn build_token_service(
     );
     Ok(service)
 }
+
+/// Tell the user that the previous session crashed; the UI lists the reports through
+/// `get_crash_reports` and opens one with `open_crash_report`
+pub fn notify_previous_crash(app_handle: &tauri::AppHandle, log_dir: &Path) {
+    use tauri_plugin_notification::NotificationExt;
+
+    let reports = match logging::take_unnotified_crash_reports(log_dir) {
+        Ok(reports) => reports,
+        Err(e) => {
+            warn!("⚠️  Failed to check for crash reports: {}", e);
+            return;
+        }
+    };
+    let Some(latest) = reports.first() else {
+        return;
+    };
+
+    warn!(
+        "⚠️  Previous session crashed at {}: {} (report: {})",
+        latest.report.occurred_at.to_rfc3339(),
+        latest.report.message,
+        latest.path.display()
+    );
+    let body = format!(
+        "NeuraDock closed unexpectedly: {}. The crash report was saved to {}",
+        latest.report.message,
+        latest.path.display()
+    );
+    if let Err(e) = app_handle
+        .notification()
+        .builder()
+        .title("NeuraDock crashed last time")
+        .body(body)
+        .show()
+    {
+        warn!("⚠️  Failed to send crash notification: {}", e);
+    }
+}
+
+/// Tell the user that a corrupt database was replaced; the UI can read the details
+/// through `get_database_recovery`
+fn notify_stale_data_location(app_handle: &tauri::AppHandle, stale: &StaleDataLocation) {
+    use tauri_plugin_notification::NotificationExt;
+
+    error!("❌ {}", stale.message());
+    if let Err(e) = app_handle
+        .notification()
+        .builder()
+        .title("Data directory not available")
+        .body(stale.message())
+        .show()
+    {
+        warn!("⚠️  Failed to send data location notification: {}", e);
+    }
+}
+
+fn notify_database_recovery(app_handle: &tauri::AppHandle, recovery: &DatabaseRecovery) {
+    use tauri_plugin_notification::NotificationExt;
+
+    warn!("⚠️  {}", recovery.message());
+    let title = match recovery {
+        DatabaseRecovery::RestoredFromBackup { .. } => "Database restored from backup",
+        DatabaseRecovery::StartedFresh { .. } => "Database was reset",
+    };
+    if let Err(e) = app_handle
+        .notification()
+        .builder()
+        .title(title)
+        .body(recovery.message())
+        .show()
+    {
+        warn!("⚠️  Failed to send database recovery notification: {}", e);
+    }
+}
+
+fn notify_migration_failure(app_handle: &tauri::AppHandle, failure: &MigrationFailure) {
+    use tauri_plugin_notification::NotificationExt;
+
+    error!("❌ {}", failure.message());
+    if let Err(e) = app_handle
+        .notification()
+        .builder()
+        .title("Database update failed")
+        .body(failure.message())
+        .show()
+    {
+        warn!("⚠️  Failed to send migration failure notification: {}", e);
+    }
+}
@@ -18,6 +18,7 @@ pub async fn create_account(
         provider_id: input.provider_id,
         cookies: input.cookies,
         api_user: input.api_user,
+        extra_headers: input.extra_headers,
         auto_checkin_enabled: input.auto_checkin_enabled,
         auto_checkin_hour: input.auto_checkin_hour,
         auto_checkin_minute: input.auto_checkin_minute,
@@ -48,10 +49,12 @@ pub async fn update_account(
         provider_id: input.provider_id,
         cookies: input.cookies,
         api_user: input.api_user,
+        extra_headers: input.extra_headers,
         auto_checkin_enabled: input.auto_checkin_enabled,
         auto_checkin_hour: input.auto_checkin_hour,
         auto_checkin_minute: input.auto_checkin_minute,
         check_in_interval_hours: input.check_in_interval_hours,
+        max_attempts_per_day: input.max_attempts_per_day,
     };
 
     let result = state
@@ -66,7 +69,7 @@ pub async fn update_account(
     Ok(result.success)
 }
 
-/// Delete an account
+/// Delete an account, restorable with `undo_delete_account` until it is purged
 #[tauri::command]
 #[specta::specta]
 pub async fn delete_account(
@@ -87,6 +90,47 @@ pub async fn delete_account(
     Ok(result.success)
 }
 
+/// Restore an account deleted within its undo window
+#[tauri::command]
+#[specta::specta]
+pub async fn undo_delete_account(
+    account_id: String,
+    state: State<'_, CommandHandlers>,
+) -> Result<bool, CommandError> {
+    let command = UndoDeleteAccountCommand { account_id };
+
+    let result = state
+        .undo_delete_account
+        .handle(command)
+        .await
+        .map_err(CommandError::from)?;
+
+    // Scheduler will be reloaded automatically via AccountRestored event
+    // handled by SchedulerReloadEventHandler
+
+    Ok(result.success)
+}
+
+/// Permanently remove all deleted accounts without waiting for their undo
+/// window, returning how many were removed
+#[tauri::command]
+#[specta::specta]
+pub async fn purge_deleted_accounts(
+    state: State<'_, CommandHandlers>,
+) -> Result<u32, CommandError> {
+    let command = PurgeDeletedAccountsCommand {
+        deleted_before: chrono::Utc::now(),
+    };
+
+    let result = state
+        .purge_deleted_accounts
+        .handle(command)
+        .await
+        .map_err(CommandError::from)?;
+
+    Ok(result.purged as u32)
+}
+
 /// Toggle account enabled/disabled status
 #[tauri::command]
 #[specta::specta]
@@ -111,3 +155,21 @@ pub async fn toggle_account(
 
     Ok(result.success)
 }
+
+/// Pin or unpin an account, returning the new pinned state
+#[tauri::command]
+#[specta::specta]
+pub async fn toggle_pin(
+    account_id: String,
+    state: State<'_, CommandHandlers>,
+) -> Result<bool, CommandError> {
+    let command = TogglePinCommand { account_id };
+
+    let result = state
+        .toggle_pin
+        .handle(command)
+        .await
+        .map_err(CommandError::from)?;
+
+    Ok(result.pinned)
+}
@@ -1,6 +1,6 @@
 use crate::application::dtos::ExportAccountsInput;
 use crate::presentation::error::CommandError;
-use crate::presentation::state::Repositories;
+use crate::presentation::state::{Repositories, Services};
 use neuradock_domain::shared::AccountId;
 use tauri::State;
 
@@ -10,7 +10,12 @@ use tauri::State;
 pub async fn export_accounts_to_json(
     input: ExportAccountsInput,
     repositories: State<'_, Repositories>,
+    services: State<'_, Services>,
 ) -> Result<String, CommandError> {
+    if input.include_credentials {
+        services.app_lock.ensure_unlocked()?;
+    }
+
     let accounts = if input.account_ids.is_empty() {
         repositories
             .account
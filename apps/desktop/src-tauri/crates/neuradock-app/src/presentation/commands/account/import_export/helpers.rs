@@ -1,24 +1,188 @@
 use chrono::{Duration, Utc};
+use neuradock_domain::account::{Account, AccountRepository, Credentials};
 use neuradock_domain::session::{Session, SessionRepository, SessionTokenExtractor};
-use neuradock_domain::shared::AccountId;
+use neuradock_domain::shared::{AccountId, ProviderId, TransactionContext, UnitOfWork};
 use std::collections::HashMap;
 use std::sync::Arc;
+use tracing::warn;
 
+use crate::application::dtos::ImportAccountInput;
 use crate::presentation::error::CommandError;
+use crate::presentation::state::Repositories;
 
 const DEFAULT_SESSION_EXPIRATION_DAYS: i64 = 30;
 
+/// Error reported for rows of an atomic batch that were discarded with it
+const ROLLED_BACK_ERROR: &str = "Rolled back because another item in the batch failed";
+
+/// Account and its default session, saved together for one imported row
+pub(super) struct AccountWrite {
+    pub account: Account,
+    pub session: Session,
+}
+
+impl AccountWrite {
+    /// New account built from an import input
+    pub fn create(input: ImportAccountInput) -> Result<Self, CommandError> {
+        let session_cookies = input.cookies.clone();
+        let credentials = Credentials::new(input.cookies, input.api_user);
+        let account = Account::new(
+            input.name,
+            ProviderId::from_string(&input.provider),
+            credentials,
+        )
+        .map_err(CommandError::from)?;
+        let session = default_session(account.id().clone(), &session_cookies)?;
+
+        Ok(Self { account, session })
+    }
+
+    /// Existing account with its credentials replaced
+    pub fn update(
+        mut account: Account,
+        cookies: HashMap<String, String>,
+        api_user: String,
+    ) -> Result<Self, CommandError> {
+        let session = default_session(account.id().clone(), &cookies)?;
+        account
+            .update_credentials(Credentials::new(cookies, api_user))
+            .map_err(CommandError::from)?;
+
+        Ok(Self { account, session })
+    }
+
+    pub fn account_id(&self) -> String {
+        self.account.id().as_str().to_string()
+    }
+
+    /// Save the account and its session outside of any transaction
+    pub async fn save(
+        &self,
+        account_repo: &Arc<dyn AccountRepository>,
+        session_repo: &Arc<dyn SessionRepository>,
+    ) -> Result<(), CommandError> {
+        account_repo
+            .save(&self.account)
+            .await
+            .map_err(CommandError::from)?;
+        session_repo
+            .save(&self.session)
+            .await
+            .map_err(CommandError::from)
+    }
+}
+
+/// Save every row of a batch, or none of them if any row fails
+///
+/// Returns the outcome of each row in input order. The row that failed keeps
+/// its own error while the others report that they were rolled back.
+pub(super) async fn save_batch_atomically(
+    rows: Vec<Result<AccountWrite, CommandError>>,
+    repositories: &Repositories,
+) -> Vec<Result<AccountWrite, String>> {
+    if rows.iter().any(Result::is_err) {
+        return rows
+            .into_iter()
+            .map(|row| match row {
+                Ok(_) => Err(ROLLED_BACK_ERROR.to_string()),
+                Err(e) => Err(e.to_string()),
+            })
+            .collect();
+    }
+
+    let writes: Vec<AccountWrite> = rows.into_iter().flatten().collect();
+    match save_all_in_transaction(&writes, repositories).await {
+        Ok(()) => writes.into_iter().map(Ok).collect(),
+        Err((failed_index, error)) => (0..writes.len())
+            .map(|index| match failed_index {
+                Some(failed) if failed != index => Err(ROLLED_BACK_ERROR.to_string()),
+                _ => Err(error.to_string()),
+            })
+            .collect(),
+    }
+}
+
+/// Save every row of a batch on its own, keeping the rows that succeed
+pub(super) async fn save_batch_best_effort(
+    rows: Vec<Result<AccountWrite, CommandError>>,
+    repositories: &Repositories,
+) -> Vec<Result<AccountWrite, String>> {
+    let mut outcomes = Vec::with_capacity(rows.len());
+    for row in rows {
+        let outcome = match row {
+            Ok(write) => match write
+                .save(&repositories.account, &repositories.session)
+                .await
+            {
+                Ok(()) => Ok(write),
+                Err(e) => Err(e.to_string()),
+            },
+            Err(e) => Err(e.to_string()),
+        };
+        outcomes.push(outcome);
+    }
+    outcomes
+}
+
+/// The error carries the index of the failing write, or `None` when the
+/// transaction itself could not be started or committed
+async fn save_all_in_transaction(
+    writes: &[AccountWrite],
+    repositories: &Repositories,
+) -> Result<(), (Option<usize>, CommandError)> {
+    let mut tx = repositories
+        .unit_of_work
+        .begin()
+        .await
+        .map_err(|e| (None, CommandError::infrastructure(e.to_string())))?;
+
+    for (index, write) in writes.iter().enumerate() {
+        let saved = async {
+            repositories
+                .account
+                .save_in_transaction(tx.as_mut(), &write.account)
+                .await?;
+            repositories
+                .session
+                .save_in_transaction(tx.as_mut(), &write.session)
+                .await
+        }
+        .await;
+
+        if let Err(e) = saved {
+            if let Err(rollback_err) = tx.rollback().await {
+                warn!(
+                    target: "neuradock::import",
+                    "Failed to roll back batch import: {}",
+                    rollback_err
+                );
+            }
+            return Err((Some(index), CommandError::from(e)));
+        }
+    }
+
+    tx.commit()
+        .await
+        .map_err(|e| (None, CommandError::infrastructure(e.to_string())))
+}
+
+fn default_session(
+    account_id: AccountId,
+    cookies: &HashMap<String, String>,
+) -> Result<Session, CommandError> {
+    let session_token = SessionTokenExtractor::extract(cookies);
+    let expires_at = Utc::now() + Duration::days(DEFAULT_SESSION_EXPIRATION_DAYS);
+
+    Session::new(account_id, session_token, expires_at).map_err(CommandError::from)
+}
+
 /// Helper function to create and save a default session for an account
 pub(super) async fn create_and_save_default_session(
     account_id: AccountId,
     cookies: &HashMap<String, String>,
     session_repo: &Arc<dyn SessionRepository>,
 ) -> Result<(), CommandError> {
-    let session_token = SessionTokenExtractor::extract(cookies);
-
-    let expires_at = Utc::now() + Duration::days(DEFAULT_SESSION_EXPIRATION_DAYS);
-    let session =
-        Session::new(account_id, session_token, expires_at).map_err(CommandError::from)?;
+    let session = default_session(account_id, cookies)?;
 
     session_repo
         .save(&session)
@@ -29,59 +193,12 @@ pub(super) async fn create_and_save_default_session(
 
 /// Helper function to import a single account
 pub(super) async fn import_single_account(
-    input: crate::application::dtos::ImportAccountInput,
-    account_repo: &Arc<dyn neuradock_domain::account::AccountRepository>,
+    input: ImportAccountInput,
+    account_repo: &Arc<dyn AccountRepository>,
     session_repo: &Arc<dyn SessionRepository>,
 ) -> Result<String, CommandError> {
-    use neuradock_domain::account::{Account, Credentials};
-    use neuradock_domain::shared::ProviderId;
-
-    let cookies = input.cookies.clone();
-    let credentials = Credentials::new(input.cookies, input.api_user);
-    let account = Account::new(
-        input.name,
-        ProviderId::from_string(&input.provider),
-        credentials,
-    )
-    .map_err(CommandError::from)?;
-
-    let account_id = account.id().clone();
-    account_repo
-        .save(&account)
-        .await
-        .map_err(CommandError::from)?;
-
-    create_and_save_default_session(account_id.clone(), &cookies, session_repo).await?;
-
-    Ok(account_id.as_str().to_string())
-}
-
-/// Helper function to update account cookies
-pub(super) async fn update_account_cookies(
-    account_id: &AccountId,
-    cookies: HashMap<String, String>,
-    api_user: String,
-    account_repo: &Arc<dyn neuradock_domain::account::AccountRepository>,
-    session_repo: &Arc<dyn SessionRepository>,
-) -> Result<(), CommandError> {
-    use neuradock_domain::account::Credentials;
-
-    let mut account = account_repo
-        .find_by_id(account_id)
-        .await
-        .map_err(CommandError::from)?
-        .ok_or_else(|| CommandError::not_found("Account not found"))?;
+    let write = AccountWrite::create(input)?;
+    write.save(account_repo, session_repo).await?;
 
-    let credentials = Credentials::new(cookies.clone(), api_user);
-    account
-        .update_credentials(credentials)
-        .map_err(CommandError::from)?;
-    account_repo
-        .save(&account)
-        .await
-        .map_err(CommandError::from)?;
-
-    create_and_save_default_session(account_id.clone(), &cookies, session_repo).await?;
-
-    Ok(())
+    Ok(write.account_id())
 }
@@ -4,28 +4,40 @@ use crate::presentation::state::{Repositories, Services};
 use tauri::State;
 use tracing::warn;
 
-use super::helpers::import_single_account;
+use super::helpers::{save_batch_atomically, save_batch_best_effort, AccountWrite};
 
 /// Import multiple accounts from JSON (batch)
+///
+/// With `atomic` (the default) either every account is imported or none is;
+/// otherwise each account is saved on its own and failures are skipped.
 #[tauri::command]
 #[specta::specta]
 pub async fn import_accounts_batch(
     json_data: String,
+    atomic: Option<bool>,
     repositories: State<'_, Repositories>,
     services: State<'_, Services>,
 ) -> Result<BatchImportResult, CommandError> {
     let inputs: Vec<ImportAccountInput> =
         serde_json::from_str(&json_data).map_err(CommandError::from)?;
 
+    let account_names: Vec<String> = inputs.iter().map(|input| input.name.clone()).collect();
+    let rows = inputs.into_iter().map(AccountWrite::create).collect();
+    let outcomes = if atomic.unwrap_or(true) {
+        save_batch_atomically(rows, &repositories).await
+    } else {
+        save_batch_best_effort(rows, &repositories).await
+    };
+
     let mut results = Vec::new();
     let mut succeeded = 0;
     let mut failed = 0;
 
-    for input in inputs {
-        let account_name = input.name.clone();
-        match import_single_account(input, &repositories.account, &repositories.session).await {
-            Ok(account_id) => {
+    for (account_name, outcome) in account_names.into_iter().zip(outcomes) {
+        match outcome {
+            Ok(write) => {
                 succeeded += 1;
+                let account_id = write.account_id();
                 if let Err(err) = services
                     .balance
                     .fetch_account_balance(&account_id, true)
@@ -51,7 +63,7 @@ pub async fn import_accounts_batch(
                     success: false,
                     account_id: None,
                     account_name,
-                    error: Some(e.to_string()),
+                    error: Some(e),
                 });
             }
         }
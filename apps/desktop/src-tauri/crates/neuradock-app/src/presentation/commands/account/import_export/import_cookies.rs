@@ -0,0 +1,123 @@
+use crate::application::dtos::ImportAccountInput;
+use crate::presentation::error::CommandError;
+use crate::presentation::state::{Repositories, Services};
+use neuradock_domain::session::CookieImportParser;
+use neuradock_domain::shared::ProviderId;
+use std::collections::HashMap;
+use tauri::State;
+use tracing::{info, warn};
+
+use super::helpers::import_single_account;
+
+/// Import a single account from a browser cookie export file
+/// (Netscape cookies.txt or cookie-editor JSON)
+#[tauri::command]
+#[specta::specta]
+pub async fn import_account_from_cookie_file(
+    path: String,
+    provider: String,
+    name: String,
+    api_user: Option<String>,
+    repositories: State<'_, Repositories>,
+    services: State<'_, Services>,
+) -> Result<String, CommandError> {
+    let content = tokio::fs::read_to_string(&path)
+        .await
+        .map_err(|e| CommandError::validation(format!("Failed to read cookie file: {}", e)))?;
+
+    let provider_domain = provider_domain(&provider, &repositories).await?;
+    let cookies =
+        CookieImportParser::parse(&content, &provider_domain).map_err(CommandError::from)?;
+
+    info!(
+        target: "neuradock::import",
+        provider = %provider,
+        "Parsed {} cookies for {} from cookie file",
+        cookies.len(),
+        provider_domain
+    );
+
+    import_with_cookies(name, provider, cookies, api_user, &repositories, &services).await
+}
+
+/// Import a single account from a raw `Cookie` header string copied from devtools
+#[tauri::command]
+#[specta::specta]
+pub async fn import_account_from_cookie_string(
+    cookie_string: String,
+    provider: String,
+    name: String,
+    repositories: State<'_, Repositories>,
+    services: State<'_, Services>,
+) -> Result<String, CommandError> {
+    let cookies = CookieImportParser::parse_header(&cookie_string).map_err(CommandError::from)?;
+
+    info!(
+        target: "neuradock::import",
+        provider = %provider,
+        "Parsed {} cookies from cookie string",
+        cookies.len()
+    );
+
+    import_with_cookies(name, provider, cookies, None, &repositories, &services).await
+}
+
+/// Resolve the provider's domain for cookie filtering
+async fn provider_domain(
+    provider_id: &str,
+    repositories: &Repositories,
+) -> Result<String, CommandError> {
+    let provider = repositories
+        .provider
+        .find_by_id(&ProviderId::from_string(provider_id))
+        .await
+        .map_err(CommandError::from)?
+        .ok_or_else(|| {
+            CommandError::from_code(
+                neuradock_domain::shared::ErrorCode::ProviderNotFound,
+                format!("Provider not found: {}", provider_id),
+            )
+        })?;
+
+    Ok(provider.domain().to_string())
+}
+
+/// Create the account from parsed cookies and prefetch its balance
+async fn import_with_cookies(
+    name: String,
+    provider: String,
+    cookies: HashMap<String, String>,
+    api_user: Option<String>,
+    repositories: &Repositories,
+    services: &Services,
+) -> Result<String, CommandError> {
+    if cookies.is_empty() {
+        return Err(CommandError::validation(
+            "No cookies found for the provider's domain",
+        ));
+    }
+
+    let input = ImportAccountInput {
+        name,
+        provider,
+        cookies,
+        api_user: api_user.unwrap_or_default(),
+    };
+    let account_id =
+        import_single_account(input, &repositories.account, &repositories.session).await?;
+
+    if let Err(err) = services
+        .balance
+        .fetch_account_balance(&account_id, true)
+        .await
+    {
+        warn!(
+            target: "neuradock::import",
+            account_id = %account_id,
+            "Failed to prefetch balance after cookie import: {}",
+            err
+        );
+    }
+
+    Ok(account_id)
+}
@@ -3,15 +3,19 @@ use crate::presentation::error::CommandError;
 use crate::presentation::state::Repositories;
 use tauri::State;
 
-use super::helpers::{import_single_account, update_account_cookies};
+use super::helpers::{save_batch_atomically, save_batch_best_effort, AccountWrite};
 
 /// Batch update accounts - match by name+provider, update cookies and api_user
 /// If account doesn't exist and create_if_not_exists is true, create it
+///
+/// With `atomic` (the default) either every account is written or none is;
+/// otherwise each account is saved on its own and failures are skipped.
 #[tauri::command]
 #[specta::specta]
 pub async fn update_accounts_batch(
     json_data: String,
     create_if_not_exists: bool,
+    atomic: Option<bool>,
     repositories: State<'_, Repositories>,
 ) -> Result<BatchUpdateResult, CommandError> {
     let inputs: Vec<ImportAccountInput> =
@@ -24,14 +28,12 @@ pub async fn update_accounts_batch(
         .await
         .map_err(CommandError::from)?;
 
-    let mut results = Vec::new();
-    let mut updated = 0;
-    let mut created = 0;
-    let mut failed = 0;
+    // (account name, existing account id, action) per row
+    let mut items = Vec::with_capacity(inputs.len());
+    let mut rows = Vec::with_capacity(inputs.len());
 
     for input in inputs {
         let account_name = input.name.clone();
-        let provider_id = input.provider.clone();
 
         // Try to find existing account by name + provider
         let existing = existing_accounts
@@ -40,76 +42,63 @@ pub async fn update_accounts_batch(
 
         match existing {
             Some(acc) => {
-                // Update existing account
-                let account_id = acc.id().clone();
-                match update_account_cookies(
-                    &account_id,
+                items.push((account_name, Some(acc.id().as_str().to_string()), "updated"));
+                rows.push(AccountWrite::update(
+                    acc.clone(),
                     input.cookies,
                     input.api_user,
-                    &repositories.account,
-                    &repositories.session,
-                )
-                .await
-                {
-                    Ok(_) => {
-                        updated += 1;
-                        results.push(UpdateItemResult {
-                            success: true,
-                            account_id: Some(account_id.as_str().to_string()),
-                            account_name,
-                            action: "updated".to_string(),
-                            error: None,
-                        });
-                    }
-                    Err(e) => {
-                        failed += 1;
-                        results.push(UpdateItemResult {
-                            success: false,
-                            account_id: Some(account_id.as_str().to_string()),
-                            account_name,
-                            action: "failed".to_string(),
-                            error: Some(e.to_string()),
-                        });
-                    }
-                }
+                ));
+            }
+            None if create_if_not_exists => {
+                items.push((account_name, None, "created"));
+                rows.push(AccountWrite::create(input));
             }
             None => {
-                if create_if_not_exists {
-                    // Create new account
-                    match import_single_account(input, &repositories.account, &repositories.session)
-                        .await
-                    {
-                        Ok(account_id) => {
-                            created += 1;
-                            results.push(UpdateItemResult {
-                                success: true,
-                                account_id: Some(account_id),
-                                account_name,
-                                action: "created".to_string(),
-                                error: None,
-                            });
-                        }
-                        Err(e) => {
-                            failed += 1;
-                            results.push(UpdateItemResult {
-                                success: false,
-                                account_id: None,
-                                account_name,
-                                action: "failed".to_string(),
-                                error: Some(e.to_string()),
-                            });
-                        }
-                    }
+                items.push((account_name, None, "failed"));
+                rows.push(Err(CommandError::not_found(format!(
+                    "Account not found (provider: {})",
+                    input.provider
+                ))));
+            }
+        }
+    }
+
+    let outcomes = if atomic.unwrap_or(true) {
+        save_batch_atomically(rows, &repositories).await
+    } else {
+        save_batch_best_effort(rows, &repositories).await
+    };
+
+    let mut results = Vec::new();
+    let mut updated = 0;
+    let mut created = 0;
+    let mut failed = 0;
+
+    for ((account_name, existing_id, action), outcome) in items.into_iter().zip(outcomes) {
+        match outcome {
+            Ok(write) => {
+                if action == "created" {
+                    created += 1;
                 } else {
-                    failed += 1;
-                    results.push(UpdateItemResult {
-                        success: false,
-                        account_id: None,
-                        account_name,
-                        action: "failed".to_string(),
-                        error: Some(format!("Account not found (provider: {})", provider_id)),
-                    });
+                    updated += 1;
                 }
+                results.push(UpdateItemResult {
+                    success: true,
+                    account_id: Some(write.account_id()),
+                    account_name,
+                    action: action.to_string(),
+                    error: None,
+                });
+            }
+            Err(e) => {
+                failed += 1;
+                results.push(UpdateItemResult {
+                    success: false,
+                    account_id: existing_id,
+                    account_name,
+                    action: "failed".to_string(),
+                    error: Some(e),
+                });
             }
         }
     }
@@ -1,6 +1,6 @@
 use crate::application::dtos;
 use crate::presentation::error::CommandError;
-use crate::presentation::state::{Queries, Repositories};
+use crate::presentation::state::{Queries, Repositories, Services};
 use neuradock_domain::check_in::Provider;
 use neuradock_domain::shared::AccountId;
 use std::collections::HashMap;
@@ -25,13 +25,19 @@ pub async fn get_all_accounts(
         .map_err(CommandError::from)
 }
 
-/// Get account detail by ID
+/// Get account detail by ID, including its cookies
+///
+/// Fails with `AppLocked` while the app lock is engaged.
 #[tauri::command]
 #[specta::specta]
 pub async fn get_account_detail(
     account_id: String,
     repositories: State<'_, Repositories>,
+    services: State<'_, Services>,
 ) -> Result<dtos::AccountDetailDto, CommandError> {
+    // The detail carries the account's cookies
+    services.app_lock.ensure_unlocked()?;
+
     let id = AccountId::from_string(&account_id);
     let account = repositories
         .account
@@ -0,0 +1,19 @@
+use crate::application::dtos::BalanceBackfillDto;
+use crate::presentation::error::CommandError;
+use crate::presentation::state::Services;
+use tauri::State;
+
+/// Backfill balance history from the provider's transaction log
+/// Days that already have history are kept, so it is safe to run again
+#[tauri::command]
+#[specta::specta]
+pub async fn backfill_balance_history(
+    account_id: String,
+    state: State<'_, Services>,
+) -> Result<BalanceBackfillDto, CommandError> {
+    state
+        .balance
+        .backfill_balance_history(&account_id)
+        .await
+        .map_err(CommandError::from)
+}
@@ -1,31 +1,50 @@
 use crate::application::dtos::BalanceDto;
 use crate::presentation::error::CommandError;
+use crate::presentation::events::{BalanceRefreshProgress, BalanceUpdated};
 use crate::presentation::state::Services;
 use std::collections::HashMap;
 use tauri::State;
-
-use super::fetch::fetch_account_balance;
+use tauri_specta::Event;
 
 /// Fetch balances for multiple accounts
+///
+/// Accounts are refreshed concurrently. A `BalanceRefreshProgress` event is emitted as
+/// each account completes, plus a `BalanceUpdated` event for each successful refresh.
 #[tauri::command]
 #[specta::specta]
 pub async fn fetch_accounts_balances(
+    app: tauri::AppHandle,
     account_ids: Vec<String>,
     force_refresh: Option<bool>,
     state: State<'_, Services>,
 ) -> Result<HashMap<String, Option<BalanceDto>>, CommandError> {
-    let mut results = HashMap::new();
-
-    for account_id in account_ids {
-        match fetch_account_balance(account_id.clone(), force_refresh, state.clone()).await {
-            Ok(balance) => {
-                results.insert(account_id, Some(balance));
+    let results = state
+        .balance
+        .fetch_accounts_balances(account_ids, force_refresh.unwrap_or(false), |progress| {
+            if let Some(balance) = &progress.balance {
+                let event = BalanceUpdated {
+                    account_id: progress.account_id.clone(),
+                    current_balance: balance.current_balance,
+                    total_consumed: balance.total_consumed,
+                    total_quota: balance.total_quota,
+                };
+                if let Err(e) = event.emit(&app) {
+                    log::warn!("Failed to emit balance update: {}", e);
+                }
             }
-            Err(_) => {
-                results.insert(account_id, None);
+
+            let event = BalanceRefreshProgress {
+                success: progress.balance.is_some(),
+                account_id: progress.account_id,
+                completed: progress.completed as u32,
+                total: progress.total as u32,
+                error: progress.error,
+            };
+            if let Err(e) = event.emit(&app) {
+                log::warn!("Failed to emit balance refresh progress: {}", e);
             }
-        }
-    }
+        })
+        .await;
 
     Ok(results)
 }
@@ -1,8 +1,10 @@
+mod backfill;
 mod batch;
 mod fetch;
 mod statistics;
 
 // Re-export all commands for backward compatibility
+pub use backfill::backfill_balance_history;
 pub use batch::fetch_accounts_balances;
 pub use fetch::fetch_account_balance;
-pub use statistics::get_balance_statistics;
+pub use statistics::{get_balance_statistics, get_balance_statistics_over_time};
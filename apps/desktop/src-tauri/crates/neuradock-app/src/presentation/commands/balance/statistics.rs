@@ -1,17 +1,34 @@
-use crate::application::dtos::BalanceStatisticsDto;
+use crate::application::dtos::{BalanceStatisticsDto, BalanceStatisticsOverTimeDto};
 use crate::presentation::error::CommandError;
 use crate::presentation::state::Queries;
 use tauri::State;
 
 /// Get balance statistics by provider
+///
+/// Disabled accounts are left out unless `include_disabled` is set.
 #[tauri::command]
 #[specta::specta]
 pub async fn get_balance_statistics(
+    include_disabled: Option<bool>,
     state: State<'_, Queries>,
 ) -> Result<BalanceStatisticsDto, CommandError> {
     state
         .balance_statistics
-        .get_balance_statistics()
+        .get_balance_statistics(include_disabled.unwrap_or(false))
+        .await
+        .map_err(CommandError::from)
+}
+
+/// Get provider balance totals per day over the last `days` days
+#[tauri::command]
+#[specta::specta]
+pub async fn get_balance_statistics_over_time(
+    days: u32,
+    state: State<'_, Queries>,
+) -> Result<BalanceStatisticsOverTimeDto, CommandError> {
+    state
+        .balance_statistics
+        .get_balance_statistics_over_time(days)
         .await
         .map_err(CommandError::from)
 }
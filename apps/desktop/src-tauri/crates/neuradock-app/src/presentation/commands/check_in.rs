@@ -1,11 +1,13 @@
 use crate::application::commands::check_in_commands::*;
 use crate::application::commands::command_handler::CommandHandler;
 use crate::application::dtos::{
-    self, BatchCheckInResult, CheckInHistoryDto, CheckInStatsDto, ExecuteCheckInResult,
-    RunningJobDto,
+    self, BatchCheckInResult, CheckInHistoryDto, CheckInStatsDto, CheckInTimingDto,
+    CheckInTimingMetricsDto, ExecuteCheckInResult, RunningJobDto,
 };
+use crate::application::services::{RunningJobs, MAX_STREAK_FREEZES_PER_MONTH};
 use crate::presentation::error::CommandError;
-use crate::presentation::state::{CommandHandlers, Queries};
+use crate::presentation::state::{CommandHandlers, Queries, Services};
+use neuradock_infrastructure::monitoring::CheckInTimingMetrics;
 use tauri::State;
 
 /// Execute check-in for a single account
@@ -35,6 +37,7 @@ pub async fn execute_check_in(
         account_name: result.account_name,
         provider_id: result.provider_id,
         success: result.success,
+        reward: result.reward,
         balance: result.balance,
         error: if result.success {
             None
@@ -59,26 +62,25 @@ pub async fn execute_batch_check_in(
         .await
         .map_err(CommandError::from)?;
 
-    // Convert results to DTOs
-    let results_dto: Vec<ExecuteCheckInResult> = result
-        .results
-        .into_iter()
-        .map(|r| ExecuteCheckInResult {
-            account_id: r.account_id,
-            account_name: r.account_name,
-            provider_id: r.provider_id,
-            success: r.success,
-            balance: r.balance,
-            error: if r.success { None } else { Some(r.message) },
-        })
-        .collect();
+    Ok(batch_result_dto(result))
+}
 
-    Ok(BatchCheckInResult {
-        total: result.total as i32,
-        succeeded: result.succeeded as i32,
-        failed: result.failed as i32,
-        results: results_dto,
-    })
+/// Execute check-in for every enabled account of a provider
+#[tauri::command]
+#[specta::specta]
+pub async fn execute_check_in_for_provider(
+    provider_id: String,
+    handlers: State<'_, CommandHandlers>,
+) -> Result<BatchCheckInResult, CommandError> {
+    let command = ExecuteProviderCheckInCommand { provider_id };
+
+    let result = handlers
+        .batch_execute_check_in
+        .handle(command)
+        .await
+        .map_err(CommandError::from)?;
+
+    Ok(batch_result_dto(result))
 }
 
 /// Stop a running check-in job
@@ -116,7 +118,33 @@ pub async fn get_check_in_stats(
 #[tauri::command]
 #[specta::specta]
 pub async fn get_running_jobs() -> Result<Vec<RunningJobDto>, CommandError> {
-    Err(CommandError::infrastructure("Not implemented yet"))
+    Ok(RunningJobs::global()
+        .snapshot()
+        .into_iter()
+        .map(RunningJobDto::from)
+        .collect())
+}
+
+/// Get the phase timings of the latest check-ins of an account, newest first
+#[tauri::command]
+#[specta::specta]
+pub async fn get_check_in_timings(
+    account_id: String,
+    limit: Option<u32>,
+    queries: State<'_, Queries>,
+) -> Result<Vec<CheckInTimingDto>, CommandError> {
+    queries
+        .check_in_timing
+        .get_check_in_timings(&account_id, limit)
+        .await
+        .map_err(CommandError::from)
+}
+
+/// Get check-in phase timings aggregated since app start
+#[tauri::command]
+#[specta::specta]
+pub async fn get_check_in_timing_metrics() -> Result<CheckInTimingMetricsDto, CommandError> {
+    Ok(CheckInTimingMetrics::global().snapshot().into())
 }
 
 /// Get check-in streak statistics for an account
@@ -162,6 +190,36 @@ pub async fn get_check_in_calendar(
         .map_err(CommandError::from)
 }
 
+/// Get a whole year of check-ins as one heatmap intensity per day
+#[tauri::command]
+#[specta::specta]
+pub async fn get_check_in_year_heatmap(
+    account_id: String,
+    year: i32,
+    queries: State<'_, Queries>,
+) -> Result<dtos::CheckInYearHeatmapDto, CommandError> {
+    queries
+        .streak
+        .get_year_heatmap(&account_id, year)
+        .await
+        .map_err(CommandError::from)
+}
+
+/// Compare per-day quota and balance series and streaks of several accounts
+#[tauri::command]
+#[specta::specta]
+pub async fn get_accounts_comparison(
+    account_ids: Vec<String>,
+    days: u32,
+    queries: State<'_, Queries>,
+) -> Result<dtos::AccountsComparisonDto, CommandError> {
+    queries
+        .streak
+        .get_accounts_comparison(&account_ids, days)
+        .await
+        .map_err(CommandError::from)
+}
+
 /// Get check-in trend over a period of days
 #[tauri::command]
 #[specta::specta]
@@ -177,6 +235,21 @@ pub async fn get_check_in_trend(
         .map_err(CommandError::from)
 }
 
+/// Get days checked in over the current week, month or a custom range
+#[tauri::command]
+#[specta::specta]
+pub async fn get_check_in_consistency(
+    account_id: String,
+    period: dtos::ConsistencyPeriod,
+    queries: State<'_, Queries>,
+) -> Result<dtos::CheckInConsistencyDto, CommandError> {
+    queries
+        .streak
+        .get_consistency(&account_id, &period)
+        .await
+        .map_err(CommandError::from)
+}
+
 /// Get detailed check-in information for a specific day
 #[tauri::command]
 #[specta::specta]
@@ -202,3 +275,84 @@ pub async fn recalculate_check_in_streaks(queries: State<'_, Queries>) -> Result
         .await
         .map_err(CommandError::from)
 }
+
+/// Recalculate one account's check-in streak from its history
+#[tauri::command]
+#[specta::specta]
+pub async fn recalculate_check_in_streak(
+    account_id: String,
+    queries: State<'_, Queries>,
+) -> Result<dtos::CheckInStreakDto, CommandError> {
+    queries
+        .streak
+        .recalculate_streak(&account_id)
+        .await
+        .map_err(CommandError::from)
+}
+
+/// Start one account's check-in streak over, earlier check-ins no longer count
+#[tauri::command]
+#[specta::specta]
+pub async fn reset_check_in_streak(
+    account_id: String,
+    queries: State<'_, Queries>,
+) -> Result<dtos::CheckInStreakDto, CommandError> {
+    queries
+        .streak
+        .reset_streak(&account_id)
+        .await
+        .map_err(CommandError::from)
+}
+
+/// Get how many missed days per month do not break a check-in streak
+#[tauri::command]
+#[specta::specta]
+pub async fn get_streak_freezes_per_month(state: State<'_, Services>) -> Result<u32, CommandError> {
+    Ok(state.config.get_streak_freezes_per_month())
+}
+
+/// Set how many missed days per month do not break a check-in streak
+#[tauri::command]
+#[specta::specta]
+pub async fn set_streak_freezes_per_month(
+    freezes: u32,
+    state: State<'_, Services>,
+) -> Result<(), CommandError> {
+    if freezes > MAX_STREAK_FREEZES_PER_MONTH {
+        return Err(CommandError::validation(format!(
+            "Streak freezes per month must be at most {}",
+            MAX_STREAK_FREEZES_PER_MONTH
+        )));
+    }
+
+    state
+        .config
+        .set_streak_freezes_per_month(freezes)
+        .map_err(|e| {
+            CommandError::infrastructure(format!("Failed to save streak setting: {}", e))
+        })?;
+    Ok(())
+}
+
+fn batch_result_dto(result: BatchCheckInCommandResult) -> BatchCheckInResult {
+    let results_dto: Vec<ExecuteCheckInResult> = result
+        .results
+        .into_iter()
+        .map(|r| ExecuteCheckInResult {
+            account_id: r.account_id,
+            account_name: r.account_name,
+            provider_id: r.provider_id,
+            success: r.success,
+            reward: r.reward,
+            balance: r.balance,
+            error: if r.success { None } else { Some(r.message) },
+        })
+        .collect();
+
+    BatchCheckInResult {
+        total: result.total as i32,
+        succeeded: result.succeeded as i32,
+        failed: result.failed as i32,
+        results: results_dto,
+    }
+}
@@ -1,6 +1,9 @@
-use crate::application::services::LogLevel;
+use crate::application::dtos::{ModuleLogLevelDto, WafDiagnosticCaptureDto, WafMetricsDto};
+use crate::application::services::{LogLevel, WafBypassConfig, MAX_RESPONSE_BODY_MB};
 use crate::presentation::error::CommandError;
 use crate::presentation::state::Services;
+use neuradock_infrastructure::http::waf_bypass;
+use neuradock_infrastructure::monitoring::WafMetrics;
 use tauri::State;
 
 /// Get current log level
@@ -15,18 +18,7 @@ pub async fn get_log_level(state: State<'_, Services>) -> Result<String, Command
 #[tauri::command]
 #[specta::specta]
 pub async fn set_log_level(level: String, state: State<'_, Services>) -> Result<(), CommandError> {
-    let log_level = match level.to_lowercase().as_str() {
-        "error" => LogLevel::Error,
-        "warn" => LogLevel::Warn,
-        "info" => LogLevel::Info,
-        "debug" => LogLevel::Debug,
-        "trace" => LogLevel::Trace,
-        _ => {
-            return Err(CommandError::validation(
-                "Invalid log level. Must be one of: error, warn, info, debug, trace",
-            ));
-        }
-    };
+    let log_level = parse_log_level(&level)?;
 
     state
         .config
@@ -34,3 +26,254 @@ pub async fn set_log_level(level: String, state: State<'_, Services>) -> Result<
         .map_err(|e| CommandError::infrastructure(format!("Failed to save log level: {}", e)))?;
     Ok(())
 }
+
+fn parse_log_level(level: &str) -> Result<LogLevel, CommandError> {
+    LogLevel::parse(level).ok_or_else(|| {
+        CommandError::validation(
+            "Invalid log level. Must be one of: error, warn, info, debug, trace",
+        )
+    })
+}
+
+/// Get the log level overrides by module prefix
+#[tauri::command]
+#[specta::specta]
+pub async fn get_module_log_levels(
+    state: State<'_, Services>,
+) -> Result<Vec<ModuleLogLevelDto>, CommandError> {
+    Ok(state
+        .config
+        .get_module_log_levels()
+        .into_iter()
+        .map(|(module, level)| ModuleLogLevelDto {
+            module,
+            level: level.as_str().to_string(),
+        })
+        .collect())
+}
+
+/// Override the log level of a module prefix, e.g. `neuradock_infrastructure::http`
+/// Applied immediately, an explicit RUST_LOG still wins
+#[tauri::command]
+#[specta::specta]
+pub async fn set_module_log_level(
+    module: String,
+    level: String,
+    state: State<'_, Services>,
+) -> Result<(), CommandError> {
+    let log_level = parse_log_level(&level)?;
+
+    state
+        .config
+        .set_module_log_level(&module, log_level)
+        .map_err(|e| CommandError::validation(format!("Invalid module log level: {}", e)))?;
+    Ok(())
+}
+
+/// Remove the log level override of a module prefix
+#[tauri::command]
+#[specta::specta]
+pub async fn clear_module_log_level(
+    module: String,
+    state: State<'_, Services>,
+) -> Result<(), CommandError> {
+    state.config.clear_module_log_level(&module).map_err(|e| {
+        CommandError::infrastructure(format!("Failed to save module log levels: {}", e))
+    })?;
+    Ok(())
+}
+
+/// Get the configured browser path for WAF bypass (None = auto-detect)
+#[tauri::command]
+#[specta::specta]
+pub async fn get_browser_path(state: State<'_, Services>) -> Result<Option<String>, CommandError> {
+    Ok(state.config.get_browser_path())
+}
+
+/// Set or clear the browser path for WAF bypass
+#[tauri::command]
+#[specta::specta]
+pub async fn set_browser_path(
+    path: Option<String>,
+    state: State<'_, Services>,
+) -> Result<(), CommandError> {
+    state
+        .config
+        .set_browser_path(path)
+        .map_err(|e| CommandError::validation(format!("Invalid browser path: {}", e)))?;
+    Ok(())
+}
+
+/// Get the extra Chromium flags used for WAF bypass
+#[tauri::command]
+#[specta::specta]
+pub async fn get_browser_extra_args(
+    state: State<'_, Services>,
+) -> Result<Vec<String>, CommandError> {
+    Ok(state.config.get_browser_extra_args())
+}
+
+/// Set the extra Chromium flags used for WAF bypass
+#[tauri::command]
+#[specta::specta]
+pub async fn set_browser_extra_args(
+    args: Vec<String>,
+    state: State<'_, Services>,
+) -> Result<(), CommandError> {
+    state
+        .config
+        .set_browser_extra_args(args)
+        .map_err(|e| CommandError::validation(format!("Invalid browser arguments: {}", e)))?;
+    Ok(())
+}
+
+/// Get providers whose cached WAF cookies are used without validation
+#[tauri::command]
+#[specta::specta]
+pub async fn get_waf_validation_skip_providers(
+    state: State<'_, Services>,
+) -> Result<Vec<String>, CommandError> {
+    Ok(state.config.get_waf_validation_skip_providers())
+}
+
+/// Set providers whose cached WAF cookies are used without validation
+#[tauri::command]
+#[specta::specta]
+pub async fn set_waf_validation_skip_providers(
+    provider_ids: Vec<String>,
+    state: State<'_, Services>,
+) -> Result<(), CommandError> {
+    state
+        .config
+        .set_waf_validation_skip_providers(provider_ids)
+        .map_err(|e| CommandError::infrastructure(format!("Failed to save WAF settings: {}", e)))?;
+    Ok(())
+}
+
+/// Get providers whose WAF cookies are cached per account instead of shared per domain
+#[tauri::command]
+#[specta::specta]
+pub async fn get_waf_per_account_cookie_providers(
+    state: State<'_, Services>,
+) -> Result<Vec<String>, CommandError> {
+    Ok(state.config.get_waf_per_account_cookie_providers())
+}
+
+/// Set providers whose WAF cookies are cached per account instead of shared per domain
+#[tauri::command]
+#[specta::specta]
+pub async fn set_waf_per_account_cookie_providers(
+    provider_ids: Vec<String>,
+    state: State<'_, Services>,
+) -> Result<(), CommandError> {
+    state
+        .config
+        .set_waf_per_account_cookie_providers(provider_ids)
+        .map_err(|e| CommandError::infrastructure(format!("Failed to save WAF settings: {}", e)))?;
+    Ok(())
+}
+
+/// Get the WAF bypass navigation timeout and cookie wait settings
+#[tauri::command]
+#[specta::specta]
+pub async fn get_waf_bypass_config(
+    state: State<'_, Services>,
+) -> Result<WafBypassConfig, CommandError> {
+    Ok(state.config.get_waf_bypass_config())
+}
+
+/// Set the WAF bypass navigation timeout and cookie wait settings
+#[tauri::command]
+#[specta::specta]
+pub async fn set_waf_bypass_config(
+    config: WafBypassConfig,
+    state: State<'_, Services>,
+) -> Result<(), CommandError> {
+    state
+        .config
+        .set_waf_bypass_config(config)
+        .map_err(|e| CommandError::validation(format!("Invalid WAF bypass settings: {}", e)))?;
+    Ok(())
+}
+
+/// Get the largest provider response body read in MiB, `None` for the built-in default
+#[tauri::command]
+#[specta::specta]
+pub async fn get_max_response_body_mb(
+    state: State<'_, Services>,
+) -> Result<Option<u32>, CommandError> {
+    Ok(state.config.get_max_response_body_mb())
+}
+
+/// Set the largest provider response body read in MiB, `None` for the built-in default
+#[tauri::command]
+#[specta::specta]
+pub async fn set_max_response_body_mb(
+    mb: Option<u32>,
+    state: State<'_, Services>,
+) -> Result<(), CommandError> {
+    if mb.is_some_and(|mb| !(1..=MAX_RESPONSE_BODY_MB).contains(&mb)) {
+        return Err(CommandError::validation(format!(
+            "Response body limit must be between 1 and {} MiB",
+            MAX_RESPONSE_BODY_MB
+        )));
+    }
+
+    state.config.set_max_response_body_mb(mb).map_err(|e| {
+        CommandError::infrastructure(format!("Failed to save HTTP settings: {}", e))
+    })?;
+    Ok(())
+}
+
+/// Get WAF refresh and cookie cache telemetry collected since app start
+#[tauri::command]
+#[specta::specta]
+pub async fn get_waf_metrics() -> Result<WafMetricsDto, CommandError> {
+    Ok(WafMetrics::global().snapshot().into())
+}
+
+/// List the most recent WAF bypass failure captures, newest first
+#[tauri::command]
+#[specta::specta]
+pub async fn get_waf_diagnostics() -> Result<Vec<WafDiagnosticCaptureDto>, CommandError> {
+    let captures = waf_bypass::list_waf_diagnostics()?;
+    Ok(captures.into_iter().map(Into::into).collect())
+}
+
+/// Get whether WAF bypass reuses a persistent per-provider browser profile
+#[tauri::command]
+#[specta::specta]
+pub async fn get_persistent_browser_profile(
+    state: State<'_, Services>,
+) -> Result<bool, CommandError> {
+    Ok(state.config.get_persistent_browser_profile())
+}
+
+/// Enable or disable persistent per-provider browser profiles for WAF bypass
+#[tauri::command]
+#[specta::specta]
+pub async fn set_persistent_browser_profile(
+    enabled: bool,
+    state: State<'_, Services>,
+) -> Result<(), CommandError> {
+    state
+        .config
+        .set_persistent_browser_profile(enabled)
+        .map_err(|e| {
+            CommandError::infrastructure(format!("Failed to save browser profile setting: {}", e))
+        })?;
+    Ok(())
+}
+
+/// Delete all persistent browser profiles, returning how many were removed
+#[tauri::command]
+#[specta::specta]
+pub async fn clear_browser_profiles() -> Result<u32, CommandError> {
+    let removed = tokio::task::spawn_blocking(waf_bypass::clear_browser_profiles)
+        .await
+        .map_err(|e| CommandError::infrastructure(format!("Task join error: {}", e)))?
+        .map_err(|e| {
+            CommandError::infrastructure(format!("Failed to clear browser profiles: {}", e))
+        })?;
+    Ok(removed as u32)
+}
@@ -2,7 +2,7 @@ use tauri::State;
 
 use crate::application::services::token::CodexConfigService;
 use crate::presentation::error::CommandError;
-use crate::presentation::state::Repositories;
+use crate::presentation::state::{Repositories, Services};
 use neuradock_domain::independent_key::IndependentKeyId;
 
 /// Configure independent API key to Codex globally
@@ -44,7 +44,10 @@ pub async fn generate_independent_key_codex_temp(
     key_id: i64,
     model: Option<String>,
     repositories: State<'_, Repositories>,
+    services: State<'_, Services>,
 ) -> Result<String, CommandError> {
+    services.app_lock.ensure_unlocked()?;
+
     let id = IndependentKeyId::new(key_id);
 
     // Get the independent key
@@ -1,10 +1,13 @@
 use crate::application::commands::command_handler::CommandHandler;
 use crate::application::commands::notification_commands::*;
 use crate::application::dtos::{
-    CreateNotificationChannelInput, NotificationChannelDto, UpdateNotificationChannelInput,
+    CreateNotificationChannelInput, DailySummarySettingsDto, NotificationChannelDto,
+    NotificationTemplatesDto, UpdateNotificationChannelInput,
 };
 use crate::presentation::error::CommandError;
-use crate::presentation::state::{CommandHandlers, Repositories};
+use crate::presentation::state::{CommandHandlers, Repositories, Services};
+use neuradock_domain::notification::Locale;
+use std::str::FromStr;
 use tauri::State;
 
 /// Create a notification channel
@@ -74,6 +77,9 @@ pub async fn get_all_notification_channels(
             channel_type: channel.channel_type().as_str().to_string(),
             config: serde_json::to_value(channel.config()).unwrap_or(serde_json::json!({})),
             enabled: channel.is_enabled(),
+            min_severity: channel.min_severity(),
+            templates: channel.templates().into(),
+            locale: channel.locale(),
             created_at: channel.created_at().to_rfc3339(),
         })
         .collect();
@@ -96,3 +102,102 @@ pub async fn test_notification_channel(
         .await
         .map_err(CommandError::from)
 }
+
+/// Get the daily summary time and the day it was last sent for
+#[tauri::command]
+#[specta::specta]
+pub async fn get_daily_summary_settings(
+    state: State<'_, Services>,
+) -> Result<DailySummarySettingsDto, CommandError> {
+    Ok(DailySummarySettingsDto {
+        time: state
+            .config
+            .get_daily_summary_time()
+            .map(|time| time.format("%H:%M").to_string()),
+        last_sent_on: state
+            .config
+            .get_last_daily_summary_on()
+            .map(|day| day.to_string()),
+    })
+}
+
+/// Set the local time (HH:MM) of the daily summary, `None` disables it
+#[tauri::command]
+#[specta::specta]
+pub async fn set_daily_summary_time(
+    time: Option<String>,
+    state: State<'_, Services>,
+) -> Result<(), CommandError> {
+    let time = time
+        .as_deref()
+        .map(str::trim)
+        .filter(|time| !time.is_empty())
+        .map(|time| {
+            chrono::NaiveTime::parse_from_str(time, "%H:%M").map_err(|_| {
+                CommandError::validation(format!("Invalid daily summary time: {}", time))
+            })
+        })
+        .transpose()?;
+
+    state.config.set_daily_summary_time(time).map_err(|e| {
+        CommandError::infrastructure(format!("Failed to save daily summary setting: {}", e))
+    })?;
+    Ok(())
+}
+
+/// Get the check-in notification templates used by channels without their own
+#[tauri::command]
+#[specta::specta]
+pub async fn get_notification_templates(
+    state: State<'_, Services>,
+) -> Result<NotificationTemplatesDto, CommandError> {
+    Ok((&state.config.get_notification_templates()).into())
+}
+
+/// Set the global check-in notification templates, blank ones use the built-in content
+#[tauri::command]
+#[specta::specta]
+pub async fn set_notification_templates(
+    templates: NotificationTemplatesDto,
+    state: State<'_, Services>,
+) -> Result<(), CommandError> {
+    let templates = templates.parse().map_err(CommandError::from)?;
+
+    state
+        .config
+        .set_notification_templates(templates)
+        .map_err(|e| {
+            CommandError::infrastructure(format!("Failed to save notification templates: {}", e))
+        })?;
+    Ok(())
+}
+
+/// Get the locale of notifications for channels without their own, `None` follows the system
+#[tauri::command]
+#[specta::specta]
+pub async fn get_notification_locale(
+    state: State<'_, Services>,
+) -> Result<Option<Locale>, CommandError> {
+    Ok(state.config.get_notification_locale())
+}
+
+/// Set the default notification locale ("zh-CN" or "en-US"), empty follows the system
+#[tauri::command]
+#[specta::specta]
+pub async fn set_notification_locale(
+    locale: Option<String>,
+    state: State<'_, Services>,
+) -> Result<(), CommandError> {
+    let locale = locale
+        .as_deref()
+        .map(str::trim)
+        .filter(|locale| !locale.is_empty())
+        .map(Locale::from_str)
+        .transpose()
+        .map_err(CommandError::from)?;
+
+    state.config.set_notification_locale(locale).map_err(|e| {
+        CommandError::infrastructure(format!("Failed to save notification locale: {}", e))
+    })?;
+    Ok(())
+}
@@ -1,8 +1,11 @@
 use crate::application::commands::command_handler::CommandHandler;
 use crate::application::commands::provider_commands::*;
-use crate::application::dtos::{AddProviderInput, BrowserInfoDto, ProviderDto};
+use crate::application::dtos::{
+    AddProviderInput, BalanceDisplayDto, BrowserInfoDto, ProviderDto, QuotaResetInfoDto,
+    QuotaResetScheduleDto, RewardExtractionDto,
+};
 use crate::presentation::error::CommandError;
-use crate::presentation::state::{CommandHandlers, Repositories};
+use crate::presentation::state::{CommandHandlers, Queries, Repositories};
 use tauri::State;
 
 /// Add a provider (deprecated - use create_provider instead)
@@ -22,15 +25,30 @@ pub async fn add_provider(
 #[tauri::command]
 #[specta::specta]
 pub async fn check_browser_available() -> Result<BrowserInfoDto, CommandError> {
-    use neuradock_infrastructure::http::waf_bypass::check_available_browser;
+    get_browser_info().await
+}
+
+/// Get detected and configured browser paths plus the browser version
+#[tauri::command]
+#[specta::specta]
+pub async fn get_browser_info() -> Result<BrowserInfoDto, CommandError> {
+    use neuradock_infrastructure::http::waf_bypass;
+
+    // Version detection spawns the browser process, keep it off the async runtime
+    let info = tokio::task::spawn_blocking(waf_bypass::get_browser_info)
+        .await
+        .map_err(|e| CommandError::infrastructure(format!("Browser detection failed: {}", e)))?;
 
-    match check_available_browser() {
+    match info.effective_path().map(str::to_string) {
         Some(path) => {
-            log::info!("Browser found at: {}", path);
+            log::info!("Browser found at: {} (version: {:?})", path, info.version);
             Ok(BrowserInfoDto {
                 available: true,
                 path: Some(path),
                 message: Some("Browser is available for WAF bypass".to_string()),
+                detected_path: info.detected_path,
+                configured_path: info.configured_path,
+                version: info.version,
             })
         }
         None => {
@@ -38,7 +56,10 @@ pub async fn check_browser_available() -> Result<BrowserInfoDto, CommandError> {
             Ok(BrowserInfoDto {
                 available: false,
                 path: None,
-                message: Some("No browser found. Please install Chrome, Chromium, Brave, or Microsoft Edge for WAF bypass functionality.".to_string()),
+                message: Some("No browser found. Please install Chrome, Chromium, Brave, or Microsoft Edge, or configure a browser path for WAF bypass functionality.".to_string()),
+                detected_path: None,
+                configured_path: None,
+                version: None,
             })
         }
     }
@@ -93,6 +114,7 @@ pub async fn get_all_providers(
                     .login_url()
                     .trim_start_matches(provider.domain())
                     .to_string(),
+                sign_in_body: provider.sign_in_body().map(|body| body.to_string()),
                 sign_in_path: provider
                     .sign_in_url()
                     .as_ref()
@@ -109,8 +131,20 @@ pub async fn get_all_providers(
                     .models_url()
                     .as_ref()
                     .map(|url| url.trim_start_matches(provider.domain()).to_string()),
+                transaction_log_path: provider.transaction_log_path().map(str::to_string),
                 api_user_key: provider.api_user_key().to_string(),
+                extra_headers: provider.extra_headers().as_map().clone(),
+                dialect: provider.dialect().as_str().to_string(),
                 needs_waf_bypass: provider.needs_waf_bypass(),
+                bypass_method: provider
+                    .waf_challenge_kind()
+                    .map(|kind| kind.as_bypass_method().to_string()),
+                requires_turnstile: provider.requires_turnstile(),
+                quota_reset_schedule: provider
+                    .quota_reset_schedule()
+                    .map(QuotaResetScheduleDto::from),
+                balance_display: BalanceDisplayDto::from(provider.balance_display()),
+                reward_extraction: provider.reward_extraction().map(RewardExtractionDto::from),
             }
         })
         .collect();
@@ -128,6 +162,20 @@ pub async fn get_all_providers(
     Ok(dtos)
 }
 
+/// Get the next quota reset time of a provider (None if it has no reset schedule)
+#[tauri::command]
+#[specta::specta]
+pub async fn get_quota_reset_info(
+    provider_id: String,
+    queries: State<'_, Queries>,
+) -> Result<Option<QuotaResetInfoDto>, CommandError> {
+    queries
+        .provider
+        .get_quota_reset_info(&provider_id)
+        .await
+        .map_err(CommandError::from)
+}
+
 /// Create a custom provider
 #[tauri::command]
 #[specta::specta]
@@ -1,8 +1,26 @@
+use crate::application::dtos::{
+    AppDataExportDto, AppDataImportDto, AppLockStatusDto, CrashReportDto, DatabaseLocationDto,
+    DatabaseMoveDto, DatabaseRecoveryDto, DatabaseStatsDto, DbMaintenanceReportDto,
+    DbMaintenanceSettingsDto, DiagnosticsExportDto, EncryptionKeyRotationDto, FrontendLogInput,
+    LogPageDto, LogQueryInput, MigrationStatusDto, RequestCaptureDto, RuntimeMetricsDto,
+    SchedulerHealthDto, SetAppLockInput,
+};
+use crate::application::services::{DEFAULT_DIAGNOSTIC_LOG_DAYS, MAX_DIAGNOSTIC_LOG_DAYS};
 use crate::presentation::error::CommandError;
-use neuradock_infrastructure::logging::{log_from_frontend as log_fe, FrontendLog};
+use crate::presentation::events::EncryptionKeyRotationProgress;
+use crate::presentation::state::Services;
+use neuradock_infrastructure::http::capture::MAX_CAPTURE_REQUESTS;
+use neuradock_infrastructure::http::RequestCapture;
+use neuradock_infrastructure::logging::{self, LogQuery};
+use neuradock_infrastructure::monitoring::RuntimeMetrics;
+use neuradock_infrastructure::persistence::{ImportMode, VacuumMode};
+use std::path::{Path, PathBuf};
 
-use tauri::Manager;
+use tauri::{Manager, State};
 use tauri_plugin_opener::OpenerExt;
+use tauri_specta::Event;
+
+const MAX_LOG_QUERY_LIMIT: u32 = 1000;
 
 /// Get application version information
 #[tauri::command]
@@ -21,31 +39,29 @@ pub fn get_app_version() -> String {
 #[tauri::command]
 #[specta::specta]
 pub fn log_from_frontend(level: String, target: String, message: String, fields: Option<String>) {
-    // Parse fields JSON string
-    let parsed_fields = fields.and_then(|f| serde_json::from_str(&f).ok());
-
-    let log = FrontendLog {
-        level,
-        target,
-        message,
-        fields: parsed_fields,
-    };
+    logging::log_from_frontend(
+        FrontendLogInput {
+            level,
+            target,
+            message,
+            fields,
+        }
+        .into(),
+    );
+}
 
-    log_fe(log);
+/// Log several frontend lines in one call, subject to the same rate limit
+#[tauri::command]
+#[specta::specta]
+pub fn log_batch_from_frontend(logs: Vec<FrontendLogInput>) {
+    logging::log_batch_from_frontend(logs.into_iter().map(Into::into).collect());
 }
 
 /// Open log directory in file explorer
 #[tauri::command]
 #[specta::specta]
 pub async fn open_log_dir(app: tauri::AppHandle) -> Result<String, CommandError> {
-    use neuradock_infrastructure::logging;
-
-    let log_dir = logging::get_log_dir()
-        .or_else(|| {
-            // If not initialized yet, try to get default path
-            app.path().app_log_dir().ok().map(|dir| dir.join("logs"))
-        })
-        .ok_or_else(|| CommandError::infrastructure("Failed to get log directory"))?;
+    let log_dir = resolve_log_dir(&app)?;
 
     // Ensure directory exists
     std::fs::create_dir_all(&log_dir).map_err(|e| CommandError::from(e.to_string()))?;
@@ -56,3 +72,430 @@ pub async fn open_log_dir(app: tauri::AppHandle) -> Result<String, CommandError>
 
     Ok(log_dir.display().to_string())
 }
+
+/// Query the JSON log files for the in-app log viewer, newest first
+#[tauri::command]
+#[specta::specta]
+pub async fn query_logs(
+    query: LogQueryInput,
+    app: tauri::AppHandle,
+) -> Result<LogPageDto, CommandError> {
+    let log_dir = resolve_log_dir(&app)?;
+    let query = parse_log_query(query)?;
+
+    // Log files can be large, keep the scan off the async runtime
+    let page = tokio::task::spawn_blocking(move || logging::query_logs(&log_dir, &query))
+        .await
+        .map_err(|e| CommandError::infrastructure(format!("Log query failed: {}", e)))?
+        .map_err(|e| CommandError::infrastructure(format!("Failed to read log files: {}", e)))?;
+
+    Ok(page.into())
+}
+
+/// Crash reports from previous sessions, newest first
+#[tauri::command]
+#[specta::specta]
+pub async fn get_crash_reports(app: tauri::AppHandle) -> Result<Vec<CrashReportDto>, CommandError> {
+    let log_dir = resolve_log_dir(&app)?;
+
+    let reports = tokio::task::spawn_blocking(move || logging::list_crash_reports(&log_dir))
+        .await
+        .map_err(|e| CommandError::infrastructure(format!("Crash report lookup failed: {}", e)))?
+        .map_err(|e| {
+            CommandError::infrastructure(format!("Failed to read crash reports: {}", e))
+        })?;
+
+    Ok(reports.into_iter().map(CrashReportDto::from).collect())
+}
+
+/// Open a crash report, by its file name from `get_crash_reports`, in the default app
+#[tauri::command]
+#[specta::specta]
+pub async fn open_crash_report(
+    app: tauri::AppHandle,
+    file_name: String,
+) -> Result<(), CommandError> {
+    let log_dir = resolve_log_dir(&app)?;
+    let path = logging::find_crash_report(&log_dir, &file_name)
+        .ok_or_else(|| CommandError::not_found(format!("Crash report not found: {}", file_name)))?;
+
+    app.opener()
+        .open_path(path.display().to_string(), None::<&str>)
+        .map_err(|e| CommandError::from(e.to_string()))
+}
+
+/// Provider requests still to be captured and the capture file
+#[tauri::command]
+#[specta::specta]
+pub async fn get_request_capture() -> Result<RequestCaptureDto, CommandError> {
+    let capture = RequestCapture::global();
+    Ok(RequestCaptureDto {
+        remaining: capture.remaining(),
+        file_path: capture.path().map(|path| path.display().to_string()),
+    })
+}
+
+/// Capture the redacted request and response of the next `count` check-in and
+/// user info calls to a debug file in the log directory, 0 turns it off
+#[tauri::command]
+#[specta::specta]
+pub async fn set_request_capture(
+    app: tauri::AppHandle,
+    count: u32,
+    state: State<'_, Services>,
+) -> Result<RequestCaptureDto, CommandError> {
+    if count > MAX_CAPTURE_REQUESTS {
+        return Err(CommandError::validation(format!(
+            "Request capture is limited to {} requests",
+            MAX_CAPTURE_REQUESTS
+        )));
+    }
+
+    let log_dir = resolve_log_dir(&app)?;
+    state
+        .config
+        .set_request_capture(&log_dir, count)
+        .map_err(|e| {
+            CommandError::infrastructure(format!("Failed to save request capture: {}", e))
+        })?;
+    get_request_capture().await
+}
+
+fn resolve_log_dir(app: &tauri::AppHandle) -> Result<PathBuf, CommandError> {
+    logging::get_log_dir()
+        .or_else(|| {
+            // If not initialized yet, try to get default path
+            app.path().app_log_dir().ok().map(|dir| dir.join("logs"))
+        })
+        .ok_or_else(|| CommandError::infrastructure("Failed to get log directory"))
+}
+
+fn parse_log_query(input: LogQueryInput) -> Result<LogQuery, CommandError> {
+    let parse_time = |value: Option<String>| {
+        value
+            .map(|v| {
+                chrono::DateTime::parse_from_rfc3339(&v)
+                    .map(|t| t.with_timezone(&chrono::Utc))
+                    .map_err(|_| CommandError::validation(format!("Invalid timestamp: {}", v)))
+            })
+            .transpose()
+    };
+    let non_empty = |value: Option<String>| value.filter(|v| !v.trim().is_empty());
+
+    let min_level = input
+        .level
+        .map(|level| {
+            level
+                .parse::<tracing::Level>()
+                .map_err(|_| CommandError::validation(format!("Invalid log level: {}", level)))
+        })
+        .transpose()?;
+
+    Ok(LogQuery {
+        min_level,
+        target_prefix: non_empty(input.target_prefix),
+        since: parse_time(input.since)?,
+        until: parse_time(input.until)?,
+        text: non_empty(input.text),
+        correlation_id: non_empty(input.correlation_id),
+        include_previous: input.include_previous,
+        limit: input.limit.unwrap_or(200).clamp(1, MAX_LOG_QUERY_LIMIT) as usize,
+    })
+}
+
+/// Live tasks, memory, database connections and event bus depth, with the last hour of samples
+#[tauri::command]
+#[specta::specta]
+pub async fn get_runtime_metrics(
+    state: State<'_, Services>,
+) -> Result<RuntimeMetricsDto, CommandError> {
+    Ok(RuntimeMetricsDto {
+        current: state.runtime_metrics.sample().into(),
+        history: RuntimeMetrics::global()
+            .history()
+            .into_iter()
+            .map(Into::into)
+            .collect(),
+    })
+}
+
+/// Scheduled auto check-in tasks, their last run and restarts
+#[tauri::command]
+#[specta::specta]
+pub async fn get_scheduler_health(
+    state: State<'_, Services>,
+) -> Result<SchedulerHealthDto, CommandError> {
+    Ok(state.diagnostics.scheduler_health().await)
+}
+
+/// Write a zip for bug reports to `path`
+///
+/// Holds the last `log_days` days of logs (3 by default) with cookies and
+/// authorization headers redacted, database statistics, scheduler health,
+/// version and OS information, and the settings with the proxy host masked.
+#[tauri::command]
+#[specta::specta]
+pub async fn export_diagnostics(
+    path: String,
+    log_days: Option<u32>,
+    app: tauri::AppHandle,
+    state: State<'_, Services>,
+) -> Result<DiagnosticsExportDto, CommandError> {
+    let log_days = log_days.unwrap_or(DEFAULT_DIAGNOSTIC_LOG_DAYS);
+    if log_days > MAX_DIAGNOSTIC_LOG_DAYS {
+        return Err(CommandError::validation(format!(
+            "At most {} days of logs can be exported",
+            MAX_DIAGNOSTIC_LOG_DAYS
+        )));
+    }
+    let log_dir = resolve_log_dir(&app)?;
+
+    let bundle = state
+        .diagnostics
+        .export(Path::new(&path), log_dir, log_days)
+        .await?;
+
+    Ok(bundle.into())
+}
+
+/// Optimize, analyze, vacuum and checkpoint the database, refused while check-ins run
+#[tauri::command]
+#[specta::specta]
+pub async fn run_db_maintenance(
+    full_vacuum: Option<bool>,
+    state: State<'_, Services>,
+) -> Result<DbMaintenanceReportDto, CommandError> {
+    let vacuum_mode = if full_vacuum.unwrap_or(true) {
+        VacuumMode::Full
+    } else {
+        VacuumMode::Incremental
+    };
+
+    let report = state.db_maintenance.run(vacuum_mode, "manual").await?;
+    if let Err(e) = state.config.record_db_maintenance(chrono::Utc::now()) {
+        log::warn!("Failed to save database maintenance time: {}", e);
+    }
+
+    Ok(report.into())
+}
+
+/// Get the weekly database maintenance setting and the last run time
+#[tauri::command]
+#[specta::specta]
+pub async fn get_db_maintenance_settings(
+    state: State<'_, Services>,
+) -> Result<DbMaintenanceSettingsDto, CommandError> {
+    Ok(DbMaintenanceSettingsDto {
+        weekly: state.config.get_weekly_db_maintenance(),
+        last_run_at: state
+            .config
+            .get_last_db_maintenance_at()
+            .map(|at| at.to_rfc3339()),
+    })
+}
+
+/// Enable or disable weekly background database maintenance
+#[tauri::command]
+#[specta::specta]
+pub async fn set_weekly_db_maintenance(
+    enabled: bool,
+    state: State<'_, Services>,
+) -> Result<(), CommandError> {
+    state
+        .config
+        .set_weekly_db_maintenance(enabled)
+        .map_err(|e| {
+            CommandError::infrastructure(format!("Failed to save maintenance setting: {}", e))
+        })?;
+    Ok(())
+}
+
+/// Recovery of a corrupt database done at startup, `None` when the database was healthy
+#[tauri::command]
+#[specta::specta]
+pub async fn get_database_recovery(
+    state: State<'_, Services>,
+) -> Result<Option<DatabaseRecoveryDto>, CommandError> {
+    Ok(state
+        .db_maintenance
+        .startup_recovery()
+        .map(DatabaseRecoveryDto::from))
+}
+
+/// Whether startup migrations failed and were rolled back, leaving the app read-only
+#[tauri::command]
+#[specta::specta]
+pub async fn get_migration_status(
+    state: State<'_, Services>,
+) -> Result<MigrationStatusDto, CommandError> {
+    Ok(state.db_maintenance.migration_failure().into())
+}
+
+/// Database and WAL sizes, row counts per table, schema version and last backup and maintenance
+#[tauri::command]
+#[specta::specta]
+pub async fn get_database_stats(
+    state: State<'_, Services>,
+) -> Result<DatabaseStatsDto, CommandError> {
+    let stats = state.db_maintenance.stats().await?;
+    let last_maintenance_at = state
+        .config
+        .get_last_db_maintenance_at()
+        .map(|at| at.to_rfc3339());
+
+    Ok(DatabaseStatsDto::new(stats, last_maintenance_at))
+}
+
+/// Where the database is stored, and why a configured directory was not used
+#[tauri::command]
+#[specta::specta]
+pub async fn get_database_location(
+    state: State<'_, Services>,
+) -> Result<DatabaseLocationDto, CommandError> {
+    Ok(DatabaseLocationDto {
+        db_path: state.db_maintenance.db_path().to_string_lossy().to_string(),
+        is_default: state.db_maintenance.is_default_location(),
+        stale_error: state
+            .db_maintenance
+            .stale_location()
+            .map(|stale| stale.message()),
+    })
+}
+
+/// Move the database and its encryption salt to `new_dir`, then restart
+///
+/// The copy is verified at the destination before the original is deleted.
+/// The app restarts to open the database from its new location.
+#[tauri::command]
+#[specta::specta]
+pub async fn move_database(
+    app: tauri::AppHandle,
+    new_dir: String,
+    state: State<'_, Services>,
+) -> Result<DatabaseMoveDto, CommandError> {
+    let moved = state.db_maintenance.move_to(Path::new(&new_dir)).await?;
+    app.request_restart();
+
+    Ok(moved.into())
+}
+
+/// Export all accounts, providers, history, keys, channels and settings to an encrypted archive
+#[tauri::command]
+#[specta::specta]
+pub async fn export_app_data(
+    path: String,
+    passphrase: String,
+    state: State<'_, Services>,
+) -> Result<AppDataExportDto, CommandError> {
+    state.app_lock.ensure_unlocked()?;
+    let row_counts = state.app_data.export(Path::new(&path), &passphrase).await?;
+
+    Ok(AppDataExportDto { path, row_counts })
+}
+
+/// Import an archive written by `export_app_data`
+///
+/// `mode` is `merge` to keep existing data and add what is missing, or
+/// `replace` to make this installation match the archive, settings included.
+#[tauri::command]
+#[specta::specta]
+pub async fn import_app_data(
+    path: String,
+    passphrase: String,
+    mode: String,
+    state: State<'_, Services>,
+) -> Result<AppDataImportDto, CommandError> {
+    let mode = match mode.to_lowercase().as_str() {
+        "merge" => ImportMode::Merge,
+        "replace" => ImportMode::Replace,
+        _ => {
+            return Err(CommandError::validation(
+                "Invalid import mode. Must be one of: merge, replace",
+            ));
+        }
+    };
+
+    let report = state
+        .app_data
+        .import(Path::new(&path), &passphrase, mode)
+        .await?;
+
+    Ok(AppDataImportDto::new(mode, report))
+}
+
+/// Re-encrypt all stored credentials under a newly generated key
+///
+/// Every value is first verified to decrypt with the current key; a `dry_run`
+/// stops there. `EncryptionKeyRotationProgress` events report the rows done.
+#[tauri::command]
+#[specta::specta]
+pub async fn rotate_encryption_key(
+    app: tauri::AppHandle,
+    dry_run: bool,
+    state: State<'_, Services>,
+) -> Result<EncryptionKeyRotationDto, CommandError> {
+    let report = state
+        .encryption_key
+        .rotate(dry_run, |progress| {
+            let event = EncryptionKeyRotationProgress {
+                column: progress.column,
+                processed: progress.processed as u32,
+                total: progress.total as u32,
+            };
+            if let Err(e) = event.emit(&app) {
+                log::warn!("Failed to emit key rotation progress: {}", e);
+            }
+        })
+        .await?;
+
+    Ok(EncryptionKeyRotationDto::new(dry_run, report))
+}
+
+/// App lock state, for showing the unlock prompt
+#[tauri::command]
+#[specta::specta]
+pub fn get_app_lock_status(state: State<'_, Services>) -> AppLockStatusDto {
+    state.app_lock.status().into()
+}
+
+/// Turn the passphrase app lock on or off, or change its passphrase or idle timeout
+///
+/// While the lock is on, the current passphrase is required.
+#[tauri::command]
+#[specta::specta]
+pub async fn set_app_lock(
+    input: SetAppLockInput,
+    state: State<'_, Services>,
+) -> Result<AppLockStatusDto, CommandError> {
+    state
+        .app_lock
+        .set(
+            input.enabled,
+            input.passphrase,
+            input.idle_timeout_minutes,
+            input.current_passphrase,
+        )
+        .await?;
+    Ok(state.app_lock.status().into())
+}
+
+/// Unlock credential access with the app passphrase
+///
+/// Repeated wrong passphrases delay further attempts.
+#[tauri::command]
+#[specta::specta]
+pub async fn unlock_app(
+    passphrase: String,
+    state: State<'_, Services>,
+) -> Result<AppLockStatusDto, CommandError> {
+    state.app_lock.unlock(passphrase).await?;
+    Ok(state.app_lock.status().into())
+}
+
+/// Lock credential access until the passphrase is entered again
+#[tauri::command]
+#[specta::specta]
+pub fn lock_app(state: State<'_, Services>) -> AppLockStatusDto {
+    state.app_lock.lock();
+    state.app_lock.status().into()
+}
@@ -45,6 +45,8 @@ pub async fn generate_claude_temp_commands(
     model: Option<String>,
     services: State<'_, Services>,
 ) -> Result<String, CommandError> {
+    services.app_lock.ensure_unlocked()?;
+
     let account_id = AccountId::from_string(&account_id);
     let token_id = neuradock_domain::token::TokenId::new(token_id);
 
@@ -63,6 +63,8 @@ pub async fn generate_codex_temp_commands(
     services: State<'_, Services>,
     repositories: State<'_, Repositories>,
 ) -> Result<String, CommandError> {
+    services.app_lock.ensure_unlocked()?;
+
     let account_id = AccountId::from_string(&account_id);
     let token_id = neuradock_domain::token::TokenId::new(token_id);
 
@@ -12,6 +12,9 @@ pub async fn fetch_account_tokens(
     services: State<'_, Services>,
     repositories: State<'_, Repositories>,
 ) -> Result<Vec<TokenDto>, CommandError> {
+    // Token DTOs carry the full key
+    services.app_lock.ensure_unlocked()?;
+
     log::info!(
         "fetch_account_tokens called: account_id={}, force_refresh={}",
         account_id,
@@ -1,4 +1,5 @@
 use neuradock_domain::shared::{DomainError, ErrorCode, ErrorSeverity};
+use neuradock_infrastructure::http::waf_bypass::BrowserLaunchError;
 use serde::{Deserialize, Serialize};
 use specta::Type;
 
@@ -76,6 +77,10 @@ impl From<&str> for CommandError {
 
 impl From<anyhow::Error> for CommandError {
     fn from(err: anyhow::Error) -> Self {
+        // Keep the browser launch classification so the UI can point at the browser settings
+        if let Some(launch_error) = BrowserLaunchError::find(&err) {
+            return DomainError::from(launch_error.clone()).into();
+        }
         Self::infrastructure(err.to_string())
     }
 }
@@ -135,4 +140,16 @@ mod tests {
         let infra_err = CommandError::infrastructure("Service unavailable");
         assert_eq!(infra_err.code, 5001);
     }
+
+    #[test]
+    fn test_command_error_keeps_browser_launch_classification() {
+        let launch_error = BrowserLaunchError::new(None, "No such file or directory (os error 2)");
+        let err = anyhow::Error::new(launch_error).context("Failed to get WAF cookies");
+
+        let cmd_err: CommandError = err.into();
+
+        assert_eq!(cmd_err.code, ErrorCode::BrowserUnavailable.code());
+        assert!(cmd_err.message.starts_with("Browser not found"));
+        assert!(!cmd_err.recoverable);
+    }
 }
@@ -16,3 +16,22 @@ pub struct BalanceUpdated {
     pub total_consumed: f64,
     pub total_quota: f64,
 }
+
+/// Emitted once per account while a batch balance refresh is running
+#[derive(Serialize, Type, Event, Clone)]
+pub struct BalanceRefreshProgress {
+    pub account_id: String,
+    pub completed: u32,
+    pub total: u32,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+/// Emitted while the encryption key is verified or rotated
+#[derive(Serialize, Type, Event, Clone)]
+pub struct EncryptionKeyRotationProgress {
+    /// `table.column` being processed
+    pub column: String,
+    pub processed: u32,
+    pub total: u32,
+}
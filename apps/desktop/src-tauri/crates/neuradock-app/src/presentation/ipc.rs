@@ -11,23 +11,33 @@ pub fn builder() -> Builder<tauri::Wry> {
             create_account,
             update_account,
             delete_account,
+            undo_delete_account,
+            purge_deleted_accounts,
             toggle_account,
+            toggle_pin,
             import_account_from_json,
+            import_account_from_cookie_file,
+            import_account_from_cookie_string,
             import_accounts_batch,
             update_accounts_batch,
             export_accounts_to_json,
             // Check-in commands
             execute_check_in,
             execute_batch_check_in,
+            execute_check_in_for_provider,
             stop_check_in,
             // Balance commands
             fetch_account_balance,
             fetch_accounts_balances,
             get_balance_statistics,
+            get_balance_statistics_over_time,
+            backfill_balance_history,
             // Provider commands
             add_provider,
             check_browser_available,
+            get_browser_info,
             get_all_providers,
+            get_quota_reset_info,
             create_provider,
             update_provider,
             delete_provider,
@@ -37,16 +47,45 @@ pub fn builder() -> Builder<tauri::Wry> {
             get_check_in_history,
             get_check_in_stats,
             get_running_jobs,
+            get_check_in_timings,
+            get_check_in_timing_metrics,
             // Check-in Streak commands
             get_check_in_streak,
             get_all_check_in_streaks,
             get_check_in_calendar,
+            get_check_in_year_heatmap,
+            get_accounts_comparison,
             get_check_in_trend,
             get_check_in_day_detail,
+            get_check_in_consistency,
             recalculate_check_in_streaks,
+            recalculate_check_in_streak,
+            reset_check_in_streak,
+            get_streak_freezes_per_month,
+            set_streak_freezes_per_month,
             // Config commands
             get_log_level,
             set_log_level,
+            get_module_log_levels,
+            set_module_log_level,
+            clear_module_log_level,
+            get_browser_path,
+            set_browser_path,
+            get_browser_extra_args,
+            set_browser_extra_args,
+            get_waf_validation_skip_providers,
+            set_waf_validation_skip_providers,
+            get_waf_per_account_cookie_providers,
+            set_waf_per_account_cookie_providers,
+            get_waf_bypass_config,
+            set_waf_bypass_config,
+            get_max_response_body_mb,
+            set_max_response_body_mb,
+            get_waf_metrics,
+            get_waf_diagnostics,
+            get_persistent_browser_profile,
+            set_persistent_browser_profile,
+            clear_browser_profiles,
             get_proxy_config,
             update_proxy_config,
             // Notification commands
@@ -55,6 +94,12 @@ pub fn builder() -> Builder<tauri::Wry> {
             delete_notification_channel,
             get_all_notification_channels,
             test_notification_channel,
+            get_daily_summary_settings,
+            set_daily_summary_time,
+            get_notification_templates,
+            set_notification_templates,
+            get_notification_locale,
+            set_notification_locale,
             // Token commands
             fetch_account_tokens,
             configure_claude_global,
@@ -84,10 +129,36 @@ pub fn builder() -> Builder<tauri::Wry> {
             // System & Logging commands
             get_app_version,
             log_from_frontend,
+            log_batch_from_frontend,
             open_log_dir,
+            query_logs,
+            get_crash_reports,
+            open_crash_report,
+            get_request_capture,
+            set_request_capture,
+            export_diagnostics,
+            get_scheduler_health,
+            get_runtime_metrics,
+            run_db_maintenance,
+            get_db_maintenance_settings,
+            set_weekly_db_maintenance,
+            get_database_recovery,
+            get_migration_status,
+            get_database_location,
+            get_database_stats,
+            move_database,
+            export_app_data,
+            import_app_data,
+            rotate_encryption_key,
+            get_app_lock_status,
+            set_app_lock,
+            unlock_app,
+            lock_app,
         ])
         .events(collect_events![
             crate::presentation::events::CheckInProgress,
             crate::presentation::events::BalanceUpdated,
+            crate::presentation::events::BalanceRefreshProgress,
+            crate::presentation::events::EncryptionKeyRotationProgress,
         ])
 }
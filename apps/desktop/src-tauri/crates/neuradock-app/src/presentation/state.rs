@@ -2,10 +2,12 @@ use std::sync::Arc;
 
 use crate::application::commands::handlers::*;
 use crate::application::queries::{
-    AccountQueryService, BalanceStatisticsQueryService, CheckInStreakQueries,
+    AccountQueryService, BalanceStatisticsQueryService, CheckInStreakQueries, CheckInTimingQueries,
+    ProviderQueryService,
 };
 use crate::application::services::{
-    BalanceService, ClaudeConfigService, CodexConfigService, ConfigService,
+    AppDataService, AppLockService, BalanceService, ClaudeConfigService, CodexConfigService,
+    ConfigService, DbMaintenanceService, DiagnosticsService, EncryptionKeyService,
     ProviderModelsQueryService, ProxyConfigService, TokenService,
 };
 use neuradock_domain::account::AccountRepository;
@@ -14,6 +16,8 @@ use neuradock_domain::custom_node::CustomProviderNodeRepository;
 use neuradock_domain::independent_key::IndependentKeyRepository;
 use neuradock_domain::notification::NotificationChannelRepository;
 use neuradock_domain::session::SessionRepository;
+use neuradock_infrastructure::monitoring::RuntimeMetricsCollector;
+use neuradock_infrastructure::persistence::SqliteUnitOfWork;
 
 /// Command handlers container
 #[derive(Clone)]
@@ -21,7 +25,10 @@ pub struct CommandHandlers {
     pub create_account: Arc<CreateAccountCommandHandler>,
     pub update_account: Arc<UpdateAccountCommandHandler>,
     pub delete_account: Arc<DeleteAccountCommandHandler>,
+    pub undo_delete_account: Arc<UndoDeleteAccountCommandHandler>,
+    pub purge_deleted_accounts: Arc<PurgeDeletedAccountsCommandHandler>,
     pub toggle_account: Arc<ToggleAccountCommandHandler>,
+    pub toggle_pin: Arc<TogglePinCommandHandler>,
     pub execute_check_in: Arc<ExecuteCheckInCommandHandler>,
     pub batch_execute_check_in: Arc<BatchExecuteCheckInCommandHandler>,
     pub create_notification_channel: Arc<CreateNotificationChannelHandler>,
@@ -41,6 +48,7 @@ pub struct Repositories {
     pub custom_node: Arc<dyn CustomProviderNodeRepository>,
     pub independent_key: Arc<dyn IndependentKeyRepository>,
     pub provider: Arc<dyn ProviderRepository>,
+    pub unit_of_work: Arc<SqliteUnitOfWork>,
 }
 
 #[derive(Clone)]
@@ -52,13 +60,21 @@ pub struct Services {
     pub balance: Arc<BalanceService>,
     pub proxy_config: Arc<ProxyConfigService>,
     pub provider_models_query: Arc<ProviderModelsQueryService>,
+    pub db_maintenance: Arc<DbMaintenanceService>,
+    pub app_data: Arc<AppDataService>,
+    pub encryption_key: Arc<EncryptionKeyService>,
+    pub diagnostics: Arc<DiagnosticsService>,
+    pub runtime_metrics: Arc<RuntimeMetricsCollector>,
+    pub app_lock: Arc<AppLockService>,
 }
 
 #[derive(Clone)]
 pub struct Queries {
     pub account: Arc<AccountQueryService>,
     pub streak: Arc<CheckInStreakQueries>,
+    pub check_in_timing: Arc<CheckInTimingQueries>,
     pub balance_statistics: Arc<BalanceStatisticsQueryService>,
+    pub provider: Arc<ProviderQueryService>,
 }
 
 #[derive(Clone)]
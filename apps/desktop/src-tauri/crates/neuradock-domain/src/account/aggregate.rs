@@ -1,9 +1,9 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use specta::Type;
 
 use super::value_objects::Credentials;
-use crate::shared::{AccountId, DomainError, ProviderId};
+use crate::shared::{AccountId, DomainError, ExtraHeaders, ProviderId};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct Account {
@@ -11,6 +11,8 @@ pub struct Account {
     name: String,
     provider_id: ProviderId,
     credentials: Credentials,
+    #[serde(default)]
+    extra_headers: ExtraHeaders,
     enabled: bool,
     last_check_in: Option<DateTime<Utc>>,
     created_at: DateTime<Utc>,
@@ -18,6 +20,9 @@ pub struct Account {
     auto_checkin_hour: u8,
     auto_checkin_minute: u8,
     check_in_interval_hours: u8,
+    max_attempts_per_day: u8,
+    check_in_attempts_day: Option<NaiveDate>,
+    check_in_attempts: u32,
     last_login_at: Option<DateTime<Utc>>,
     session_token: Option<String>,
     session_expires_at: Option<DateTime<Utc>>,
@@ -25,11 +30,13 @@ pub struct Account {
     current_balance: Option<f64>,
     total_consumed: Option<f64>,
     total_quota: Option<f64>,
+    pinned: bool,
 }
 
 impl Account {
     pub const DEFAULT_SESSION_EXPIRATION_DAYS: i64 = 30;
     pub const DEFAULT_CHECK_IN_INTERVAL_HOURS: u8 = 0;
+    pub const DEFAULT_MAX_ATTEMPTS_PER_DAY: u8 = 0;
 
     pub fn new(
         name: String,
@@ -53,6 +60,7 @@ impl Account {
             name: name.trim().to_string(),
             provider_id,
             credentials,
+            extra_headers: ExtraHeaders::default(),
             enabled: true,
             last_check_in: None,
             created_at: Utc::now(),
@@ -60,6 +68,9 @@ impl Account {
             auto_checkin_hour: 9,
             auto_checkin_minute: 0,
             check_in_interval_hours: Self::DEFAULT_CHECK_IN_INTERVAL_HOURS,
+            max_attempts_per_day: Self::DEFAULT_MAX_ATTEMPTS_PER_DAY,
+            check_in_attempts_day: None,
+            check_in_attempts: 0,
             last_login_at: None,
             session_token: None,
             session_expires_at: None,
@@ -67,6 +78,7 @@ impl Account {
             current_balance: None,
             total_consumed: None,
             total_quota: None,
+            pinned: false,
         })
     }
 
@@ -82,6 +94,7 @@ impl Account {
             name,
             provider_id,
             credentials,
+            extra_headers: ExtraHeaders::default(),
             enabled: true,
             last_check_in: None,
             created_at: Utc::now(),
@@ -89,6 +102,9 @@ impl Account {
             auto_checkin_hour: 9,
             auto_checkin_minute: 0,
             check_in_interval_hours: Self::DEFAULT_CHECK_IN_INTERVAL_HOURS,
+            max_attempts_per_day: Self::DEFAULT_MAX_ATTEMPTS_PER_DAY,
+            check_in_attempts_day: None,
+            check_in_attempts: 0,
             last_login_at: None,
             session_token: None,
             session_expires_at: None,
@@ -96,6 +112,7 @@ impl Account {
             current_balance: None,
             total_consumed: None,
             total_quota: None,
+            pinned: false,
         }
     }
 
@@ -115,6 +132,11 @@ impl Account {
         &self.credentials
     }
 
+    /// Headers added to this account's requests, over the provider's own
+    pub fn extra_headers(&self) -> &ExtraHeaders {
+        &self.extra_headers
+    }
+
     pub fn is_enabled(&self) -> bool {
         self.enabled
     }
@@ -147,6 +169,10 @@ impl Account {
         Ok(())
     }
 
+    pub fn update_extra_headers(&mut self, extra_headers: ExtraHeaders) {
+        self.extra_headers = extra_headers;
+    }
+
     pub fn update_provider_id(&mut self, provider_id: ProviderId) {
         self.provider_id = provider_id;
     }
@@ -155,6 +181,16 @@ impl Account {
         self.enabled = enabled;
     }
 
+    pub fn is_pinned(&self) -> bool {
+        self.pinned
+    }
+
+    /// Flip the pinned flag, returning the new state
+    pub fn toggle_pin(&mut self) -> bool {
+        self.pinned = !self.pinned;
+        self.pinned
+    }
+
     pub fn record_check_in(&mut self) {
         self.last_check_in = Some(Utc::now());
     }
@@ -207,6 +243,30 @@ impl Account {
         Ok(())
     }
 
+    /// Maximum check-in attempts (including retries) per day, 0 means no limit
+    pub fn max_attempts_per_day(&self) -> u8 {
+        self.max_attempts_per_day
+    }
+
+    pub fn set_max_attempts_per_day(&mut self, attempts: u8) {
+        self.max_attempts_per_day = attempts;
+    }
+
+    /// Check-in attempts made on `day`; the counter starts over every day
+    pub fn check_in_attempts_on(&self, day: NaiveDate) -> u32 {
+        if self.check_in_attempts_day == Some(day) {
+            self.check_in_attempts
+        } else {
+            0
+        }
+    }
+
+    /// Count a check-in attempt made on `day`
+    pub fn record_check_in_attempt(&mut self, day: NaiveDate) {
+        self.check_in_attempts = self.check_in_attempts_on(day) + 1;
+        self.check_in_attempts_day = Some(day);
+    }
+
     pub fn last_login_at(&self) -> Option<DateTime<Utc>> {
         self.last_login_at
     }
@@ -277,6 +337,7 @@ pub struct AccountBuilder {
     name: String,
     provider_id: ProviderId,
     credentials: Credentials,
+    extra_headers: ExtraHeaders,
     enabled: bool,
     last_check_in: Option<DateTime<Utc>>,
     created_at: DateTime<Utc>,
@@ -284,6 +345,9 @@ pub struct AccountBuilder {
     auto_checkin_hour: u8,
     auto_checkin_minute: u8,
     check_in_interval_hours: u8,
+    max_attempts_per_day: u8,
+    check_in_attempts_day: Option<NaiveDate>,
+    check_in_attempts: u32,
     last_login_at: Option<DateTime<Utc>>,
     session_token: Option<String>,
     session_expires_at: Option<DateTime<Utc>>,
@@ -291,9 +355,15 @@ pub struct AccountBuilder {
     current_balance: Option<f64>,
     total_consumed: Option<f64>,
     total_quota: Option<f64>,
+    pinned: bool,
 }
 
 impl AccountBuilder {
+    pub fn extra_headers(mut self, extra_headers: ExtraHeaders) -> Self {
+        self.extra_headers = extra_headers;
+        self
+    }
+
     pub fn enabled(mut self, enabled: bool) -> Self {
         self.enabled = enabled;
         self
@@ -329,6 +399,17 @@ impl AccountBuilder {
         self
     }
 
+    pub fn max_attempts_per_day(mut self, attempts: u8) -> Self {
+        self.max_attempts_per_day = attempts;
+        self
+    }
+
+    pub fn check_in_attempts(mut self, day: Option<NaiveDate>, attempts: u32) -> Self {
+        self.check_in_attempts_day = day;
+        self.check_in_attempts = attempts;
+        self
+    }
+
     pub fn last_login_at(mut self, last_login_at: Option<DateTime<Utc>>) -> Self {
         self.last_login_at = last_login_at;
         self
@@ -364,12 +445,18 @@ impl AccountBuilder {
         self
     }
 
+    pub fn pinned(mut self, pinned: bool) -> Self {
+        self.pinned = pinned;
+        self
+    }
+
     pub fn build(self) -> Account {
         Account {
             id: self.id,
             name: self.name,
             provider_id: self.provider_id,
             credentials: self.credentials,
+            extra_headers: self.extra_headers,
             enabled: self.enabled,
             last_check_in: self.last_check_in,
             created_at: self.created_at,
@@ -377,6 +464,9 @@ impl AccountBuilder {
             auto_checkin_hour: self.auto_checkin_hour,
             auto_checkin_minute: self.auto_checkin_minute,
             check_in_interval_hours: self.check_in_interval_hours,
+            max_attempts_per_day: self.max_attempts_per_day,
+            check_in_attempts_day: self.check_in_attempts_day,
+            check_in_attempts: self.check_in_attempts,
             last_login_at: self.last_login_at,
             session_token: self.session_token,
             session_expires_at: self.session_expires_at,
@@ -384,6 +474,7 @@ impl AccountBuilder {
             current_balance: self.current_balance,
             total_consumed: self.total_consumed,
             total_quota: self.total_quota,
+            pinned: self.pinned,
         }
     }
 }
@@ -388,4 +388,20 @@ mod tests {
 
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_toggle_pin() {
+        let mut account = Account::new(
+            "Test Account".to_string(),
+            ProviderId::from_string("anyrouter"),
+            create_test_credentials(),
+        )
+        .unwrap();
+
+        assert!(!account.is_pinned());
+        assert!(account.toggle_pin());
+        assert!(account.is_pinned());
+        assert!(!account.toggle_pin());
+        assert!(!account.is_pinned());
+    }
 }
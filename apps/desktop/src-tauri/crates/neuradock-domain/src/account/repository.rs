@@ -1,13 +1,41 @@
 use super::Account;
-use crate::shared::{AccountId, DomainError};
+use crate::shared::{AccountId, DomainError, DynTransactionContext};
 use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
 
 #[async_trait]
 pub trait AccountRepository: Send + Sync {
     async fn save(&self, account: &Account) -> Result<(), DomainError>;
+    /// Save as part of `tx`, written only when the caller commits it
+    async fn save_in_transaction(
+        &self,
+        tx: &mut DynTransactionContext,
+        account: &Account,
+    ) -> Result<(), DomainError>;
     async fn find_by_id(&self, id: &AccountId) -> Result<Option<Account>, DomainError>;
     async fn find_by_ids(&self, ids: &[AccountId]) -> Result<Vec<Account>, DomainError>;
     async fn find_all(&self) -> Result<Vec<Account>, DomainError>;
     async fn find_enabled(&self) -> Result<Vec<Account>, DomainError>;
+    /// Permanently delete the account with its sessions and history
     async fn delete(&self, id: &AccountId) -> Result<(), DomainError>;
+    /// Hide the account from every query until it is restored or purged
+    async fn soft_delete(
+        &self,
+        id: &AccountId,
+        deleted_at: DateTime<Utc>,
+    ) -> Result<(), DomainError>;
+    /// Bring back a soft-deleted account, `false` when there was none
+    async fn restore_deleted(&self, id: &AccountId) -> Result<bool, DomainError>;
+    /// Permanently delete accounts soft-deleted before `deleted_before`,
+    /// returning their ids and names
+    async fn purge_deleted(
+        &self,
+        deleted_before: DateTime<Utc>,
+    ) -> Result<Vec<(AccountId, String)>, DomainError>;
+    /// Count a check-in attempt made on `day`, returning the attempts made that day
+    async fn record_check_in_attempt(
+        &self,
+        id: &AccountId,
+        day: NaiveDate,
+    ) -> Result<u32, DomainError>;
 }
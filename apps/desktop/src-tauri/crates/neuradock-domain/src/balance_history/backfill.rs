@@ -0,0 +1,137 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use std::collections::{BTreeMap, HashSet};
+
+use super::BalanceHistoryDailySummary;
+
+/// A change of an account's balance read from a provider's transaction log
+#[derive(Debug, Clone, PartialEq)]
+pub struct BalanceChange {
+    pub at: DateTime<Utc>,
+    /// Quota added, e.g. a top-up or a check-in reward
+    pub income: f64,
+    /// Quota used
+    pub consumed: f64,
+}
+
+/// Rebuild the end-of-day balances before `today` by walking `changes` back from
+/// the current balance.
+///
+/// Only days with changes get a snapshot, and days in `existing_days` are left
+/// out so recorded history is never replaced. The walk stops at the oldest change,
+/// or earlier if the balance would turn negative because the log misses changes.
+/// Snapshots are returned from oldest to newest.
+pub fn backfill_daily_snapshots(
+    current_balance: f64,
+    total_consumed: f64,
+    changes: &[BalanceChange],
+    today: NaiveDate,
+    existing_days: &HashSet<NaiveDate>,
+) -> Vec<BalanceHistoryDailySummary> {
+    let mut changes_by_day: BTreeMap<NaiveDate, (f64, f64)> = BTreeMap::new();
+    for change in changes {
+        let day = changes_by_day.entry(change.at.date_naive()).or_default();
+        day.0 += change.income;
+        day.1 += change.consumed;
+    }
+
+    let mut balance = current_balance;
+    let mut consumed = total_consumed;
+    let mut snapshots = Vec::new();
+    for (date, (day_income, day_consumed)) in changes_by_day.into_iter().rev() {
+        if date < today {
+            let day_balance = round_cents(balance);
+            let day_total_consumed = round_cents(consumed);
+            if day_balance < 0.0 || day_total_consumed < 0.0 {
+                break;
+            }
+            if !existing_days.contains(&date) {
+                snapshots.push(BalanceHistoryDailySummary::restore(
+                    date,
+                    round_cents(day_balance + day_total_consumed),
+                    day_balance,
+                    day_total_consumed,
+                ));
+            }
+        }
+
+        // Undo the day to get the balance at the end of the day before
+        balance -= day_income - day_consumed;
+        consumed -= day_consumed;
+    }
+
+    snapshots.reverse();
+    snapshots
+}
+
+fn round_cents(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, day).unwrap()
+    }
+
+    fn change(day: u32, hour: u32, income: f64, consumed: f64) -> BalanceChange {
+        BalanceChange {
+            at: Utc.with_ymd_and_hms(2026, 3, day, hour, 0, 0).unwrap(),
+            income,
+            consumed,
+        }
+    }
+
+    fn values(snapshots: &[BalanceHistoryDailySummary]) -> Vec<(NaiveDate, f64, f64, f64)> {
+        snapshots
+            .iter()
+            .map(|s| {
+                (
+                    s.check_in_date(),
+                    s.daily_total_quota(),
+                    s.daily_balance(),
+                    s.daily_consumed(),
+                )
+            })
+            .collect()
+    }
+
+    fn changes() -> Vec<BalanceChange> {
+        vec![
+            change(1, 8, 2.0, 0.0),
+            change(1, 12, 0.0, 1.0),
+            change(3, 9, 0.0, 0.5),
+            change(4, 10, 1.0, 0.0),
+        ]
+    }
+
+    #[test]
+    fn test_backfill_walks_back_from_current_balance() {
+        let snapshots = backfill_daily_snapshots(10.0, 5.0, &changes(), date(4), &HashSet::new());
+
+        // Today's changes are undone but today itself is left to the regular refresh
+        assert_eq!(
+            values(&snapshots),
+            vec![(date(1), 14.0, 9.5, 4.5), (date(3), 14.0, 9.0, 5.0)]
+        );
+    }
+
+    #[test]
+    fn test_backfill_skips_days_with_history_but_keeps_walking() {
+        let existing = HashSet::from([date(3)]);
+        let snapshots = backfill_daily_snapshots(10.0, 5.0, &changes(), date(4), &existing);
+
+        assert_eq!(values(&snapshots), vec![(date(1), 14.0, 9.5, 4.5)]);
+    }
+
+    #[test]
+    fn test_backfill_stops_when_log_misses_changes() {
+        // Day 2 consumes more than was consumed in total, the log is incomplete
+        let changes = vec![change(1, 8, 1.0, 0.0), change(2, 8, 0.0, 3.0)];
+        let snapshots = backfill_daily_snapshots(1.0, 2.0, &changes, date(3), &HashSet::new());
+
+        assert_eq!(values(&snapshots), vec![(date(2), 3.0, 1.0, 2.0)]);
+    }
+}
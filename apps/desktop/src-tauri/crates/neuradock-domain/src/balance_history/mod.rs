@@ -1,5 +1,7 @@
+mod backfill;
 mod repository;
 mod types;
 
+pub use backfill::*;
 pub use repository::*;
 pub use types::*;
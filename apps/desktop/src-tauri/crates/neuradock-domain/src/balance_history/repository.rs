@@ -1,7 +1,8 @@
 use async_trait::async_trait;
-use chrono::NaiveDate;
+use chrono::{DateTime, NaiveDate, Utc};
+use std::collections::HashMap;
 
-use super::{BalanceHistoryDailySummary, BalanceHistoryRecord};
+use super::{BalanceHistoryDailySummary, BalanceHistoryRecord, ProviderDailyBalanceTotal};
 use crate::shared::{AccountId, DomainError};
 
 #[async_trait]
@@ -12,6 +13,19 @@ pub trait BalanceHistoryRepository: Send + Sync {
     /// providing a deterministic `id`.
     async fn save(&self, record: &BalanceHistoryRecord) -> Result<(), DomainError>;
 
+    /// Record an account's balance as its snapshot of the UTC day of `recorded_at`.
+    ///
+    /// There is one snapshot per account and day: a later snapshot the same
+    /// day replaces the earlier one.
+    async fn upsert_daily_snapshot(
+        &self,
+        account_id: &AccountId,
+        current_balance: f64,
+        total_consumed: f64,
+        total_quota: f64,
+        recorded_at: DateTime<Utc>,
+    ) -> Result<BalanceHistoryRecord, DomainError>;
+
     /// Find the latest balance record for an account.
     async fn find_latest_by_account_id(
         &self,
@@ -39,6 +53,23 @@ pub trait BalanceHistoryRepository: Send + Sync {
         end_date: NaiveDate,
     ) -> Result<Vec<BalanceHistoryDailySummary>, DomainError>;
 
+    /// List daily summaries (grouped by date) of several accounts in a date range
+    /// with one query. Accounts without history in the range are left out.
+    async fn list_daily_summaries_for_accounts_in_range(
+        &self,
+        account_ids: &[AccountId],
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<HashMap<AccountId, Vec<BalanceHistoryDailySummary>>, DomainError>;
+
+    /// Sum the daily summaries of enabled accounts by provider and date in a date range.
+    /// An account only counts on the days it has history.
+    async fn list_provider_daily_totals_in_range(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<Vec<ProviderDailyBalanceTotal>, DomainError>;
+
     /// Find a daily summary (grouped by date) for an account on a specific date.
     async fn find_daily_summary(
         &self,
@@ -48,4 +79,20 @@ pub trait BalanceHistoryRepository: Send + Sync {
 
     /// List distinct account IDs present in balance_history.
     async fn list_distinct_account_ids(&self) -> Result<Vec<AccountId>, DomainError>;
+
+    /// Day an account's streak was reset on, check-ins up to it do not count.
+    async fn find_streak_reset(
+        &self,
+        account_id: &AccountId,
+    ) -> Result<Option<NaiveDate>, DomainError>;
+
+    /// Reset an account's streak as of `reset_on`, `None` counts the whole history again.
+    async fn set_streak_reset(
+        &self,
+        account_id: &AccountId,
+        reset_on: Option<NaiveDate>,
+    ) -> Result<(), DomainError>;
+
+    /// Count the whole history again for every account.
+    async fn clear_streak_resets(&self) -> Result<(), DomainError>;
 }
@@ -1,7 +1,7 @@
 use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::shared::{AccountId, DomainError};
+use crate::shared::{AccountId, DomainError, ProviderId};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BalanceHistoryRecord {
@@ -165,3 +165,59 @@ impl BalanceHistoryDailySummary {
         self.daily_consumed
     }
 }
+
+/// Daily snapshots of a provider's accounts summed for one day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderDailyBalanceTotal {
+    provider_id: ProviderId,
+    date: NaiveDate,
+    current_balance: f64,
+    total_consumed: f64,
+    total_quota: f64,
+    /// Accounts with a snapshot on the day
+    account_count: u32,
+}
+
+impl ProviderDailyBalanceTotal {
+    pub fn restore(
+        provider_id: ProviderId,
+        date: NaiveDate,
+        current_balance: f64,
+        total_consumed: f64,
+        total_quota: f64,
+        account_count: u32,
+    ) -> Self {
+        Self {
+            provider_id,
+            date,
+            current_balance,
+            total_consumed,
+            total_quota,
+            account_count,
+        }
+    }
+
+    pub fn provider_id(&self) -> &ProviderId {
+        &self.provider_id
+    }
+
+    pub fn date(&self) -> NaiveDate {
+        self.date
+    }
+
+    pub fn current_balance(&self) -> f64 {
+        self.current_balance
+    }
+
+    pub fn total_consumed(&self) -> f64 {
+        self.total_consumed
+    }
+
+    pub fn total_quota(&self) -> f64 {
+        self.total_quota
+    }
+
+    pub fn account_count(&self) -> u32 {
+        self.account_count
+    }
+}
@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::shared::DomainError;
+
+/// How a provider's balance figures should be labelled
+///
+/// Display metadata only: stored balances are not converted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct BalanceDisplay {
+    /// Symbol shown before amounts, e.g. `$` or `¥`
+    currency_symbol: String,
+    /// Unit name shown after amounts, e.g. `USD` or `credits`
+    unit_label: String,
+    /// Number of decimal places to render
+    decimal_places: u8,
+}
+
+impl BalanceDisplay {
+    pub const MAX_DECIMAL_PLACES: u8 = 8;
+
+    pub fn new(
+        currency_symbol: impl Into<String>,
+        unit_label: impl Into<String>,
+        decimal_places: u8,
+    ) -> Result<Self, DomainError> {
+        let currency_symbol = currency_symbol.into().trim().to_string();
+        let unit_label = unit_label.into().trim().to_string();
+
+        if currency_symbol.chars().count() > 8 {
+            return Err(DomainError::Validation(
+                "Currency symbol must be at most 8 characters".to_string(),
+            ));
+        }
+        if unit_label.chars().count() > 32 {
+            return Err(DomainError::Validation(
+                "Unit label must be at most 32 characters".to_string(),
+            ));
+        }
+        if decimal_places > Self::MAX_DECIMAL_PLACES {
+            return Err(DomainError::Validation(format!(
+                "Decimal places must be between 0 and {}",
+                Self::MAX_DECIMAL_PLACES
+            )));
+        }
+
+        Ok(Self {
+            currency_symbol,
+            unit_label,
+            decimal_places,
+        })
+    }
+
+    pub fn currency_symbol(&self) -> &str {
+        &self.currency_symbol
+    }
+
+    pub fn unit_label(&self) -> &str {
+        &self.unit_label
+    }
+
+    pub fn decimal_places(&self) -> u8 {
+        self.decimal_places
+    }
+}
+
+impl Default for BalanceDisplay {
+    /// new-api quotas are converted to dollars (500000 quota = $1)
+    fn default() -> Self {
+        Self {
+            currency_symbol: "$".to_string(),
+            unit_label: "USD".to_string(),
+            decimal_places: 2,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_is_dollars() {
+        let display = BalanceDisplay::default();
+        assert_eq!(display.currency_symbol(), "$");
+        assert_eq!(display.unit_label(), "USD");
+        assert_eq!(display.decimal_places(), 2);
+    }
+
+    #[test]
+    fn test_new_trims_and_validates() {
+        let display = BalanceDisplay::new(" ¥ ", " credits ", 0).unwrap();
+        assert_eq!(display.currency_symbol(), "¥");
+        assert_eq!(display.unit_label(), "credits");
+        assert_eq!(display.decimal_places(), 0);
+
+        assert!(BalanceDisplay::new("$", "USD", 9).is_err());
+        assert!(BalanceDisplay::new("too-long-symbol", "USD", 2).is_err());
+    }
+}
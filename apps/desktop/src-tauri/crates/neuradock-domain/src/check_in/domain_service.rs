@@ -1,3 +1,5 @@
+use chrono::NaiveDate;
+
 use crate::account::Account;
 use crate::check_in::Provider;
 use crate::shared::DomainError;
@@ -39,6 +41,29 @@ impl CheckInDomainService {
         Ok(())
     }
 
+    /// Validate the account has check-in attempts left on `today`
+    pub fn check_daily_attempt_limit(
+        account: &Account,
+        today: NaiveDate,
+    ) -> Result<(), DomainError> {
+        let max_attempts = account.max_attempts_per_day() as u32;
+
+        // 0 means no daily limit
+        if max_attempts == 0 {
+            return Ok(());
+        }
+
+        let attempts = account.check_in_attempts_on(today);
+        if attempts >= max_attempts {
+            return Err(DomainError::Validation(format!(
+                "Daily check-in attempt limit reached ({}/{} attempts on {}). Check-in will be allowed again tomorrow.",
+                attempts, max_attempts, today
+            )));
+        }
+
+        Ok(())
+    }
+
     /// Validate provider configuration
     pub fn validate_provider(provider: &Provider) -> Result<(), DomainError> {
         // Provider is valid as long as it has basic configuration
@@ -130,13 +155,21 @@ mod tests {
             domain: "https://example.com".to_string(),
             login_path: "/login".to_string(),
             sign_in_path: Some("/checkin".to_string()),
+            sign_in_body: None,
             user_info_path: "/userinfo".to_string(),
             token_api_path: Some("/token".to_string()),
             models_path: Some("/models".to_string()),
+            transaction_log_path: None,
             api_user_key: "user".to_string(),
+            extra_headers: Default::default(),
+            dialect: Default::default(),
             bypass_method: None,
+            requires_turnstile: false,
             supports_check_in: true,
             check_in_bugged: false,
+            quota_reset_schedule: None,
+            balance_display: None,
+            reward_extraction: None,
         })
     }
 
@@ -222,6 +255,54 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_daily_attempt_limit_boundary() {
+        let today = NaiveDate::from_ymd_opt(2025, 12, 26).unwrap();
+        let mut account = create_test_account();
+        account.set_max_attempts_per_day(2);
+
+        assert!(CheckInDomainService::check_daily_attempt_limit(&account, today).is_ok());
+        account.record_check_in_attempt(today);
+        assert!(CheckInDomainService::check_daily_attempt_limit(&account, today).is_ok());
+        account.record_check_in_attempt(today);
+
+        match CheckInDomainService::check_daily_attempt_limit(&account, today) {
+            Err(DomainError::Validation(msg)) => {
+                assert!(msg.contains("limit reached"));
+                assert!(msg.contains("2/2"));
+            }
+            _ => panic!("Expected Validation error for daily attempt limit"),
+        }
+    }
+
+    #[test]
+    fn test_daily_attempt_limit_resets_next_day() {
+        let today = NaiveDate::from_ymd_opt(2025, 12, 26).unwrap();
+        let tomorrow = today.succ_opt().unwrap();
+        let mut account = create_test_account();
+        account.set_max_attempts_per_day(1);
+
+        account.record_check_in_attempt(today);
+        assert!(CheckInDomainService::check_daily_attempt_limit(&account, today).is_err());
+        assert!(CheckInDomainService::check_daily_attempt_limit(&account, tomorrow).is_ok());
+
+        account.record_check_in_attempt(tomorrow);
+        assert_eq!(account.check_in_attempts_on(tomorrow), 1);
+        assert!(CheckInDomainService::check_daily_attempt_limit(&account, tomorrow).is_err());
+    }
+
+    #[test]
+    fn test_daily_attempt_limit_zero_means_unlimited() {
+        let today = NaiveDate::from_ymd_opt(2025, 12, 26).unwrap();
+        let mut account = create_test_account();
+
+        for _ in 0..10 {
+            account.record_check_in_attempt(today);
+        }
+
+        assert!(CheckInDomainService::check_daily_attempt_limit(&account, today).is_ok());
+    }
+
     #[test]
     fn test_validate_provider() {
         let provider = create_test_provider();
@@ -1,8 +1,13 @@
 mod aggregate;
+mod balance_display;
 mod domain_service;
 mod provider;
+mod provider_dialect;
+mod quota_reset;
 mod repository;
+mod reward_extraction;
 mod value_objects;
+mod waf_challenge;
 
 #[cfg(test)]
 mod aggregate_test;
@@ -10,9 +15,14 @@ mod aggregate_test;
 mod value_objects_test;
 
 pub use aggregate::CheckInJob;
+pub use balance_display::BalanceDisplay;
 pub use domain_service::CheckInDomainService;
 pub use provider::{Provider, ProviderConfig};
+pub use provider_dialect::ProviderDialect;
+pub use quota_reset::QuotaResetSchedule;
 pub use repository::{CheckInJobRepository, ProviderRepository};
+pub use reward_extraction::RewardExtraction;
 pub use value_objects::Balance;
 #[allow(unused_imports)]
 pub use value_objects::{CheckInResult, CheckInStatus};
+pub use waf_challenge::WafChallengeKind;
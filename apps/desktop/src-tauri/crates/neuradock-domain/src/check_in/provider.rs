@@ -2,7 +2,10 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use specta::Type;
 
-use crate::shared::ProviderId;
+use super::{
+    BalanceDisplay, ProviderDialect, QuotaResetSchedule, RewardExtraction, WafChallengeKind,
+};
+use crate::shared::{ExtraHeaders, ProviderId};
 
 /// Configuration for creating a Provider
 #[derive(Debug, Clone)]
@@ -11,13 +14,28 @@ pub struct ProviderConfig {
     pub domain: String,
     pub login_path: String,
     pub sign_in_path: Option<String>,
+    /// JSON body POSTed to the sign-in endpoint (None = no body)
+    pub sign_in_body: Option<serde_json::Value>,
     pub user_info_path: String,
     pub token_api_path: Option<String>,
     pub models_path: Option<String>,
+    /// Usage/transaction log endpoint used to backfill balance history
+    pub transaction_log_path: Option<String>,
     pub api_user_key: String,
+    /// Static headers added to user info and check-in requests
+    pub extra_headers: ExtraHeaders,
+    /// API fork the provider runs, selects how its responses are parsed
+    pub dialect: ProviderDialect,
     pub bypass_method: Option<String>,
+    /// Check-in needs a Cloudflare Turnstile token solved in a browser
+    pub requires_turnstile: bool,
     pub supports_check_in: bool,
     pub check_in_bugged: bool,
+    pub quota_reset_schedule: Option<QuotaResetSchedule>,
+    /// Balance labels (None = dollars)
+    pub balance_display: Option<BalanceDisplay>,
+    /// Where the check-in response reports the reward (None = not parsed)
+    pub reward_extraction: Option<RewardExtraction>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -27,13 +45,24 @@ pub struct Provider {
     domain: String,
     login_path: String,
     sign_in_path: Option<String>,
+    #[specta(type = Option<String>)]
+    sign_in_body: Option<serde_json::Value>,
     user_info_path: String,
     token_api_path: Option<String>,
     models_path: Option<String>,
+    transaction_log_path: Option<String>,
     api_user_key: String,
+    #[serde(default)]
+    extra_headers: ExtraHeaders,
+    #[serde(default)]
+    dialect: ProviderDialect,
     bypass_method: Option<String>,
+    requires_turnstile: bool,
     supports_check_in: bool,
     check_in_bugged: bool,
+    quota_reset_schedule: Option<QuotaResetSchedule>,
+    balance_display: BalanceDisplay,
+    reward_extraction: Option<RewardExtraction>,
     is_builtin: bool,
     created_at: DateTime<Utc>,
 }
@@ -50,13 +79,21 @@ impl Provider {
             domain: Self::normalize_domain(config.domain),
             login_path: config.login_path,
             sign_in_path: config.sign_in_path,
+            sign_in_body: config.sign_in_body,
             user_info_path: config.user_info_path,
             token_api_path: config.token_api_path,
             models_path: config.models_path,
+            transaction_log_path: config.transaction_log_path,
             api_user_key: config.api_user_key,
+            extra_headers: config.extra_headers,
+            dialect: config.dialect,
             bypass_method: config.bypass_method,
+            requires_turnstile: config.requires_turnstile,
             supports_check_in: config.supports_check_in,
             check_in_bugged: config.check_in_bugged,
+            quota_reset_schedule: config.quota_reset_schedule,
+            balance_display: config.balance_display.unwrap_or_default(),
+            reward_extraction: config.reward_extraction,
             is_builtin: false,
             created_at: Utc::now(),
         }
@@ -69,13 +106,21 @@ impl Provider {
             domain: Self::normalize_domain(config.domain),
             login_path: config.login_path,
             sign_in_path: config.sign_in_path,
+            sign_in_body: config.sign_in_body,
             user_info_path: config.user_info_path,
             token_api_path: config.token_api_path,
             models_path: config.models_path,
+            transaction_log_path: config.transaction_log_path,
             api_user_key: config.api_user_key,
+            extra_headers: config.extra_headers,
+            dialect: config.dialect,
             bypass_method: config.bypass_method,
+            requires_turnstile: config.requires_turnstile,
             supports_check_in: config.supports_check_in,
             check_in_bugged: config.check_in_bugged,
+            quota_reset_schedule: config.quota_reset_schedule,
+            balance_display: config.balance_display.unwrap_or_default(),
+            reward_extraction: config.reward_extraction,
             is_builtin: true,
             created_at: Utc::now(),
         }
@@ -94,13 +139,21 @@ impl Provider {
             domain: Self::normalize_domain(config.domain),
             login_path: config.login_path,
             sign_in_path: config.sign_in_path,
+            sign_in_body: config.sign_in_body,
             user_info_path: config.user_info_path,
             token_api_path: config.token_api_path,
             models_path: config.models_path,
+            transaction_log_path: config.transaction_log_path,
             api_user_key: config.api_user_key,
+            extra_headers: config.extra_headers,
+            dialect: config.dialect,
             bypass_method: config.bypass_method,
+            requires_turnstile: config.requires_turnstile,
             supports_check_in: config.supports_check_in,
             check_in_bugged: config.check_in_bugged,
+            quota_reset_schedule: config.quota_reset_schedule,
+            balance_display: config.balance_display.unwrap_or_default(),
+            reward_extraction: config.reward_extraction,
             is_builtin,
             created_at,
         }
@@ -138,6 +191,10 @@ impl Provider {
         self.models_path.as_deref()
     }
 
+    pub fn transaction_log_path(&self) -> Option<&str> {
+        self.transaction_log_path.as_deref()
+    }
+
     pub fn login_url(&self) -> String {
         format!("{}{}", self.domain, self.login_path)
     }
@@ -148,6 +205,11 @@ impl Provider {
             .map(|p| format!("{}{}", self.domain, p))
     }
 
+    /// JSON body to POST on check-in, `None` to send no body
+    pub fn sign_in_body(&self) -> Option<&serde_json::Value> {
+        self.sign_in_body.as_ref()
+    }
+
     pub fn user_info_url(&self) -> String {
         format!("{}{}", self.domain, self.user_info_path)
     }
@@ -164,12 +226,41 @@ impl Provider {
             .map(|p| format!("{}{}", self.domain, p))
     }
 
+    pub fn transaction_log_url(&self) -> Option<String> {
+        self.transaction_log_path
+            .as_ref()
+            .map(|p| format!("{}{}", self.domain, p))
+    }
+
     pub fn api_user_key(&self) -> &str {
         &self.api_user_key
     }
 
+    pub fn extra_headers(&self) -> &ExtraHeaders {
+        &self.extra_headers
+    }
+
+    pub fn dialect(&self) -> ProviderDialect {
+        self.dialect
+    }
+
+    pub fn bypass_method(&self) -> Option<&str> {
+        self.bypass_method.as_deref()
+    }
+
+    /// WAF challenge the provider sits behind, if its bypass method is known
+    pub fn waf_challenge_kind(&self) -> Option<WafChallengeKind> {
+        self.bypass_method
+            .as_deref()
+            .and_then(WafChallengeKind::from_bypass_method)
+    }
+
     pub fn needs_waf_bypass(&self) -> bool {
-        self.bypass_method.as_deref() == Some("waf_cookies")
+        self.waf_challenge_kind().is_some()
+    }
+
+    pub fn requires_turnstile(&self) -> bool {
+        self.requires_turnstile
     }
 
     pub fn supports_check_in(&self) -> bool {
@@ -180,6 +271,18 @@ impl Provider {
         self.check_in_bugged
     }
 
+    pub fn quota_reset_schedule(&self) -> Option<&QuotaResetSchedule> {
+        self.quota_reset_schedule.as_ref()
+    }
+
+    pub fn balance_display(&self) -> &BalanceDisplay {
+        &self.balance_display
+    }
+
+    pub fn reward_extraction(&self) -> Option<&RewardExtraction> {
+        self.reward_extraction.as_ref()
+    }
+
     pub fn is_builtin(&self) -> bool {
         self.is_builtin
     }
@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::shared::DomainError;
+
+/// API fork a provider runs, selecting how its responses are read
+///
+/// Stored on the provider as a string. Defaults to new-api, the fork of the
+/// built-in providers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, Type)]
+pub enum ProviderDialect {
+    /// new-api and its forks, which report check-in results in several shapes
+    #[default]
+    NewApi,
+    /// one-api, where every response carries a `success` flag
+    OneApi,
+}
+
+impl ProviderDialect {
+    /// Stored value of the dialect
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProviderDialect::NewApi => "new_api",
+            ProviderDialect::OneApi => "one_api",
+        }
+    }
+
+    /// Parse a stored or user-supplied dialect
+    pub fn parse(value: &str) -> Result<Self, DomainError> {
+        match value.trim().to_ascii_lowercase().replace('-', "_").as_str() {
+            "new_api" | "newapi" => Ok(ProviderDialect::NewApi),
+            "one_api" | "oneapi" => Ok(ProviderDialect::OneApi),
+            _ => Err(DomainError::Validation(format!(
+                "Unknown provider dialect '{}', expected one of: new_api, one_api",
+                value
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dialect_round_trip() {
+        for dialect in [ProviderDialect::NewApi, ProviderDialect::OneApi] {
+            assert_eq!(ProviderDialect::parse(dialect.as_str()).unwrap(), dialect);
+        }
+    }
+
+    #[test]
+    fn test_dialect_aliases_and_unknown() {
+        assert_eq!(
+            ProviderDialect::parse(" New-API ").unwrap(),
+            ProviderDialect::NewApi
+        );
+        assert_eq!(
+            ProviderDialect::parse("oneapi").unwrap(),
+            ProviderDialect::OneApi
+        );
+        assert!(ProviderDialect::parse("veloera").is_err());
+    }
+}
@@ -0,0 +1,178 @@
+use chrono::{DateTime, Duration, FixedOffset, NaiveTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::shared::DomainError;
+
+/// Daily quota reset schedule of a provider
+///
+/// Providers reset quotas at a fixed wall-clock time in their own timezone.
+/// The timezone is expressed as a fixed UTC offset (e.g. `+08:00`), which
+/// covers the providers we support; DST-observing zones are not modelled.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct QuotaResetSchedule {
+    /// Reset time of day in `HH:MM` format
+    reset_time: String,
+    /// Timezone of `reset_time` as a UTC offset, e.g. `+08:00` or `-05:00`
+    utc_offset: String,
+}
+
+impl QuotaResetSchedule {
+    pub fn new(
+        reset_time: impl Into<String>,
+        utc_offset: impl Into<String>,
+    ) -> Result<Self, DomainError> {
+        let schedule = Self {
+            reset_time: reset_time.into().trim().to_string(),
+            utc_offset: utc_offset.into().trim().to_string(),
+        };
+        schedule.parse_time()?;
+        schedule.parse_offset()?;
+        Ok(schedule)
+    }
+
+    pub fn reset_time(&self) -> &str {
+        &self.reset_time
+    }
+
+    pub fn utc_offset(&self) -> &str {
+        &self.utc_offset
+    }
+
+    /// Next reset strictly after `now`
+    pub fn next_reset_after(&self, now: DateTime<Utc>) -> Result<DateTime<Utc>, DomainError> {
+        let time = self.parse_time()?;
+        let offset = self.parse_offset()?;
+
+        let local_now = now.with_timezone(&offset);
+        let mut candidate_date = local_now.date_naive();
+        if local_now.time() >= time {
+            candidate_date += Duration::days(1);
+        }
+
+        let candidate = offset
+            .from_local_datetime(&candidate_date.and_time(time))
+            .single()
+            .ok_or_else(|| {
+                DomainError::Validation(format!(
+                    "Invalid reset time {} {}",
+                    self.reset_time, self.utc_offset
+                ))
+            })?;
+
+        Ok(candidate.with_timezone(&Utc))
+    }
+
+    /// Most recent reset at or before `now`
+    pub fn previous_reset_before(&self, now: DateTime<Utc>) -> Result<DateTime<Utc>, DomainError> {
+        Ok(self.next_reset_after(now)? - Duration::days(1))
+    }
+
+    fn parse_time(&self) -> Result<NaiveTime, DomainError> {
+        NaiveTime::parse_from_str(&self.reset_time, "%H:%M").map_err(|_| {
+            DomainError::Validation(format!(
+                "Invalid reset time '{}'. Expected HH:MM",
+                self.reset_time
+            ))
+        })
+    }
+
+    fn parse_offset(&self) -> Result<FixedOffset, DomainError> {
+        let invalid = || {
+            DomainError::Validation(format!(
+                "Invalid UTC offset '{}'. Expected +HH:MM or -HH:MM",
+                self.utc_offset
+            ))
+        };
+
+        let raw = self.utc_offset.as_str();
+        if raw.eq_ignore_ascii_case("z") || raw.eq_ignore_ascii_case("utc") {
+            return Ok(FixedOffset::east_opt(0).expect("zero offset is valid"));
+        }
+
+        let (sign, rest) = match raw.chars().next() {
+            Some('+') => (1, &raw[1..]),
+            Some('-') => (-1, &raw[1..]),
+            _ => return Err(invalid()),
+        };
+        let (hours, minutes) = rest.split_once(':').ok_or_else(invalid)?;
+        let hours: i32 = hours.parse().map_err(|_| invalid())?;
+        let minutes: i32 = minutes.parse().map_err(|_| invalid())?;
+        if hours > 14 || minutes >= 60 {
+            return Err(invalid());
+        }
+
+        FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60)).ok_or_else(invalid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_rejects_invalid_schedule() {
+        assert!(QuotaResetSchedule::new("25:00", "+08:00").is_err());
+        assert!(QuotaResetSchedule::new("0800", "+08:00").is_err());
+        assert!(QuotaResetSchedule::new("08:00", "08:00").is_err());
+        assert!(QuotaResetSchedule::new("08:00", "+15:00").is_err());
+        assert!(QuotaResetSchedule::new("08:00", "Asia/Shanghai").is_err());
+    }
+
+    #[test]
+    fn test_next_reset_later_same_day() {
+        let schedule = QuotaResetSchedule::new("12:00", "UTC").unwrap();
+        let next = schedule
+            .next_reset_after(utc("2025-01-10T08:30:00Z"))
+            .unwrap();
+        assert_eq!(next, utc("2025-01-10T12:00:00Z"));
+    }
+
+    #[test]
+    fn test_next_reset_rolls_to_next_day_at_boundary() {
+        let schedule = QuotaResetSchedule::new("12:00", "+00:00").unwrap();
+        let next = schedule
+            .next_reset_after(utc("2025-01-10T12:00:00Z"))
+            .unwrap();
+        assert_eq!(next, utc("2025-01-11T12:00:00Z"));
+    }
+
+    #[test]
+    fn test_next_reset_uses_provider_timezone() {
+        // Midnight in UTC+8 is 16:00 UTC of the previous day
+        let schedule = QuotaResetSchedule::new("00:00", "+08:00").unwrap();
+
+        let next = schedule
+            .next_reset_after(utc("2025-01-10T15:59:00Z"))
+            .unwrap();
+        assert_eq!(next, utc("2025-01-10T16:00:00Z"));
+
+        let next = schedule
+            .next_reset_after(utc("2025-01-10T16:00:00Z"))
+            .unwrap();
+        assert_eq!(next, utc("2025-01-11T16:00:00Z"));
+    }
+
+    #[test]
+    fn test_next_reset_negative_offset_crosses_utc_date() {
+        // 22:30 in UTC-05:00 is 03:30 UTC the next day
+        let schedule = QuotaResetSchedule::new("22:30", "-05:00").unwrap();
+        let next = schedule
+            .next_reset_after(utc("2025-03-01T01:00:00Z"))
+            .unwrap();
+        assert_eq!(next, utc("2025-03-01T03:30:00Z"));
+    }
+
+    #[test]
+    fn test_previous_reset() {
+        let schedule = QuotaResetSchedule::new("00:00", "+08:00").unwrap();
+        let previous = schedule
+            .previous_reset_before(utc("2025-01-10T20:00:00Z"))
+            .unwrap();
+        assert_eq!(previous, utc("2025-01-10T16:00:00Z"));
+    }
+}
@@ -0,0 +1,160 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use specta::Type;
+
+use crate::shared::DomainError;
+
+/// Where a provider reports the reward granted by a check-in
+///
+/// The JSON path is tried first, the message pattern is the fallback for
+/// providers that only mention the amount in their message.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+pub struct RewardExtraction {
+    /// Dot-separated path into the response JSON, e.g. `data.quota`
+    json_path: Option<String>,
+    /// Regex on the response message, the first capture group is the amount
+    message_pattern: Option<String>,
+    /// Raw reward units per balance unit, e.g. 500000 for new-api quota
+    quota_per_unit: f64,
+}
+
+impl RewardExtraction {
+    pub fn new(
+        json_path: Option<String>,
+        message_pattern: Option<String>,
+        quota_per_unit: f64,
+    ) -> Result<Self, DomainError> {
+        let json_path = json_path
+            .map(|path| path.trim().to_string())
+            .filter(|path| !path.is_empty());
+        let message_pattern = message_pattern.filter(|pattern| !pattern.trim().is_empty());
+
+        if json_path.is_none() && message_pattern.is_none() {
+            return Err(DomainError::Validation(
+                "Reward extraction needs a JSON path or a message pattern".to_string(),
+            ));
+        }
+        if let Some(pattern) = &message_pattern {
+            Regex::new(pattern).map_err(|e| {
+                DomainError::Validation(format!("Invalid reward message pattern: {}", e))
+            })?;
+        }
+        if !quota_per_unit.is_finite() || quota_per_unit <= 0.0 {
+            return Err(DomainError::Validation(
+                "Reward quota per unit must be greater than zero".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            json_path,
+            message_pattern,
+            quota_per_unit,
+        })
+    }
+
+    pub fn json_path(&self) -> Option<&str> {
+        self.json_path.as_deref()
+    }
+
+    pub fn message_pattern(&self) -> Option<&str> {
+        self.message_pattern.as_deref()
+    }
+
+    pub fn quota_per_unit(&self) -> f64 {
+        self.quota_per_unit
+    }
+
+    /// Reward in balance units, from the response JSON or else the message
+    pub fn extract(&self, response: Option<&Value>, message: &str) -> Option<f64> {
+        let from_json = || {
+            let path = self.json_path.as_deref()?;
+            parse_amount(lookup(response?, path)?)
+        };
+        let from_message = || {
+            let pattern = Regex::new(self.message_pattern.as_deref()?).ok()?;
+            let captures = pattern.captures(message)?;
+            let amount = captures.get(1).or_else(|| captures.get(0))?;
+            amount.as_str().trim().replace(',', "").parse::<f64>().ok()
+        };
+
+        from_json()
+            .or_else(from_message)
+            .filter(|amount| amount.is_finite() && *amount >= 0.0)
+            .map(|amount| amount / self.quota_per_unit)
+    }
+}
+
+/// Follow a dot-separated path, numeric segments index into arrays
+fn lookup<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.')
+        .try_fold(value, |current, segment| match current {
+            Value::Array(items) => items.get(segment.parse::<usize>().ok()?),
+            _ => current.get(segment),
+        })
+}
+
+fn parse_amount(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(number) => number.as_f64(),
+        Value::String(text) => text.trim().replace(',', "").parse().ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_extracts_quota_from_json_path() {
+        let extraction =
+            RewardExtraction::new(Some("data.quota".to_string()), None, 500000.0).unwrap();
+        let response = json!({
+            "success": true,
+            "message": "签到成功",
+            "data": { "quota": 500000, "checkin_date": "2025-12-30" }
+        });
+
+        assert_eq!(extraction.extract(Some(&response), "签到成功"), Some(1.0));
+    }
+
+    #[test]
+    fn test_json_path_indexes_arrays_and_numeric_strings() {
+        let extraction =
+            RewardExtraction::new(Some("data.rewards.0.amount".to_string()), None, 1.0).unwrap();
+        let response = json!({ "data": { "rewards": [{ "amount": "2.50" }] } });
+
+        assert_eq!(extraction.extract(Some(&response), ""), Some(2.5));
+        assert_eq!(extraction.extract(None, ""), None);
+    }
+
+    #[test]
+    fn test_falls_back_to_message_pattern() {
+        let extraction = RewardExtraction::new(
+            Some("data.quota".to_string()),
+            Some(r"获得\s*\$?([\d.,]+)".to_string()),
+            1.0,
+        )
+        .unwrap();
+        let response = json!({ "success": true, "message": "签到成功，获得 $1.25 额度" });
+
+        assert_eq!(
+            extraction.extract(Some(&response), "签到成功，获得 $1.25 额度"),
+            Some(1.25)
+        );
+        assert_eq!(extraction.extract(Some(&response), "今日已签到"), None);
+    }
+
+    #[test]
+    fn test_new_validates_configuration() {
+        assert!(RewardExtraction::new(None, Some("  ".to_string()), 1.0).is_err());
+        assert!(RewardExtraction::new(None, Some("([".to_string()), 1.0).is_err());
+        assert!(RewardExtraction::new(Some("data".to_string()), None, 0.0).is_err());
+
+        let extraction =
+            RewardExtraction::new(Some(" data.quota ".to_string()), None, 1.0).unwrap();
+        assert_eq!(extraction.json_path(), Some("data.quota"));
+    }
+}
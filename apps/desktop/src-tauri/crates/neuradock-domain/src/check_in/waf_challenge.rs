@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::shared::DomainError;
+
+/// WAF protection a provider sits behind
+///
+/// Stored on the provider as its `bypass_method` string. Defaults to Aliyun,
+/// the protection of the built-in providers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, Type)]
+pub enum WafChallengeKind {
+    /// Aliyun JavaScript challenge setting `acw_sc__v2`
+    #[default]
+    Aliyun,
+    /// Cloudflare interstitial setting `cf_clearance`
+    Cloudflare,
+}
+
+impl WafChallengeKind {
+    /// `bypass_method` value of the kind
+    pub fn as_bypass_method(&self) -> &'static str {
+        match self {
+            // kept for compatibility with providers saved before Cloudflare support
+            WafChallengeKind::Aliyun => "waf_cookies",
+            WafChallengeKind::Cloudflare => "cloudflare",
+        }
+    }
+
+    /// Map a provider `bypass_method` to the challenge kind, `None` if unknown
+    pub fn from_bypass_method(method: &str) -> Option<Self> {
+        match method.trim().to_ascii_lowercase().as_str() {
+            "waf_cookies" | "aliyun" => Some(WafChallengeKind::Aliyun),
+            "cloudflare" => Some(WafChallengeKind::Cloudflare),
+            _ => None,
+        }
+    }
+
+    /// Parse a user-supplied `bypass_method`
+    pub fn parse(method: &str) -> Result<Self, DomainError> {
+        Self::from_bypass_method(method).ok_or_else(|| {
+            DomainError::Validation(format!(
+                "Unknown bypass method '{}', expected one of: waf_cookies, aliyun, cloudflare",
+                method
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bypass_method_round_trip() {
+        for kind in [WafChallengeKind::Aliyun, WafChallengeKind::Cloudflare] {
+            assert_eq!(
+                WafChallengeKind::from_bypass_method(kind.as_bypass_method()),
+                Some(kind)
+            );
+        }
+    }
+
+    #[test]
+    fn test_bypass_method_aliases() {
+        assert_eq!(
+            WafChallengeKind::from_bypass_method("aliyun"),
+            Some(WafChallengeKind::Aliyun)
+        );
+        assert_eq!(
+            WafChallengeKind::from_bypass_method(" Cloudflare "),
+            Some(WafChallengeKind::Cloudflare)
+        );
+        assert_eq!(WafChallengeKind::from_bypass_method("captcha"), None);
+        assert!(WafChallengeKind::parse("captcha").is_err());
+    }
+}
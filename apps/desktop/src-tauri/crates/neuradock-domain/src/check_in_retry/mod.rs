@@ -0,0 +1,239 @@
+mod repository;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::shared::{AccountId, ErrorCode};
+
+pub use repository::CheckInRetryRepository;
+
+/// State of a queued check-in retry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CheckInRetryStatus {
+    /// Waiting for the next attempt
+    Pending,
+    /// Gave up after a permanent failure or too many attempts
+    Failed,
+}
+
+impl CheckInRetryStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CheckInRetryStatus::Pending => "pending",
+            CheckInRetryStatus::Failed => "failed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pending" => Some(CheckInRetryStatus::Pending),
+            "failed" => Some(CheckInRetryStatus::Failed),
+            _ => None,
+        }
+    }
+}
+
+/// Queued retry of a check-in that failed with a recoverable error
+///
+/// Entries are persisted so that a check-in lost to a transient failure is
+/// retried even if the app is restarted in between. There is at most one
+/// entry per account.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckInRetry {
+    account_id: AccountId,
+    attempts: u32,
+    next_attempt_at: DateTime<Utc>,
+    last_error: String,
+    status: CheckInRetryStatus,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl CheckInRetry {
+    /// Failed attempts (including the original check-in) before giving up
+    pub const MAX_ATTEMPTS: u32 = 5;
+    const BASE_DELAY_MINUTES: i64 = 5;
+    const MAX_DELAY_MINUTES: i64 = 6 * 60;
+
+    /// Queue a retry for a failed check-in
+    ///
+    /// Returns `None` when the failure is not recoverable, since retrying
+    /// would fail the same way.
+    pub fn for_failure(
+        account_id: AccountId,
+        code: ErrorCode,
+        error: impl Into<String>,
+        now: DateTime<Utc>,
+    ) -> Option<Self> {
+        if !code.is_recoverable() {
+            return None;
+        }
+
+        Some(Self {
+            account_id,
+            attempts: 1,
+            next_attempt_at: now + Self::backoff(1),
+            last_error: error.into(),
+            status: CheckInRetryStatus::Pending,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    /// Restore a retry from persistence
+    pub fn restore(
+        account_id: AccountId,
+        attempts: u32,
+        next_attempt_at: DateTime<Utc>,
+        last_error: String,
+        status: CheckInRetryStatus,
+        created_at: DateTime<Utc>,
+        updated_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            account_id,
+            attempts,
+            next_attempt_at,
+            last_error,
+            status,
+            created_at,
+            updated_at,
+        }
+    }
+
+    /// Delay before the next attempt after `attempts` failures (exponential, capped)
+    pub fn backoff(attempts: u32) -> Duration {
+        let exponent = attempts.saturating_sub(1).min(16);
+        let minutes = Self::BASE_DELAY_MINUTES.saturating_mul(1 << exponent);
+        Duration::minutes(minutes.min(Self::MAX_DELAY_MINUTES))
+    }
+
+    /// Record another failed attempt
+    ///
+    /// The retry is marked failed when the error is permanent or the attempt
+    /// limit is reached, otherwise it is rescheduled with backoff.
+    pub fn record_failure(
+        &mut self,
+        code: ErrorCode,
+        error: impl Into<String>,
+        now: DateTime<Utc>,
+    ) {
+        self.attempts += 1;
+        self.last_error = error.into();
+        self.updated_at = now;
+
+        if !code.is_recoverable() || self.attempts >= Self::MAX_ATTEMPTS {
+            self.status = CheckInRetryStatus::Failed;
+        } else {
+            self.next_attempt_at = now + Self::backoff(self.attempts);
+        }
+    }
+
+    /// Check if the retry should be attempted at `now`
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.status == CheckInRetryStatus::Pending && self.next_attempt_at <= now
+    }
+
+    // Getters
+    pub fn account_id(&self) -> &AccountId {
+        &self.account_id
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    pub fn next_attempt_at(&self) -> DateTime<Utc> {
+        self.next_attempt_at
+    }
+
+    pub fn last_error(&self) -> &str {
+        &self.last_error
+    }
+
+    pub fn status(&self) -> CheckInRetryStatus {
+        self.status
+    }
+
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    pub fn updated_at(&self) -> DateTime<Utc> {
+        self.updated_at
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recoverable_failure_is_queued() {
+        let now = Utc::now();
+        let retry =
+            CheckInRetry::for_failure(AccountId::new(), ErrorCode::NetworkError, "timeout", now)
+                .expect("network errors should be retried");
+
+        assert_eq!(retry.attempts(), 1);
+        assert_eq!(retry.status(), CheckInRetryStatus::Pending);
+        assert_eq!(retry.next_attempt_at(), now + Duration::minutes(5));
+        assert!(!retry.is_due(now));
+        assert!(retry.is_due(now + Duration::minutes(5)));
+    }
+
+    #[test]
+    fn test_permanent_failure_is_not_queued() {
+        let retry = CheckInRetry::for_failure(
+            AccountId::new(),
+            ErrorCode::ValidationError,
+            "Account is disabled",
+            Utc::now(),
+        );
+
+        assert!(retry.is_none());
+    }
+
+    #[test]
+    fn test_backoff_grows_and_is_capped() {
+        assert_eq!(CheckInRetry::backoff(1), Duration::minutes(5));
+        assert_eq!(CheckInRetry::backoff(2), Duration::minutes(10));
+        assert_eq!(CheckInRetry::backoff(3), Duration::minutes(20));
+        assert_eq!(CheckInRetry::backoff(20), Duration::hours(6));
+        assert_eq!(CheckInRetry::backoff(u32::MAX), Duration::hours(6));
+    }
+
+    #[test]
+    fn test_record_failure_reschedules_until_attempts_exhausted() {
+        let now = Utc::now();
+        let mut retry =
+            CheckInRetry::for_failure(AccountId::new(), ErrorCode::TimeoutError, "timeout", now)
+                .unwrap();
+
+        retry.record_failure(ErrorCode::NetworkError, "still down", now);
+        assert_eq!(retry.attempts(), 2);
+        assert_eq!(retry.status(), CheckInRetryStatus::Pending);
+        assert_eq!(retry.next_attempt_at(), now + Duration::minutes(10));
+        assert_eq!(retry.last_error(), "still down");
+
+        for _ in retry.attempts()..CheckInRetry::MAX_ATTEMPTS {
+            retry.record_failure(ErrorCode::NetworkError, "still down", now);
+        }
+        assert_eq!(retry.attempts(), CheckInRetry::MAX_ATTEMPTS);
+        assert_eq!(retry.status(), CheckInRetryStatus::Failed);
+        assert!(!retry.is_due(now + Duration::days(1)));
+    }
+
+    #[test]
+    fn test_record_permanent_failure_marks_failed() {
+        let now = Utc::now();
+        let mut retry =
+            CheckInRetry::for_failure(AccountId::new(), ErrorCode::NetworkError, "timeout", now)
+                .unwrap();
+
+        retry.record_failure(ErrorCode::InvalidCredentials, "session expired", now);
+
+        assert_eq!(retry.status(), CheckInRetryStatus::Failed);
+        assert_eq!(retry.last_error(), "session expired");
+    }
+}
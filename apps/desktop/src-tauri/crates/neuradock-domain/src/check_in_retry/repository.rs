@@ -0,0 +1,24 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use super::CheckInRetry;
+use crate::shared::{AccountId, DomainError};
+
+/// Repository trait for the persistent check-in retry queue
+#[async_trait]
+pub trait CheckInRetryRepository: Send + Sync {
+    /// Save or update the retry of an account
+    async fn save(&self, retry: &CheckInRetry) -> Result<(), DomainError>;
+
+    /// Find the retry of an account
+    async fn find_by_account_id(
+        &self,
+        account_id: &AccountId,
+    ) -> Result<Option<CheckInRetry>, DomainError>;
+
+    /// Find pending retries due at `now`, ordered by next attempt time
+    async fn find_due(&self, now: DateTime<Utc>) -> Result<Vec<CheckInRetry>, DomainError>;
+
+    /// Delete the retry of an account
+    async fn delete(&self, account_id: &AccountId) -> Result<(), DomainError>;
+}
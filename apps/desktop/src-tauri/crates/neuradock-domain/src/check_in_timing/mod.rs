@@ -0,0 +1,178 @@
+mod repository;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::shared::{AccountId, ProviderId};
+
+pub use repository::CheckInTimingRepository;
+
+/// Phase of the check-in pipeline
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CheckInPhase {
+    /// Loading cached WAF cookies or solving the challenge up front
+    CookiePreparation,
+    /// Solving the WAF challenge again after cookies were rejected
+    WafRefresh,
+    /// Fetching the balance before the check-in
+    UserInfo,
+    /// The check-in request itself, including a Turnstile token when needed
+    CheckInRequest,
+    /// Fetching the balance after a successful check-in, including the wait
+    /// for the provider to credit it
+    BalanceFetch,
+}
+
+impl CheckInPhase {
+    pub const ALL: [CheckInPhase; 5] = [
+        CheckInPhase::CookiePreparation,
+        CheckInPhase::WafRefresh,
+        CheckInPhase::UserInfo,
+        CheckInPhase::CheckInRequest,
+        CheckInPhase::BalanceFetch,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CheckInPhase::CookiePreparation => "cookie_preparation",
+            CheckInPhase::WafRefresh => "waf_refresh",
+            CheckInPhase::UserInfo => "user_info",
+            CheckInPhase::CheckInRequest => "check_in_request",
+            CheckInPhase::BalanceFetch => "balance_fetch",
+        }
+    }
+
+    fn index(&self) -> usize {
+        match self {
+            CheckInPhase::CookiePreparation => 0,
+            CheckInPhase::WafRefresh => 1,
+            CheckInPhase::UserInfo => 2,
+            CheckInPhase::CheckInRequest => 3,
+            CheckInPhase::BalanceFetch => 4,
+        }
+    }
+}
+
+/// Time spent in each phase of a check-in run, in milliseconds
+///
+/// A phase that ran more than once, such as a WAF refresh during the user
+/// info fetch and again during the check-in request, adds up.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckInPhaseDurations {
+    millis: [u64; 5],
+}
+
+impl CheckInPhaseDurations {
+    pub fn add(&mut self, phase: CheckInPhase, elapsed: Duration) {
+        self.millis[phase.index()] += elapsed.as_millis() as u64;
+    }
+
+    pub fn set_ms(&mut self, phase: CheckInPhase, ms: u64) {
+        self.millis[phase.index()] = ms;
+    }
+
+    pub fn ms(&self, phase: CheckInPhase) -> u64 {
+        self.millis[phase.index()]
+    }
+}
+
+/// Phase timings of one check-in run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckInTiming {
+    account_id: AccountId,
+    provider_id: ProviderId,
+    started_at: DateTime<Utc>,
+    success: bool,
+    phases: CheckInPhaseDurations,
+    total_ms: u64,
+}
+
+impl CheckInTiming {
+    /// Runs kept per account, older runs are dropped on save
+    pub const MAX_PER_ACCOUNT: u32 = 200;
+
+    pub fn new(
+        account_id: AccountId,
+        provider_id: ProviderId,
+        started_at: DateTime<Utc>,
+        success: bool,
+        phases: CheckInPhaseDurations,
+        total: Duration,
+    ) -> Self {
+        Self {
+            account_id,
+            provider_id,
+            started_at,
+            success,
+            phases,
+            total_ms: total.as_millis() as u64,
+        }
+    }
+
+    /// Restore timings from persistence
+    pub fn restore(
+        account_id: AccountId,
+        provider_id: ProviderId,
+        started_at: DateTime<Utc>,
+        success: bool,
+        phases: CheckInPhaseDurations,
+        total_ms: u64,
+    ) -> Self {
+        Self {
+            account_id,
+            provider_id,
+            started_at,
+            success,
+            phases,
+            total_ms,
+        }
+    }
+
+    // Getters
+    pub fn account_id(&self) -> &AccountId {
+        &self.account_id
+    }
+
+    pub fn provider_id(&self) -> &ProviderId {
+        &self.provider_id
+    }
+
+    pub fn started_at(&self) -> DateTime<Utc> {
+        self.started_at
+    }
+
+    pub fn success(&self) -> bool {
+        self.success
+    }
+
+    pub fn phases(&self) -> &CheckInPhaseDurations {
+        &self.phases
+    }
+
+    pub fn phase_ms(&self, phase: CheckInPhase) -> u64 {
+        self.phases.ms(phase)
+    }
+
+    /// Wall time of the run, including time outside the timed phases
+    pub fn total_ms(&self) -> u64 {
+        self.total_ms
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_phase_durations_add_up_per_phase() {
+        let mut phases = CheckInPhaseDurations::default();
+        phases.add(CheckInPhase::WafRefresh, Duration::from_millis(1200));
+        phases.add(CheckInPhase::WafRefresh, Duration::from_millis(800));
+        phases.add(CheckInPhase::UserInfo, Duration::from_micros(2500));
+
+        assert_eq!(phases.ms(CheckInPhase::WafRefresh), 2000);
+        assert_eq!(phases.ms(CheckInPhase::UserInfo), 2);
+        assert_eq!(phases.ms(CheckInPhase::CookiePreparation), 0);
+    }
+}
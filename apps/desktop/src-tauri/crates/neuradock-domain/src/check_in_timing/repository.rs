@@ -0,0 +1,18 @@
+use async_trait::async_trait;
+
+use super::CheckInTiming;
+use crate::shared::{AccountId, DomainError};
+
+/// Repository trait for the phase timings of check-in runs
+#[async_trait]
+pub trait CheckInTimingRepository: Send + Sync {
+    /// Save the timings of a run, dropping the oldest runs of the account past the retention limit
+    async fn save(&self, timing: &CheckInTiming) -> Result<(), DomainError>;
+
+    /// Find the latest runs of an account, newest first
+    async fn find_recent_by_account(
+        &self,
+        account_id: &AccountId,
+        limit: u32,
+    ) -> Result<Vec<CheckInTiming>, DomainError>;
+}
@@ -5,21 +5,6 @@ use std::any::Any;
 use crate::events::DomainEvent;
 use crate::shared::{AccountId, ProviderId};
 
-/// Macro to implement DomainEvent trait with type name
-macro_rules! impl_domain_event {
-    ($type:ty) => {
-        impl DomainEvent for $type {
-            fn as_any(&self) -> &(dyn Any + Send + Sync) {
-                self
-            }
-
-            fn event_type_name(&self) -> &'static str {
-                std::any::type_name::<Self>()
-            }
-        }
-    };
-}
-
 /// Event fired when an account is created
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AccountCreated {
@@ -50,11 +35,24 @@ impl_domain_event!(AccountUpdated);
 pub struct AccountDeleted {
     pub account_id: AccountId,
     pub name: String,
+    /// `false` while the delete can still be undone, `true` once the account
+    /// and its history are gone
+    pub permanent: bool,
     pub occurred_at: DateTime<Utc>,
 }
 
 impl_domain_event!(AccountDeleted);
 
+/// Event fired when a soft-deleted account is restored
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountRestored {
+    pub account_id: AccountId,
+    pub name: String,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl_domain_event!(AccountRestored);
+
 /// Event fired when an account is toggled (enabled/disabled)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AccountToggled {
@@ -1,7 +1,23 @@
 use std::any::Any;
 
+/// Macro to implement DomainEvent trait with type name
+macro_rules! impl_domain_event {
+    ($type:ty) => {
+        impl DomainEvent for $type {
+            fn as_any(&self) -> &(dyn Any + Send + Sync) {
+                self
+            }
+
+            fn event_type_name(&self) -> &'static str {
+                std::any::type_name::<Self>()
+            }
+        }
+    };
+}
+
 pub mod account_events;
 pub mod event_bus;
+pub mod provider_events;
 
 pub use event_bus::{DynamicEventHandler, EventBus, EventHandler, TypedEventHandlerWrapper};
 
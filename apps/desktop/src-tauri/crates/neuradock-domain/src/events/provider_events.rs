@@ -0,0 +1,35 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::any::Any;
+
+use crate::events::DomainEvent;
+use crate::shared::ProviderId;
+
+/// Event fired when a provider is created
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderCreated {
+    pub provider_id: ProviderId,
+    pub name: String,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl_domain_event!(ProviderCreated);
+
+/// Event fired when a provider is updated
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderUpdated {
+    pub provider_id: ProviderId,
+    pub name: String,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl_domain_event!(ProviderUpdated);
+
+/// Event fired when a provider is deleted
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderDeleted {
+    pub provider_id: ProviderId,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl_domain_event!(ProviderDeleted);
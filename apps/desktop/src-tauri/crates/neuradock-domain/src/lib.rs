@@ -5,6 +5,8 @@ pub mod account;
 pub mod balance;
 pub mod balance_history;
 pub mod check_in;
+pub mod check_in_retry;
+pub mod check_in_timing;
 pub mod custom_node;
 pub mod events;
 pub mod independent_key;
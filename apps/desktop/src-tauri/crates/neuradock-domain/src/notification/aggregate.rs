@@ -2,8 +2,9 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use specta::Type;
 
-use super::value_objects::{ChannelConfig, ChannelType, NotificationChannelId};
-use crate::shared::DomainError;
+use super::template::NotificationTemplates;
+use super::value_objects::{ChannelConfig, ChannelType, Locale, NotificationChannelId};
+use crate::shared::{DomainError, ErrorSeverity};
 
 /// NotificationChannel aggregate root
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -12,6 +13,12 @@ pub struct NotificationChannel {
     channel_type: ChannelType,
     config: ChannelConfig,
     enabled: bool,
+    /// Messages below this severity are not sent through the channel
+    min_severity: ErrorSeverity,
+    /// Content templates for this channel, over the global ones
+    templates: NotificationTemplates,
+    /// Language of the channel's messages, `None` follows the app setting
+    locale: Option<Locale>,
     created_at: DateTime<Utc>,
 }
 
@@ -28,16 +35,23 @@ impl NotificationChannel {
             channel_type,
             config,
             enabled: true,
+            min_severity: ErrorSeverity::Info,
+            templates: NotificationTemplates::default(),
+            locale: None,
             created_at: Utc::now(),
         })
     }
 
     /// Reconstruct from persistence
+    #[allow(clippy::too_many_arguments)]
     pub fn from_persistence(
         id: NotificationChannelId,
         channel_type: ChannelType,
         config: ChannelConfig,
         enabled: bool,
+        min_severity: ErrorSeverity,
+        templates: NotificationTemplates,
+        locale: Option<Locale>,
         created_at: DateTime<Utc>,
     ) -> Self {
         Self {
@@ -45,6 +59,9 @@ impl NotificationChannel {
             channel_type,
             config,
             enabled,
+            min_severity,
+            templates,
+            locale,
             created_at,
         }
     }
@@ -66,6 +83,18 @@ impl NotificationChannel {
         self.enabled
     }
 
+    pub fn min_severity(&self) -> ErrorSeverity {
+        self.min_severity
+    }
+
+    pub fn templates(&self) -> &NotificationTemplates {
+        &self.templates
+    }
+
+    pub fn locale(&self) -> Option<Locale> {
+        self.locale
+    }
+
     pub fn created_at(&self) -> DateTime<Utc> {
         self.created_at
     }
@@ -82,6 +111,27 @@ impl NotificationChannel {
         self.enabled = false;
     }
 
+    /// Only send messages at or above `severity` through this channel
+    pub fn set_min_severity(&mut self, severity: ErrorSeverity) {
+        self.min_severity = severity;
+    }
+
+    /// Replace the channel's content templates, empty ones use the global or built-in content
+    pub fn set_templates(&mut self, templates: NotificationTemplates) {
+        self.templates = templates;
+    }
+
+    /// Write this channel's messages in `locale`, `None` follows the app setting
+    pub fn set_locale(&mut self, locale: Option<Locale>) {
+        self.locale = locale;
+    }
+
+    /// Whether a message of `severity` should be sent through this channel,
+    /// a disabled channel accepts nothing
+    pub fn accepts(&self, severity: ErrorSeverity) -> bool {
+        self.enabled && severity >= self.min_severity
+    }
+
     /// Update configuration
     pub fn update_config(&mut self, new_config: ChannelConfig) -> Result<(), DomainError> {
         // Validate new configuration
@@ -156,4 +206,34 @@ mod tests {
         let result = channel.update_config(new_config);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_error_only_channel_suppresses_warnings() {
+        let config = ChannelConfig::Feishu {
+            webhook_key: "test_key_123".to_string(),
+        };
+
+        let mut channel = NotificationChannel::new(config).unwrap();
+        assert!(channel.accepts(ErrorSeverity::Info));
+
+        channel.set_min_severity(ErrorSeverity::Error);
+        assert!(!channel.accepts(ErrorSeverity::Info));
+        assert!(!channel.accepts(ErrorSeverity::Warning));
+        assert!(channel.accepts(ErrorSeverity::Error));
+        assert!(channel.accepts(ErrorSeverity::Critical));
+    }
+
+    #[test]
+    fn test_disabled_channel_accepts_nothing() {
+        let config = ChannelConfig::Feishu {
+            webhook_key: "test_key_123".to_string(),
+        };
+
+        let mut channel = NotificationChannel::new(config).unwrap();
+        channel.disable();
+        assert!(!channel.accepts(ErrorSeverity::Critical));
+
+        channel.enable();
+        assert!(channel.accepts(ErrorSeverity::Info));
+    }
 }
@@ -1,9 +1,14 @@
 mod aggregate;
 mod repository;
 mod sender;
+mod template;
 mod value_objects;
 
 pub use aggregate::NotificationChannel;
 pub use repository::NotificationChannelRepository;
-pub use sender::{NotificationMessage, NotificationSender};
-pub use value_objects::{ChannelConfig, ChannelType, NotificationChannelId};
+pub use sender::{NotificationMessage, NotificationSender, TEST_MESSAGE_LABEL};
+pub use template::{
+    NotificationTemplate, NotificationTemplates, TemplateKind, TemplateValues,
+    TEMPLATE_PLACEHOLDERS,
+};
+pub use value_objects::{ChannelConfig, ChannelType, Locale, NotificationChannelId};
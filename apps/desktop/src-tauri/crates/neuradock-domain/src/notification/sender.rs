@@ -2,7 +2,8 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use specta::Type;
 
-use crate::shared::DomainError;
+use super::template::{NotificationTemplate, TemplateValues};
+use crate::shared::{DomainError, ErrorSeverity};
 
 /// Notification message to be sent
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -13,14 +14,31 @@ pub struct NotificationMessage {
     pub content: String,
     /// Optional link URL
     pub link: Option<String>,
+    /// How important the message is, compared against each channel's threshold
+    pub severity: ErrorSeverity,
+    /// Values a notification template can render the content from
+    pub template_values: Option<TemplateValues>,
 }
 
+/// Marks test notifications so they are not mistaken for real check-in results
+pub const TEST_MESSAGE_LABEL: &str = "【测试】";
+
 impl NotificationMessage {
+    /// Message sent when a channel is tested, clearly labeled as a test
+    pub fn test() -> Self {
+        Self::new(
+            format!("{}NeuraDock 通知渠道测试", TEST_MESSAGE_LABEL),
+            "这是一条测试通知，不代表任何签到或余额结果。如果您收到此消息，说明通知渠道配置成功！",
+        )
+    }
+
     pub fn new(title: impl Into<String>, content: impl Into<String>) -> Self {
         Self {
             title: title.into(),
             content: content.into(),
             link: None,
+            severity: ErrorSeverity::Info,
+            template_values: None,
         }
     }
 
@@ -28,6 +46,26 @@ impl NotificationMessage {
         self.link = Some(link.into());
         self
     }
+
+    pub fn with_severity(mut self, severity: ErrorSeverity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    pub fn with_template_values(mut self, values: TemplateValues) -> Self {
+        self.template_values = Some(values);
+        self
+    }
+
+    /// This message with its content rendered from `template`, unchanged
+    /// without a template or without values to render
+    pub fn rendered(&self, template: Option<&NotificationTemplate>) -> Self {
+        let mut message = self.clone();
+        if let (Some(template), Some(values)) = (template, &self.template_values) {
+            message.content = template.render(values);
+        }
+        message
+    }
 }
 
 /// Notification sender trait (Strategy pattern)
@@ -37,6 +75,8 @@ pub trait NotificationSender: Send + Sync {
     /// Send a notification message
     async fn send(&self, message: &NotificationMessage) -> Result<(), DomainError>;
 
-    /// Test the notification channel connectivity
-    async fn test(&self) -> Result<(), DomainError>;
+    /// Test the notification channel connectivity with a labeled test message
+    async fn test(&self) -> Result<(), DomainError> {
+        self.send(&NotificationMessage::test()).await
+    }
 }
@@ -0,0 +1,255 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::shared::DomainError;
+
+/// Placeholders a template may use, written as `{name}`
+pub const TEMPLATE_PLACEHOLDERS: [&str; 5] = ["account", "provider", "balance", "reward", "error"];
+
+/// Shown for a placeholder whose value the message does not have
+const MISSING_VALUE: &str = "-";
+
+/// Which notification a template replaces the built-in content of
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum TemplateKind {
+    CheckInSuccess,
+    CheckInFailure,
+}
+
+/// Values a templated message is rendered from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+pub struct TemplateValues {
+    pub kind: TemplateKind,
+    pub account: String,
+    pub provider: String,
+    pub balance: Option<String>,
+    pub reward: Option<String>,
+    pub error: Option<String>,
+}
+
+impl TemplateValues {
+    pub fn new(
+        kind: TemplateKind,
+        account: impl Into<String>,
+        provider: impl Into<String>,
+    ) -> Self {
+        Self {
+            kind,
+            account: account.into(),
+            provider: provider.into(),
+            balance: None,
+            reward: None,
+            error: None,
+        }
+    }
+
+    fn get(&self, placeholder: &str) -> Option<&str> {
+        match placeholder {
+            "account" => Some(&self.account),
+            "provider" => Some(&self.provider),
+            "balance" => self.balance.as_deref(),
+            "reward" => self.reward.as_deref(),
+            "error" => self.error.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// One piece of a parsed template
+enum Segment<'a> {
+    Text(&'a str),
+    Placeholder(&'a str),
+}
+
+/// Split `template` into text and placeholders, `{{` and `}}` are literal braces
+fn segments(template: &str) -> Result<Vec<Segment<'_>>, DomainError> {
+    let mut segments = Vec::new();
+    let mut rest = template;
+
+    while let Some(pos) = rest.find(['{', '}']) {
+        let (text, tail) = rest.split_at(pos);
+        if !text.is_empty() {
+            segments.push(Segment::Text(text));
+        }
+
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            segments.push(Segment::Text(&tail[..1]));
+            rest = &tail[2..];
+            continue;
+        }
+        if tail.starts_with('}') {
+            return Err(DomainError::Validation(
+                "Unmatched '}' in notification template, write '}}' for a literal brace"
+                    .to_string(),
+            ));
+        }
+
+        let end = tail.find('}').ok_or_else(|| {
+            DomainError::Validation("Unclosed '{' in notification template".to_string())
+        })?;
+        let name = &tail[1..end];
+        if !TEMPLATE_PLACEHOLDERS.contains(&name) {
+            return Err(DomainError::Validation(format!(
+                "Unknown placeholder {{{}}} in notification template, expected one of: {}",
+                name,
+                TEMPLATE_PLACEHOLDERS
+                    .iter()
+                    .map(|p| format!("{{{}}}", p))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )));
+        }
+        segments.push(Segment::Placeholder(name));
+        rest = &tail[end + 1..];
+    }
+
+    if !rest.is_empty() {
+        segments.push(Segment::Text(rest));
+    }
+    Ok(segments)
+}
+
+/// User-written notification content with `{placeholder}`s
+///
+/// Only templates that passed [`NotificationTemplate::parse`] can be built,
+/// so rendering never meets an unknown placeholder.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(try_from = "String", into = "String")]
+pub struct NotificationTemplate(String);
+
+impl NotificationTemplate {
+    /// Validate a template, rejecting empty ones and unknown placeholders
+    pub fn parse(template: impl Into<String>) -> Result<Self, DomainError> {
+        let template = template.into();
+        if template.trim().is_empty() {
+            return Err(DomainError::Validation(
+                "Notification template cannot be empty".to_string(),
+            ));
+        }
+        segments(&template)?;
+        Ok(Self(template))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Substitute `values` into the template
+    pub fn render(&self, values: &TemplateValues) -> String {
+        let segments = segments(&self.0).expect("template validated when built");
+        segments
+            .into_iter()
+            .map(|segment| match segment {
+                Segment::Text(text) => text,
+                Segment::Placeholder(name) => values.get(name).unwrap_or(MISSING_VALUE),
+            })
+            .collect()
+    }
+}
+
+impl TryFrom<String> for NotificationTemplate {
+    type Error = DomainError;
+
+    fn try_from(template: String) -> Result<Self, Self::Error> {
+        Self::parse(template)
+    }
+}
+
+impl From<NotificationTemplate> for String {
+    fn from(template: NotificationTemplate) -> Self {
+        template.0
+    }
+}
+
+/// Templates replacing the built-in check-in notification content
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(default)]
+pub struct NotificationTemplates {
+    pub check_in_success: Option<NotificationTemplate>,
+    pub check_in_failure: Option<NotificationTemplate>,
+}
+
+impl NotificationTemplates {
+    /// Validate optional template strings, blank ones mean the built-in content
+    pub fn parse(
+        check_in_success: Option<String>,
+        check_in_failure: Option<String>,
+    ) -> Result<Self, DomainError> {
+        let parse = |template: Option<String>| {
+            template
+                .filter(|template| !template.trim().is_empty())
+                .map(NotificationTemplate::parse)
+                .transpose()
+        };
+
+        Ok(Self {
+            check_in_success: parse(check_in_success)?,
+            check_in_failure: parse(check_in_failure)?,
+        })
+    }
+
+    pub fn get(&self, kind: TemplateKind) -> Option<&NotificationTemplate> {
+        match kind {
+            TemplateKind::CheckInSuccess => self.check_in_success.as_ref(),
+            TemplateKind::CheckInFailure => self.check_in_failure.as_ref(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.check_in_success.is_none() && self.check_in_failure.is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values() -> TemplateValues {
+        TemplateValues {
+            balance: Some("$12.50".to_string()),
+            reward: Some("$0.50".to_string()),
+            ..TemplateValues::new(TemplateKind::CheckInSuccess, "main", "AnyRouter")
+        }
+    }
+
+    #[test]
+    fn test_placeholders_are_substituted() {
+        let template =
+            NotificationTemplate::parse("{account} @ {provider}: {balance} (+{reward})").unwrap();
+
+        assert_eq!(
+            template.render(&values()),
+            "main @ AnyRouter: $12.50 (+$0.50)"
+        );
+    }
+
+    #[test]
+    fn test_missing_values_and_literal_braces() {
+        let template = NotificationTemplate::parse("{{{account}}} error: {error}").unwrap();
+
+        assert_eq!(template.render(&values()), "{main} error: -");
+    }
+
+    #[test]
+    fn test_unknown_placeholder_is_rejected() {
+        let err = NotificationTemplate::parse("Hi {acount}").unwrap_err();
+        assert!(matches!(err, DomainError::Validation(ref msg) if msg.contains("{acount}")));
+
+        assert!(NotificationTemplate::parse("{account").is_err());
+        assert!(NotificationTemplate::parse("account}").is_err());
+        assert!(NotificationTemplate::parse("  ").is_err());
+        assert!(serde_json::from_str::<NotificationTemplate>(r#""{balanse}""#).is_err());
+    }
+
+    #[test]
+    fn test_blank_templates_fall_back_to_built_in() {
+        let templates = NotificationTemplates::parse(Some(" ".to_string()), None).unwrap();
+        assert!(templates.is_empty());
+
+        let templates =
+            NotificationTemplates::parse(None, Some("❌ {account}: {error}".to_string())).unwrap();
+        assert!(templates.get(TemplateKind::CheckInSuccess).is_none());
+        assert!(templates.get(TemplateKind::CheckInFailure).is_some());
+    }
+}
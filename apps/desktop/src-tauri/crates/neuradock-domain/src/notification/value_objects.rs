@@ -78,6 +78,54 @@ impl fmt::Display for ChannelType {
     }
 }
 
+/// Language notifications are written in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Type)]
+pub enum Locale {
+    #[serde(rename = "zh-CN")]
+    ZhCn,
+    #[serde(rename = "en-US")]
+    EnUs,
+}
+
+impl Locale {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Locale::ZhCn => "zh-CN",
+            Locale::EnUs => "en-US",
+        }
+    }
+
+    /// Closest supported locale for a system language tag like `zh_CN.UTF-8`,
+    /// Chinese for any `zh` tag and English otherwise
+    pub fn from_language_tag(tag: &str) -> Self {
+        if tag.trim().to_lowercase().starts_with("zh") {
+            Locale::ZhCn
+        } else {
+            Locale::EnUs
+        }
+    }
+}
+
+impl FromStr for Locale {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().replace('_', "-").to_lowercase().as_str() {
+            "zh-cn" | "zh" => Ok(Locale::ZhCn),
+            "en-us" | "en" => Ok(Locale::EnUs),
+            _ => Err(DomainError::InvalidInput(format!(
+                "Unsupported locale: {s}, expected zh-CN or en-US"
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 /// Channel configuration
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -0,0 +1,321 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use crate::shared::DomainError;
+
+/// Parser for cookies exported from browsers
+///
+/// Supported formats:
+/// - Netscape `cookies.txt` (curl / wget / "Get cookies.txt" extensions)
+/// - JSON arrays produced by cookie-editor extensions (Cookie-Editor, EditThisCookie)
+/// - Raw `Cookie:` request header strings copied from devtools
+pub struct CookieImportParser;
+
+/// Single cookie entry of a JSON cookie export
+#[derive(Debug, Deserialize)]
+struct JsonCookie {
+    #[serde(default)]
+    domain: Option<String>,
+    name: String,
+    #[serde(default)]
+    value: String,
+    #[serde(default, rename = "hostOnly")]
+    host_only: bool,
+}
+
+/// Some extensions wrap the cookie list in an object
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum JsonCookieExport {
+    List(Vec<JsonCookie>),
+    Wrapped { cookies: Vec<JsonCookie> },
+}
+
+impl CookieImportParser {
+    /// Parse a cookie export, detecting the format from its content, and keep only
+    /// cookies that a browser would send to `provider_domain`
+    pub fn parse(
+        content: &str,
+        provider_domain: &str,
+    ) -> Result<HashMap<String, String>, DomainError> {
+        let trimmed = content.trim_start_matches('\u{feff}').trim();
+        if trimmed.starts_with('[') || trimmed.starts_with('{') {
+            Self::parse_json(trimmed, provider_domain)
+        } else {
+            Self::parse_netscape(trimmed, provider_domain)
+        }
+    }
+
+    /// Parse a Netscape `cookies.txt` export
+    pub fn parse_netscape(
+        content: &str,
+        provider_domain: &str,
+    ) -> Result<HashMap<String, String>, DomainError> {
+        let host = Self::host_of(provider_domain)?;
+        let mut cookies = HashMap::new();
+
+        for line in content.lines() {
+            // HttpOnly cookies are prefixed with "#HttpOnly_" and must not be treated as comments
+            let line = line.trim_end_matches('\r');
+            let line = match line.strip_prefix("#HttpOnly_") {
+                Some(rest) => rest,
+                None if line.trim().is_empty() || line.starts_with('#') => continue,
+                None => line,
+            };
+
+            // domain, include_subdomains, path, secure, expiry, name, value
+            let fields: Vec<&str> = line.split('\t').collect();
+            if fields.len() < 7 {
+                return Err(DomainError::InvalidInput(format!(
+                    "Invalid cookies.txt line (expected 7 tab-separated fields): {}",
+                    line
+                )));
+            }
+
+            let (domain, include_subdomains, name) = (fields[0], fields[1], fields[5]);
+            let host_only = include_subdomains.eq_ignore_ascii_case("FALSE");
+            let value = fields[6..].join("\t");
+            if !name.is_empty() && Self::domain_matches(domain, host_only, &host) {
+                cookies.insert(name.to_string(), value);
+            }
+        }
+
+        Ok(cookies)
+    }
+
+    /// Parse a JSON cookie export (array of `{ domain, name, value, ... }` objects)
+    pub fn parse_json(
+        content: &str,
+        provider_domain: &str,
+    ) -> Result<HashMap<String, String>, DomainError> {
+        let host = Self::host_of(provider_domain)?;
+        let export: JsonCookieExport = serde_json::from_str(content)
+            .map_err(|e| DomainError::InvalidInput(format!("Invalid JSON cookie export: {}", e)))?;
+        let entries = match export {
+            JsonCookieExport::List(entries) | JsonCookieExport::Wrapped { cookies: entries } => {
+                entries
+            }
+        };
+
+        Ok(entries
+            .into_iter()
+            .filter(|cookie| !cookie.name.is_empty())
+            .filter(|cookie| {
+                cookie
+                    .domain
+                    .as_deref()
+                    .is_none_or(|domain| Self::domain_matches(domain, cookie.host_only, &host))
+            })
+            .map(|cookie| (cookie.name, cookie.value))
+            .collect())
+    }
+
+    /// Parse a raw `Cookie` header string (`k=v; k2=v2`), optionally prefixed with `Cookie:`
+    pub fn parse_header(content: &str) -> Result<HashMap<String, String>, DomainError> {
+        let trimmed = content.trim();
+        let header = match trimmed.split_once(':') {
+            Some((prefix, rest)) if prefix.trim().eq_ignore_ascii_case("cookie") => rest,
+            _ => trimmed,
+        };
+
+        let mut cookies = HashMap::new();
+        for segment in header.split(';') {
+            let segment = segment.trim();
+            if segment.is_empty() {
+                continue;
+            }
+
+            // Split on the first '=' only, values may contain '=' (e.g. base64 padding)
+            let (name, value) = segment.split_once('=').ok_or_else(|| {
+                DomainError::InvalidInput(format!(
+                    "Invalid cookie pair (expected name=value): {}",
+                    segment
+                ))
+            })?;
+            let name = name.trim();
+            if name.is_empty() {
+                return Err(DomainError::InvalidInput(format!(
+                    "Cookie name must not be empty: {}",
+                    segment
+                )));
+            }
+            cookies.insert(name.to_string(), value.trim().to_string());
+        }
+
+        Ok(cookies)
+    }
+
+    /// Whether a cookie set for `cookie_domain` is sent to `host`
+    fn domain_matches(cookie_domain: &str, host_only: bool, host: &str) -> bool {
+        let cookie_domain = cookie_domain.trim().trim_start_matches('.').to_lowercase();
+        if cookie_domain.is_empty() {
+            return false;
+        }
+        host == cookie_domain || (!host_only && host.ends_with(&format!(".{}", cookie_domain)))
+    }
+
+    /// Extract the lowercase host of a provider domain (accepts bare hosts and full URLs)
+    fn host_of(provider_domain: &str) -> Result<String, DomainError> {
+        let trimmed = provider_domain.trim();
+        let with_scheme = if trimmed.contains("://") {
+            trimmed.to_string()
+        } else {
+            format!("https://{}", trimmed)
+        };
+
+        url::Url::parse(&with_scheme)
+            .ok()
+            .and_then(|url| url.host_str().map(|host| host.to_lowercase()))
+            .ok_or_else(|| {
+                DomainError::InvalidInput(format!("Invalid provider domain: {}", provider_domain))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NETSCAPE_EXPORT: &str = "# Netscape HTTP Cookie File\n\
+# https://curl.se/docs/http-cookies.html\n\
+\n\
+.anyrouter.top\tTRUE\t/\tTRUE\t1767225600\tsession\tabc123==\n\
+#HttpOnly_anyrouter.top\tFALSE\t/\tTRUE\t0\tnew-api-user\t42\n\
+.example.com\tTRUE\t/\tFALSE\t1767225600\ttracking\tnope\n\
+evil-anyrouter.top\tFALSE\t/\tFALSE\t0\tspoof\tnope\n";
+
+    const JSON_EXPORT: &str = r#"[
+        {
+            "domain": ".anyrouter.top",
+            "expirationDate": 1767225600,
+            "hostOnly": false,
+            "httpOnly": true,
+            "name": "session",
+            "path": "/",
+            "sameSite": "lax",
+            "secure": true,
+            "session": false,
+            "storeId": "0",
+            "value": "abc123=="
+        },
+        {
+            "domain": "anyrouter.top",
+            "hostOnly": true,
+            "name": "acw_tc",
+            "path": "/",
+            "value": "waf"
+        },
+        {
+            "domain": "api.anyrouter.top",
+            "name": "subdomain_only",
+            "value": "nope"
+        },
+        {
+            "domain": ".google.com",
+            "name": "NID",
+            "value": "nope"
+        }
+    ]"#;
+
+    #[test]
+    fn test_parse_netscape_filters_by_domain() {
+        let cookies =
+            CookieImportParser::parse_netscape(NETSCAPE_EXPORT, "https://anyrouter.top").unwrap();
+
+        assert_eq!(cookies.len(), 2);
+        assert_eq!(cookies.get("session").unwrap(), "abc123==");
+        assert_eq!(cookies.get("new-api-user").unwrap(), "42");
+        assert!(!cookies.contains_key("tracking"));
+        assert!(!cookies.contains_key("spoof"));
+    }
+
+    #[test]
+    fn test_parse_netscape_rejects_malformed_line() {
+        let result = CookieImportParser::parse_netscape(
+            "anyrouter.top\tTRUE\t/\tsession",
+            "https://anyrouter.top",
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_json_filters_by_domain() {
+        let cookies =
+            CookieImportParser::parse_json(JSON_EXPORT, "https://anyrouter.top/").unwrap();
+
+        assert_eq!(cookies.len(), 2);
+        assert_eq!(cookies.get("session").unwrap(), "abc123==");
+        assert_eq!(cookies.get("acw_tc").unwrap(), "waf");
+        assert!(!cookies.contains_key("subdomain_only"));
+        assert!(!cookies.contains_key("NID"));
+    }
+
+    #[test]
+    fn test_parent_domain_cookie_applies_to_subdomain_provider() {
+        let cookies =
+            CookieImportParser::parse_json(JSON_EXPORT, "https://api.anyrouter.top").unwrap();
+
+        // Host-only cookies of the parent domain are not sent to subdomains
+        assert_eq!(cookies.len(), 2);
+        assert!(cookies.contains_key("session"));
+        assert!(cookies.contains_key("subdomain_only"));
+        assert!(!cookies.contains_key("acw_tc"));
+    }
+
+    #[test]
+    fn test_parse_json_wrapped_object() {
+        let content = r#"{"cookies":[{"domain":"anyrouter.top","name":"session","value":"x"}]}"#;
+        let cookies = CookieImportParser::parse_json(content, "anyrouter.top").unwrap();
+        assert_eq!(cookies.get("session").unwrap(), "x");
+    }
+
+    #[test]
+    fn test_parse_detects_format() {
+        let from_json = CookieImportParser::parse(JSON_EXPORT, "https://anyrouter.top").unwrap();
+        let from_txt = CookieImportParser::parse(NETSCAPE_EXPORT, "https://anyrouter.top").unwrap();
+
+        assert_eq!(from_json.get("session"), from_txt.get("session"));
+    }
+
+    #[test]
+    fn test_parse_header_keeps_equals_in_values() {
+        let cookies =
+            CookieImportParser::parse_header("session=abc==; token=a=b=c; new-api-user=42")
+                .unwrap();
+
+        assert_eq!(cookies.len(), 3);
+        assert_eq!(cookies.get("session").unwrap(), "abc==");
+        assert_eq!(cookies.get("token").unwrap(), "a=b=c");
+        assert_eq!(cookies.get("new-api-user").unwrap(), "42");
+    }
+
+    #[test]
+    fn test_parse_header_skips_empty_segments_and_whitespace() {
+        let cookies =
+            CookieImportParser::parse_header("  Cookie:  session=abc ;; ;  acw_tc = waf ;  ")
+                .unwrap();
+
+        assert_eq!(cookies.len(), 2);
+        assert_eq!(cookies.get("session").unwrap(), "abc");
+        assert_eq!(cookies.get("acw_tc").unwrap(), "waf");
+    }
+
+    #[test]
+    fn test_parse_header_allows_empty_value() {
+        let cookies = CookieImportParser::parse_header("session=; theme=dark;").unwrap();
+        assert_eq!(cookies.get("session").unwrap(), "");
+        assert_eq!(cookies.get("theme").unwrap(), "dark");
+    }
+
+    #[test]
+    fn test_parse_header_rejects_invalid_pairs() {
+        assert!(CookieImportParser::parse_header("session").is_err());
+        assert!(CookieImportParser::parse_header("=value").is_err());
+        assert!(CookieImportParser::parse_header(" ; ").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_json() {
+        assert!(CookieImportParser::parse("[{\"name\": }]", "https://anyrouter.top").is_err());
+    }
+}
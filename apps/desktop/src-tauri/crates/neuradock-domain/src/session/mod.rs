@@ -1,3 +1,4 @@
+mod cookie_parser;
 mod repository;
 mod token_extractor;
 
@@ -6,6 +7,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::shared::{AccountId, DomainError};
 
+pub use cookie_parser::CookieImportParser;
 pub use repository::SessionRepository;
 pub use token_extractor::SessionTokenExtractor;
 
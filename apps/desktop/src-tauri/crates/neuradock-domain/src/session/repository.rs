@@ -1,7 +1,7 @@
 use async_trait::async_trait;
 
 use super::Session;
-use crate::shared::{AccountId, DomainError};
+use crate::shared::{AccountId, DomainError, DynTransactionContext};
 
 /// Session repository trait
 #[async_trait]
@@ -9,6 +9,13 @@ pub trait SessionRepository: Send + Sync {
     /// Save or update a session
     async fn save(&self, session: &Session) -> Result<(), DomainError>;
 
+    /// Save or update a session as part of `tx`, written only when the caller commits it
+    async fn save_in_transaction(
+        &self,
+        tx: &mut DynTransactionContext,
+        session: &Session,
+    ) -> Result<(), DomainError>;
+
     /// Find session by account ID
     async fn find_by_account_id(
         &self,
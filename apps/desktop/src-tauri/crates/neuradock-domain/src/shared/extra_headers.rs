@@ -0,0 +1,118 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::BTreeMap;
+
+use super::DomainError;
+
+/// Headers the app sets itself, which extra headers may not replace
+const RESERVED_HEADERS: &[&str] = &["cookie", "host", "content-length", "transfer-encoding"];
+
+/// Static headers added to a provider's API requests, e.g. `Authorization: Bearer <token>`
+///
+/// Names are stored lowercase, since header names are case-insensitive and a
+/// provider and an account naming the same header differently must still merge.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct ExtraHeaders(BTreeMap<String, String>);
+
+impl ExtraHeaders {
+    pub fn new(headers: BTreeMap<String, String>) -> Result<Self, DomainError> {
+        let mut normalized = BTreeMap::new();
+        for (name, value) in headers {
+            let name = name.trim().to_ascii_lowercase();
+            let value = value.trim().to_string();
+
+            if name.is_empty() || !name.bytes().all(is_token_char) {
+                return Err(DomainError::Validation(format!(
+                    "Invalid header name '{}'",
+                    name
+                )));
+            }
+            if RESERVED_HEADERS.contains(&name.as_str()) {
+                return Err(DomainError::Validation(format!(
+                    "Header '{}' is set by the app and cannot be customized",
+                    name
+                )));
+            }
+            if value.bytes().any(|b| b == b'\r' || b == b'\n' || b == 0) {
+                return Err(DomainError::Validation(format!(
+                    "Invalid value for header '{}'",
+                    name
+                )));
+            }
+
+            normalized.insert(name, value);
+        }
+        Ok(Self(normalized))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0.get(&name.to_ascii_lowercase()).map(String::as_str)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    pub fn as_map(&self) -> &BTreeMap<String, String> {
+        &self.0
+    }
+
+    /// These headers with `overrides` applied on top, e.g. an account's over its provider's
+    pub fn merged_with(&self, overrides: &ExtraHeaders) -> ExtraHeaders {
+        let mut merged = self.0.clone();
+        merged.extend(overrides.0.clone());
+        Self(merged)
+    }
+}
+
+/// RFC 9110 token characters allowed in a header name
+fn is_token_char(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_extra_headers_normalize_names() {
+        let extra = ExtraHeaders::new(headers(&[(" Authorization ", " Bearer abc ")])).unwrap();
+
+        assert_eq!(extra.get("authorization"), Some("Bearer abc"));
+        assert_eq!(extra.get("AUTHORIZATION"), Some("Bearer abc"));
+    }
+
+    #[test]
+    fn test_extra_headers_reject_invalid_and_reserved() {
+        assert!(ExtraHeaders::new(headers(&[("X Token", "a")])).is_err());
+        assert!(ExtraHeaders::new(headers(&[("", "a")])).is_err());
+        assert!(ExtraHeaders::new(headers(&[("Cookie", "session=1")])).is_err());
+        assert!(ExtraHeaders::new(headers(&[("X-Token", "a\r\nX-Evil: 1")])).is_err());
+    }
+
+    #[test]
+    fn test_extra_headers_overrides_win() {
+        let provider = ExtraHeaders::new(headers(&[
+            ("Authorization", "Bearer provider"),
+            ("X-Client", "neuradock"),
+        ]))
+        .unwrap();
+        let account = ExtraHeaders::new(headers(&[("authorization", "Bearer account")])).unwrap();
+
+        let merged = provider.merged_with(&account);
+
+        assert_eq!(merged.get("Authorization"), Some("Bearer account"));
+        assert_eq!(merged.get("X-Client"), Some("neuradock"));
+    }
+}
@@ -2,8 +2,10 @@ use serde::{Deserialize, Serialize};
 use specta::Type;
 use uuid::Uuid;
 
+pub mod extra_headers;
 pub mod transaction;
-pub use transaction::{TransactionContext, UnitOfWork, UnitOfWorkError};
+pub use extra_headers::ExtraHeaders;
+pub use transaction::{DynTransactionContext, TransactionContext, UnitOfWork, UnitOfWorkError};
 
 macro_rules! define_id {
     ($name:ident) => {
@@ -51,6 +53,7 @@ pub enum ErrorCode {
     InvalidCredentials = 1001,
     ExpiredSession = 1002,
     MissingApiKey = 1003,
+    AppLocked = 1004,
 
     // Resource Not Found (2xxx)
     AccountNotFound = 2001,
@@ -76,6 +79,7 @@ pub enum ErrorCode {
     NetworkError = 5002,
     TimeoutError = 5003,
     ExternalServiceError = 5004,
+    BrowserUnavailable = 5005,
 
     // Validation (6xxx)
     ValidationError = 6001,
@@ -106,7 +110,8 @@ impl ErrorCode {
             | ErrorCode::DatabaseConstraintViolation
             | ErrorCode::EncryptionError
             | ErrorCode::DecryptionError
-            | ErrorCode::InfrastructureError => ErrorSeverity::Error,
+            | ErrorCode::InfrastructureError
+            | ErrorCode::BrowserUnavailable => ErrorSeverity::Error,
 
             _ => ErrorSeverity::Warning,
         }
@@ -124,8 +129,8 @@ impl ErrorCode {
     }
 }
 
-/// Error severity levels
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+/// Error severity levels, ordered from least to most severe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Type)]
 pub enum ErrorSeverity {
     Info,
     Warning,
@@ -133,6 +138,33 @@ pub enum ErrorSeverity {
     Critical,
 }
 
+impl ErrorSeverity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorSeverity::Info => "info",
+            ErrorSeverity::Warning => "warning",
+            ErrorSeverity::Error => "error",
+            ErrorSeverity::Critical => "critical",
+        }
+    }
+}
+
+impl std::str::FromStr for ErrorSeverity {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "info" => Ok(ErrorSeverity::Info),
+            "warning" => Ok(ErrorSeverity::Warning),
+            "error" => Ok(ErrorSeverity::Error),
+            "critical" => Ok(ErrorSeverity::Critical),
+            _ => Err(DomainError::InvalidInput(format!(
+                "Unknown error severity: {s}"
+            ))),
+        }
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum DomainError {
     #[error("Invalid credentials: {0}")]
@@ -144,6 +176,9 @@ pub enum DomainError {
     #[error("Provider not found: {0}")]
     ProviderNotFound(String),
 
+    #[error("App locked: {0}")]
+    AppLocked(String),
+
     #[error("Check-in failed: {0}")]
     CheckInFailed(String),
 
@@ -153,6 +188,9 @@ pub enum DomainError {
     #[error("Infrastructure error: {0}")]
     Infrastructure(String),
 
+    #[error("Browser launch failed: {0}")]
+    BrowserLaunch(String),
+
     #[error("Validation error: {0}")]
     Validation(String),
 
@@ -182,9 +220,11 @@ impl DomainError {
             DomainError::InvalidCredentials(_) => ErrorCode::InvalidCredentials,
             DomainError::AccountNotFound(_) => ErrorCode::AccountNotFound,
             DomainError::ProviderNotFound(_) => ErrorCode::ProviderNotFound,
+            DomainError::AppLocked(_) => ErrorCode::AppLocked,
             DomainError::CheckInFailed(_) => ErrorCode::CheckInFailed,
             DomainError::Repository(_) => ErrorCode::RepositoryError,
             DomainError::Infrastructure(_) => ErrorCode::InfrastructureError,
+            DomainError::BrowserLaunch(_) => ErrorCode::BrowserUnavailable,
             DomainError::Validation(_) => ErrorCode::ValidationError,
             DomainError::DataIntegrity(_) => ErrorCode::DataIntegrityError,
             DomainError::InvalidInput(_) => ErrorCode::InvalidInput,
@@ -201,9 +241,11 @@ impl DomainError {
             DomainError::InvalidCredentials(msg)
             | DomainError::AccountNotFound(msg)
             | DomainError::ProviderNotFound(msg)
+            | DomainError::AppLocked(msg)
             | DomainError::CheckInFailed(msg)
             | DomainError::Repository(msg)
             | DomainError::Infrastructure(msg)
+            | DomainError::BrowserLaunch(msg)
             | DomainError::Validation(msg)
             | DomainError::DataIntegrity(msg)
             | DomainError::InvalidInput(msg)
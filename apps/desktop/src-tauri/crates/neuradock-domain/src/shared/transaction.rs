@@ -1,4 +1,5 @@
 use async_trait::async_trait;
+use std::any::Any;
 use std::fmt;
 
 /// Abstract transaction context for Unit of Work pattern.
@@ -13,8 +14,14 @@ pub trait TransactionContext: Send + Sync {
 
     /// Rollback the transaction
     async fn rollback(self: Box<Self>) -> Result<(), Self::Error>;
+
+    /// Access the concrete transaction, for repositories of the same backend
+    fn as_any_mut(&mut self) -> &mut dyn Any;
 }
 
+/// Transaction passed to the transaction-aware repository methods
+pub type DynTransactionContext = dyn TransactionContext<Error = UnitOfWorkError>;
+
 /// Error type for Unit of Work operations
 #[derive(Debug)]
 pub enum UnitOfWorkError {
@@ -7,7 +7,8 @@ use crate::shared::DomainError;
 /// WAF cookies domain entity
 #[derive(Debug, Clone)]
 pub struct WafCookies {
-    pub provider_id: String,
+    /// Provider domain, or domain plus account for account-specific cookies
+    pub cache_key: String,
     pub cookies: HashMap<String, String>,
     pub fetched_at: DateTime<Utc>,
     pub expires_at: DateTime<Utc>,
@@ -17,24 +18,64 @@ impl WafCookies {
     pub fn is_valid(&self) -> bool {
         Utc::now() < self.expires_at
     }
+
+    /// Cache key shared by all accounts of a provider domain
+    ///
+    /// WAF cookies such as `acw_tc` are issued to the browser, not to the user,
+    /// so every account on the same domain can reuse them.
+    pub fn domain_key(domain: &str) -> String {
+        domain.trim().trim_end_matches('/').to_ascii_lowercase()
+    }
+
+    /// Cache key of a single account, for providers whose WAF cookies are account-specific
+    pub fn account_key(domain: &str, account_id: &str) -> String {
+        format!("{}#{}", Self::domain_key(domain), account_id)
+    }
 }
 
 /// Repository trait for WAF cookies
 #[async_trait]
 pub trait WafCookiesRepository: Send + Sync {
-    /// Save or update WAF cookies for a provider
+    /// Save or update WAF cookies under a cache key
     async fn save(
         &self,
-        provider_id: &str,
+        cache_key: &str,
         cookies: &HashMap<String, String>,
     ) -> Result<(), DomainError>;
 
-    /// Get valid (non-expired) WAF cookies for a provider
-    async fn get_valid(&self, provider_id: &str) -> Result<Option<WafCookies>, DomainError>;
+    /// Get valid (non-expired) WAF cookies stored under a cache key
+    async fn get_valid(&self, cache_key: &str) -> Result<Option<WafCookies>, DomainError>;
 
-    /// Delete WAF cookies for a provider
-    async fn delete(&self, provider_id: &str) -> Result<(), DomainError>;
+    /// Delete WAF cookies stored under a cache key
+    async fn delete(&self, cache_key: &str) -> Result<(), DomainError>;
 
     /// Clean up all expired WAF cookies
     async fn cleanup_expired(&self) -> Result<u64, DomainError>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_domain_key_normalizes_domain() {
+        assert_eq!(
+            WafCookies::domain_key("https://AnyRouter.top/"),
+            "https://anyrouter.top"
+        );
+        assert_eq!(
+            WafCookies::domain_key("https://anyrouter.top"),
+            WafCookies::domain_key(" https://anyrouter.top/ ")
+        );
+    }
+
+    #[test]
+    fn test_account_key_is_scoped_to_domain_and_account() {
+        let first = WafCookies::account_key("https://anyrouter.top/", "account-1");
+        let second = WafCookies::account_key("https://anyrouter.top", "account-2");
+
+        assert_eq!(first, "https://anyrouter.top#account-1");
+        assert_ne!(first, second);
+        assert_ne!(first, WafCookies::domain_key("https://anyrouter.top"));
+    }
+}
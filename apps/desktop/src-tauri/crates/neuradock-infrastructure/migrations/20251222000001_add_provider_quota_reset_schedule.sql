@@ -0,0 +1,7 @@
+-- Add daily quota reset schedule to providers (JSON: {"reset_time":"HH:MM","utc_offset":"+HH:MM"})
+ALTER TABLE providers ADD COLUMN quota_reset_schedule TEXT;
+
+-- Built-in providers reset quotas at midnight Beijing time
+UPDATE providers
+SET quota_reset_schedule = '{"reset_time":"00:00","utc_offset":"+08:00"}'
+WHERE is_builtin = 1 AND quota_reset_schedule IS NULL;
@@ -0,0 +1,2 @@
+-- Allow pinning favorite accounts to the top of the account list
+ALTER TABLE accounts ADD COLUMN pinned BOOLEAN NOT NULL DEFAULT 0;
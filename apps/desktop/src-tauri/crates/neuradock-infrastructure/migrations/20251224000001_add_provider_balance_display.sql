@@ -0,0 +1,2 @@
+-- Provider-level display metadata for balances (currency symbol, unit label, decimals)
+ALTER TABLE providers ADD COLUMN balance_display TEXT;
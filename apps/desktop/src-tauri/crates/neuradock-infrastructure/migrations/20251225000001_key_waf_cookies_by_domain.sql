@@ -0,0 +1,7 @@
+-- WAF cookies are shared per provider domain (or per account when configured),
+-- so the cache is keyed by a cache key instead of the provider id.
+-- Entries keyed by provider id cannot be mapped to a domain here. They are only a cache, so drop them.
+DELETE FROM waf_cookies;
+ALTER TABLE waf_cookies RENAME COLUMN provider_id TO cache_key;
+DROP INDEX IF EXISTS idx_waf_cookies_provider;
+CREATE INDEX IF NOT EXISTS idx_waf_cookies_cache_key ON waf_cookies(cache_key);
@@ -0,0 +1,14 @@
+-- Persistent queue of check-ins that failed with a recoverable error.
+-- One entry per account, drained by a background worker with backoff.
+CREATE TABLE IF NOT EXISTS check_in_retry_queue (
+    account_id TEXT PRIMARY KEY,
+    attempts INTEGER NOT NULL DEFAULT 1,
+    next_attempt_at TEXT NOT NULL,
+    last_error TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending',  -- pending or failed
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (account_id) REFERENCES accounts(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_check_in_retry_queue_due ON check_in_retry_queue(status, next_attempt_at);
@@ -0,0 +1,10 @@
+-- Per-account cap on check-in attempts (including retries) per day, 0 means no limit
+ALTER TABLE accounts ADD COLUMN max_attempts_per_day INTEGER NOT NULL DEFAULT 0;
+
+-- Check-in attempts made on the current day, one row per account
+CREATE TABLE IF NOT EXISTS check_in_attempts (
+    account_id TEXT PRIMARY KEY,
+    day TEXT NOT NULL,  -- local date (YYYY-MM-DD) the counter belongs to
+    attempts INTEGER NOT NULL DEFAULT 0,
+    FOREIGN KEY (account_id) REFERENCES accounts(id) ON DELETE CASCADE
+);
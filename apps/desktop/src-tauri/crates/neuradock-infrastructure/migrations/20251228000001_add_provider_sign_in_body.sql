@@ -0,0 +1,2 @@
+-- Optional JSON body POSTed to the sign-in endpoint (NULL = no body)
+ALTER TABLE providers ADD COLUMN sign_in_body TEXT;
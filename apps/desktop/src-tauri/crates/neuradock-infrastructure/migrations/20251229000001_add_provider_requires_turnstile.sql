@@ -0,0 +1,2 @@
+-- Providers whose check-in needs a Cloudflare Turnstile token solved in a browser
+ALTER TABLE providers ADD COLUMN requires_turnstile INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,2 @@
+-- Where a provider reports the check-in reward, JSON encoded RewardExtraction (NULL = not parsed)
+ALTER TABLE providers ADD COLUMN reward_extraction TEXT;
@@ -0,0 +1,12 @@
+-- Shadow columns holding credentials re-encrypted under the next key while the
+-- encryption key is rotated (NULL outside of a rotation)
+ALTER TABLE accounts ADD COLUMN cookies_rotated TEXT;
+ALTER TABLE accounts ADD COLUMN api_user_rotated TEXT;
+ALTER TABLE independent_api_keys ADD COLUMN api_key_rotated TEXT;
+
+-- Salt of a committed key rotation, kept until it replaces the salt file
+CREATE TABLE IF NOT EXISTS encryption_key_rotation (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    salt BLOB NOT NULL,
+    rotated_at TEXT NOT NULL
+);
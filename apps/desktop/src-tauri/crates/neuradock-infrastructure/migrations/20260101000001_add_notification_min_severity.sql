@@ -0,0 +1,2 @@
+-- Lowest ErrorSeverity sent through a channel: info, warning, error or critical
+ALTER TABLE notification_channels ADD COLUMN min_severity TEXT NOT NULL DEFAULT 'info';
@@ -0,0 +1,6 @@
+-- accounts.last_check_in now holds the newest balance_history.recorded_at of the account,
+-- kept up to date when history is written so account reads no longer aggregate the history
+UPDATE accounts
+SET last_check_in = (
+    SELECT MAX(recorded_at) FROM balance_history WHERE balance_history.account_id = accounts.id
+);
@@ -0,0 +1,5 @@
+-- When the account was deleted from the list, NULL while it is active.
+-- Deleted accounts are kept for an undo window and purged afterwards
+ALTER TABLE accounts ADD COLUMN deleted_at TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_accounts_deleted_at ON accounts(deleted_at);
@@ -0,0 +1,2 @@
+-- Per-channel content templates as JSON, NULL uses the global or built-in content
+ALTER TABLE notification_channels ADD COLUMN templates TEXT;
@@ -0,0 +1,2 @@
+-- Language of a channel's messages: zh-CN or en-US, NULL follows the app setting
+ALTER TABLE notification_channels ADD COLUMN locale TEXT;
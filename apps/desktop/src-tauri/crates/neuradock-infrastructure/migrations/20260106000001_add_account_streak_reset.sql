@@ -0,0 +1,3 @@
+-- Day the account's streak was reset on, NULL when streaks follow the whole history.
+-- Check-ins up to and including that day do not count towards streaks
+ALTER TABLE accounts ADD COLUMN streak_reset_on TEXT;
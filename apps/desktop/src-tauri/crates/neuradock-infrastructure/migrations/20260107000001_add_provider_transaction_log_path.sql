@@ -0,0 +1,2 @@
+-- Usage/transaction log endpoint used to backfill balance history (NULL = not available)
+ALTER TABLE providers ADD COLUMN transaction_log_path TEXT;
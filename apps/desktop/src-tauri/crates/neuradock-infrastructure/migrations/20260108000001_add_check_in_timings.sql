@@ -0,0 +1,18 @@
+-- Per-run phase timings of the check-in pipeline, in milliseconds.
+-- The latest runs of each account are kept for the performance panel.
+CREATE TABLE IF NOT EXISTS check_in_timings (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    account_id TEXT NOT NULL,
+    provider_id TEXT NOT NULL,
+    started_at TEXT NOT NULL,
+    success INTEGER NOT NULL,
+    cookie_preparation_ms INTEGER NOT NULL DEFAULT 0,
+    waf_refresh_ms INTEGER NOT NULL DEFAULT 0,
+    user_info_ms INTEGER NOT NULL DEFAULT 0,
+    check_in_request_ms INTEGER NOT NULL DEFAULT 0,
+    balance_fetch_ms INTEGER NOT NULL DEFAULT 0,
+    total_ms INTEGER NOT NULL,
+    FOREIGN KEY (account_id) REFERENCES accounts(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_check_in_timings_account ON check_in_timings(account_id, started_at DESC);
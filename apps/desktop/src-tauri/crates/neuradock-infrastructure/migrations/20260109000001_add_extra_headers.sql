@@ -0,0 +1,7 @@
+-- Static headers added to user info and check-in requests, as a JSON object (NULL = none)
+ALTER TABLE providers ADD COLUMN extra_headers TEXT;
+
+-- Per-account headers such as an Authorization token, encrypted like the cookies,
+-- with the shadow column used while the encryption key is rotated
+ALTER TABLE accounts ADD COLUMN extra_headers TEXT;
+ALTER TABLE accounts ADD COLUMN extra_headers_rotated TEXT;
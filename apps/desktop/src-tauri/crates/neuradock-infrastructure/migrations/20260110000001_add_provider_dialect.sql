@@ -0,0 +1,2 @@
+-- API fork a provider runs, selecting how its responses are parsed (new_api or one_api)
+ALTER TABLE providers ADD COLUMN dialect TEXT NOT NULL DEFAULT 'new_api';
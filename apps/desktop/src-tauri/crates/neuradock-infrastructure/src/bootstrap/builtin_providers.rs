@@ -1,10 +1,13 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::Arc;
 
-use neuradock_domain::check_in::{Provider, ProviderConfig, ProviderRepository};
+use neuradock_domain::check_in::{
+    BalanceDisplay, Provider, ProviderConfig, ProviderDialect, ProviderRepository,
+    QuotaResetSchedule, RewardExtraction,
+};
 use neuradock_domain::custom_node::{CustomProviderNode, CustomProviderNodeRepository};
-use neuradock_domain::shared::DomainError;
 use neuradock_domain::shared::ProviderId;
+use neuradock_domain::shared::{DomainError, ExtraHeaders};
 use serde::Deserialize;
 use tracing::info;
 
@@ -22,13 +25,42 @@ struct BuiltinProviderConfig {
     default_nodes: Option<Vec<BuiltinProviderNodeConfig>>,
     login_path: String,
     sign_in_path: Option<String>,
+    sign_in_body: Option<serde_json::Value>,
     user_info_path: String,
     token_api_path: Option<String>,
     models_path: Option<String>,
+    transaction_log_path: Option<String>,
     api_user_key: String,
+    extra_headers: Option<BTreeMap<String, String>>,
+    /// `new_api` (default) or `one_api`
+    dialect: Option<String>,
     bypass_method: Option<String>,
+    requires_turnstile: Option<bool>,
     supports_check_in: Option<bool>,
     check_in_bugged: Option<bool>,
+    quota_reset_schedule: Option<BuiltinQuotaResetConfig>,
+    balance_display: Option<BuiltinBalanceDisplayConfig>,
+    reward_extraction: Option<BuiltinRewardExtractionConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BuiltinQuotaResetConfig {
+    reset_time: String,
+    utc_offset: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BuiltinBalanceDisplayConfig {
+    currency_symbol: String,
+    unit_label: String,
+    decimal_places: u8,
+}
+
+#[derive(Debug, Deserialize)]
+struct BuiltinRewardExtractionConfig {
+    json_path: Option<String>,
+    message_pattern: Option<String>,
+    quota_per_unit: Option<f64>,
 }
 
 fn builtin_provider_configs() -> Result<Vec<BuiltinProviderConfig>, DomainError> {
@@ -75,6 +107,41 @@ pub async fn seed_builtin_providers(
     let mut seeded_count = 0;
     for config in configs.iter() {
         if !existing_ids.contains(&config.id) {
+            let quota_reset_schedule = config
+                .quota_reset_schedule
+                .as_ref()
+                .map(|reset| QuotaResetSchedule::new(&reset.reset_time, &reset.utc_offset))
+                .transpose()?;
+            let balance_display = config
+                .balance_display
+                .as_ref()
+                .map(|display| {
+                    BalanceDisplay::new(
+                        &display.currency_symbol,
+                        &display.unit_label,
+                        display.decimal_places,
+                    )
+                })
+                .transpose()?;
+            let reward_extraction = config
+                .reward_extraction
+                .as_ref()
+                .map(|reward| {
+                    RewardExtraction::new(
+                        reward.json_path.clone(),
+                        reward.message_pattern.clone(),
+                        reward.quota_per_unit.unwrap_or(1.0),
+                    )
+                })
+                .transpose()?;
+            let extra_headers =
+                ExtraHeaders::new(config.extra_headers.clone().unwrap_or_default())?;
+            let dialect = config
+                .dialect
+                .as_deref()
+                .map(ProviderDialect::parse)
+                .transpose()?
+                .unwrap_or_default();
             let provider = Provider::builtin(
                 &config.id,
                 ProviderConfig {
@@ -82,13 +149,21 @@ pub async fn seed_builtin_providers(
                     domain: config.domain.clone(),
                     login_path: config.login_path.clone(),
                     sign_in_path: config.sign_in_path.clone(),
+                    sign_in_body: config.sign_in_body.clone(),
                     user_info_path: config.user_info_path.clone(),
                     token_api_path: config.token_api_path.clone(),
                     models_path: config.models_path.clone(),
+                    transaction_log_path: config.transaction_log_path.clone(),
                     api_user_key: config.api_user_key.clone(),
+                    extra_headers,
+                    dialect,
                     bypass_method: config.bypass_method.clone(),
+                    requires_turnstile: config.requires_turnstile.unwrap_or(false),
                     supports_check_in: config.supports_check_in.unwrap_or(true),
                     check_in_bugged: config.check_in_bugged.unwrap_or(false),
+                    quota_reset_schedule,
+                    balance_display,
+                    reward_extraction,
                 },
             );
             provider_repo.save(&provider).await?;
@@ -0,0 +1,58 @@
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+/// User-facing browser settings used by the WAF bypass service
+#[derive(Debug, Clone, Default)]
+pub struct BrowserSettings {
+    /// Browser executable configured by the user, preferred over auto-detection
+    pub browser_path: Option<PathBuf>,
+    /// Reuse a per-provider browser profile instead of a fresh temp profile
+    pub persistent_profile: bool,
+    /// Root directory of persistent browser profiles (under the app data directory)
+    pub profiles_dir: Option<PathBuf>,
+    /// Validated user flags appended to the browser command line
+    pub extra_args: Vec<String>,
+}
+
+impl BrowserSettings {
+    /// Get a snapshot of the global browser settings
+    pub fn global() -> Self {
+        GLOBAL_BROWSER_SETTINGS
+            .read()
+            .map(|settings| settings.clone())
+            .unwrap_or_default()
+    }
+
+    /// Update the global browser settings in place
+    pub fn update(f: impl FnOnce(&mut BrowserSettings)) {
+        match GLOBAL_BROWSER_SETTINGS.write() {
+            Ok(mut settings) => f(&mut settings),
+            Err(poisoned) => f(&mut poisoned.into_inner()),
+        }
+    }
+}
+
+/// Global browser settings instance, populated from the persisted app config at startup
+static GLOBAL_BROWSER_SETTINGS: RwLock<BrowserSettings> = RwLock::new(BrowserSettings {
+    browser_path: None,
+    persistent_profile: false,
+    profiles_dir: None,
+    extra_args: Vec::new(),
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_global_settings() {
+        BrowserSettings::update(|s| s.browser_path = Some(PathBuf::from("/opt/chrome/chrome")));
+        assert_eq!(
+            BrowserSettings::global().browser_path,
+            Some(PathBuf::from("/opt/chrome/chrome"))
+        );
+
+        BrowserSettings::update(|s| s.browser_path = None);
+        assert!(BrowserSettings::global().browser_path.is_none());
+    }
+}
@@ -0,0 +1,39 @@
+use std::sync::RwLock;
+
+/// User-facing settings of the provider HTTP client
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpSettings {
+    /// Largest response body read from a provider, bigger ones are rejected
+    pub max_response_body_bytes: usize,
+}
+
+impl HttpSettings {
+    pub const DEFAULT: Self = Self {
+        max_response_body_bytes: 10 * 1024 * 1024,
+    };
+
+    /// Get a snapshot of the global HTTP settings
+    pub fn global() -> Self {
+        GLOBAL_HTTP_SETTINGS
+            .read()
+            .map(|settings| settings.clone())
+            .unwrap_or_default()
+    }
+
+    /// Update the global HTTP settings in place
+    pub fn update(f: impl FnOnce(&mut HttpSettings)) {
+        match GLOBAL_HTTP_SETTINGS.write() {
+            Ok(mut settings) => f(&mut settings),
+            Err(poisoned) => f(&mut poisoned.into_inner()),
+        }
+    }
+}
+
+impl Default for HttpSettings {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Global HTTP settings instance, populated from the persisted app config at startup
+static GLOBAL_HTTP_SETTINGS: RwLock<HttpSettings> = RwLock::new(HttpSettings::DEFAULT);
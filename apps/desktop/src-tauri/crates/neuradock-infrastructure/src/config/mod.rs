@@ -1,3 +1,9 @@
+pub mod browser;
+pub mod http;
 pub mod timeouts;
+pub mod waf;
 
+pub use browser::BrowserSettings;
+pub use http::HttpSettings;
 pub use timeouts::TimeoutConfig;
+pub use waf::{WafBypassTuning, WafSettings};
@@ -0,0 +1,80 @@
+use std::collections::BTreeSet;
+use std::sync::RwLock;
+use std::time::Duration;
+
+/// User-facing settings for WAF cookie handling
+#[derive(Debug, Clone, Default)]
+pub struct WafSettings {
+    /// Providers whose cached WAF cookies are used without a validation request
+    pub skip_validation_providers: BTreeSet<String>,
+    /// Providers whose WAF cookies are cached per account instead of per domain
+    pub per_account_cookie_providers: BTreeSet<String>,
+    /// Browser timing used while waiting for the WAF challenge to set its cookies
+    pub bypass: WafBypassTuning,
+}
+
+/// Timing of a browser WAF bypass attempt and parallelism of batched ones
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WafBypassTuning {
+    /// Maximum time for the login page navigation itself
+    pub navigation_timeout: Duration,
+    /// Delay after the page loaded before the first cookie read
+    pub settle_delay: Duration,
+    /// Deadline for the whole attempt, from navigation until the cookies are read
+    pub max_total_time: Duration,
+    /// Cookie names to wait for (empty = the built-in WAF cookie names)
+    pub wait_for_cookies: Vec<String>,
+    /// Tabs solving challenges at the same time when a batch shares one browser
+    pub tab_concurrency: usize,
+}
+
+impl WafBypassTuning {
+    pub const DEFAULT: Self = Self {
+        navigation_timeout: Duration::from_secs(20),
+        settle_delay: Duration::from_secs(2),
+        max_total_time: Duration::from_secs(30),
+        wait_for_cookies: Vec::new(),
+        tab_concurrency: 3,
+    };
+}
+
+impl Default for WafBypassTuning {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl WafSettings {
+    /// Get a snapshot of the global WAF settings
+    pub fn global() -> Self {
+        GLOBAL_WAF_SETTINGS
+            .read()
+            .map(|settings| settings.clone())
+            .unwrap_or_default()
+    }
+
+    /// Update the global WAF settings in place
+    pub fn update(f: impl FnOnce(&mut WafSettings)) {
+        match GLOBAL_WAF_SETTINGS.write() {
+            Ok(mut settings) => f(&mut settings),
+            Err(poisoned) => f(&mut poisoned.into_inner()),
+        }
+    }
+
+    /// Whether cached WAF cookies of a provider should be validated before use
+    pub fn should_validate(&self, provider_id: &str) -> bool {
+        !self.skip_validation_providers.contains(provider_id)
+    }
+
+    /// Whether WAF cookies of a provider are account-specific and must not be shared
+    pub fn per_account_cookies(&self, provider_id: &str) -> bool {
+        self.per_account_cookie_providers.contains(provider_id)
+    }
+}
+
+/// Global WAF settings instance, populated from the persisted app config at startup
+static GLOBAL_WAF_SETTINGS: RwLock<WafSettings> = RwLock::new(WafSettings {
+    skip_validation_providers: BTreeSet::new(),
+    per_account_cookie_providers: BTreeSet::new(),
+    bypass: WafBypassTuning::DEFAULT,
+});
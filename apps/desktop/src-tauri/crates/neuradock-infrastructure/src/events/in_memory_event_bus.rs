@@ -1,6 +1,7 @@
 use async_trait::async_trait;
 use log::{error, info};
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
@@ -13,6 +14,8 @@ use neuradock_domain::shared::DomainError;
 /// In production, you might want to use a message queue for better scalability
 pub struct InMemoryEventBus {
     handlers: Arc<RwLock<EventHandlers>>,
+    /// Events being dispatched to their handlers
+    pending: AtomicUsize,
 }
 
 type EventHandlers = HashMap<String, Vec<Arc<dyn DynamicEventHandler>>>;
@@ -21,6 +24,7 @@ impl InMemoryEventBus {
     pub fn new() -> Self {
         Self {
             handlers: Arc::new(RwLock::new(HashMap::new())),
+            pending: AtomicUsize::new(0),
         }
     }
 
@@ -47,6 +51,11 @@ impl InMemoryEventBus {
         let handlers = self.handlers.read().await;
         handlers.get(event_type_name).map_or(0, |h| h.len())
     }
+
+    /// Events published but not yet handled by all their handlers
+    pub fn pending_events(&self) -> usize {
+        self.pending.load(Ordering::Relaxed)
+    }
 }
 
 impl Default for InMemoryEventBus {
@@ -61,6 +70,7 @@ impl EventBus for InMemoryEventBus {
         let event_type_name = event.event_type_name();
 
         info!("Publishing event: {}", event_type_name);
+        self.pending.fetch_add(1, Ordering::Relaxed);
 
         // Clone the handlers list and release lock immediately to avoid blocking
         // This prevents long-running handlers from blocking subscribe/publish operations
@@ -95,6 +105,7 @@ impl EventBus for InMemoryEventBus {
             info!("No handlers registered for event type: {}", event_type_name);
         }
 
+        self.pending.fetch_sub(1, Ordering::Relaxed);
         Ok(())
     }
 }
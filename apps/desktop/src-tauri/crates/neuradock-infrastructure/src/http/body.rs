@@ -0,0 +1,122 @@
+use reqwest::Response;
+use thiserror::Error;
+
+use crate::config::HttpSettings;
+
+/// A response body over the configured size limit
+#[derive(Debug, Error)]
+#[error("Response too large: body exceeds {limit} bytes")]
+pub struct ResponseTooLarge {
+    pub limit: usize,
+}
+
+/// Read a response body as text, up to [`HttpSettings::max_response_body_bytes`]
+///
+/// Used instead of `Response::text` wherever a provider response is read, so
+/// a misbehaving endpoint cannot make the app buffer an unbounded body.
+pub async fn read_text(response: Response) -> anyhow::Result<String> {
+    read_text_limited(response, HttpSettings::global().max_response_body_bytes).await
+}
+
+/// Read a response body as text, failing with [`ResponseTooLarge`] beyond `limit` bytes
+///
+/// A declared `Content-Length` over the limit is rejected before anything is
+/// read, otherwise the body is read chunk by chunk until it passes the limit.
+pub async fn read_text_limited(mut response: Response, limit: usize) -> anyhow::Result<String> {
+    if response
+        .content_length()
+        .is_some_and(|length| length > limit as u64)
+    {
+        return Err(ResponseTooLarge { limit }.into());
+    }
+
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        if body.len() + chunk.len() > limit {
+            return Err(ResponseTooLarge { limit }.into());
+        }
+        body.extend_from_slice(&chunk);
+    }
+
+    Ok(String::from_utf8_lossy(&body).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Serve one response with `body`, chunked when `content_length` is false
+    async fn serve_once(body: Vec<u8>, content_length: bool) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = [0u8; 1024];
+            let _ = socket.read(&mut request).await;
+
+            let mut response = if content_length {
+                format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n", body.len()).into_bytes()
+            } else {
+                let mut response =
+                    b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n".to_vec();
+                for chunk in body.chunks(1024) {
+                    response.extend_from_slice(format!("{:x}\r\n", chunk.len()).as_bytes());
+                    response.extend_from_slice(chunk);
+                    response.extend_from_slice(b"\r\n");
+                }
+                response.extend_from_slice(b"0\r\n\r\n");
+                response
+            };
+            if content_length {
+                response.extend_from_slice(&body);
+            }
+            let _ = socket.write_all(&response).await;
+        });
+        format!("http://{}/", addr)
+    }
+
+    async fn fetch(url: &str) -> Response {
+        reqwest::Client::builder()
+            .no_proxy()
+            .build()
+            .unwrap()
+            .get(url)
+            .send()
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_body_within_limit_is_read() {
+        let url = serve_once(br#"{"data":{"quota":1}}"#.to_vec(), true).await;
+
+        let text = read_text_limited(fetch(&url).await, 1024).await.unwrap();
+
+        assert_eq!(text, r#"{"data":{"quota":1}}"#);
+    }
+
+    #[tokio::test]
+    async fn test_declared_length_over_limit_is_rejected() {
+        let url = serve_once(vec![b'x'; 4096], true).await;
+
+        let err = read_text_limited(fetch(&url).await, 1024)
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.downcast_ref::<ResponseTooLarge>().unwrap().limit, 1024);
+        assert!(err.to_string().contains("Response too large"));
+    }
+
+    #[tokio::test]
+    async fn test_chunked_body_over_limit_is_rejected() {
+        let url = serve_once(vec![b'x'; 8192], false).await;
+
+        let err = read_text_limited(fetch(&url).await, 2048)
+            .await
+            .unwrap_err();
+
+        assert!(err.downcast_ref::<ResponseTooLarge>().is_some());
+    }
+}
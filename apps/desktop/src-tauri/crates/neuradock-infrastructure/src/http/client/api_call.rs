@@ -2,6 +2,8 @@ use anyhow::{Context, Result};
 use reqwest::header;
 use std::collections::HashMap;
 
+use crate::http::body::read_text;
+
 impl super::HttpClient {
     /// Call API endpoint with GET request (for triggering balance updates)
     pub async fn call_api_endpoint(
@@ -56,7 +58,7 @@ impl super::HttpClient {
         log::info!("API endpoint response status: {}", status);
 
         // Get response text to check for WAF challenge
-        let response_text = response.text().await.unwrap_or_else(|_| String::new());
+        let response_text = read_text(response).await.unwrap_or_else(|_| String::new());
 
         // Check for WAF challenge
         if response_text.contains("acw_sc__v2") || response_text.contains("<script>var arg1=") {
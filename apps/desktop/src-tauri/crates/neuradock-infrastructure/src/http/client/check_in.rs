@@ -2,16 +2,27 @@ use anyhow::{Context, Result};
 use reqwest::header;
 use std::collections::HashMap;
 
-use super::types::{extract_domain, CheckInResult};
+use neuradock_domain::check_in::ProviderDialect;
+use neuradock_domain::shared::ExtraHeaders;
+
+use crate::http::body::read_text;
+use crate::http::capture::{CapturedRequest, RequestCapture};
+
+use super::dialect::dialect_for;
+use super::types::{apply_extra_headers, extract_domain, CheckInResult};
 
 impl super::HttpClient {
     /// Execute check-in with retry logic
+    #[allow(clippy::too_many_arguments)]
     pub async fn execute_check_in(
         &self,
         url: &str,
         cookies: &HashMap<String, String>,
         api_user_key: &str,
         api_user_value: &str,
+        extra_headers: &ExtraHeaders,
+        dialect: ProviderDialect,
+        body: Option<&serde_json::Value>,
     ) -> Result<CheckInResult> {
         const MAX_RETRIES: u32 = 3;
         const INITIAL_DELAY_MS: u64 = 1000;
@@ -31,7 +42,15 @@ impl super::HttpClient {
             }
 
             match self
-                .execute_check_in_once(url, cookies, api_user_key, api_user_value)
+                .execute_check_in_once(
+                    url,
+                    cookies,
+                    api_user_key,
+                    api_user_value,
+                    extra_headers,
+                    dialect,
+                    body,
+                )
                 .await
             {
                 Ok(result) => return Ok(result),
@@ -47,57 +66,30 @@ impl super::HttpClient {
     }
 
     /// Execute check-in once (internal method)
+    #[allow(clippy::too_many_arguments)]
     async fn execute_check_in_once(
         &self,
         url: &str,
         cookies: &HashMap<String, String>,
         api_user_key: &str,
         api_user_value: &str,
+        extra_headers: &ExtraHeaders,
+        dialect: ProviderDialect,
+        body: Option<&serde_json::Value>,
     ) -> Result<CheckInResult> {
-        // Build headers
-        let mut headers = header::HeaderMap::new();
-        headers.insert(
-            header::CONTENT_TYPE,
-            header::HeaderValue::from_static("application/json"),
-        );
-        headers.insert(
-            header::ACCEPT,
-            header::HeaderValue::from_static("application/json, text/plain, */*"),
-        );
-        headers.insert(
-            header::HeaderName::from_static("x-requested-with"),
-            header::HeaderValue::from_static("XMLHttpRequest"),
-        );
-        headers.insert(
-            header::REFERER,
-            header::HeaderValue::from_str(&extract_domain(url)?)?,
-        );
-        headers.insert(
-            header::ORIGIN,
-            header::HeaderValue::from_str(&extract_domain(url)?)?,
-        );
-
-        // Add API user header
-        if !api_user_value.is_empty() {
-            headers.insert(
-                header::HeaderName::from_bytes(api_user_key.as_bytes())?,
-                header::HeaderValue::from_str(api_user_value)?,
-            );
-        }
-
-        // Build request with cookies
-        let mut request = self.client.post(url).headers(headers);
-
-        // Add cookies as header string
-        let cookie_string = cookies
-            .iter()
-            .map(|(k, v)| format!("{}={}", k, v))
-            .collect::<Vec<_>>()
-            .join("; ");
-
-        if !cookie_string.is_empty() {
-            request = request.header(header::COOKIE, cookie_string);
-        }
+        let request = self.build_check_in_request(
+            url,
+            cookies,
+            api_user_key,
+            api_user_value,
+            extra_headers,
+            body,
+        )?;
+        let capture = RequestCapture::global();
+        let captured = capture
+            .is_active()
+            .then(|| CapturedRequest::from_builder(&request))
+            .flatten();
 
         // Send request
         let response = request
@@ -110,10 +102,10 @@ impl super::HttpClient {
         log::info!("Check-in response status: {}", status);
 
         if !status.is_success() {
-            let error_text = response
-                .text()
+            let error_text = read_text(response)
                 .await
                 .unwrap_or_else(|_| "Unable to read response".to_string());
+            capture.record("check_in", captured, status.as_u16(), &error_text);
             // Check if this is a WAF challenge
             if error_text.contains("acw_sc__v2") || error_text.contains("<script>var arg1=") {
                 anyhow::bail!(
@@ -134,7 +126,10 @@ impl super::HttpClient {
         }
 
         // Parse response
-        let text = response.text().await?;
+        let text = read_text(response)
+            .await
+            .context("Failed to read check-in response")?;
+        capture.record("check_in", captured, status.as_u16(), &text);
 
         // Check if response is HTML (WAF challenge page)
         if text.trim().starts_with('<')
@@ -151,33 +146,11 @@ impl super::HttpClient {
             );
         }
 
-        // Log full response for debugging
-        log::info!("Check-in response body: {}", text);
+        log::debug!("Check-in response body: {}", text);
 
         // Try to parse as JSON
         if let Ok(data) = serde_json::from_str::<serde_json::Value>(&text) {
-            log::info!(
-                "Parsed JSON response: {}",
-                serde_json::to_string_pretty(&data).unwrap_or_default()
-            );
-
-            // Check for success indicators with more detailed logging
-            let ret_value = data["ret"].as_i64();
-            let code_value = data["code"].as_i64();
-            let success_value = data["success"].as_bool();
-
-            log::info!(
-                "Success indicators - ret: {:?}, code: {:?}, success: {:?}",
-                ret_value,
-                code_value,
-                success_value
-            );
-
-            // Updated success check to handle more cases
-            let success = ret_value == Some(1)
-                || code_value == Some(0)
-                || code_value == Some(200)
-                || success_value == Some(true);
+            let success = dialect_for(dialect).is_check_in_success(&data);
 
             let message = if success {
                 data["msg"]
@@ -196,9 +169,16 @@ impl super::HttpClient {
                 error_msg.to_string()
             };
 
-            Ok(CheckInResult { success, message })
+            Ok(CheckInResult {
+                success,
+                message,
+                response: Some(data),
+            })
         } else {
-            log::warn!("Failed to parse as JSON, raw response: {}", text);
+            log::warn!(
+                "Failed to parse as JSON, raw response: {}",
+                text.chars().take(500).collect::<String>()
+            );
 
             // If not JSON, check if response contains "success"
             let success = text.to_lowercase().contains("success");
@@ -215,7 +195,157 @@ impl super::HttpClient {
                 )
             };
 
-            Ok(CheckInResult { success, message })
+            Ok(CheckInResult {
+                success,
+                message,
+                response: None,
+            })
         }
     }
+
+    /// Build the check-in POST request (headers, cookies and optional JSON body)
+    fn build_check_in_request(
+        &self,
+        url: &str,
+        cookies: &HashMap<String, String>,
+        api_user_key: &str,
+        api_user_value: &str,
+        extra_headers: &ExtraHeaders,
+        body: Option<&serde_json::Value>,
+    ) -> Result<reqwest::RequestBuilder> {
+        // Build headers
+        let mut headers = header::HeaderMap::new();
+        headers.insert(
+            header::CONTENT_TYPE,
+            header::HeaderValue::from_static("application/json"),
+        );
+        headers.insert(
+            header::ACCEPT,
+            header::HeaderValue::from_static("application/json, text/plain, */*"),
+        );
+        headers.insert(
+            header::HeaderName::from_static("x-requested-with"),
+            header::HeaderValue::from_static("XMLHttpRequest"),
+        );
+        headers.insert(
+            header::REFERER,
+            header::HeaderValue::from_str(&extract_domain(url)?)?,
+        );
+        headers.insert(
+            header::ORIGIN,
+            header::HeaderValue::from_str(&extract_domain(url)?)?,
+        );
+
+        apply_extra_headers(&mut headers, extra_headers);
+
+        // Add API user header
+        if !api_user_value.is_empty() {
+            headers.insert(
+                header::HeaderName::from_bytes(api_user_key.as_bytes())?,
+                header::HeaderValue::from_str(api_user_value)?,
+            );
+        }
+
+        // Build request with cookies
+        let mut request = self.client.post(url).headers(headers);
+
+        // Some forks reject sign-in requests without a JSON body
+        if let Some(body) = body {
+            request = request.body(serde_json::to_vec(body)?);
+        }
+
+        // Add cookies as header string
+        let cookie_string = cookies
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, v))
+            .collect::<Vec<_>>()
+            .join("; ");
+
+        if !cookie_string.is_empty() {
+            request = request.header(header::COOKIE, cookie_string);
+        }
+
+        Ok(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::HttpClient;
+
+    fn build(body: Option<&serde_json::Value>) -> reqwest::Request {
+        build_with_headers(body, &ExtraHeaders::default())
+    }
+
+    fn build_with_headers(
+        body: Option<&serde_json::Value>,
+        extra_headers: &ExtraHeaders,
+    ) -> reqwest::Request {
+        let mut cookies = HashMap::new();
+        cookies.insert("session".to_string(), "abc".to_string());
+
+        HttpClient::new()
+            .unwrap()
+            .build_check_in_request(
+                "https://example.com/api/user/sign_in",
+                &cookies,
+                "new-api-user",
+                "42",
+                extra_headers,
+                body,
+            )
+            .unwrap()
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_check_in_request_sends_configured_body() {
+        let body = serde_json::json!({ "turnstile": "" });
+        let request = build(Some(&body));
+
+        assert_eq!(request.headers()[header::CONTENT_TYPE], "application/json");
+        assert_eq!(request.headers()["new-api-user"], "42");
+        let sent = request.body().and_then(|b| b.as_bytes()).unwrap();
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(sent).unwrap(),
+            body
+        );
+    }
+
+    #[test]
+    fn test_check_in_request_sends_empty_object_body() {
+        let body = serde_json::json!({});
+        let request = build(Some(&body));
+
+        assert_eq!(request.body().and_then(|b| b.as_bytes()), Some(&b"{}"[..]));
+    }
+
+    #[test]
+    fn test_check_in_request_without_body_by_default() {
+        let request = build(None);
+
+        assert_eq!(request.method(), reqwest::Method::POST);
+        assert_eq!(request.headers()[header::CONTENT_TYPE], "application/json");
+        assert!(request.body().is_none());
+    }
+
+    #[test]
+    fn test_check_in_request_sends_extra_headers() {
+        let extra_headers = ExtraHeaders::new(std::collections::BTreeMap::from([
+            ("Authorization".to_string(), "Bearer token".to_string()),
+            ("X-Requested-With".to_string(), "fetch".to_string()),
+            ("New-Api-User".to_string(), "ignored".to_string()),
+        ]))
+        .unwrap();
+
+        let request = build_with_headers(None, &extra_headers);
+
+        assert_eq!(request.headers()[header::AUTHORIZATION], "Bearer token");
+        // Extra headers replace defaults, the api_user header stays the account's
+        assert_eq!(request.headers()["x-requested-with"], "fetch");
+        assert_eq!(request.headers()["new-api-user"], "42");
+        assert_eq!(request.headers()[header::COOKIE], "session=abc");
+    }
 }
@@ -0,0 +1,207 @@
+use anyhow::Result;
+use serde_json::Value;
+
+use neuradock_domain::check_in::ProviderDialect;
+
+use super::types::UserInfo;
+
+/// How a provider's API fork reports balances and check-in results
+///
+/// Each `ProviderDialect` maps to one implementation, a fork with its own
+/// response shapes gets a new variant and implementation.
+pub trait Dialect: Send + Sync {
+    /// Raw quota units per balance unit
+    fn quota_per_unit(&self) -> f64;
+
+    /// Balance figures of a parsed user info response
+    fn parse_user_info(&self, data: &Value) -> Result<UserInfo>;
+
+    /// Whether a parsed check-in response reports success
+    fn is_check_in_success(&self, data: &Value) -> bool;
+
+    /// Raw quota in balance units, rounded to cents
+    fn scale_quota(&self, quota: f64) -> f64 {
+        (quota / self.quota_per_unit() * 100.0).round() / 100.0
+    }
+}
+
+/// Implementation of a provider dialect
+pub fn dialect_for(dialect: ProviderDialect) -> &'static dyn Dialect {
+    match dialect {
+        ProviderDialect::NewApi => &NewApiDialect,
+        ProviderDialect::OneApi => &OneApiDialect,
+    }
+}
+
+/// new-api: `data.quota` and `data.used_quota` at 500000 per dollar, check-in
+/// success reported as `ret: 1`, `code: 0`/`200` or `success: true`
+pub struct NewApiDialect;
+
+impl Dialect for NewApiDialect {
+    fn quota_per_unit(&self) -> f64 {
+        500000.0
+    }
+
+    fn parse_user_info(&self, data: &Value) -> Result<UserInfo> {
+        // Check if response has expected structure
+        if data["data"].is_null() {
+            anyhow::bail!("API response missing 'data' field: {}", data);
+        }
+        parse_quota_fields(self, &data["data"])
+    }
+
+    fn is_check_in_success(&self, data: &Value) -> bool {
+        let ret_value = data["ret"].as_i64();
+        let code_value = data["code"].as_i64();
+        let success_value = data["success"].as_bool();
+
+        log::info!(
+            "Success indicators - ret: {:?}, code: {:?}, success: {:?}",
+            ret_value,
+            code_value,
+            success_value
+        );
+
+        ret_value == Some(1)
+            || code_value == Some(0)
+            || code_value == Some(200)
+            || success_value == Some(true)
+    }
+}
+
+/// one-api: every response is `{"success", "message", "data"}` and `success`
+/// alone decides, quotas use the new-api scale
+pub struct OneApiDialect;
+
+impl Dialect for OneApiDialect {
+    fn quota_per_unit(&self) -> f64 {
+        500000.0
+    }
+
+    fn parse_user_info(&self, data: &Value) -> Result<UserInfo> {
+        if data["success"].as_bool() != Some(true) {
+            anyhow::bail!(
+                "API returned error: {}",
+                data["message"].as_str().unwrap_or("unknown error")
+            );
+        }
+        parse_quota_fields(self, &data["data"])
+    }
+
+    fn is_check_in_success(&self, data: &Value) -> bool {
+        data["success"].as_bool() == Some(true)
+    }
+}
+
+/// Balance figures from the `quota` and `used_quota` fields of a user object
+fn parse_quota_fields(dialect: &dyn Dialect, user: &Value) -> Result<UserInfo> {
+    let quota = user["quota"]
+        .as_f64()
+        .ok_or_else(|| anyhow::anyhow!("Missing or invalid 'quota' field in API response"))?;
+    let used_quota = user["used_quota"]
+        .as_f64()
+        .ok_or_else(|| anyhow::anyhow!("Missing or invalid 'used_quota' field in API response"))?;
+
+    let current_balance = dialect.scale_quota(quota);
+    let total_consumed = dialect.scale_quota(used_quota);
+
+    // NOTE: Upstream's HTTP payload still calls `quota`, `used_quota`, and `total_income`.
+    // We normalize semantics right here so the rest of the app only deals with
+    // `current_balance`, `total_consumed`, and `total_quota` to avoid confusion.
+    Ok(UserInfo {
+        current_balance,
+        total_consumed,
+        total_quota: current_balance + total_consumed,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// A fork reporting balances directly in dollars under `data.balance`
+    struct DollarDialect;
+
+    impl Dialect for DollarDialect {
+        fn quota_per_unit(&self) -> f64 {
+            1.0
+        }
+
+        fn parse_user_info(&self, data: &Value) -> Result<UserInfo> {
+            let balance = data["data"]["balance"]
+                .as_f64()
+                .ok_or_else(|| anyhow::anyhow!("Missing 'balance'"))?;
+            let used = data["data"]["used"].as_f64().unwrap_or(0.0);
+            Ok(UserInfo {
+                current_balance: self.scale_quota(balance),
+                total_consumed: self.scale_quota(used),
+                total_quota: self.scale_quota(balance + used),
+            })
+        }
+
+        fn is_check_in_success(&self, data: &Value) -> bool {
+            data["status"] == "ok"
+        }
+    }
+
+    #[test]
+    fn test_new_api_user_info_scales_quota() {
+        let info = dialect_for(ProviderDialect::NewApi)
+            .parse_user_info(&json!({"data": {"quota": 2500000, "used_quota": 250000}}))
+            .unwrap();
+
+        assert_eq!(info.current_balance, 5.0);
+        assert_eq!(info.total_consumed, 0.5);
+        assert_eq!(info.total_quota, 5.5);
+    }
+
+    #[test]
+    fn test_new_api_check_in_success_shapes() {
+        let dialect = dialect_for(ProviderDialect::NewApi);
+
+        assert!(dialect.is_check_in_success(&json!({"ret": 1})));
+        assert!(dialect.is_check_in_success(&json!({"code": 200})));
+        assert!(dialect.is_check_in_success(&json!({"success": true})));
+        assert!(!dialect.is_check_in_success(&json!({"ret": 0, "msg": "already"})));
+    }
+
+    #[test]
+    fn test_one_api_requires_success_flag() {
+        let dialect = dialect_for(ProviderDialect::OneApi);
+
+        assert!(!dialect.is_check_in_success(&json!({"ret": 1, "code": 0})));
+        assert!(dialect.is_check_in_success(&json!({"success": true, "message": ""})));
+
+        let err = dialect
+            .parse_user_info(&json!({"success": false, "message": "no permission", "data": null}))
+            .unwrap_err();
+        assert!(err.to_string().contains("no permission"));
+
+        let info = dialect
+            .parse_user_info(&json!({
+                "success": true,
+                "message": "",
+                "data": {"quota": 1000000, "used_quota": 0}
+            }))
+            .unwrap();
+        assert_eq!(info.current_balance, 2.0);
+    }
+
+    #[test]
+    fn test_custom_dialect_plugs_in() {
+        let dialects: [&dyn Dialect; 2] = [&NewApiDialect, &DollarDialect];
+        let responses = [
+            json!({"data": {"quota": 1500000, "used_quota": 500000}}),
+            json!({"data": {"balance": 3.0, "used": 1.0}}),
+        ];
+
+        for (dialect, response) in dialects.iter().zip(responses.iter()) {
+            let info = dialect.parse_user_info(response).unwrap();
+            assert_eq!(info.current_balance, 3.0);
+            assert_eq!(info.total_consumed, 1.0);
+        }
+        assert!(DollarDialect.is_check_in_success(&json!({"status": "ok"})));
+        assert!(!DollarDialect.is_check_in_success(&json!({"success": true})));
+    }
+}
@@ -1,9 +1,13 @@
 mod api_call;
 mod check_in;
+mod dialect;
+mod probe;
+mod transaction_log;
 mod types;
 mod user_info;
 mod visit;
 
+pub use dialect::{dialect_for, Dialect};
 pub use types::{CheckInResult, RetryConfig, UserInfo};
 
 use anyhow::{Context, Result};
@@ -0,0 +1,49 @@
+use anyhow::{Context, Result};
+use reqwest::header;
+use std::collections::HashMap;
+
+use neuradock_domain::check_in::WafChallengeKind;
+
+use crate::http::body::read_text;
+use crate::http::WafDetector;
+
+impl super::HttpClient {
+    /// Issue a single GET with the given cookies and report the WAF challenge page that came back, if any
+    pub async fn detect_waf_challenge(
+        &self,
+        url: &str,
+        cookies: &HashMap<String, String>,
+    ) -> Result<Option<WafChallengeKind>> {
+        let mut request = self.client.get(url).header(
+            header::ACCEPT,
+            "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8",
+        );
+
+        let cookie_string = cookies
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, v))
+            .collect::<Vec<_>>()
+            .join("; ");
+
+        if !cookie_string.is_empty() {
+            request = request.header(header::COOKIE, cookie_string);
+        }
+
+        let response = request
+            .send()
+            .await
+            .context("Failed to send WAF validation request")?;
+        let status = response.status();
+        let body = read_text(response)
+            .await
+            .context("Failed to read WAF validation response")?;
+
+        log::debug!(
+            "WAF validation response status: {}, length: {} bytes",
+            status,
+            body.len()
+        );
+
+        Ok(WafDetector::detect(&body))
+    }
+}
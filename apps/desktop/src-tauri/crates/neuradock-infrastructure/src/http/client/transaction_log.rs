@@ -0,0 +1,326 @@
+use anyhow::{Context, Result};
+use chrono::DateTime;
+use reqwest::{header, Client};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use neuradock_domain::balance_history::BalanceChange;
+
+use neuradock_domain::check_in::ProviderDialect;
+use neuradock_domain::shared::ExtraHeaders;
+
+use crate::http::body::read_text;
+
+use super::dialect::{dialect_for, Dialect};
+use super::types::{apply_extra_headers, extract_domain};
+
+/// Log entries requested per page
+const PAGE_SIZE: u32 = 100;
+
+/// Most pages fetched by one backfill, older entries are left out
+const MAX_PAGES: u32 = 50;
+
+/// new-api log types that change the balance
+const LOG_TYPE_TOP_UP: i32 = 1;
+const LOG_TYPE_CONSUME: i32 = 2;
+const LOG_TYPE_SYSTEM: i32 = 4;
+
+#[derive(Debug, Deserialize)]
+struct TransactionLogResponse {
+    success: bool,
+    #[serde(default)]
+    message: String,
+    data: Option<TransactionLogData>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum TransactionLogData {
+    /// new-api format: {"data": {"page": 1, "total": 2, "items": [...]}}
+    Paginated {
+        items: Vec<TransactionLogItem>,
+        total: Option<u32>,
+    },
+    /// Older format: {"data": [...]}
+    Direct(Vec<TransactionLogItem>),
+}
+
+#[derive(Debug, Deserialize)]
+struct TransactionLogItem {
+    /// Unix seconds
+    created_at: i64,
+    #[serde(rename = "type")]
+    log_type: i32,
+    #[serde(default)]
+    quota: i64,
+}
+
+/// One page of a transaction log
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct TransactionLogPage {
+    pub changes: Vec<BalanceChange>,
+    /// Log entries on the page, including ones that do not change the balance
+    pub entries: usize,
+    /// Total entries of the log, when reported
+    pub total: Option<u32>,
+}
+
+/// Parse a page of a new-api style transaction log
+///
+/// Top-ups and system entries (gifts, check-in rewards) count as income and
+/// consume entries as usage. Other entries and entries without a quota are skipped.
+/// Quotas are converted with the dialect's scale, the same as the user info.
+pub(super) fn parse_transaction_log_page(
+    text: &str,
+    dialect: &dyn Dialect,
+) -> Result<TransactionLogPage> {
+    let response: TransactionLogResponse = serde_json::from_str(text).context(format!(
+        "Failed to parse transaction log response: {}",
+        &text[..text.len().min(200)]
+    ))?;
+
+    if !response.success {
+        anyhow::bail!("API returned error: {}", response.message);
+    }
+
+    let (items, total) = match response.data {
+        Some(TransactionLogData::Paginated { items, total }) => (items, total),
+        Some(TransactionLogData::Direct(items)) => (items, None),
+        None => (Vec::new(), None),
+    };
+
+    let changes = items
+        .iter()
+        .filter(|item| item.quota > 0)
+        .filter_map(|item| {
+            let amount = item.quota as f64 / dialect.quota_per_unit();
+            let (income, consumed) = match item.log_type {
+                LOG_TYPE_TOP_UP | LOG_TYPE_SYSTEM => (amount, 0.0),
+                LOG_TYPE_CONSUME => (0.0, amount),
+                _ => return None,
+            };
+            Some(BalanceChange {
+                at: DateTime::from_timestamp(item.created_at, 0)?,
+                income,
+                consumed,
+            })
+        })
+        .collect();
+
+    Ok(TransactionLogPage {
+        changes,
+        entries: items.len(),
+        total,
+    })
+}
+
+impl super::HttpClient {
+    /// Get the balance changes of a user's transaction log, newest pages first
+    ///
+    /// Pages are fetched until the log ends or `MAX_PAGES` is reached.
+    pub async fn get_transaction_log(
+        &self,
+        url: &str,
+        cookies: &HashMap<String, String>,
+        api_user_key: &str,
+        api_user_value: &str,
+        extra_headers: &ExtraHeaders,
+        dialect: ProviderDialect,
+    ) -> Result<Vec<BalanceChange>> {
+        let mut changes = Vec::new();
+        let mut fetched = 0usize;
+
+        for page in 1..=MAX_PAGES {
+            let page_url = page_url(url, page)?;
+            let result = self
+                .execute_with_retry("Get transaction log", || {
+                    let client = self.client.clone();
+                    let page_url = page_url.clone();
+                    async move {
+                        Self::get_transaction_log_page_once(
+                            &client,
+                            &page_url,
+                            cookies,
+                            api_user_key,
+                            api_user_value,
+                            extra_headers,
+                            dialect,
+                        )
+                        .await
+                    }
+                })
+                .await?;
+
+            fetched += result.entries;
+            changes.extend(result.changes);
+
+            let log_ended = result.entries < PAGE_SIZE as usize
+                || result.total.is_some_and(|total| fetched >= total as usize);
+            if log_ended {
+                break;
+            }
+            if page == MAX_PAGES {
+                log::warn!(
+                    "Transaction log has more than {} pages, older entries are left out",
+                    MAX_PAGES
+                );
+            }
+        }
+
+        log::info!(
+            "Fetched {} transaction log entries, {} balance changes",
+            fetched,
+            changes.len()
+        );
+        Ok(changes)
+    }
+
+    /// Get one page of the transaction log - single attempt
+    async fn get_transaction_log_page_once(
+        client: &Client,
+        url: &str,
+        cookies: &HashMap<String, String>,
+        api_user_key: &str,
+        api_user_value: &str,
+        extra_headers: &ExtraHeaders,
+        dialect: ProviderDialect,
+    ) -> Result<TransactionLogPage> {
+        let mut headers = header::HeaderMap::new();
+        headers.insert(
+            header::ACCEPT,
+            header::HeaderValue::from_static("application/json, text/plain, */*"),
+        );
+        headers.insert(
+            header::REFERER,
+            header::HeaderValue::from_str(&extract_domain(url)?)?,
+        );
+        apply_extra_headers(&mut headers, extra_headers);
+        if !api_user_value.is_empty() {
+            headers.insert(
+                header::HeaderName::from_bytes(api_user_key.as_bytes())?,
+                header::HeaderValue::from_str(api_user_value)?,
+            );
+        }
+
+        let mut request = client.get(url).headers(headers);
+
+        let cookie_string = cookies
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, v))
+            .collect::<Vec<_>>()
+            .join("; ");
+        if !cookie_string.is_empty() {
+            request = request.header(header::COOKIE, cookie_string);
+        }
+
+        let response = request
+            .send()
+            .await
+            .context("Failed to send transaction log request")?;
+
+        let status = response.status();
+        let response_text = read_text(response)
+            .await
+            .context("Failed to read transaction log response")?;
+
+        if response_text.contains("acw_sc__v2") || response_text.contains("<script>var arg1=") {
+            anyhow::bail!("WAF_CHALLENGE: Received HTML instead of JSON");
+        }
+        if !status.is_success() {
+            anyhow::bail!(
+                "Transaction log request failed with status {}: {}",
+                status,
+                &response_text[..response_text.len().min(500)]
+            );
+        }
+
+        parse_transaction_log_page(&response_text, dialect_for(dialect))
+    }
+}
+
+/// `url` with the page parameters added, keeping any query it already has
+fn page_url(url: &str, page: u32) -> Result<String> {
+    let mut url = url::Url::parse(url)?;
+    url.query_pairs_mut()
+        .append_pair("p", &page.to_string())
+        .append_pair("page_size", &PAGE_SIZE.to_string());
+    Ok(url.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::client::dialect::NewApiDialect;
+    use chrono::{TimeZone, Utc};
+
+    const SAMPLE_LOG: &str = r#"{
+        "success": true,
+        "message": "",
+        "data": {
+            "page": 1,
+            "page_size": 100,
+            "total": 4,
+            "items": [
+                {"id": 4, "created_at": 1772524800, "type": 2, "content": "model gpt-4o", "quota": 250000},
+                {"id": 3, "created_at": 1772438400, "type": 4, "content": "check-in reward", "quota": 1000000},
+                {"id": 2, "created_at": 1772438300, "type": 5, "content": "upstream error", "quota": 0},
+                {"id": 1, "created_at": 1772352000, "type": 1, "content": "redeem code", "quota": 5000000}
+            ]
+        }
+    }"#;
+
+    #[test]
+    fn test_parse_sample_transaction_log() {
+        let page = parse_transaction_log_page(SAMPLE_LOG, &NewApiDialect).unwrap();
+
+        assert_eq!(page.entries, 4);
+        assert_eq!(page.total, Some(4));
+        assert_eq!(
+            page.changes,
+            vec![
+                BalanceChange {
+                    at: Utc.with_ymd_and_hms(2026, 3, 3, 8, 0, 0).unwrap(),
+                    income: 0.0,
+                    consumed: 0.5,
+                },
+                BalanceChange {
+                    at: Utc.with_ymd_and_hms(2026, 3, 2, 8, 0, 0).unwrap(),
+                    income: 2.0,
+                    consumed: 0.0,
+                },
+                BalanceChange {
+                    at: Utc.with_ymd_and_hms(2026, 3, 1, 8, 0, 0).unwrap(),
+                    income: 10.0,
+                    consumed: 0.0,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_transaction_log_direct_format_and_errors() {
+        let page = parse_transaction_log_page(
+            r#"{"success": true, "data": [{"created_at": 1772352000, "type": 2, "quota": 500000}]}"#,
+            &NewApiDialect,
+        )
+        .unwrap();
+        assert_eq!(page.entries, 1);
+        assert_eq!(page.total, None);
+        assert_eq!(page.changes[0].consumed, 1.0);
+
+        let err = parse_transaction_log_page(
+            r#"{"success": false, "message": "no access"}"#,
+            &NewApiDialect,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("no access"));
+    }
+
+    #[test]
+    fn test_page_url_keeps_existing_query() {
+        assert_eq!(
+            page_url("https://example.com/api/log/self?type=0", 2).unwrap(),
+            "https://example.com/api/log/self?type=0&p=2&page_size=100"
+        );
+    }
+}
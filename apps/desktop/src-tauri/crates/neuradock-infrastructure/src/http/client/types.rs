@@ -1,6 +1,9 @@
 use anyhow::Result;
+use reqwest::header;
 use serde::{Deserialize, Serialize};
 
+use neuradock_domain::shared::ExtraHeaders;
+
 pub const USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/138.0.0.0 Safari/537.36";
 
 /// HTTP retry configuration
@@ -41,6 +44,8 @@ pub struct UserInfo {
 pub struct CheckInResult {
     pub success: bool,
     pub message: String,
+    /// Parsed JSON response, kept for provider-specific fields such as the reward
+    pub response: Option<serde_json::Value>,
 }
 
 /// Extract domain from URL (including port if present)
@@ -55,6 +60,25 @@ pub(super) fn extract_domain(url: &str) -> Result<String> {
     }
 }
 
+/// Insert configured extra headers, replacing defaults of the same name
+///
+/// Call before adding the api_user header so the header the app manages wins.
+/// Values are marked sensitive since they usually carry tokens.
+pub(super) fn apply_extra_headers(headers: &mut header::HeaderMap, extra: &ExtraHeaders) {
+    for (name, value) in extra.iter() {
+        match (
+            header::HeaderName::from_bytes(name.as_bytes()),
+            header::HeaderValue::from_str(value),
+        ) {
+            (Ok(name), Ok(mut value)) => {
+                value.set_sensitive(true);
+                headers.insert(name, value);
+            }
+            _ => log::warn!("Skipping invalid extra header '{}'", name),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
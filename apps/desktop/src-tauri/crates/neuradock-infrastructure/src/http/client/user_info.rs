@@ -2,7 +2,14 @@ use anyhow::{Context, Result};
 use reqwest::{header, Client};
 use std::collections::HashMap;
 
-use super::types::{extract_domain, UserInfo};
+use neuradock_domain::check_in::ProviderDialect;
+use neuradock_domain::shared::ExtraHeaders;
+
+use crate::http::body::read_text;
+use crate::http::capture::{CapturedRequest, RequestCapture};
+
+use super::dialect::dialect_for;
+use super::types::{apply_extra_headers, extract_domain, UserInfo};
 
 impl super::HttpClient {
     /// Get user info (quota and used quota) with retry
@@ -12,22 +19,34 @@ impl super::HttpClient {
         cookies: &HashMap<String, String>,
         api_user_key: &str,
         api_user_value: &str,
+        extra_headers: &ExtraHeaders,
+        dialect: ProviderDialect,
     ) -> Result<UserInfo> {
         let url = url.to_string();
         let cookies = cookies.clone();
         let api_user_key = api_user_key.to_string();
         let api_user_value = api_user_value.to_string();
+        let extra_headers = extra_headers.clone();
 
         self.execute_with_retry("Get user info", move || {
             let url = url.clone();
             let cookies = cookies.clone();
             let api_user_key = api_user_key.clone();
             let api_user_value = api_user_value.clone();
+            let extra_headers = extra_headers.clone();
             let client = self.client.clone();
 
             async move {
-                Self::get_user_info_once(&client, &url, &cookies, &api_user_key, &api_user_value)
-                    .await
+                Self::get_user_info_once(
+                    &client,
+                    &url,
+                    &cookies,
+                    &api_user_key,
+                    &api_user_value,
+                    &extra_headers,
+                    dialect,
+                )
+                .await
             }
         })
         .await
@@ -40,43 +59,22 @@ impl super::HttpClient {
         cookies: &HashMap<String, String>,
         api_user_key: &str,
         api_user_value: &str,
+        extra_headers: &ExtraHeaders,
+        dialect: ProviderDialect,
     ) -> Result<UserInfo> {
-        // Build headers
-        let mut headers = header::HeaderMap::new();
-        headers.insert(
-            header::ACCEPT,
-            header::HeaderValue::from_static("application/json, text/plain, */*"),
-        );
-        headers.insert(
-            header::ACCEPT_LANGUAGE,
-            header::HeaderValue::from_static("zh-CN,zh;q=0.9,en;q=0.8"),
-        );
-        headers.insert(
-            header::REFERER,
-            header::HeaderValue::from_str(&extract_domain(url)?)?,
-        );
-
-        // Add API user header
-        if !api_user_value.is_empty() {
-            headers.insert(
-                header::HeaderName::from_bytes(api_user_key.as_bytes())?,
-                header::HeaderValue::from_str(api_user_value)?,
-            );
-        }
-
-        // Build request with cookies
-        let mut request = client.get(url).headers(headers);
-
-        // Add cookies as header string
-        let cookie_string = cookies
-            .iter()
-            .map(|(k, v)| format!("{}={}", k, v))
-            .collect::<Vec<_>>()
-            .join("; ");
-
-        if !cookie_string.is_empty() {
-            request = request.header(header::COOKIE, cookie_string);
-        }
+        let request = Self::build_user_info_request(
+            client,
+            url,
+            cookies,
+            api_user_key,
+            api_user_value,
+            extra_headers,
+        )?;
+        let capture = RequestCapture::global();
+        let captured = capture
+            .is_active()
+            .then(|| CapturedRequest::from_builder(&request))
+            .flatten();
 
         // Send request
         let response = request
@@ -88,10 +86,10 @@ impl super::HttpClient {
         log::info!("User info response status: {}", status);
 
         if !status.is_success() {
-            let error_text = response
-                .text()
+            let error_text = read_text(response)
                 .await
                 .unwrap_or_else(|_| "Unable to read response".to_string());
+            capture.record("user_info", captured, status.as_u16(), &error_text);
             log::error!(
                 "User info request failed with status {}: {}",
                 status,
@@ -117,10 +115,10 @@ impl super::HttpClient {
         }
 
         // Get response text first to check for WAF challenge
-        let response_text = response
-            .text()
+        let response_text = read_text(response)
             .await
             .context("Failed to read user info response")?;
+        capture.record("user_info", captured, status.as_u16(), &response_text);
 
         log::debug!("User info response length: {} bytes", response_text.len());
 
@@ -145,36 +143,95 @@ impl super::HttpClient {
         ))?;
 
         // Debug: Log the full response to understand structure
-        log::info!(
-            "User info API response: {}",
-            serde_json::to_string_pretty(&data)
-                .unwrap_or_else(|_| "failed to serialize".to_string())
+        log::debug!("User info API response: {}", data);
+
+        dialect_for(dialect).parse_user_info(&data)
+    }
+
+    /// Build the user info GET request (headers and cookies)
+    fn build_user_info_request(
+        client: &Client,
+        url: &str,
+        cookies: &HashMap<String, String>,
+        api_user_key: &str,
+        api_user_value: &str,
+        extra_headers: &ExtraHeaders,
+    ) -> Result<reqwest::RequestBuilder> {
+        // Build headers
+        let mut headers = header::HeaderMap::new();
+        headers.insert(
+            header::ACCEPT,
+            header::HeaderValue::from_static("application/json, text/plain, */*"),
         );
+        headers.insert(
+            header::ACCEPT_LANGUAGE,
+            header::HeaderValue::from_static("zh-CN,zh;q=0.9,en;q=0.8"),
+        );
+        headers.insert(
+            header::REFERER,
+            header::HeaderValue::from_str(&extract_domain(url)?)?,
+        );
+
+        apply_extra_headers(&mut headers, extra_headers);
+
+        // Add API user header
+        if !api_user_value.is_empty() {
+            headers.insert(
+                header::HeaderName::from_bytes(api_user_key.as_bytes())?,
+                header::HeaderValue::from_str(api_user_value)?,
+            );
+        }
 
-        // Check if response has expected structure
-        if data["data"].is_null() {
-            anyhow::bail!("API response missing 'data' field: {}", data);
+        // Build request with cookies
+        let mut request = client.get(url).headers(headers);
+
+        // Add cookies as header string
+        let cookie_string = cookies
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, v))
+            .collect::<Vec<_>>()
+            .join("; ");
+
+        if !cookie_string.is_empty() {
+            request = request.header(header::COOKIE, cookie_string);
         }
 
-        // Parse quota and used_quota (convert from bytes to dollars, 500000 bytes = $1)
-        let quota_bytes = data["data"]["quota"]
-            .as_f64()
-            .ok_or_else(|| anyhow::anyhow!("Missing or invalid 'quota' field in API response"))?;
-        let used_quota_bytes = data["data"]["used_quota"].as_f64().ok_or_else(|| {
-            anyhow::anyhow!("Missing or invalid 'used_quota' field in API response")
-        })?;
-
-        let current_balance = (quota_bytes / 500000.0 * 100.0).round() / 100.0;
-        let total_consumed = (used_quota_bytes / 500000.0 * 100.0).round() / 100.0;
-        let total_quota = current_balance + total_consumed;
-
-        // NOTE: Upstream's HTTP payload still calls `quota`, `used_quota`, and `total_income`.
-        // We normalize semantics right here so the rest of the app only deals with
-        // `current_balance`, `total_consumed`, and `total_quota` to avoid confusion.
-        Ok(UserInfo {
-            current_balance,
-            total_consumed,
-            total_quota,
-        })
+        Ok(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::HttpClient;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_user_info_request_sends_extra_headers() {
+        let cookies = HashMap::from([("session".to_string(), "abc".to_string())]);
+        let extra_headers = ExtraHeaders::new(BTreeMap::from([
+            ("Authorization".to_string(), "Bearer token".to_string()),
+            ("Accept".to_string(), "application/json".to_string()),
+            ("new-api-user".to_string(), "ignored".to_string()),
+        ]))
+        .unwrap();
+
+        let request = HttpClient::build_user_info_request(
+            &Client::new(),
+            "https://example.com/api/user/self",
+            &cookies,
+            "new-api-user",
+            "42",
+            &extra_headers,
+        )
+        .unwrap()
+        .build()
+        .unwrap();
+
+        assert_eq!(request.headers()[header::AUTHORIZATION], "Bearer token");
+        // Extra headers replace defaults, the api_user header stays the account's
+        assert_eq!(request.headers()[header::ACCEPT], "application/json");
+        assert_eq!(request.headers()["new-api-user"], "42");
+        assert_eq!(request.headers()[header::COOKIE], "session=abc");
     }
 }
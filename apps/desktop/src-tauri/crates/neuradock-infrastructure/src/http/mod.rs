@@ -1,7 +1,13 @@
+pub mod body;
+pub mod capture;
 mod client;
 pub mod token;
 pub mod waf_bypass;
+mod waf_detector;
 
-pub use client::{CheckInResult, HttpClient, UserInfo};
+pub use body::ResponseTooLarge;
+pub use capture::{CapturedExchange, RequestCapture};
+pub use client::{dialect_for, CheckInResult, Dialect, HttpClient, UserInfo};
 pub use token::{TokenClient, TokenData, TokenResponse};
-pub use waf_bypass::WafBypassService;
+pub use waf_bypass::{WafBatchRequest, WafBypassService};
+pub use waf_detector::WafDetector;
@@ -1,5 +1,7 @@
 use anyhow::Result;
 
+use crate::http::body::read_text;
+
 use super::types::ProviderModelsResponse;
 
 impl super::TokenClient {
@@ -38,7 +40,7 @@ impl super::TokenClient {
             anyhow::bail!("Failed to fetch models: HTTP {}", response.status());
         }
 
-        let response_text = response.text().await?;
+        let response_text = read_text(response).await?;
         log::debug!("Models response: {}", response_text);
 
         // Check if response is WAF challenge page
@@ -1,5 +1,7 @@
 use anyhow::Result;
 
+use crate::http::body::read_text;
+
 use super::types::{FetchTokensRequest, TokenResponse};
 
 impl super::TokenClient {
@@ -41,15 +43,11 @@ impl super::TokenClient {
             anyhow::bail!("Failed to fetch tokens: HTTP {}", response.status());
         }
 
-        log::debug!(
-            "Response status: {}, headers: {:?}",
-            response.status(),
-            response.headers()
-        );
+        log::debug!("Response status: {}", response.status());
 
-        // Read response text first for debugging
-        let response_text = response.text().await?;
-        log::debug!("Response body: {}", response_text);
+        // Read response text first for debugging, it carries the token keys
+        let response_text = read_text(response).await?;
+        log::debug!("Response body: {} bytes", response_text.len());
 
         // Check if response is WAF challenge page
         if response_text.contains("<html>") && response_text.contains("acw_sc__v2") {
@@ -60,7 +58,10 @@ impl super::TokenClient {
         // Parse JSON
         let token_response: TokenResponse = serde_json::from_str(&response_text).map_err(|e| {
             log::error!("Failed to parse JSON: {}", e);
-            log::error!("Response text was: {}", response_text);
+            log::error!(
+                "Response text was: {}",
+                response_text.chars().take(500).collect::<String>()
+            );
             anyhow::anyhow!("Failed to parse response: {}", e)
         })?;
 
@@ -0,0 +1,222 @@
+use anyhow::Result;
+use chromiumoxide::browser::Browser;
+use chromiumoxide::cdp::browser_protocol::target::{
+    CreateBrowserContextParams, CreateTargetParams,
+};
+use futures::future::join_all;
+use log::{info, warn};
+use std::collections::HashMap;
+use std::future::Future;
+use tokio::sync::Semaphore;
+
+use neuradock_domain::check_in::WafChallengeKind;
+
+use super::cleanup::cleanup_browser;
+use super::navigation::extract_cookies_on_page;
+use super::require_waf_cookies;
+use crate::config::{TimeoutConfig, WafSettings};
+
+/// Label used in logs for the browser shared by a batch
+const BATCH_LOG_NAME: &str = "WAF batch";
+
+/// One account's WAF bypass inside a batch sharing a browser
+#[derive(Debug, Clone)]
+pub struct WafBatchRequest {
+    pub login_url: String,
+    pub account_name: String,
+}
+
+impl super::WafBypassService {
+    /// Get WAF cookies for several accounts of one provider with a single browser
+    ///
+    /// Every request solves its challenge in its own tab and browser context, so
+    /// tabs never see each other's cookies. At most `tab_concurrency` tabs run at
+    /// once. Results are in request order and a failed tab only fails its own
+    /// request; callers fall back to [`Self::get_waf_cookies`] for those.
+    pub async fn get_waf_cookies_batch(
+        &self,
+        requests: &[WafBatchRequest],
+        kind: WafChallengeKind,
+    ) -> Vec<Result<HashMap<String, String>>> {
+        let Some(first) = requests.first() else {
+            return Vec::new();
+        };
+        let tab_concurrency = WafSettings::global().bypass.tab_concurrency;
+        info!(
+            "[{}] Starting browser for {} {:?} WAF bypasses, {} tabs at a time",
+            BATCH_LOG_NAME,
+            requests.len(),
+            kind,
+            tab_concurrency
+        );
+
+        // The profile is keyed by the login URL host, which all requests share
+        let (browser, handler_task, profile) = match self
+            .launch_browser_with_config(&first.login_url, BATCH_LOG_NAME, kind)
+            .await
+        {
+            Ok(launched) => launched,
+            Err(e) => {
+                let err_msg = format!("Failed to launch batch browser: {}", e);
+                return requests
+                    .iter()
+                    .map(|_| Err(anyhow::anyhow!(err_msg.clone())))
+                    .collect();
+            }
+        };
+
+        let results = run_bounded(requests, tab_concurrency, |request| {
+            solve_in_tab(&browser, request, kind)
+        })
+        .await;
+
+        cleanup_browser(browser, handler_task, profile, BATCH_LOG_NAME).await;
+
+        results
+    }
+}
+
+/// Solve one request in a fresh browser context, closing the tab and context afterwards
+async fn solve_in_tab(
+    browser: &Browser,
+    request: &WafBatchRequest,
+    kind: WafChallengeKind,
+) -> Result<HashMap<String, String>> {
+    let account_name = request.account_name.as_str();
+    let close_timeout = TimeoutConfig::global().browser_close;
+
+    let context_id = browser
+        .create_browser_context(CreateBrowserContextParams::default())
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to create browser context: {}", e))?;
+
+    let page = CreateTargetParams::builder()
+        .url("about:blank")
+        .browser_context_id(context_id.clone())
+        .build()
+        .map_err(|e| anyhow::anyhow!(e));
+    let page = match page {
+        Ok(params) => browser
+            .new_page(params)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to create new page: {}", e)),
+        Err(e) => Err(e),
+    };
+
+    let captured = match page {
+        Ok(page) => {
+            info!("[{}] New tab created", account_name);
+            let captured =
+                extract_cookies_on_page(&page, &request.login_url, account_name, kind).await;
+            if tokio::time::timeout(close_timeout, page.close())
+                .await
+                .is_err()
+            {
+                warn!("[{}] Closing WAF tab timed out", account_name);
+            }
+            captured
+        }
+        Err(e) => Err(e),
+    };
+
+    // Disposing the context drops its cookies and any tab left behind
+    match tokio::time::timeout(close_timeout, browser.dispose_browser_context(context_id)).await {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => warn!(
+            "[{}] Failed to dispose browser context: {}",
+            account_name, e
+        ),
+        Err(_) => warn!("[{}] Disposing browser context timed out", account_name),
+    }
+
+    require_waf_cookies(captured?, account_name, "")
+}
+
+/// Run `task` for every item with at most `limit` of them in flight
+///
+/// Results keep the order of `items`. A permit is released as soon as its task
+/// finishes, whether it succeeded or not.
+async fn run_bounded<'a, T, R, F, Fut>(items: &'a [T], limit: usize, task: F) -> Vec<R>
+where
+    F: Fn(&'a T) -> Fut,
+    Fut: Future<Output = R>,
+{
+    let semaphore = Semaphore::new(limit.max(1));
+    let semaphore = &semaphore;
+    let task = &task;
+
+    join_all(items.iter().map(|item| async move {
+        let _permit = semaphore.acquire().await;
+        task(item).await
+    }))
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_run_bounded_limits_tasks_in_flight() {
+        let items: Vec<usize> = (0..10).collect();
+        let in_flight = AtomicUsize::new(0);
+        let max_in_flight = AtomicUsize::new(0);
+
+        let results = run_bounded(&items, 3, |item| {
+            let in_flight = &in_flight;
+            let max_in_flight = &max_in_flight;
+            async move {
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                max_in_flight.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                *item * 2
+            }
+        })
+        .await;
+
+        assert_eq!(max_in_flight.load(Ordering::SeqCst), 3);
+        assert_eq!(results, (0..10).map(|item| item * 2).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn test_run_bounded_treats_zero_limit_as_one() {
+        let items = [1, 2, 3];
+        let in_flight = AtomicUsize::new(0);
+        let max_in_flight = AtomicUsize::new(0);
+
+        run_bounded(&items, 0, |_| {
+            let in_flight = &in_flight;
+            let max_in_flight = &max_in_flight;
+            async move {
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                max_in_flight.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(5)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+            }
+        })
+        .await;
+
+        assert_eq!(max_in_flight.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_failed_task_does_not_block_the_others() {
+        let items = ["ok-1", "fail", "ok-2", "ok-3"];
+
+        let results = run_bounded(&items, 1, |item| async move {
+            if *item == "fail" {
+                anyhow::bail!("challenge not solved");
+            }
+            Ok(item.to_string())
+        })
+        .await;
+
+        assert_eq!(results.len(), 4);
+        assert!(results[1].is_err());
+        let solved: Vec<String> = results.into_iter().filter_map(Result::ok).collect();
+        assert_eq!(solved, vec!["ok-1", "ok-2", "ok-3"]);
+    }
+}
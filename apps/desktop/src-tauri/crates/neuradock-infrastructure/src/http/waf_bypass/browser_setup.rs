@@ -1,11 +1,17 @@
 use anyhow::Result;
-use chromiumoxide::browser::{Browser, BrowserConfig};
+use chromiumoxide::browser::{Browser, BrowserConfig, HeadlessMode};
 use futures::StreamExt;
-use log::info;
-use std::path::PathBuf;
+use log::{info, warn};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 use tokio::task::JoinHandle;
 
-use crate::config::TimeoutConfig;
+use neuradock_domain::check_in::WafChallengeKind;
+
+use super::launch_error::BrowserLaunchError;
+use super::profile::ProfileDir;
+use super::types::challenge_browser_args;
+use crate::config::{BrowserSettings, TimeoutConfig};
 
 /// Find available Chromium-based browser on the system
 pub(super) fn find_browser() -> Option<PathBuf> {
@@ -81,6 +87,148 @@ pub(super) fn find_browser() -> Option<PathBuf> {
     None
 }
 
+/// Validate that a user-supplied browser path exists and is executable
+pub fn validate_browser_path(path: &str) -> Result<PathBuf> {
+    let trimmed = path.trim();
+    if trimmed.is_empty() {
+        anyhow::bail!("Browser path cannot be empty");
+    }
+
+    let browser_path = PathBuf::from(trimmed);
+    let metadata = std::fs::metadata(&browser_path)
+        .map_err(|e| anyhow::anyhow!("Browser path {:?} is not accessible: {}", browser_path, e))?;
+
+    if !metadata.is_file() {
+        anyhow::bail!("Browser path {:?} is not a file", browser_path);
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if metadata.permissions().mode() & 0o111 == 0 {
+            anyhow::bail!("Browser path {:?} is not executable", browser_path);
+        }
+    }
+
+    Ok(browser_path)
+}
+
+/// Flags NeuraDock sets itself or that weaken the browser's security, with the reason
+const RESERVED_BROWSER_FLAGS: &[(&str, &str)] = &[
+    (
+        "--user-data-dir",
+        "the browser profile is managed by NeuraDock",
+    ),
+    (
+        "--remote-debugging-port",
+        "NeuraDock controls the browser over DevTools",
+    ),
+    (
+        "--remote-debugging-pipe",
+        "NeuraDock controls the browser over DevTools",
+    ),
+    (
+        "--remote-debugging-address",
+        "it would expose DevTools to other machines",
+    ),
+    (
+        "--remote-allow-origins",
+        "it would let websites connect to DevTools",
+    ),
+    ("--proxy-server", "use the proxy settings instead"),
+    ("--headless", "use the headless browser setting instead"),
+    (
+        "--disable-web-security",
+        "it disables the same-origin policy",
+    ),
+    (
+        "--load-extension",
+        "extensions could read the captured cookies",
+    ),
+];
+
+/// Validate user-supplied browser flags, returning them trimmed and deduplicated
+///
+/// Every entry must be a `--flag` or `--flag=value`. Flags NeuraDock manages
+/// itself or that weaken security are rejected rather than silently overriding
+/// the launch configuration.
+pub fn validate_browser_args(args: Vec<String>) -> Result<Vec<String>> {
+    let mut validated: Vec<String> = Vec::new();
+    for arg in args {
+        let arg = arg.trim();
+        if arg.is_empty() {
+            continue;
+        }
+        if !arg.starts_with("--") || arg.contains(char::is_whitespace) {
+            anyhow::bail!(
+                "Browser argument {:?} must be a single --flag or --flag=value",
+                arg
+            );
+        }
+
+        let flag = arg.split('=').next().unwrap_or(arg).to_lowercase();
+        if let Some((_, reason)) = RESERVED_BROWSER_FLAGS
+            .iter()
+            .find(|(reserved, _)| *reserved == flag)
+        {
+            anyhow::bail!("Browser argument {} is not allowed: {}", flag, reason);
+        }
+
+        if !validated.iter().any(|existing| existing == arg) {
+            validated.push(arg.to_string());
+        }
+    }
+
+    Ok(validated)
+}
+
+/// Command line flags added on top of the chromiumoxide defaults
+///
+/// User flags come last so they take precedence over the challenge flags.
+fn launch_args(
+    proxy_url: Option<&str>,
+    kind: WafChallengeKind,
+    extra_args: &[String],
+) -> Vec<String> {
+    let mut args = Vec::new();
+    // Chrome's proxy flag supports http(s):// and socks5://
+    if let Some(proxy_url) = proxy_url {
+        args.push(format!("--proxy-server={}", proxy_url));
+    }
+    args.extend(
+        challenge_browser_args(kind)
+            .iter()
+            .map(|arg| arg.to_string()),
+    );
+    args.extend(extra_args.iter().cloned());
+    args
+}
+
+/// Detect the browser version by running `<browser> --version`
+pub fn detect_browser_version(browser_path: &Path) -> Option<String> {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let path = browser_path.to_path_buf();
+
+    // Some browsers never return from --version (e.g. when they try to open a window),
+    // so run the probe on a separate thread and give up after a short timeout.
+    std::thread::spawn(move || {
+        let output = std::process::Command::new(&path).arg("--version").output();
+        let _ = tx.send(output);
+    });
+
+    let output = rx.recv_timeout(Duration::from_secs(5)).ok()?.ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if version.is_empty() {
+        None
+    } else {
+        Some(version)
+    }
+}
+
 #[cfg(target_os = "windows")]
 fn get_chrome_from_registry() -> Result<PathBuf> {
     use std::process::Command;
@@ -112,55 +260,116 @@ fn get_chrome_from_registry() -> Result<PathBuf> {
 
 impl super::WafBypassService {
     /// Launch browser with proper configuration
-    /// Returns (browser, handler_task, temp_dir)
+    /// Returns (browser, handler_task, profile_dir)
     pub(super) async fn launch_browser_with_config(
         &self,
+        login_url: &str,
         account_name: &str,
-    ) -> Result<(Browser, JoinHandle<()>, PathBuf)> {
-        // Use unique temporary directory for each session to avoid lock conflicts
-        let temp_dir = std::env::temp_dir().join(format!("chromiumoxide-{}", uuid::Uuid::new_v4()));
-        std::fs::create_dir_all(&temp_dir)
-            .map_err(|e| anyhow::anyhow!("Failed to create temp directory: {}", e))?;
-
-        info!(
-            "[{}] Using temp profile directory: {:?}",
-            account_name, temp_dir
-        );
+        kind: WafChallengeKind,
+    ) -> Result<(Browser, JoinHandle<()>, ProfileDir)> {
+        let profile = ProfileDir::for_url(login_url, account_name).await?;
+
+        // Prefer the user-configured browser, falling back to auto-detection on failure
+        let configured_path = BrowserSettings::global().browser_path;
+        let mut configured_error = None;
+        if let Some(configured_path) = configured_path.as_ref() {
+            info!(
+                "[{}] Using configured browser at: {:?}",
+                account_name, configured_path
+            );
+            match self
+                .launch_browser_executable(account_name, configured_path, profile.path(), kind)
+                .await
+            {
+                Ok((browser, handler_task)) => return Ok((browser, handler_task, profile)),
+                Err(e) => {
+                    warn!(
+                        "[{}] Configured browser failed to launch: {}. Falling back to auto-detection",
+                        account_name, e
+                    );
+                    configured_error = Some(e);
+                }
+            }
+        }
 
         // Find available browser
-        let browser_path = find_browser().ok_or_else(|| {
-            let err_msg = "No Chromium-based browser found. Please install one of: Google Chrome, Chromium, Brave, or Microsoft Edge";
-            log::error!("[{}] {}", account_name, err_msg);
-            anyhow::anyhow!(err_msg)
-        })?;
+        let detected_path = find_browser().filter(|path| Some(path) != configured_path.as_ref());
+        let browser_path = match detected_path {
+            Some(path) => path,
+            None => {
+                profile.discard_if_temporary();
+                // The configured browser's own failure says more than "nothing else was found"
+                let error = configured_error.unwrap_or_else(|| {
+                    BrowserLaunchError::new(
+                        None,
+                        "No Chromium-based browser found. Please install one of: Google Chrome, Chromium, Brave, or Microsoft Edge, or configure a browser path in settings",
+                    )
+                });
+                log::error!("[{}] {}", account_name, error);
+                return Err(error.into());
+            }
+        };
 
         info!("[{}] Using browser at: {:?}", account_name, browser_path);
 
+        match self
+            .launch_browser_executable(account_name, &browser_path, profile.path(), kind)
+            .await
+        {
+            Ok((browser, handler_task)) => Ok((browser, handler_task, profile)),
+            Err(e) => {
+                // Clean up temp directory on failure
+                profile.discard_if_temporary();
+                Err(e.into())
+            }
+        }
+    }
+
+    /// Launch a specific browser executable using the given profile directory
+    async fn launch_browser_executable(
+        &self,
+        account_name: &str,
+        browser_path: &Path,
+        profile_dir: &Path,
+        kind: WafChallengeKind,
+    ) -> std::result::Result<(Browser, JoinHandle<()>), BrowserLaunchError> {
         // Configure browser
         let mut builder = BrowserConfig::builder()
             .window_size(1920, 1080)
             .no_sandbox() // Add no-sandbox for compatibility
-            .user_data_dir(&temp_dir) // Use unique user data directory
-            .chrome_executable(&browser_path); // Use found browser
+            .user_data_dir(profile_dir) // Temporary or per-provider persistent profile
+            .chrome_executable(browser_path); // Use found browser
 
-        // Apply proxy if configured (Chrome flag supports http(s):// and socks5://).
         if let Some(proxy_url) = self.proxy_url.as_deref() {
             info!(
                 "[{}] Launching browser with proxy: {}",
                 account_name, proxy_url
             );
-            builder = builder.arg(format!("--proxy-server={}", proxy_url));
         }
 
-        // Set headless mode
+        let extra_args = BrowserSettings::global().extra_args;
+        if !extra_args.is_empty() {
+            info!(
+                "[{}] Adding configured browser arguments: {:?}",
+                account_name, extra_args
+            );
+        }
+        builder = builder.args(launch_args(self.proxy_url.as_deref(), kind, &extra_args));
+
+        // Set headless mode (Cloudflare rejects the old headless mode)
         if !self.headless {
             builder = builder.with_head();
+        } else if kind == WafChallengeKind::Cloudflare {
+            builder = builder.headless_mode(HeadlessMode::New);
         }
 
         let config = builder.build().map_err(|e| {
-            let err_msg = format!("Failed to build browser config: {}", e);
-            log::error!("[{}] {}", account_name, err_msg);
-            anyhow::anyhow!(err_msg)
+            let error = BrowserLaunchError::new(
+                Some(browser_path.to_path_buf()),
+                format!("Failed to build browser config: {}", e),
+            );
+            log::error!("[{}] {}", account_name, error);
+            error
         })?;
 
         info!("[{}] Browser config created, launching...", account_name);
@@ -173,21 +382,21 @@ impl super::WafBypassService {
         let (browser, mut handler) = match launch_result {
             Ok(Ok(browser_handler)) => browser_handler,
             Ok(Err(e)) => {
-                // Clean up temp directory on failure
-                let _ = std::fs::remove_dir_all(&temp_dir);
-                let err_msg = format!(
-                    "Failed to launch browser: {}. Make sure Chrome is installed and has proper permissions.",
-                    e
-                );
-                log::error!("[{}] {}", account_name, err_msg);
-                return Err(anyhow::anyhow!(err_msg));
+                let error =
+                    BrowserLaunchError::new(Some(browser_path.to_path_buf()), e.to_string());
+                log::error!("[{}] {}", account_name, error);
+                return Err(error);
             }
             Err(_) => {
-                // Clean up temp directory on timeout
-                let _ = std::fs::remove_dir_all(&temp_dir);
-                let err_msg = "Browser launch timed out after 30 seconds".to_string();
-                log::error!("[{}] {}", account_name, err_msg);
-                return Err(anyhow::anyhow!(err_msg));
+                let error = BrowserLaunchError::new(
+                    Some(browser_path.to_path_buf()),
+                    format!(
+                        "Browser launch timed out after {} seconds",
+                        timeout_config.browser_launch.as_secs()
+                    ),
+                );
+                log::error!("[{}] {}", account_name, error);
+                return Err(error);
             }
         };
 
@@ -200,6 +409,62 @@ impl super::WafBypassService {
             }
         });
 
-        Ok((browser, handler_task, temp_dir))
+        Ok((browser, handler_task))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_configured_args_appear_in_launch_args() {
+        let extra_args = validate_browser_args(vec![
+            " --disable-gpu ".to_string(),
+            "--lang=en-US".to_string(),
+            String::new(),
+            "--disable-gpu".to_string(),
+        ])
+        .unwrap();
+
+        let args = launch_args(
+            Some("socks5://127.0.0.1:1080"),
+            WafChallengeKind::Cloudflare,
+            &extra_args,
+        );
+
+        assert_eq!(args[0], "--proxy-server=socks5://127.0.0.1:1080");
+        assert!(args.contains(&"--disable-blink-features=AutomationControlled".to_string()));
+        assert_eq!(&args[args.len() - 2..], ["--disable-gpu", "--lang=en-US"]);
+    }
+
+    #[test]
+    fn test_no_configured_args_keeps_default_launch_args() {
+        let args = launch_args(None, WafChallengeKind::Aliyun, &[]);
+        assert!(args.is_empty());
+    }
+
+    #[test]
+    fn test_reserved_and_malformed_args_are_rejected() {
+        for arg in [
+            "--remote-debugging-port=9222",
+            "--User-Data-Dir=/tmp/profile",
+            "--proxy-server=http://proxy:8080",
+            "--disable-web-security",
+            "--load-extension=/tmp/ext",
+            "disable-gpu",
+            "--lang en-US",
+        ] {
+            assert!(
+                validate_browser_args(vec![arg.to_string()]).is_err(),
+                "{} should be rejected",
+                arg
+            );
+        }
+
+        assert_eq!(
+            validate_browser_args(vec!["--no-sandbox".to_string()]).unwrap(),
+            vec!["--no-sandbox"]
+        );
     }
 }
@@ -1,16 +1,19 @@
 use chromiumoxide::browser::Browser;
 use log::{info, warn};
-use std::path::{Path, PathBuf};
+use std::path::Path;
 use std::time::Duration;
 use tokio::task::JoinHandle;
 
+use super::profile::ProfileDir;
 use crate::config::TimeoutConfig;
 
 /// Helper to clean up browser resources with timeout
+///
+/// Temporary profiles are deleted; persistent profiles are kept for the next run.
 pub(super) async fn cleanup_browser(
     mut browser: Browser,
     handler_task: JoinHandle<()>,
-    temp_dir: PathBuf,
+    profile: ProfileDir,
     account_name: &str,
 ) {
     let config = TimeoutConfig::global();
@@ -19,27 +22,42 @@ pub(super) async fn cleanup_browser(
     handler_task.abort();
 
     // Try to close browser with timeout
-    match tokio::time::timeout(config.browser_close, browser.close()).await {
+    let closed = match tokio::time::timeout(config.browser_close, browser.close()).await {
         Ok(Ok(_)) => {
             info!("[{}] Browser closed successfully", account_name);
+            true
         }
         Ok(Err(e)) => {
             warn!(
                 "[{}] Failed to close browser: {}, will force cleanup",
                 account_name, e
             );
+            false
         }
         Err(_) => {
             warn!(
                 "[{}] Browser close timed out, continuing with cleanup",
                 account_name
             );
+            false
         }
-    }
+    };
 
     // Give Chrome a moment to fully exit
     tokio::time::sleep(Duration::from_secs(1)).await;
 
+    let temp_dir = match profile {
+        ProfileDir::Temporary(dir) => dir,
+        ProfileDir::Persistent(dir, _guard) => {
+            // A lingering browser would hold the profile lock for the next run
+            if !closed {
+                force_kill_chrome_processes(&dir, account_name).await;
+            }
+            info!("[{}] Kept persistent profile directory", account_name);
+            return;
+        }
+    };
+
     // Try to clean up temp directory
     let cleanup_result = std::fs::remove_dir_all(&temp_dir);
 
@@ -0,0 +1,254 @@
+use anyhow::{Context, Result};
+use chromiumoxide::page::ScreenshotParams;
+use chromiumoxide::Page;
+use chrono::{DateTime, Utc};
+use log::warn;
+use std::path::{Path, PathBuf};
+
+use crate::logging::get_log_dir;
+
+/// Captures kept in the diagnostics folder, older ones are pruned
+const MAX_CAPTURES: usize = 10;
+/// Larger page HTML is truncated
+const MAX_HTML_BYTES: usize = 1024 * 1024;
+/// Larger screenshots are not written
+const MAX_SCREENSHOT_BYTES: usize = 5 * 1024 * 1024;
+
+const SCREENSHOT_FILE: &str = "screenshot.png";
+const HTML_FILE: &str = "page.html";
+
+/// Screenshot and HTML of the page a failed WAF bypass ended on
+#[derive(Debug, Clone, PartialEq)]
+pub struct WafDiagnosticCapture {
+    /// Folder name of the capture, `<timestamp>_<account>`
+    pub id: String,
+    pub captured_at: DateTime<Utc>,
+    pub screenshot_path: Option<PathBuf>,
+    pub html_path: Option<PathBuf>,
+    pub size_bytes: u64,
+}
+
+impl WafDiagnosticCapture {
+    /// File paths of the capture, for error messages
+    pub fn describe(&self) -> String {
+        let paths: Vec<String> = [&self.screenshot_path, &self.html_path]
+            .into_iter()
+            .flatten()
+            .map(|path| path.display().to_string())
+            .collect();
+        paths.join(", ")
+    }
+}
+
+/// Folder WAF bypass diagnostics are written to, under the log directory
+pub fn waf_diagnostics_dir() -> Option<PathBuf> {
+    get_log_dir().map(|dir| dir.join("waf_diagnostics"))
+}
+
+/// Recent WAF bypass diagnostics, newest first
+pub fn list_waf_diagnostics() -> Result<Vec<WafDiagnosticCapture>> {
+    match waf_diagnostics_dir() {
+        Some(dir) => list_captures(&dir),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Save a screenshot and the HTML of `page`, then prune old captures
+pub(super) async fn capture_page(page: &Page, account_name: &str) -> Result<WafDiagnosticCapture> {
+    let root = waf_diagnostics_dir().context("Log directory is not initialized")?;
+    let captured_at = Utc::now();
+    let id = format!(
+        "{}_{}",
+        captured_at.format("%Y%m%dT%H%M%S%3fZ"),
+        sanitize(account_name)
+    );
+    let dir = root.join(&id);
+    tokio::fs::create_dir_all(&dir)
+        .await
+        .with_context(|| format!("Failed to create {}", dir.display()))?;
+
+    let screenshot_path = match page.screenshot(ScreenshotParams::default()).await {
+        Ok(png) if png.len() <= MAX_SCREENSHOT_BYTES => {
+            let path = dir.join(SCREENSHOT_FILE);
+            tokio::fs::write(&path, png).await?;
+            Some(path)
+        }
+        Ok(png) => {
+            warn!(
+                "[{}] Skipping {} byte WAF diagnostics screenshot",
+                account_name,
+                png.len()
+            );
+            None
+        }
+        Err(e) => {
+            warn!(
+                "[{}] Failed to take WAF diagnostics screenshot: {}",
+                account_name, e
+            );
+            None
+        }
+    };
+
+    let html_path = match page.content().await {
+        Ok(html) => {
+            let path = dir.join(HTML_FILE);
+            tokio::fs::write(&path, truncate_utf8(&html, MAX_HTML_BYTES)).await?;
+            Some(path)
+        }
+        Err(e) => {
+            warn!(
+                "[{}] Failed to read page HTML for WAF diagnostics: {}",
+                account_name, e
+            );
+            None
+        }
+    };
+
+    if let Err(e) = prune_captures(&root, MAX_CAPTURES) {
+        warn!("Failed to prune WAF diagnostics: {}", e);
+    }
+
+    Ok(WafDiagnosticCapture {
+        size_bytes: dir_size(&dir),
+        id,
+        captured_at,
+        screenshot_path,
+        html_path,
+    })
+}
+
+/// Captures in `root`, newest first
+fn list_captures(root: &Path) -> Result<Vec<WafDiagnosticCapture>> {
+    if !root.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut captures = Vec::new();
+    for entry in std::fs::read_dir(root)? {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+        let dir = entry.path();
+        let id = entry.file_name().to_string_lossy().to_string();
+        let captured_at = entry
+            .metadata()?
+            .modified()
+            .map(DateTime::<Utc>::from)
+            .unwrap_or_else(|_| Utc::now());
+        let existing = |name: &str| Some(dir.join(name)).filter(|path| path.is_file());
+
+        captures.push(WafDiagnosticCapture {
+            screenshot_path: existing(SCREENSHOT_FILE),
+            html_path: existing(HTML_FILE),
+            size_bytes: dir_size(&dir),
+            id,
+            captured_at,
+        });
+    }
+
+    // Ids start with the capture timestamp
+    captures.sort_by(|a, b| b.id.cmp(&a.id));
+    Ok(captures)
+}
+
+/// Delete all but the `keep` newest captures in `root`
+fn prune_captures(root: &Path, keep: usize) -> Result<()> {
+    for capture in list_captures(root)?.into_iter().skip(keep) {
+        std::fs::remove_dir_all(root.join(&capture.id))?;
+    }
+    Ok(())
+}
+
+fn dir_size(dir: &Path) -> u64 {
+    std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok()?.metadata().ok())
+                .map(|metadata| metadata.len())
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+/// Account names are user input, keep them filesystem-safe
+fn sanitize(name: &str) -> String {
+    let sanitized: String = name
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .take(40)
+        .collect();
+    if sanitized.is_empty() {
+        "account".to_string()
+    } else {
+        sanitized
+    }
+}
+
+fn truncate_utf8(text: &str, max_bytes: usize) -> &str {
+    if text.len() <= max_bytes {
+        return text;
+    }
+    let mut end = max_bytes;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    &text[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_capture(root: &Path, id: &str) {
+        let dir = root.join(id);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(HTML_FILE), "<html></html>").unwrap();
+    }
+
+    #[test]
+    fn test_list_captures_newest_first() {
+        let root = tempfile::tempdir().unwrap();
+        write_capture(root.path(), "20251201T100000000Z_a");
+        write_capture(root.path(), "20251203T100000000Z_b");
+        write_capture(root.path(), "20251202T100000000Z_c");
+
+        let captures = list_captures(root.path()).unwrap();
+        let ids: Vec<&str> = captures.iter().map(|c| c.id.as_str()).collect();
+
+        assert_eq!(
+            ids,
+            vec![
+                "20251203T100000000Z_b",
+                "20251202T100000000Z_c",
+                "20251201T100000000Z_a"
+            ]
+        );
+        assert!(captures[0].html_path.is_some());
+        assert!(captures[0].screenshot_path.is_none());
+        assert_eq!(captures[0].size_bytes, 13);
+    }
+
+    #[test]
+    fn test_prune_keeps_newest_captures() {
+        let root = tempfile::tempdir().unwrap();
+        for day in 1..=12 {
+            write_capture(root.path(), &format!("202512{:02}T100000000Z_a", day));
+        }
+
+        prune_captures(root.path(), MAX_CAPTURES).unwrap();
+
+        let captures = list_captures(root.path()).unwrap();
+        assert_eq!(captures.len(), MAX_CAPTURES);
+        assert_eq!(captures.last().unwrap().id, "20251203T100000000Z_a");
+    }
+
+    #[test]
+    fn test_sanitize_and_truncate() {
+        assert_eq!(sanitize("me@example.com"), "me_example_com");
+        assert_eq!(sanitize(""), "account");
+        assert_eq!(truncate_utf8("héllo", 2), "h");
+        assert_eq!(truncate_utf8("hello", 10), "hello");
+    }
+}
@@ -0,0 +1,234 @@
+use neuradock_domain::shared::DomainError;
+use std::fmt;
+use std::path::PathBuf;
+
+/// Why the browser used for a WAF bypass could not be started
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BrowserLaunchFailure {
+    /// No browser executable at the configured or detected path
+    NotFound,
+    /// The executable exists but may not be run by this user
+    PermissionDenied,
+    /// Chromium's sandbox could not be set up
+    Sandbox,
+    /// The build does not fit this system or is too old for the DevTools protocol
+    VersionMismatch,
+    /// The process started and exited before DevTools became available
+    CrashedOnStart,
+    /// The process did not become ready within the launch timeout
+    TimedOut,
+    Unknown,
+}
+
+impl BrowserLaunchFailure {
+    /// Classify a launch error from chromiumoxide or the browser's stderr
+    pub fn classify(error: &str) -> Self {
+        let error = error.to_lowercase();
+        let mentions = |needles: &[&str]| needles.iter().any(|needle| error.contains(needle));
+
+        // Checked in this order because e.g. a missing glibc symbol also says "not found"
+        if mentions(&["sandbox", "namespace", "zygote"]) {
+            BrowserLaunchFailure::Sandbox
+        } else if mentions(&[
+            "glibc",
+            "version `",
+            "protocol version",
+            "not supported",
+            "unsupported",
+            "requires a newer",
+            "incompatible",
+            "exec format error",
+            "bad cpu type",
+            "wrong elf class",
+        ]) {
+            BrowserLaunchFailure::VersionMismatch
+        } else if mentions(&[
+            "no such file or directory",
+            "os error 2)",
+            "cannot find",
+            "could not auto detect",
+            "no chromium-based browser found",
+        ]) {
+            BrowserLaunchFailure::NotFound
+        } else if mentions(&[
+            "permission denied",
+            "os error 13)",
+            "operation not permitted",
+            "access is denied",
+        ]) {
+            BrowserLaunchFailure::PermissionDenied
+        } else if mentions(&["timed out", "timeout"]) {
+            BrowserLaunchFailure::TimedOut
+        } else if mentions(&[
+            "exited",
+            "crash",
+            "signal",
+            "segmentation fault",
+            "core dumped",
+            "websocket url",
+        ]) {
+            BrowserLaunchFailure::CrashedOnStart
+        } else {
+            BrowserLaunchFailure::Unknown
+        }
+    }
+
+    /// Short description for the start of the error message
+    pub fn summary(&self) -> &'static str {
+        match self {
+            BrowserLaunchFailure::NotFound => "Browser not found",
+            BrowserLaunchFailure::PermissionDenied => "Browser could not be executed",
+            BrowserLaunchFailure::Sandbox => "Browser sandbox could not start",
+            BrowserLaunchFailure::VersionMismatch => "Browser version is not compatible",
+            BrowserLaunchFailure::CrashedOnStart => "Browser crashed on start",
+            BrowserLaunchFailure::TimedOut => "Browser did not start in time",
+            BrowserLaunchFailure::Unknown => "Browser failed to launch",
+        }
+    }
+
+    /// What the user can do about it
+    pub fn hint(&self) -> &'static str {
+        match self {
+            BrowserLaunchFailure::NotFound => "Install Google Chrome, Chromium, Brave or Microsoft Edge, or correct the configured browser path.",
+            BrowserLaunchFailure::PermissionDenied => "Make sure your user may run the browser file (chmod +x on Linux and macOS). On macOS, open the browser once so Gatekeeper allows it.",
+            BrowserLaunchFailure::Sandbox => "NeuraDock already passes --no-sandbox. If the configured path is a wrapper script, make sure it forwards its arguments. On Linux, enable unprivileged user namespaces (sysctl kernel.unprivileged_userns_clone=1).",
+            BrowserLaunchFailure::VersionMismatch => "Update the browser, or install a build made for this system.",
+            BrowserLaunchFailure::CrashedOnStart => "Close other browser windows using the same profile, clear the browser profiles in settings, or configure another browser.",
+            BrowserLaunchFailure::TimedOut => "The system may be busy, or security software may be scanning the browser. Try again in a moment.",
+            BrowserLaunchFailure::Unknown => "Check the logs for the browser output, or configure another browser.",
+        }
+    }
+
+    /// Whether launching the same browser again may work
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            BrowserLaunchFailure::CrashedOnStart
+                | BrowserLaunchFailure::TimedOut
+                | BrowserLaunchFailure::Unknown
+        )
+    }
+}
+
+/// Classified browser launch failure with an actionable message
+#[derive(Debug, Clone)]
+pub struct BrowserLaunchError {
+    pub failure: BrowserLaunchFailure,
+    pub browser_path: Option<PathBuf>,
+    /// Original error text
+    pub details: String,
+}
+
+impl BrowserLaunchError {
+    /// Classify `details` into a launch error for the browser at `browser_path`
+    pub fn new(browser_path: Option<PathBuf>, details: impl Into<String>) -> Self {
+        let details = details.into();
+        Self {
+            failure: BrowserLaunchFailure::classify(&details),
+            browser_path,
+            details,
+        }
+    }
+
+    /// Find a launch error anywhere in an error chain
+    pub fn find(error: &anyhow::Error) -> Option<&BrowserLaunchError> {
+        error
+            .chain()
+            .find_map(|cause| cause.downcast_ref::<BrowserLaunchError>())
+    }
+}
+
+impl fmt::Display for BrowserLaunchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.failure.summary())?;
+        if let Some(path) = &self.browser_path {
+            write!(f, " ({})", path.display())?;
+        }
+        write!(f, ": {}. {}", self.details, self.failure.hint())
+    }
+}
+
+impl std::error::Error for BrowserLaunchError {}
+
+impl From<BrowserLaunchError> for DomainError {
+    fn from(error: BrowserLaunchError) -> Self {
+        DomainError::BrowserLaunch(error.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use neuradock_domain::shared::ErrorCode;
+
+    #[test]
+    fn test_classify_simulated_launch_failures() {
+        let cases = [
+            (
+                "No such file or directory (os error 2)",
+                BrowserLaunchFailure::NotFound,
+            ),
+            (
+                "No Chromium-based browser found. Please install one of: Google Chrome",
+                BrowserLaunchFailure::NotFound,
+            ),
+            (
+                "Permission denied (os error 13)",
+                BrowserLaunchFailure::PermissionDenied,
+            ),
+            (
+                "Browser process exited with status ExitStatus(unix_wait_status(256)) before websocket URL could be resolved, stderr: [0101/000000.000:FATAL:zygote_host_impl_linux.cc(127)] No usable sandbox!",
+                BrowserLaunchFailure::Sandbox,
+            ),
+            (
+                "Browser process exited with status 127, stderr: chrome: /lib/x86_64-linux-gnu/libc.so.6: version `GLIBC_2.38' not found",
+                BrowserLaunchFailure::VersionMismatch,
+            ),
+            (
+                "Exec format error (os error 8)",
+                BrowserLaunchFailure::VersionMismatch,
+            ),
+            (
+                "Browser process exited with status ExitStatus(unix_wait_status(139)) before websocket URL could be resolved, stderr: Segmentation fault",
+                BrowserLaunchFailure::CrashedOnStart,
+            ),
+            (
+                "Timeout while resolving websocket URL from browser process",
+                BrowserLaunchFailure::TimedOut,
+            ),
+            ("Something else entirely", BrowserLaunchFailure::Unknown),
+        ];
+
+        for (error, expected) in cases {
+            assert_eq!(BrowserLaunchFailure::classify(error), expected, "{}", error);
+        }
+    }
+
+    #[test]
+    fn test_launch_error_message_is_actionable() {
+        let error = BrowserLaunchError::new(
+            Some(PathBuf::from("/usr/bin/chromium")),
+            "Failed to move to new namespace: errno = Operation not permitted",
+        );
+
+        assert_eq!(error.failure, BrowserLaunchFailure::Sandbox);
+        let message = error.to_string();
+        assert!(message.starts_with("Browser sandbox could not start (/usr/bin/chromium)"));
+        assert!(message.contains("--no-sandbox"));
+        assert!(!error.failure.is_transient());
+    }
+
+    #[test]
+    fn test_launch_error_maps_to_domain_error() {
+        let error = BrowserLaunchError::new(None, "Browser launch timed out after 30 seconds");
+        let wrapped = anyhow::Error::new(error.clone()).context("Failed to get WAF cookies");
+        assert_eq!(
+            BrowserLaunchError::find(&wrapped).map(|e| e.failure),
+            Some(BrowserLaunchFailure::TimedOut)
+        );
+
+        let domain_error = DomainError::from(error);
+        assert_eq!(domain_error.code(), ErrorCode::BrowserUnavailable);
+        assert!(domain_error.message().contains("Try again"));
+    }
+}
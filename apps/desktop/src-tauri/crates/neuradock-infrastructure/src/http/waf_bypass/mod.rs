@@ -1,16 +1,31 @@
+mod batch;
 mod browser_setup;
 mod cleanup;
+mod diagnostics;
+mod launch_error;
 mod navigation;
+mod profile;
+mod turnstile;
 mod types;
 
 use anyhow::Result;
+use chromiumoxide::Page;
 use log::{info, warn};
 use std::collections::HashMap;
 use std::time::Duration;
 
+use neuradock_domain::check_in::WafChallengeKind;
+
+use crate::config::{BrowserSettings, WafSettings};
+pub use batch::WafBatchRequest;
 use browser_setup::find_browser;
+pub use browser_setup::{detect_browser_version, validate_browser_args, validate_browser_path};
 use cleanup::cleanup_browser;
-use types::REQUIRED_WAF_COOKIES;
+pub use diagnostics::{list_waf_diagnostics, waf_diagnostics_dir, WafDiagnosticCapture};
+pub use launch_error::{BrowserLaunchError, BrowserLaunchFailure};
+use navigation::CapturedWafCookies;
+pub use profile::clear_browser_profiles;
+pub use turnstile::{BrowserTurnstileSolver, TurnstileTokenSource};
 
 pub struct WafBypassService {
     headless: bool,
@@ -33,10 +48,13 @@ impl WafBypassService {
     }
 
     /// Get WAF cookies using chromiumoxide (pure Rust)
+    ///
+    /// `kind` selects the cookies to wait for and the browser flags to launch with.
     pub async fn get_waf_cookies(
         &self,
         login_url: &str,
         account_name: &str,
+        kind: WafChallengeKind,
     ) -> Result<HashMap<String, String>> {
         const MAX_RETRIES: u32 = 2;
         let mut last_error = None;
@@ -52,7 +70,12 @@ impl WafBypassService {
                 tokio::time::sleep(Duration::from_secs(2)).await;
             }
 
-            match self.get_waf_cookies_once(login_url, account_name).await {
+            // Only the last attempt keeps what the browser saw
+            let capture_diagnostics = attempt + 1 == MAX_RETRIES;
+            match self
+                .get_waf_cookies_once(login_url, account_name, kind, capture_diagnostics)
+                .await
+            {
                 Ok(cookies) => return Ok(cookies),
                 Err(e) => {
                     warn!(
@@ -61,7 +84,13 @@ impl WafBypassService {
                         attempt + 1,
                         e
                     );
+                    // A missing or incompatible browser fails the same way every time
+                    let retryable = BrowserLaunchError::find(&e)
+                        .is_none_or(|launch| launch.failure.is_transient());
                     last_error = Some(e);
+                    if !retryable {
+                        break;
+                    }
                 }
             }
         }
@@ -76,44 +105,110 @@ impl WafBypassService {
         &self,
         login_url: &str,
         account_name: &str,
+        kind: WafChallengeKind,
+        capture_diagnostics: bool,
     ) -> Result<HashMap<String, String>> {
         info!(
-            "[{}] Starting browser to get WAF cookies (chromiumoxide)...",
-            account_name
+            "[{}] Starting browser to get {:?} WAF cookies (chromiumoxide)...",
+            account_name, kind
         );
 
         // 1. Launch browser with proper configuration
-        let (browser, handler_task, temp_dir) =
-            self.launch_browser_with_config(account_name).await?;
+        let (browser, handler_task, profile) = self
+            .launch_browser_with_config(login_url, account_name, kind)
+            .await?;
 
         // 2. Navigate to page and extract cookies
-        let (browser, waf_cookies_result) = self
-            .navigate_and_extract_cookies(browser, login_url, account_name)
+        let (browser, page, waf_cookies_result) = self
+            .navigate_and_extract_cookies(browser, login_url, account_name, kind)
             .await;
 
-        // 3. Clean up browser resources (always execute even if error)
-        cleanup_browser(browser, handler_task, temp_dir, account_name).await;
+        // 3. Capture what the browser saw if this attempt failed
+        let failed = waf_cookies_result
+            .as_ref()
+            .map_or(true, |captured| captured.cookies.is_empty());
+        let diagnostics = match page {
+            Some(page) if capture_diagnostics && failed => {
+                capture_failure_diagnostics(&page, account_name).await
+            }
+            _ => None,
+        };
+        let diagnostics_note = diagnostics
+            .map(|capture| format!(" Diagnostics saved to: {}", capture.describe()))
+            .unwrap_or_default();
 
-        // 4. Return result
-        let waf_cookies = waf_cookies_result?;
+        // 4. Clean up browser resources (always execute even if error)
+        cleanup_browser(browser, handler_task, profile, account_name).await;
 
-        // Check if we got any cookies
-        if waf_cookies.is_empty() {
-            let err_msg = format!(
-                "No WAF cookies obtained. Expected cookies: {:?}. This might indicate that the page didn't load properly or WAF protection has changed.",
-                REQUIRED_WAF_COOKIES
-            );
-            warn!("[{}] {}", account_name, err_msg);
-            anyhow::bail!(err_msg);
-        }
+        // 5. Return result
+        let captured =
+            waf_cookies_result.map_err(|e| anyhow::anyhow!("{}.{}", e, diagnostics_note))?;
+        require_waf_cookies(captured, account_name, &diagnostics_note)
+    }
+}
 
-        info!(
-            "[{}] ✓ Successfully got {} WAF cookies",
-            account_name,
-            waf_cookies.len()
+/// Accept the captured cookies unless none were set, warning about the ones still missing
+fn require_waf_cookies(
+    captured: CapturedWafCookies,
+    account_name: &str,
+    diagnostics_note: &str,
+) -> Result<HashMap<String, String>> {
+    let CapturedWafCookies {
+        cookies: waf_cookies,
+        missing,
+    } = captured;
+
+    // Check if we got any cookies
+    if waf_cookies.is_empty() {
+        let err_msg = format!(
+            "No WAF cookies obtained within {:?}. Missing cookies: {:?}. This might indicate that the page didn't load properly or WAF protection has changed.{}",
+            WafSettings::global().bypass.max_total_time,
+            missing,
+            diagnostics_note
+        );
+        warn!("[{}] {}", account_name, err_msg);
+        anyhow::bail!(err_msg);
+    }
+    if !missing.is_empty() {
+        warn!(
+            "[{}] Gave up waiting for WAF cookies {:?}, continuing with the ones captured",
+            account_name, missing
         );
+    }
+
+    info!(
+        "[{}] ✓ Successfully got {} WAF cookies",
+        account_name,
+        waf_cookies.len()
+    );
 
-        Ok(waf_cookies)
+    Ok(waf_cookies)
+}
+
+/// Save a screenshot and the HTML of the page a failed bypass ended on
+async fn capture_failure_diagnostics(
+    page: &Page,
+    account_name: &str,
+) -> Option<WafDiagnosticCapture> {
+    match diagnostics::capture_page(page, account_name).await {
+        Ok(capture) => {
+            tracing::warn!(
+                account = account_name,
+                capture_id = %capture.id,
+                screenshot_path = ?capture.screenshot_path,
+                html_path = ?capture.html_path,
+                "[{}] Captured WAF bypass failure diagnostics",
+                account_name
+            );
+            Some(capture)
+        }
+        Err(e) => {
+            warn!(
+                "[{}] Failed to capture WAF bypass diagnostics: {}",
+                account_name, e
+            );
+            None
+        }
     }
 }
 
@@ -128,6 +223,42 @@ pub fn check_available_browser() -> Option<String> {
     find_browser().map(|path| path.to_string_lossy().to_string())
 }
 
+/// Detected and configured browser information
+#[derive(Debug, Clone)]
+pub struct BrowserInfo {
+    /// Browser found by scanning standard install locations
+    pub detected_path: Option<String>,
+    /// Browser configured by the user
+    pub configured_path: Option<String>,
+    /// Version of the browser that will be used for WAF bypass
+    pub version: Option<String>,
+}
+
+impl BrowserInfo {
+    /// Path of the browser that will be launched first
+    pub fn effective_path(&self) -> Option<&str> {
+        self.configured_path
+            .as_deref()
+            .or(self.detected_path.as_deref())
+    }
+}
+
+/// Collect detected/configured browser paths and the effective browser version
+pub fn get_browser_info() -> BrowserInfo {
+    let detected = find_browser();
+    let configured = BrowserSettings::global().browser_path;
+    let version = configured
+        .as_deref()
+        .or(detected.as_deref())
+        .and_then(detect_browser_version);
+
+    BrowserInfo {
+        detected_path: detected.map(|path| path.to_string_lossy().to_string()),
+        configured_path: configured.map(|path| path.to_string_lossy().to_string()),
+        version,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -145,4 +276,33 @@ mod tests {
         // This test will pass even if no browser is found
         // It's just for checking during development
     }
+
+    #[test]
+    fn test_validate_browser_path_rejects_missing_and_empty() {
+        assert!(validate_browser_path("").is_err());
+        assert!(validate_browser_path("   ").is_err());
+        assert!(validate_browser_path("/definitely/not/a/browser").is_err());
+    }
+
+    #[test]
+    fn test_validate_browser_path_rejects_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(validate_browser_path(dir.path().to_str().unwrap()).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_validate_browser_path_requires_executable() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("chrome");
+        std::fs::write(&path, "#!/bin/sh\n").unwrap();
+
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+        assert!(validate_browser_path(path.to_str().unwrap()).is_err());
+
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        assert_eq!(validate_browser_path(path.to_str().unwrap()).unwrap(), path);
+    }
 }
@@ -1,97 +1,218 @@
 use anyhow::Result;
 use chromiumoxide::browser::Browser;
-use log::info;
+use chromiumoxide::Page;
+use log::{info, warn};
 use std::collections::HashMap;
+use std::time::Duration;
+use tokio::time::Instant;
 
-use super::types::{REQUIRED_WAF_COOKIES, USER_AGENT};
-use crate::config::TimeoutConfig;
+use neuradock_domain::check_in::WafChallengeKind;
+
+use super::types::{required_waf_cookies, USER_AGENT};
+use crate::config::{WafBypassTuning, WafSettings};
 use crate::logging::log_utils::mask_sensitive;
 
+/// Interval between cookie reads while waiting for the WAF challenge
+const COOKIE_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// WAF cookies read from the browser when polling stopped
+#[derive(Debug, Default)]
+pub(super) struct CapturedWafCookies {
+    pub cookies: HashMap<String, String>,
+    /// Cookies that were still missing at the deadline (empty on success)
+    pub missing: Vec<String>,
+}
+
+/// Cookie names to wait for, falling back to the built-in cookie names of the challenge kind
+fn cookies_to_wait_for(tuning: &WafBypassTuning, kind: WafChallengeKind) -> Vec<String> {
+    if tuning.wait_for_cookies.is_empty() {
+        required_waf_cookies(kind)
+            .iter()
+            .map(|name| name.to_string())
+            .collect()
+    } else {
+        tuning.wait_for_cookies.clone()
+    }
+}
+
+/// Required cookie names not present in `captured`
+fn missing_cookies(required: &[String], captured: &HashMap<String, String>) -> Vec<String> {
+    required
+        .iter()
+        .filter(|name| !captured.contains_key(name.as_str()))
+        .cloned()
+        .collect()
+}
+
 impl super::WafBypassService {
-    /// Navigate to page and extract WAF cookies
-    /// Returns (browser, cookies_result) to allow cleanup even on error
+    /// Navigate to page and poll for WAF cookies until they are all set or the deadline passes
+    /// Returns (browser, page, cookies_result) to allow diagnostics and cleanup even on error
     pub(super) async fn navigate_and_extract_cookies(
         &self,
         browser: Browser,
         login_url: &str,
         account_name: &str,
-    ) -> (Browser, Result<HashMap<String, String>>) {
+        kind: WafChallengeKind,
+    ) -> (Browser, Option<Page>, Result<CapturedWafCookies>) {
         // Create new page
         let page = match browser.new_page("about:blank").await {
             Ok(p) => p,
             Err(e) => {
                 let err_msg = format!("Failed to create new page: {}", e);
                 log::error!("[{}] {}", account_name, err_msg);
-                return (browser, Err(anyhow::anyhow!(err_msg)));
+                return (browser, None, Err(anyhow::anyhow!(err_msg)));
             }
         };
 
         info!("[{}] New page created", account_name);
 
-        // Set user agent
-        if let Err(e) = page.set_user_agent(USER_AGENT).await {
-            let err_msg = format!("Failed to set user agent: {}", e);
-            log::error!("[{}] {}", account_name, err_msg);
-            return (browser, Err(anyhow::anyhow!(err_msg)));
-        }
+        let result = extract_cookies_on_page(&page, login_url, account_name, kind).await;
+        (browser, Some(page), result)
+    }
+}
+
+/// Navigate an open page to `login_url` and poll for WAF cookies until they are all set
+/// or the deadline passes
+pub(super) async fn extract_cookies_on_page(
+    page: &Page,
+    login_url: &str,
+    account_name: &str,
+    kind: WafChallengeKind,
+) -> Result<CapturedWafCookies> {
+    let tuning = WafSettings::global().bypass;
+    let required = cookies_to_wait_for(&tuning, kind);
+    let deadline = Instant::now() + tuning.max_total_time;
+
+    // Set user agent
+    if let Err(e) = page.set_user_agent(USER_AGENT).await {
+        let err_msg = format!("Failed to set user agent: {}", e);
+        log::error!("[{}] {}", account_name, err_msg);
+        anyhow::bail!(err_msg);
+    }
 
-        info!("[{}] Navigating to: {}", account_name, login_url);
+    info!("[{}] Navigating to: {}", account_name, login_url);
 
-        // Navigate to login page
-        if let Err(e) = page.goto(login_url).await {
+    // Navigate to login page, bounded by both the navigation timeout and the deadline
+    let navigation_timeout = tuning
+        .navigation_timeout
+        .min(deadline.saturating_duration_since(Instant::now()));
+    match tokio::time::timeout(navigation_timeout, page.goto(login_url)).await {
+        Ok(Ok(_)) => {}
+        Ok(Err(e)) => {
             let err_msg = format!("Failed to navigate to login page: {}", e);
             log::error!("[{}] {}", account_name, err_msg);
-            return (browser, Err(anyhow::anyhow!(err_msg)));
+            anyhow::bail!(err_msg);
         }
+        Err(_) => {
+            // Slow pages may still have run the challenge script, keep polling for cookies
+            warn!(
+                "[{}] Navigation did not finish within {:?}, checking cookies anyway",
+                account_name, navigation_timeout
+            );
+        }
+    }
 
-        info!("[{}] Page loaded, waiting for WAF cookies...", account_name);
+    info!(
+        "[{}] Page loaded, waiting for {:?} WAF cookies {:?}...",
+        account_name, kind, required
+    );
 
-        // Wait for cookies to be set (configurable timeout)
-        let timeout_config = TimeoutConfig::global();
-        tokio::time::sleep(timeout_config.waf_wait).await;
+    sleep_until_or_deadline(tuning.settle_delay, deadline).await;
 
-        // Get all cookies
+    let mut waf_cookies = HashMap::new();
+    loop {
         let cookies = match page.get_cookies().await {
             Ok(c) => c,
             Err(e) => {
                 let err_msg = format!("Failed to get cookies: {}", e);
                 log::error!("[{}] {}", account_name, err_msg);
-                return (browser, Err(anyhow::anyhow!(err_msg)));
+                anyhow::bail!(err_msg);
             }
         };
 
-        info!(
-            "[{}] Retrieved {} cookies from browser",
-            account_name,
-            cookies.len()
-        );
-
-        // Extract WAF cookies
-        let mut waf_cookies = HashMap::new();
         for cookie in cookies {
-            let cookie_name = &cookie.name;
-            let cookie_value = &cookie.value;
-
-            info!(
-                "[{}] Cookie found: {} = {}",
-                account_name,
-                cookie_name,
-                mask_sensitive(cookie_value)
-            );
-
-            if REQUIRED_WAF_COOKIES.contains(&cookie_name.as_str()) {
-                waf_cookies.insert(cookie_name.clone(), cookie_value.clone());
-                info!("[{}] ✓ WAF cookie captured: {}", account_name, cookie_name);
+            if !required.contains(&cookie.name) {
+                continue;
+            }
+            if !waf_cookies.contains_key(&cookie.name) {
+                info!(
+                    "[{}] ✓ WAF cookie captured: {} = {}",
+                    account_name,
+                    cookie.name,
+                    mask_sensitive(&cookie.value)
+                );
             }
+            waf_cookies.insert(cookie.name, cookie.value);
+        }
+
+        if missing_cookies(&required, &waf_cookies).is_empty() || Instant::now() >= deadline {
+            break;
         }
+        sleep_until_or_deadline(COOKIE_POLL_INTERVAL, deadline).await;
+    }
+
+    let missing = missing_cookies(&required, &waf_cookies);
+    info!(
+        "[{}] Captured {} WAF cookies out of {} required",
+        account_name,
+        waf_cookies.len(),
+        required.len()
+    );
+
+    Ok(CapturedWafCookies {
+        cookies: waf_cookies,
+        missing,
+    })
+}
+
+/// Sleep for `duration`, but never past `deadline`
+async fn sleep_until_or_deadline(duration: Duration, deadline: Instant) {
+    tokio::time::sleep_until((Instant::now() + duration).min(deadline)).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cookies_to_wait_for_defaults_to_required_waf_cookies() {
+        let tuning = WafBypassTuning::default();
+        assert_eq!(
+            cookies_to_wait_for(&tuning, WafChallengeKind::Aliyun),
+            vec!["acw_tc", "cdn_sec_tc", "acw_sc__v2"]
+        );
+        assert_eq!(
+            cookies_to_wait_for(&tuning, WafChallengeKind::Cloudflare),
+            vec!["cf_clearance"]
+        );
+
+        let tuning = WafBypassTuning {
+            wait_for_cookies: vec!["cf_clearance".to_string()],
+            ..WafBypassTuning::default()
+        };
+        assert_eq!(
+            cookies_to_wait_for(&tuning, WafChallengeKind::Aliyun),
+            vec!["cf_clearance"]
+        );
+    }
+
+    #[test]
+    fn test_missing_cookies_keeps_required_order() {
+        let required = vec![
+            "acw_tc".to_string(),
+            "cdn_sec_tc".to_string(),
+            "acw_sc__v2".to_string(),
+        ];
+        let mut captured = HashMap::new();
+        captured.insert("cdn_sec_tc".to_string(), "value".to_string());
 
-        info!(
-            "[{}] Captured {} WAF cookies out of {} required",
-            account_name,
-            waf_cookies.len(),
-            REQUIRED_WAF_COOKIES.len()
+        assert_eq!(
+            missing_cookies(&required, &captured),
+            vec!["acw_tc", "acw_sc__v2"]
         );
 
-        (browser, Ok(waf_cookies))
+        captured.insert("acw_tc".to_string(), "value".to_string());
+        captured.insert("acw_sc__v2".to_string(), "value".to_string());
+        assert!(missing_cookies(&required, &captured).is_empty());
     }
 }
@@ -0,0 +1,230 @@
+use anyhow::{Context, Result};
+use log::{info, warn};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use tokio::sync::{Mutex as AsyncMutex, OwnedMutexGuard};
+
+use crate::config::BrowserSettings;
+
+/// Process-wide locks so a persistent profile is only used by one browser at a time
+static PROFILE_LOCKS: OnceLock<Mutex<HashMap<PathBuf, Arc<AsyncMutex<()>>>>> = OnceLock::new();
+
+fn profile_lock(dir: &Path) -> Arc<AsyncMutex<()>> {
+    let locks = PROFILE_LOCKS.get_or_init(|| Mutex::new(HashMap::new()));
+    let mut locks = locks
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    locks
+        .entry(dir.to_path_buf())
+        .or_insert_with(|| Arc::new(AsyncMutex::new(())))
+        .clone()
+}
+
+/// Browser user-data-dir used for a single WAF bypass
+pub(super) enum ProfileDir {
+    /// Fresh profile removed after the browser closes
+    Temporary(PathBuf),
+    /// Per-provider profile kept between runs so the WAF sees a stable fingerprint;
+    /// the guard is held until the browser using it has been cleaned up
+    Persistent(PathBuf, OwnedMutexGuard<()>),
+}
+
+impl ProfileDir {
+    /// Pick the profile directory for a bypass against `login_url`
+    ///
+    /// Persistent profiles are keyed by the provider host so cookies never leak
+    /// across sites. Check-ins already serialize per domain in `WafCookieManager`;
+    /// the profile lock also covers token and model fetches that bypass it.
+    pub(super) async fn for_url(login_url: &str, account_name: &str) -> Result<Self> {
+        let profile = match persistent_profile_path(&BrowserSettings::global(), login_url)? {
+            Some(dir) => {
+                let guard = profile_lock(&dir).lock_owned().await;
+                ProfileDir::Persistent(dir, guard)
+            }
+            // Use unique temporary directory for each session to avoid lock conflicts
+            None => ProfileDir::Temporary(
+                std::env::temp_dir().join(format!("chromiumoxide-{}", uuid::Uuid::new_v4())),
+            ),
+        };
+
+        std::fs::create_dir_all(profile.path())
+            .with_context(|| format!("Failed to create profile directory {:?}", profile.path()))?;
+
+        match &profile {
+            ProfileDir::Temporary(dir) => {
+                info!("[{}] Using temp profile directory: {:?}", account_name, dir)
+            }
+            ProfileDir::Persistent(dir, _) => info!(
+                "[{}] Using persistent profile directory: {:?}",
+                account_name, dir
+            ),
+        }
+
+        Ok(profile)
+    }
+
+    pub(super) fn path(&self) -> &Path {
+        match self {
+            ProfileDir::Temporary(dir) | ProfileDir::Persistent(dir, _) => dir,
+        }
+    }
+
+    /// Remove the directory if it is a temporary profile
+    pub(super) fn discard_if_temporary(&self) {
+        if let ProfileDir::Temporary(dir) = self {
+            let _ = std::fs::remove_dir_all(dir);
+        }
+    }
+}
+
+/// Persistent profile directory for `login_url`, `None` when a temp profile should be used
+fn persistent_profile_path(settings: &BrowserSettings, login_url: &str) -> Result<Option<PathBuf>> {
+    match settings.profiles_dir.as_ref() {
+        Some(profiles_dir) if settings.persistent_profile => {
+            Ok(Some(profiles_dir.join(profile_key(login_url)?)))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Directory name of a provider's persistent profile, derived from its host and port
+fn profile_key(login_url: &str) -> Result<String> {
+    let url = url::Url::parse(login_url).context("Invalid login URL")?;
+    let host = url
+        .host_str()
+        .ok_or_else(|| anyhow::anyhow!("Login URL has no host: {}", login_url))?;
+
+    let key = match url.port() {
+        Some(port) => format!("{}_{}", host, port),
+        None => host.to_string(),
+    };
+
+    Ok(key
+        .to_lowercase()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect())
+}
+
+/// Delete all persistent browser profiles, returning how many were removed
+///
+/// Profiles a browser is using right now are skipped rather than pulled out
+/// from under it.
+pub fn clear_browser_profiles() -> Result<usize> {
+    match BrowserSettings::global().profiles_dir {
+        Some(profiles_dir) => clear_profiles_in(&profiles_dir),
+        None => Ok(0),
+    }
+}
+
+fn clear_profiles_in(profiles_dir: &Path) -> Result<usize> {
+    if !profiles_dir.exists() {
+        return Ok(0);
+    }
+
+    let mut removed = 0;
+    for entry in std::fs::read_dir(profiles_dir)
+        .with_context(|| format!("Failed to read profiles directory {:?}", profiles_dir))?
+    {
+        let path = entry?.path();
+        if !path.is_dir() {
+            continue;
+        }
+
+        let lock = profile_lock(&path);
+        let Ok(_in_use) = lock.try_lock() else {
+            warn!("Skipping browser profile {:?}, it is in use", path);
+            continue;
+        };
+        std::fs::remove_dir_all(&path)
+            .with_context(|| format!("Failed to remove browser profile {:?}", path))?;
+        removed += 1;
+    }
+
+    info!(
+        "Cleared {} browser profiles from {:?}",
+        removed, profiles_dir
+    );
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_key_is_isolated_per_host() {
+        assert_eq!(
+            profile_key("https://anyrouter.top/login").unwrap(),
+            "anyrouter.top"
+        );
+        assert_eq!(
+            profile_key("https://API.Example.com:8443/login").unwrap(),
+            "api.example.com_8443"
+        );
+        assert_ne!(
+            profile_key("https://anyrouter.top/login").unwrap(),
+            profile_key("https://agentrouter.org/login").unwrap()
+        );
+        assert!(profile_key("not a url").is_err());
+    }
+
+    #[test]
+    fn test_persistent_profile_path_selection() {
+        let mut settings = BrowserSettings {
+            profiles_dir: Some(PathBuf::from("/data/browser_profiles")),
+            ..BrowserSettings::default()
+        };
+        let login_url = "https://anyrouter.top/login";
+
+        // Opt-in: without the setting every bypass gets a temp profile
+        assert_eq!(persistent_profile_path(&settings, login_url).unwrap(), None);
+
+        settings.persistent_profile = true;
+        assert_eq!(
+            persistent_profile_path(&settings, login_url).unwrap(),
+            Some(PathBuf::from("/data/browser_profiles/anyrouter.top"))
+        );
+        assert_eq!(
+            persistent_profile_path(&settings, "https://anyrouter.top/console").unwrap(),
+            persistent_profile_path(&settings, login_url).unwrap()
+        );
+
+        settings.profiles_dir = None;
+        assert_eq!(persistent_profile_path(&settings, login_url).unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_clear_profiles_in_skips_profiles_in_use() {
+        let dir = tempfile::tempdir().unwrap();
+        let busy = dir.path().join("anyrouter.top");
+        std::fs::create_dir_all(&busy).unwrap();
+        std::fs::create_dir_all(dir.path().join("agentrouter.org")).unwrap();
+
+        let guard = profile_lock(&busy).lock_owned().await;
+        assert_eq!(clear_profiles_in(dir.path()).unwrap(), 1);
+        assert!(busy.exists());
+
+        drop(guard);
+        assert_eq!(clear_profiles_in(dir.path()).unwrap(), 1);
+        assert!(!busy.exists());
+    }
+
+    #[test]
+    fn test_clear_profiles_in_removes_profile_dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("anyrouter.top/Default")).unwrap();
+        std::fs::create_dir_all(dir.path().join("agentrouter.org")).unwrap();
+
+        assert_eq!(clear_profiles_in(dir.path()).unwrap(), 2);
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+        assert_eq!(clear_profiles_in(&dir.path().join("missing")).unwrap(), 0);
+    }
+}
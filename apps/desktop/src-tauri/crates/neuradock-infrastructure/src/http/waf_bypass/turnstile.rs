@@ -0,0 +1,114 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chromiumoxide::browser::Browser;
+use log::{info, warn};
+use std::time::Duration;
+use tokio::time::Instant;
+
+use neuradock_domain::check_in::WafChallengeKind;
+
+use super::cleanup::cleanup_browser;
+use super::types::USER_AGENT;
+use super::WafBypassService;
+use crate::config::WafSettings;
+
+/// Interval between reads of the Turnstile response field
+const TOKEN_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Value of the hidden input the Turnstile widget fills in once solved
+const READ_TOKEN_SCRIPT: &str = r#"(() => {
+    const input = document.querySelector('[name="cf-turnstile-response"]');
+    return input ? input.value : "";
+})()"#;
+
+/// Source of Cloudflare Turnstile tokens for check-in requests
+#[async_trait]
+pub trait TurnstileTokenSource: Send + Sync {
+    /// Solve the Turnstile widget rendered on `page_url` and return its token
+    async fn solve(&self, page_url: &str, account_name: &str) -> Result<String>;
+}
+
+/// Solves Turnstile in a visible browser window
+///
+/// Turnstile refuses headless browsers, and an interactive challenge can be
+/// clicked through by the user while the window is open.
+pub struct BrowserTurnstileSolver {
+    service: WafBypassService,
+}
+
+impl BrowserTurnstileSolver {
+    pub fn with_proxy(proxy_url: Option<String>) -> Self {
+        Self {
+            service: WafBypassService::with_proxy(false, proxy_url),
+        }
+    }
+}
+
+#[async_trait]
+impl TurnstileTokenSource for BrowserTurnstileSolver {
+    async fn solve(&self, page_url: &str, account_name: &str) -> Result<String> {
+        info!(
+            "[{}] Starting browser to solve Turnstile on {}",
+            account_name, page_url
+        );
+
+        let (browser, handler_task, profile) = self
+            .service
+            .launch_browser_with_config(page_url, account_name, WafChallengeKind::Cloudflare)
+            .await?;
+
+        let token = wait_for_token(&browser, page_url, account_name).await;
+
+        cleanup_browser(browser, handler_task, profile, account_name).await;
+
+        let token = token?;
+        info!("[{}] ✓ Turnstile solved", account_name);
+        Ok(token)
+    }
+}
+
+/// Open `page_url` and poll the Turnstile response field until it is filled in
+async fn wait_for_token(browser: &Browser, page_url: &str, account_name: &str) -> Result<String> {
+    let tuning = WafSettings::global().bypass;
+    let deadline = Instant::now() + tuning.max_total_time;
+
+    let page = browser
+        .new_page("about:blank")
+        .await
+        .context("Failed to create new page")?;
+    page.set_user_agent(USER_AGENT)
+        .await
+        .context("Failed to set user agent")?;
+
+    let navigation_timeout = tuning
+        .navigation_timeout
+        .min(deadline.saturating_duration_since(Instant::now()));
+    match tokio::time::timeout(navigation_timeout, page.goto(page_url)).await {
+        Ok(Ok(_)) => {}
+        Ok(Err(e)) => anyhow::bail!("Failed to navigate to {}: {}", page_url, e),
+        Err(_) => warn!(
+            "[{}] Navigation did not finish within {:?}, waiting for Turnstile anyway",
+            account_name, navigation_timeout
+        ),
+    }
+
+    loop {
+        match page.evaluate(READ_TOKEN_SCRIPT).await {
+            Ok(result) => {
+                let token: String = result.into_value().unwrap_or_default();
+                if !token.is_empty() {
+                    return Ok(token);
+                }
+            }
+            Err(e) => warn!("[{}] Failed to read Turnstile token: {}", account_name, e),
+        }
+
+        if Instant::now() >= deadline {
+            anyhow::bail!(
+                "Turnstile was not solved within {:?}",
+                tuning.max_total_time
+            );
+        }
+        tokio::time::sleep_until((Instant::now() + TOKEN_POLL_INTERVAL).min(deadline)).await;
+    }
+}
@@ -1,2 +1,31 @@
+use neuradock_domain::check_in::WafChallengeKind;
+
 pub const USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/138.0.0.0 Safari/537.36";
-pub const REQUIRED_WAF_COOKIES: &[&str] = &["acw_tc", "cdn_sec_tc", "acw_sc__v2"];
+
+/// Cookies set by the Aliyun WAF once its challenge is solved
+const ALIYUN_WAF_COOKIES: &[&str] = &["acw_tc", "cdn_sec_tc", "acw_sc__v2"];
+
+/// Cookies set by Cloudflare once its challenge is solved
+const CLOUDFLARE_WAF_COOKIES: &[&str] = &["cf_clearance"];
+
+/// Cookies to wait for after solving a challenge of the given kind
+pub fn required_waf_cookies(kind: WafChallengeKind) -> &'static [&'static str] {
+    match kind {
+        WafChallengeKind::Aliyun => ALIYUN_WAF_COOKIES,
+        WafChallengeKind::Cloudflare => CLOUDFLARE_WAF_COOKIES,
+    }
+}
+
+/// Extra browser flags for the given challenge kind
+///
+/// Cloudflare fingerprints automated browsers, so hide the automation flag
+/// and use the new headless mode, which is closer to a regular browser.
+pub fn challenge_browser_args(kind: WafChallengeKind) -> &'static [&'static str] {
+    match kind {
+        WafChallengeKind::Aliyun => &[],
+        WafChallengeKind::Cloudflare => &[
+            "--disable-blink-features=AutomationControlled",
+            "--disable-features=IsolateOrigins,site-per-process",
+        ],
+    }
+}
@@ -0,0 +1,66 @@
+use neuradock_domain::check_in::WafChallengeKind;
+
+/// Detects WAF challenge pages in HTTP responses
+///
+/// Recognizes the Aliyun `acw_sc__v2` JavaScript challenge used by the supported
+/// providers as well as the Cloudflare interstitial.
+pub struct WafDetector;
+
+/// Body markers (lowercase) that only appear on Aliyun challenge pages
+const ALIYUN_MARKERS: &[&str] = &["acw_sc__v2", "<script>var arg1="];
+
+/// Body markers (lowercase) that only appear on Cloudflare challenge pages
+const CLOUDFLARE_MARKERS: &[&str] = &["cf-chl", "just a moment...", "checking your browser"];
+
+impl WafDetector {
+    /// Whether a response body is a WAF challenge page
+    pub fn is_challenge_page(body: &str) -> bool {
+        Self::detect(body).is_some()
+    }
+
+    /// Kind of WAF challenge a response body is, if any
+    pub fn detect(body: &str) -> Option<WafChallengeKind> {
+        let body = body.to_lowercase();
+        let matches = |markers: &[&str]| markers.iter().any(|marker| body.contains(marker));
+
+        if matches(ALIYUN_MARKERS) {
+            Some(WafChallengeKind::Aliyun)
+        } else if matches(CLOUDFLARE_MARKERS) {
+            Some(WafChallengeKind::Cloudflare)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_aliyun_challenge() {
+        let body = "<html><script>var arg1='3F2A';</script><script>document.cookie='acw_sc__v2=' + x</script></html>";
+        assert!(WafDetector::is_challenge_page(body));
+        assert_eq!(WafDetector::detect(body), Some(WafChallengeKind::Aliyun));
+    }
+
+    #[test]
+    fn test_detects_cloudflare_challenge() {
+        let body = "<!DOCTYPE html><title>Just a moment...</title><div id=\"cf-chl-widget\"></div>";
+        assert!(WafDetector::is_challenge_page(body));
+        assert_eq!(
+            WafDetector::detect(body),
+            Some(WafChallengeKind::Cloudflare)
+        );
+    }
+
+    #[test]
+    fn test_regular_pages_are_not_challenges() {
+        assert!(!WafDetector::is_challenge_page(
+            "<!doctype html><html><head><title>New API</title></head><body><div id=\"root\"></div></body></html>"
+        ));
+        assert!(!WafDetector::is_challenge_page(
+            r#"{"success":true,"data":{}}"#
+        ));
+    }
+}
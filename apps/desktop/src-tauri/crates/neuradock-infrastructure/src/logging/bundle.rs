@@ -0,0 +1,159 @@
+//! 诊断包
+//!
+//! 把最近几天的日志和诊断报告打包为一个 zip，用于提交问题。
+//! 日志逐行脱敏后写入，cookie、Authorization 头等不会离开本机。
+
+use chrono::{Duration, NaiveDate};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use super::log_utils::redact_log_line;
+
+/// 日志文件名前缀，轮转后的文件为 `neuradock.log.YYYY-MM-DD`
+const LOG_FILE_PREFIX: &str = "neuradock.log.";
+
+/// 写入完成的诊断包
+#[derive(Debug, Clone)]
+pub struct DiagnosticBundle {
+    pub path: PathBuf,
+    pub size_bytes: u64,
+    /// 包含的日志文件名
+    pub log_files: Vec<String>,
+}
+
+/// 写入诊断包
+///
+/// - `logs/`：`today` 及之前 `log_days - 1` 天的日志，逐行脱敏
+/// - `reports/`：`reports` 中的每一项，格式化为 JSON
+pub fn write_diagnostic_bundle(
+    path: &Path,
+    log_dir: &Path,
+    log_days: u32,
+    today: NaiveDate,
+    reports: &[(&str, serde_json::Value)],
+) -> io::Result<DiagnosticBundle> {
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    let mut zip = ZipWriter::new(File::create(path)?);
+
+    let mut log_files = Vec::new();
+    for log_path in recent_log_files(log_dir, log_days, today)? {
+        let name = log_path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        zip.start_file(format!("logs/{}", name), options)
+            .map_err(io::Error::other)?;
+        write_redacted(&log_path, &mut zip)?;
+        log_files.push(name);
+    }
+
+    for (name, report) in reports {
+        zip.start_file(format!("reports/{}.json", name), options)
+            .map_err(io::Error::other)?;
+        let content = serde_json::to_string_pretty(report).map_err(io::Error::other)?;
+        zip.write_all(redact_log_line(&content).as_bytes())?;
+    }
+
+    zip.finish().map_err(io::Error::other)?;
+
+    Ok(DiagnosticBundle {
+        path: path.to_path_buf(),
+        size_bytes: std::fs::metadata(path)?.len(),
+        log_files,
+    })
+}
+
+/// 最近 `days` 天的日志文件，按日期从旧到新
+fn recent_log_files(log_dir: &Path, days: u32, today: NaiveDate) -> io::Result<Vec<PathBuf>> {
+    if days == 0 || !log_dir.exists() {
+        return Ok(Vec::new());
+    }
+    let oldest = today - Duration::days(days as i64 - 1);
+
+    let mut files: Vec<(NaiveDate, PathBuf)> = std::fs::read_dir(log_dir)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .filter_map(|path| {
+            let date = path
+                .file_name()?
+                .to_str()?
+                .strip_prefix(LOG_FILE_PREFIX)
+                .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())?;
+            Some((date, path))
+        })
+        .filter(|(date, _)| (oldest..=today).contains(date))
+        .collect();
+    files.sort();
+
+    Ok(files.into_iter().map(|(_, path)| path).collect())
+}
+
+/// 逐行脱敏复制，不把整个日志文件读入内存
+fn write_redacted(source: &Path, out: &mut impl Write) -> io::Result<()> {
+    let mut reader = BufReader::new(File::open(source)?);
+    let mut line = Vec::new();
+    loop {
+        line.clear();
+        if reader.read_until(b'\n', &mut line)? == 0 {
+            return Ok(());
+        }
+        let text = String::from_utf8_lossy(&line);
+        out.write_all(redact_log_line(&text).as_bytes())?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, day).unwrap()
+    }
+
+    #[test]
+    fn test_bundle_contains_recent_redacted_logs_and_reports() {
+        let dir = tempfile::tempdir().unwrap();
+        let log_dir = dir.path().join("logs");
+        std::fs::create_dir_all(&log_dir).unwrap();
+        std::fs::write(log_dir.join("neuradock.log.2026-03-01"), "too old\n").unwrap();
+        std::fs::write(
+            log_dir.join("neuradock.log.2026-03-09"),
+            "{\"message\":\"Cookie: session=abc\"}\nplain line\n",
+        )
+        .unwrap();
+        std::fs::write(log_dir.join("neuradock.log.2026-03-10"), "today\n").unwrap();
+        std::fs::write(log_dir.join("notes.txt"), "not a log\n").unwrap();
+
+        let path = dir.path().join("diagnostics.zip");
+        let reports = [("system", serde_json::json!({ "os": "linux" }))];
+        let bundle = write_diagnostic_bundle(&path, &log_dir, 3, date(10), &reports).unwrap();
+
+        assert_eq!(
+            bundle.log_files,
+            vec!["neuradock.log.2026-03-09", "neuradock.log.2026-03-10"]
+        );
+        assert!(bundle.size_bytes > 0);
+
+        let mut archive = zip::ZipArchive::new(File::open(&path).unwrap()).unwrap();
+        let mut log = String::new();
+        archive
+            .by_name("logs/neuradock.log.2026-03-09")
+            .unwrap()
+            .read_to_string(&mut log)
+            .unwrap();
+        assert_eq!(log, "{\"message\":\"Cookie: [REDACTED]\"}\nplain line\n");
+
+        let mut report = String::new();
+        archive
+            .by_name("reports/system.json")
+            .unwrap()
+            .read_to_string(&mut report)
+            .unwrap();
+        assert!(report.contains("linux"));
+    }
+}
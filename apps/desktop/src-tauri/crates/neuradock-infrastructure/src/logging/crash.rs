@@ -0,0 +1,294 @@
+//! 崩溃报告
+//!
+//! panic hook 将 panic 消息、位置、backtrace、应用版本、系统信息以及最近的日志
+//! 写入日志目录下的 `crashes/`，每次崩溃一个 JSON 文件，超过数量上限的旧报告会被删除。
+//! 下次启动时通过 [`take_unnotified_crash_reports`] 检查上次运行是否崩溃。
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::backtrace::{Backtrace, BacktraceStatus};
+use std::io::{self, Write};
+use std::panic::PanicHookInfo;
+use std::path::{Path, PathBuf};
+
+use super::log_utils::redact_log_line;
+use super::query::{log_files, ReverseLines};
+
+/// 日志目录下存放崩溃报告的子目录
+pub const CRASH_DIR: &str = "crashes";
+
+/// 保留的崩溃报告数量，超出的旧报告会被删除
+pub const MAX_CRASH_REPORTS: usize = 20;
+
+/// 报告中附带的最近日志行数
+pub const CRASH_LOG_LINES: usize = 200;
+
+/// 报告文件名为 `crash-YYYYMMDD-HHMMSS-mmm.json`，按字典序即按时间排序
+const CRASH_FILE_PREFIX: &str = "crash-";
+const CRASH_FILE_EXTENSION: &str = ".json";
+
+/// 记录最近一次已提醒用户的报告文件名
+const NOTIFIED_MARKER: &str = ".notified";
+
+/// 一次崩溃的报告
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CrashReport {
+    pub occurred_at: DateTime<Utc>,
+    pub message: String,
+    /// `file:line:column`
+    pub location: Option<String>,
+    pub thread: Option<String>,
+    /// 平台不支持时为空
+    pub backtrace: Option<String>,
+    pub version: String,
+    pub os: String,
+    pub arch: String,
+    /// 崩溃前最近的日志（已脱敏），从旧到新
+    pub recent_logs: Vec<String>,
+}
+
+impl CrashReport {
+    /// 从 panic 信息生成报告，日志目录可用时附带最近的日志
+    pub fn from_panic(info: &PanicHookInfo<'_>, log_dir: Option<&Path>) -> Self {
+        let message = info
+            .payload()
+            .downcast_ref::<&str>()
+            .copied()
+            .or_else(|| info.payload().downcast_ref::<String>().map(|s| s.as_str()))
+            .unwrap_or("<non-string panic payload>");
+
+        // 崩溃时无论 RUST_BACKTRACE 如何设置都尝试捕获
+        let backtrace = Backtrace::force_capture();
+        let backtrace =
+            (backtrace.status() == BacktraceStatus::Captured).then(|| backtrace.to_string());
+
+        let recent_logs = log_dir
+            .and_then(|dir| recent_log_lines(dir, CRASH_LOG_LINES).ok())
+            .unwrap_or_default();
+
+        Self {
+            occurred_at: Utc::now(),
+            message: redact_log_line(message),
+            location: info
+                .location()
+                .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column())),
+            thread: std::thread::current().name().map(str::to_string),
+            backtrace,
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            recent_logs,
+        }
+    }
+}
+
+/// 磁盘上的一份崩溃报告
+#[derive(Debug, Clone, PartialEq)]
+pub struct CrashReportFile {
+    pub file_name: String,
+    pub path: PathBuf,
+    pub report: CrashReport,
+}
+
+/// 写入崩溃报告并删除超出数量上限的旧报告，返回报告路径
+pub fn write_crash_report(log_dir: &Path, report: &CrashReport) -> io::Result<PathBuf> {
+    let dir = log_dir.join(CRASH_DIR);
+    std::fs::create_dir_all(&dir)?;
+
+    let path = dir.join(format!(
+        "{}{}{}",
+        CRASH_FILE_PREFIX,
+        report.occurred_at.format("%Y%m%d-%H%M%S-%3f"),
+        CRASH_FILE_EXTENSION
+    ));
+    let mut file = std::fs::File::create(&path)?;
+    serde_json::to_writer_pretty(&mut file, report)?;
+    file.flush()?;
+
+    let names = crash_file_names(&dir)?;
+    for name in names.iter().skip(MAX_CRASH_REPORTS) {
+        let _ = std::fs::remove_file(dir.join(name));
+    }
+
+    Ok(path)
+}
+
+/// 所有崩溃报告，最新的在前；无法解析的文件会被跳过
+pub fn list_crash_reports(log_dir: &Path) -> io::Result<Vec<CrashReportFile>> {
+    let dir = log_dir.join(CRASH_DIR);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut reports = Vec::new();
+    for file_name in crash_file_names(&dir)? {
+        let path = dir.join(&file_name);
+        let parsed = std::fs::read(&path)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<CrashReport>(&bytes).ok());
+        match parsed {
+            Some(report) => reports.push(CrashReportFile {
+                file_name,
+                path,
+                report,
+            }),
+            None => tracing::warn!("Skipping unreadable crash report {}", path.display()),
+        }
+    }
+    Ok(reports)
+}
+
+/// 按文件名查找崩溃报告，名称必须是报告文件本身（不能包含路径）
+pub fn find_crash_report(log_dir: &Path, file_name: &str) -> Option<PathBuf> {
+    if !is_crash_file_name(file_name) || file_name.contains(['/', '\\']) {
+        return None;
+    }
+    let path = log_dir.join(CRASH_DIR).join(file_name);
+    path.is_file().then_some(path)
+}
+
+/// 上次提醒之后新增的崩溃报告，最新的在前
+///
+/// 返回后会记录最新的报告，同一份报告只会返回一次。
+pub fn take_unnotified_crash_reports(log_dir: &Path) -> io::Result<Vec<CrashReportFile>> {
+    let dir = log_dir.join(CRASH_DIR);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let marker = dir.join(NOTIFIED_MARKER);
+    let last_notified = std::fs::read_to_string(&marker).unwrap_or_default();
+    let last_notified = last_notified.trim();
+
+    let reports: Vec<CrashReportFile> = list_crash_reports(log_dir)?
+        .into_iter()
+        .filter(|r| r.file_name.as_str() > last_notified)
+        .collect();
+    if let Some(newest) = reports.first() {
+        std::fs::write(&marker, &newest.file_name)?;
+    }
+    Ok(reports)
+}
+
+/// 最新日志文件的最后 `count` 行（已脱敏），从旧到新
+pub fn recent_log_lines(log_dir: &Path, count: usize) -> io::Result<Vec<String>> {
+    let Some(path) = log_files(log_dir)?.into_iter().next() else {
+        return Ok(Vec::new());
+    };
+
+    let mut reader = ReverseLines::open(&path)?;
+    let mut lines = Vec::new();
+    while lines.len() < count {
+        let Some(line) = reader.next_line()? else {
+            break;
+        };
+        let line = String::from_utf8_lossy(&line);
+        let line = line.trim_end();
+        if !line.is_empty() {
+            lines.push(redact_log_line(line));
+        }
+    }
+    lines.reverse();
+    Ok(lines)
+}
+
+/// 目录中的报告文件名，最新的在前
+fn crash_file_names(dir: &Path) -> io::Result<Vec<String>> {
+    let mut names: Vec<String> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_file())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| is_crash_file_name(name))
+        .collect();
+    names.sort();
+    names.reverse();
+    Ok(names)
+}
+
+fn is_crash_file_name(name: &str) -> bool {
+    name.starts_with(CRASH_FILE_PREFIX) && name.ends_with(CRASH_FILE_EXTENSION)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn report(second: u32) -> CrashReport {
+        CrashReport {
+            occurred_at: Utc.with_ymd_and_hms(2026, 1, 1, 10, 0, second).unwrap(),
+            message: format!("boom {}", second),
+            location: Some("src/main.rs:1:1".to_string()),
+            thread: Some("main".to_string()),
+            backtrace: None,
+            version: "1.0.0".to_string(),
+            os: "linux".to_string(),
+            arch: "x86_64".to_string(),
+            recent_logs: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_write_prunes_oldest_reports() {
+        let dir = tempfile::tempdir().unwrap();
+        for second in 0..(MAX_CRASH_REPORTS as u32 + 3) {
+            write_crash_report(dir.path(), &report(second)).unwrap();
+        }
+
+        let reports = list_crash_reports(dir.path()).unwrap();
+
+        assert_eq!(reports.len(), MAX_CRASH_REPORTS);
+        assert_eq!(reports[0].report, report(MAX_CRASH_REPORTS as u32 + 2));
+        assert_eq!(reports.last().unwrap().report, report(3));
+    }
+
+    #[test]
+    fn test_unnotified_reports_are_returned_once() {
+        let dir = tempfile::tempdir().unwrap();
+        write_crash_report(dir.path(), &report(1)).unwrap();
+        write_crash_report(dir.path(), &report(2)).unwrap();
+
+        assert_eq!(take_unnotified_crash_reports(dir.path()).unwrap().len(), 2);
+        assert!(take_unnotified_crash_reports(dir.path())
+            .unwrap()
+            .is_empty());
+
+        write_crash_report(dir.path(), &report(3)).unwrap();
+        let new = take_unnotified_crash_reports(dir.path()).unwrap();
+        assert_eq!(new.len(), 1);
+        assert_eq!(new[0].report, report(3));
+    }
+
+    #[test]
+    fn test_find_rejects_paths_outside_crash_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_crash_report(dir.path(), &report(1)).unwrap();
+        let name = path.file_name().unwrap().to_str().unwrap();
+
+        assert_eq!(find_crash_report(dir.path(), name), Some(path.clone()));
+        assert_eq!(find_crash_report(dir.path(), "crash-../../x.json"), None);
+        assert_eq!(
+            find_crash_report(dir.path(), "neuradock.log.2026-01-01"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_recent_log_lines_tail_newest_file_redacted() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("neuradock.log.2025-12-31"), "old\n").unwrap();
+        let lines: Vec<String> = (0..5).map(|i| format!("line {}", i)).collect();
+        std::fs::write(
+            dir.path().join("neuradock.log.2026-01-01"),
+            format!("{}\nCookie: session=secret\n", lines.join("\n")),
+        )
+        .unwrap();
+
+        let tail = recent_log_lines(dir.path(), 3).unwrap();
+
+        assert_eq!(tail.len(), 3);
+        assert_eq!(tail[0], "line 3");
+        assert_eq!(tail[1], "line 4");
+        assert!(!tail[2].contains("secret"));
+    }
+}
@@ -0,0 +1,142 @@
+//! 日志过滤器
+//!
+//! 在默认级别之上叠加按模块前缀的级别覆盖（例如 `neuradock_infrastructure::http=trace`），
+//! 修改后通过 reload 句柄立即生效。显式设置 RUST_LOG 时以环境变量为准，覆盖不生效。
+
+use std::collections::BTreeMap;
+use std::sync::{OnceLock, RwLock};
+use tracing::Level;
+use tracing_subscriber::EnvFilter;
+
+/// 替换某一层的过滤器
+pub(super) type FilterReloader =
+    Box<dyn Fn(EnvFilter) -> Result<(), tracing_subscriber::reload::Error> + Send + Sync>;
+
+/// 模块前缀 → 级别
+static MODULE_LEVELS: RwLock<BTreeMap<String, Level>> = RwLock::new(BTreeMap::new());
+
+pub(super) static FILE_FILTER_RELOADER: OnceLock<FilterReloader> = OnceLock::new();
+pub(super) static STDOUT_FILTER_RELOADER: OnceLock<FilterReloader> = OnceLock::new();
+
+/// 文件日志的默认级别
+fn file_default_directives() -> &'static str {
+    // 生产环境：INFO 及以上
+    // 开发环境：DEBUG 及以上
+    if cfg!(debug_assertions) {
+        "debug,neuradock=trace"
+    } else {
+        "info,neuradock=info"
+    }
+}
+
+/// stdout 日志的默认级别（仅 debug 模式输出）
+const STDOUT_DEFAULT_DIRECTIVES: &str = "debug,neuradock=trace";
+
+/// 获取文件日志的过滤器
+pub(super) fn get_file_filter() -> EnvFilter {
+    build_filter(file_default_directives(), "info")
+}
+
+/// 获取 stdout 日志的过滤器
+pub(super) fn get_stdout_filter() -> EnvFilter {
+    build_filter(STDOUT_DEFAULT_DIRECTIVES, "debug")
+}
+
+/// RUST_LOG 优先，否则为默认级别加上模块覆盖
+fn build_filter(default_directives: &str, fallback: &str) -> EnvFilter {
+    if let Ok(filter) = EnvFilter::try_from_default_env() {
+        return filter;
+    }
+
+    let levels = MODULE_LEVELS
+        .read()
+        .map(|levels| levels.clone())
+        .unwrap_or_default();
+    EnvFilter::try_new(compose_directives(default_directives, &levels))
+        .or_else(|_| EnvFilter::try_new(default_directives))
+        .unwrap_or_else(|_| EnvFilter::new(fallback))
+}
+
+/// 默认级别后追加 `模块=级别`，更具体的前缀优先
+fn compose_directives(default_directives: &str, levels: &BTreeMap<String, Level>) -> String {
+    let mut directives = default_directives.to_string();
+    for (module, level) in levels {
+        directives.push_str(&format!(",{}={}", module, level.as_str().to_lowercase()));
+    }
+    directives
+}
+
+/// 校验并规范化模块前缀，例如 `neuradock_infrastructure::http`
+pub fn normalize_log_module(module: &str) -> anyhow::Result<String> {
+    let module = module.trim();
+    let valid = !module.is_empty()
+        && module
+            .split("::")
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_alphanumeric() || c == '_'));
+    if !valid {
+        anyhow::bail!(
+            "Invalid module '{}': expected a module path such as neuradock_infrastructure::http",
+            module
+        );
+    }
+    Ok(module.to_string())
+}
+
+/// 当前的模块级别覆盖
+pub fn module_log_levels() -> BTreeMap<String, Level> {
+    MODULE_LEVELS
+        .read()
+        .map(|levels| levels.clone())
+        .unwrap_or_default()
+}
+
+/// 替换全部模块级别覆盖并立即生效
+pub fn set_module_log_levels(levels: BTreeMap<String, Level>) -> anyhow::Result<()> {
+    for module in levels.keys() {
+        normalize_log_module(module)?;
+    }
+    *MODULE_LEVELS
+        .write()
+        .map_err(|_| anyhow::anyhow!("Module log levels lock poisoned"))? = levels;
+
+    if let Some(reload) = FILE_FILTER_RELOADER.get() {
+        reload(get_file_filter())?;
+    }
+    if let Some(reload) = STDOUT_FILTER_RELOADER.get() {
+        reload(get_stdout_filter())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compose_directives_appends_module_levels() {
+        let levels = BTreeMap::from([
+            ("neuradock_infrastructure::http".to_string(), Level::TRACE),
+            ("sqlx".to_string(), Level::WARN),
+        ]);
+
+        let directives = compose_directives("info,neuradock=info", &levels);
+
+        assert_eq!(
+            directives,
+            "info,neuradock=info,neuradock_infrastructure::http=trace,sqlx=warn"
+        );
+        assert!(EnvFilter::try_new(&directives).is_ok());
+    }
+
+    #[test]
+    fn test_normalize_log_module() {
+        assert_eq!(
+            normalize_log_module(" neuradock_infrastructure::http ").unwrap(),
+            "neuradock_infrastructure::http"
+        );
+        assert!(normalize_log_module("").is_err());
+        assert!(normalize_log_module("sqlx=trace").is_err());
+        assert!(normalize_log_module("neuradock::").is_err());
+        assert!(normalize_log_module("a,b").is_err());
+    }
+}
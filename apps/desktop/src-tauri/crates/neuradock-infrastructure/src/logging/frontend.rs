@@ -0,0 +1,228 @@
+//! 前端日志上报
+//!
+//! 前端每行日志都会经 IPC 上报，渲染循环里的日志可能同时刷爆 IPC 和日志文件。
+//! 写入前会校验级别并截断 target、消息和字段，且按秒限流：超出的日志被丢弃，
+//! 下一个窗口的第一条日志之前补写一条汇总，说明丢弃了多少条。
+
+use std::borrow::Cow;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::Level;
+
+/// 每秒最多写入的前端日志条数
+const MAX_FRONTEND_LOGS_PER_SECOND: u32 = 100;
+
+/// 单条消息的最大字节数，超出部分截断
+const MAX_MESSAGE_BYTES: usize = 8 * 1024;
+
+/// target 的最大字节数
+const MAX_TARGET_BYTES: usize = 128;
+
+/// 序列化后字段的最大字节数，超出时只记录大小
+const MAX_FIELDS_BYTES: usize = 4 * 1024;
+
+const RATE_WINDOW: Duration = Duration::from_secs(1);
+
+static RATE_LIMITER: Mutex<RateLimiter> = Mutex::new(RateLimiter::new());
+
+/// 前端日志结构
+#[derive(Debug, serde::Deserialize)]
+pub struct FrontendLog {
+    pub level: String,
+    pub target: String,
+    pub message: String,
+    #[serde(default)]
+    pub fields: Option<serde_json::Value>,
+}
+
+/// 记录来自前端的日志
+pub fn log_from_frontend(log: FrontendLog) {
+    log_batch_from_frontend(vec![log]);
+}
+
+/// 批量记录来自前端的日志，按顺序写入，超出速率限制的部分被丢弃
+pub fn log_batch_from_frontend(logs: Vec<FrontendLog>) {
+    let now = Instant::now();
+    for log in logs {
+        let (admitted, dropped) = RATE_LIMITER
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .admit(now);
+
+        if dropped > 0 {
+            tracing::warn!(
+                target: "frontend",
+                source = "frontend",
+                dropped,
+                limit = MAX_FRONTEND_LOGS_PER_SECOND,
+                "Dropped {} frontend log entries over the limit of {} per second",
+                dropped,
+                MAX_FRONTEND_LOGS_PER_SECOND
+            );
+        }
+        if admitted {
+            write_frontend_log(&log);
+        }
+    }
+}
+
+/// 前端日志通过 "frontend" target 写入，原 target 放在 frontend_target 字段
+macro_rules! frontend_event {
+    ($level:expr, $target:expr, $fields:expr, $message:expr) => {
+        tracing::event!(
+            target: "frontend",
+            $level,
+            source = "frontend",
+            frontend_target = %$target,
+            fields = ?$fields,
+            "{}",
+            $message
+        )
+    };
+}
+
+fn write_frontend_log(log: &FrontendLog) {
+    let target = truncate_utf8(&log.target, MAX_TARGET_BYTES);
+    let message = truncate_utf8(&log.message, MAX_MESSAGE_BYTES);
+    let fields = log.fields.as_ref().map(clamp_fields);
+    let fields = fields.as_ref();
+
+    match parse_level(&log.level) {
+        Level::ERROR => frontend_event!(Level::ERROR, target, fields, message),
+        Level::WARN => frontend_event!(Level::WARN, target, fields, message),
+        Level::DEBUG => frontend_event!(Level::DEBUG, target, fields, message),
+        Level::TRACE => frontend_event!(Level::TRACE, target, fields, message),
+        _ => frontend_event!(Level::INFO, target, fields, message),
+    }
+}
+
+/// 解析前端级别，未知级别按 INFO 处理
+fn parse_level(level: &str) -> Level {
+    match level.trim().to_lowercase().as_str() {
+        "error" => Level::ERROR,
+        "warn" | "warning" => Level::WARN,
+        "debug" => Level::DEBUG,
+        "trace" => Level::TRACE,
+        _ => Level::INFO,
+    }
+}
+
+/// 按字节截断到字符边界，并注明截掉的字节数
+fn truncate_utf8(value: &str, max_bytes: usize) -> Cow<'_, str> {
+    if value.len() <= max_bytes {
+        return Cow::Borrowed(value);
+    }
+
+    let mut end = max_bytes;
+    while !value.is_char_boundary(end) {
+        end -= 1;
+    }
+    Cow::Owned(format!(
+        "{}…[truncated {} bytes]",
+        &value[..end],
+        value.len() - end
+    ))
+}
+
+/// 字段过大时用其大小代替
+fn clamp_fields(fields: &serde_json::Value) -> serde_json::Value {
+    let size = serde_json::to_string(fields).map_or(0, |json| json.len());
+    if size <= MAX_FIELDS_BYTES {
+        fields.clone()
+    } else {
+        serde_json::Value::String(format!("[fields omitted: {} bytes]", size))
+    }
+}
+
+/// 固定一秒窗口的计数限流
+struct RateLimiter {
+    window_start: Option<Instant>,
+    written: u32,
+    dropped: u64,
+}
+
+impl RateLimiter {
+    const fn new() -> Self {
+        Self {
+            window_start: None,
+            written: 0,
+            dropped: 0,
+        }
+    }
+
+    /// 返回本条是否可以写入，以及新窗口开始时上一窗口丢弃的条数
+    fn admit(&mut self, now: Instant) -> (bool, u64) {
+        let mut previous_dropped = 0;
+        let window_elapsed = self
+            .window_start
+            .is_none_or(|start| now.saturating_duration_since(start) >= RATE_WINDOW);
+        if window_elapsed {
+            previous_dropped = std::mem::take(&mut self.dropped);
+            self.window_start = Some(now);
+            self.written = 0;
+        }
+
+        if self.written < MAX_FRONTEND_LOGS_PER_SECOND {
+            self.written += 1;
+            (true, previous_dropped)
+        } else {
+            self.dropped += 1;
+            (false, previous_dropped)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limiter_drops_over_limit_and_reports_in_next_window() {
+        let mut limiter = RateLimiter::new();
+        let start = Instant::now();
+
+        for _ in 0..MAX_FRONTEND_LOGS_PER_SECOND {
+            assert_eq!(limiter.admit(start), (true, 0));
+        }
+        assert_eq!(limiter.admit(start), (false, 0));
+        assert_eq!(
+            limiter.admit(start + Duration::from_millis(900)),
+            (false, 0)
+        );
+
+        // 新窗口：写入并报告上一窗口丢弃的 2 条
+        assert_eq!(limiter.admit(start + Duration::from_secs(1)), (true, 2));
+        assert_eq!(
+            limiter.admit(start + Duration::from_millis(1500)),
+            (true, 0)
+        );
+    }
+
+    #[test]
+    fn test_parse_level_falls_back_to_info() {
+        assert_eq!(parse_level("ERROR"), Level::ERROR);
+        assert_eq!(parse_level(" warning "), Level::WARN);
+        assert_eq!(parse_level("trace"), Level::TRACE);
+        assert_eq!(parse_level("fatal"), Level::INFO);
+        assert_eq!(parse_level(""), Level::INFO);
+    }
+
+    #[test]
+    fn test_truncate_utf8_keeps_char_boundaries() {
+        assert_eq!(truncate_utf8("short", 10), "short");
+        // "日" 占 3 字节，截断点落在字符中间时回退
+        assert_eq!(truncate_utf8("日志内容", 4), "日…[truncated 9 bytes]");
+    }
+
+    #[test]
+    fn test_clamp_fields_replaces_oversized_fields() {
+        let small = serde_json::json!({"component": "Dashboard"});
+        assert_eq!(clamp_fields(&small), small);
+
+        let large = serde_json::json!({"payload": "x".repeat(MAX_FIELDS_BYTES)});
+        let clamped = clamp_fields(&large);
+        assert!(clamped
+            .as_str()
+            .is_some_and(|note| note.starts_with("[fields omitted:")));
+    }
+}
@@ -4,7 +4,12 @@
 //! - 结构化 JSON 日志（生产环境）- One-line JSON 格式
 //! - 人类可读彩色日志（开发环境）
 //! - 日志文件轮转
-//! - 前端日志上报
+//! - 前端日志上报（支持批量，按秒限流）
+//! - 敏感信息脱敏（cookie、token、Authorization 等）
+//! - 应用内日志查询
+//! - 诊断包导出（日志脱敏）
+//! - 按模块覆盖日志级别（即时生效）
+//! - 崩溃报告（panic 时写入，下次启动提醒）
 //!
 //! 每条日志包含完整元数据：
 //! - timestamp: ISO 8601 带时区，毫秒精度（例如 2025-12-09T10:32:15.123+08:00）
@@ -19,7 +24,21 @@
 //! - version: 应用版本
 
 // Re-export log masking utilities for use in logging
+mod bundle;
+mod crash;
+mod filter;
+mod frontend;
 pub mod log_utils;
+mod query;
+
+pub use bundle::{write_diagnostic_bundle, DiagnosticBundle};
+pub use crash::{
+    find_crash_report, list_crash_reports, take_unnotified_crash_reports, write_crash_report,
+    CrashReport, CrashReportFile, CRASH_DIR, MAX_CRASH_REPORTS,
+};
+pub use filter::{module_log_levels, normalize_log_module, set_module_log_levels};
+pub use frontend::{log_batch_from_frontend, log_from_frontend, FrontendLog};
+pub use query::{query_logs, LogEntry, LogPage, LogQuery};
 
 use log::LevelFilter;
 use std::path::PathBuf;
@@ -27,7 +46,9 @@ use std::sync::OnceLock;
 use tracing::Level;
 use tracing_appender::{non_blocking::WorkerGuard, rolling};
 use tracing_log::LogTracer;
-use tracing_subscriber::{fmt, layer::SubscriberExt, EnvFilter, Layer, Registry};
+use tracing_subscriber::{fmt, layer::SubscriberExt, reload, Layer, Registry};
+
+use filter::{get_file_filter, get_stdout_filter, FILE_FILTER_RELOADER, STDOUT_FILTER_RELOADER};
 
 static LOG_DIR: OnceLock<PathBuf> = OnceLock::new();
 static APP_VERSION: OnceLock<String> = OnceLock::new();
@@ -64,6 +85,10 @@ pub fn init_logger(log_dir: PathBuf) -> anyhow::Result<()> {
     let (non_blocking, guard) = tracing_appender::non_blocking(file_appender);
     let _ = FILE_GUARD.set(guard);
 
+    // 过滤器可重新加载，模块级别覆盖修改后立即生效
+    let (file_filter, file_filter_handle) = reload::Layer::new(get_file_filter());
+    let _ = FILE_FILTER_RELOADER.set(Box::new(move |filter| file_filter_handle.reload(filter)));
+
     // JSON 层：写入文件（one-line JSON 格式）
     let json_layer = fmt::layer()
         .with_writer(non_blocking)
@@ -79,10 +104,13 @@ pub fn init_logger(log_dir: PathBuf) -> anyhow::Result<()> {
             "%Y-%m-%dT%H:%M:%S%.3f%:z".to_string(), // ISO 8601 带时区和毫秒
         ))
         .event_format(JsonFormatter::new())
-        .with_filter(get_file_filter());
+        .with_filter(file_filter);
 
     // 人类可读层：输出到 stdout（仅 debug 模式）
     let stdout_layer = if cfg!(debug_assertions) {
+        let (stdout_filter, stdout_filter_handle) = reload::Layer::new(get_stdout_filter());
+        let _ =
+            STDOUT_FILTER_RELOADER.set(Box::new(move |filter| stdout_filter_handle.reload(filter)));
         Some(
             fmt::layer()
                 .with_target(true)
@@ -95,7 +123,7 @@ pub fn init_logger(log_dir: PathBuf) -> anyhow::Result<()> {
                     "%Y-%m-%d %H:%M:%S%.3f".to_string(),
                 ))
                 .event_format(HumanReadableFormatter::new())
-                .with_filter(get_stdout_filter()),
+                .with_filter(stdout_filter),
         )
     } else {
         None
@@ -123,116 +151,11 @@ pub fn init_logger(log_dir: PathBuf) -> anyhow::Result<()> {
     Ok(())
 }
 
-/// 获取文件日志的过滤器
-fn get_file_filter() -> EnvFilter {
-    // 默认 INFO 级别，可通过 RUST_LOG 环境变量覆盖
-    // 生产环境：INFO 及以上
-    // 开发环境：DEBUG 及以上
-    let default_level = if cfg!(debug_assertions) {
-        "debug,neuradock=trace"
-    } else {
-        "info,neuradock=info"
-    };
-
-    EnvFilter::try_from_default_env()
-        .or_else(|_| EnvFilter::try_new(default_level))
-        .unwrap_or_else(|_| EnvFilter::new("info"))
-}
-
-/// 获取 stdout 日志的过滤器
-fn get_stdout_filter() -> EnvFilter {
-    // 仅在 debug 模式下输出到 stdout
-    // 默认 DEBUG 级别
-    EnvFilter::try_from_default_env()
-        .or_else(|_| EnvFilter::try_new("debug,neuradock=trace"))
-        .unwrap_or_else(|_| EnvFilter::new("debug"))
-}
-
 /// 获取日志目录路径
 pub fn get_log_dir() -> Option<PathBuf> {
     LOG_DIR.get().cloned()
 }
 
-/// 前端日志结构
-#[derive(Debug, serde::Deserialize)]
-pub struct FrontendLog {
-    pub level: String,
-    pub target: String,
-    pub message: String,
-    #[serde(default)]
-    pub fields: Option<serde_json::Value>,
-}
-
-/// 记录来自前端的日志
-pub fn log_from_frontend(log: FrontendLog) {
-    let target_val = log.target.clone();
-    let message = log.message.as_str();
-    let fields = log.fields.as_ref();
-
-    match log.level.to_lowercase().as_str() {
-        "error" => {
-            tracing::error!(
-                target: "frontend",
-                source = "frontend",
-                frontend_target = %target_val,
-                fields = ?fields,
-                "{}",
-                message
-            );
-        }
-        "warn" => {
-            tracing::warn!(
-                target: "frontend",
-                source = "frontend",
-                frontend_target = %target_val,
-                fields = ?fields,
-                "{}",
-                message
-            );
-        }
-        "info" => {
-            tracing::info!(
-                target: "frontend",
-                source = "frontend",
-                frontend_target = %target_val,
-                fields = ?fields,
-                "{}",
-                message
-            );
-        }
-        "debug" => {
-            tracing::debug!(
-                target: "frontend",
-                source = "frontend",
-                frontend_target = %target_val,
-                fields = ?fields,
-                "{}",
-                message
-            );
-        }
-        "trace" => {
-            tracing::trace!(
-                target: "frontend",
-                source = "frontend",
-                frontend_target = %target_val,
-                fields = ?fields,
-                "{}",
-                message
-            );
-        }
-        _ => {
-            tracing::info!(
-                target: "frontend",
-                source = "frontend",
-                frontend_target = %target_val,
-                fields = ?fields,
-                "{}",
-                message
-            );
-        }
-    }
-}
-
 // ============================================================
 // 自定义格式化器
 // ============================================================
@@ -429,6 +352,9 @@ where
 }
 
 /// 访问者模式收集事件字段
+///
+/// 文本字段在写出前脱敏：名为 cookie/token/key/authorization 等的字段整体替换，
+/// 其余文本（包括 message）按 [`log_utils::redact_log_line`] 的规则处理。
 struct JsonVisitor {
     fields: serde_json::Map<String, serde_json::Value>,
 }
@@ -439,6 +365,16 @@ impl JsonVisitor {
             fields: serde_json::Map::new(),
         }
     }
+
+    fn insert_text(&mut self, field: &tracing::field::Field, value: &str) {
+        let value = if log_utils::is_secret_field(field.name()) {
+            log_utils::REDACTED.to_string()
+        } else {
+            log_utils::redact_log_line(value)
+        };
+        self.fields
+            .insert(field.name().to_string(), serde_json::json!(value));
+    }
 }
 
 impl tracing::field::Visit for JsonVisitor {
@@ -463,14 +399,60 @@ impl tracing::field::Visit for JsonVisitor {
     }
 
     fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
-        self.fields
-            .insert(field.name().to_string(), serde_json::json!(value));
+        self.insert_text(field, value);
     }
 
     fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
-        self.fields.insert(
-            field.name().to_string(),
-            serde_json::json!(format!("{:?}", value)),
+        self.insert_text(field, &format!("{:?}", value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+    use std::sync::{Arc, Mutex};
+
+    /// Collects formatted output in memory
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_json_output_masks_session_cookie() {
+        let buffer = Buffer::default();
+        let writer = buffer.clone();
+        let subscriber = Registry::default().with(
+            fmt::layer()
+                .with_writer(move || writer.clone())
+                .event_format(JsonFormatter::new()),
         );
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!(
+                target: "neuradock::test",
+                cookie = "session=fake-session-123",
+                account = "Main",
+                "Sending Cookie: session=fake-session-123; theme=dark"
+            );
+        });
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let line: serde_json::Value = serde_json::from_str(output.trim()).unwrap();
+
+        assert!(!output.contains("fake-session-123"));
+        assert_eq!(line["message"], "Sending Cookie: [REDACTED]");
+        assert_eq!(line["fields"]["cookie"], "[REDACTED]");
+        assert_eq!(line["fields"]["account"], "Main");
     }
 }
@@ -0,0 +1,400 @@
+//! 日志查询
+//!
+//! 从文件末尾按块倒序读取 JSON 日志，逐行解析并过滤，
+//! 无需把整个日志文件载入内存。无法解析的行（写入中被截断、损坏）会被跳过并计数。
+
+use chrono::{DateTime, FixedOffset, Utc};
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use tracing::Level;
+
+/// 日志文件名前缀，轮转后的文件为 `neuradock.log.YYYY-MM-DD`
+const LOG_FILE_PREFIX: &str = "neuradock.log";
+
+/// 倒序读取时每次读取的块大小
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// 结构化字段中的关联 ID 键名
+const CORRELATION_ID_FIELD: &str = "correlation_id";
+
+/// 日志查询条件
+#[derive(Debug, Clone)]
+pub struct LogQuery {
+    /// 最低级别，例如 WARN 会返回 WARN 和 ERROR
+    pub min_level: Option<Level>,
+    /// target 前缀，例如 `neuradock_app::application`
+    pub target_prefix: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    /// 在 message 和结构化字段中搜索（不区分大小写）
+    pub text: Option<String>,
+    pub correlation_id: Option<String>,
+    /// 同时读取上一个轮转的日志文件
+    pub include_previous: bool,
+    pub limit: usize,
+}
+
+impl Default for LogQuery {
+    fn default() -> Self {
+        Self {
+            min_level: None,
+            target_prefix: None,
+            since: None,
+            until: None,
+            text: None,
+            correlation_id: None,
+            include_previous: false,
+            limit: 200,
+        }
+    }
+}
+
+/// 单条解析后的日志
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogEntry {
+    pub timestamp: DateTime<FixedOffset>,
+    pub level: Level,
+    pub target: String,
+    pub message: String,
+    pub source: Option<String>,
+    pub fields: Option<serde_json::Value>,
+    pub file: Option<String>,
+    pub line: Option<u32>,
+}
+
+/// 一页查询结果，按时间从新到旧
+#[derive(Debug, Clone, Default)]
+pub struct LogPage {
+    pub entries: Vec<LogEntry>,
+    /// 无法解析而被跳过的行数
+    pub skipped_lines: usize,
+    /// 达到 limit 时仍有未读取的日志
+    pub has_more: bool,
+}
+
+/// 在日志目录中查询日志
+pub fn query_logs(log_dir: &Path, query: &LogQuery) -> io::Result<LogPage> {
+    let file_count = if query.include_previous { 2 } else { 1 };
+    let files = log_files(log_dir)?;
+
+    let mut page = LogPage::default();
+    for path in files.into_iter().take(file_count) {
+        if scan_file(&path, query, &mut page)? {
+            break;
+        }
+    }
+    Ok(page)
+}
+
+/// 日志目录下的日志文件，最新的在前
+pub(super) fn log_files(log_dir: &Path) -> io::Result<Vec<PathBuf>> {
+    if !log_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut files: Vec<PathBuf> = std::fs::read_dir(log_dir)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with(LOG_FILE_PREFIX))
+        })
+        .collect();
+    // 日期后缀按字典序即按时间排序
+    files.sort();
+    files.reverse();
+    Ok(files)
+}
+
+/// 扫描单个文件，返回 true 表示查询已结束（达到 limit 或早于 since）
+fn scan_file(path: &Path, query: &LogQuery, page: &mut LogPage) -> io::Result<bool> {
+    let text = query.text.as_ref().map(|t| t.to_lowercase());
+    let mut lines = ReverseLines::open(path)?;
+
+    while let Some(line) = lines.next_line()? {
+        if line.iter().all(u8::is_ascii_whitespace) {
+            continue;
+        }
+        let Some(entry) = parse_line(&line) else {
+            page.skipped_lines += 1;
+            continue;
+        };
+
+        // 日志按时间顺序追加，早于 since 之后的内容都不需要再读
+        if query
+            .since
+            .is_some_and(|since| entry.timestamp.with_timezone(&Utc) < since)
+        {
+            return Ok(true);
+        }
+        if !matches(&entry, query, text.as_deref()) {
+            continue;
+        }
+        if page.entries.len() == query.limit {
+            page.has_more = true;
+            return Ok(true);
+        }
+        page.entries.push(entry);
+    }
+
+    Ok(false)
+}
+
+fn matches(entry: &LogEntry, query: &LogQuery, text: Option<&str>) -> bool {
+    // tracing 中越详细的级别越大
+    if query.min_level.is_some_and(|min| entry.level > min) {
+        return false;
+    }
+    if let Some(prefix) = &query.target_prefix {
+        if !entry.target.starts_with(prefix.as_str()) {
+            return false;
+        }
+    }
+    if query
+        .until
+        .is_some_and(|until| entry.timestamp.with_timezone(&Utc) > until)
+    {
+        return false;
+    }
+    if let Some(correlation_id) = &query.correlation_id {
+        let found = entry
+            .fields
+            .as_ref()
+            .and_then(|fields| fields.get(CORRELATION_ID_FIELD))
+            .and_then(|value| value.as_str());
+        if found != Some(correlation_id.as_str()) {
+            return false;
+        }
+    }
+    if let Some(text) = text {
+        let in_message = entry.message.to_lowercase().contains(text);
+        let in_fields = entry
+            .fields
+            .as_ref()
+            .is_some_and(|fields| fields.to_string().to_lowercase().contains(text));
+        if !in_message && !in_fields {
+            return false;
+        }
+    }
+    true
+}
+
+/// 解析一行 JSON 日志，格式不符时返回 None
+fn parse_line(line: &[u8]) -> Option<LogEntry> {
+    let value: serde_json::Value = serde_json::from_slice(line).ok()?;
+    let timestamp = DateTime::parse_from_rfc3339(value.get("timestamp")?.as_str()?).ok()?;
+    let level = value.get("level")?.as_str()?.parse::<Level>().ok()?;
+    let text = |key: &str| value.get(key).and_then(|v| v.as_str()).map(str::to_string);
+
+    Some(LogEntry {
+        timestamp,
+        level,
+        target: text("target").unwrap_or_default(),
+        message: text("message").unwrap_or_default(),
+        source: text("source"),
+        fields: value.get("fields").cloned(),
+        file: text("file"),
+        line: value
+            .get("line")
+            .and_then(|v| v.as_u64())
+            .and_then(|v| u32::try_from(v).ok()),
+    })
+}
+
+/// 从文件末尾开始按块倒序返回各行
+pub(super) struct ReverseLines {
+    file: File,
+    /// 尚未读取部分的结束位置
+    pos: u64,
+    /// 已读取但尚未返回的数据（位于 pos 之后）
+    buf: Vec<u8>,
+}
+
+impl ReverseLines {
+    pub(super) fn open(path: &Path) -> io::Result<Self> {
+        let file = File::open(path)?;
+        let pos = file.metadata()?.len();
+        Ok(Self {
+            file,
+            pos,
+            buf: Vec::new(),
+        })
+    }
+
+    pub(super) fn next_line(&mut self) -> io::Result<Option<Vec<u8>>> {
+        loop {
+            if let Some(newline) = self.buf.iter().rposition(|&b| b == b'\n') {
+                let line = self.buf.split_off(newline + 1);
+                self.buf.truncate(newline);
+                if line.is_empty() {
+                    continue;
+                }
+                return Ok(Some(line));
+            }
+
+            if self.pos == 0 {
+                if self.buf.is_empty() {
+                    return Ok(None);
+                }
+                return Ok(Some(std::mem::take(&mut self.buf)));
+            }
+
+            let read_len = (CHUNK_SIZE as u64).min(self.pos);
+            self.pos -= read_len;
+            let mut chunk = vec![0u8; read_len as usize];
+            self.file.seek(SeekFrom::Start(self.pos))?;
+            self.file.read_exact(&mut chunk)?;
+            chunk.append(&mut self.buf);
+            self.buf = chunk;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn line(timestamp: &str, level: &str, target: &str, message: &str) -> String {
+        serde_json::json!({
+            "timestamp": timestamp,
+            "level": level,
+            "target": target,
+            "message": message,
+        })
+        .to_string()
+    }
+
+    fn write_log(dir: &Path, name: &str, lines: &[String]) {
+        let mut file = File::create(dir.join(name)).unwrap();
+        for line in lines {
+            writeln!(file, "{}", line).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_reverse_lines_across_chunks() {
+        let dir = tempfile::tempdir().unwrap();
+        let lines: Vec<String> = (0..5000).map(|i| format!("line-{:05}", i)).collect();
+        write_log(dir.path(), "neuradock.log.2026-01-01", &lines);
+
+        let mut reader = ReverseLines::open(&dir.path().join("neuradock.log.2026-01-01")).unwrap();
+        let mut read = Vec::new();
+        while let Some(line) = reader.next_line().unwrap() {
+            read.push(String::from_utf8(line).unwrap());
+        }
+        read.reverse();
+        assert_eq!(read, lines);
+    }
+
+    #[test]
+    fn test_newest_first_skips_corrupt_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut lines = vec![
+            line(
+                "2026-01-01T10:00:00.000+08:00",
+                "INFO",
+                "neuradock::a",
+                "first",
+            ),
+            "not json".to_string(),
+            line(
+                "2026-01-01T10:01:00.000+08:00",
+                "ERROR",
+                "neuradock::b",
+                "second",
+            ),
+        ];
+        // 正在写入时被截断的最后一行
+        lines.push(r#"{"timestamp":"2026-01-01T10:02:00.000+08:00","lev"#.to_string());
+        write_log(dir.path(), "neuradock.log.2026-01-01", &lines);
+
+        let page = query_logs(dir.path(), &LogQuery::default()).unwrap();
+        let messages: Vec<&str> = page.entries.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages, vec!["second", "first"]);
+        assert_eq!(page.skipped_lines, 2);
+        assert!(!page.has_more);
+    }
+
+    #[test]
+    fn test_filters_and_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut with_id: serde_json::Value = serde_json::from_str(&line(
+            "2026-01-02T09:00:00.000+00:00",
+            "WARN",
+            "neuradock_app::check_in",
+            "Check-in retry",
+        ))
+        .unwrap();
+        with_id["fields"] = serde_json::json!({ "correlation_id": "abc-123" });
+        write_log(
+            dir.path(),
+            "neuradock.log.2026-01-01",
+            &[line(
+                "2026-01-01T09:00:00.000+00:00",
+                "ERROR",
+                "neuradock_app::check_in",
+                "old",
+            )],
+        );
+        write_log(
+            dir.path(),
+            "neuradock.log.2026-01-02",
+            &[
+                line(
+                    "2026-01-02T08:00:00.000+00:00",
+                    "DEBUG",
+                    "neuradock_app::check_in",
+                    "debug",
+                ),
+                with_id.to_string(),
+                line(
+                    "2026-01-02T10:00:00.000+00:00",
+                    "ERROR",
+                    "frontend",
+                    "Check-in page crashed",
+                ),
+            ],
+        );
+
+        let query = LogQuery {
+            min_level: Some(Level::WARN),
+            target_prefix: Some("neuradock_app".to_string()),
+            ..LogQuery::default()
+        };
+        let page = query_logs(dir.path(), &query).unwrap();
+        assert_eq!(page.entries.len(), 1);
+        assert_eq!(page.entries[0].message, "Check-in retry");
+
+        let query = LogQuery {
+            correlation_id: Some("abc-123".to_string()),
+            ..LogQuery::default()
+        };
+        assert_eq!(query_logs(dir.path(), &query).unwrap().entries.len(), 1);
+
+        let query = LogQuery {
+            text: Some("CHECK-IN".to_string()),
+            include_previous: true,
+            limit: 1,
+            ..LogQuery::default()
+        };
+        let page = query_logs(dir.path(), &query).unwrap();
+        assert_eq!(page.entries[0].message, "Check-in page crashed");
+        assert!(page.has_more);
+
+        let query = LogQuery {
+            min_level: Some(Level::ERROR),
+            include_previous: true,
+            since: Some("2026-01-01T00:00:00Z".parse().unwrap()),
+            until: Some("2026-01-02T09:30:00Z".parse().unwrap()),
+            ..LogQuery::default()
+        };
+        let page = query_logs(dir.path(), &query).unwrap();
+        let messages: Vec<&str> = page.entries.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages, vec!["old"]);
+    }
+}
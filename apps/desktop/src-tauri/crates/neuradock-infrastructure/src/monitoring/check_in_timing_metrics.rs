@@ -0,0 +1,173 @@
+use serde::Serialize;
+use std::sync::Mutex;
+
+use neuradock_domain::check_in_timing::{CheckInPhase, CheckInTiming};
+
+/// Counters of a single phase
+#[derive(Debug, Clone, Copy)]
+struct PhaseCounters {
+    runs: u64,
+    total_ms: u64,
+    max_ms: u64,
+}
+
+impl PhaseCounters {
+    const EMPTY: Self = Self {
+        runs: 0,
+        total_ms: 0,
+        max_ms: 0,
+    };
+
+    fn record(&mut self, ms: u64) {
+        self.runs += 1;
+        self.total_ms += ms;
+        self.max_ms = self.max_ms.max(ms);
+    }
+}
+
+#[derive(Debug)]
+struct Counters {
+    total: PhaseCounters,
+    phases: [PhaseCounters; CheckInPhase::ALL.len()],
+}
+
+/// Process-wide check-in phase timings
+pub struct CheckInTimingMetrics {
+    counters: Mutex<Counters>,
+}
+
+/// Point-in-time copy of the check-in timing counters
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct CheckInTimingMetricsSnapshot {
+    /// Timed check-in runs
+    pub runs: u64,
+    /// Average wall time per run, `None` before the first run
+    pub average_total_ms: Option<u64>,
+    pub max_total_ms: u64,
+    /// Per-phase timings, in pipeline order
+    pub phases: Vec<CheckInPhaseMetrics>,
+}
+
+/// Point-in-time copy of the timings of a phase
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CheckInPhaseMetrics {
+    pub phase: CheckInPhase,
+    /// Runs in which the phase took place
+    pub runs: u64,
+    /// Average time of the runs in which the phase took place
+    pub average_ms: Option<u64>,
+    pub max_ms: u64,
+}
+
+static GLOBAL_CHECK_IN_TIMING_METRICS: CheckInTimingMetrics = CheckInTimingMetrics::new();
+
+impl CheckInTimingMetrics {
+    pub const fn new() -> Self {
+        Self {
+            counters: Mutex::new(Counters {
+                total: PhaseCounters::EMPTY,
+                phases: [PhaseCounters::EMPTY; CheckInPhase::ALL.len()],
+            }),
+        }
+    }
+
+    /// Shared metrics instance
+    pub fn global() -> &'static CheckInTimingMetrics {
+        &GLOBAL_CHECK_IN_TIMING_METRICS
+    }
+
+    /// Record the timings of a run; phases that did not take place are not counted
+    pub fn record(&self, timing: &CheckInTiming) {
+        let mut counters = self
+            .counters
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        counters.total.record(timing.total_ms());
+        for (index, phase) in CheckInPhase::ALL.iter().enumerate() {
+            let ms = timing.phase_ms(*phase);
+            if ms > 0 {
+                counters.phases[index].record(ms);
+            }
+        }
+    }
+
+    pub fn snapshot(&self) -> CheckInTimingMetricsSnapshot {
+        let counters = self
+            .counters
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        CheckInTimingMetricsSnapshot {
+            runs: counters.total.runs,
+            average_total_ms: average(&counters.total),
+            max_total_ms: counters.total.max_ms,
+            phases: CheckInPhase::ALL
+                .iter()
+                .zip(counters.phases.iter())
+                .map(|(phase, phase_counters)| CheckInPhaseMetrics {
+                    phase: *phase,
+                    runs: phase_counters.runs,
+                    average_ms: average(phase_counters),
+                    max_ms: phase_counters.max_ms,
+                })
+                .collect(),
+        }
+    }
+}
+
+fn average(counters: &PhaseCounters) -> Option<u64> {
+    (counters.runs > 0).then(|| counters.total_ms / counters.runs)
+}
+
+impl Default for CheckInTimingMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use neuradock_domain::check_in_timing::CheckInPhaseDurations;
+    use neuradock_domain::shared::{AccountId, ProviderId};
+
+    fn timing(waf_refresh_ms: u64, user_info_ms: u64, total_ms: u64) -> CheckInTiming {
+        let mut phases = CheckInPhaseDurations::default();
+        phases.set_ms(CheckInPhase::WafRefresh, waf_refresh_ms);
+        phases.set_ms(CheckInPhase::UserInfo, user_info_ms);
+        CheckInTiming::restore(
+            AccountId::from_string("account"),
+            ProviderId::from_string("provider"),
+            Utc::now(),
+            true,
+            phases,
+            total_ms,
+        )
+    }
+
+    #[test]
+    fn test_record_aggregates_phases_that_took_place() {
+        let metrics = CheckInTimingMetrics::new();
+        metrics.record(&timing(0, 200, 1000));
+        metrics.record(&timing(6000, 400, 9000));
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.runs, 2);
+        assert_eq!(snapshot.average_total_ms, Some(5000));
+        assert_eq!(snapshot.max_total_ms, 9000);
+        assert_eq!(snapshot.phases.len(), CheckInPhase::ALL.len());
+
+        let waf_refresh = &snapshot.phases[1];
+        assert_eq!(waf_refresh.phase, CheckInPhase::WafRefresh);
+        assert_eq!(waf_refresh.runs, 1);
+        assert_eq!(waf_refresh.average_ms, Some(6000));
+
+        let user_info = &snapshot.phases[2];
+        assert_eq!(user_info.runs, 2);
+        assert_eq!(user_info.average_ms, Some(300));
+        assert_eq!(user_info.max_ms, 400);
+
+        assert_eq!(snapshot.phases[0].average_ms, None);
+    }
+}
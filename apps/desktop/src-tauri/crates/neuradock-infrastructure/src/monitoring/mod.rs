@@ -1,3 +1,13 @@
+pub mod check_in_timing_metrics;
 pub mod performance;
+pub mod runtime_metrics;
+pub mod waf_metrics;
 
+pub use check_in_timing_metrics::{
+    CheckInPhaseMetrics, CheckInTimingMetrics, CheckInTimingMetricsSnapshot,
+};
 pub use performance::*;
+pub use runtime_metrics::{
+    RuntimeMetrics, RuntimeMetricsCollector, RuntimeSample, TaskGuard, TrackedTask,
+};
+pub use waf_metrics::{ProviderWafMetrics, WafMetrics, WafMetricsSnapshot, WafValidationOutcome};
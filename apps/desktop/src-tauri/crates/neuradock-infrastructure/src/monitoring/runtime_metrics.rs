@@ -0,0 +1,294 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::SqlitePool;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+
+use crate::events::InMemoryEventBus;
+
+/// How often the collector takes a sample
+pub const SAMPLE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Samples kept in the history, one hour at the sample interval
+pub const HISTORY_SIZE: usize = 120;
+
+/// Kind of a spawned task counted while it is alive
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrackedTask {
+    /// Per-account auto check-in loop
+    Scheduler,
+    /// Check-in being executed, manual, scheduled or retried
+    CheckIn,
+}
+
+/// Keeps a task counted until dropped, including when the task is aborted
+#[must_use = "the task stops being counted when the guard is dropped"]
+pub struct TaskGuard {
+    counter: &'static AtomicU64,
+}
+
+impl Drop for TaskGuard {
+    fn drop(&mut self) {
+        self.counter.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// One sample of the runtime state
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RuntimeSample {
+    pub sampled_at: DateTime<Utc>,
+    pub scheduler_tasks: u64,
+    pub running_check_ins: u64,
+    /// Resident memory of the process, `None` where it cannot be read
+    pub memory_rss_bytes: Option<u64>,
+    /// Open SQLite connections, idle ones included
+    pub db_connections: u32,
+    pub db_idle_connections: u32,
+    /// Events published but not yet handled by all their handlers
+    pub event_bus_pending: u64,
+    pub uptime_secs: u64,
+}
+
+/// Process-wide live task counters and the sample history
+pub struct RuntimeMetrics {
+    scheduler_tasks: AtomicU64,
+    running_check_ins: AtomicU64,
+    history: Mutex<VecDeque<RuntimeSample>>,
+}
+
+static GLOBAL_RUNTIME_METRICS: RuntimeMetrics = RuntimeMetrics::new();
+
+impl RuntimeMetrics {
+    pub const fn new() -> Self {
+        Self {
+            scheduler_tasks: AtomicU64::new(0),
+            running_check_ins: AtomicU64::new(0),
+            history: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Shared metrics instance
+    pub fn global() -> &'static RuntimeMetrics {
+        &GLOBAL_RUNTIME_METRICS
+    }
+
+    /// Count a task as live until the returned guard is dropped
+    pub fn track(&'static self, task: TrackedTask) -> TaskGuard {
+        let counter = match task {
+            TrackedTask::Scheduler => &self.scheduler_tasks,
+            TrackedTask::CheckIn => &self.running_check_ins,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        TaskGuard { counter }
+    }
+
+    pub fn live_tasks(&self, task: TrackedTask) -> u64 {
+        match task {
+            TrackedTask::Scheduler => self.scheduler_tasks.load(Ordering::Relaxed),
+            TrackedTask::CheckIn => self.running_check_ins.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Add a sample, dropping the oldest beyond `HISTORY_SIZE`
+    pub fn record(&self, sample: RuntimeSample) {
+        let mut history = self
+            .history
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if history.len() == HISTORY_SIZE {
+            history.pop_front();
+        }
+        history.push_back(sample);
+    }
+
+    /// Recorded samples, oldest first
+    pub fn history(&self) -> Vec<RuntimeSample> {
+        self.history
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .iter()
+            .cloned()
+            .collect()
+    }
+}
+
+impl Default for RuntimeMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Samples the runtime state into [`RuntimeMetrics::global`]
+pub struct RuntimeMetricsCollector {
+    pool: SqlitePool,
+    event_bus: Arc<InMemoryEventBus>,
+    started_at: Instant,
+}
+
+impl RuntimeMetricsCollector {
+    pub fn new(pool: SqlitePool, event_bus: Arc<InMemoryEventBus>) -> Self {
+        Self {
+            pool,
+            event_bus,
+            started_at: Instant::now(),
+        }
+    }
+
+    /// Current runtime state
+    pub fn sample(&self) -> RuntimeSample {
+        let metrics = RuntimeMetrics::global();
+        RuntimeSample {
+            sampled_at: Utc::now(),
+            scheduler_tasks: metrics.live_tasks(TrackedTask::Scheduler),
+            running_check_ins: metrics.live_tasks(TrackedTask::CheckIn),
+            memory_rss_bytes: process_rss_bytes(),
+            db_connections: self.pool.size(),
+            db_idle_connections: self.pool.num_idle() as u32,
+            event_bus_pending: self.event_bus.pending_events() as u64,
+            uptime_secs: self.started_at.elapsed().as_secs(),
+        }
+    }
+
+    /// Record a sample every `SAMPLE_INTERVAL`
+    pub fn spawn_worker(self: Arc<Self>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(SAMPLE_INTERVAL);
+            loop {
+                interval.tick().await;
+                RuntimeMetrics::global().record(self.sample());
+            }
+        })
+    }
+}
+
+/// Resident memory of this process in bytes, `None` where it cannot be read
+pub fn process_rss_bytes() -> Option<u64> {
+    memory::rss_bytes()
+}
+
+#[cfg(target_os = "linux")]
+mod memory {
+    pub fn rss_bytes() -> Option<u64> {
+        let status = std::fs::read_to_string("/proc/self/status").ok()?;
+        status
+            .lines()
+            .find_map(|line| line.strip_prefix("VmRSS:"))
+            .and_then(|value| value.trim().strip_suffix("kB"))
+            .and_then(|kb| kb.trim().parse::<u64>().ok())
+            .map(|kb| kb * 1024)
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod memory {
+    pub fn rss_bytes() -> Option<u64> {
+        let size = std::mem::size_of::<libc::proc_taskinfo>() as libc::c_int;
+        // SAFETY: proc_taskinfo is plain data and the buffer size matches it
+        let (written, info) = unsafe {
+            let mut info: libc::proc_taskinfo = std::mem::zeroed();
+            let written = libc::proc_pidinfo(
+                libc::getpid(),
+                libc::PROC_PIDTASKINFO,
+                0,
+                &mut info as *mut libc::proc_taskinfo as *mut libc::c_void,
+                size,
+            );
+            (written, info)
+        };
+        (written == size).then_some(info.pti_resident_size)
+    }
+}
+
+#[cfg(windows)]
+mod memory {
+    use windows_sys::Win32::System::ProcessStatus::{
+        GetProcessMemoryInfo, PROCESS_MEMORY_COUNTERS,
+    };
+    use windows_sys::Win32::System::Threading::GetCurrentProcess;
+
+    pub fn rss_bytes() -> Option<u64> {
+        let size = std::mem::size_of::<PROCESS_MEMORY_COUNTERS>() as u32;
+        // SAFETY: the counters are plain data and the buffer size matches them
+        let (ok, counters) = unsafe {
+            let mut counters: PROCESS_MEMORY_COUNTERS = std::mem::zeroed();
+            let ok = GetProcessMemoryInfo(GetCurrentProcess(), &mut counters, size);
+            (ok, counters)
+        };
+        (ok != 0).then_some(counters.WorkingSetSize as u64)
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+mod memory {
+    pub fn rss_bytes() -> Option<u64> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(uptime_secs: u64) -> RuntimeSample {
+        RuntimeSample {
+            sampled_at: Utc::now(),
+            scheduler_tasks: 0,
+            running_check_ins: 0,
+            memory_rss_bytes: None,
+            db_connections: 0,
+            db_idle_connections: 0,
+            event_bus_pending: 0,
+            uptime_secs,
+        }
+    }
+
+    #[test]
+    fn test_task_guard_counts_until_dropped() {
+        static METRICS: RuntimeMetrics = RuntimeMetrics::new();
+
+        let first = METRICS.track(TrackedTask::CheckIn);
+        let second = METRICS.track(TrackedTask::CheckIn);
+        let scheduler = METRICS.track(TrackedTask::Scheduler);
+        assert_eq!(METRICS.live_tasks(TrackedTask::CheckIn), 2);
+        assert_eq!(METRICS.live_tasks(TrackedTask::Scheduler), 1);
+
+        drop(first);
+        drop(scheduler);
+        assert_eq!(METRICS.live_tasks(TrackedTask::CheckIn), 1);
+        assert_eq!(METRICS.live_tasks(TrackedTask::Scheduler), 0);
+        drop(second);
+    }
+
+    #[test]
+    fn test_history_keeps_newest_samples() {
+        let metrics = RuntimeMetrics::new();
+        for uptime in 0..(HISTORY_SIZE as u64 + 5) {
+            metrics.record(sample(uptime));
+        }
+
+        let history = metrics.history();
+        assert_eq!(history.len(), HISTORY_SIZE);
+        assert_eq!(history[0].uptime_secs, 5);
+        assert_eq!(history.last().unwrap().uptime_secs, HISTORY_SIZE as u64 + 4);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_process_rss_is_read() {
+        assert!(process_rss_bytes().is_some_and(|bytes| bytes > 0));
+    }
+
+    #[tokio::test]
+    async fn test_sample_reads_pool_and_event_bus() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        let collector = RuntimeMetricsCollector::new(pool, Arc::new(InMemoryEventBus::new()));
+
+        let sample = collector.sample();
+
+        assert!(sample.db_connections >= 1);
+        assert_eq!(sample.event_bus_pending, 0);
+    }
+}
@@ -0,0 +1,234 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Outcome of validating cached WAF cookies before use
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WafValidationOutcome {
+    /// Cached cookies passed the WAF
+    Valid,
+    /// Cached cookies were answered with a challenge page and got refreshed
+    Challenged,
+    /// Validation request failed; cached cookies were used as-is
+    Failed,
+    /// Validation disabled for the provider
+    Skipped,
+}
+
+/// Refresh and cache counters of a single provider
+#[derive(Debug, Clone, Default)]
+struct ProviderCounters {
+    refresh_attempts: u64,
+    refresh_successes: u64,
+    refresh_failures: u64,
+    browser_time: Duration,
+    cache_hits: u64,
+    cache_misses: u64,
+    last_failure_reason: Option<String>,
+    last_failure_at: Option<DateTime<Utc>>,
+}
+
+/// Process-wide WAF counters
+pub struct WafMetrics {
+    validation_valid: AtomicU64,
+    validation_challenged: AtomicU64,
+    validation_failed: AtomicU64,
+    validation_skipped: AtomicU64,
+    providers: Mutex<BTreeMap<String, ProviderCounters>>,
+}
+
+/// Point-in-time copy of the WAF counters
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct WafMetricsSnapshot {
+    pub validation_valid: u64,
+    pub validation_challenged: u64,
+    pub validation_failed: u64,
+    pub validation_skipped: u64,
+    /// Per-provider counters, ordered by provider id
+    pub providers: Vec<ProviderWafMetrics>,
+}
+
+/// Point-in-time copy of the WAF counters of a provider
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ProviderWafMetrics {
+    pub provider_id: String,
+    /// Browser bypass runs to obtain fresh cookies
+    pub refresh_attempts: u64,
+    pub refresh_successes: u64,
+    pub refresh_failures: u64,
+    /// Average browser time per refresh attempt, `None` before the first attempt
+    pub average_browser_time_ms: Option<u64>,
+    /// Lookups answered with valid cookies from the cookies repository
+    pub cache_hits: u64,
+    pub cache_misses: u64,
+    /// Share of cache lookups that were hits, `None` before the first lookup
+    pub cache_hit_rate: Option<f64>,
+    pub last_failure_reason: Option<String>,
+    pub last_failure_at: Option<DateTime<Utc>>,
+}
+
+static GLOBAL_WAF_METRICS: WafMetrics = WafMetrics::new();
+
+impl WafMetrics {
+    pub const fn new() -> Self {
+        Self {
+            validation_valid: AtomicU64::new(0),
+            validation_challenged: AtomicU64::new(0),
+            validation_failed: AtomicU64::new(0),
+            validation_skipped: AtomicU64::new(0),
+            providers: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Shared metrics instance
+    pub fn global() -> &'static WafMetrics {
+        &GLOBAL_WAF_METRICS
+    }
+
+    pub fn record_validation(&self, outcome: WafValidationOutcome) {
+        let counter = match outcome {
+            WafValidationOutcome::Valid => &self.validation_valid,
+            WafValidationOutcome::Challenged => &self.validation_challenged,
+            WafValidationOutcome::Failed => &self.validation_failed,
+            WafValidationOutcome::Skipped => &self.validation_skipped,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record a browser bypass run, with the failure reason if it failed
+    pub fn record_refresh(&self, provider_id: &str, browser_time: Duration, error: Option<&str>) {
+        self.with_provider(provider_id, |counters| {
+            counters.refresh_attempts += 1;
+            counters.browser_time += browser_time;
+            match error {
+                None => counters.refresh_successes += 1,
+                Some(reason) => {
+                    counters.refresh_failures += 1;
+                    counters.last_failure_reason = Some(reason.to_string());
+                    counters.last_failure_at = Some(Utc::now());
+                }
+            }
+        });
+    }
+
+    /// Record a lookup of cached WAF cookies
+    pub fn record_cache_lookup(&self, provider_id: &str, hit: bool) {
+        self.with_provider(provider_id, |counters| {
+            if hit {
+                counters.cache_hits += 1;
+            } else {
+                counters.cache_misses += 1;
+            }
+        });
+    }
+
+    pub fn snapshot(&self) -> WafMetricsSnapshot {
+        let providers = self
+            .providers
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .iter()
+            .map(|(provider_id, counters)| counters.snapshot(provider_id))
+            .collect();
+
+        WafMetricsSnapshot {
+            validation_valid: self.validation_valid.load(Ordering::Relaxed),
+            validation_challenged: self.validation_challenged.load(Ordering::Relaxed),
+            validation_failed: self.validation_failed.load(Ordering::Relaxed),
+            validation_skipped: self.validation_skipped.load(Ordering::Relaxed),
+            providers,
+        }
+    }
+
+    fn with_provider(&self, provider_id: &str, update: impl FnOnce(&mut ProviderCounters)) {
+        let mut providers = self
+            .providers
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        update(providers.entry(provider_id.to_string()).or_default());
+    }
+}
+
+impl ProviderCounters {
+    fn snapshot(&self, provider_id: &str) -> ProviderWafMetrics {
+        let lookups = self.cache_hits + self.cache_misses;
+
+        ProviderWafMetrics {
+            provider_id: provider_id.to_string(),
+            refresh_attempts: self.refresh_attempts,
+            refresh_successes: self.refresh_successes,
+            refresh_failures: self.refresh_failures,
+            average_browser_time_ms: (self.refresh_attempts > 0)
+                .then(|| self.browser_time.as_millis() as u64 / self.refresh_attempts),
+            cache_hits: self.cache_hits,
+            cache_misses: self.cache_misses,
+            cache_hit_rate: (lookups > 0).then(|| self.cache_hits as f64 / lookups as f64),
+            last_failure_reason: self.last_failure_reason.clone(),
+            last_failure_at: self.last_failure_at,
+        }
+    }
+}
+
+impl Default for WafMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_validation_outcomes() {
+        let metrics = WafMetrics::new();
+        metrics.record_validation(WafValidationOutcome::Valid);
+        metrics.record_validation(WafValidationOutcome::Valid);
+        metrics.record_validation(WafValidationOutcome::Challenged);
+        metrics.record_validation(WafValidationOutcome::Skipped);
+
+        assert_eq!(
+            metrics.snapshot(),
+            WafMetricsSnapshot {
+                validation_valid: 2,
+                validation_challenged: 1,
+                validation_failed: 0,
+                validation_skipped: 1,
+                providers: Vec::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_record_refreshes_and_cache_lookups_per_provider() {
+        let metrics = WafMetrics::new();
+        metrics.record_refresh("anyrouter", Duration::from_millis(3000), None);
+        metrics.record_refresh("anyrouter", Duration::from_millis(5000), Some("timeout"));
+        metrics.record_cache_lookup("anyrouter", true);
+        metrics.record_cache_lookup("anyrouter", true);
+        metrics.record_cache_lookup("anyrouter", true);
+        metrics.record_cache_lookup("anyrouter", false);
+        metrics.record_cache_lookup("agentrouter", false);
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.providers.len(), 2);
+
+        let agentrouter = &snapshot.providers[0];
+        assert_eq!(agentrouter.provider_id, "agentrouter");
+        assert_eq!(agentrouter.refresh_attempts, 0);
+        assert_eq!(agentrouter.average_browser_time_ms, None);
+        assert_eq!(agentrouter.cache_hit_rate, Some(0.0));
+
+        let anyrouter = &snapshot.providers[1];
+        assert_eq!(anyrouter.refresh_attempts, 2);
+        assert_eq!(anyrouter.refresh_successes, 1);
+        assert_eq!(anyrouter.refresh_failures, 1);
+        assert_eq!(anyrouter.average_browser_time_ms, Some(4000));
+        assert_eq!(anyrouter.cache_hit_rate, Some(0.75));
+        assert_eq!(anyrouter.last_failure_reason.as_deref(), Some("timeout"));
+        assert!(anyrouter.last_failure_at.is_some());
+    }
+}
@@ -1,6 +1,15 @@
 use neuradock_domain::notification::NotificationMessage;
 use serde_json::json;
 
+/// Marks the yesterday and today balance sections of check-in content
+const BALANCE_SECTION: &str = "📅";
+/// Marks the balance changes section of check-in content
+const CHANGES_SECTION: &str = "💰";
+
+fn is_section_start(line: &str) -> bool {
+    line.starts_with(BALANCE_SECTION) || line.starts_with(CHANGES_SECTION)
+}
+
 impl super::FeishuWebhookSender {
     /// Build a rich text (post) message for Feishu
     pub(super) fn build_rich_message(&self, message: &NotificationMessage) -> serde_json::Value {
@@ -10,7 +19,7 @@ impl super::FeishuWebhookSender {
         let is_checkin_title = title_lc.contains("签到")
             || title_lc.contains("check-in")
             || title_lc.contains("checkin");
-        if is_checkin_title && message.content.contains("📅") {
+        if is_checkin_title && message.content.contains(BALANCE_SECTION) {
             // Build a card message for check-in success with balance comparison
             return self.build_card_message(message);
         }
@@ -21,11 +30,11 @@ impl super::FeishuWebhookSender {
             "text": message.content
         })];
 
-        // Add link if provided
+        // Add link if provided, shown as the URL so it reads the same in every locale
         if let Some(link) = &message.link {
             content_elements.push(json!({
                 "tag": "a",
-                "text": "查看详情",
+                "text": link,
                 "href": link
             }));
         }
@@ -51,9 +60,13 @@ impl super::FeishuWebhookSender {
         // Build card elements
         let mut elements = vec![];
 
-        // Add header with account and provider info
-        for line in lines.iter() {
-            if line.starts_with("账户:") || line.starts_with("服务商:") {
+        // Add header with account and provider info, the lines before the first section
+        let header = lines
+            .iter()
+            .map(|line| line.trim())
+            .take_while(|line| !is_section_start(line));
+        for line in header {
+            if !line.is_empty() {
                 elements.push(json!({
                     "tag": "div",
                     "text": {
@@ -74,7 +87,7 @@ impl super::FeishuWebhookSender {
         for line in lines.iter() {
             let trimmed = line.trim();
 
-            if trimmed.starts_with("📅") {
+            if trimmed.starts_with(BALANCE_SECTION) {
                 // Start new section
                 if !current_section.is_empty() {
                     elements.push(json!({
@@ -89,7 +102,7 @@ impl super::FeishuWebhookSender {
 
                 current_section = format!("**{}**\n", trimmed);
                 in_section = true;
-            } else if trimmed.starts_with("💰") {
+            } else if trimmed.starts_with(CHANGES_SECTION) {
                 // Changes section
                 if !current_section.is_empty() {
                     elements.push(json!({
@@ -105,11 +118,7 @@ impl super::FeishuWebhookSender {
                 elements.push(json!({"tag": "hr"}));
                 current_section = format!("**{}**\n", trimmed);
                 in_section = true;
-            } else if in_section
-                && !trimmed.is_empty()
-                && !trimmed.starts_with("账户")
-                && !trimmed.starts_with("服务商")
-            {
+            } else if in_section && !trimmed.is_empty() {
                 current_section.push_str(&format!("{}\n", trimmed));
             }
         }
@@ -33,7 +33,7 @@ impl FeishuWebhookSender {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use neuradock_domain::notification::NotificationMessage;
+    use neuradock_domain::notification::{NotificationMessage, TEST_MESSAGE_LABEL};
 
     #[test]
     fn test_build_webhook_url() {
@@ -56,6 +56,38 @@ mod tests {
         assert_eq!(payload["content"]["post"]["zh_cn"]["title"], "标题");
     }
 
+    #[test]
+    fn test_card_header_does_not_depend_on_locale() {
+        let sender = FeishuWebhookSender::new("test_key".to_string());
+        let message = NotificationMessage::new(
+            "✅ Check-in Success",
+            "Account: main\nProvider: AnyRouter\n\n📅 Today's Balance:\n   Current Balance: $12.50",
+        );
+
+        let payload = sender.build_rich_message(&message);
+
+        assert_eq!(payload["msg_type"], "interactive");
+        let elements = payload["card"]["elements"].as_array().unwrap();
+        assert_eq!(elements[0]["text"]["content"], "**Account: main**");
+        assert_eq!(elements[1]["text"]["content"], "**Provider: AnyRouter**");
+        assert_eq!(
+            elements[3]["text"]["content"],
+            "**📅 Today's Balance:**\nCurrent Balance: $12.50\n"
+        );
+    }
+
+    #[test]
+    fn test_test_message_is_labeled() {
+        let sender = FeishuWebhookSender::new("test_key".to_string());
+
+        let payload = sender.build_rich_message(&NotificationMessage::test());
+
+        assert!(payload["content"]["post"]["zh_cn"]["title"]
+            .as_str()
+            .unwrap()
+            .starts_with(TEST_MESSAGE_LABEL));
+    }
+
     #[test]
     fn test_build_text_message() {
         let sender = FeishuWebhookSender::new("test_key".to_string());
@@ -50,13 +50,4 @@ impl NotificationSender for super::FeishuWebhookSender {
 
         Ok(())
     }
-
-    async fn test(&self) -> Result<(), DomainError> {
-        let test_message = NotificationMessage::new(
-            "测试通知",
-            "这是一条来自 NeuraDock 的测试通知，如果您收到此消息，说明通知渠道配置成功！",
-        );
-
-        self.send(&test_message).await
-    }
 }
@@ -5,10 +5,10 @@ use std::str::FromStr;
 use std::sync::Arc;
 
 use neuradock_domain::notification::{
-    ChannelConfig, ChannelType, NotificationChannel, NotificationChannelId,
-    NotificationChannelRepository,
+    ChannelConfig, ChannelType, Locale, NotificationChannel, NotificationChannelId,
+    NotificationChannelRepository, NotificationTemplates,
 };
-use neuradock_domain::shared::DomainError;
+use neuradock_domain::shared::{DomainError, ErrorSeverity};
 
 use crate::persistence::RepositoryErrorMapper;
 
@@ -18,6 +18,9 @@ struct NotificationChannelRow {
     channel_type: String,
     config: String,
     enabled: bool,
+    min_severity: String,
+    templates: Option<String>,
+    locale: Option<String>,
     created_at: DateTime<Utc>,
 }
 
@@ -27,17 +30,36 @@ impl NotificationChannelRow {
         let id = NotificationChannelId::from_string(&self.id);
         let channel_type = ChannelType::from_str(&self.channel_type)?;
         let config = ChannelConfig::from_json(&self.config)?;
+        let templates = match self.templates.as_deref() {
+            Some(json) => serde_json::from_str(json).map_err(|e| {
+                DomainError::Deserialization(format!("Invalid notification templates: {}", e))
+            })?,
+            None => NotificationTemplates::default(),
+        };
 
         Ok(NotificationChannel::from_persistence(
             id,
             channel_type,
             config,
             self.enabled,
+            ErrorSeverity::from_str(&self.min_severity)?,
+            templates,
+            self.locale.as_deref().map(Locale::from_str).transpose()?,
             self.created_at,
         ))
     }
 }
 
+/// Templates as stored, NULL when the channel has none
+fn templates_to_json(templates: &NotificationTemplates) -> Result<Option<String>, DomainError> {
+    if templates.is_empty() {
+        return Ok(None);
+    }
+    serde_json::to_string(templates)
+        .map(Some)
+        .map_err(|e| DomainError::Serialization(format!("Invalid notification templates: {}", e)))
+}
+
 pub struct SqliteNotificationChannelRepository {
     pool: Arc<SqlitePool>,
 }
@@ -52,17 +74,21 @@ impl SqliteNotificationChannelRepository {
 impl NotificationChannelRepository for SqliteNotificationChannelRepository {
     async fn save(&self, channel: &NotificationChannel) -> Result<(), DomainError> {
         let config_json = channel.config().to_json()?;
+        let templates_json = templates_to_json(channel.templates())?;
 
         sqlx::query(
             r#"
-            INSERT INTO notification_channels (id, channel_type, config, enabled, created_at)
-            VALUES (?1, ?2, ?3, ?4, ?5)
+            INSERT INTO notification_channels (id, channel_type, config, enabled, min_severity, templates, locale, created_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
             "#,
         )
         .bind(channel.id().as_str())
         .bind(channel.channel_type().as_str())
         .bind(&config_json)
         .bind(channel.is_enabled())
+        .bind(channel.min_severity().as_str())
+        .bind(&templates_json)
+        .bind(channel.locale().map(|locale| locale.as_str()))
         .bind(channel.created_at())
         .execute(&*self.pool)
         .await
@@ -77,7 +103,7 @@ impl NotificationChannelRepository for SqliteNotificationChannelRepository {
     ) -> Result<Option<NotificationChannel>, DomainError> {
         let row: Option<NotificationChannelRow> = sqlx::query_as(
             r#"
-            SELECT id, channel_type, config, enabled, created_at
+            SELECT id, channel_type, config, enabled, min_severity, templates, locale, created_at
             FROM notification_channels
             WHERE id = ?1
             "#,
@@ -96,7 +122,7 @@ impl NotificationChannelRepository for SqliteNotificationChannelRepository {
     async fn find_all(&self) -> Result<Vec<NotificationChannel>, DomainError> {
         let rows: Vec<NotificationChannelRow> = sqlx::query_as(
             r#"
-            SELECT id, channel_type, config, enabled, created_at
+            SELECT id, channel_type, config, enabled, min_severity, templates, locale, created_at
             FROM notification_channels
             ORDER BY created_at DESC
             "#,
@@ -111,7 +137,7 @@ impl NotificationChannelRepository for SqliteNotificationChannelRepository {
     async fn find_all_enabled(&self) -> Result<Vec<NotificationChannel>, DomainError> {
         let rows: Vec<NotificationChannelRow> = sqlx::query_as(
             r#"
-            SELECT id, channel_type, config, enabled, created_at
+            SELECT id, channel_type, config, enabled, min_severity, templates, locale, created_at
             FROM notification_channels
             WHERE enabled = 1
             ORDER BY created_at DESC
@@ -126,17 +152,22 @@ impl NotificationChannelRepository for SqliteNotificationChannelRepository {
 
     async fn update(&self, channel: &NotificationChannel) -> Result<(), DomainError> {
         let config_json = channel.config().to_json()?;
+        let templates_json = templates_to_json(channel.templates())?;
 
         let result = sqlx::query(
             r#"
             UPDATE notification_channels
-            SET channel_type = ?1, config = ?2, enabled = ?3
-            WHERE id = ?4
+            SET channel_type = ?1, config = ?2, enabled = ?3, min_severity = ?4, templates = ?5,
+                locale = ?6
+            WHERE id = ?7
             "#,
         )
         .bind(channel.channel_type().as_str())
         .bind(&config_json)
         .bind(channel.is_enabled())
+        .bind(channel.min_severity().as_str())
+        .bind(&templates_json)
+        .bind(channel.locale().map(|locale| locale.as_str()))
         .bind(channel.id().as_str())
         .execute(&*self.pool)
         .await
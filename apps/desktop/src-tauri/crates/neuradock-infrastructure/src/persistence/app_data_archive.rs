@@ -0,0 +1,572 @@
+use base64::{engine::general_purpose, Engine as _};
+use chrono::{DateTime, Utc};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sqlx::sqlite::{SqliteArguments, SqliteRow};
+use sqlx::{Column, Row, Sqlite, SqliteConnection, SqlitePool, TypeInfo, ValueRef};
+use std::collections::BTreeMap;
+
+use super::repositories::balance_history_repo::refresh_last_check_in;
+use super::ResultExt;
+use crate::security::EncryptionService;
+use neuradock_domain::shared::DomainError;
+
+/// Identifies a sealed NeuraDock archive
+const ARCHIVE_FORMAT: &str = "neuradock-app-data";
+const ARCHIVE_VERSION: u32 = 1;
+
+/// Shortest passphrase accepted when sealing an archive
+pub const MIN_PASSPHRASE_LEN: usize = 8;
+
+/// A table carried by the archive
+struct ArchivedTable {
+    name: &'static str,
+    /// Columns encrypted with the machine key, stored as plaintext inside the sealed archive
+    encrypted_columns: &'static [&'static str],
+    /// Whether `id` is an AUTOINCREMENT key that is reassigned when merging
+    surrogate_id: bool,
+}
+
+/// User data carried by the archive, parents before the tables referencing them
+///
+/// Caches (WAF cookies, provider models) and check-in bookkeeping (retry queue,
+/// attempt counters) are rebuilt by the app and left out.
+const ARCHIVED_TABLES: &[ArchivedTable] = &[
+    ArchivedTable {
+        name: "providers",
+        encrypted_columns: &[],
+        surrogate_id: false,
+    },
+    ArchivedTable {
+        name: "custom_provider_nodes",
+        encrypted_columns: &[],
+        surrogate_id: true,
+    },
+    ArchivedTable {
+        name: "accounts",
+        encrypted_columns: &["cookies", "api_user", "extra_headers"],
+        surrogate_id: false,
+    },
+    ArchivedTable {
+        name: "sessions",
+        encrypted_columns: &[],
+        surrogate_id: false,
+    },
+    ArchivedTable {
+        name: "balances",
+        encrypted_columns: &[],
+        surrogate_id: false,
+    },
+    ArchivedTable {
+        name: "balance_history",
+        encrypted_columns: &[],
+        surrogate_id: false,
+    },
+    ArchivedTable {
+        name: "api_tokens",
+        encrypted_columns: &[],
+        surrogate_id: true,
+    },
+    ArchivedTable {
+        name: "independent_api_keys",
+        encrypted_columns: &["api_key"],
+        surrogate_id: true,
+    },
+    ArchivedTable {
+        name: "notification_channels",
+        encrypted_columns: &[],
+        surrogate_id: false,
+    },
+    ArchivedTable {
+        name: "proxy_settings",
+        encrypted_columns: &[],
+        surrogate_id: false,
+    },
+];
+
+/// Everything needed to move NeuraDock to another machine
+///
+/// Credentials are plaintext in this struct; it only leaves memory sealed by
+/// [`seal_archive`] under the user's passphrase.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AppDataArchive {
+    pub exported_at: DateTime<Utc>,
+    /// Latest migration applied to the exporting database
+    pub schema_version: i64,
+    /// Rows per table, each row a column name to value map
+    pub tables: BTreeMap<String, Vec<Map<String, Value>>>,
+    /// Application settings, filled in by the app layer
+    #[serde(default)]
+    pub settings: Option<Value>,
+}
+
+impl AppDataArchive {
+    /// Number of rows per table
+    pub fn row_counts(&self) -> BTreeMap<String, u64> {
+        self.tables
+            .iter()
+            .map(|(table, rows)| (table.clone(), rows.len() as u64))
+            .collect()
+    }
+}
+
+/// How an import treats data already in the database
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportMode {
+    /// Keep existing rows and add the ones the database does not have yet
+    Merge,
+    /// Delete the archived tables first so the database matches the archive
+    Replace,
+}
+
+impl ImportMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImportMode::Merge => "merge",
+            ImportMode::Replace => "replace",
+        }
+    }
+}
+
+/// Outcome of an import
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AppDataImportReport {
+    /// Rows written per table
+    pub imported: BTreeMap<String, u64>,
+    /// Rows per table left out because the database already had them (merge only)
+    pub skipped: BTreeMap<String, u64>,
+}
+
+/// On-disk form of a sealed archive
+#[derive(Serialize, Deserialize)]
+struct SealedArchive {
+    format: String,
+    version: u32,
+    /// Argon2 salt for the passphrase key, base64
+    salt: String,
+    /// AES-256-GCM encrypted archive JSON, see [`EncryptionService::encrypt`]
+    data: String,
+}
+
+/// Read all archived tables, decrypting credentials with the machine key
+pub async fn export_app_data(
+    pool: &SqlitePool,
+    encryption: &EncryptionService,
+) -> Result<AppDataArchive, DomainError> {
+    // One read transaction so the tables are consistent with each other, rolled back on drop
+    let mut tx = pool.begin().await.map_repo_error("Begin export")?;
+    let (schema_version, tables) = read_tables(&mut tx, encryption).await?;
+
+    Ok(AppDataArchive {
+        exported_at: Utc::now(),
+        schema_version,
+        tables,
+        settings: None,
+    })
+}
+
+async fn read_tables(
+    conn: &mut SqliteConnection,
+    encryption: &EncryptionService,
+) -> Result<(i64, BTreeMap<String, Vec<Map<String, Value>>>), DomainError> {
+    let schema_version = schema_version(conn).await?;
+
+    let mut tables = BTreeMap::new();
+    for table in ARCHIVED_TABLES {
+        let rows = sqlx::query(&format!("SELECT * FROM \"{}\"", table.name))
+            .fetch_all(&mut *conn)
+            .await
+            .map_repo_error(&format!("Export {}", table.name))?;
+
+        let mut exported = Vec::with_capacity(rows.len());
+        for row in &rows {
+            let mut values = row_to_map(row, table.name)?;
+            for column in table.encrypted_columns {
+                if let Some(Value::String(encrypted)) = values.get(*column) {
+                    let plaintext = encryption.decrypt(encrypted).map_err(|e| {
+                        DomainError::DataIntegrity(format!(
+                            "Failed to decrypt {}.{}: {}",
+                            table.name, column, e
+                        ))
+                    })?;
+                    values.insert(column.to_string(), Value::String(plaintext));
+                }
+            }
+            exported.push(values);
+        }
+        tables.insert(table.name.to_string(), exported);
+    }
+
+    Ok((schema_version, tables))
+}
+
+/// Write an archive into the database in one transaction
+///
+/// Credentials are encrypted with this machine's key. Columns the archive has
+/// but this database does not are ignored, so archives from older versions
+/// import; archives from newer versions are refused.
+pub async fn import_app_data(
+    pool: &SqlitePool,
+    encryption: &EncryptionService,
+    archive: &AppDataArchive,
+    mode: ImportMode,
+) -> Result<AppDataImportReport, DomainError> {
+    let mut tx = pool.begin().await.map_repo_error("Begin import")?;
+
+    let local_version = schema_version(&mut tx).await?;
+    if archive.schema_version > local_version {
+        return Err(DomainError::Validation(
+            "The archive was exported by a newer version of NeuraDock, update this installation first"
+                .to_string(),
+        ));
+    }
+
+    if mode == ImportMode::Replace {
+        for table in ARCHIVED_TABLES.iter().rev() {
+            sqlx::query(&format!("DELETE FROM \"{}\"", table.name))
+                .execute(&mut *tx)
+                .await
+                .map_repo_error(&format!("Clear {}", table.name))?;
+        }
+    }
+
+    let mut report = AppDataImportReport::default();
+    for table in ARCHIVED_TABLES {
+        let Some(rows) = archive.tables.get(table.name) else {
+            continue;
+        };
+
+        let mut columns: Vec<String> = sqlx::query_scalar("SELECT name FROM pragma_table_info(?1)")
+            .bind(table.name)
+            .fetch_all(&mut *tx)
+            .await
+            .map_repo_error(&format!("Read columns of {}", table.name))?;
+        if mode == ImportMode::Merge && table.surrogate_id {
+            columns.retain(|column| column != "id");
+        }
+
+        let mut imported = 0;
+        for row in rows {
+            let present: Vec<&String> = columns.iter().filter(|c| row.contains_key(*c)).collect();
+            if present.is_empty() {
+                continue;
+            }
+
+            let mut values = Vec::with_capacity(present.len());
+            for column in &present {
+                let value = &row[column.as_str()];
+                let value = match value {
+                    Value::String(plaintext)
+                        if table.encrypted_columns.contains(&column.as_str()) =>
+                    {
+                        Value::String(encryption.encrypt(plaintext).map_err(|e| {
+                            DomainError::DataIntegrity(format!(
+                                "Failed to encrypt {}.{}: {}",
+                                table.name, column, e
+                            ))
+                        })?)
+                    }
+                    other => other.clone(),
+                };
+                values.push(value);
+            }
+
+            let sql = format!(
+                "INSERT OR IGNORE INTO \"{}\" ({}) VALUES ({})",
+                table.name,
+                present
+                    .iter()
+                    .map(|column| format!("\"{}\"", column))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                vec!["?"; present.len()].join(", ")
+            );
+            let query = values.into_iter().fold(sqlx::query(&sql), bind_value);
+            imported += query
+                .execute(&mut *tx)
+                .await
+                .map_repo_error(&format!("Import {}", table.name))?
+                .rows_affected();
+        }
+
+        report.imported.insert(table.name.to_string(), imported);
+        report
+            .skipped
+            .insert(table.name.to_string(), rows.len() as u64 - imported);
+    }
+
+    refresh_last_check_in(&mut tx, None)
+        .await
+        .map_repo_error("Refresh last check-in")?;
+    tx.commit().await.map_repo_error("Commit import")?;
+
+    Ok(report)
+}
+
+/// Encrypt an archive under `passphrase` into the bytes written to disk
+pub fn seal_archive(archive: &AppDataArchive, passphrase: &str) -> Result<Vec<u8>, DomainError> {
+    if passphrase.chars().count() < MIN_PASSPHRASE_LEN {
+        return Err(DomainError::Validation(format!(
+            "Passphrase must be at least {} characters",
+            MIN_PASSPHRASE_LEN
+        )));
+    }
+
+    let mut salt = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut salt);
+    let cipher = passphrase_cipher(passphrase, &salt)?;
+
+    let json = serde_json::to_string(archive).map_repo_error("Serialize archive")?;
+    let data = cipher
+        .encrypt(&json)
+        .map_err(|e| DomainError::Infrastructure(format!("Failed to encrypt archive: {}", e)))?;
+
+    let sealed = SealedArchive {
+        format: ARCHIVE_FORMAT.to_string(),
+        version: ARCHIVE_VERSION,
+        salt: general_purpose::STANDARD.encode(salt),
+        data,
+    };
+    serde_json::to_vec(&sealed).map_repo_error("Serialize archive")
+}
+
+/// Decrypt an archive written by [`seal_archive`]
+pub fn open_archive(bytes: &[u8], passphrase: &str) -> Result<AppDataArchive, DomainError> {
+    let sealed: SealedArchive = serde_json::from_slice(bytes)
+        .ok()
+        .filter(|sealed: &SealedArchive| sealed.format == ARCHIVE_FORMAT)
+        .ok_or_else(|| {
+            DomainError::Validation("The file is not a NeuraDock data archive".to_string())
+        })?;
+    if sealed.version > ARCHIVE_VERSION {
+        return Err(DomainError::Validation(
+            "The archive format is newer than this version of NeuraDock supports".to_string(),
+        ));
+    }
+
+    let salt: [u8; 32] = general_purpose::STANDARD
+        .decode(&sealed.salt)
+        .ok()
+        .and_then(|salt| salt.try_into().ok())
+        .ok_or_else(|| DomainError::Validation("The archive header is damaged".to_string()))?;
+    let json = passphrase_cipher(passphrase, &salt)?
+        .decrypt(&sealed.data)
+        .map_err(|_| {
+            DomainError::Validation("Wrong passphrase, or the archive is damaged".to_string())
+        })?;
+
+    serde_json::from_str(&json).map_err(|e| {
+        DomainError::Deserialization(format!("Failed to read archive contents: {}", e))
+    })
+}
+
+fn passphrase_cipher(passphrase: &str, salt: &[u8; 32]) -> Result<EncryptionService, DomainError> {
+    EncryptionService::from_password(passphrase, salt)
+        .map_err(|e| DomainError::Infrastructure(format!("Failed to derive archive key: {}", e)))
+}
+
+/// Latest applied migration, 0 for a database without migrations
+pub(super) async fn schema_version(conn: &mut SqliteConnection) -> Result<i64, DomainError> {
+    let has_migrations: bool = sqlx::query_scalar(
+        "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = '_sqlx_migrations')",
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .map_repo_error("Read schema version")?;
+    if !has_migrations {
+        return Ok(0);
+    }
+
+    let version: Option<i64> =
+        sqlx::query_scalar("SELECT MAX(version) FROM _sqlx_migrations WHERE success = 1")
+            .fetch_one(&mut *conn)
+            .await
+            .map_repo_error("Read schema version")?;
+    Ok(version.unwrap_or(0))
+}
+
+/// Convert a row to JSON using each value's SQLite storage class
+fn row_to_map(row: &SqliteRow, table: &str) -> Result<Map<String, Value>, DomainError> {
+    let mut values = Map::new();
+    for column in row.columns() {
+        let index = column.ordinal();
+        let raw = row
+            .try_get_raw(index)
+            .map_repo_error(&format!("Export {}", table))?;
+
+        let value = if raw.is_null() {
+            Value::Null
+        } else {
+            match raw.type_info().name() {
+                "INTEGER" => Value::from(
+                    row.try_get_unchecked::<i64, _>(index)
+                        .map_repo_error(&format!("Export {}", table))?,
+                ),
+                "REAL" => Value::from(
+                    row.try_get_unchecked::<f64, _>(index)
+                        .map_repo_error(&format!("Export {}", table))?,
+                ),
+                "TEXT" => Value::from(
+                    row.try_get_unchecked::<String, _>(index)
+                        .map_repo_error(&format!("Export {}", table))?,
+                ),
+                other => {
+                    return Err(DomainError::DataIntegrity(format!(
+                        "Cannot export {}.{} with SQLite type {}",
+                        table,
+                        column.name(),
+                        other
+                    )))
+                }
+            }
+        };
+        values.insert(column.name().to_string(), value);
+    }
+    Ok(values)
+}
+
+fn bind_value<'q>(
+    query: sqlx::query::Query<'q, Sqlite, SqliteArguments<'q>>,
+    value: Value,
+) -> sqlx::query::Query<'q, Sqlite, SqliteArguments<'q>> {
+    match value {
+        Value::Null => query.bind(None::<String>),
+        Value::Bool(value) => query.bind(value),
+        Value::Number(number) => match number.as_i64() {
+            Some(value) => query.bind(value),
+            None => query.bind(number.as_f64()),
+        },
+        Value::String(value) => query.bind(value),
+        // Not produced by the export, kept as JSON text
+        other => query.bind(other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::Database;
+
+    fn encryption(password: &str) -> EncryptionService {
+        EncryptionService::from_password(password, &[7u8; 32]).unwrap()
+    }
+
+    async fn migrated_database(dir: &std::path::Path, name: &str) -> Database {
+        let database = Database::new(dir.join(name).to_str().unwrap())
+            .await
+            .unwrap();
+        database.run_migrations().await.unwrap();
+        database
+    }
+
+    async fn insert_account(database: &Database, encryption: &EncryptionService, id: &str) {
+        sqlx::query(
+            "INSERT INTO accounts (id, name, provider_id, cookies, api_user, enabled, created_at) VALUES (?1, ?2, 'anyrouter', ?3, ?4, 1, '2025-01-01T00:00:00Z')",
+        )
+        .bind(id)
+        .bind(format!("Account {}", id))
+        .bind(encryption.encrypt(r#"{"session":"secret"}"#).unwrap())
+        .bind(encryption.encrypt("api-user-1").unwrap())
+        .execute(database.pool())
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO balance_history (id, account_id, current_balance, total_consumed, total_quota, recorded_at) VALUES (?1, ?2, 1.5, 0.5, 2.0, '2025-01-02T00:00:00Z')",
+        )
+        .bind(format!("history-{}", id))
+        .bind(id)
+        .execute(database.pool())
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_archive_moves_credentials_to_another_machine_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let source_key = encryption("source-machine");
+        let source = migrated_database(dir.path(), "source.db").await;
+        insert_account(&source, &source_key, "acc-1").await;
+
+        let mut archive = export_app_data(source.pool(), &source_key).await.unwrap();
+        archive.settings = Some(serde_json::json!({"log_level": "debug"}));
+        assert_eq!(archive.tables["accounts"][0]["api_user"], "api-user-1");
+
+        let sealed = seal_archive(&archive, "correct horse").unwrap();
+        assert!(!String::from_utf8_lossy(&sealed).contains("api-user-1"));
+        let opened = open_archive(&sealed, "correct horse").unwrap();
+        assert_eq!(opened, archive);
+
+        let target_key = encryption("target-machine");
+        let target = migrated_database(dir.path(), "target.db").await;
+        let report = import_app_data(target.pool(), &target_key, &opened, ImportMode::Merge)
+            .await
+            .unwrap();
+        assert_eq!(report.imported["accounts"], 1);
+        assert_eq!(report.imported["balance_history"], 1);
+
+        let api_user: String =
+            sqlx::query_scalar("SELECT api_user FROM accounts WHERE id = 'acc-1'")
+                .fetch_one(target.pool())
+                .await
+                .unwrap();
+        assert_eq!(target_key.decrypt(&api_user).unwrap(), "api-user-1");
+        assert!(source_key.decrypt(&api_user).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_merge_keeps_existing_rows_and_replace_drops_them() {
+        let dir = tempfile::tempdir().unwrap();
+        let key = encryption("machine");
+        let source = migrated_database(dir.path(), "source.db").await;
+        insert_account(&source, &key, "acc-1").await;
+        let archive = export_app_data(source.pool(), &key).await.unwrap();
+
+        let target = migrated_database(dir.path(), "target.db").await;
+        insert_account(&target, &key, "acc-1").await;
+        insert_account(&target, &key, "local-only").await;
+
+        let report = import_app_data(target.pool(), &key, &archive, ImportMode::Merge)
+            .await
+            .unwrap();
+        assert_eq!(report.imported["accounts"], 0);
+        assert_eq!(report.skipped["accounts"], 1);
+
+        import_app_data(target.pool(), &key, &archive, ImportMode::Replace)
+            .await
+            .unwrap();
+        let ids: Vec<String> = sqlx::query_scalar("SELECT id FROM accounts ORDER BY id")
+            .fetch_all(target.pool())
+            .await
+            .unwrap();
+        assert_eq!(ids, vec!["acc-1"]);
+        let history: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM balance_history")
+            .fetch_one(target.pool())
+            .await
+            .unwrap();
+        assert_eq!(history, 1);
+    }
+
+    #[tokio::test]
+    async fn test_wrong_passphrase_and_newer_schema_are_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let key = encryption("machine");
+        let database = migrated_database(dir.path(), "neuradock.db").await;
+        let mut archive = export_app_data(database.pool(), &key).await.unwrap();
+
+        assert!(seal_archive(&archive, "short").is_err());
+        let sealed = seal_archive(&archive, "correct horse").unwrap();
+        assert!(matches!(
+            open_archive(&sealed, "wrong horse"),
+            Err(DomainError::Validation(_))
+        ));
+        assert!(open_archive(b"{}", "correct horse").is_err());
+
+        archive.schema_version += 1;
+        assert!(
+            import_app_data(database.pool(), &key, &archive, ImportMode::Merge)
+                .await
+                .is_err()
+        );
+    }
+}
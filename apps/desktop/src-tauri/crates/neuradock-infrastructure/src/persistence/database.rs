@@ -1,10 +1,29 @@
+use chrono::Utc;
+use log::{error, info};
 use neuradock_domain::shared::DomainError;
-use sqlx::{sqlite::SqlitePoolOptions, SqlitePool};
+use sqlx::migrate::Migrator;
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions, SqliteSynchronous};
+use sqlx::SqlitePool;
 use std::fs::OpenOptions;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use super::integrity::{check_and_recover, DatabaseRecovery};
+use super::migration::{
+    clear_failure, failed_migration, pending_versions, record_failure, restore_snapshot, snapshot,
+    MigrationFailure,
+};
+
+static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
+
+/// How long a connection waits for another writer before failing with SQLITE_BUSY
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
 
 pub struct Database {
     pool: SqlitePool,
+    path: PathBuf,
+    recovery: Option<DatabaseRecovery>,
+    migration_failure: Option<MigrationFailure>,
 }
 
 impl Database {
@@ -17,6 +36,9 @@ impl Database {
             })?;
         }
 
+        // Move a corrupt file aside before connecting, a missing file is recreated below
+        let recovery = check_and_recover(path).await?;
+
         if !path.exists() {
             OpenOptions::new()
                 .create(true)
@@ -30,22 +52,234 @@ impl Database {
 
         let pool = SqlitePoolOptions::new()
             .max_connections(5)
-            .connect(&format!("sqlite:{}", db_path))
+            .connect_with(connect_options(path))
             .await
             .map_err(|e| DomainError::Infrastructure(e.to_string()))?;
 
-        Ok(Self { pool })
+        Ok(Self {
+            pool,
+            path: path.to_path_buf(),
+            recovery,
+            migration_failure: None,
+        })
     }
 
     pub async fn run_migrations(&self) -> Result<(), DomainError> {
-        sqlx::migrate!("./migrations")
+        MIGRATOR
             .run(&self.pool)
             .await
             .map_err(|e| DomainError::Infrastructure(e.to_string()))?;
         Ok(())
     }
 
+    /// Apply pending migrations, restoring a snapshot if one of them fails
+    ///
+    /// The database is copied into the backups folder first. When a migration
+    /// fails the snapshot replaces the half-migrated file, the failure is
+    /// recorded and the pool is reopened read-only, see [`Self::migration_failure`].
+    pub async fn run_migrations_with_rollback(&mut self) -> Result<(), DomainError> {
+        self.migrate_with_rollback(&MIGRATOR).await
+    }
+
+    async fn migrate_with_rollback(&mut self, migrator: &Migrator) -> Result<(), DomainError> {
+        let pending = pending_versions(&self.pool, migrator).await?;
+        let Some(&first_pending) = pending.first() else {
+            clear_failure(&self.path);
+            return Ok(());
+        };
+
+        // A database without applied migrations holds nothing worth a snapshot
+        let snapshot_path = if pending.len() < migrator.iter().count() {
+            Some(snapshot(&self.pool, &self.path, first_pending).await?)
+        } else {
+            None
+        };
+
+        let error = match migrator.run(&self.pool).await {
+            Ok(()) => {
+                info!("Applied {} migration(s)", pending.len());
+                clear_failure(&self.path);
+                return Ok(());
+            }
+            Err(e) => e,
+        };
+        let Some(snapshot_path) = snapshot_path else {
+            return Err(DomainError::Infrastructure(error.to_string()));
+        };
+
+        let still_pending = pending_versions(&self.pool, migrator)
+            .await
+            .unwrap_or_default();
+        let (version, description) = failed_migration(migrator, &error, &still_pending);
+        error!("Migration {} ({}) failed: {}", version, description, error);
+
+        self.pool.close().await;
+        restore_snapshot(&self.path, &snapshot_path)?;
+        self.pool = SqlitePoolOptions::new()
+            .max_connections(5)
+            .connect_with(read_only_options(&self.path))
+            .await
+            .map_err(|e| DomainError::Infrastructure(e.to_string()))?;
+
+        let failure = MigrationFailure {
+            version,
+            description,
+            error: error.to_string(),
+            snapshot_path,
+            failed_at: Utc::now(),
+        };
+        record_failure(&self.path, &failure);
+        self.migration_failure = Some(failure);
+        Ok(())
+    }
+
     pub fn pool(&self) -> &SqlitePool {
         &self.pool
     }
+
+    /// How a corrupt database was recovered while opening, if it was
+    pub fn recovery(&self) -> Option<&DatabaseRecovery> {
+        self.recovery.as_ref()
+    }
+
+    /// The migration that failed and was rolled back, the pool is read-only when set
+    pub fn migration_failure(&self) -> Option<&MigrationFailure> {
+        self.migration_failure.as_ref()
+    }
+}
+
+/// Pragmas applied to every pooled connection
+///
+/// WAL lets the UI read while a check-in writes, and `synchronous=NORMAL` is
+/// durable enough in WAL mode while avoiding an fsync per commit. Writers that
+/// collide wait up to [`BUSY_TIMEOUT`] instead of failing immediately.
+fn connect_options(path: &Path) -> SqliteConnectOptions {
+    SqliteConnectOptions::new()
+        .filename(path)
+        .journal_mode(SqliteJournalMode::Wal)
+        .synchronous(SqliteSynchronous::Normal)
+        .busy_timeout(BUSY_TIMEOUT)
+        .foreign_keys(true)
+}
+
+/// Connections to a database restored after a failed migration, which must not be written
+///
+/// The journal mode is left alone, switching a restored snapshot to WAL is a write.
+fn read_only_options(path: &Path) -> SqliteConnectOptions {
+    SqliteConnectOptions::new()
+        .filename(path)
+        .read_only(true)
+        .busy_timeout(BUSY_TIMEOUT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_connections_use_configured_pragmas() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("neuradock.db");
+        let database = Database::new(db_path.to_str().unwrap()).await.unwrap();
+
+        let journal_mode: String = sqlx::query_scalar("PRAGMA journal_mode")
+            .fetch_one(database.pool())
+            .await
+            .unwrap();
+        let busy_timeout: i64 = sqlx::query_scalar("PRAGMA busy_timeout")
+            .fetch_one(database.pool())
+            .await
+            .unwrap();
+        let foreign_keys: i64 = sqlx::query_scalar("PRAGMA foreign_keys")
+            .fetch_one(database.pool())
+            .await
+            .unwrap();
+        // NORMAL is 1
+        let synchronous: i64 = sqlx::query_scalar("PRAGMA synchronous")
+            .fetch_one(database.pool())
+            .await
+            .unwrap();
+
+        assert_eq!(journal_mode, "wal");
+        assert_eq!(busy_timeout, BUSY_TIMEOUT.as_millis() as i64);
+        assert_eq!(foreign_keys, 1);
+        assert_eq!(synchronous, 1);
+    }
+
+    /// Migrator over the real migrations plus `extra`, written to `dir`
+    async fn migrator_with(dir: &Path, extra: &[(&str, &str)]) -> Migrator {
+        let migrations = dir.join("migrations");
+        std::fs::create_dir_all(&migrations).unwrap();
+        for migration in MIGRATOR.iter() {
+            let name = format!(
+                "{}_{}.sql",
+                migration.version,
+                migration.description.replace(' ', "_")
+            );
+            std::fs::write(migrations.join(name), migration.sql.as_bytes()).unwrap();
+        }
+        for (name, sql) in extra {
+            std::fs::write(migrations.join(name), sql).unwrap();
+        }
+        Migrator::new(migrations.as_path()).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_failed_migration_restores_snapshot_read_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("neuradock.db");
+        let mut database = Database::new(db_path.to_str().unwrap()).await.unwrap();
+        database.run_migrations_with_rollback().await.unwrap();
+        assert!(database.migration_failure().is_none());
+        sqlx::query("UPDATE proxy_settings SET host = 'kept' WHERE id = 1")
+            .execute(database.pool())
+            .await
+            .unwrap();
+
+        // The first statement applies, the second fails halfway through the migration
+        let migrator = migrator_with(
+            dir.path(),
+            &[
+                (
+                    "29990101000001_add_proxy_note.sql",
+                    "ALTER TABLE proxy_settings ADD COLUMN note TEXT",
+                ),
+                (
+                    "29990101000002_broken.sql",
+                    "UPDATE proxy_settings SET host = 'changed'; SELECT * FROM missing_table",
+                ),
+            ],
+        )
+        .await;
+        database.migrate_with_rollback(&migrator).await.unwrap();
+
+        let failure = database.migration_failure().unwrap().clone();
+        assert_eq!(failure.version, 29990101000002);
+        assert!(failure.snapshot_path.exists());
+        assert!(dir.path().join("migration_failure.json").exists());
+
+        // The data is back to before the first new migration, and cannot be written
+        let host: String = sqlx::query_scalar("SELECT host FROM proxy_settings")
+            .fetch_one(database.pool())
+            .await
+            .unwrap();
+        assert_eq!(host, "kept");
+        let columns: Vec<String> =
+            sqlx::query_scalar("SELECT name FROM pragma_table_info('proxy_settings')")
+                .fetch_all(database.pool())
+                .await
+                .unwrap();
+        assert!(!columns.iter().any(|name| name == "note"));
+        assert!(sqlx::query("UPDATE proxy_settings SET host = 'written'")
+            .execute(database.pool())
+            .await
+            .is_err());
+        database.pool().close().await;
+
+        // A later start with working migrations clears the recorded failure
+        let mut database = Database::new(db_path.to_str().unwrap()).await.unwrap();
+        database.run_migrations_with_rollback().await.unwrap();
+        assert!(database.migration_failure().is_none());
+        assert!(!dir.path().join("migration_failure.json").exists());
+    }
 }
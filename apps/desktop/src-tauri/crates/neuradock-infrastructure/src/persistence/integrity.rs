@@ -0,0 +1,311 @@
+use chrono::Utc;
+use log::{info, warn};
+use neuradock_domain::shared::DomainError;
+use sqlx::sqlite::SqliteConnectOptions;
+use sqlx::{ConnectOptions, Connection};
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+/// SQLite primary result codes that mean the file itself is damaged
+const SQLITE_CORRUPT: i32 = 11;
+const SQLITE_NOTADB: i32 = 26;
+
+/// Integrity messages kept in the logs, `integrity_check` can report thousands
+const MAX_REPORTED_PROBLEMS: usize = 5;
+
+/// What startup did with a database that failed its integrity check
+#[derive(Debug, Clone, PartialEq)]
+pub enum DatabaseRecovery {
+    /// The newest healthy automatic backup replaced the corrupt file
+    RestoredFromBackup {
+        corrupt_path: PathBuf,
+        backup_path: PathBuf,
+    },
+    /// No healthy backup was found, an empty database was created
+    StartedFresh { corrupt_path: PathBuf },
+}
+
+impl DatabaseRecovery {
+    /// Where the corrupt database was moved to
+    pub fn corrupt_path(&self) -> &Path {
+        match self {
+            DatabaseRecovery::RestoredFromBackup { corrupt_path, .. }
+            | DatabaseRecovery::StartedFresh { corrupt_path } => corrupt_path,
+        }
+    }
+
+    /// Explanation for the user of what happened to their data
+    pub fn message(&self) -> String {
+        match self {
+            DatabaseRecovery::RestoredFromBackup {
+                corrupt_path,
+                backup_path,
+            } => format!(
+                "The database was corrupted and has been restored from the backup {}. Changes made after that backup are lost. The corrupted file was kept at {}.",
+                backup_path.display(),
+                corrupt_path.display()
+            ),
+            DatabaseRecovery::StartedFresh { corrupt_path } => format!(
+                "The database was corrupted and no backup was available, so NeuraDock started with an empty database. The corrupted file was kept at {}.",
+                corrupt_path.display()
+            ),
+        }
+    }
+}
+
+/// Directory searched for automatic backups of the database at `db_path`
+pub fn backups_dir(db_path: &Path) -> PathBuf {
+    db_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join("backups")
+}
+
+/// Check an existing database file and recover it if it is corrupt
+///
+/// The full `integrity_check` runs in debug builds, release builds use the
+/// faster `quick_check`. A corrupt file is moved aside together with its WAL,
+/// then the newest healthy backup is restored; without one the caller starts
+/// with a fresh database.
+pub(super) async fn check_and_recover(
+    db_path: &Path,
+) -> Result<Option<DatabaseRecovery>, DomainError> {
+    let is_empty = std::fs::metadata(db_path).map_or(true, |metadata| metadata.len() == 0);
+    if is_empty {
+        return Ok(None);
+    }
+
+    let Some(problem) = integrity_problem(db_path).await? else {
+        return Ok(None);
+    };
+    warn!(
+        "Database {} failed its integrity check: {}",
+        db_path.display(),
+        problem
+    );
+
+    let corrupt_path = move_aside(db_path)?;
+    warn!("Moved corrupt database to {}", corrupt_path.display());
+
+    for backup_path in backups_newest_first(db_path) {
+        std::fs::copy(&backup_path, db_path).map_err(|e| {
+            DomainError::DataIntegrity(format!(
+                "Failed to restore backup {}: {}",
+                backup_path.display(),
+                e
+            ))
+        })?;
+
+        match integrity_problem(db_path).await? {
+            None => {
+                info!("Restored database from backup {}", backup_path.display());
+                return Ok(Some(DatabaseRecovery::RestoredFromBackup {
+                    corrupt_path,
+                    backup_path,
+                }));
+            }
+            Some(problem) => {
+                warn!(
+                    "Skipping corrupt backup {}: {}",
+                    backup_path.display(),
+                    problem
+                );
+                remove_database_files(db_path);
+            }
+        }
+    }
+
+    warn!("No healthy database backup found, starting with an empty database");
+    Ok(Some(DatabaseRecovery::StartedFresh { corrupt_path }))
+}
+
+/// Description of the corruption, `None` when the database is healthy
+pub(super) async fn integrity_problem(db_path: &Path) -> Result<Option<String>, DomainError> {
+    let pragma = if cfg!(debug_assertions) {
+        "PRAGMA integrity_check"
+    } else {
+        "PRAGMA quick_check"
+    };
+
+    let mut conn = match SqliteConnectOptions::new()
+        .filename(db_path)
+        .connect()
+        .await
+    {
+        Ok(conn) => conn,
+        Err(e) if is_corruption(&e) => return Ok(Some(e.to_string())),
+        Err(e) => {
+            return Err(DomainError::Infrastructure(format!(
+                "Failed to open database for integrity check: {}",
+                e
+            )))
+        }
+    };
+
+    let result = sqlx::query_scalar::<_, String>(pragma)
+        .fetch_all(&mut conn)
+        .await;
+    let _ = conn.close().await;
+
+    match result {
+        Ok(rows) if rows.len() == 1 && rows[0] == "ok" => Ok(None),
+        Ok(rows) => Ok(Some(
+            rows.into_iter()
+                .take(MAX_REPORTED_PROBLEMS)
+                .collect::<Vec<_>>()
+                .join("; "),
+        )),
+        Err(e) if is_corruption(&e) => Ok(Some(e.to_string())),
+        Err(e) => Err(DomainError::Infrastructure(format!(
+            "Database integrity check failed to run: {}",
+            e
+        ))),
+    }
+}
+
+fn is_corruption(error: &sqlx::Error) -> bool {
+    error
+        .as_database_error()
+        .and_then(|e| e.code())
+        .and_then(|code| code.parse::<i32>().ok())
+        .is_some_and(|code| matches!(code & 0xff, SQLITE_CORRUPT | SQLITE_NOTADB))
+}
+
+/// Rename the database and its WAL and shared-memory files with a `.corrupt-<time>` suffix
+fn move_aside(db_path: &Path) -> Result<PathBuf, DomainError> {
+    let suffix = format!(".corrupt-{}", Utc::now().format("%Y%m%d%H%M%S"));
+    let corrupt_path = with_suffix(db_path, &suffix);
+
+    std::fs::rename(db_path, &corrupt_path).map_err(|e| {
+        DomainError::DataIntegrity(format!(
+            "Database file {} is corrupted and could not be moved aside: {}",
+            db_path.display(),
+            e
+        ))
+    })?;
+    for sidecar in ["-wal", "-shm"] {
+        let path = with_suffix(db_path, sidecar);
+        if path.exists() {
+            let target = with_suffix(&corrupt_path, sidecar);
+            if let Err(e) = std::fs::rename(&path, &target) {
+                warn!("Failed to move {} aside: {}", path.display(), e);
+                let _ = std::fs::remove_file(&path);
+            }
+        }
+    }
+
+    Ok(corrupt_path)
+}
+
+/// Backups of the database, newest first
+pub(super) fn backups_newest_first(db_path: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(backups_dir(db_path)) else {
+        return Vec::new();
+    };
+
+    let mut backups: Vec<_> = entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "db"))
+        .filter_map(|entry| {
+            let modified = entry.metadata().ok()?.modified().ok()?;
+            Some((modified, entry.path()))
+        })
+        .collect();
+    backups.sort_by_key(|(modified, _)| std::cmp::Reverse(*modified));
+    backups.into_iter().map(|(_, path)| path).collect()
+}
+
+pub(super) fn remove_database_files(db_path: &Path) {
+    let _ = std::fs::remove_file(db_path);
+    for sidecar in ["-wal", "-shm"] {
+        let _ = std::fs::remove_file(with_suffix(db_path, sidecar));
+    }
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = OsString::from(path.as_os_str());
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::Database;
+
+    async fn create_database(path: &Path, note: &str) {
+        let database = Database::new(path.to_str().unwrap()).await.unwrap();
+        sqlx::query("CREATE TABLE notes (body TEXT NOT NULL)")
+            .execute(database.pool())
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO notes (body) VALUES (?)")
+            .bind(note)
+            .execute(database.pool())
+            .await
+            .unwrap();
+        database.pool().close().await;
+    }
+
+    fn corrupt(path: &Path) {
+        std::fs::write(path, vec![0x42; 8192]).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_healthy_database_is_left_alone() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("neuradock.db");
+        create_database(&db_path, "kept").await;
+
+        let database = Database::new(db_path.to_str().unwrap()).await.unwrap();
+
+        assert!(database.recovery().is_none());
+        let body: String = sqlx::query_scalar("SELECT body FROM notes")
+            .fetch_one(database.pool())
+            .await
+            .unwrap();
+        assert_eq!(body, "kept");
+    }
+
+    #[tokio::test]
+    async fn test_corrupt_database_without_backup_starts_fresh() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("neuradock.db");
+        corrupt(&db_path);
+
+        let database = Database::new(db_path.to_str().unwrap()).await.unwrap();
+
+        let recovery = database.recovery().unwrap();
+        assert!(matches!(recovery, DatabaseRecovery::StartedFresh { .. }));
+        assert!(recovery.corrupt_path().exists());
+        database.run_migrations().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_corrupt_database_is_restored_from_newest_healthy_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("neuradock.db");
+        let backups = backups_dir(&db_path);
+        std::fs::create_dir_all(&backups).unwrap();
+
+        create_database(&backups.join("older.db"), "backup").await;
+        // Newer than the healthy backup, but corrupt itself
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        corrupt(&backups.join("newer.db"));
+        corrupt(&db_path);
+
+        let database = Database::new(db_path.to_str().unwrap()).await.unwrap();
+
+        match database.recovery().unwrap() {
+            DatabaseRecovery::RestoredFromBackup { backup_path, .. } => {
+                assert_eq!(backup_path, &backups.join("older.db"))
+            }
+            other => panic!("expected a restore, got {:?}", other),
+        }
+        let body: String = sqlx::query_scalar("SELECT body FROM notes")
+            .fetch_one(database.pool())
+            .await
+            .unwrap();
+        assert_eq!(body, "backup");
+    }
+}
@@ -0,0 +1,342 @@
+use chrono::Utc;
+use sqlx::{Row, SqliteConnection, SqlitePool};
+use std::collections::BTreeMap;
+
+use super::ResultExt;
+use crate::security::EncryptionService;
+use neuradock_domain::shared::DomainError;
+
+/// A column encrypted with the machine key
+struct EncryptedColumn {
+    table: &'static str,
+    column: &'static str,
+    /// Holds the value re-encrypted under the next key until the rotation flips it
+    shadow: &'static str,
+}
+
+impl EncryptedColumn {
+    fn name(&self) -> String {
+        format!("{}.{}", self.table, self.column)
+    }
+}
+
+/// Every column written with [`EncryptionService`]
+const ENCRYPTED_COLUMNS: &[EncryptedColumn] = &[
+    EncryptedColumn {
+        table: "accounts",
+        column: "cookies",
+        shadow: "cookies_rotated",
+    },
+    EncryptedColumn {
+        table: "accounts",
+        column: "api_user",
+        shadow: "api_user_rotated",
+    },
+    EncryptedColumn {
+        table: "accounts",
+        column: "extra_headers",
+        shadow: "extra_headers_rotated",
+    },
+    EncryptedColumn {
+        table: "independent_api_keys",
+        column: "api_key",
+        shadow: "api_key_rotated",
+    },
+];
+
+/// Progress is reported every this many rows, and at the end of each column
+const PROGRESS_STEP: usize = 50;
+
+/// Rows of one encrypted column processed so far
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyRotationProgress {
+    /// `table.column` being processed
+    pub column: String,
+    pub processed: u64,
+    pub total: u64,
+}
+
+/// Rows processed per encrypted `table.column`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeyRotationReport {
+    pub rows: BTreeMap<String, u64>,
+}
+
+/// Check that every encrypted value decrypts with `encryption`, writing nothing
+pub async fn verify_encrypted_columns(
+    pool: &SqlitePool,
+    encryption: &EncryptionService,
+    mut on_progress: impl FnMut(KeyRotationProgress),
+) -> Result<KeyRotationReport, DomainError> {
+    let mut conn = pool.acquire().await.map_repo_error("Acquire connection")?;
+
+    let mut report = KeyRotationReport::default();
+    for column in ENCRYPTED_COLUMNS {
+        let rows = read_column(&mut conn, column).await?;
+        for (index, (rowid, ciphertext)) in rows.iter().enumerate() {
+            decrypt(encryption, column, *rowid, ciphertext)?;
+            report_progress(&mut on_progress, column, index + 1, rows.len());
+        }
+        report.rows.insert(column.name(), rows.len() as u64);
+    }
+
+    Ok(report)
+}
+
+/// Re-encrypt every encrypted value from `current` to `next` in one transaction
+///
+/// All values are first written to shadow columns and only copied over the
+/// originals once every row re-encrypted, so a row that fails to decrypt
+/// leaves the database untouched. `next_salt` is recorded in the same
+/// transaction: after a crash between the commit and replacing the salt file,
+/// [`pending_key_rotation`] returns the salt the database is encrypted with.
+pub async fn rotate_encrypted_columns(
+    pool: &SqlitePool,
+    current: &EncryptionService,
+    next: &EncryptionService,
+    next_salt: &[u8; 32],
+    mut on_progress: impl FnMut(KeyRotationProgress),
+) -> Result<KeyRotationReport, DomainError> {
+    let mut tx = pool.begin().await.map_repo_error("Begin key rotation")?;
+
+    // Writing first takes the database write lock, so no value can be stored
+    // under the old key while the rotation runs
+    sqlx::query(
+        "INSERT OR REPLACE INTO encryption_key_rotation (id, salt, rotated_at) VALUES (1, ?1, ?2)",
+    )
+    .bind(&next_salt[..])
+    .bind(Utc::now().to_rfc3339())
+    .execute(&mut *tx)
+    .await
+    .map_repo_error("Record key rotation")?;
+
+    let mut report = KeyRotationReport::default();
+    for column in ENCRYPTED_COLUMNS {
+        let rows = read_column(&mut tx, column).await?;
+        let update = format!(
+            "UPDATE \"{}\" SET \"{}\" = ?1 WHERE rowid = ?2",
+            column.table, column.shadow
+        );
+
+        for (index, (rowid, ciphertext)) in rows.iter().enumerate() {
+            let plaintext = decrypt(current, column, *rowid, ciphertext)?;
+            let rotated = next.encrypt(&plaintext).map_err(|e| {
+                DomainError::DataIntegrity(format!("Failed to encrypt {}: {}", column.name(), e))
+            })?;
+
+            sqlx::query(&update)
+                .bind(rotated)
+                .bind(rowid)
+                .execute(&mut *tx)
+                .await
+                .map_repo_error(&format!("Rotate {}", column.name()))?;
+            report_progress(&mut on_progress, column, index + 1, rows.len());
+        }
+        report.rows.insert(column.name(), rows.len() as u64);
+    }
+
+    for column in ENCRYPTED_COLUMNS {
+        sqlx::query(&format!(
+            "UPDATE \"{table}\" SET \"{column}\" = \"{shadow}\", \"{shadow}\" = NULL WHERE \"{shadow}\" IS NOT NULL",
+            table = column.table,
+            column = column.column,
+            shadow = column.shadow
+        ))
+        .execute(&mut *tx)
+        .await
+        .map_repo_error(&format!("Flip {}", column.name()))?;
+    }
+
+    tx.commit().await.map_repo_error("Commit key rotation")?;
+
+    Ok(report)
+}
+
+/// Salt of a committed rotation that has not replaced the salt file yet
+pub async fn pending_key_rotation(pool: &SqlitePool) -> Result<Option<[u8; 32]>, DomainError> {
+    let salt: Option<Vec<u8>> =
+        sqlx::query_scalar("SELECT salt FROM encryption_key_rotation WHERE id = 1")
+            .fetch_optional(pool)
+            .await
+            .map_repo_error("Read pending key rotation")?;
+
+    salt.map(|salt| {
+        <[u8; 32]>::try_from(salt.as_slice()).map_err(|_| {
+            DomainError::DataIntegrity(format!(
+                "Pending key rotation salt has {} bytes, expected 32",
+                salt.len()
+            ))
+        })
+    })
+    .transpose()
+}
+
+/// Forget the pending rotation once its salt is stored
+pub async fn finish_key_rotation(pool: &SqlitePool) -> Result<(), DomainError> {
+    sqlx::query("DELETE FROM encryption_key_rotation")
+        .execute(pool)
+        .await
+        .map_repo_error("Finish key rotation")?;
+    Ok(())
+}
+
+async fn read_column(
+    conn: &mut SqliteConnection,
+    column: &EncryptedColumn,
+) -> Result<Vec<(i64, String)>, DomainError> {
+    let rows = sqlx::query(&format!(
+        "SELECT rowid, \"{}\" FROM \"{}\" WHERE \"{}\" IS NOT NULL ORDER BY rowid",
+        column.column, column.table, column.column
+    ))
+    .fetch_all(&mut *conn)
+    .await
+    .map_repo_error(&format!("Read {}", column.name()))?;
+
+    Ok(rows
+        .iter()
+        .map(|row| (row.get::<i64, _>(0), row.get::<String, _>(1)))
+        .collect())
+}
+
+fn decrypt(
+    encryption: &EncryptionService,
+    column: &EncryptedColumn,
+    rowid: i64,
+    ciphertext: &str,
+) -> Result<String, DomainError> {
+    encryption.decrypt(ciphertext).map_err(|e| {
+        DomainError::DataIntegrity(format!(
+            "Failed to decrypt {} of row {}: {}",
+            column.name(),
+            rowid,
+            e
+        ))
+    })
+}
+
+fn report_progress(
+    on_progress: &mut impl FnMut(KeyRotationProgress),
+    column: &EncryptedColumn,
+    processed: usize,
+    total: usize,
+) {
+    if processed.is_multiple_of(PROGRESS_STEP) || processed == total {
+        on_progress(KeyRotationProgress {
+            column: column.name(),
+            processed: processed as u64,
+            total: total as u64,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::Database;
+
+    fn encryption(salt: u8) -> EncryptionService {
+        EncryptionService::from_password("test_password", &[salt; 32]).unwrap()
+    }
+
+    async fn database_with_accounts(
+        dir: &std::path::Path,
+        encryption: &EncryptionService,
+        count: usize,
+    ) -> Database {
+        let database = Database::new(dir.join("neuradock.db").to_str().unwrap())
+            .await
+            .unwrap();
+        database.run_migrations().await.unwrap();
+
+        for index in 0..count {
+            sqlx::query(
+                "INSERT INTO accounts (id, name, provider_id, cookies, api_user, enabled, created_at) VALUES (?1, ?2, 'anyrouter', ?3, ?4, 1, '2025-01-01T00:00:00Z')",
+            )
+            .bind(format!("account-{}", index))
+            .bind(format!("Account {}", index))
+            .bind(encryption.encrypt(&format!("cookies-{}", index)).unwrap())
+            .bind(encryption.encrypt(&format!("user-{}", index)).unwrap())
+            .execute(database.pool())
+            .await
+            .unwrap();
+        }
+        database
+    }
+
+    async fn account_values(database: &Database) -> Vec<(String, String)> {
+        sqlx::query_as("SELECT cookies, api_user FROM accounts ORDER BY id")
+            .fetch_all(database.pool())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_rotation_reencrypts_every_row_and_records_salt() {
+        let dir = tempfile::tempdir().unwrap();
+        let current = encryption(1);
+        let next = encryption(2);
+        let database = database_with_accounts(dir.path(), &current, 60).await;
+
+        let mut progress = Vec::new();
+        let report = rotate_encrypted_columns(database.pool(), &current, &next, &[2; 32], |p| {
+            progress.push(p)
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(report.rows["accounts.cookies"], 60);
+        assert_eq!(report.rows["independent_api_keys.api_key"], 0);
+        assert!(progress.contains(&KeyRotationProgress {
+            column: "accounts.api_user".to_string(),
+            processed: 60,
+            total: 60,
+        }));
+
+        let values = account_values(&database).await;
+        assert_eq!(next.decrypt(&values[0].0).unwrap(), "cookies-0");
+        assert_eq!(next.decrypt(&values[0].1).unwrap(), "user-0");
+        assert!(values
+            .iter()
+            .all(|(cookies, _)| current.decrypt(cookies).is_err()));
+
+        let shadows: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM accounts WHERE cookies_rotated IS NOT NULL OR api_user_rotated IS NOT NULL",
+        )
+        .fetch_one(database.pool())
+        .await
+        .unwrap();
+        assert_eq!(shadows, 0);
+
+        assert_eq!(
+            pending_key_rotation(database.pool()).await.unwrap(),
+            Some([2; 32])
+        );
+        finish_key_rotation(database.pool()).await.unwrap();
+        assert_eq!(pending_key_rotation(database.pool()).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_undecryptable_row_aborts_rotation_without_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let current = encryption(1);
+        let next = encryption(2);
+        let database = database_with_accounts(dir.path(), &current, 3).await;
+        sqlx::query("UPDATE accounts SET api_user = ?1 WHERE id = 'account-2'")
+            .bind(encryption(9).encrypt("foreign").unwrap())
+            .execute(database.pool())
+            .await
+            .unwrap();
+        let before = account_values(&database).await;
+
+        let verified = verify_encrypted_columns(database.pool(), &current, |_| {}).await;
+        assert!(matches!(verified, Err(DomainError::DataIntegrity(_))));
+
+        let rotated =
+            rotate_encrypted_columns(database.pool(), &current, &next, &[2; 32], |_| {}).await;
+        assert!(matches!(rotated, Err(DomainError::DataIntegrity(_))));
+
+        assert_eq!(account_values(&database).await, before);
+        assert_eq!(current.decrypt(&before[0].0).unwrap(), "cookies-0");
+        assert_eq!(pending_key_rotation(database.pool()).await.unwrap(), None);
+    }
+}
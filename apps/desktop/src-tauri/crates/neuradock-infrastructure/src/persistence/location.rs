@@ -0,0 +1,468 @@
+use log::{info, warn};
+use neuradock_domain::shared::DomainError;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::path::{Path, PathBuf};
+
+use super::integrity::{integrity_problem, remove_database_files};
+use super::ResultExt;
+use crate::security::KeyManager;
+
+/// File in the app data directory naming a custom data directory
+const POINTER_FILE: &str = "data_location.json";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct LocationPointer {
+    data_dir: PathBuf,
+}
+
+/// Where the database and its encryption salt live
+///
+/// By default they sit in the app data directory. Moving them writes a small
+/// pointer file there, which is read before the database is opened.
+#[derive(Debug, Clone)]
+pub struct DataLocation {
+    app_data_dir: PathBuf,
+    db_filename: String,
+}
+
+/// Data directory to open the database from
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedDataDir {
+    pub data_dir: PathBuf,
+    /// Set when a configured directory was unusable and the default is used instead
+    pub stale: Option<StaleDataLocation>,
+}
+
+/// A configured data directory that could not be used at startup
+#[derive(Debug, Clone, PartialEq)]
+pub struct StaleDataLocation {
+    pub configured_dir: PathBuf,
+    pub fallback_dir: PathBuf,
+    pub reason: String,
+}
+
+impl StaleDataLocation {
+    /// Explanation for the user of which data is open
+    pub fn message(&self) -> String {
+        format!(
+            "The data directory {} is not available ({}), so NeuraDock opened the default location {} instead. Reconnect the drive holding the data directory and restart, or move the database again.",
+            self.configured_dir.display(),
+            self.reason,
+            self.fallback_dir.display()
+        )
+    }
+}
+
+/// A database copied to a new data directory
+#[derive(Debug, Clone, PartialEq)]
+pub struct DatabaseMove {
+    pub from_path: PathBuf,
+    pub to_path: PathBuf,
+    pub size_bytes: u64,
+    /// Encryption salt file or keyring marker left behind, removed with the
+    /// original database
+    from_key_files: Vec<PathBuf>,
+}
+
+impl DatabaseMove {
+    /// Delete the original database and salt
+    ///
+    /// Only call once the pool on the original file is closed, writes made
+    /// through it afterwards would be lost.
+    pub fn remove_original(&self) {
+        remove_database_files(&self.from_path);
+        for path in &self.from_key_files {
+            if let Err(e) = std::fs::remove_file(path) {
+                warn!("Failed to remove {}: {}", path.display(), e);
+            }
+        }
+        info!("Removed original database {}", self.from_path.display());
+    }
+}
+
+impl DataLocation {
+    pub fn new(app_data_dir: PathBuf, db_filename: impl Into<String>) -> Self {
+        Self {
+            app_data_dir,
+            db_filename: db_filename.into(),
+        }
+    }
+
+    /// Directory used when no custom location is configured
+    pub fn default_dir(&self) -> &Path {
+        &self.app_data_dir
+    }
+
+    /// Path of the database inside `data_dir`
+    pub fn db_path(&self, data_dir: &Path) -> PathBuf {
+        data_dir.join(&self.db_filename)
+    }
+
+    /// Custom data directory from the pointer file, `None` for the default
+    pub fn configured_dir(&self) -> Result<Option<PathBuf>, DomainError> {
+        let pointer_path = self.pointer_path();
+        let contents = match std::fs::read_to_string(&pointer_path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => {
+                return Err(DomainError::Infrastructure(format!(
+                    "Failed to read {}: {}",
+                    pointer_path.display(),
+                    e
+                )))
+            }
+        };
+
+        let pointer: LocationPointer = serde_json::from_str(&contents).map_err(|e| {
+            DomainError::Infrastructure(format!("Invalid {}: {}", pointer_path.display(), e))
+        })?;
+        Ok(Some(pointer.data_dir))
+    }
+
+    /// Directory to open the database from
+    ///
+    /// A configured directory is used when its database file exists. When it
+    /// does not, for example because the drive is unmounted, the default
+    /// directory is used and the pointer is kept so the next start can find
+    /// the data again.
+    pub fn resolve(&self) -> ResolvedDataDir {
+        let configured_dir = match self.configured_dir() {
+            Ok(Some(dir)) => dir,
+            Ok(None) => return self.fallback(None),
+            Err(e) => {
+                return self.fallback(Some(StaleDataLocation {
+                    configured_dir: self.pointer_path(),
+                    fallback_dir: self.app_data_dir.clone(),
+                    reason: e.to_string(),
+                }))
+            }
+        };
+
+        let reason = if !configured_dir.is_dir() {
+            "the directory does not exist"
+        } else if !self.db_path(&configured_dir).is_file() {
+            "it contains no database"
+        } else {
+            return ResolvedDataDir {
+                data_dir: configured_dir,
+                stale: None,
+            };
+        };
+
+        self.fallback(Some(StaleDataLocation {
+            configured_dir,
+            fallback_dir: self.app_data_dir.clone(),
+            reason: reason.to_string(),
+        }))
+    }
+
+    /// Point startup at `data_dir`, the default directory removes the pointer
+    pub fn point_to(&self, data_dir: &Path) -> Result<(), DomainError> {
+        let pointer_path = self.pointer_path();
+        if data_dir == self.app_data_dir {
+            return match std::fs::remove_file(&pointer_path) {
+                Ok(()) => Ok(()),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+                Err(e) => Err(DomainError::Infrastructure(format!(
+                    "Failed to remove {}: {}",
+                    pointer_path.display(),
+                    e
+                ))),
+            };
+        }
+
+        let contents = serde_json::to_string_pretty(&LocationPointer {
+            data_dir: data_dir.to_path_buf(),
+        })
+        .map_err(|e| {
+            DomainError::Infrastructure(format!("Failed to serialize data location: {}", e))
+        })?;
+
+        // Write then rename, a torn pointer would send startup to the default directory
+        let temp_path = pointer_path.with_extension("tmp");
+        std::fs::write(&temp_path, contents)
+            .and_then(|_| std::fs::rename(&temp_path, &pointer_path))
+            .map_err(|e| {
+                DomainError::Infrastructure(format!(
+                    "Failed to write {}: {}",
+                    pointer_path.display(),
+                    e
+                ))
+            })
+    }
+
+    fn fallback(&self, stale: Option<StaleDataLocation>) -> ResolvedDataDir {
+        ResolvedDataDir {
+            data_dir: self.app_data_dir.clone(),
+            stale,
+        }
+    }
+
+    fn pointer_path(&self) -> PathBuf {
+        self.app_data_dir.join(POINTER_FILE)
+    }
+}
+
+/// Copy the database in `from_dir` to `to_dir` and point startup at it
+///
+/// The WAL is checkpointed into the main file first, so callers must make
+/// sure nothing writes while this runs. The copy and the encryption salt are
+/// checked at the destination before the pointer changes; the original stays
+/// until [`DatabaseMove::remove_original`] is called. Backups stay where they
+/// are.
+pub async fn move_database(
+    pool: &SqlitePool,
+    location: &DataLocation,
+    from_dir: &Path,
+    to_dir: &Path,
+) -> Result<DatabaseMove, DomainError> {
+    if !to_dir.is_absolute() {
+        return Err(DomainError::Validation(format!(
+            "Data directory must be an absolute path: {}",
+            to_dir.display()
+        )));
+    }
+    std::fs::create_dir_all(to_dir).map_err(|e| {
+        DomainError::Infrastructure(format!(
+            "Failed to create data directory {}: {}",
+            to_dir.display(),
+            e
+        ))
+    })?;
+    if same_dir(from_dir, to_dir) {
+        return Err(DomainError::Validation(format!(
+            "The database is already in {}",
+            to_dir.display()
+        )));
+    }
+
+    let from_path = location.db_path(from_dir);
+    let to_path = location.db_path(to_dir);
+    if to_path.exists() {
+        return Err(DomainError::Validation(format!(
+            "{} already contains a database",
+            to_dir.display()
+        )));
+    }
+
+    let (busy, _, _): (i64, i64, i64) = sqlx::query_as("PRAGMA wal_checkpoint(TRUNCATE)")
+        .fetch_one(pool)
+        .await
+        .map_repo_error("Checkpoint WAL")?;
+    if busy != 0 {
+        return Err(DomainError::Infrastructure(
+            "The database is busy, could not checkpoint the WAL before moving it".to_string(),
+        ));
+    }
+
+    let size_bytes = std::fs::copy(&from_path, &to_path).map_err(|e| {
+        DomainError::Infrastructure(format!(
+            "Failed to copy {} to {}: {}",
+            from_path.display(),
+            to_path.display(),
+            e
+        ))
+    })?;
+
+    let copied = async {
+        if let Some(problem) = integrity_problem(&to_path).await? {
+            return Err(DomainError::DataIntegrity(format!(
+                "The copied database failed its integrity check: {}",
+                problem
+            )));
+        }
+        let from_key_files = copy_key_files(from_dir, to_dir)?;
+        location.point_to(to_dir)?;
+        Ok(from_key_files)
+    }
+    .await;
+
+    match copied {
+        Ok(from_key_files) => {
+            info!(
+                "Moved database from {} to {}",
+                from_path.display(),
+                to_path.display()
+            );
+            Ok(DatabaseMove {
+                from_path,
+                to_path,
+                size_bytes,
+                from_key_files,
+            })
+        }
+        Err(e) => {
+            remove_database_files(&to_path);
+            for path in KeyManager::file_only(to_dir.to_path_buf()).key_files() {
+                let _ = std::fs::remove_file(path);
+            }
+            Err(e)
+        }
+    }
+}
+
+/// Copy the encryption salt file or keyring marker, returning the originals
+///
+/// A salt kept in the OS keyring stays there, the copied marker points the
+/// new data directory at the same entry.
+fn copy_key_files(from_dir: &Path, to_dir: &Path) -> Result<Vec<PathBuf>, DomainError> {
+    let from_files = KeyManager::file_only(from_dir.to_path_buf()).key_files();
+    for from_path in &from_files {
+        let Some(name) = from_path.file_name() else {
+            continue;
+        };
+        let to_path = to_dir.join(name);
+        std::fs::copy(from_path, &to_path).map_err(|e| {
+            DomainError::Infrastructure(format!("Failed to copy the encryption salt: {}", e))
+        })?;
+        let same = std::fs::read(from_path)
+            .and_then(|from_salt| Ok(std::fs::read(&to_path)? == from_salt))
+            .map_err(|e| {
+                DomainError::Infrastructure(format!("Failed to verify the encryption salt: {}", e))
+            })?;
+        if !same {
+            return Err(DomainError::DataIntegrity(
+                "The copied encryption salt does not match the original".to_string(),
+            ));
+        }
+    }
+
+    Ok(from_files)
+}
+
+fn same_dir(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::Database;
+
+    const DB_FILENAME: &str = "neuradock.db";
+
+    async fn database_with_note(dir: &Path, note: &str) -> Database {
+        let database = Database::new(dir.join(DB_FILENAME).to_str().unwrap())
+            .await
+            .unwrap();
+        sqlx::query("CREATE TABLE notes (text TEXT NOT NULL)")
+            .execute(database.pool())
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO notes (text) VALUES (?1)")
+            .bind(note)
+            .execute(database.pool())
+            .await
+            .unwrap();
+        database
+    }
+
+    #[tokio::test]
+    async fn test_move_copies_database_and_salt_then_points_startup_at_it() {
+        let app_data = tempfile::tempdir().unwrap();
+        let target = tempfile::tempdir().unwrap();
+        let to_dir = target.path().join("data");
+        let location = DataLocation::new(app_data.path().to_path_buf(), DB_FILENAME);
+        let salt = KeyManager::file_only(app_data.path().to_path_buf())
+            .initialize()
+            .unwrap();
+        let database = database_with_note(app_data.path(), "moved").await;
+
+        let moved = move_database(database.pool(), &location, app_data.path(), &to_dir)
+            .await
+            .unwrap();
+
+        assert_eq!(moved.to_path, to_dir.join(DB_FILENAME));
+        assert_eq!(
+            location.resolve(),
+            ResolvedDataDir {
+                data_dir: to_dir.clone(),
+                stale: None,
+            }
+        );
+        assert_eq!(
+            KeyManager::file_only(to_dir.clone()).initialize().unwrap(),
+            salt
+        );
+
+        database.pool().close().await;
+        moved.remove_original();
+        assert!(!app_data.path().join(DB_FILENAME).exists());
+        assert!(!app_data.path().join(".encryption_salt").exists());
+
+        let reopened = Database::new(moved.to_path.to_str().unwrap())
+            .await
+            .unwrap();
+        let note: String = sqlx::query_scalar("SELECT text FROM notes")
+            .fetch_one(reopened.pool())
+            .await
+            .unwrap();
+        assert_eq!(note, "moved");
+    }
+
+    #[tokio::test]
+    async fn test_move_carries_keyring_marker() {
+        let app_data = tempfile::tempdir().unwrap();
+        let target = tempfile::tempdir().unwrap();
+        let location = DataLocation::new(app_data.path().to_path_buf(), DB_FILENAME);
+        std::fs::write(app_data.path().join(".encryption_keyring"), "entry").unwrap();
+        let database = database_with_note(app_data.path(), "moved").await;
+
+        let moved = move_database(database.pool(), &location, app_data.path(), target.path())
+            .await
+            .unwrap();
+
+        assert!(KeyManager::file_only(target.path().to_path_buf()).uses_keyring());
+        database.pool().close().await;
+        moved.remove_original();
+        assert!(!app_data.path().join(".encryption_keyring").exists());
+    }
+
+    #[tokio::test]
+    async fn test_move_refuses_directory_that_already_has_a_database() {
+        let app_data = tempfile::tempdir().unwrap();
+        let target = tempfile::tempdir().unwrap();
+        let location = DataLocation::new(app_data.path().to_path_buf(), DB_FILENAME);
+        let database = database_with_note(app_data.path(), "original").await;
+        std::fs::write(target.path().join(DB_FILENAME), b"other").unwrap();
+
+        let moved = move_database(database.pool(), &location, app_data.path(), target.path()).await;
+
+        assert!(matches!(moved, Err(DomainError::Validation(_))));
+        assert_eq!(location.configured_dir().unwrap(), None);
+        assert!(app_data.path().join(DB_FILENAME).exists());
+    }
+
+    #[test]
+    fn test_stale_pointer_falls_back_to_default_and_is_kept() {
+        let app_data = tempfile::tempdir().unwrap();
+        let location = DataLocation::new(app_data.path().to_path_buf(), DB_FILENAME);
+        let missing = app_data.path().join("unmounted");
+        location.point_to(&missing).unwrap();
+
+        let resolved = location.resolve();
+
+        assert_eq!(resolved.data_dir, app_data.path());
+        let stale = resolved.stale.expect("stale location reported");
+        assert_eq!(stale.configured_dir, missing);
+        assert!(stale.message().contains("does not exist"));
+        assert_eq!(location.configured_dir().unwrap(), Some(missing));
+    }
+
+    #[test]
+    fn test_pointing_at_default_dir_removes_pointer() {
+        let app_data = tempfile::tempdir().unwrap();
+        let other = tempfile::tempdir().unwrap();
+        let location = DataLocation::new(app_data.path().to_path_buf(), DB_FILENAME);
+
+        location.point_to(other.path()).unwrap();
+        location.point_to(app_data.path()).unwrap();
+
+        assert_eq!(location.configured_dir().unwrap(), None);
+        assert_eq!(location.resolve().stale, None);
+    }
+}
@@ -0,0 +1,172 @@
+use neuradock_domain::shared::DomainError;
+use sqlx::SqlitePool;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use super::unit_of_work::RepositoryErrorMapper;
+
+/// How much free space a maintenance run gives back to the filesystem
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VacuumMode {
+    /// `PRAGMA incremental_vacuum`, only reclaims pages when auto_vacuum is INCREMENTAL
+    Incremental,
+    /// `VACUUM`, rewrites the whole file
+    Full,
+}
+
+impl VacuumMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VacuumMode::Incremental => "incremental",
+            VacuumMode::Full => "full",
+        }
+    }
+}
+
+/// Outcome of a database maintenance run
+#[derive(Debug, Clone, PartialEq)]
+pub struct DatabaseMaintenanceReport {
+    pub vacuum_mode: VacuumMode,
+    /// Database file plus its WAL, before the run
+    pub size_before_bytes: u64,
+    /// Database file plus its WAL, after the run
+    pub size_after_bytes: u64,
+    pub duration: Duration,
+}
+
+impl DatabaseMaintenanceReport {
+    pub fn reclaimed_bytes(&self) -> u64 {
+        self.size_before_bytes.saturating_sub(self.size_after_bytes)
+    }
+}
+
+/// Optimize, analyze, vacuum and checkpoint the database at `db_path`
+///
+/// All statements run on one pooled connection. VACUUM needs exclusive access,
+/// so callers must make sure no check-in is writing at the same time.
+pub async fn run_database_maintenance(
+    pool: &SqlitePool,
+    db_path: &Path,
+    vacuum_mode: VacuumMode,
+) -> Result<DatabaseMaintenanceReport, DomainError> {
+    let started_at = Instant::now();
+    let size_before_bytes = database_size(db_path);
+
+    let mut conn = pool
+        .acquire()
+        .await
+        .map_err(|e| RepositoryErrorMapper::map_sqlx_error(e, "Acquire maintenance connection"))?;
+
+    let vacuum = match vacuum_mode {
+        VacuumMode::Incremental => "PRAGMA incremental_vacuum",
+        VacuumMode::Full => "VACUUM",
+    };
+    for (statement, context) in [
+        ("PRAGMA optimize", "Optimize database"),
+        ("ANALYZE", "Analyze database"),
+        (vacuum, "Vacuum database"),
+        ("PRAGMA wal_checkpoint(TRUNCATE)", "Checkpoint WAL"),
+    ] {
+        sqlx::query(statement)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| RepositoryErrorMapper::map_sqlx_error(e, context))?;
+    }
+    drop(conn);
+
+    Ok(DatabaseMaintenanceReport {
+        vacuum_mode,
+        size_before_bytes,
+        size_after_bytes: database_size(db_path),
+        duration: started_at.elapsed(),
+    })
+}
+
+/// Size of the database file and its WAL, missing files count as empty
+pub fn database_size(db_path: &Path) -> u64 {
+    file_size(db_path) + file_size(&wal_path(db_path))
+}
+
+/// Write-ahead log next to the database at `db_path`
+pub(super) fn wal_path(db_path: &Path) -> PathBuf {
+    let mut wal_path = PathBuf::from(db_path).into_os_string();
+    wal_path.push("-wal");
+    PathBuf::from(wal_path)
+}
+
+/// Size of the file at `path`, zero when it is missing
+pub(super) fn file_size(path: &Path) -> u64 {
+    std::fs::metadata(path).map_or(0, |metadata| metadata.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::Database;
+
+    async fn database_with_churn(dir: &Path) -> (Database, PathBuf) {
+        let db_path = dir.join("maintenance.db");
+        let database = Database::new(db_path.to_str().unwrap()).await.unwrap();
+        let pool = database.pool();
+
+        sqlx::query("CREATE TABLE churn (id INTEGER PRIMARY KEY, payload TEXT NOT NULL)")
+            .execute(pool)
+            .await
+            .unwrap();
+        let payload = "x".repeat(4096);
+        for _ in 0..200 {
+            sqlx::query("INSERT INTO churn (payload) VALUES (?)")
+                .bind(&payload)
+                .execute(pool)
+                .await
+                .unwrap();
+        }
+        sqlx::query("DELETE FROM churn")
+            .execute(pool)
+            .await
+            .unwrap();
+
+        (database, db_path)
+    }
+
+    #[tokio::test]
+    async fn test_full_vacuum_shrinks_database() {
+        let dir = tempfile::tempdir().unwrap();
+        let (database, db_path) = database_with_churn(dir.path()).await;
+
+        let report = run_database_maintenance(database.pool(), &db_path, VacuumMode::Full)
+            .await
+            .unwrap();
+
+        assert_eq!(report.vacuum_mode, VacuumMode::Full);
+        assert!(report.size_before_bytes > 200 * 4096);
+        assert!(report.size_after_bytes < report.size_before_bytes);
+        assert_eq!(
+            report.reclaimed_bytes(),
+            report.size_before_bytes - report.size_after_bytes
+        );
+    }
+
+    #[tokio::test]
+    async fn test_incremental_vacuum_keeps_database_usable() {
+        let dir = tempfile::tempdir().unwrap();
+        let (database, db_path) = database_with_churn(dir.path()).await;
+
+        let report = run_database_maintenance(database.pool(), &db_path, VacuumMode::Incremental)
+            .await
+            .unwrap();
+
+        assert_eq!(report.vacuum_mode, VacuumMode::Incremental);
+        let (rows,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM churn")
+            .fetch_one(database.pool())
+            .await
+            .unwrap();
+        assert_eq!(rows, 0);
+    }
+
+    #[test]
+    fn test_database_size_of_missing_file_is_zero() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(database_size(&dir.path().join("missing.db")), 0);
+    }
+}
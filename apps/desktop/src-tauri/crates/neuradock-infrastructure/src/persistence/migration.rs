@@ -0,0 +1,160 @@
+use chrono::{DateTime, Utc};
+use log::{info, warn};
+use neuradock_domain::shared::DomainError;
+use serde::{Deserialize, Serialize};
+use sqlx::migrate::{MigrateError, Migrator};
+use sqlx::SqlitePool;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use super::integrity::{backups_dir, remove_database_files};
+
+/// Record of the last failed migration, kept next to the database
+const FAILURE_FILE: &str = "migration_failure.json";
+
+/// A migration that failed at startup, after which the pre-migration snapshot was restored
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MigrationFailure {
+    /// Version of the migration that failed
+    pub version: i64,
+    pub description: String,
+    pub error: String,
+    /// Snapshot the database was restored from
+    pub snapshot_path: PathBuf,
+    pub failed_at: DateTime<Utc>,
+}
+
+impl MigrationFailure {
+    /// Explanation for the user of what happened to their data
+    pub fn message(&self) -> String {
+        format!(
+            "Updating the database failed at migration {} ({}), so it was restored from the snapshot {} taken just before. NeuraDock is running read-only: your data can be viewed and exported but not changed until an update fixes the migration.",
+            self.version,
+            self.description,
+            self.snapshot_path.display()
+        )
+    }
+}
+
+/// Versions of `migrator` not yet applied to the database
+pub(super) async fn pending_versions(
+    pool: &SqlitePool,
+    migrator: &Migrator,
+) -> Result<Vec<i64>, DomainError> {
+    let has_table: bool = sqlx::query_scalar(
+        "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = '_sqlx_migrations')",
+    )
+    .fetch_one(pool)
+    .await
+    .map_err(|e| DomainError::Infrastructure(format!("Failed to read migrations: {}", e)))?;
+
+    let applied: HashSet<i64> = if has_table {
+        sqlx::query_scalar("SELECT version FROM _sqlx_migrations WHERE success = 1")
+            .fetch_all(pool)
+            .await
+            .map_err(|e| DomainError::Infrastructure(format!("Failed to read migrations: {}", e)))?
+            .into_iter()
+            .collect()
+    } else {
+        HashSet::new()
+    };
+
+    Ok(migrator
+        .iter()
+        .filter(|migration| migration.migration_type.is_up_migration())
+        .map(|migration| migration.version)
+        .filter(|version| !applied.contains(version))
+        .collect())
+}
+
+/// Copy the database into the backups folder before migrating to `version`
+pub(super) async fn snapshot(
+    pool: &SqlitePool,
+    db_path: &Path,
+    version: i64,
+) -> Result<PathBuf, DomainError> {
+    let dir = backups_dir(db_path);
+    std::fs::create_dir_all(&dir).map_err(|e| {
+        DomainError::Infrastructure(format!("Failed to create backups directory: {}", e))
+    })?;
+
+    let snapshot_path = dir.join(format!(
+        "pre-migration-{}-{}.db",
+        version,
+        Utc::now().format("%Y%m%d%H%M%S")
+    ));
+    // VACUUM INTO reads one consistent state, including pages still in the WAL
+    sqlx::query("VACUUM INTO ?")
+        .bind(snapshot_path.to_string_lossy().to_string())
+        .execute(pool)
+        .await
+        .map_err(|e| DomainError::Infrastructure(format!("Failed to snapshot database: {}", e)))?;
+
+    info!(
+        "Snapshot before migrating taken at {}",
+        snapshot_path.display()
+    );
+    Ok(snapshot_path)
+}
+
+/// Replace the database and its WAL with the snapshot, the pool must be closed
+pub(super) fn restore_snapshot(db_path: &Path, snapshot_path: &Path) -> Result<(), DomainError> {
+    remove_database_files(db_path);
+    std::fs::copy(snapshot_path, db_path).map_err(|e| {
+        DomainError::DataIntegrity(format!(
+            "A migration failed and the snapshot {} could not be restored: {}",
+            snapshot_path.display(),
+            e
+        ))
+    })?;
+    warn!("Database restored from {}", snapshot_path.display());
+    Ok(())
+}
+
+/// Version and description of the migration that `error` happened in
+///
+/// sqlx names the version for errors in a migration's SQL, otherwise the first
+/// migration still pending after the run is the one that failed.
+pub(super) fn failed_migration(
+    migrator: &Migrator,
+    error: &MigrateError,
+    still_pending: &[i64],
+) -> (i64, String) {
+    let version = match error {
+        MigrateError::ExecuteMigration(_, version) | MigrateError::Dirty(version) => *version,
+        _ => still_pending.first().copied().unwrap_or_default(),
+    };
+    let description = migrator
+        .iter()
+        .find(|migration| migration.version == version)
+        .map(|migration| migration.description.to_string())
+        .unwrap_or_default();
+    (version, description)
+}
+
+fn failure_path(db_path: &Path) -> PathBuf {
+    db_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join(FAILURE_FILE)
+}
+
+/// Remember `failure` until migrations succeed again
+pub(super) fn record_failure(db_path: &Path, failure: &MigrationFailure) {
+    let result = serde_json::to_string_pretty(failure)
+        .map_err(|e| e.to_string())
+        .and_then(|json| std::fs::write(failure_path(db_path), json).map_err(|e| e.to_string()));
+    if let Err(e) = result {
+        warn!("Failed to record migration failure: {}", e);
+    }
+}
+
+/// Forget a recorded failure once all migrations are applied
+pub(super) fn clear_failure(db_path: &Path) {
+    let path = failure_path(db_path);
+    if path.exists() {
+        if let Err(e) = std::fs::remove_file(&path) {
+            warn!("Failed to remove {}: {}", path.display(), e);
+        }
+    }
+}
@@ -1,11 +1,31 @@
 pub mod repositories;
 pub mod unit_of_work;
 
+mod app_data_archive;
 mod database;
+mod integrity;
+mod key_rotation;
+mod location;
+mod maintenance;
+mod migration;
 mod repository_base;
 mod result_ext;
+mod stats;
 
+pub use app_data_archive::{
+    export_app_data, import_app_data, open_archive, seal_archive, AppDataArchive,
+    AppDataImportReport, ImportMode, MIN_PASSPHRASE_LEN,
+};
 pub use database::Database;
-pub use repository_base::SqliteRepositoryBase;
+pub use integrity::{backups_dir, DatabaseRecovery};
+pub use key_rotation::{
+    finish_key_rotation, pending_key_rotation, rotate_encrypted_columns, verify_encrypted_columns,
+    KeyRotationProgress, KeyRotationReport,
+};
+pub use location::{move_database, DataLocation, DatabaseMove, ResolvedDataDir, StaleDataLocation};
+pub use maintenance::{run_database_maintenance, DatabaseMaintenanceReport, VacuumMode};
+pub use migration::MigrationFailure;
+pub use repository_base::{is_busy, retry_on_busy, SqliteRepositoryBase};
 pub use result_ext::ResultExt;
-pub use unit_of_work::{RepositoryErrorMapper, UnitOfWork};
+pub use stats::{database_stats, DatabaseStats};
+pub use unit_of_work::{RepositoryErrorMapper, SqliteTransaction, SqliteUnitOfWork, UnitOfWork};
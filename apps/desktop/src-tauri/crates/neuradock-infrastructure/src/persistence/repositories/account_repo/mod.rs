@@ -5,12 +5,13 @@ mod queries;
 mod types;
 
 use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
 use sqlx::SqlitePool;
 use std::sync::Arc;
 
 use crate::security::EncryptionService;
 use neuradock_domain::account::{Account, AccountRepository};
-use neuradock_domain::shared::{AccountId, DomainError};
+use neuradock_domain::shared::{AccountId, DomainError, DynTransactionContext};
 
 pub struct SqliteAccountRepository {
     pool: Arc<SqlitePool>,
@@ -20,22 +21,23 @@ pub struct SqliteAccountRepository {
 impl SqliteAccountRepository {
     const SELECT_QUERY: &'static str = r#"
             SELECT
-                a.id, a.name, a.provider_id, a.cookies, a.api_user, a.enabled,
-                bh.latest_recorded_at as last_check_in, a.created_at, a.auto_checkin_enabled,
+                a.id, a.name, a.provider_id, a.cookies, a.api_user, a.extra_headers, a.enabled,
+                a.last_check_in, a.created_at, a.auto_checkin_enabled,
                 a.auto_checkin_hour, a.auto_checkin_minute, a.check_in_interval_hours,
+                a.max_attempts_per_day,
+                ca.day as check_in_attempts_day,
+                ca.attempts as check_in_attempts,
                 s.last_login_at, s.token as session_token, s.expires_at as session_expires_at,
                 b.last_checked_at as last_balance_check_at,
                 b.current as current_balance,
                 b.total_consumed,
-                b.total_quota
+                b.total_quota,
+                a.pinned
             FROM accounts a
             LEFT JOIN sessions s ON a.id = s.account_id
             LEFT JOIN balances b ON a.id = b.account_id
-            LEFT JOIN (
-                SELECT account_id, MAX(recorded_at) as latest_recorded_at
-                FROM balance_history
-                GROUP BY account_id
-            ) bh ON a.id = bh.account_id
+            LEFT JOIN check_in_attempts ca ON a.id = ca.account_id
+            WHERE a.deleted_at IS NULL
         "#;
 
     pub fn new(pool: Arc<SqlitePool>, encryption: Arc<EncryptionService>) -> Self {
@@ -49,6 +51,14 @@ impl AccountRepository for SqliteAccountRepository {
         self.save_impl(account).await
     }
 
+    async fn save_in_transaction(
+        &self,
+        tx: &mut DynTransactionContext,
+        account: &Account,
+    ) -> Result<(), DomainError> {
+        self.save_in_transaction_impl(tx, account).await
+    }
+
     async fn find_by_id(&self, id: &AccountId) -> Result<Option<Account>, DomainError> {
         self.find_by_id_impl(id).await
     }
@@ -68,4 +78,31 @@ impl AccountRepository for SqliteAccountRepository {
     async fn delete(&self, id: &AccountId) -> Result<(), DomainError> {
         self.delete_impl(id).await
     }
+
+    async fn soft_delete(
+        &self,
+        id: &AccountId,
+        deleted_at: DateTime<Utc>,
+    ) -> Result<(), DomainError> {
+        self.soft_delete_impl(id, deleted_at).await
+    }
+
+    async fn restore_deleted(&self, id: &AccountId) -> Result<bool, DomainError> {
+        self.restore_deleted_impl(id).await
+    }
+
+    async fn purge_deleted(
+        &self,
+        deleted_before: DateTime<Utc>,
+    ) -> Result<Vec<(AccountId, String)>, DomainError> {
+        self.purge_deleted_impl(deleted_before).await
+    }
+
+    async fn record_check_in_attempt(
+        &self,
+        id: &AccountId,
+        day: NaiveDate,
+    ) -> Result<u32, DomainError> {
+        self.record_check_in_attempt_impl(id, day).await
+    }
 }
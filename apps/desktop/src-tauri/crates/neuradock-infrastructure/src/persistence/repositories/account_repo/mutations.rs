@@ -1,39 +1,59 @@
+use chrono::{DateTime, NaiveDate, Utc};
 use serde_json;
+use sqlx::SqliteConnection;
 use std::time::Instant;
 use tracing::info;
 
-use crate::persistence::RepositoryErrorMapper;
+use crate::persistence::{retry_on_busy, RepositoryErrorMapper, SqliteTransaction};
 use neuradock_domain::account::Account;
-use neuradock_domain::shared::{AccountId, DomainError};
+use neuradock_domain::shared::{AccountId, DomainError, DynTransactionContext};
+
+/// Account columns encrypted with the machine key
+struct EncryptedCredentials {
+    cookies: String,
+    api_user: String,
+    /// `None` when the account has no extra headers
+    extra_headers: Option<String>,
+}
 
 impl super::SqliteAccountRepository {
     pub(super) async fn save_impl(&self, account: &Account) -> Result<(), DomainError> {
         let start = Instant::now();
+        let encrypted = self.encrypt_credentials(account)?;
 
-        // Start a transaction
-        let mut tx = self
-            .pool
-            .begin()
-            .await
-            .map_err(|e| RepositoryErrorMapper::map_sqlx_error(e, "Begin transaction"))?;
+        retry_on_busy("Save account", || async {
+            let mut tx = self.pool.begin().await?;
+            Self::write_account(&mut tx, account, &encrypted).await?;
+            tx.commit().await
+        })
+        .await?;
 
-        // 1. Save/Update account (without balance/session fields)
-        let account_query = r#"
-            INSERT INTO accounts (id, name, provider_id, cookies, api_user, enabled, last_check_in, created_at, auto_checkin_enabled, auto_checkin_hour, auto_checkin_minute, check_in_interval_hours)
-            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)
-            ON CONFLICT(id) DO UPDATE SET
-                name = ?2,
-                provider_id = ?3,
-                cookies = ?4,
-                api_user = ?5,
-                enabled = ?6,
-                last_check_in = ?7,
-                auto_checkin_enabled = ?9,
-                auto_checkin_hour = ?10,
-                auto_checkin_minute = ?11,
-                check_in_interval_hours = ?12
-        "#;
+        let elapsed = start.elapsed();
+        info!(
+            "📊 Account saved: {} in {:.2}ms",
+            account.id().as_str(),
+            elapsed.as_secs_f64() * 1000.0
+        );
+
+        Ok(())
+    }
+
+    /// Write the account into a transaction owned by the caller's unit of work
+    pub(super) async fn save_in_transaction_impl(
+        &self,
+        tx: &mut DynTransactionContext,
+        account: &Account,
+    ) -> Result<(), DomainError> {
+        let encrypted = self.encrypt_credentials(account)?;
+        let tx = SqliteTransaction::from_context(tx)?;
+
+        Self::write_account(tx, account, &encrypted)
+            .await
+            .map_err(|e| RepositoryErrorMapper::map_sqlx_error(e, "Save account"))
+    }
 
+    /// Encrypted cookies JSON, API user and extra headers JSON
+    fn encrypt_credentials(&self, account: &Account) -> Result<EncryptedCredentials, DomainError> {
         // Encrypt cookies JSON
         let cookies_json = serde_json::to_string(account.credentials().cookies())
             .map_err(|e| RepositoryErrorMapper::map_json_error(e, "Serialize account cookies"))?;
@@ -50,12 +70,57 @@ impl super::SqliteAccountRepository {
                 DomainError::DataIntegrity(format!("Failed to encrypt api_user: {}", e))
             })?;
 
+        // Encrypt extra headers JSON, which may carry tokens
+        let encrypted_extra_headers = if account.extra_headers().is_empty() {
+            None
+        } else {
+            let headers_json = serde_json::to_string(account.extra_headers()).map_err(|e| {
+                RepositoryErrorMapper::map_json_error(e, "Serialize account extra headers")
+            })?;
+            Some(self.encryption.encrypt(&headers_json).map_err(|e| {
+                DomainError::DataIntegrity(format!("Failed to encrypt extra_headers: {}", e))
+            })?)
+        };
+
+        Ok(EncryptedCredentials {
+            cookies: encrypted_cookies,
+            api_user: encrypted_api_user,
+            extra_headers: encrypted_extra_headers,
+        })
+    }
+
+    /// Write the account with its session and balance inside `conn`'s transaction
+    async fn write_account(
+        conn: &mut SqliteConnection,
+        account: &Account,
+        encrypted: &EncryptedCredentials,
+    ) -> Result<(), sqlx::Error> {
+        // 1. Save/Update account (without balance/session fields). last_check_in is
+        // only set on insert, afterwards balance history writes maintain it
+        let account_query = r#"
+            INSERT INTO accounts (id, name, provider_id, cookies, api_user, enabled, last_check_in, created_at, auto_checkin_enabled, auto_checkin_hour, auto_checkin_minute, check_in_interval_hours, pinned, max_attempts_per_day, extra_headers)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)
+            ON CONFLICT(id) DO UPDATE SET
+                name = ?2,
+                provider_id = ?3,
+                cookies = ?4,
+                api_user = ?5,
+                enabled = ?6,
+                auto_checkin_enabled = ?9,
+                auto_checkin_hour = ?10,
+                auto_checkin_minute = ?11,
+                check_in_interval_hours = ?12,
+                pinned = ?13,
+                max_attempts_per_day = ?14,
+                extra_headers = ?15
+        "#;
+
         sqlx::query(account_query)
             .bind(account.id().as_str())
             .bind(account.name())
             .bind(account.provider_id().as_str())
-            .bind(encrypted_cookies)
-            .bind(encrypted_api_user)
+            .bind(&encrypted.cookies)
+            .bind(&encrypted.api_user)
             .bind(account.is_enabled())
             .bind(account.last_check_in())
             .bind(account.created_at())
@@ -63,9 +128,11 @@ impl super::SqliteAccountRepository {
             .bind(account.auto_checkin_hour() as i64)
             .bind(account.auto_checkin_minute() as i64)
             .bind(account.check_in_interval_hours() as i64)
-            .execute(&mut *tx)
-            .await
-            .map_err(|e| RepositoryErrorMapper::map_sqlx_error(e, "Save account"))?;
+            .bind(account.is_pinned())
+            .bind(account.max_attempts_per_day() as i64)
+            .bind(&encrypted.extra_headers)
+            .execute(&mut *conn)
+            .await?;
 
         // 2. Save/Update session if exists
         if let (Some(token), Some(expires_at), Some(last_login_at)) = (
@@ -87,9 +154,8 @@ impl super::SqliteAccountRepository {
                 .bind(token)
                 .bind(expires_at.to_rfc3339())
                 .bind(last_login_at.to_rfc3339())
-                .execute(&mut *tx)
-                .await
-                .map_err(|e| RepositoryErrorMapper::map_sqlx_error(e, "Save session"))?;
+                .execute(&mut *conn)
+                .await?;
         }
 
         // 3. Save/Update balance if exists
@@ -115,35 +181,50 @@ impl super::SqliteAccountRepository {
                 .bind(consumed)
                 .bind(income)
                 .bind(checked_at.to_rfc3339())
-                .execute(&mut *tx)
-                .await
-                .map_err(|e| RepositoryErrorMapper::map_sqlx_error(e, "Save balance"))?;
+                .execute(&mut *conn)
+                .await?;
         }
 
-        // Commit transaction
-        tx.commit()
-            .await
-            .map_err(|e| RepositoryErrorMapper::map_sqlx_error(e, "Commit transaction"))?;
+        Ok(())
+    }
 
-        let elapsed = start.elapsed();
-        info!(
-            "📊 Account saved: {} in {:.2}ms",
-            account.id().as_str(),
-            elapsed.as_secs_f64() * 1000.0
-        );
+    /// Increment the attempt counter, starting over when `day` differs from the stored day
+    ///
+    /// The counter lives outside the `accounts` row so that saving an account
+    /// loaded before the attempt cannot roll it back.
+    pub(super) async fn record_check_in_attempt_impl(
+        &self,
+        id: &AccountId,
+        day: NaiveDate,
+    ) -> Result<u32, DomainError> {
+        let query = r#"
+            INSERT INTO check_in_attempts (account_id, day, attempts)
+            VALUES (?1, ?2, 1)
+            ON CONFLICT(account_id) DO UPDATE SET
+                attempts = CASE WHEN day = ?2 THEN attempts + 1 ELSE 1 END,
+                day = ?2
+            RETURNING attempts
+        "#;
 
-        Ok(())
+        let attempts: i64 = retry_on_busy("Record check-in attempt", || {
+            sqlx::query_scalar(query)
+                .bind(id.as_str())
+                .bind(day)
+                .fetch_one(&*self.pool)
+        })
+        .await?;
+
+        Ok(attempts.max(0) as u32)
     }
 
     pub(super) async fn delete_impl(&self, id: &AccountId) -> Result<(), DomainError> {
         let start = Instant::now();
         let query = "DELETE FROM accounts WHERE id = ?1";
 
-        sqlx::query(query)
-            .bind(id.as_str())
-            .execute(&*self.pool)
-            .await
-            .map_err(|e| RepositoryErrorMapper::map_sqlx_error(e, "Delete account"))?;
+        retry_on_busy("Delete account", || {
+            sqlx::query(query).bind(id.as_str()).execute(&*self.pool)
+        })
+        .await?;
 
         let elapsed = start.elapsed();
         info!(
@@ -154,4 +235,65 @@ impl super::SqliteAccountRepository {
 
         Ok(())
     }
+
+    pub(super) async fn soft_delete_impl(
+        &self,
+        id: &AccountId,
+        deleted_at: DateTime<Utc>,
+    ) -> Result<(), DomainError> {
+        let query = "UPDATE accounts SET deleted_at = ?2 WHERE id = ?1 AND deleted_at IS NULL";
+
+        retry_on_busy("Soft delete account", || {
+            sqlx::query(query)
+                .bind(id.as_str())
+                .bind(deleted_at.to_rfc3339())
+                .execute(&*self.pool)
+        })
+        .await?;
+
+        info!("📊 soft_delete({})", id.as_str());
+        Ok(())
+    }
+
+    pub(super) async fn restore_deleted_impl(&self, id: &AccountId) -> Result<bool, DomainError> {
+        let query =
+            "UPDATE accounts SET deleted_at = NULL WHERE id = ?1 AND deleted_at IS NOT NULL";
+
+        let result = retry_on_busy("Restore deleted account", || {
+            sqlx::query(query).bind(id.as_str()).execute(&*self.pool)
+        })
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Permanently delete accounts soft-deleted before `deleted_before`
+    ///
+    /// Their sessions, balances and history go with them through the foreign
+    /// keys.
+    pub(super) async fn purge_deleted_impl(
+        &self,
+        deleted_before: DateTime<Utc>,
+    ) -> Result<Vec<(AccountId, String)>, DomainError> {
+        let query = r#"
+            DELETE FROM accounts
+            WHERE deleted_at IS NOT NULL AND datetime(deleted_at) <= datetime(?1)
+            RETURNING id, name
+        "#;
+
+        let purged: Vec<(String, String)> = retry_on_busy("Purge deleted accounts", || {
+            sqlx::query_as(query)
+                .bind(deleted_before.to_rfc3339())
+                .fetch_all(&*self.pool)
+        })
+        .await?;
+
+        if !purged.is_empty() {
+            info!("📊 purge_deleted(): {} accounts removed", purged.len());
+        }
+        Ok(purged
+            .into_iter()
+            .map(|(id, name)| (AccountId::from_string(&id), name))
+            .collect())
+    }
 }
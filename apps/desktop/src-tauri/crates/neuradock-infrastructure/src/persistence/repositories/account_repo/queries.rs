@@ -16,7 +16,7 @@ impl super::SqliteAccountRepository {
         let query = format!(
             r#"
             {}
-            WHERE a.id = ?1
+            AND a.id = ?1
         "#,
             Self::SELECT_QUERY
         );
@@ -62,7 +62,7 @@ impl super::SqliteAccountRepository {
         let query = format!(
             r#"
             {}
-            WHERE a.id IN ({})
+            AND a.id IN ({})
         "#,
             Self::SELECT_QUERY,
             placeholders
@@ -147,7 +147,7 @@ impl super::SqliteAccountRepository {
         let query = format!(
             r#"
             {}
-            WHERE a.enabled = true
+            AND a.enabled = true
             ORDER BY a.created_at DESC
         "#,
             Self::SELECT_QUERY
@@ -1,11 +1,11 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde_json;
 use sqlx::FromRow;
 
 use crate::persistence::RepositoryErrorMapper;
 use crate::security::EncryptionService;
 use neuradock_domain::account::{Account, Credentials};
-use neuradock_domain::shared::{AccountId, DomainError, ProviderId};
+use neuradock_domain::shared::{AccountId, DomainError, ExtraHeaders, ProviderId};
 
 #[derive(FromRow)]
 pub(super) struct AccountRow {
@@ -14,6 +14,7 @@ pub(super) struct AccountRow {
     pub provider_id: String,
     pub cookies: String,
     pub api_user: String,
+    pub extra_headers: Option<String>,
     pub enabled: bool,
     pub last_check_in: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
@@ -21,6 +22,9 @@ pub(super) struct AccountRow {
     pub auto_checkin_hour: i64,
     pub auto_checkin_minute: i64,
     pub check_in_interval_hours: i64,
+    pub max_attempts_per_day: i64,
+    pub check_in_attempts_day: Option<NaiveDate>,
+    pub check_in_attempts: Option<i64>,
     pub last_login_at: Option<DateTime<Utc>>,
     pub session_token: Option<String>,
     pub session_expires_at: Option<DateTime<Utc>>,
@@ -28,6 +32,7 @@ pub(super) struct AccountRow {
     pub current_balance: Option<f64>,
     pub total_consumed: Option<f64>,
     pub total_quota: Option<f64>,
+    pub pinned: bool,
 }
 
 impl AccountRow {
@@ -57,12 +62,28 @@ impl AccountRow {
 
         let credentials = Credentials::new(cookies, api_user);
 
+        let extra_headers = match &self.extra_headers {
+            Some(encrypted) => {
+                let headers_json = encryption.decrypt(encrypted).map_err(|e| {
+                    DomainError::DataIntegrity(format!(
+                        "Failed to decrypt extra_headers for account {}: {}",
+                        self.id, e
+                    ))
+                })?;
+                serde_json::from_str::<ExtraHeaders>(&headers_json).map_err(|e| {
+                    RepositoryErrorMapper::map_json_error(e, "Deserialize account extra headers")
+                })?
+            }
+            None => ExtraHeaders::default(),
+        };
+
         Ok(Account::builder(
             AccountId::from_string(&self.id),
             self.name,
             ProviderId::from_string(&self.provider_id),
             credentials,
         )
+        .extra_headers(extra_headers)
         .enabled(self.enabled)
         .last_check_in(self.last_check_in)
         .created_at(self.created_at)
@@ -70,6 +91,11 @@ impl AccountRow {
         .auto_checkin_hour(self.auto_checkin_hour as u8)
         .auto_checkin_minute(self.auto_checkin_minute as u8)
         .check_in_interval_hours(self.check_in_interval_hours as u8)
+        .max_attempts_per_day(self.max_attempts_per_day.clamp(0, u8::MAX as i64) as u8)
+        .check_in_attempts(
+            self.check_in_attempts_day,
+            self.check_in_attempts.unwrap_or(0).max(0) as u32,
+        )
         .last_login_at(self.last_login_at)
         .session_token(self.session_token)
         .session_expires_at(self.session_expires_at)
@@ -77,6 +103,7 @@ impl AccountRow {
         .current_balance(self.current_balance)
         .total_consumed(self.total_consumed)
         .total_quota(self.total_quota)
+        .pinned(self.pinned)
         .build())
     }
 }
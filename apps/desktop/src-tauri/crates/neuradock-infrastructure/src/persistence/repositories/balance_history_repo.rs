@@ -1,13 +1,16 @@
 use async_trait::async_trait;
 use chrono::{DateTime, NaiveDate, Utc};
-use sqlx::{FromRow, SqlitePool};
+use sha2::{Digest, Sha256};
+use sqlx::{FromRow, SqliteConnection, SqlitePool};
+use std::collections::HashMap;
 use std::sync::Arc;
 
-use crate::persistence::SqliteRepositoryBase;
+use crate::persistence::{retry_on_busy, SqliteRepositoryBase};
 use neuradock_domain::balance_history::{
     BalanceHistoryDailySummary, BalanceHistoryRecord, BalanceHistoryRepository,
+    ProviderDailyBalanceTotal,
 };
-use neuradock_domain::shared::{AccountId, DomainError};
+use neuradock_domain::shared::{AccountId, DomainError, ProviderId};
 
 #[derive(FromRow)]
 struct BalanceHistoryRow {
@@ -40,6 +43,43 @@ struct DailySummaryRow {
     daily_consumed: f64,
 }
 
+#[derive(FromRow)]
+struct AccountDailySummaryRow {
+    account_id: String,
+    #[sqlx(flatten)]
+    summary: DailySummaryRow,
+}
+
+#[derive(FromRow)]
+struct ProviderDailyTotalRow {
+    provider_id: String,
+    check_in_date: String,
+    current_balance: f64,
+    total_consumed: f64,
+    total_quota: f64,
+    account_count: i64,
+}
+
+impl ProviderDailyTotalRow {
+    fn try_into_total(self) -> Result<ProviderDailyBalanceTotal, DomainError> {
+        let date = NaiveDate::parse_from_str(&self.check_in_date, "%Y-%m-%d").map_err(|e| {
+            DomainError::Validation(format!(
+                "Invalid check_in_date: {} ({})",
+                self.check_in_date, e
+            ))
+        })?;
+
+        Ok(ProviderDailyBalanceTotal::restore(
+            ProviderId::from_string(&self.provider_id),
+            date,
+            self.current_balance,
+            self.total_consumed,
+            self.total_quota,
+            self.account_count as u32,
+        ))
+    }
+}
+
 impl DailySummaryRow {
     fn try_into_summary(self) -> Result<BalanceHistoryDailySummary, DomainError> {
         let date = NaiveDate::parse_from_str(&self.check_in_date, "%Y-%m-%d").map_err(|e| {
@@ -58,6 +98,41 @@ impl DailySummaryRow {
     }
 }
 
+/// Copy the newest history time onto `accounts.last_check_in`
+///
+/// Account reads use the denormalized column instead of aggregating the whole
+/// history, so every write to `balance_history` must call this. `None`
+/// refreshes every account.
+pub(crate) async fn refresh_last_check_in(
+    conn: &mut SqliteConnection,
+    account_id: Option<&str>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        UPDATE accounts
+        SET last_check_in = (
+            SELECT MAX(recorded_at) FROM balance_history WHERE balance_history.account_id = accounts.id
+        )
+        WHERE ?1 IS NULL OR id = ?1
+        "#,
+    )
+    .bind(account_id)
+    .execute(&mut *conn)
+    .await?;
+    Ok(())
+}
+
+/// Id of an account's snapshot for the UTC day of `recorded_at`
+///
+/// The same account and day always give the same id, so saving a later
+/// snapshot replaces the earlier one.
+fn daily_snapshot_id(account_id: &AccountId, recorded_at: DateTime<Utc>) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(account_id.as_str().as_bytes());
+    hasher.update(recorded_at.format("%Y-%m-%d").to_string().as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
 pub struct SqliteBalanceHistoryRepository {
     base: SqliteRepositoryBase,
 }
@@ -84,22 +159,45 @@ impl BalanceHistoryRepository for SqliteBalanceHistoryRepository {
             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6)
         "#;
 
-        self.base
-            .execute(
-                sqlx::query(query)
-                    .bind(record.id())
-                    .bind(record.account_id().as_str())
-                    .bind(record.current_balance())
-                    .bind(record.total_consumed())
-                    .bind(record.total_quota())
-                    .bind(record.recorded_at()),
-                "Save balance history",
-            )
-            .await?;
+        retry_on_busy("Save balance history", || async {
+            let mut tx = self.base.pool().begin().await?;
+            sqlx::query(query)
+                .bind(record.id())
+                .bind(record.account_id().as_str())
+                .bind(record.current_balance())
+                .bind(record.total_consumed())
+                .bind(record.total_quota())
+                .bind(record.recorded_at())
+                .execute(&mut *tx)
+                .await?;
+            refresh_last_check_in(&mut tx, Some(record.account_id().as_str())).await?;
+            tx.commit().await
+        })
+        .await?;
 
         Ok(())
     }
 
+    async fn upsert_daily_snapshot(
+        &self,
+        account_id: &AccountId,
+        current_balance: f64,
+        total_consumed: f64,
+        total_quota: f64,
+        recorded_at: DateTime<Utc>,
+    ) -> Result<BalanceHistoryRecord, DomainError> {
+        let record = BalanceHistoryRecord::new(
+            daily_snapshot_id(account_id, recorded_at),
+            account_id.clone(),
+            current_balance,
+            total_consumed,
+            total_quota,
+            recorded_at,
+        )?;
+        self.save(&record).await?;
+        Ok(record)
+    }
+
     async fn find_latest_by_account_id(
         &self,
         account_id: &AccountId,
@@ -230,6 +328,106 @@ impl BalanceHistoryRepository for SqliteBalanceHistoryRepository {
         rows.into_iter().map(|r| r.try_into_summary()).collect()
     }
 
+    async fn list_daily_summaries_for_accounts_in_range(
+        &self,
+        account_ids: &[AccountId],
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<HashMap<AccountId, Vec<BalanceHistoryDailySummary>>, DomainError> {
+        if account_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        // ?1 and ?2 are the range, account ids follow
+        let placeholders = (3..account_ids.len() + 3)
+            .map(|i| format!("?{}", i))
+            .collect::<Vec<_>>()
+            .join(",");
+        let query = format!(
+            r#"
+            SELECT
+                account_id,
+                DATE(recorded_at) AS check_in_date,
+                MAX(total_quota) AS daily_total_quota,
+                MAX(current_balance) AS daily_balance,
+                MAX(total_consumed) AS daily_consumed
+            FROM balance_history
+            WHERE account_id IN ({})
+              AND DATE(recorded_at) >= ?1
+              AND DATE(recorded_at) <= ?2
+            GROUP BY account_id, DATE(recorded_at)
+            ORDER BY account_id ASC, check_in_date ASC
+        "#,
+            placeholders
+        );
+
+        let mut query_builder = sqlx::query_as::<_, AccountDailySummaryRow>(&query)
+            .bind(start_date.format("%Y-%m-%d").to_string())
+            .bind(end_date.format("%Y-%m-%d").to_string());
+        for account_id in account_ids {
+            query_builder = query_builder.bind(account_id.as_str());
+        }
+
+        let rows: Vec<AccountDailySummaryRow> = self
+            .base
+            .fetch_all(query_builder, "List daily summaries of accounts in range")
+            .await?;
+
+        let mut summaries: HashMap<AccountId, Vec<BalanceHistoryDailySummary>> = HashMap::new();
+        for row in rows {
+            summaries
+                .entry(AccountId::from_string(&row.account_id))
+                .or_default()
+                .push(row.summary.try_into_summary()?);
+        }
+        Ok(summaries)
+    }
+
+    async fn list_provider_daily_totals_in_range(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<Vec<ProviderDailyBalanceTotal>, DomainError> {
+        let query = r#"
+            WITH daily_summary AS (
+                SELECT
+                    account_id,
+                    DATE(recorded_at) AS check_in_date,
+                    MAX(total_quota) AS daily_total_quota,
+                    MAX(current_balance) AS daily_balance,
+                    MAX(total_consumed) AS daily_consumed
+                FROM balance_history
+                WHERE DATE(recorded_at) >= ?1
+                  AND DATE(recorded_at) <= ?2
+                GROUP BY account_id, DATE(recorded_at)
+            )
+            SELECT
+                a.provider_id AS provider_id,
+                d.check_in_date AS check_in_date,
+                SUM(d.daily_balance) AS current_balance,
+                SUM(d.daily_consumed) AS total_consumed,
+                SUM(d.daily_total_quota) AS total_quota,
+                COUNT(*) AS account_count
+            FROM daily_summary d
+            JOIN accounts a ON a.id = d.account_id
+            WHERE a.enabled = 1
+            GROUP BY a.provider_id, d.check_in_date
+            ORDER BY a.provider_id ASC, d.check_in_date ASC
+        "#;
+
+        let rows: Vec<ProviderDailyTotalRow> = self
+            .base
+            .fetch_all(
+                sqlx::query_as(query)
+                    .bind(start_date.format("%Y-%m-%d").to_string())
+                    .bind(end_date.format("%Y-%m-%d").to_string()),
+                "List provider daily totals in range",
+            )
+            .await?;
+
+        rows.into_iter().map(|r| r.try_into_total()).collect()
+    }
+
     async fn find_daily_summary(
         &self,
         account_id: &AccountId,
@@ -281,4 +479,60 @@ impl BalanceHistoryRepository for SqliteBalanceHistoryRepository {
             .map(|id| AccountId::from_string(&id))
             .collect())
     }
+    async fn find_streak_reset(
+        &self,
+        account_id: &AccountId,
+    ) -> Result<Option<NaiveDate>, DomainError> {
+        let reset_on: Option<Option<String>> =
+            sqlx::query_scalar("SELECT streak_reset_on FROM accounts WHERE id = ?1")
+                .bind(account_id.as_str())
+                .fetch_optional(self.base.pool())
+                .await
+                .map_err(|e| DomainError::Repository(format!("Find streak reset: {e}")))?;
+
+        reset_on
+            .flatten()
+            .map(|day| {
+                NaiveDate::parse_from_str(&day, "%Y-%m-%d").map_err(|e| {
+                    DomainError::Validation(format!("Invalid streak_reset_on: {} ({})", day, e))
+                })
+            })
+            .transpose()
+    }
+
+    async fn set_streak_reset(
+        &self,
+        account_id: &AccountId,
+        reset_on: Option<NaiveDate>,
+    ) -> Result<(), DomainError> {
+        let reset_on = reset_on.map(|day| day.format("%Y-%m-%d").to_string());
+        let updated = self
+            .base
+            .execute_with_retry(
+                || {
+                    sqlx::query("UPDATE accounts SET streak_reset_on = ?1 WHERE id = ?2")
+                        .bind(reset_on.clone())
+                        .bind(account_id.as_str())
+                },
+                "Set streak reset",
+            )
+            .await?;
+
+        if updated == 0 {
+            return Err(DomainError::AccountNotFound(
+                account_id.as_str().to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn clear_streak_resets(&self) -> Result<(), DomainError> {
+        self.base
+            .execute_with_retry(
+                || sqlx::query("UPDATE accounts SET streak_reset_on = NULL"),
+                "Clear streak resets",
+            )
+            .await?;
+        Ok(())
+    }
 }
@@ -0,0 +1,129 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, SqlitePool};
+use std::sync::Arc;
+
+use neuradock_domain::check_in_retry::{CheckInRetry, CheckInRetryRepository, CheckInRetryStatus};
+use neuradock_domain::shared::{AccountId, DomainError};
+
+use crate::persistence::SqliteRepositoryBase;
+
+#[derive(FromRow)]
+struct CheckInRetryRow {
+    account_id: String,
+    attempts: i64,
+    next_attempt_at: DateTime<Utc>,
+    last_error: String,
+    status: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl CheckInRetryRow {
+    fn into_retry(self) -> Result<CheckInRetry, DomainError> {
+        let status = CheckInRetryStatus::parse(&self.status).ok_or_else(|| {
+            DomainError::DataIntegrity(format!("Invalid check-in retry status: {}", self.status))
+        })?;
+
+        Ok(CheckInRetry::restore(
+            AccountId::from_string(&self.account_id),
+            self.attempts.max(0) as u32,
+            self.next_attempt_at,
+            self.last_error,
+            status,
+            self.created_at,
+            self.updated_at,
+        ))
+    }
+}
+
+pub struct SqliteCheckInRetryRepository {
+    base: SqliteRepositoryBase,
+}
+
+impl SqliteCheckInRetryRepository {
+    pub fn new(pool: Arc<SqlitePool>) -> Self {
+        Self {
+            base: SqliteRepositoryBase::new(pool),
+        }
+    }
+}
+
+#[async_trait]
+impl CheckInRetryRepository for SqliteCheckInRetryRepository {
+    async fn save(&self, retry: &CheckInRetry) -> Result<(), DomainError> {
+        let query = r#"
+            INSERT INTO check_in_retry_queue
+                (account_id, attempts, next_attempt_at, last_error, status, created_at, updated_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+            ON CONFLICT(account_id) DO UPDATE SET
+                attempts = ?2,
+                next_attempt_at = ?3,
+                last_error = ?4,
+                status = ?5,
+                updated_at = ?7
+        "#;
+
+        self.base
+            .execute(
+                sqlx::query(query)
+                    .bind(retry.account_id().as_str())
+                    .bind(retry.attempts() as i64)
+                    .bind(retry.next_attempt_at())
+                    .bind(retry.last_error())
+                    .bind(retry.status().as_str())
+                    .bind(retry.created_at())
+                    .bind(retry.updated_at()),
+                "Save check-in retry",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn find_by_account_id(
+        &self,
+        account_id: &AccountId,
+    ) -> Result<Option<CheckInRetry>, DomainError> {
+        let query = "SELECT account_id, attempts, next_attempt_at, last_error, status, created_at, updated_at FROM check_in_retry_queue WHERE account_id = ?1";
+
+        let row: Option<CheckInRetryRow> = self
+            .base
+            .fetch_optional(
+                sqlx::query_as(query).bind(account_id.as_str()),
+                "Find check-in retry by account ID",
+            )
+            .await?;
+
+        row.map(|r| r.into_retry()).transpose()
+    }
+
+    async fn find_due(&self, now: DateTime<Utc>) -> Result<Vec<CheckInRetry>, DomainError> {
+        let query = "SELECT account_id, attempts, next_attempt_at, last_error, status, created_at, updated_at FROM check_in_retry_queue WHERE status = ?1 AND next_attempt_at <= ?2 ORDER BY next_attempt_at ASC";
+
+        let rows: Vec<CheckInRetryRow> = self
+            .base
+            .fetch_all(
+                sqlx::query_as(query)
+                    .bind(CheckInRetryStatus::Pending.as_str())
+                    .bind(now),
+                "Find due check-in retries",
+            )
+            .await?;
+
+        rows.into_iter().map(|r| r.into_retry()).collect()
+    }
+
+    async fn delete(&self, account_id: &AccountId) -> Result<(), DomainError> {
+        let query = "DELETE FROM check_in_retry_queue WHERE account_id = ?1";
+
+        self.base
+            .execute(
+                sqlx::query(query).bind(account_id.as_str()),
+                "Delete check-in retry",
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,138 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, SqlitePool};
+use std::sync::Arc;
+
+use neuradock_domain::check_in_timing::{
+    CheckInPhase, CheckInPhaseDurations, CheckInTiming, CheckInTimingRepository,
+};
+use neuradock_domain::shared::{AccountId, DomainError, ProviderId};
+
+use crate::persistence::SqliteRepositoryBase;
+
+#[derive(FromRow)]
+struct CheckInTimingRow {
+    account_id: String,
+    provider_id: String,
+    started_at: DateTime<Utc>,
+    success: bool,
+    cookie_preparation_ms: i64,
+    waf_refresh_ms: i64,
+    user_info_ms: i64,
+    check_in_request_ms: i64,
+    balance_fetch_ms: i64,
+    total_ms: i64,
+}
+
+impl CheckInTimingRow {
+    fn into_timing(self) -> CheckInTiming {
+        let mut phases = CheckInPhaseDurations::default();
+        for (phase, ms) in [
+            (CheckInPhase::CookiePreparation, self.cookie_preparation_ms),
+            (CheckInPhase::WafRefresh, self.waf_refresh_ms),
+            (CheckInPhase::UserInfo, self.user_info_ms),
+            (CheckInPhase::CheckInRequest, self.check_in_request_ms),
+            (CheckInPhase::BalanceFetch, self.balance_fetch_ms),
+        ] {
+            phases.set_ms(phase, ms.max(0) as u64);
+        }
+
+        CheckInTiming::restore(
+            AccountId::from_string(&self.account_id),
+            ProviderId::from_string(&self.provider_id),
+            self.started_at,
+            self.success,
+            phases,
+            self.total_ms.max(0) as u64,
+        )
+    }
+}
+
+pub struct SqliteCheckInTimingRepository {
+    base: SqliteRepositoryBase,
+}
+
+impl SqliteCheckInTimingRepository {
+    pub fn new(pool: Arc<SqlitePool>) -> Self {
+        Self {
+            base: SqliteRepositoryBase::new(pool),
+        }
+    }
+}
+
+#[async_trait]
+impl CheckInTimingRepository for SqliteCheckInTimingRepository {
+    async fn save(&self, timing: &CheckInTiming) -> Result<(), DomainError> {
+        let query = r#"
+            INSERT INTO check_in_timings
+                (account_id, provider_id, started_at, success, cookie_preparation_ms,
+                 waf_refresh_ms, user_info_ms, check_in_request_ms, balance_fetch_ms, total_ms)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+        "#;
+
+        self.base
+            .execute(
+                sqlx::query(query)
+                    .bind(timing.account_id().as_str())
+                    .bind(timing.provider_id().as_str())
+                    .bind(timing.started_at())
+                    .bind(timing.success())
+                    .bind(timing.phase_ms(CheckInPhase::CookiePreparation) as i64)
+                    .bind(timing.phase_ms(CheckInPhase::WafRefresh) as i64)
+                    .bind(timing.phase_ms(CheckInPhase::UserInfo) as i64)
+                    .bind(timing.phase_ms(CheckInPhase::CheckInRequest) as i64)
+                    .bind(timing.phase_ms(CheckInPhase::BalanceFetch) as i64)
+                    .bind(timing.total_ms() as i64),
+                "Save check-in timing",
+            )
+            .await?;
+
+        let prune = r#"
+            DELETE FROM check_in_timings
+            WHERE account_id = ?1 AND id NOT IN (
+                SELECT id FROM check_in_timings
+                WHERE account_id = ?1
+                ORDER BY started_at DESC, id DESC
+                LIMIT ?2
+            )
+        "#;
+
+        self.base
+            .execute(
+                sqlx::query(prune)
+                    .bind(timing.account_id().as_str())
+                    .bind(CheckInTiming::MAX_PER_ACCOUNT as i64),
+                "Prune check-in timings",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn find_recent_by_account(
+        &self,
+        account_id: &AccountId,
+        limit: u32,
+    ) -> Result<Vec<CheckInTiming>, DomainError> {
+        let query = r#"
+            SELECT account_id, provider_id, started_at, success, cookie_preparation_ms,
+                   waf_refresh_ms, user_info_ms, check_in_request_ms, balance_fetch_ms, total_ms
+            FROM check_in_timings
+            WHERE account_id = ?1
+            ORDER BY started_at DESC, id DESC
+            LIMIT ?2
+        "#;
+
+        let rows: Vec<CheckInTimingRow> = self
+            .base
+            .fetch_all(
+                sqlx::query_as(query)
+                    .bind(account_id.as_str())
+                    .bind(limit as i64),
+                "Find recent check-in timings",
+            )
+            .await?;
+
+        Ok(rows.into_iter().map(|r| r.into_timing()).collect())
+    }
+}
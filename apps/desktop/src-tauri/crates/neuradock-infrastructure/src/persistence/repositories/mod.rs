@@ -1,6 +1,8 @@
 pub mod account_repo;
 pub mod balance_history_repo;
 pub mod balance_repo;
+pub mod check_in_retry_repo;
+pub mod check_in_timing_repo;
 pub mod custom_node_repository;
 pub mod independent_key_repo;
 pub mod provider_models_repository;
@@ -13,6 +15,8 @@ pub mod waf_cookies_repository;
 pub use account_repo::SqliteAccountRepository;
 pub use balance_history_repo::SqliteBalanceHistoryRepository;
 pub use balance_repo::SqliteBalanceRepository;
+pub use check_in_retry_repo::SqliteCheckInRetryRepository;
+pub use check_in_timing_repo::SqliteCheckInTimingRepository;
 pub use custom_node_repository::SqliteCustomProviderNodeRepository;
 pub use independent_key_repo::SqliteIndependentKeyRepository;
 pub use provider_models_repository::SqliteProviderModelsRepository;
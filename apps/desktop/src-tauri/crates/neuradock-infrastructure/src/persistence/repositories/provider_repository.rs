@@ -3,8 +3,11 @@ use chrono::{DateTime, Utc};
 use sqlx::{FromRow, SqlitePool};
 use std::sync::Arc;
 
-use neuradock_domain::check_in::{Provider, ProviderConfig, ProviderRepository};
-use neuradock_domain::shared::{DomainError, ProviderId};
+use neuradock_domain::check_in::{
+    BalanceDisplay, Provider, ProviderConfig, ProviderDialect, ProviderRepository,
+    QuotaResetSchedule, RewardExtraction,
+};
+use neuradock_domain::shared::{DomainError, ExtraHeaders, ProviderId};
 
 use crate::persistence::unit_of_work::RepositoryErrorMapper;
 use crate::persistence::SqliteRepositoryBase;
@@ -16,13 +19,21 @@ struct ProviderRow {
     domain: String,
     login_path: String,
     sign_in_path: Option<String>,
+    sign_in_body: Option<String>,
     user_info_path: String,
     token_api_path: Option<String>,
     models_path: Option<String>,
+    transaction_log_path: Option<String>,
     api_user_key: String,
+    extra_headers: Option<String>,
+    dialect: String,
     bypass_method: Option<String>,
+    requires_turnstile: bool,
     supports_check_in: bool,
     check_in_bugged: bool,
+    quota_reset_schedule: Option<String>,
+    balance_display: Option<String>,
+    reward_extraction: Option<String>,
     is_builtin: bool,
     created_at: String,
 }
@@ -43,18 +54,69 @@ impl SqliteProviderRepository {
             .map_err(|e| DomainError::Validation(format!("Invalid created_at: {}", e)))?
             .with_timezone(&Utc);
 
+        let quota_reset_schedule = row
+            .quota_reset_schedule
+            .as_deref()
+            .map(serde_json::from_str::<QuotaResetSchedule>)
+            .transpose()
+            .map_err(|e| {
+                DomainError::Deserialization(format!("Invalid quota_reset_schedule: {}", e))
+            })?;
+
+        let balance_display = row
+            .balance_display
+            .as_deref()
+            .map(serde_json::from_str::<BalanceDisplay>)
+            .transpose()
+            .map_err(|e| DomainError::Deserialization(format!("Invalid balance_display: {}", e)))?;
+
+        let sign_in_body = row
+            .sign_in_body
+            .as_deref()
+            .map(serde_json::from_str::<serde_json::Value>)
+            .transpose()
+            .map_err(|e| DomainError::Deserialization(format!("Invalid sign_in_body: {}", e)))?;
+
+        let reward_extraction = row
+            .reward_extraction
+            .as_deref()
+            .map(serde_json::from_str::<RewardExtraction>)
+            .transpose()
+            .map_err(|e| {
+                DomainError::Deserialization(format!("Invalid reward_extraction: {}", e))
+            })?;
+
+        let extra_headers = row
+            .extra_headers
+            .as_deref()
+            .map(serde_json::from_str::<ExtraHeaders>)
+            .transpose()
+            .map_err(|e| DomainError::Deserialization(format!("Invalid extra_headers: {}", e)))?
+            .unwrap_or_default();
+
+        let dialect = ProviderDialect::parse(&row.dialect)
+            .map_err(|e| DomainError::Deserialization(format!("Invalid dialect: {}", e)))?;
+
         let config = ProviderConfig {
             name: row.name,
             domain: row.domain,
             login_path: row.login_path,
             sign_in_path: row.sign_in_path,
+            sign_in_body,
             user_info_path: row.user_info_path,
             token_api_path: row.token_api_path,
             models_path: row.models_path,
+            transaction_log_path: row.transaction_log_path,
             api_user_key: row.api_user_key,
+            extra_headers,
+            dialect,
             bypass_method: row.bypass_method,
+            requires_turnstile: row.requires_turnstile,
             supports_check_in: row.supports_check_in,
             check_in_bugged: row.check_in_bugged,
+            quota_reset_schedule,
+            balance_display,
+            reward_extraction,
         };
 
         let provider = Provider::restore(
@@ -72,16 +134,39 @@ impl SqliteProviderRepository {
 impl ProviderRepository for SqliteProviderRepository {
     async fn save(&self, provider: &Provider) -> Result<(), DomainError> {
         let created_at = provider.created_at().to_rfc3339();
+        let quota_reset_schedule = provider
+            .quota_reset_schedule()
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|e| DomainError::Serialization(e.to_string()))?;
+        let balance_display = serde_json::to_string(provider.balance_display())
+            .map_err(|e| DomainError::Serialization(e.to_string()))?;
+        let sign_in_body = provider
+            .sign_in_body()
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|e| DomainError::Serialization(e.to_string()))?;
+        let reward_extraction = provider
+            .reward_extraction()
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|e| DomainError::Serialization(e.to_string()))?;
+
+        let extra_headers = (!provider.extra_headers().is_empty())
+            .then(|| serde_json::to_string(provider.extra_headers()))
+            .transpose()
+            .map_err(|e| DomainError::Serialization(e.to_string()))?;
 
         sqlx::query(
             r#"
             INSERT INTO providers (
                 id, name, domain, login_path, sign_in_path, user_info_path,
-                token_api_path, models_path, api_user_key, bypass_method,
-                supports_check_in, check_in_bugged,
-                is_builtin, created_at
+                token_api_path, models_path, transaction_log_path, api_user_key, bypass_method,
+                supports_check_in, check_in_bugged, quota_reset_schedule,
+                balance_display, sign_in_body, requires_turnstile, reward_extraction,
+                extra_headers, dialect, is_builtin, created_at
             )
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             ON CONFLICT(id) DO UPDATE SET
                 name = excluded.name,
                 domain = excluded.domain,
@@ -90,10 +175,18 @@ impl ProviderRepository for SqliteProviderRepository {
                 user_info_path = excluded.user_info_path,
                 token_api_path = excluded.token_api_path,
                 models_path = excluded.models_path,
+                transaction_log_path = excluded.transaction_log_path,
                 api_user_key = excluded.api_user_key,
                 bypass_method = excluded.bypass_method,
                 supports_check_in = excluded.supports_check_in,
-                check_in_bugged = excluded.check_in_bugged
+                check_in_bugged = excluded.check_in_bugged,
+                quota_reset_schedule = excluded.quota_reset_schedule,
+                balance_display = excluded.balance_display,
+                sign_in_body = excluded.sign_in_body,
+                requires_turnstile = excluded.requires_turnstile,
+                reward_extraction = excluded.reward_extraction,
+                extra_headers = excluded.extra_headers,
+                dialect = excluded.dialect
             "#,
         )
         .bind(provider.id().as_str())
@@ -123,14 +216,27 @@ impl ProviderRepository for SqliteProviderRepository {
                 .as_ref()
                 .map(|url| url.trim_start_matches(provider.domain())),
         )
+        .bind(
+            provider
+                .transaction_log_url()
+                .as_ref()
+                .map(|url| url.trim_start_matches(provider.domain())),
+        )
         .bind(provider.api_user_key())
-        .bind(if provider.needs_waf_bypass() {
-            Some("waf_cookies")
-        } else {
-            None
-        })
+        .bind(
+            provider
+                .waf_challenge_kind()
+                .map(|kind| kind.as_bypass_method()),
+        )
         .bind(provider.supports_check_in())
         .bind(provider.check_in_bugged())
+        .bind(quota_reset_schedule)
+        .bind(balance_display)
+        .bind(sign_in_body)
+        .bind(provider.requires_turnstile())
+        .bind(reward_extraction)
+        .bind(extra_headers)
+        .bind(provider.dialect().as_str())
         .bind(provider.is_builtin())
         .bind(created_at)
         .execute(self.base.pool())
@@ -144,9 +250,10 @@ impl ProviderRepository for SqliteProviderRepository {
         let row = sqlx::query_as::<_, ProviderRow>(
             r#"
             SELECT id, name, domain, login_path, sign_in_path, user_info_path,
-                   token_api_path, models_path, api_user_key, bypass_method,
-                   supports_check_in, check_in_bugged,
-                   is_builtin, created_at
+                   token_api_path, models_path, transaction_log_path, api_user_key, bypass_method,
+                   supports_check_in, check_in_bugged, quota_reset_schedule,
+                   balance_display, sign_in_body, requires_turnstile,
+                   reward_extraction, extra_headers, dialect, is_builtin, created_at
             FROM providers
             WHERE id = ?
             "#,
@@ -166,9 +273,10 @@ impl ProviderRepository for SqliteProviderRepository {
         let rows = sqlx::query_as::<_, ProviderRow>(
             r#"
             SELECT id, name, domain, login_path, sign_in_path, user_info_path,
-                   token_api_path, models_path, api_user_key, bypass_method,
-                   supports_check_in, check_in_bugged,
-                   is_builtin, created_at
+                   token_api_path, models_path, transaction_log_path, api_user_key, bypass_method,
+                   supports_check_in, check_in_bugged, quota_reset_schedule,
+                   balance_display, sign_in_body, requires_turnstile,
+                   reward_extraction, extra_headers, dialect, is_builtin, created_at
             FROM providers
             ORDER BY is_builtin DESC, created_at ASC
             "#,
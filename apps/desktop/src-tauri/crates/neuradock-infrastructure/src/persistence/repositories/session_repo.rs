@@ -1,10 +1,12 @@
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
-use sqlx::{FromRow, SqlitePool};
+use sqlx::query::Query;
+use sqlx::sqlite::SqliteArguments;
+use sqlx::{FromRow, Sqlite, SqlitePool};
 use std::sync::Arc;
 
 use neuradock_domain::session::{Session, SessionRepository};
-use neuradock_domain::shared::{AccountId, DomainError};
+use neuradock_domain::shared::{AccountId, DomainError, DynTransactionContext};
 
 #[derive(FromRow)]
 struct SessionRow {
@@ -25,7 +27,7 @@ impl SessionRow {
     }
 }
 
-use crate::persistence::SqliteRepositoryBase;
+use crate::persistence::{ResultExt, SqliteRepositoryBase, SqliteTransaction};
 
 pub struct SqliteSessionRepository {
     base: SqliteRepositoryBase,
@@ -37,11 +39,8 @@ impl SqliteSessionRepository {
             base: SqliteRepositoryBase::new(pool),
         }
     }
-}
 
-#[async_trait]
-impl SessionRepository for SqliteSessionRepository {
-    async fn save(&self, session: &Session) -> Result<(), DomainError> {
+    fn save_query(session: &Session) -> Query<'_, Sqlite, SqliteArguments<'_>> {
         let query = r#"
             INSERT INTO sessions (account_id, token, expires_at, last_login_at)
             VALUES (?1, ?2, ?3, ?4)
@@ -51,20 +50,38 @@ impl SessionRepository for SqliteSessionRepository {
                 last_login_at = ?4
         "#;
 
+        sqlx::query(query)
+            .bind(session.account_id().as_str())
+            .bind(session.token())
+            .bind(session.expires_at())
+            .bind(session.last_login_at())
+    }
+}
+
+#[async_trait]
+impl SessionRepository for SqliteSessionRepository {
+    async fn save(&self, session: &Session) -> Result<(), DomainError> {
         self.base
-            .execute(
-                sqlx::query(query)
-                    .bind(session.account_id().as_str())
-                    .bind(session.token())
-                    .bind(session.expires_at())
-                    .bind(session.last_login_at()),
-                "Save session",
-            )
+            .execute(Self::save_query(session), "Save session")
             .await?;
 
         Ok(())
     }
 
+    async fn save_in_transaction(
+        &self,
+        tx: &mut DynTransactionContext,
+        session: &Session,
+    ) -> Result<(), DomainError> {
+        let tx = SqliteTransaction::from_context(tx)?;
+        Self::save_query(session)
+            .execute(&mut **tx)
+            .await
+            .map_repo_error("Save session")?;
+
+        Ok(())
+    }
+
     async fn find_by_account_id(
         &self,
         account_id: &AccountId,
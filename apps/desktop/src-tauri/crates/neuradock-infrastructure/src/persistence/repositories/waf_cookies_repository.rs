@@ -17,7 +17,7 @@ const WAF_CACHE_HOURS: i64 = 24;
 struct WafCookiesRow {
     #[allow(dead_code)]
     id: i64,
-    provider_id: String,
+    cache_key: String,
     cookies: String, // JSON object
     fetched_at: String,
     expires_at: String,
@@ -47,7 +47,7 @@ impl SqliteWafCookiesRepository {
             .with_timezone(&Utc);
 
         Ok(WafCookies {
-            provider_id: row.provider_id,
+            cache_key: row.cache_key,
             cookies,
             fetched_at,
             expires_at,
@@ -57,10 +57,10 @@ impl SqliteWafCookiesRepository {
 
 #[async_trait]
 impl WafCookiesRepository for SqliteWafCookiesRepository {
-    /// Save or update WAF cookies for a provider
+    /// Save or update WAF cookies under a cache key
     async fn save(
         &self,
-        provider_id: &str,
+        cache_key: &str,
         cookies: &HashMap<String, String>,
     ) -> Result<(), DomainError> {
         let cookies_json = serde_json::to_string(cookies)
@@ -71,15 +71,15 @@ impl WafCookiesRepository for SqliteWafCookiesRepository {
 
         sqlx::query(
             r#"
-            INSERT INTO waf_cookies (provider_id, cookies, fetched_at, expires_at)
+            INSERT INTO waf_cookies (cache_key, cookies, fetched_at, expires_at)
             VALUES (?, ?, ?, ?)
-            ON CONFLICT(provider_id) DO UPDATE SET
+            ON CONFLICT(cache_key) DO UPDATE SET
                 cookies = excluded.cookies,
                 fetched_at = excluded.fetched_at,
                 expires_at = excluded.expires_at
             "#,
         )
-        .bind(provider_id)
+        .bind(cache_key)
         .bind(cookies_json)
         .bind(now.to_rfc3339())
         .bind(expires_at.to_rfc3339())
@@ -88,24 +88,24 @@ impl WafCookiesRepository for SqliteWafCookiesRepository {
         .map_err(|e| RepositoryErrorMapper::map_sqlx_error(e, "Save WAF cookies"))?;
 
         log::info!(
-            "WAF cookies saved for provider {}, expires at {}",
-            provider_id,
+            "WAF cookies saved for {}, expires at {}",
+            cache_key,
             expires_at
         );
 
         Ok(())
     }
 
-    /// Get valid (non-expired) WAF cookies for a provider
-    async fn get_valid(&self, provider_id: &str) -> Result<Option<WafCookies>, DomainError> {
+    /// Get valid (non-expired) WAF cookies stored under a cache key
+    async fn get_valid(&self, cache_key: &str) -> Result<Option<WafCookies>, DomainError> {
         let row = sqlx::query_as::<_, WafCookiesRow>(
             r#"
-            SELECT id, provider_id, cookies, fetched_at, expires_at
+            SELECT id, cache_key, cookies, fetched_at, expires_at
             FROM waf_cookies
-            WHERE provider_id = ?
+            WHERE cache_key = ?
             "#,
         )
-        .bind(provider_id)
+        .bind(cache_key)
         .fetch_optional(self.base.pool())
         .await
         .map_err(|e| RepositoryErrorMapper::map_sqlx_error(e, "Get WAF cookies"))?;
@@ -115,27 +115,27 @@ impl WafCookiesRepository for SqliteWafCookiesRepository {
                 let waf_cookies = self.row_to_domain(row)?;
                 if waf_cookies.is_valid() {
                     log::info!(
-                        "Using cached WAF cookies for provider {}, expires at {}",
-                        provider_id,
+                        "Using cached WAF cookies for {}, expires at {}",
+                        cache_key,
                         waf_cookies.expires_at
                     );
                     Ok(Some(waf_cookies))
                 } else {
-                    log::info!("WAF cookies for provider {} have expired", provider_id);
+                    log::info!("WAF cookies for {} have expired", cache_key);
                     Ok(None)
                 }
             }
             None => {
-                log::info!("No cached WAF cookies for provider {}", provider_id);
+                log::info!("No cached WAF cookies for {}", cache_key);
                 Ok(None)
             }
         }
     }
 
-    /// Delete WAF cookies for a provider
-    async fn delete(&self, provider_id: &str) -> Result<(), DomainError> {
-        sqlx::query("DELETE FROM waf_cookies WHERE provider_id = ?")
-            .bind(provider_id)
+    /// Delete WAF cookies stored under a cache key
+    async fn delete(&self, cache_key: &str) -> Result<(), DomainError> {
+        sqlx::query("DELETE FROM waf_cookies WHERE cache_key = ?")
+            .bind(cache_key)
             .execute(self.base.pool())
             .await
             .map_err(|e| RepositoryErrorMapper::map_sqlx_error(e, "Delete WAF cookies"))?;
@@ -1,9 +1,57 @@
+use log::warn;
 use sqlx::{FromRow, SqlitePool};
+use std::future::Future;
 use std::sync::Arc;
+use std::time::Duration;
 
 use crate::persistence::ResultExt;
 use neuradock_domain::shared::DomainError;
 
+/// SQLite primary result codes for a database or table held by another connection
+const SQLITE_BUSY: i32 = 5;
+const SQLITE_LOCKED: i32 = 6;
+
+/// Attempts made by [`retry_on_busy`], including the first one
+const BUSY_RETRY_ATTEMPTS: u32 = 3;
+/// Delay before the first retry, grows linearly with each attempt
+const BUSY_RETRY_DELAY: Duration = Duration::from_millis(100);
+
+/// Whether `error` means another connection held the lock past the busy timeout
+pub fn is_busy(error: &sqlx::Error) -> bool {
+    error
+        .as_database_error()
+        .and_then(|e| e.code())
+        .and_then(|code| code.parse::<i32>().ok())
+        .is_some_and(|code| matches!(code & 0xff, SQLITE_BUSY | SQLITE_LOCKED))
+}
+
+/// Run a write, retrying it when SQLite reports the database as busy
+///
+/// The busy timeout already makes a connection wait for the lock, but a
+/// transaction that read before writing fails at once when another writer got
+/// in first. `write` is called again from the start, so it must rebuild its
+/// query or transaction each time.
+pub async fn retry_on_busy<T, F, Fut>(operation: &str, mut write: F) -> Result<T, DomainError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, sqlx::Error>>,
+{
+    let mut attempt = 1;
+    loop {
+        match write().await {
+            Err(e) if is_busy(&e) && attempt < BUSY_RETRY_ATTEMPTS => {
+                warn!(
+                    "{}: database busy, retrying (attempt {}/{}): {}",
+                    operation, attempt, BUSY_RETRY_ATTEMPTS, e
+                );
+                tokio::time::sleep(BUSY_RETRY_DELAY * attempt).await;
+                attempt += 1;
+            }
+            result => return result.map_repo_error(operation),
+        }
+    }
+}
+
 /// Base struct for SQLite repositories
 ///
 /// Provides common functionality:
@@ -77,6 +125,20 @@ impl SqliteRepositoryBase {
 
         Ok(result.rows_affected())
     }
+
+    /// Execute a write built by `build`, retrying it while the database is busy
+    pub async fn execute_with_retry<'q, F>(
+        &self,
+        build: F,
+        operation: &str,
+    ) -> Result<u64, DomainError>
+    where
+        F: Fn() -> sqlx::query::Query<'q, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'q>>,
+    {
+        let result = retry_on_busy(operation, || build().execute(self.pool())).await?;
+
+        Ok(result.rows_affected())
+    }
 }
 
 #[cfg(test)]
@@ -97,4 +159,70 @@ mod tests {
 
         assert!(Arc::ptr_eq(&pool, &repo.arc_pool()));
     }
+
+    /// A connection holding the write lock, and a pool that fails at once while it is held
+    async fn locked_database(dir: &std::path::Path) -> (sqlx::SqliteConnection, Arc<SqlitePool>) {
+        use sqlx::sqlite::SqliteConnectOptions;
+        use sqlx::ConnectOptions;
+
+        let options = SqliteConnectOptions::new()
+            .filename(dir.join("busy.db"))
+            .create_if_missing(true)
+            .busy_timeout(Duration::ZERO);
+        let mut conn = options.connect().await.unwrap();
+        sqlx::query("CREATE TABLE items (id INTEGER PRIMARY KEY)")
+            .execute(&mut conn)
+            .await
+            .unwrap();
+        sqlx::query("BEGIN IMMEDIATE")
+            .execute(&mut conn)
+            .await
+            .unwrap();
+
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(options)
+            .await
+            .unwrap();
+        (conn, Arc::new(pool))
+    }
+
+    #[tokio::test]
+    async fn test_write_is_retried_until_the_lock_is_released() {
+        let dir = tempfile::tempdir().unwrap();
+        let (mut conn, pool) = locked_database(dir.path()).await;
+        let repo = SqliteRepositoryBase::new(pool);
+
+        let release = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            sqlx::query("COMMIT").execute(&mut conn).await.unwrap();
+        });
+
+        let rows = repo
+            .execute_with_retry(
+                || sqlx::query("INSERT INTO items (id) VALUES (1)"),
+                "Insert item",
+            )
+            .await
+            .unwrap();
+        release.await.unwrap();
+
+        assert_eq!(rows, 1);
+    }
+
+    #[tokio::test]
+    async fn test_busy_error_is_returned_after_the_last_attempt() {
+        let dir = tempfile::tempdir().unwrap();
+        let (_conn, pool) = locked_database(dir.path()).await;
+        let mut attempts = 0;
+
+        let result = retry_on_busy("Insert item", || {
+            attempts += 1;
+            sqlx::query("INSERT INTO items (id) VALUES (1)").execute(pool.as_ref())
+        })
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(attempts, BUSY_RETRY_ATTEMPTS);
+    }
 }
@@ -0,0 +1,114 @@
+use chrono::{DateTime, Utc};
+use sqlx::{Row, SqlitePool};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use super::app_data_archive::schema_version;
+use super::integrity::backups_newest_first;
+use super::maintenance::{file_size, wal_path};
+use super::ResultExt;
+use neuradock_domain::shared::DomainError;
+
+/// Tables whose row counts are reported, the ones that grow with use. Sent
+/// notifications are not logged, so only the channels are counted.
+const COUNTED_TABLES: &[&str] = &[
+    "accounts",
+    "balance_history",
+    "sessions",
+    "api_tokens",
+    "waf_cookies",
+    "notification_channels",
+];
+
+/// Storage used by the database
+#[derive(Debug, Clone, PartialEq)]
+pub struct DatabaseStats {
+    pub db_size_bytes: u64,
+    pub wal_size_bytes: u64,
+    /// Rows per table, read from one snapshot
+    pub row_counts: BTreeMap<String, u64>,
+    /// Latest applied migration, 0 before the first one
+    pub schema_version: i64,
+    /// Modification time of the newest automatic backup
+    pub last_backup_at: Option<DateTime<Utc>>,
+}
+
+/// Sizes, row counts and schema version of the database at `db_path`
+///
+/// All counts come from a single read-only statement, which in WAL mode sees
+/// one consistent snapshot without blocking writers, so this is safe to call
+/// while check-ins run.
+pub async fn database_stats(
+    pool: &SqlitePool,
+    db_path: &Path,
+) -> Result<DatabaseStats, DomainError> {
+    let mut conn = pool.acquire().await.map_repo_error("Acquire connection")?;
+
+    let counts_query = format!(
+        "SELECT {}",
+        COUNTED_TABLES
+            .iter()
+            .map(|table| format!("(SELECT COUNT(*) FROM \"{}\")", table))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    let row = sqlx::query(&counts_query)
+        .fetch_one(&mut *conn)
+        .await
+        .map_repo_error("Count table rows")?;
+    let row_counts = COUNTED_TABLES
+        .iter()
+        .enumerate()
+        .map(|(index, table)| (table.to_string(), row.get::<i64, _>(index) as u64))
+        .collect();
+
+    let schema_version = schema_version(&mut conn).await?;
+    drop(conn);
+
+    let last_backup_at = backups_newest_first(db_path)
+        .first()
+        .and_then(|backup| std::fs::metadata(backup).ok()?.modified().ok())
+        .map(DateTime::<Utc>::from);
+
+    Ok(DatabaseStats {
+        db_size_bytes: file_size(db_path),
+        wal_size_bytes: file_size(&wal_path(db_path)),
+        row_counts,
+        schema_version,
+        last_backup_at,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::{backups_dir, Database};
+
+    #[tokio::test]
+    async fn test_stats_count_rows_and_report_schema_and_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("neuradock.db");
+        let database = Database::new(db_path.to_str().unwrap()).await.unwrap();
+        database.run_migrations().await.unwrap();
+        sqlx::query(
+            "INSERT INTO accounts (id, name, provider_id, cookies, api_user, enabled, created_at) VALUES ('a1', 'Main', 'anyrouter', '{}', 'user', 1, '2025-01-01T00:00:00Z')",
+        )
+        .execute(database.pool())
+        .await
+        .unwrap();
+
+        let stats = database_stats(database.pool(), &db_path).await.unwrap();
+
+        assert_eq!(stats.row_counts["accounts"], 1);
+        assert_eq!(stats.row_counts["balance_history"], 0);
+        assert_eq!(stats.row_counts.len(), COUNTED_TABLES.len());
+        assert!(stats.schema_version > 0);
+        assert!(stats.db_size_bytes > 0);
+        assert_eq!(stats.last_backup_at, None);
+
+        std::fs::create_dir_all(backups_dir(&db_path)).unwrap();
+        std::fs::write(backups_dir(&db_path).join("snapshot.db"), b"backup").unwrap();
+        let stats = database_stats(database.pool(), &db_path).await.unwrap();
+        assert!(stats.last_backup_at.is_some());
+    }
+}
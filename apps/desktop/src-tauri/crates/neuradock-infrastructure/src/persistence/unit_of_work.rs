@@ -1,6 +1,12 @@
+use async_trait::async_trait;
 use sqlx::{Sqlite, SqlitePool, Transaction};
+use std::any::Any;
+use std::sync::Arc;
 
-use neuradock_domain::shared::DomainError;
+use neuradock_domain::shared::{
+    DomainError, DynTransactionContext, TransactionContext, UnitOfWork as UnitOfWorkPort,
+    UnitOfWorkError,
+};
 
 /// Unit of Work pattern for managing database transactions
 /// Ensures consistency across multiple repository operations
@@ -39,6 +45,79 @@ impl<'a> UnitOfWork<'a> {
     }
 }
 
+/// SQLite implementation of the domain Unit of Work
+///
+/// Hands out [`SqliteTransaction`]s that the transaction-aware repository
+/// methods write into, so several aggregates commit or roll back together.
+pub struct SqliteUnitOfWork {
+    pool: Arc<SqlitePool>,
+}
+
+impl SqliteUnitOfWork {
+    pub fn new(pool: Arc<SqlitePool>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl UnitOfWorkPort for SqliteUnitOfWork {
+    type Transaction = SqliteTransaction;
+
+    async fn begin(&self) -> Result<Box<SqliteTransaction>, UnitOfWorkError> {
+        let transaction = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| UnitOfWorkError::TransactionFailed(e.to_string()))?;
+
+        Ok(Box::new(SqliteTransaction { transaction }))
+    }
+}
+
+/// Open SQLite transaction, rolled back when dropped without a commit
+pub struct SqliteTransaction {
+    transaction: Transaction<'static, Sqlite>,
+}
+
+impl SqliteTransaction {
+    /// The sqlx transaction behind a domain transaction context
+    pub fn from_context(
+        tx: &mut DynTransactionContext,
+    ) -> Result<&mut Transaction<'static, Sqlite>, DomainError> {
+        tx.as_any_mut()
+            .downcast_mut::<SqliteTransaction>()
+            .map(|tx| &mut tx.transaction)
+            .ok_or_else(|| {
+                DomainError::Infrastructure(
+                    "Transaction does not belong to the SQLite unit of work".to_string(),
+                )
+            })
+    }
+}
+
+#[async_trait]
+impl TransactionContext for SqliteTransaction {
+    type Error = UnitOfWorkError;
+
+    async fn commit(self: Box<Self>) -> Result<(), UnitOfWorkError> {
+        self.transaction
+            .commit()
+            .await
+            .map_err(|e| UnitOfWorkError::CommitFailed(e.to_string()))
+    }
+
+    async fn rollback(self: Box<Self>) -> Result<(), UnitOfWorkError> {
+        self.transaction
+            .rollback()
+            .await
+            .map_err(|e| UnitOfWorkError::RollbackFailed(e.to_string()))
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
 /// Repository error mapper
 /// Converts sqlx errors to domain errors with contextual information
 pub struct RepositoryErrorMapper;
@@ -6,6 +6,7 @@ use argon2::Argon2;
 use base64::{engine::general_purpose, Engine as _};
 use rand::RngCore;
 use std::fmt;
+use std::sync::RwLock;
 
 /// Encryption service using AES-256-GCM
 ///
@@ -14,8 +15,9 @@ use std::fmt;
 /// - Key derived from master password using Argon2id
 /// - Unique nonce for each encryption
 /// - Authenticated encryption prevents tampering
+/// - The key can be replaced in place after a key rotation
 pub struct EncryptionService {
-    cipher: Aes256Gcm,
+    cipher: RwLock<Aes256Gcm>,
 }
 
 impl EncryptionService {
@@ -38,7 +40,25 @@ impl EncryptionService {
         let cipher = Aes256Gcm::new_from_slice(&key)
             .map_err(|e| EncryptionError::InvalidKey(e.to_string()))?;
 
-        Ok(Self { cipher })
+        Ok(Self {
+            cipher: RwLock::new(cipher),
+        })
+    }
+
+    /// Switch to the key of `other`, for every holder of this service
+    pub fn replace_key(&self, other: EncryptionService) {
+        let cipher = other.cipher();
+        match self.cipher.write() {
+            Ok(mut current) => *current = cipher,
+            Err(poisoned) => *poisoned.into_inner() = cipher,
+        }
+    }
+
+    fn cipher(&self) -> Aes256Gcm {
+        match self.cipher.read() {
+            Ok(cipher) => cipher.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
     }
 
     /// Encrypt plaintext
@@ -56,7 +76,7 @@ impl EncryptionService {
 
         // Encrypt with authentication
         let ciphertext = self
-            .cipher
+            .cipher()
             .encrypt(nonce, plaintext.as_bytes())
             .map_err(|e| EncryptionError::EncryptionFailed(e.to_string()))?;
 
@@ -94,7 +114,7 @@ impl EncryptionService {
         let nonce = Nonce::from_slice(nonce_bytes);
 
         // Decrypt and verify authentication
-        let plaintext = self.cipher.decrypt(nonce, ciphertext).map_err(|e| {
+        let plaintext = self.cipher().decrypt(nonce, ciphertext).map_err(|e| {
             EncryptionError::DecryptionFailed(format!(
                 "Decryption failed (data may be tampered): {}",
                 e
@@ -264,4 +284,18 @@ mod tests {
 
         assert_eq!(json_data, decrypted);
     }
+
+    #[test]
+    fn test_replace_key_switches_cipher() {
+        let service = create_test_service();
+        let old_ciphertext = service.encrypt("secret").unwrap();
+
+        service.replace_key(
+            EncryptionService::from_password("test_password_123", &[7u8; 32]).unwrap(),
+        );
+
+        assert!(service.decrypt(&old_ciphertext).is_err());
+        let new_ciphertext = service.encrypt("secret").unwrap();
+        assert_eq!(service.decrypt(&new_ciphertext).unwrap(), "secret");
+    }
 }
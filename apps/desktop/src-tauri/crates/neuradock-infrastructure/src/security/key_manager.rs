@@ -1,86 +1,301 @@
+use log::{info, warn};
 use rand::RngCore;
 use std::fs;
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use thiserror::Error;
 
+/// Service name of the keyring entries holding encryption salts
+const KEYRING_SERVICE: &str = "NeuraDock";
+
+/// Secret storage the salt is kept in instead of a file next to the database
+///
+/// Implemented by [`OsKeyring`] for the platform keyring; tests use an
+/// in-memory store.
+pub trait KeyringStore: Send + Sync {
+    /// Stored secret of an account, `None` when there is no entry
+    fn get(&self, account: &str) -> Result<Option<Vec<u8>>, KeyManagerError>;
+
+    /// Create or overwrite the secret of an account
+    fn set(&self, account: &str, secret: &[u8]) -> Result<(), KeyManagerError>;
+}
+
+/// Windows Credential Manager, macOS Keychain or the Secret Service on Linux
+///
+/// Calls block, on Linux until the Secret Service answers over D-Bus, so they
+/// should not run on an async executor thread.
+pub struct OsKeyring;
+
+impl KeyringStore for OsKeyring {
+    fn get(&self, account: &str) -> Result<Option<Vec<u8>>, KeyManagerError> {
+        match keyring::Entry::new(KEYRING_SERVICE, account).and_then(|e| e.get_secret()) {
+            Ok(secret) => Ok(Some(secret)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(KeyManagerError::Keyring(e.to_string())),
+        }
+    }
+
+    fn set(&self, account: &str, secret: &[u8]) -> Result<(), KeyManagerError> {
+        keyring::Entry::new(KEYRING_SERVICE, account)
+            .and_then(|e| e.set_secret(secret))
+            .map_err(|e| KeyManagerError::Keyring(e.to_string()))
+    }
+}
+
 /// Key manager for encryption salt storage
 ///
 /// # Security Design
-/// - Stores 256-bit random salt in app data directory
-/// - Salt is generated once and reused for consistency
-/// - Salt file protected by filesystem permissions
-/// - Salt is NOT a secret, but ensures unique keys per installation
+/// - Stores 256-bit random salt, generated once and reused for consistency
+/// - Prefers the OS keyring, so copying the data directory does not copy the salt
+/// - A marker file next to the database names the keyring entry; once it
+///   exists the salt is only ever read from the keyring
+/// - Falls back to a salt file protected by filesystem permissions when no
+///   keyring is available, e.g. on headless Linux
+/// - An existing salt file is moved into the keyring on first run
 pub struct KeyManager {
     salt_path: PathBuf,
+    marker_path: PathBuf,
+    keyring: Option<Arc<dyn KeyringStore>>,
 }
 
 impl KeyManager {
-    /// Create a new key manager
+    /// Create a key manager that keeps the salt in the OS keyring when it can
     ///
     /// # Arguments
     /// * `app_data_dir` - Application data directory path
     pub fn new(app_data_dir: PathBuf) -> Self {
-        let salt_path = app_data_dir.join(".encryption_salt");
-        Self { salt_path }
+        Self::with_keyring(app_data_dir, Some(Arc::new(OsKeyring)))
+    }
+
+    /// Create a key manager that only uses the salt file
+    pub fn file_only(app_data_dir: PathBuf) -> Self {
+        Self::with_keyring(app_data_dir, None)
+    }
+
+    /// Create a key manager with a specific keyring, `None` for the salt file only
+    pub fn with_keyring(app_data_dir: PathBuf, keyring: Option<Arc<dyn KeyringStore>>) -> Self {
+        Self {
+            salt_path: app_data_dir.join(".encryption_salt"),
+            marker_path: app_data_dir.join(".encryption_keyring"),
+            keyring,
+        }
     }
 
     /// Initialize encryption salt (load or generate)
     ///
-    /// - If salt file exists, load it
-    /// - If not, generate new random salt and save it
+    /// - If the salt was moved to the keyring, read it from there and fail
+    ///   when that is not possible
+    /// - Otherwise load the salt file or generate a new salt, and move it into
+    ///   the keyring when one is available
     ///
     /// # Returns
     /// 32-byte salt for key derivation
     pub fn initialize(&self) -> Result<[u8; 32], KeyManagerError> {
-        if self.salt_path.exists() {
-            self.load_salt()
+        if let Some(account) = self.keyring_account()? {
+            let salt = self.load_from_keyring(&account)?;
+            // Left behind when the last start stopped halfway through the move
+            if self.salt_path.exists() && self.load_salt().ok() == Some(salt) {
+                self.remove_salt_file();
+            }
+            return Ok(salt);
+        }
+
+        let salt = if self.salt_path.exists() {
+            self.load_salt()?
         } else {
-            self.generate_and_save_salt()
+            Self::generate_salt()
+        };
+
+        if let Some(keyring) = &self.keyring {
+            match self.move_to_keyring(keyring.as_ref(), &salt) {
+                Ok(()) => return Ok(salt),
+                Err(e) => warn!(
+                    "OS keyring unavailable, keeping the encryption salt in a file: {}",
+                    e
+                ),
+            }
+        }
+
+        if !self.salt_path.exists() {
+            self.replace_salt_file(&salt)?;
         }
+        Ok(salt)
     }
 
     /// Load existing salt from file
     fn load_salt(&self) -> Result<[u8; 32], KeyManagerError> {
         let bytes = fs::read(&self.salt_path)
             .map_err(|e| KeyManagerError::IoError(format!("Failed to read salt file: {}", e)))?;
+        parse_salt(&bytes)
+    }
 
-        if bytes.len() != 32 {
-            return Err(KeyManagerError::InvalidSalt(format!(
-                "Expected 32 bytes, got {}",
-                bytes.len()
-            )));
+    /// Read the salt of an installation whose salt lives in the keyring
+    fn load_from_keyring(&self, account: &str) -> Result<[u8; 32], KeyManagerError> {
+        let keyring = self.keyring.as_ref().ok_or_else(|| {
+            KeyManagerError::Keyring(format!(
+                "The encryption salt is stored in the OS keyring (entry '{}'), which is not available here. \
+                 Start NeuraDock where that keyring is available.",
+                account
+            ))
+        })?;
+
+        match keyring.get(account) {
+            Ok(Some(bytes)) => parse_salt(&bytes),
+            Ok(None) => Err(KeyManagerError::Keyring(format!(
+                "The encryption salt is missing from the OS keyring (service '{}', entry '{}'). \
+                 Restore the keyring entry, or restore '{}' from a backup and delete '{}'. \
+                 Starting without it would leave saved credentials unreadable.",
+                KEYRING_SERVICE,
+                account,
+                self.salt_path.display(),
+                self.marker_path.display()
+            ))),
+            Err(e) => Err(KeyManagerError::Keyring(format!(
+                "Failed to read the encryption salt from the OS keyring: {}. \
+                 Unlock the keyring (on Linux, make sure a Secret Service such as \
+                 GNOME Keyring or KWallet is running) and restart NeuraDock.",
+                e
+            ))),
         }
+    }
 
-        let mut salt = [0u8; 32];
-        salt.copy_from_slice(&bytes);
+    /// Store the salt in the keyring under a new entry, then record the entry
+    /// in the marker file and delete the salt file
+    fn move_to_keyring(
+        &self,
+        keyring: &dyn KeyringStore,
+        salt: &[u8; 32],
+    ) -> Result<(), KeyManagerError> {
+        let account = format!("encryption-salt-{:016x}", rand::random::<u64>());
+        keyring.set(&account, salt)?;
+        if keyring.get(&account)?.as_deref() != Some(salt.as_slice()) {
+            return Err(KeyManagerError::Keyring(
+                "The salt read back from the keyring does not match".to_string(),
+            ));
+        }
 
-        Ok(salt)
+        write_atomically(&self.marker_path, account.as_bytes())?;
+        if self.salt_path.exists() {
+            self.remove_salt_file();
+            info!("Moved the encryption salt into the OS keyring");
+        }
+        Ok(())
     }
 
-    /// Generate new random salt and save to file
-    fn generate_and_save_salt(&self) -> Result<[u8; 32], KeyManagerError> {
-        // Generate cryptographically secure random salt
+    /// Generate a cryptographically secure random salt
+    pub fn generate_salt() -> [u8; 32] {
         let mut salt = [0u8; 32];
         rand::thread_rng().fill_bytes(&mut salt);
+        salt
+    }
 
-        // Ensure parent directory exists
-        if let Some(parent) = self.salt_path.parent() {
-            fs::create_dir_all(parent).map_err(|e| {
-                KeyManagerError::IoError(format!("Failed to create directory: {}", e))
-            })?;
+    /// Replace the stored salt
+    ///
+    /// A salt in the keyring is overwritten in its entry. A salt file is
+    /// written to a temporary file and renamed over the old one, so a crash
+    /// leaves either the old or the new salt, never a partial file.
+    pub fn replace_salt(&self, salt: &[u8; 32]) -> Result<(), KeyManagerError> {
+        let Some(account) = self.keyring_account()? else {
+            return self.replace_salt_file(salt);
+        };
+
+        let keyring = self.keyring.as_ref().ok_or_else(|| {
+            KeyManagerError::Keyring("The OS keyring holding the salt is not available".to_string())
+        })?;
+        keyring.set(&account, salt)?;
+        if keyring.get(&account)?.as_deref() != Some(salt.as_slice()) {
+            return Err(KeyManagerError::Keyring(
+                "The salt read back from the keyring does not match".to_string(),
+            ));
         }
+        Ok(())
+    }
 
-        // Write salt to file
-        fs::write(&self.salt_path, salt)
-            .map_err(|e| KeyManagerError::IoError(format!("Failed to write salt file: {}", e)))?;
+    fn replace_salt_file(&self, salt: &[u8; 32]) -> Result<(), KeyManagerError> {
+        write_atomically(&self.salt_path, salt)
+    }
 
-        Ok(salt)
+    fn remove_salt_file(&self) {
+        if let Err(e) = fs::remove_file(&self.salt_path) {
+            warn!(
+                "Failed to remove the salt file {}: {}",
+                self.salt_path.display(),
+                e
+            );
+        }
+    }
+
+    /// Keyring entry named by the marker file, `None` while the salt is in a file
+    fn keyring_account(&self) -> Result<Option<String>, KeyManagerError> {
+        if !self.marker_path.exists() {
+            return Ok(None);
+        }
+        let account = fs::read_to_string(&self.marker_path).map_err(|e| {
+            KeyManagerError::IoError(format!("Failed to read keyring marker file: {}", e))
+        })?;
+        let account = account.trim();
+        if account.is_empty() {
+            return Err(KeyManagerError::Keyring(format!(
+                "The keyring marker file {} is empty",
+                self.marker_path.display()
+            )));
+        }
+        Ok(Some(account.to_string()))
+    }
+
+    /// Whether the salt is kept in the OS keyring
+    pub fn uses_keyring(&self) -> bool {
+        self.marker_path.exists()
     }
 
     /// Get salt file path (for testing/debugging)
     pub fn salt_path(&self) -> &PathBuf {
         &self.salt_path
     }
+
+    /// Files in the data directory the salt depends on, the salt file or the
+    /// keyring marker, that currently exist
+    pub fn key_files(&self) -> Vec<PathBuf> {
+        [&self.salt_path, &self.marker_path]
+            .into_iter()
+            .filter(|path| path.exists())
+            .cloned()
+            .collect()
+    }
+}
+
+fn parse_salt(bytes: &[u8]) -> Result<[u8; 32], KeyManagerError> {
+    if bytes.len() != 32 {
+        return Err(KeyManagerError::InvalidSalt(format!(
+            "Expected 32 bytes, got {}",
+            bytes.len()
+        )));
+    }
+
+    let mut salt = [0u8; 32];
+    salt.copy_from_slice(bytes);
+    Ok(salt)
+}
+
+/// Write through a temporary file renamed over `path`
+fn write_atomically(path: &Path, contents: &[u8]) -> Result<(), KeyManagerError> {
+    // Ensure parent directory exists
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| KeyManagerError::IoError(format!("Failed to create directory: {}", e)))?;
+    }
+
+    let temp_path = path.with_extension("tmp");
+    let mut file = fs::File::create(&temp_path)
+        .map_err(|e| KeyManagerError::IoError(format!("Failed to write salt file: {}", e)))?;
+    file.write_all(contents)
+        .and_then(|_| file.sync_all())
+        .map_err(|e| KeyManagerError::IoError(format!("Failed to write salt file: {}", e)))?;
+    fs::rename(&temp_path, path)
+        .map_err(|e| KeyManagerError::IoError(format!("Failed to replace salt file: {}", e)))?;
+
+    Ok(())
 }
 
 /// Key manager errors
@@ -91,20 +306,68 @@ pub enum KeyManagerError {
 
     #[error("Invalid salt: {0}")]
     InvalidSalt(String),
+
+    #[error("Keyring error: {0}")]
+    Keyring(String),
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashMap;
     use std::fs;
+    use std::sync::Mutex;
     use tempfile::TempDir;
 
+    /// In-memory keyring, optionally failing every call like a locked keyring
+    #[derive(Default)]
+    struct MemoryKeyring {
+        entries: Mutex<HashMap<String, Vec<u8>>>,
+        unavailable: bool,
+    }
+
+    impl MemoryKeyring {
+        fn unavailable() -> Self {
+            Self {
+                unavailable: true,
+                ..Default::default()
+            }
+        }
+
+        fn check(&self) -> Result<(), KeyManagerError> {
+            if self.unavailable {
+                return Err(KeyManagerError::Keyring("no Secret Service".to_string()));
+            }
+            Ok(())
+        }
+    }
+
+    impl KeyringStore for MemoryKeyring {
+        fn get(&self, account: &str) -> Result<Option<Vec<u8>>, KeyManagerError> {
+            self.check()?;
+            Ok(self.entries.lock().unwrap().get(account).cloned())
+        }
+
+        fn set(&self, account: &str, secret: &[u8]) -> Result<(), KeyManagerError> {
+            self.check()?;
+            self.entries
+                .lock()
+                .unwrap()
+                .insert(account.to_string(), secret.to_vec());
+            Ok(())
+        }
+    }
+
     fn create_test_manager() -> (KeyManager, TempDir) {
         let temp_dir = TempDir::new().unwrap();
-        let manager = KeyManager::new(temp_dir.path().to_path_buf());
+        let manager = KeyManager::file_only(temp_dir.path().to_path_buf());
         (manager, temp_dir)
     }
 
+    fn keyring_manager(dir: &TempDir, keyring: Arc<dyn KeyringStore>) -> KeyManager {
+        KeyManager::with_keyring(dir.path().to_path_buf(), Some(keyring))
+    }
+
     #[test]
     fn test_initialize_generates_salt_if_not_exists() {
         let (manager, _temp_dir) = create_test_manager();
@@ -180,4 +443,91 @@ mod tests {
         assert!(metadata.is_file());
         assert!(metadata.len() == 32);
     }
+
+    #[test]
+    fn test_replace_salt_overwrites_existing_salt() {
+        let (manager, _temp_dir) = create_test_manager();
+        let original = manager.initialize().unwrap();
+
+        let replacement = KeyManager::generate_salt();
+        manager.replace_salt(&replacement).unwrap();
+
+        assert_ne!(original, replacement);
+        assert_eq!(manager.initialize().unwrap(), replacement);
+        assert!(!manager.salt_path().with_extension("tmp").exists());
+    }
+
+    #[test]
+    fn test_new_salt_is_kept_in_keyring() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = keyring_manager(&temp_dir, Arc::new(MemoryKeyring::default()));
+
+        let salt = manager.initialize().unwrap();
+
+        assert!(manager.uses_keyring());
+        assert!(!manager.salt_path().exists());
+        assert_eq!(manager.initialize().unwrap(), salt);
+    }
+
+    #[test]
+    fn test_salt_file_is_migrated_to_keyring() {
+        let temp_dir = TempDir::new().unwrap();
+        let salt = KeyManager::file_only(temp_dir.path().to_path_buf())
+            .initialize()
+            .unwrap();
+        let keyring = Arc::new(MemoryKeyring::default());
+        let manager = keyring_manager(&temp_dir, keyring.clone());
+
+        assert_eq!(manager.initialize().unwrap(), salt);
+
+        assert!(!manager.salt_path().exists());
+        assert_eq!(keyring.entries.lock().unwrap().len(), 1);
+        assert_eq!(manager.initialize().unwrap(), salt);
+    }
+
+    #[test]
+    fn test_falls_back_to_file_without_keyring() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = keyring_manager(&temp_dir, Arc::new(MemoryKeyring::unavailable()));
+
+        let salt = manager.initialize().unwrap();
+
+        assert!(!manager.uses_keyring());
+        assert!(manager.salt_path().exists());
+        assert_eq!(manager.initialize().unwrap(), salt);
+    }
+
+    #[test]
+    fn test_unreadable_keyring_fails_instead_of_new_salt() {
+        let temp_dir = TempDir::new().unwrap();
+        keyring_manager(&temp_dir, Arc::new(MemoryKeyring::default()))
+            .initialize()
+            .unwrap();
+
+        // Locked keyring
+        let locked = keyring_manager(&temp_dir, Arc::new(MemoryKeyring::unavailable()));
+        let err = locked.initialize().unwrap_err();
+        assert!(matches!(err, KeyManagerError::Keyring(_)));
+        assert!(err.to_string().contains("Unlock the keyring"));
+
+        // Data directory copied to a machine without the entry
+        let copied = keyring_manager(&temp_dir, Arc::new(MemoryKeyring::default()));
+        let err = copied.initialize().unwrap_err();
+        assert!(err.to_string().contains("missing from the OS keyring"));
+
+        assert!(!locked.salt_path().exists());
+    }
+
+    #[test]
+    fn test_replace_salt_updates_keyring_entry() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = keyring_manager(&temp_dir, Arc::new(MemoryKeyring::default()));
+        manager.initialize().unwrap();
+
+        let replacement = KeyManager::generate_salt();
+        manager.replace_salt(&replacement).unwrap();
+
+        assert_eq!(manager.initialize().unwrap(), replacement);
+        assert!(!manager.salt_path().exists());
+    }
 }
@@ -1,5 +1,7 @@
 pub mod encryption;
 pub mod key_manager;
+pub mod passphrase;
 
 pub use encryption::{EncryptionError, EncryptionService};
-pub use key_manager::{KeyManager, KeyManagerError};
+pub use key_manager::{KeyManager, KeyManagerError, KeyringStore, OsKeyring};
+pub use passphrase::{hash_passphrase, verify_passphrase};
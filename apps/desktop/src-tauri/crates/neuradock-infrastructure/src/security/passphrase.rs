@@ -0,0 +1,59 @@
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use rand::RngCore;
+
+use super::EncryptionError;
+
+/// Hash a passphrase with Argon2id into a PHC string for storing in settings
+///
+/// The string carries its own random salt and parameters, so it is all that
+/// [`verify_passphrase`] needs later.
+pub fn hash_passphrase(passphrase: &str) -> Result<String, EncryptionError> {
+    let mut salt = [0u8; 16];
+    rand::thread_rng().fill_bytes(&mut salt);
+    let salt =
+        SaltString::encode_b64(&salt).map_err(|e| EncryptionError::KeyDerivation(e.to_string()))?;
+
+    Argon2::default()
+        .hash_password(passphrase.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| EncryptionError::KeyDerivation(e.to_string()))
+}
+
+/// Whether `passphrase` matches a hash from [`hash_passphrase`]
+///
+/// A malformed stored hash is an error rather than a mismatch.
+pub fn verify_passphrase(passphrase: &str, hash: &str) -> Result<bool, EncryptionError> {
+    let hash =
+        PasswordHash::new(hash).map_err(|e| EncryptionError::KeyDerivation(e.to_string()))?;
+    Ok(Argon2::default()
+        .verify_password(passphrase.as_bytes(), &hash)
+        .is_ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_verifies_only_its_passphrase() {
+        let hash = hash_passphrase("correct horse").unwrap();
+
+        assert!(hash.starts_with("$argon2id$"));
+        assert!(verify_passphrase("correct horse", &hash).unwrap());
+        assert!(!verify_passphrase("wrong horse", &hash).unwrap());
+    }
+
+    #[test]
+    fn test_hashes_are_salted() {
+        assert_ne!(
+            hash_passphrase("same").unwrap(),
+            hash_passphrase("same").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_malformed_hash_is_an_error() {
+        assert!(verify_passphrase("anything", "not-a-hash").is_err());
+    }
+}
@@ -0,0 +1,181 @@
+use chrono::{Duration, Utc};
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Instant;
+
+use neuradock_domain::account::{Account, AccountRepository, Credentials};
+use neuradock_domain::balance_history::{BalanceHistoryRecord, BalanceHistoryRepository};
+use neuradock_domain::shared::ProviderId;
+use neuradock_infrastructure::persistence::repositories::{
+    SqliteAccountRepository, SqliteBalanceHistoryRepository,
+};
+
+mod test_helpers;
+
+const ACCOUNTS: usize = 50;
+const HISTORY_ROWS: usize = 50_000;
+
+/// How the account list used to find the last check-in, kept to compare against
+const GROUPED_HISTORY_QUERY: &str = r#"
+    SELECT a.id, bh.latest_recorded_at
+    FROM accounts a
+    LEFT JOIN (
+        SELECT account_id, MAX(recorded_at) as latest_recorded_at
+        FROM balance_history
+        GROUP BY account_id
+    ) bh ON a.id = bh.account_id
+"#;
+
+fn account(name: &str) -> Account {
+    let mut cookies = HashMap::new();
+    cookies.insert("session".to_string(), format!("{}-session", name));
+    Account::new(
+        name.to_string(),
+        ProviderId::from_string("test-provider"),
+        Credentials::new(cookies, format!("{}-api-user", name)),
+    )
+    .expect("Create account aggregate")
+}
+
+fn history(account: &Account, balance: f64, age: Duration) -> BalanceHistoryRecord {
+    BalanceHistoryRecord::restore(
+        uuid::Uuid::new_v4().to_string(),
+        account.id().clone(),
+        balance,
+        0.0,
+        balance,
+        Utc::now() - age,
+    )
+}
+
+/// Best of a few runs, so a busy machine does not decide the comparison
+async fn fastest<F, Fut>(mut run: F) -> std::time::Duration
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = ()>,
+{
+    let mut best = std::time::Duration::MAX;
+    for _ in 0..5 {
+        let started_at = Instant::now();
+        run().await;
+        best = best.min(started_at.elapsed());
+    }
+    best
+}
+
+async fn seed_history(pool: &SqlitePool) {
+    sqlx::query(
+        r#"
+        WITH RECURSIVE
+            n(i) AS (SELECT 0 UNION ALL SELECT i + 1 FROM n WHERE i < ?1),
+            numbered AS (SELECT id, ROW_NUMBER() OVER (ORDER BY id) - 1 AS k FROM accounts)
+        INSERT INTO balance_history (id, account_id, current_balance, total_consumed, total_quota, recorded_at)
+        SELECT
+            'seed-' || n.i,
+            numbered.id,
+            1.0, 0.0, 1.0,
+            datetime('2024-01-01', '+' || n.i || ' minutes')
+        FROM n
+        JOIN numbered ON numbered.k = n.i % ?2
+        "#,
+    )
+    .bind((HISTORY_ROWS - 1) as i64)
+    .bind(ACCOUNTS as i64)
+    .execute(pool)
+    .await
+    .expect("seed balance history");
+}
+
+#[tokio::test]
+async fn history_writes_keep_last_check_in_current() {
+    let (pool, encryption) = test_helpers::setup_in_memory_db().await;
+    let pool = Arc::new(pool);
+    let account_repo = SqliteAccountRepository::new(pool.clone(), encryption);
+    let history_repo = SqliteBalanceHistoryRepository::new(pool.clone());
+
+    let account = account("tracked");
+    account_repo.save(&account).await.expect("save account");
+    let loaded = account_repo
+        .find_by_id(account.id())
+        .await
+        .unwrap()
+        .expect("account exists");
+    assert!(loaded.last_check_in().is_none());
+
+    let latest = history(&account, 10.0, Duration::hours(1));
+    history_repo.save(&latest).await.expect("save history");
+    // An older record written later does not move the time back
+    history_repo
+        .save(&history(&account, 5.0, Duration::days(3)))
+        .await
+        .expect("save older history");
+
+    // Saving the account loaded before the history was written keeps the newer time
+    account_repo
+        .save(&loaded)
+        .await
+        .expect("save stale account");
+
+    let reloaded = account_repo
+        .find_by_id(account.id())
+        .await
+        .unwrap()
+        .expect("account exists");
+    assert_eq!(
+        reloaded.last_check_in().map(|at| at.timestamp_millis()),
+        Some(latest.recorded_at().timestamp_millis())
+    );
+}
+
+#[tokio::test]
+async fn account_list_does_not_scan_balance_history() {
+    let (pool, encryption) = test_helpers::setup_in_memory_db().await;
+    let pool = Arc::new(pool);
+    let account_repo = SqliteAccountRepository::new(pool.clone(), encryption);
+    let history_repo = SqliteBalanceHistoryRepository::new(pool.clone());
+
+    let mut accounts = Vec::with_capacity(ACCOUNTS);
+    for index in 0..ACCOUNTS {
+        let account = account(&format!("account-{}", index));
+        account_repo.save(&account).await.expect("save account");
+        accounts.push(account);
+    }
+    seed_history(&pool).await;
+    // The newest record of each account goes through the repository
+    for account in &accounts {
+        history_repo
+            .save(&history(account, 2.0, Duration::zero()))
+            .await
+            .expect("save history");
+    }
+
+    let listed = account_repo.find_all().await.expect("find all");
+    assert_eq!(listed.len(), ACCOUNTS);
+    assert!(listed
+        .iter()
+        .all(|account| account.last_check_in().unwrap() > Utc::now() - Duration::minutes(1)));
+
+    let grouped = fastest(|| async {
+        sqlx::query(GROUPED_HISTORY_QUERY)
+            .fetch_all(&*pool)
+            .await
+            .expect("grouped history query");
+    })
+    .await;
+    let denormalized = fastest(|| async {
+        account_repo.find_all().await.expect("find all");
+    })
+    .await;
+
+    println!(
+        "account list over {} history rows: grouped subquery {:?}, denormalized {:?}",
+        HISTORY_ROWS, grouped, denormalized
+    );
+    assert!(
+        denormalized < grouped,
+        "listing accounts ({:?}) should not cost as much as aggregating the history ({:?})",
+        denormalized,
+        grouped
+    );
+}
@@ -1,8 +1,8 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 
 use neuradock_domain::account::{Account, AccountRepository, Credentials};
-use neuradock_domain::shared::ProviderId;
+use neuradock_domain::shared::{ExtraHeaders, ProviderId};
 use neuradock_infrastructure::persistence::repositories::SqliteAccountRepository;
 
 mod test_helpers;
@@ -74,6 +74,47 @@ async fn account_repo_update_account() {
     assert_eq!(found.name(), "Updated Name");
 }
 
+#[tokio::test]
+async fn account_repo_extra_headers_are_encrypted() {
+    let (pool, encryption) = test_helpers::setup_in_memory_db().await;
+    let repo = SqliteAccountRepository::new(Arc::new(pool.clone()), encryption);
+
+    let mut cookies = HashMap::new();
+    cookies.insert("session".to_string(), "abc123".to_string());
+    let mut account = Account::new(
+        "Header Account".to_string(),
+        ProviderId::from_string("test-provider"),
+        Credentials::new(cookies, "api_user_1".to_string()),
+    )
+    .expect("Create account");
+    account.update_extra_headers(
+        ExtraHeaders::new(BTreeMap::from([(
+            "Authorization".to_string(),
+            "Bearer secret-token".to_string(),
+        )]))
+        .expect("Valid headers"),
+    );
+    repo.save(&account).await.expect("Save account");
+
+    let stored: Option<String> =
+        sqlx::query_scalar("SELECT extra_headers FROM accounts WHERE id = ?1")
+            .bind(account.id().as_str())
+            .fetch_one(&pool)
+            .await
+            .expect("Read stored headers");
+    assert!(!stored.expect("Headers stored").contains("secret-token"));
+
+    let found = repo
+        .find_by_id(account.id())
+        .await
+        .expect("Find account")
+        .expect("Account should exist");
+    assert_eq!(
+        found.extra_headers().get("Authorization"),
+        Some("Bearer secret-token")
+    );
+}
+
 #[tokio::test]
 async fn account_repo_delete_account() {
     let (pool, encryption) = test_helpers::setup_in_memory_db().await;
@@ -286,3 +327,144 @@ async fn account_repo_empty_batch_find() {
     let found = repo.find_by_ids(&[]).await.expect("Find by empty IDs");
     assert_eq!(found.len(), 0);
 }
+
+#[tokio::test]
+async fn account_repo_check_in_attempts_reset_daily() {
+    let (pool, encryption) = test_helpers::setup_in_memory_db().await;
+    let repo = SqliteAccountRepository::new(Arc::new(pool.clone()), encryption);
+
+    let mut cookies = HashMap::new();
+    cookies.insert("session".to_string(), "abc123".to_string());
+    let mut account = Account::new(
+        "Attempts Account".to_string(),
+        ProviderId::from_string("test-provider"),
+        Credentials::new(cookies, "api_user_1".to_string()),
+    )
+    .expect("Create account aggregate");
+    account.set_max_attempts_per_day(3);
+    repo.save(&account).await.expect("Save account");
+
+    let today = chrono::NaiveDate::from_ymd_opt(2025, 12, 26).unwrap();
+    let tomorrow = today.succ_opt().unwrap();
+
+    assert_eq!(
+        repo.record_check_in_attempt(account.id(), today)
+            .await
+            .unwrap(),
+        1
+    );
+    assert_eq!(
+        repo.record_check_in_attempt(account.id(), today)
+            .await
+            .unwrap(),
+        2
+    );
+
+    // Saving the stale aggregate must not roll the counter back
+    repo.save(&account).await.expect("Save stale account");
+
+    let found = repo
+        .find_by_id(account.id())
+        .await
+        .expect("Find account")
+        .expect("Account should be found");
+    assert_eq!(found.max_attempts_per_day(), 3);
+    assert_eq!(found.check_in_attempts_on(today), 2);
+    assert_eq!(found.check_in_attempts_on(tomorrow), 0);
+
+    // A new day starts the counter over
+    assert_eq!(
+        repo.record_check_in_attempt(account.id(), tomorrow)
+            .await
+            .unwrap(),
+        1
+    );
+    let found = repo
+        .find_by_id(account.id())
+        .await
+        .expect("Find account")
+        .expect("Account should be found");
+    assert_eq!(found.check_in_attempts_on(tomorrow), 1);
+    assert_eq!(found.check_in_attempts_on(today), 0);
+}
+
+#[tokio::test]
+async fn account_repo_soft_delete_restore_and_purge() {
+    use chrono::{Duration, Utc};
+    use neuradock_domain::balance_history::{BalanceHistoryRecord, BalanceHistoryRepository};
+    use neuradock_infrastructure::persistence::repositories::SqliteBalanceHistoryRepository;
+
+    let (pool, encryption) = test_helpers::setup_in_memory_db().await;
+    let pool = Arc::new(pool);
+    let repo = SqliteAccountRepository::new(pool.clone(), encryption);
+    let history_repo = SqliteBalanceHistoryRepository::new(pool.clone());
+
+    let mut accounts = Vec::new();
+    for name in ["Expired", "Recent"] {
+        let mut cookies = HashMap::new();
+        cookies.insert("session".to_string(), format!("{}-session", name));
+        let account = Account::new(
+            name.to_string(),
+            ProviderId::from_string("test-provider"),
+            Credentials::new(cookies, format!("{}-user", name)),
+        )
+        .expect("Create account");
+        repo.save(&account).await.expect("Save account");
+        history_repo
+            .save(&BalanceHistoryRecord::restore(
+                format!("{}-history", name),
+                account.id().clone(),
+                1.0,
+                0.0,
+                1.0,
+                Utc::now(),
+            ))
+            .await
+            .expect("Save history");
+        accounts.push(account);
+    }
+    let (expired, recent) = (&accounts[0], &accounts[1]);
+    let now = Utc::now();
+
+    // Soft-deleted accounts are hidden from every query
+    repo.soft_delete(expired.id(), now)
+        .await
+        .expect("Soft delete");
+    assert!(repo.find_by_id(expired.id()).await.unwrap().is_none());
+    assert_eq!(
+        repo.find_by_ids(&[expired.id().clone(), recent.id().clone()])
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+    assert_eq!(repo.find_all().await.unwrap().len(), 1);
+    assert_eq!(repo.find_enabled().await.unwrap().len(), 1);
+
+    // Undo brings the account back once
+    assert!(repo.restore_deleted(expired.id()).await.unwrap());
+    assert!(!repo.restore_deleted(expired.id()).await.unwrap());
+    assert!(repo.find_by_id(expired.id()).await.unwrap().is_some());
+
+    // Only accounts past the grace period are purged, with their history
+    repo.soft_delete(expired.id(), now - Duration::days(8))
+        .await
+        .unwrap();
+    repo.soft_delete(recent.id(), now).await.unwrap();
+    let purged = repo
+        .purge_deleted(now - Duration::days(7))
+        .await
+        .expect("Purge");
+    assert_eq!(purged, vec![(expired.id().clone(), "Expired".to_string())]);
+    assert!(history_repo
+        .find_latest_by_account_id(expired.id())
+        .await
+        .unwrap()
+        .is_none());
+    assert!(history_repo
+        .find_latest_by_account_id(recent.id())
+        .await
+        .unwrap()
+        .is_some());
+    assert!(repo.restore_deleted(recent.id()).await.unwrap());
+}
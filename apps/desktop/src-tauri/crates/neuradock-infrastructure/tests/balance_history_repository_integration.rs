@@ -1,4 +1,4 @@
-use chrono::{Duration, Utc};
+use chrono::{Duration, TimeZone, Utc};
 use std::sync::Arc;
 
 use neuradock_domain::balance_history::{BalanceHistoryRecord, BalanceHistoryRepository};
@@ -78,3 +78,234 @@ async fn balance_history_repo_save_and_find_latest_integration() {
     assert_eq!(latest.id(), "newer");
     assert_eq!(latest.current_balance(), 25.0);
 }
+
+#[tokio::test]
+async fn balance_history_repo_keeps_one_snapshot_per_day_integration() {
+    let (pool, _encryption) = test_helpers::setup_in_memory_db().await;
+
+    let repo = SqliteBalanceHistoryRepository::new(Arc::new(pool.clone()));
+
+    let account_id = AccountId::new();
+    sqlx::query("INSERT OR IGNORE INTO accounts (id, name, provider_id, cookies, api_user, enabled, created_at) VALUES (?1, ?2, ?3, ?4, ?5, 1, datetime('now'))")
+        .bind(account_id.as_str())
+        .bind("Test Account")
+        .bind("test-provider")
+        .bind("{}")
+        .bind("api_user")
+        .execute(&pool)
+        .await
+        .expect("insert account");
+
+    let morning = Utc.with_ymd_and_hms(2026, 3, 10, 8, 0, 0).unwrap();
+    let evening = Utc.with_ymd_and_hms(2026, 3, 10, 20, 0, 0).unwrap();
+    let next_day = Utc.with_ymd_and_hms(2026, 3, 11, 8, 0, 0).unwrap();
+
+    let first = repo
+        .upsert_daily_snapshot(&account_id, 10.0, 1.0, 11.0, morning)
+        .await
+        .expect("save morning snapshot");
+    let second = repo
+        .upsert_daily_snapshot(&account_id, 8.0, 3.0, 11.0, evening)
+        .await
+        .expect("save evening snapshot");
+    assert_eq!(first.id(), second.id());
+
+    let latest = repo
+        .find_latest_by_account_id(&account_id)
+        .await
+        .expect("find latest")
+        .expect("latest should exist");
+    assert_eq!(latest.current_balance(), 8.0);
+    assert_eq!(latest.recorded_at(), evening);
+
+    let third = repo
+        .upsert_daily_snapshot(&account_id, 7.0, 4.0, 11.0, next_day)
+        .await
+        .expect("save next day snapshot");
+    assert_ne!(third.id(), second.id());
+
+    let rows: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM balance_history WHERE account_id = ?1")
+            .bind(account_id.as_str())
+            .fetch_one(&pool)
+            .await
+            .expect("count snapshots");
+    assert_eq!(rows, 2);
+
+    let summaries = repo
+        .list_all_daily_summaries(&account_id)
+        .await
+        .expect("list daily summaries");
+    assert_eq!(summaries.len(), 2);
+    assert_eq!(summaries[0].daily_balance(), 8.0);
+    assert_eq!(summaries[1].daily_balance(), 7.0);
+
+    let err = repo
+        .upsert_daily_snapshot(&account_id, -1.0, 0.0, 0.0, next_day)
+        .await
+        .expect_err("negative balance is rejected");
+    assert!(err.to_string().contains("negative"));
+}
+
+#[tokio::test]
+async fn balance_history_repo_streak_reset_round_trip_integration() {
+    let (pool, _encryption) = test_helpers::setup_in_memory_db().await;
+
+    let repo = SqliteBalanceHistoryRepository::new(Arc::new(pool.clone()));
+
+    let account_id = AccountId::new();
+    sqlx::query("INSERT OR IGNORE INTO accounts (id, name, provider_id, cookies, api_user, enabled, created_at) VALUES (?1, ?2, ?3, ?4, ?5, 1, datetime('now'))")
+        .bind(account_id.as_str())
+        .bind("Test Account")
+        .bind("test-provider")
+        .bind("{}")
+        .bind("api_user")
+        .execute(&pool)
+        .await
+        .expect("insert account");
+
+    assert_eq!(repo.find_streak_reset(&account_id).await.unwrap(), None);
+
+    let reset_on = chrono::NaiveDate::from_ymd_opt(2026, 3, 10).unwrap();
+    repo.set_streak_reset(&account_id, Some(reset_on))
+        .await
+        .expect("reset streak");
+    assert_eq!(
+        repo.find_streak_reset(&account_id).await.unwrap(),
+        Some(reset_on)
+    );
+
+    repo.clear_streak_resets().await.expect("clear resets");
+    assert_eq!(repo.find_streak_reset(&account_id).await.unwrap(), None);
+
+    let missing = AccountId::new();
+    assert!(repo
+        .set_streak_reset(&missing, Some(reset_on))
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn balance_history_repo_lists_summaries_of_several_accounts_integration() {
+    let (pool, _encryption) = test_helpers::setup_in_memory_db().await;
+
+    let repo = SqliteBalanceHistoryRepository::new(Arc::new(pool.clone()));
+
+    let first = AccountId::new();
+    let second = AccountId::new();
+    let other = AccountId::new();
+    for account_id in [&first, &second, &other] {
+        sqlx::query("INSERT OR IGNORE INTO accounts (id, name, provider_id, cookies, api_user, enabled, created_at) VALUES (?1, ?2, ?3, ?4, ?5, 1, datetime('now'))")
+            .bind(account_id.as_str())
+            .bind("Test Account")
+            .bind("test-provider")
+            .bind("{}")
+            .bind("api_user")
+            .execute(&pool)
+            .await
+            .expect("insert account");
+    }
+
+    let day = |d: u32| Utc.with_ymd_and_hms(2026, 3, d, 8, 0, 0).unwrap();
+    for (account_id, days) in [
+        (&first, vec![1, 5, 10]),
+        (&second, vec![4, 5]),
+        (&other, vec![5]),
+    ] {
+        for d in days {
+            repo.upsert_daily_snapshot(account_id, 10.0, 0.0, d as f64, day(d))
+                .await
+                .expect("save snapshot");
+        }
+    }
+
+    let summaries = repo
+        .list_daily_summaries_for_accounts_in_range(
+            &[first.clone(), second.clone()],
+            day(2).date_naive(),
+            day(9).date_naive(),
+        )
+        .await
+        .expect("list summaries of accounts");
+
+    assert_eq!(summaries.len(), 2);
+    let dates = |account_id: &AccountId| -> Vec<u32> {
+        summaries[account_id]
+            .iter()
+            .map(|summary| chrono::Datelike::day(&summary.check_in_date()))
+            .collect()
+    };
+    assert_eq!(dates(&first), vec![5]);
+    assert_eq!(dates(&second), vec![4, 5]);
+    assert_eq!(summaries[&second][0].daily_total_quota(), 4.0);
+}
+
+#[tokio::test]
+async fn balance_history_repo_sums_daily_totals_by_provider_integration() {
+    let (pool, _encryption) = test_helpers::setup_in_memory_db().await;
+
+    let repo = SqliteBalanceHistoryRepository::new(Arc::new(pool.clone()));
+
+    let first = AccountId::new();
+    let second = AccountId::new();
+    let disabled = AccountId::new();
+    let other = AccountId::new();
+    for (account_id, provider_id, enabled) in [
+        (&first, "provider-a", true),
+        (&second, "provider-a", true),
+        (&disabled, "provider-a", false),
+        (&other, "provider-b", true),
+    ] {
+        sqlx::query("INSERT OR IGNORE INTO accounts (id, name, provider_id, cookies, api_user, enabled, created_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, datetime('now'))")
+            .bind(account_id.as_str())
+            .bind("Test Account")
+            .bind(provider_id)
+            .bind("{}")
+            .bind("api_user")
+            .bind(enabled)
+            .execute(&pool)
+            .await
+            .expect("insert account");
+    }
+
+    let day = |d: u32| Utc.with_ymd_and_hms(2026, 3, d, 8, 0, 0).unwrap();
+    for (account_id, d, balance, consumed) in [
+        (&first, 1, 10.0, 1.0),
+        (&first, 2, 12.0, 1.5),
+        (&second, 2, 5.0, 2.0),
+        (&disabled, 2, 100.0, 0.0),
+        (&other, 2, 7.0, 3.0),
+        (&first, 5, 20.0, 2.0),
+    ] {
+        repo.upsert_daily_snapshot(account_id, balance, consumed, balance + consumed, day(d))
+            .await
+            .expect("save snapshot");
+    }
+
+    let totals = repo
+        .list_provider_daily_totals_in_range(day(1).date_naive(), day(3).date_naive())
+        .await
+        .expect("list provider daily totals");
+
+    let rows: Vec<(&str, u32, f64, f64, f64, u32)> = totals
+        .iter()
+        .map(|total| {
+            (
+                total.provider_id().as_str(),
+                chrono::Datelike::day(&total.date()),
+                total.current_balance(),
+                total.total_consumed(),
+                total.total_quota(),
+                total.account_count(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        rows,
+        vec![
+            ("provider-a", 1, 10.0, 1.0, 11.0, 1),
+            ("provider-a", 2, 17.0, 3.5, 20.5, 2),
+            ("provider-b", 2, 7.0, 3.0, 10.0, 1),
+        ]
+    );
+}
@@ -0,0 +1,112 @@
+use chrono::{Duration, Utc};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+
+use neuradock_domain::check_in_retry::{CheckInRetry, CheckInRetryRepository, CheckInRetryStatus};
+use neuradock_domain::shared::{AccountId, ErrorCode};
+use neuradock_infrastructure::persistence::repositories::SqliteCheckInRetryRepository;
+
+mod test_helpers;
+
+async fn insert_account(pool: &SqlitePool, name: &str) -> AccountId {
+    let account_id = AccountId::new();
+    sqlx::query("INSERT OR IGNORE INTO accounts (id, name, provider_id, cookies, api_user, enabled, created_at) VALUES (?1, ?2, ?3, ?4, ?5, 1, datetime('now'))")
+        .bind(account_id.as_str())
+        .bind(name)
+        .bind("test-provider")
+        .bind("{}")
+        .bind("api_user")
+        .execute(pool)
+        .await
+        .expect("insert account");
+    account_id
+}
+
+#[tokio::test]
+async fn check_in_retry_repo_save_update_and_delete_integration() {
+    let (pool, _encryption) = test_helpers::setup_in_memory_db().await;
+    let repo = SqliteCheckInRetryRepository::new(Arc::new(pool.clone()));
+
+    let account_id = insert_account(&pool, "Retry Account").await;
+    let now = Utc::now();
+    let mut retry =
+        CheckInRetry::for_failure(account_id.clone(), ErrorCode::NetworkError, "timeout", now)
+            .expect("recoverable failure");
+
+    repo.save(&retry).await.expect("save retry");
+
+    let fetched = repo
+        .find_by_account_id(&account_id)
+        .await
+        .expect("find")
+        .expect("should exist");
+    assert_eq!(fetched.attempts(), 1);
+    assert_eq!(fetched.last_error(), "timeout");
+    assert_eq!(fetched.status(), CheckInRetryStatus::Pending);
+
+    // saving again updates the single entry of the account
+    retry.record_failure(ErrorCode::InvalidCredentials, "session expired", now);
+    repo.save(&retry).await.expect("update retry");
+
+    let fetched = repo
+        .find_by_account_id(&account_id)
+        .await
+        .expect("find")
+        .expect("should exist");
+    assert_eq!(fetched.attempts(), 2);
+    assert_eq!(fetched.status(), CheckInRetryStatus::Failed);
+
+    // failed entries are never due
+    let due = repo
+        .find_due(now + Duration::days(1))
+        .await
+        .expect("find due");
+    assert!(due.is_empty());
+
+    repo.delete(&account_id).await.expect("delete");
+    assert!(repo
+        .find_by_account_id(&account_id)
+        .await
+        .expect("find after delete")
+        .is_none());
+}
+
+#[tokio::test]
+async fn check_in_retry_repo_find_due_orders_by_next_attempt_integration() {
+    let (pool, _encryption) = test_helpers::setup_in_memory_db().await;
+    let repo = SqliteCheckInRetryRepository::new(Arc::new(pool.clone()));
+
+    let now = Utc::now();
+    let later = insert_account(&pool, "Later").await;
+    let earliest = insert_account(&pool, "Earliest").await;
+    let not_due = insert_account(&pool, "Not Due").await;
+
+    // queued at different times, so their first attempts are due at different times
+    for (account_id, queued_at) in [
+        (&later, now - Duration::minutes(10)),
+        (&earliest, now - Duration::minutes(30)),
+        (&not_due, now),
+    ] {
+        let retry = CheckInRetry::for_failure(
+            account_id.clone(),
+            ErrorCode::TimeoutError,
+            "timeout",
+            queued_at,
+        )
+        .expect("recoverable failure");
+        repo.save(&retry).await.expect("save retry");
+    }
+
+    let due: Vec<String> = repo
+        .find_due(now)
+        .await
+        .expect("find due")
+        .into_iter()
+        .map(|retry| retry.account_id().as_str().to_string())
+        .collect();
+
+    assert_eq!(
+        due,
+        vec![earliest.as_str().to_string(), later.as_str().to_string()]
+    );
+}
@@ -0,0 +1,97 @@
+use chrono::{Duration, Utc};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+
+use neuradock_domain::check_in_timing::{
+    CheckInPhase, CheckInPhaseDurations, CheckInTiming, CheckInTimingRepository,
+};
+use neuradock_domain::shared::{AccountId, ProviderId};
+use neuradock_infrastructure::persistence::repositories::SqliteCheckInTimingRepository;
+
+mod test_helpers;
+
+async fn insert_account(pool: &SqlitePool, name: &str) -> AccountId {
+    let account_id = AccountId::new();
+    sqlx::query("INSERT OR IGNORE INTO accounts (id, name, provider_id, cookies, api_user, enabled, created_at) VALUES (?1, ?2, ?3, ?4, ?5, 1, datetime('now'))")
+        .bind(account_id.as_str())
+        .bind(name)
+        .bind("test-provider")
+        .bind("{}")
+        .bind("api_user")
+        .execute(pool)
+        .await
+        .expect("insert account");
+    account_id
+}
+
+fn timing(account_id: &AccountId, minutes_ago: i64, waf_refresh_ms: u64) -> CheckInTiming {
+    let mut phases = CheckInPhaseDurations::default();
+    phases.set_ms(CheckInPhase::CookiePreparation, 40);
+    phases.set_ms(CheckInPhase::WafRefresh, waf_refresh_ms);
+    phases.set_ms(CheckInPhase::UserInfo, 300);
+    phases.set_ms(CheckInPhase::CheckInRequest, 450);
+    phases.set_ms(CheckInPhase::BalanceFetch, 2100);
+
+    CheckInTiming::restore(
+        account_id.clone(),
+        ProviderId::from_string("test-provider"),
+        Utc::now() - Duration::minutes(minutes_ago),
+        waf_refresh_ms == 0,
+        phases,
+        2900 + waf_refresh_ms,
+    )
+}
+
+#[tokio::test]
+async fn check_in_timing_repo_returns_recent_runs_of_account_integration() {
+    let (pool, _encryption) = test_helpers::setup_in_memory_db().await;
+    let repo = SqliteCheckInTimingRepository::new(Arc::new(pool.clone()));
+
+    let account_id = insert_account(&pool, "Timed").await;
+    let other = insert_account(&pool, "Other").await;
+
+    repo.save(&timing(&account_id, 30, 0)).await.expect("save");
+    repo.save(&timing(&account_id, 10, 6000))
+        .await
+        .expect("save");
+    repo.save(&timing(&account_id, 20, 0)).await.expect("save");
+    repo.save(&timing(&other, 5, 0)).await.expect("save");
+
+    let recent = repo
+        .find_recent_by_account(&account_id, 2)
+        .await
+        .expect("find recent");
+
+    assert_eq!(recent.len(), 2);
+    assert_eq!(recent[0].phase_ms(CheckInPhase::WafRefresh), 6000);
+    assert_eq!(recent[0].phase_ms(CheckInPhase::BalanceFetch), 2100);
+    assert_eq!(recent[0].total_ms(), 8900);
+    assert!(!recent[0].success());
+    assert!(recent[0].started_at() > recent[1].started_at());
+    assert_eq!(recent[1].phase_ms(CheckInPhase::WafRefresh), 0);
+    assert!(recent.iter().all(|t| t.account_id() == &account_id));
+}
+
+#[tokio::test]
+async fn check_in_timing_repo_keeps_latest_runs_per_account_integration() {
+    let (pool, _encryption) = test_helpers::setup_in_memory_db().await;
+    let repo = SqliteCheckInTimingRepository::new(Arc::new(pool.clone()));
+
+    let account_id = insert_account(&pool, "Busy").await;
+    let runs = CheckInTiming::MAX_PER_ACCOUNT as i64 + 5;
+    for minutes_ago in (0..runs).rev() {
+        repo.save(&timing(&account_id, minutes_ago, 0))
+            .await
+            .expect("save");
+    }
+
+    let kept = repo
+        .find_recent_by_account(&account_id, u32::MAX)
+        .await
+        .expect("find recent");
+
+    assert_eq!(kept.len(), CheckInTiming::MAX_PER_ACCOUNT as usize);
+    // The oldest runs were dropped
+    let oldest_kept = kept.last().unwrap().started_at();
+    assert!(oldest_kept > Utc::now() - Duration::minutes(CheckInTiming::MAX_PER_ACCOUNT as i64));
+}
@@ -0,0 +1,88 @@
+use std::sync::Arc;
+
+use neuradock_domain::notification::{
+    ChannelConfig, Locale, NotificationChannel, NotificationChannelRepository,
+    NotificationTemplates, TemplateKind,
+};
+use neuradock_domain::shared::ErrorSeverity;
+use neuradock_infrastructure::notification::SqliteNotificationChannelRepository;
+
+mod test_helpers;
+
+fn channel(webhook_key: &str) -> NotificationChannel {
+    NotificationChannel::new(ChannelConfig::Feishu {
+        webhook_key: webhook_key.to_string(),
+    })
+    .expect("create channel")
+}
+
+#[tokio::test]
+async fn disabled_channel_is_excluded_from_dispatch_integration() {
+    let (pool, _encryption) = test_helpers::setup_in_memory_db().await;
+    let repo = SqliteNotificationChannelRepository::new(Arc::new(pool));
+
+    let active = channel("active_key");
+    let mut muted = channel("muted_key");
+    muted.disable();
+    muted.set_min_severity(ErrorSeverity::Error);
+    repo.save(&active).await.expect("save active channel");
+    repo.save(&muted).await.expect("save muted channel");
+
+    let enabled = repo.find_all_enabled().await.expect("find enabled");
+    assert_eq!(enabled.len(), 1);
+    assert_eq!(enabled[0].id(), active.id());
+
+    // The muted channel is kept with its settings
+    let stored = repo
+        .find_by_id(muted.id())
+        .await
+        .expect("find muted")
+        .expect("muted channel exists");
+    assert!(!stored.is_enabled());
+    assert_eq!(stored.min_severity(), ErrorSeverity::Error);
+
+    muted.enable();
+    repo.update(&muted).await.expect("re-enable channel");
+    assert_eq!(
+        repo.find_all_enabled().await.expect("find enabled").len(),
+        2
+    );
+}
+
+#[tokio::test]
+async fn channel_templates_and_locale_round_trip_integration() {
+    let (pool, _encryption) = test_helpers::setup_in_memory_db().await;
+    let repo = SqliteNotificationChannelRepository::new(Arc::new(pool));
+
+    let mut templated = channel("templated_key");
+    repo.save(&templated).await.expect("save channel");
+    let stored = repo
+        .find_by_id(templated.id())
+        .await
+        .expect("find channel")
+        .expect("channel exists");
+    assert!(stored.templates().is_empty());
+    assert_eq!(stored.locale(), None);
+
+    templated.set_templates(
+        NotificationTemplates::parse(Some("✅ {account}: {balance}".to_string()), None)
+            .expect("valid templates"),
+    );
+    templated.set_locale(Some(Locale::EnUs));
+    repo.update(&templated).await.expect("update templates");
+
+    let stored = repo
+        .find_by_id(templated.id())
+        .await
+        .expect("find channel")
+        .expect("channel exists");
+    assert_eq!(stored.templates(), templated.templates());
+    assert_eq!(stored.locale(), Some(Locale::EnUs));
+    assert_eq!(
+        stored
+            .templates()
+            .get(TemplateKind::CheckInSuccess)
+            .map(|t| t.as_str()),
+        Some("✅ {account}: {balance}")
+    );
+}
@@ -0,0 +1,89 @@
+use chrono::Utc;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use neuradock_domain::account::{Account, AccountRepository, Credentials};
+use neuradock_domain::balance_history::{BalanceHistoryRecord, BalanceHistoryRepository};
+use neuradock_domain::shared::ProviderId;
+use neuradock_infrastructure::persistence::repositories::{
+    SqliteAccountRepository, SqliteBalanceHistoryRepository,
+};
+use neuradock_infrastructure::persistence::Database;
+use neuradock_infrastructure::security::EncryptionService;
+
+const TASKS: usize = 8;
+const WRITES_PER_TASK: usize = 25;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn concurrent_account_and_balance_history_writes_all_succeed() {
+    let dir = tempfile::tempdir().expect("create temp dir");
+    let db_path = dir.path().join("neuradock.db");
+    let database = Database::new(db_path.to_str().unwrap())
+        .await
+        .expect("open database");
+    database.run_migrations().await.expect("run migrations");
+
+    let pool = Arc::new(database.pool().clone());
+    let encryption = Arc::new(
+        EncryptionService::from_password("test_password", &[42u8; 32])
+            .expect("Create encryption service"),
+    );
+    let account_repo = Arc::new(SqliteAccountRepository::new(pool.clone(), encryption));
+    let history_repo = Arc::new(SqliteBalanceHistoryRepository::new(pool.clone()));
+
+    let mut handles = Vec::new();
+    for task in 0..TASKS {
+        let account_repo = account_repo.clone();
+        let history_repo = history_repo.clone();
+        handles.push(tokio::spawn(async move {
+            let mut cookies = HashMap::new();
+            cookies.insert("session".to_string(), format!("session-{}", task));
+            let mut account = Account::new(
+                format!("Account {}", task),
+                ProviderId::from_string("test-provider"),
+                Credentials::new(cookies, format!("api_user_{}", task)),
+            )
+            .expect("Create account aggregate");
+
+            for write in 0..WRITES_PER_TASK {
+                account.update_balance(write as f64, 1.0, write as f64 + 1.0);
+                account_repo.save(&account).await.expect("save account");
+
+                let record = BalanceHistoryRecord::new(
+                    format!("history-{}-{}", task, write),
+                    account.id().clone(),
+                    write as f64,
+                    1.0,
+                    write as f64 + 1.0,
+                    Utc::now(),
+                )
+                .expect("create history record");
+                history_repo.save(&record).await.expect("save history");
+            }
+        }));
+    }
+
+    for handle in handles {
+        handle.await.expect("writer task");
+    }
+
+    let accounts: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM accounts")
+        .fetch_one(pool.as_ref())
+        .await
+        .unwrap();
+    let history: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM balance_history")
+        .fetch_one(pool.as_ref())
+        .await
+        .unwrap();
+    let balances: Vec<f64> = sqlx::query_scalar("SELECT current FROM balances")
+        .fetch_all(pool.as_ref())
+        .await
+        .unwrap();
+
+    assert_eq!(accounts, TASKS as i64);
+    assert_eq!(history, (TASKS * WRITES_PER_TASK) as i64);
+    assert_eq!(balances.len(), TASKS);
+    assert!(balances
+        .iter()
+        .all(|current| *current == (WRITES_PER_TASK - 1) as f64));
+}
@@ -0,0 +1,155 @@
+use chrono::{Duration, Utc};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use neuradock_domain::account::{Account, AccountRepository, Credentials};
+use neuradock_domain::session::{Session, SessionRepository};
+use neuradock_domain::shared::{AccountId, ProviderId, TransactionContext, UnitOfWork};
+use neuradock_infrastructure::persistence::repositories::{
+    SqliteAccountRepository, SqliteSessionRepository,
+};
+use neuradock_infrastructure::persistence::{Database, SqliteUnitOfWork};
+use neuradock_infrastructure::security::EncryptionService;
+use sqlx::SqlitePool;
+
+struct Fixture {
+    _dir: tempfile::TempDir,
+    pool: Arc<SqlitePool>,
+    account_repo: SqliteAccountRepository,
+    session_repo: SqliteSessionRepository,
+    unit_of_work: SqliteUnitOfWork,
+}
+
+async fn setup() -> Fixture {
+    let dir = tempfile::tempdir().expect("create temp dir");
+    let db_path = dir.path().join("neuradock.db");
+    let database = Database::new(db_path.to_str().unwrap())
+        .await
+        .expect("open database");
+    database.run_migrations().await.expect("run migrations");
+
+    let pool = Arc::new(database.pool().clone());
+    let encryption = Arc::new(
+        EncryptionService::from_password("test_password", &[42u8; 32])
+            .expect("Create encryption service"),
+    );
+
+    Fixture {
+        _dir: dir,
+        account_repo: SqliteAccountRepository::new(pool.clone(), encryption),
+        session_repo: SqliteSessionRepository::new(pool.clone()),
+        unit_of_work: SqliteUnitOfWork::new(pool.clone()),
+        pool,
+    }
+}
+
+fn account(name: &str) -> Account {
+    let mut cookies = HashMap::new();
+    cookies.insert("session".to_string(), format!("{}-session", name));
+    Account::new(
+        name.to_string(),
+        ProviderId::from_string("test-provider"),
+        Credentials::new(cookies, format!("{}-api-user", name)),
+    )
+    .expect("Create account aggregate")
+}
+
+fn session(account_id: &AccountId) -> Session {
+    Session::new(
+        account_id.clone(),
+        "token".to_string(),
+        Utc::now() + Duration::days(30),
+    )
+    .expect("create session")
+}
+
+async fn count(pool: &SqlitePool, table: &str) -> i64 {
+    sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {}", table))
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn committed_transaction_persists_accounts_and_sessions() {
+    let fixture = setup().await;
+    let first = account("first");
+    let second = account("second");
+
+    let mut tx = fixture.unit_of_work.begin().await.expect("begin");
+    for account in [&first, &second] {
+        fixture
+            .account_repo
+            .save_in_transaction(tx.as_mut(), account)
+            .await
+            .expect("save account");
+        fixture
+            .session_repo
+            .save_in_transaction(tx.as_mut(), &session(account.id()))
+            .await
+            .expect("save session");
+    }
+
+    // Nothing is visible outside the transaction before it commits
+    assert_eq!(count(&fixture.pool, "accounts").await, 0);
+
+    tx.commit().await.expect("commit");
+
+    assert_eq!(count(&fixture.pool, "accounts").await, 2);
+    assert_eq!(count(&fixture.pool, "sessions").await, 2);
+    let restored = fixture
+        .account_repo
+        .find_by_id(second.id())
+        .await
+        .unwrap()
+        .expect("account saved");
+    assert_eq!(restored.credentials().api_user(), "second-api-user");
+}
+
+#[tokio::test]
+async fn failure_mid_batch_rolls_back_earlier_writes() {
+    let fixture = setup().await;
+    let first = account("first");
+
+    let mut tx = fixture.unit_of_work.begin().await.expect("begin");
+    fixture
+        .account_repo
+        .save_in_transaction(tx.as_mut(), &first)
+        .await
+        .expect("save account");
+    fixture
+        .session_repo
+        .save_in_transaction(tx.as_mut(), &session(first.id()))
+        .await
+        .expect("save session");
+
+    // A session for an account that was never saved violates the foreign key
+    let orphan = AccountId::from_string("missing-account");
+    let result = fixture
+        .session_repo
+        .save_in_transaction(tx.as_mut(), &session(&orphan))
+        .await;
+    assert!(result.is_err());
+
+    tx.rollback().await.expect("rollback");
+
+    assert_eq!(count(&fixture.pool, "accounts").await, 0);
+    assert_eq!(count(&fixture.pool, "sessions").await, 0);
+    assert_eq!(count(&fixture.pool, "balances").await, 0);
+}
+
+#[tokio::test]
+async fn dropped_transaction_discards_writes() {
+    let fixture = setup().await;
+
+    {
+        let mut tx = fixture.unit_of_work.begin().await.expect("begin");
+        fixture
+            .account_repo
+            .save_in_transaction(tx.as_mut(), &account("abandoned"))
+            .await
+            .expect("save account");
+    }
+
+    assert_eq!(count(&fixture.pool, "accounts").await, 0);
+}